@@ -0,0 +1,570 @@
+//! Device memory sub-allocation
+//!
+//! `MemoryAllocator` reserves large `DeviceMemory` blocks per memory type and hands out the ranges in them,
+//! so the number of `vkAllocateMemory` calls stays far below `maxMemoryAllocationCount`.
+//! Buffers and images preferring a dedicated allocation(Vulkan 1.1/VK_KHR_dedicated_allocation) are given their own `DeviceMemory`.
+//!
+//! ```rust,ignore
+//! let allocator = MemoryAllocatorBuilder::new(AllocationStrategy::FreeList).create(&device, &adapter);
+//! let buffer = BufferDesc::new(1024, BufferUsage::VERTEX_BUFFER).create(&device)?;
+//! let a = allocator.allocate_for_buffer(&buffer, MemoryPropertyFlags::DEVICE_LOCAL)?;
+//! device.bind_buffers(&[(&buffer, a.memory(), a.offset())])?;
+//! ```
+
+#![cfg_attr(not(feature = "Implements"), allow(dead_code))]
+
+use vk::*;
+use {Device, DeviceMemory, RefCounter};
+use std::sync::Mutex;
+#[cfg(feature = "Implements")] use {PhysicalDevice, MemoryBound, MemoryPropertyFlags, Buffer, Image, DeviceChild};
+
+/// How the free space in a memory block is searched
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AllocationStrategy
+{
+	/// Allocate only after the last live range. Freed ranges are reused when everything after them has been freed
+	Linear,
+	/// Best-fit search over all free ranges. Adjacent free ranges are merged on free
+	FreeList
+}
+
+/// Kind of the resource bound to a range, separated by `bufferImageGranularity`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResourceTiling
+{
+	/// Buffers and images created with `VK_IMAGE_TILING_LINEAR`
+	Linear,
+	/// Images created with `VK_IMAGE_TILING_OPTIMAL`
+	Optimal
+}
+
+/// Usage and fragmentation of the memory blocks owned by a `MemoryAllocator`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocatorStatistics
+{
+	/// Number of `DeviceMemory` blocks
+	pub blocks: usize,
+	/// Number of live allocations
+	pub allocations: usize,
+	/// Total bytes of the blocks
+	pub reserved_bytes: VkDeviceSize,
+	/// Bytes occupied by the live allocations, including the padding for alignment
+	pub used_bytes: VkDeviceSize,
+	/// Number of separated free ranges
+	pub free_ranges: usize,
+	/// Size of the largest free range
+	pub largest_free_range: VkDeviceSize
+}
+impl AllocatorStatistics
+{
+	/// Bytes not occupied by any allocation
+	pub fn free_bytes(&self) -> VkDeviceSize { self.reserved_bytes - self.used_bytes }
+	/// `0.0` when all the free bytes are in one range, approaching `1.0` as they are split into small pieces
+	pub fn fragmentation(&self) -> f32
+	{
+		let free = self.free_bytes();
+		if free == 0 { 0.0 } else { 1.0 - self.largest_free_range as f32 / free as f32 }
+	}
+
+	fn accumulate(&mut self, other: &Self)
+	{
+		self.blocks += other.blocks;
+		self.allocations += other.allocations;
+		self.reserved_bytes += other.reserved_bytes;
+		self.used_bytes += other.used_bytes;
+		self.free_ranges += other.free_ranges;
+		self.largest_free_range = self.largest_free_range.max(other.largest_free_range);
+	}
+}
+
+/// A range in a block. `tiling` is `None` for free ranges
+#[derive(Debug, Clone, Copy)]
+struct Range { offset: VkDeviceSize, size: VkDeviceSize, tiling: Option<ResourceTiling> }
+impl Range
+{
+	fn end(&self) -> VkDeviceSize { self.offset + self.size }
+}
+/// Ranges of a block, sorted by the offset and covering the whole block
+struct BlockMetadata { ranges: Vec<Range>, strategy: AllocationStrategy }
+fn align_up(v: VkDeviceSize, alignment: VkDeviceSize) -> VkDeviceSize { (v + alignment - 1) / alignment * alignment }
+/// Whether two bytes are on the same "page" of `bufferImageGranularity`
+fn same_page(a: VkDeviceSize, b: VkDeviceSize, granularity: VkDeviceSize) -> bool { a / granularity == b / granularity }
+impl BlockMetadata
+{
+	fn new(size: VkDeviceSize, strategy: AllocationStrategy) -> Self
+	{
+		BlockMetadata { ranges: vec![Range { offset: 0, size, tiling: None }], strategy }
+	}
+	fn is_empty(&self) -> bool { self.ranges.iter().all(|r| r.tiling.is_none()) }
+
+	/// Offset to place the allocation in the free range at `index`. Empty allocations never fit
+	fn fit(&self, index: usize, size: VkDeviceSize, alignment: VkDeviceSize, tiling: ResourceTiling,
+		granularity: VkDeviceSize) -> Option<VkDeviceSize>
+	{
+		if size == 0 { return None; }
+		let r = &self.ranges[index];
+		let mut offset = align_up(r.offset, alignment);
+		if let Some(prev) = index.checked_sub(1).map(|i| &self.ranges[i])
+		{
+			if prev.tiling.map_or(false, |t| t != tiling) && same_page(prev.end() - 1, offset, granularity)
+			{
+				offset = align_up(offset, granularity);
+			}
+		}
+		let end = offset + size;
+		if end > r.end() { return None; }
+		if let Some(next) = self.ranges.get(index + 1)
+		{
+			if next.tiling.map_or(false, |t| t != tiling) && same_page(end - 1, next.offset, granularity) { return None; }
+		}
+		Some(offset)
+	}
+	fn allocate(&mut self, size: VkDeviceSize, alignment: VkDeviceSize, tiling: ResourceTiling,
+		granularity: VkDeviceSize) -> Option<VkDeviceSize>
+	{
+		let candidates = match self.strategy
+		{
+			AllocationStrategy::Linear => self.ranges.len() - 1 .. self.ranges.len(),
+			AllocationStrategy::FreeList => 0 .. self.ranges.len()
+		};
+		let (index, offset) = candidates.filter(|&i| self.ranges[i].tiling.is_none())
+			.filter_map(|i| self.fit(i, size, alignment, tiling, granularity).map(|o| (i, o)))
+			.min_by_key(|&(i, _)| self.ranges[i].size)?;
+		let r = self.ranges[index];
+		let mut split = Vec::with_capacity(3);
+		if offset > r.offset { split.push(Range { offset: r.offset, size: offset - r.offset, tiling: None }); }
+		split.push(Range { offset, size, tiling: Some(tiling) });
+		if offset + size < r.end() { split.push(Range { offset: offset + size, size: r.end() - offset - size, tiling: None }); }
+		self.ranges.splice(index ..= index, split);
+		Some(offset)
+	}
+	fn free(&mut self, offset: VkDeviceSize)
+	{
+		let mut index = self.ranges.binary_search_by_key(&offset, |r| r.offset).expect("freeing an unknown range");
+		debug_assert!(self.ranges[index].tiling.is_some(), "double free of a range");
+		self.ranges[index].tiling = None;
+		if self.ranges.get(index + 1).map_or(false, |r| r.tiling.is_none())
+		{
+			self.ranges[index].size += self.ranges.remove(index + 1).size;
+		}
+		if index > 0 && self.ranges[index - 1].tiling.is_none()
+		{
+			let r = self.ranges.remove(index);
+			index -= 1;
+			self.ranges[index].size += r.size;
+		}
+	}
+	fn statistics(&self) -> AllocatorStatistics
+	{
+		let mut s = AllocatorStatistics { blocks: 1, .. Default::default() };
+		for r in &self.ranges
+		{
+			s.reserved_bytes += r.size;
+			if r.tiling.is_some() { s.allocations += 1; s.used_bytes += r.size; }
+			else { s.free_ranges += 1; s.largest_free_range = s.largest_free_range.max(r.size); }
+		}
+		s
+	}
+}
+
+struct Block { memory: RefCounter<DeviceMemory>, meta: BlockMetadata, dedicated: bool }
+struct AllocatorCell
+{
+	device: Device, types: Vec<VkMemoryType>, heap_sizes: Vec<VkDeviceSize>,
+	granularity: VkDeviceSize, block_size: VkDeviceSize, strategy: AllocationStrategy,
+	pools: Mutex<Vec<Vec<Block>>>
+}
+/// Sub-allocator of the device memory(constructed via `MemoryAllocatorBuilder`)
+#[derive(Clone)]
+pub struct MemoryAllocator(RefCounter<AllocatorCell>);
+/// A range of a `DeviceMemory` block owned by a `MemoryAllocator`. The range is returned to the allocator on drop
+pub struct Allocation
+{
+	allocator: RefCounter<AllocatorCell>, memory: RefCounter<DeviceMemory>,
+	type_index: u32, offset: VkDeviceSize, size: VkDeviceSize
+}
+impl Allocation
+{
+	/// The memory block containing this allocation
+	pub fn memory(&self) -> &DeviceMemory { &self.memory }
+	/// Offset of this allocation in the block, aligned as requested
+	pub fn offset(&self) -> VkDeviceSize { self.offset }
+	/// Size of this allocation in bytes
+	pub fn size(&self) -> VkDeviceSize { self.size }
+	/// Index of the memory type of the block
+	pub fn type_index(&self) -> u32 { self.type_index }
+}
+impl Drop for Allocation
+{
+	fn drop(&mut self)
+	{
+		let mut pools = self.allocator.pools.lock().unwrap();
+		let pool = &mut pools[self.type_index as usize];
+		let index = pool.iter().position(|b| RefCounter::ptr_eq(&b.memory, &self.memory)).expect("the block of the allocation has gone");
+		pool[index].meta.free(self.offset);
+		if pool[index].meta.is_empty()
+		{
+			// one empty block is kept per memory type to avoid reallocating it repeatedly
+			let keep = !pool[index].dedicated && pool.iter().filter(|b| !b.dedicated && b.meta.is_empty()).count() == 1;
+			if !keep { pool.remove(index); }
+		}
+	}
+}
+
+/// Builder structure specifying the parameters of a `MemoryAllocator`
+#[derive(Debug, Clone)]
+pub struct MemoryAllocatorBuilder { strategy: AllocationStrategy, block_size: VkDeviceSize }
+impl MemoryAllocatorBuilder
+{
+	/// Default block size: 64MiB
+	pub const DEFAULT_BLOCK_SIZE: VkDeviceSize = 64 << 20;
+
+	pub fn new(strategy: AllocationStrategy) -> Self
+	{
+		MemoryAllocatorBuilder { strategy, block_size: Self::DEFAULT_BLOCK_SIZE }
+	}
+	/// Size of a `DeviceMemory` block. Clamped to 1/8 of the heap size.
+	/// Requests larger than half of the block get their own `DeviceMemory`
+	pub fn block_size(&mut self, size: VkDeviceSize) -> &mut Self { self.block_size = size; self }
+}
+/// Following methods are enabled with [feature = "Implements"]
+#[cfg(feature = "Implements")]
+impl MemoryAllocatorBuilder
+{
+	/// Create an allocator for the memory types and the limits of `adapter`, which `device` was created from
+	pub fn create(&self, device: &Device, adapter: &PhysicalDevice) -> MemoryAllocator
+	{
+		let props = adapter.memory_properties();
+		let types: Vec<_> = props.types().cloned().collect();
+		MemoryAllocator(RefCounter::new(AllocatorCell
+		{
+			device: device.clone(), heap_sizes: props.heaps().map(|h| h.size).collect(),
+			granularity: adapter.properties().limits.bufferImageGranularity.max(1),
+			block_size: self.block_size, strategy: self.strategy,
+			pools: Mutex::new(types.iter().map(|_| Vec::new()).collect()), types
+		}))
+	}
+}
+
+/// Following methods are enabled with [feature = "Implements"]
+#[cfg(feature = "Implements")]
+impl MemoryAllocator
+{
+	/// Allocate a range satisfying `requirements` from the first memory type having all of `flags`.
+	/// The next allowed type is tried if a block cannot be allocated from the heap
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `Error::NoMemoryType` if no memory type satisfies `requirements.memoryTypeBits` and `flags`
+	/// * `Error::EmptyAllocation` if `requirements.size` is zero
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_TOO_MANY_OBJECTS`
+	pub fn allocate(&self, requirements: &VkMemoryRequirements, flags: MemoryPropertyFlags, tiling: ResourceTiling) -> ::Result<Allocation>
+	{
+		if requirements.size == 0 { return Err(::Error::EmptyAllocation); }
+		self.try_types(requirements.memoryTypeBits, flags, |t| self.allocate_in(t, requirements, tiling))
+	}
+	/// Allocate a range for `buffer`.
+	/// The buffer gets its own `DeviceMemory` if it prefers or requires a dedicated allocation
+	/// # Failures
+	/// On failure, this command returns the errors of `allocate` and `MemoryBound::dedicated_requirements`
+	pub fn allocate_for_buffer(&self, buffer: &Buffer, flags: MemoryPropertyFlags) -> ::Result<Allocation>
+	{
+		self.allocate_for(buffer, flags, ResourceTiling::Linear)
+	}
+	/// Allocate a range for `image` created with the `tiling`.
+	/// The image gets its own `DeviceMemory` if it prefers or requires a dedicated allocation(e.g. render targets on some implementations)
+	/// # Failures
+	/// On failure, this command returns the errors of `allocate` and `MemoryBound::dedicated_requirements`
+	pub fn allocate_for_image(&self, image: &Image, flags: MemoryPropertyFlags, tiling: ResourceTiling) -> ::Result<Allocation>
+	{
+		self.allocate_for(image, flags, tiling)
+	}
+
+	fn allocate_for<R: MemoryBound + DeviceChild>(&self, resource: &R, flags: MemoryPropertyFlags, tiling: ResourceTiling) -> ::Result<Allocation>
+	{
+		let r = resource.dedicated_requirements()?;
+		if r.requirements.size == 0 { return Err(::Error::EmptyAllocation); }
+		if !r.should_dedicate() { return self.allocate(&r.requirements, flags, tiling); }
+		self.try_types(r.requirements.memoryTypeBits, flags, |t|
+		{
+			let memory = RefCounter::new(DeviceMemory::allocate_dedicated(&self.0.device, resource, t)?);
+			let mut meta = BlockMetadata::new(memory.size(), AllocationStrategy::Linear);
+			let offset = meta.allocate(r.requirements.size, r.requirements.alignment.max(1), tiling, self.0.granularity).expect("no space in a dedicated block");
+			self.0.pools.lock().unwrap()[t as usize].push(Block { memory: memory.clone(), meta, dedicated: true });
+			Ok(Allocation { allocator: self.0.clone(), memory, type_index: t, offset, size: r.requirements.size })
+		})
+	}
+	/// Call `allocate` for each memory type allowed by `type_bits` and having all of `flags`, until it succeeds
+	fn try_types<F>(&self, type_bits: u32, flags: MemoryPropertyFlags, mut allocate: F) -> ::Result<Allocation>
+		where F: FnMut(u32) -> ::Result<Allocation>
+	{
+		let mut last_error = None;
+		for (n, t) in self.0.types.iter().enumerate()
+		{
+			if (type_bits & (1 << n)) == 0 || (t.propertyFlags & flags.bits()) != flags.bits() { continue; }
+			match allocate(n as _)
+			{
+				Err(e) => last_error = Some(e),
+				r => return r
+			}
+		}
+		Err(last_error.unwrap_or(::Error::NoMemoryType { type_bits, flags }))
+	}
+
+	fn allocate_in(&self, type_index: u32, requirements: &VkMemoryRequirements, tiling: ResourceTiling) -> ::Result<Allocation>
+	{
+		let c = &*self.0;
+		let (size, alignment) = (requirements.size, requirements.alignment.max(1));
+		let heap_size = c.heap_sizes[c.types[type_index as usize].heapIndex as usize];
+		let block_size = c.block_size.min(heap_size / 8).max(1);
+		let mut pools = c.pools.lock().unwrap();
+		let pool = &mut pools[type_index as usize];
+
+		let dedicated = size > block_size / 2;
+		let found = pool.iter_mut().filter(|b| !dedicated && !b.dedicated)
+			.find_map(|b| b.meta.allocate(size, alignment, tiling, c.granularity).map(|o| (b.memory.clone(), o)));
+		let (memory, offset) = match found
+		{
+			Some(r) => r,
+			None =>
+			{
+				let bytes = if dedicated { size } else { block_size };
+				let memory = RefCounter::new(DeviceMemory::allocate(&c.device, bytes as _, type_index)?);
+				let mut meta = BlockMetadata::new(bytes, if dedicated { AllocationStrategy::Linear } else { c.strategy });
+				let offset = meta.allocate(size, alignment, tiling, c.granularity).expect("no space in a new block");
+				pool.push(Block { memory: memory.clone(), meta, dedicated });
+				(memory, offset)
+			}
+		};
+		Ok(Allocation { allocator: self.0.clone(), memory, type_index, offset, size })
+	}
+}
+impl MemoryAllocator
+{
+	/// Statistics of all the blocks
+	pub fn statistics(&self) -> AllocatorStatistics
+	{
+		let mut s = AllocatorStatistics::default();
+		for pool in self.0.pools.lock().unwrap().iter() { for b in pool { s.accumulate(&b.meta.statistics()); } }
+		s
+	}
+	/// Statistics of the blocks of a memory type
+	pub fn type_statistics(&self, type_index: u32) -> AllocatorStatistics
+	{
+		let mut s = AllocatorStatistics::default();
+		for b in &self.0.pools.lock().unwrap()[type_index as usize] { s.accumulate(&b.meta.statistics()); }
+		s
+	}
+	/// Release the empty blocks kept for reuse
+	pub fn trim(&self)
+	{
+		for pool in self.0.pools.lock().unwrap().iter_mut() { pool.retain(|b| !b.meta.is_empty()); }
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use self::ResourceTiling::{Linear, Optimal};
+
+	#[test]
+	fn alignment()
+	{
+		let mut m = BlockMetadata::new(4096, AllocationStrategy::FreeList);
+		assert_eq!(m.allocate(100, 1, Linear, 1), Some(0));
+		assert_eq!(m.allocate(10, 256, Linear, 1), Some(256));
+		assert_eq!(m.allocate(10, 256, Linear, 1), Some(512));
+		let s = m.statistics();
+		assert_eq!((s.allocations, s.used_bytes, s.free_ranges), (3, 120, 3));
+	}
+	#[test]
+	fn granularity_separates_tilings()
+	{
+		let mut m = BlockMetadata::new(8192, AllocationStrategy::Linear);
+		assert_eq!(m.allocate(100, 16, Linear, 1024), Some(0));
+		assert_eq!(m.allocate(100, 16, Optimal, 1024), Some(1024));
+		// same tiling may share the page
+		assert_eq!(m.allocate(100, 16, Optimal, 1024), Some(1136));
+		assert_eq!(m.allocate(100, 16, Linear, 1024), Some(2048));
+
+		// a free range followed by the other tiling on the same page
+		let mut m = BlockMetadata::new(4096, AllocationStrategy::FreeList);
+		let o1 = m.allocate(100, 1, Optimal, 1024).unwrap();
+		assert_eq!(m.allocate(100, 1, Optimal, 1024), Some(100));
+		m.free(o1);
+		assert_eq!(m.allocate(50, 1, Linear, 1024), Some(1024));
+		assert_eq!(m.allocate(50, 1, Optimal, 1024), Some(0));
+	}
+	#[test]
+	fn free_merges_neighbours()
+	{
+		let mut m = BlockMetadata::new(4096, AllocationStrategy::FreeList);
+		let (a, b, c) = (m.allocate(100, 1, Linear, 1).unwrap(), m.allocate(100, 1, Linear, 1).unwrap(), m.allocate(100, 1, Linear, 1).unwrap());
+		m.free(b);
+		assert_eq!(m.statistics().free_ranges, 2);
+		m.free(a);
+		let s = m.statistics();
+		assert_eq!((s.free_ranges, s.largest_free_range), (2, 3796));
+		assert_eq!(m.ranges[0].size, 200);
+		m.free(c);
+		assert_eq!(m.ranges.len(), 1);
+		assert!(m.is_empty());
+	}
+	#[test]
+	fn strategies()
+	{
+		// best fit over all the free ranges
+		let mut m = BlockMetadata::new(4096, AllocationStrategy::FreeList);
+		let a = m.allocate(300, 1, Linear, 1).unwrap();
+		m.allocate(100, 1, Linear, 1).unwrap();
+		let c = m.allocate(150, 1, Linear, 1).unwrap();
+		m.allocate(100, 1, Linear, 1).unwrap();
+		m.free(a); m.free(c);
+		assert_eq!(m.allocate(120, 1, Linear, 1), Some(400));
+
+		// only after the last live range
+		let mut m = BlockMetadata::new(4096, AllocationStrategy::Linear);
+		let a = m.allocate(100, 1, Linear, 1).unwrap();
+		let b = m.allocate(100, 1, Linear, 1).unwrap();
+		m.free(a);
+		let c = m.allocate(50, 1, Linear, 1).unwrap();
+		assert_eq!(c, 200);
+		m.free(b); m.free(c);
+		assert_eq!(m.allocate(50, 1, Linear, 1), Some(0));
+	}
+	#[test]
+	fn rejects_empty_and_oversized()
+	{
+		let mut m = BlockMetadata::new(4096, AllocationStrategy::FreeList);
+		assert_eq!(m.allocate(0, 1, Linear, 1), None);
+		assert_eq!(m.allocate(4097, 1, Linear, 1), None);
+		assert!(m.is_empty());
+	}
+	#[test]
+	fn fragmentation()
+	{
+		let mut m = BlockMetadata::new(4096, AllocationStrategy::FreeList);
+		assert_eq!(m.statistics().fragmentation(), 0.0);
+		let offsets: Vec<_> = (0 .. 4).map(|_| m.allocate(1024, 1, Linear, 1).unwrap()).collect();
+		let s = m.statistics();
+		assert_eq!((s.free_bytes(), s.fragmentation()), (0, 0.0));
+		m.free(offsets[0]); m.free(offsets[2]);
+		let s = m.statistics();
+		assert_eq!((s.free_bytes(), s.free_ranges, s.largest_free_range), (2048, 2, 1024));
+		assert_eq!(s.fragmentation(), 0.5);
+
+		let mut total = AllocatorStatistics::default();
+		total.accumulate(&s); total.accumulate(&BlockMetadata::new(1024, AllocationStrategy::Linear).statistics());
+		assert_eq!((total.blocks, total.reserved_bytes, total.free_ranges, total.largest_free_range), (2, 5120, 3, 1024));
+	}
+	#[test]
+	#[cfg(all(feature = "Implements", feature = "CustomResolver"))]
+	fn empty_allocation()
+	{
+		let (driver, instance, device) = ::fakedrv::default_test_device();
+		let pd = instance.iter_physical_devices().unwrap().next().unwrap();
+		let allocator = MemoryAllocatorBuilder::new(AllocationStrategy::FreeList).create(&device, &pd);
+		let r = VkMemoryRequirements { size: 0, alignment: 1, memoryTypeBits: !0 };
+		match allocator.allocate(&r, MemoryPropertyFlags::DEVICE_LOCAL, Linear)
+		{
+			Err(::Error::EmptyAllocation) => (),
+			r => panic!("unexpected result: {:?}", r.map(|a| a.size()))
+		}
+		assert_eq!(allocator.statistics().blocks, 0);
+		::fakedrv::assert_no_misuse(&driver, &instance);
+	}
+
+	/// An allocator with the blocks of `block_size` bytes, on a device tracking its `DeviceMemory` objects
+	#[cfg(all(feature = "Implements", feature = "CustomResolver"))]
+	fn tracked_allocator<I>(instance: I, block_size: VkDeviceSize) -> (::fakedrv::FakeDriver, ::Instance, Device, MemoryAllocator)
+		where I: FnOnce(&mut ::InstanceBuilder)
+	{
+		let (driver, instance, device) = ::fakedrv::test_device(instance, |b| { b.track_memory(); });
+		let pd = instance.iter_physical_devices().unwrap().next().unwrap();
+		let allocator = MemoryAllocatorBuilder::new(AllocationStrategy::FreeList).block_size(block_size).create(&device, &pd);
+		(driver, instance, device, allocator)
+	}
+	/// Number of the live `DeviceMemory` objects of the memory type
+	#[cfg(all(feature = "Implements", feature = "CustomResolver"))]
+	fn memory_objects(device: &Device, type_index: u32) -> u32
+	{
+		device.memory_tracker().unwrap().snapshot().types[type_index as usize].count
+	}
+	#[test]
+	#[cfg(all(feature = "Implements", feature = "CustomResolver"))]
+	fn block_lifecycle()
+	{
+		use VkHandle;
+		let (driver, instance, device, allocator) = tracked_allocator(|_| (), 1 << 20);
+		let req = |size| VkMemoryRequirements { size, alignment: 256, memoryTypeBits: !0 };
+		let allocate = |size| allocator.allocate(&req(size), MemoryPropertyFlags::DEVICE_LOCAL, Linear).unwrap();
+
+		let (a, b) = (allocate(1000), allocate(1000));
+		assert_eq!((a.type_index(), a.offset(), b.offset()), (0, 0, 1024));
+		assert_eq!(a.memory().native_ptr(), b.memory().native_ptr());
+		assert_eq!(memory_objects(&device, 0), 1);
+		// the range of a dropped allocation is handed out again
+		drop(a);
+		assert_eq!(allocator.statistics().allocations, 1);
+		let a = allocate(1000);
+		assert_eq!(a.offset(), 0);
+
+		// requests larger than half of the block get their own memory, freed with the allocation
+		let large = allocate(600 << 10);
+		assert_eq!((large.offset(), large.memory().size(), memory_objects(&device, 0)), (0, 600 << 10, 2));
+		drop(large);
+		assert_eq!((allocator.statistics().blocks, memory_objects(&device, 0)), (1, 1));
+
+		// a block is added when the existing one is full
+		let mut fill: Vec<_> = (0 .. 3).map(|_| allocate(400 << 10)).collect();
+		assert_eq!(fill[1].memory().native_ptr(), a.memory().native_ptr());
+		assert_ne!(fill[2].memory().native_ptr(), a.memory().native_ptr());
+		assert_eq!((allocator.statistics().blocks, memory_objects(&device, 0)), (2, 2));
+		// one empty block is kept for reuse, the others are freed as soon as they get empty
+		drop(fill.pop());
+		let s = allocator.statistics();
+		assert_eq!((s.blocks, s.allocations, memory_objects(&device, 0)), (2, 4, 2));
+		drop(a); drop(b); drop(fill);
+		let s = allocator.type_statistics(0);
+		assert_eq!((s.blocks, s.allocations, s.free_bytes(), memory_objects(&device, 0)), (1, 0, 1 << 20, 1));
+		// the kept block is reused
+		let c = allocate(1000);
+		assert_eq!((allocator.statistics().blocks, memory_objects(&device, 0)), (1, 1));
+		drop(c);
+
+		allocator.trim();
+		assert_eq!((allocator.statistics(), memory_objects(&device, 0)), (AllocatorStatistics::default(), 0));
+		::fakedrv::assert_no_misuse(&driver, &instance);
+	}
+	#[test]
+	#[cfg(all(feature = "Implements", feature = "CustomResolver"))]
+	fn dedicated_blocks()
+	{
+		use {VkHandle, ImageDesc, ImageUsage, ImageLayout, BufferDesc, BufferUsage, Extent2D};
+		// the fake driver prefers dedicated allocations for render targets
+		let (driver, instance, device, allocator) = tracked_allocator(|b| { b.api_version(VK_API_VERSION_1_1); }, 1 << 20);
+		let target = ImageDesc::new(&Extent2D(4, 4), VK_FORMAT_R8G8B8A8_UNORM, ImageUsage::COLOR_ATTACHMENT, ImageLayout::Undefined)
+			.create(&device).unwrap();
+		let buffer = BufferDesc::new(256, BufferUsage::VERTEX_BUFFER).create(&device).unwrap();
+		let ta = allocator.allocate_for_image(&target, MemoryPropertyFlags::DEVICE_LOCAL, Optimal).unwrap();
+		let ba = allocator.allocate_for_buffer(&buffer, MemoryPropertyFlags::DEVICE_LOCAL).unwrap();
+		assert_ne!(ta.memory().native_ptr(), ba.memory().native_ptr());
+		assert_eq!((ta.offset(), ta.memory().size()), (0, target.requirements().size));
+		assert_eq!(ba.memory().size(), 1 << 20);
+		target.bind(ta.memory(), ta.offset() as _).unwrap();
+		buffer.bind(ba.memory(), ba.offset() as _).unwrap();
+		assert_eq!((allocator.statistics().blocks, memory_objects(&device, 0)), (2, 2));
+
+		// the dedicated memory is never kept for reuse
+		drop(target);
+		drop(ta);
+		assert_eq!((allocator.statistics().blocks, memory_objects(&device, 0)), (1, 1));
+		drop(buffer);
+		drop(ba);
+		assert_eq!((allocator.statistics().blocks, memory_objects(&device, 0)), (1, 1));
+		::fakedrv::assert_no_misuse(&driver, &instance);
+	}
+}
@@ -0,0 +1,1517 @@
+//! API call recorder and replayer
+//!
+//! `Recorder` wraps another `ResolverInterface`(typically the system driver returned by `Resolver::load`),
+//! forwards every call to it and appends the call to a trace: the function name, the arguments and the result.
+//! Structures passed by pointer are deep-copied along with their `pNext` chains, arrays and strings, and handles
+//! are recorded as they are seen by the application. Writes to mapped memory are captured as `MemoryWrite` records
+//! just before they can be observed by the device(`vkQueueSubmit`, `vkFlushMappedMemoryRanges`, `vkUnmapMemory`, ...).
+//!
+//! `Replayer` feeds a trace back through another resolver, translating the recorded handles into the ones created
+//! during the replay. `Trace::write_text` dumps a trace with handles renumbered in order of appearance,
+//! so traces taken from two versions can be compared with an ordinary diff tool.
+//!
+//! ```rust,ignore
+//! let recorder = Recorder::create(Resolver::load(), "app.brtrace")?;
+//! set_custom_resolver(Box::new(recorder.clone()));
+//! // ... run the application ...
+//! recorder.flush()?;
+//!
+//! let trace = Trace::open("app.brtrace")?;
+//! let mut replayer = Replayer::new(FakeDriver::new());
+//! replayer.replay(&trace)?;
+//! for d in replayer.divergences() { println!("{}", d); }
+//! ```
+//!
+//! Host pointers(window system objects, allocation callbacks, debug callbacks, ...) are recorded as `Value::Opaque`.
+//! Calls receiving non-null ones cannot be replayed unless the objects they create are `substitute`d beforehand.
+
+#![allow(non_snake_case)]
+
+mod schema;
+use self::schema::*;
+
+use vk::*;
+use vkresolve::ResolverInterface;
+use libc::{c_char, c_void, c_float, size_t};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::ffi::CStr;
+use std::fs::File;
+use std::io::{self, Read, Write, BufReader, BufWriter};
+use std::mem::{size_of, zeroed};
+use std::path::Path;
+use std::ptr::{self, null, null_mut};
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::{fmt, slice};
+
+#[cfg(feature = "VK_KHR_xlib_surface")] use x11::xlib::{Display, VisualID};
+#[cfg(feature = "VK_KHR_xcb_surface")] use xcb::ffi::{xcb_connection_t, xcb_visualid_t};
+
+/// Captured argument, structure member or result
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value
+{
+	Null, UInt(u64), SInt(i64), Float(f64),
+	/// Handle value as seen by the application
+	Handle(u64),
+	Str(String), Bytes(Vec<u8>), Array(Vec<Value>),
+	/// Structure name and its members in declaration order
+	Struct(String, Vec<(String, Value)>),
+	/// Address of a host object which has no meaning outside the recorded process
+	Opaque(u64)
+}
+impl Value
+{
+	/// Integral value of `UInt`, `SInt` and `Handle`
+	pub fn as_u64(&self) -> Option<u64>
+	{
+		match *self
+		{
+			Value::UInt(v) | Value::Handle(v) => Some(v), Value::SInt(v) => Some(v as _), _ => None
+		}
+	}
+	/// Member of a `Struct`
+	pub fn member(&self, name: &str) -> Option<&Value>
+	{
+		match *self
+		{
+			Value::Struct(_, ref m) => m.iter().find(|&(n, _)| n == name).map(|(_, v)| v),
+			_ => None
+		}
+	}
+}
+/// Recorded function call
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call { pub name: String, pub args: Vec<(String, Value)>, pub result: Value }
+impl Call
+{
+	pub fn arg(&self, name: &str) -> Option<&Value> { self.args.iter().find(|&(n, _)| n == name).map(|(_, v)| v) }
+}
+#[derive(Debug, Clone, PartialEq)]
+pub enum Record
+{
+	Call(Call),
+	/// Contents written by the host into a mapped memory(`offset` is from the beginning of the memory object)
+	MemoryWrite { memory: u64, offset: VkDeviceSize, data: Vec<u8> }
+}
+
+/// Sequence of the records, in the order the calls returned
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Trace { pub records: Vec<Record> }
+
+const MAGIC: &[u8; 8] = b"BRTRACE\0";
+const VERSION: u32 = 1;
+const UNKNOWN_CHAIN_MEMBER: &str = "<unknown>";
+
+impl Trace
+{
+	/// Read a trace written by `Recorder` or `Trace::write`
+	pub fn read<Rd: Read>(mut reader: Rd) -> io::Result<Self>
+	{
+		let mut header = [0u8; 12];
+		reader.read_exact(&mut header)?;
+		if &header[..8] != MAGIC { return Err(invalid_data("not a trace file")); }
+		if header[8..] != VERSION.to_le_bytes() { return Err(invalid_data("unsupported trace version")); }
+		let mut records = Vec::new();
+		while let Some(r) = read_record(&mut reader)? { records.push(r); }
+		Ok(Trace { records })
+	}
+	pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> { File::open(path).map(BufReader::new).and_then(Self::read) }
+	pub fn write<W: Write>(&self, mut writer: W) -> io::Result<()>
+	{
+		write_header(&mut writer)?;
+		for r in &self.records { write_record(&mut writer, r)?; }
+		writer.flush()
+	}
+	pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> { File::create(path).map(BufWriter::new).and_then(|w| self.write(w)) }
+
+	pub fn calls(&self) -> impl Iterator<Item = &Call>
+	{
+		self.records.iter().filter_map(|r| match *r { Record::Call(ref c) => Some(c), _ => None })
+	}
+
+	/// Write the trace as text, one record per line.
+	/// Handles are renumbered in order of appearance, host addresses are hidden and long byte sequences are hashed,
+	/// so the output of two runs can be compared line by line
+	pub fn write_text<W: Write>(&self, mut writer: W) -> io::Result<()>
+	{
+		let mut dumper = TextDumper { handles: HashMap::new(), line: String::new() };
+		for r in &self.records
+		{
+			dumper.line.clear();
+			dumper.record(r);
+			writeln!(writer, "{}", dumper.line)?;
+		}
+		writer.flush()
+	}
+}
+
+struct TextDumper { handles: HashMap<u64, usize>, line: String }
+impl TextDumper
+{
+	fn record(&mut self, r: &Record)
+	{
+		match *r
+		{
+			Record::Call(ref c) =>
+			{
+				self.line.push_str(&c.name); self.line.push('(');
+				for (i, (n, v)) in c.args.iter().enumerate()
+				{
+					if i > 0 { self.line.push_str(", "); }
+					self.line.push_str(n); self.line.push_str(": "); self.value(v);
+				}
+				self.line.push(')');
+				if c.result != Value::Null { self.line.push_str(" -> "); self.value(&c.result); }
+			},
+			Record::MemoryWrite { memory, offset, ref data } =>
+			{
+				self.line.push_str("<memory write> ");
+				self.value(&Value::Handle(memory));
+				self.line.push_str(&format!(" +{}: ", offset));
+				self.bytes(data);
+			}
+		}
+	}
+	fn value(&mut self, v: &Value)
+	{
+		match *v
+		{
+			Value::Null | Value::Handle(0) => self.line.push_str("null"),
+			Value::UInt(x) => self.line.push_str(&x.to_string()),
+			Value::SInt(x) => self.line.push_str(&x.to_string()),
+			Value::Float(x) => self.line.push_str(&format!("{:?}", x)),
+			Value::Handle(h) =>
+			{
+				let next = self.handles.len() + 1;
+				let n = *self.handles.entry(h).or_insert(next);
+				self.line.push_str(&format!("#{}", n));
+			},
+			Value::Str(ref s) => self.line.push_str(&format!("{:?}", s)),
+			Value::Bytes(ref b) => self.bytes(b),
+			Value::Array(ref vs) =>
+			{
+				self.line.push('[');
+				for (i, x) in vs.iter().enumerate() { if i > 0 { self.line.push_str(", "); } self.value(x); }
+				self.line.push(']');
+			},
+			Value::Struct(ref n, ref m) =>
+			{
+				self.line.push_str(n); self.line.push_str(" {");
+				for (i, (f, x)) in m.iter().enumerate()
+				{
+					self.line.push_str(if i > 0 { ", " } else { " " });
+					self.line.push_str(f); self.line.push_str(": "); self.value(x);
+				}
+				self.line.push_str(" }");
+			},
+			Value::Opaque(_) => self.line.push_str("<host>")
+		}
+	}
+	fn bytes(&mut self, b: &[u8])
+	{
+		if b.len() <= 32
+		{
+			self.line.push('<');
+			for x in b { self.line.push_str(&format!("{:02x}", x)); }
+			self.line.push('>');
+		}
+		else
+		{
+			// FNV-1a
+			let h = b.iter().fold(0xcbf2_9ce4_8422_2325u64, |h, &x| (h ^ x as u64).wrapping_mul(0x0100_0000_01b3));
+			self.line.push_str(&format!("<{} bytes, fnv1a {:016x}>", b.len(), h));
+		}
+	}
+}
+
+// Binary format: MAGIC, VERSION(u32) and tagged records. Integers are little endian, lengths are u64
+fn invalid_data(msg: &str) -> io::Error { io::Error::new(io::ErrorKind::InvalidData, msg) }
+fn write_header<W: Write + ?Sized>(w: &mut W) -> io::Result<()>
+{
+	w.write_all(MAGIC)?; w.write_all(&VERSION.to_le_bytes())
+}
+fn write_u64<W: Write + ?Sized>(w: &mut W, v: u64) -> io::Result<()> { w.write_all(&v.to_le_bytes()) }
+fn write_bytes<W: Write + ?Sized>(w: &mut W, b: &[u8]) -> io::Result<()> { write_u64(w, b.len() as _)?; w.write_all(b) }
+fn write_value<W: Write + ?Sized>(w: &mut W, v: &Value) -> io::Result<()>
+{
+	match *v
+	{
+		Value::Null => w.write_all(&[0]),
+		Value::UInt(x) => { w.write_all(&[1])?; write_u64(w, x) },
+		Value::SInt(x) => { w.write_all(&[2])?; write_u64(w, x as _) },
+		Value::Float(x) => { w.write_all(&[3])?; write_u64(w, x.to_bits()) },
+		Value::Handle(x) => { w.write_all(&[4])?; write_u64(w, x) },
+		Value::Str(ref s) => { w.write_all(&[5])?; write_bytes(w, s.as_bytes()) },
+		Value::Bytes(ref b) => { w.write_all(&[6])?; write_bytes(w, b) },
+		Value::Array(ref vs) =>
+		{
+			w.write_all(&[7])?; write_u64(w, vs.len() as _)?;
+			for x in vs { write_value(w, x)?; }
+			Ok(())
+		},
+		Value::Struct(ref n, ref m) =>
+		{
+			w.write_all(&[8])?; write_bytes(w, n.as_bytes())?; write_members(w, m)
+		},
+		Value::Opaque(x) => { w.write_all(&[9])?; write_u64(w, x) }
+	}
+}
+fn write_members<W: Write + ?Sized>(w: &mut W, m: &[(String, Value)]) -> io::Result<()>
+{
+	write_u64(w, m.len() as _)?;
+	for (n, v) in m { write_bytes(w, n.as_bytes())?; write_value(w, v)?; }
+	Ok(())
+}
+fn write_record<W: Write + ?Sized>(w: &mut W, r: &Record) -> io::Result<()>
+{
+	match *r
+	{
+		Record::Call(ref c) =>
+		{
+			w.write_all(&[0])?; write_bytes(w, c.name.as_bytes())?; write_members(w, &c.args)?; write_value(w, &c.result)
+		},
+		Record::MemoryWrite { memory, offset, ref data } =>
+		{
+			w.write_all(&[1])?; write_u64(w, memory)?; write_u64(w, offset)?; write_bytes(w, data)
+		}
+	}
+}
+fn read_u8<Rd: Read + ?Sized>(r: &mut Rd) -> io::Result<u8> { let mut b = [0u8; 1]; r.read_exact(&mut b)?; Ok(b[0]) }
+fn read_u64<Rd: Read + ?Sized>(r: &mut Rd) -> io::Result<u64> { let mut b = [0u8; 8]; r.read_exact(&mut b)?; Ok(u64::from_le_bytes(b)) }
+fn read_bytes<Rd: Read + ?Sized>(r: &mut Rd) -> io::Result<Vec<u8>>
+{
+	let len = read_u64(r)?;
+	let mut b = Vec::new();
+	r.take(len).read_to_end(&mut b)?;
+	if b.len() as u64 != len { return Err(io::ErrorKind::UnexpectedEof.into()); }
+	Ok(b)
+}
+fn read_string<Rd: Read + ?Sized>(r: &mut Rd) -> io::Result<String>
+{
+	String::from_utf8(read_bytes(r)?).map_err(|_| invalid_data("invalid string"))
+}
+fn read_value<Rd: Read + ?Sized>(r: &mut Rd) -> io::Result<Value>
+{
+	Ok(match read_u8(r)?
+	{
+		0 => Value::Null,
+		1 => Value::UInt(read_u64(r)?),
+		2 => Value::SInt(read_u64(r)? as _),
+		3 => Value::Float(f64::from_bits(read_u64(r)?)),
+		4 => Value::Handle(read_u64(r)?),
+		5 => Value::Str(read_string(r)?),
+		6 => Value::Bytes(read_bytes(r)?),
+		7 =>
+		{
+			let n = read_u64(r)?;
+			Value::Array((0 .. n).map(|_| read_value(r)).collect::<io::Result<_>>()?)
+		},
+		8 => { let n = read_string(r)?; Value::Struct(n, read_members(r)?) },
+		9 => Value::Opaque(read_u64(r)?),
+		_ => return Err(invalid_data("unknown value tag"))
+	})
+}
+fn read_members<Rd: Read + ?Sized>(r: &mut Rd) -> io::Result<Vec<(String, Value)>>
+{
+	let n = read_u64(r)?;
+	(0 .. n).map(|_| Ok((read_string(r)?, read_value(r)?))).collect()
+}
+fn read_record<Rd: Read + ?Sized>(r: &mut Rd) -> io::Result<Option<Record>>
+{
+	let mut tag = [0u8; 1];
+	if r.read(&mut tag)? == 0 { return Ok(None); }
+	Ok(Some(match tag[0]
+	{
+		0 =>
+		{
+			let name = read_string(r)?;
+			let args = read_members(r)?;
+			Record::Call(Call { name, args, result: read_value(r)? })
+		},
+		1 =>
+		{
+			let memory = read_u64(r)?;
+			let offset = read_u64(r)?;
+			Record::MemoryWrite { memory, offset, data: read_bytes(r)? }
+		},
+		_ => return Err(invalid_data("unknown record tag"))
+	}))
+}
+
+/// Conversion between Vulkan handles and recorded values
+trait TraceHandle: Copy
+{
+	fn from_id(id: u64) -> Self;
+	fn id(self) -> u64;
+}
+impl<T> TraceHandle for *mut T
+{
+	fn from_id(id: u64) -> Self { id as usize as _ }
+	fn id(self) -> u64 { self as usize as _ }
+}
+impl TraceHandle for u64
+{
+	fn from_id(id: u64) -> Self { id }
+	fn id(self) -> u64 { self }
+}
+trait NullPtr { fn null() -> Self; }
+impl<T> NullPtr for *const T { fn null() -> Self { null() } }
+impl<T> NullPtr for *mut T { fn null() -> Self { null_mut() } }
+/// Return values of the API
+trait TraceResult
+{
+	fn value(&self) -> Value;
+	/// Whether the outputs have been written
+	fn succeeded(&self) -> bool { true }
+}
+impl TraceResult for () { fn value(&self) -> Value { Value::Null } }
+impl TraceResult for VkResult
+{
+	fn value(&self) -> Value { Value::SInt(*self as _) }
+	fn succeeded(&self) -> bool { *self >= 0 }
+}
+impl TraceResult for VkBool32 { fn value(&self) -> Value { Value::UInt(*self as _) } }
+impl TraceResult for Option<PFN_vkVoidFunction>
+{
+	fn value(&self) -> Value { self.map_or(Value::Null, |f| Value::Opaque(f as usize as _)) }
+}
+
+#[repr(C)]
+struct ChainHeader { sType: VkStructureType, pNext: *const c_void }
+const PNEXT_OFFSET: usize = ::std::mem::offset_of!(ChainHeader, pNext);
+
+// Capturing //
+
+unsafe fn read_scalar(kind: ScalarKind, p: *const u8) -> Value
+{
+	match kind
+	{
+		ScalarKind::U8 => Value::UInt(*p as _),
+		ScalarKind::I8 => Value::SInt(*(p as *const i8) as _),
+		ScalarKind::U16 => Value::UInt(ptr::read_unaligned(p as *const u16) as _),
+		ScalarKind::I16 => Value::SInt(ptr::read_unaligned(p as *const i16) as _),
+		ScalarKind::U32 => Value::UInt(ptr::read_unaligned(p as *const u32) as _),
+		ScalarKind::I32 => Value::SInt(ptr::read_unaligned(p as *const i32) as _),
+		ScalarKind::U64 => Value::UInt(ptr::read_unaligned(p as *const u64)),
+		ScalarKind::I64 => Value::SInt(ptr::read_unaligned(p as *const i64)),
+		ScalarKind::F32 => Value::Float(ptr::read_unaligned(p as *const f32) as _),
+		ScalarKind::F64 => Value::Float(ptr::read_unaligned(p as *const f64))
+	}
+}
+unsafe fn read_handle(size: usize, p: *const u8) -> u64
+{
+	if size == 8 { ptr::read_unaligned(p as *const u64) } else { ptr::read_unaligned(p as *const u32) as _ }
+}
+unsafe fn read_ptr(p: *const u8) -> *const u8 { *(p as *const *const u8) }
+fn scalar_value<T: TraceScalar>(v: &T) -> Value { unsafe { read_scalar(T::KIND, v as *const T as *const u8) } }
+fn opaque_value(p: *const c_void) -> Value { if p.is_null() { Value::Null } else { Value::Opaque(p as usize as _) } }
+unsafe fn capture_str(p: *const c_char) -> Value
+{
+	if p.is_null() { Value::Null } else { Value::Str(CStr::from_ptr(p).to_string_lossy().into_owned()) }
+}
+unsafe fn capture_bytes(p: *const u8, len: usize) -> Value
+{
+	if p.is_null() { Value::Null } else { Value::Bytes(slice::from_raw_parts(p, len).to_vec()) }
+}
+unsafe fn capture_elem(e: Elem, p: *const u8) -> Value
+{
+	match e
+	{
+		Elem::Scalar(k) => read_scalar(k, p),
+		Elem::Handle(s) => Value::Handle(read_handle(s, p)),
+		Elem::Struct(s) => capture_struct(s, p, true),
+		Elem::Raw(s) => Value::Bytes(slice::from_raw_parts(p, s).to_vec())
+	}
+}
+unsafe fn capture_array(e: Elem, p: *const u8, count: usize) -> Value
+{
+	if p.is_null() { return Value::Null; }
+	Value::Array((0 .. count).map(|i| capture_elem(e, p.add(i * e.size()))).collect())
+}
+unsafe fn count_member(s: &Schema, p: *const u8, name: &str) -> usize
+{
+	match s.field(name)
+	{
+		Some(&Field { offset, kind: FieldKind::Value(Elem::Scalar(k)), .. }) =>
+			read_scalar(k, p.add(offset)).as_u64().unwrap_or(0) as _,
+		_ => 0
+	}
+}
+/// Deep copy of a structure. Members of a `pNext` chain are flattened into the `pNext` of the first structure
+unsafe fn capture_struct(s: &Schema, p: *const u8, follow_chain: bool) -> Value
+{
+	let members = s.fields.iter().map(|f|
+	{
+		let fp = p.add(f.offset);
+		let v = match f.kind
+		{
+			FieldKind::Value(e) => capture_elem(e, fp),
+			FieldKind::Chain => if follow_chain { capture_chain(read_ptr(fp)) } else { Value::Null },
+			FieldKind::Str => capture_str(read_ptr(fp) as _),
+			FieldKind::Strs(c) =>
+			{
+				let a = read_ptr(fp) as *const *const c_char;
+				if a.is_null() { Value::Null }
+				else { Value::Array((0 .. count_member(s, p, c)).map(|i| capture_str(*a.add(i))).collect()) }
+			},
+			FieldKind::Array(c, e) => capture_array(e, read_ptr(fp), count_member(s, p, c)),
+			FieldKind::ArrayIf(c, e, pred) =>
+				if pred(p) { capture_array(e, read_ptr(fp), count_member(s, p, c)) } else { Value::Null },
+			FieldKind::ArrayWith(count, e) => capture_array(e, read_ptr(fp), count(p)),
+			FieldKind::Ptr(e) => { let q = read_ptr(fp); if q.is_null() { Value::Null } else { capture_elem(e, q) } },
+			FieldKind::Bytes(c) => capture_bytes(read_ptr(fp), count_member(s, p, c)),
+			FieldKind::Fixed(n, e) => Value::Array((0 .. n).map(|i| capture_elem(e, fp.add(i * e.size()))).collect()),
+			FieldKind::Output | FieldKind::Opaque => opaque_value(read_ptr(fp) as _)
+		};
+		(f.name.to_owned(), v)
+	}).collect();
+	Value::Struct(s.name.to_owned(), members)
+}
+/// Structures not known to the tracer are recorded with their `sType` only
+unsafe fn capture_chain(mut p: *const u8) -> Value
+{
+	let mut members = Vec::new();
+	while !p.is_null()
+	{
+		let header = &*(p as *const ChainHeader);
+		members.push(match by_stype(header.sType)
+		{
+			Some(s) => capture_struct(s, p, false),
+			None => Value::Struct(UNKNOWN_CHAIN_MEMBER.to_owned(), vec![("sType".to_owned(), Value::SInt(header.sType as _))])
+		});
+		p = header.pNext as _;
+	}
+	Value::Array(members)
+}
+unsafe fn capture_input<T: Traced>(p: *const T) -> Value
+{
+	if p.is_null() { Value::Null } else { capture_struct(T::SCHEMA, p as _, true) }
+}
+unsafe fn capture_structs<T: Traced>(p: *const T, count: usize) -> Value
+{
+	capture_array(Elem::Struct(T::SCHEMA), p as _, count)
+}
+unsafe fn capture_handles<T: TraceHandle>(p: *const T, count: usize) -> Value
+{
+	if p.is_null() { Value::Null } else { Value::Array((0 .. count).map(|i| Value::Handle((*p.add(i)).id())).collect()) }
+}
+unsafe fn capture_scalars<T: TraceScalar>(p: *const T, count: usize) -> Value
+{
+	if p.is_null() { Value::Null } else { Value::Array((0 .. count).map(|i| scalar_value(&*p.add(i))).collect()) }
+}
+/// Plain copy of an output structure. `pNext` of the extensible ones is cleared since it is a host address
+unsafe fn capture_raw<T>(p: *const T, chained: bool) -> Value
+{
+	if p.is_null() { return Value::Null; }
+	let mut b = slice::from_raw_parts(p as *const u8, size_of::<T>()).to_vec();
+	if chained { for x in &mut b[PNEXT_OFFSET .. PNEXT_OFFSET + size_of::<*const c_void>()] { *x = 0; } }
+	Value::Bytes(b)
+}
+unsafe fn capture_raw_array<T>(p: *const T, count: usize, chained: bool) -> Value
+{
+	if p.is_null() { Value::Null } else { Value::Array((0 .. count).map(|i| capture_raw(p.add(i), chained)).collect()) }
+}
+
+// Restoring //
+
+/// Storage of the restored structures, alive until the replayed call returns
+struct Arena(Vec<Box<[u64]>>);
+impl Arena
+{
+	fn alloc(&mut self, size: usize) -> *mut u8
+	{
+		let mut block = vec![0u64; ((size + 7) / 8).max(1)].into_boxed_slice();
+		let p = block.as_mut_ptr() as *mut u8;
+		self.0.push(block);
+		p
+	}
+	fn bytes(&mut self, b: &[u8]) -> *mut u8
+	{
+		let p = self.alloc(b.len());
+		unsafe { ptr::copy_nonoverlapping(b.as_ptr(), p, b.len()); }
+		p
+	}
+	fn cstr(&mut self, s: &str) -> *const c_char { self.bytes(s.as_bytes()) as _ }
+}
+
+/// Reason of a replay failure
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReplayErrorKind
+{
+	/// The function is not known to this build(e.g. the extension is disabled)
+	UnknownCall,
+	/// A recorded handle has not been created during the replay
+	UnknownHandle(u64),
+	/// The named argument or member holds a host pointer
+	Unreplayable(&'static str),
+	/// Mapped memory write to a memory object not mapped during the replay
+	UnmappedMemory(u64),
+	/// The record does not match the function signature
+	Malformed
+}
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReplayError
+{
+	/// Index of the record in the trace
+	pub index: usize,
+	pub call: String,
+	pub kind: ReplayErrorKind
+}
+impl fmt::Display for ReplayError
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result
+	{
+		write!(fmt, "record #{}({}): ", self.index, self.call)?;
+		match self.kind
+		{
+			ReplayErrorKind::UnknownCall => write!(fmt, "unknown function"),
+			ReplayErrorKind::UnknownHandle(h) => write!(fmt, "handle 0x{:x} is not created in the replay", h),
+			ReplayErrorKind::Unreplayable(a) => write!(fmt, "`{}` holds a host pointer", a),
+			ReplayErrorKind::UnmappedMemory(m) => write!(fmt, "memory 0x{:x} is not mapped", m),
+			ReplayErrorKind::Malformed => write!(fmt, "malformed record")
+		}
+	}
+}
+impl ::std::error::Error for ReplayError {}
+type RestoreResult<T> = Result<T, ReplayErrorKind>;
+
+unsafe fn write_scalar(kind: ScalarKind, v: &Value, p: *mut u8) -> RestoreResult<()>
+{
+	match (kind, v)
+	{
+		(ScalarKind::F32, &Value::Float(x)) => ptr::write_unaligned(p as *mut f32, x as _),
+		(ScalarKind::F64, &Value::Float(x)) => ptr::write_unaligned(p as *mut f64, x),
+		(ScalarKind::F32, _) | (ScalarKind::F64, _) => return Err(ReplayErrorKind::Malformed),
+		(k, v) =>
+		{
+			let x = v.as_u64().ok_or(ReplayErrorKind::Malformed)?;
+			ptr::copy_nonoverlapping(&x as *const u64 as *const u8, p, k.size());
+		}
+	}
+	Ok(())
+}
+unsafe fn write_handle(size: usize, h: u64, p: *mut u8)
+{
+	if size == 8 { ptr::write_unaligned(p as *mut u64, h) } else { ptr::write_unaligned(p as *mut u32, h as _) }
+}
+unsafe fn write_ptr<T>(p: *mut u8, v: *const T) { *(p as *mut *const T) = v; }
+fn restore_scalar<T: TraceScalar>(v: &Value) -> RestoreResult<T>
+{
+	unsafe { let mut t: T = zeroed(); write_scalar(T::KIND, v, &mut t as *mut T as *mut u8).map(|_| t) }
+}
+fn restore_fixed<T: TraceScalar, A: Default + AsMut<[T]>>(v: &Value) -> RestoreResult<A>
+{
+	let mut a = A::default();
+	match *v
+	{
+		Value::Array(ref vs) if vs.len() == a.as_mut().len() =>
+		{
+			for (d, x) in a.as_mut().iter_mut().zip(vs) { *d = restore_scalar(x)?; }
+			Ok(a)
+		},
+		_ => Err(ReplayErrorKind::Malformed)
+	}
+}
+fn restore_str(arena: &mut Arena, v: &Value) -> RestoreResult<*const c_char>
+{
+	match *v
+	{
+		Value::Null => Ok(null()), Value::Str(ref s) => Ok(arena.cstr(s)), _ => Err(ReplayErrorKind::Malformed)
+	}
+}
+fn restore_opaque<T: NullPtr>(v: &Value, name: &'static str) -> RestoreResult<T>
+{
+	if *v == Value::Null { Ok(T::null()) } else { Err(ReplayErrorKind::Unreplayable(name)) }
+}
+fn restore_bytes<T>(arena: &mut Arena, v: &Value) -> RestoreResult<*const T>
+{
+	match *v
+	{
+		Value::Null => Ok(null()), Value::Bytes(ref b) => Ok(arena.bytes(b) as _), _ => Err(ReplayErrorKind::Malformed)
+	}
+}
+fn restore_raw<T>(arena: &mut Arena, v: &Value) -> RestoreResult<*const T>
+{
+	match *v
+	{
+		Value::Bytes(ref b) if b.len() != size_of::<T>() => Err(ReplayErrorKind::Malformed),
+		_ => restore_bytes(arena, v)
+	}
+}
+fn restore_scalars<T: TraceScalar>(arena: &mut Arena, v: &Value) -> RestoreResult<*const T>
+{
+	match *v
+	{
+		Value::Null => Ok(null()),
+		Value::Array(ref vs) =>
+		{
+			let p = arena.alloc(vs.len() * size_of::<T>()) as *mut T;
+			for (i, x) in vs.iter().enumerate() { unsafe { *p.add(i) = restore_scalar(x)?; } }
+			Ok(p)
+		},
+		_ => Err(ReplayErrorKind::Malformed)
+	}
+}
+/// In-out element count of the enumerating functions
+fn restore_count<T: TraceScalar>(arena: &mut Arena, v: &Value) -> RestoreResult<*mut T>
+{
+	let p = arena.alloc(size_of::<T>()) as *mut T;
+	if *v != Value::Null { unsafe { *p = restore_scalar(v)?; } }
+	Ok(p)
+}
+fn alloc_out<T>(arena: &mut Arena, count: usize) -> *mut T { arena.alloc(count * size_of::<T>()) as _ }
+/// Output structures are initialized with the recorded contents so that `sType`s are set
+fn restore_out_chains<T>(arena: &mut Arena, v: &Value, count: usize) -> RestoreResult<*mut T>
+{
+	let p: *mut T = alloc_out(arena, count);
+	let elements = match *v
+	{
+		Value::Null => return Ok(p),
+		Value::Bytes(_) => slice::from_ref(v),
+		Value::Array(ref vs) => &vs[..],
+		_ => return Err(ReplayErrorKind::Malformed)
+	};
+	for (i, e) in elements.iter().take(count).enumerate()
+	{
+		match *e
+		{
+			Value::Bytes(ref b) if b.len() == size_of::<T>() => unsafe
+			{
+				let d = p.add(i) as *mut u8;
+				ptr::copy_nonoverlapping(b.as_ptr(), d, b.len());
+				write_ptr(d.add(PNEXT_OFFSET), null::<c_void>());
+			},
+			_ => return Err(ReplayErrorKind::Malformed)
+		}
+	}
+	Ok(p)
+}
+
+/// Re-issues the recorded calls through another resolver
+pub struct Replayer<R: ResolverInterface>
+{
+	resolver: R,
+	handles: HashMap<u64, u64>, substitutes: HashSet<u64>,
+	/// memory -> (mapped address, offset of the mapping)
+	mappings: HashMap<u64, (usize, VkDeviceSize)>, last_mapped: usize,
+	divergences: Vec<Divergence>
+}
+/// Call whose result differs from the recorded one
+#[derive(Debug, Clone, PartialEq)]
+pub struct Divergence { pub index: usize, pub call: String, pub recorded: Value, pub replayed: Value }
+impl fmt::Display for Divergence
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result
+	{
+		write!(fmt, "record #{}({}): recorded {:?}, replayed {:?}", self.index, self.call, self.recorded, self.replayed)
+	}
+}
+impl<R: ResolverInterface> Replayer<R>
+{
+	pub fn new(resolver: R) -> Self
+	{
+		Replayer
+		{
+			resolver, handles: HashMap::new(), substitutes: HashSet::new(),
+			mappings: HashMap::new(), last_mapped: 0, divergences: Vec::new()
+		}
+	}
+	pub fn resolver(&self) -> &R { &self.resolver }
+	pub fn into_resolver(self) -> R { self.resolver }
+	/// Use `handle` wherever `recorded` appears. The call which created `recorded` is skipped
+	pub fn substitute(&mut self, recorded: u64, handle: u64)
+	{
+		self.handles.insert(recorded, handle);
+		self.substitutes.insert(recorded);
+	}
+	/// Handle corresponding to the recorded one
+	pub fn handle(&self, recorded: u64) -> Option<u64> { self.handles.get(&recorded).cloned() }
+	/// Calls whose results differed from the recorded ones so far
+	pub fn divergences(&self) -> &[Divergence] { &self.divergences }
+
+	/// Replay every record, stopping at the first failure
+	pub fn replay(&mut self, trace: &Trace) -> Result<(), ReplayError>
+	{
+		for (i, r) in trace.records.iter().enumerate() { self.replay_record(i, r)?; }
+		Ok(())
+	}
+	/// Replay a record. `index` is used to report errors and divergences
+	pub fn replay_record(&mut self, index: usize, record: &Record) -> Result<(), ReplayError>
+	{
+		match *record
+		{
+			Record::Call(ref c) =>
+			{
+				let replayed = self.dispatch(c).map_err(|kind| ReplayError { index, call: c.name.clone(), kind })?;
+				match replayed
+				{
+					// host addresses(e.g. function pointers) never match
+					Some(Value::Opaque(_)) | None => (),
+					Some(r) => if r != c.result
+					{
+						self.divergences.push(Divergence { index, call: c.name.clone(), recorded: c.result.clone(), replayed: r });
+					}
+				}
+				Ok(())
+			},
+			Record::MemoryWrite { memory, offset, ref data } =>
+			{
+				let &(p, base) = self.mappings.get(&memory).ok_or_else(|| ReplayError
+				{
+					index, call: "<memory write>".to_owned(), kind: ReplayErrorKind::UnmappedMemory(memory)
+				})?;
+				unsafe { ptr::copy_nonoverlapping(data.as_ptr(), (p as *mut u8).add((offset - base) as _), data.len()); }
+				Ok(())
+			}
+		}
+	}
+
+	fn translate(&self, v: &Value) -> RestoreResult<u64>
+	{
+		match *v
+		{
+			Value::Null | Value::Handle(0) => Ok(0),
+			Value::Handle(h) => self.handles.get(&h).cloned().ok_or(ReplayErrorKind::UnknownHandle(h)),
+			_ => Err(ReplayErrorKind::Malformed)
+		}
+	}
+	fn substituted(&self, call: &Call, outputs: &[bool]) -> bool
+	{
+		call.args.iter().zip(outputs).any(|((_, v), &o)| o && match *v
+		{
+			Value::Handle(h) => self.substitutes.contains(&h), _ => false
+		})
+	}
+	/// Associate the recorded output handles with the replayed ones
+	unsafe fn remap<T: TraceHandle>(&mut self, v: &Value, p: *const T, count: usize)
+	{
+		match *v
+		{
+			Value::Handle(h) if count > 0 => { self.handles.insert(h, (*p).id()); },
+			Value::Array(ref vs) => for (i, x) in vs.iter().take(count).enumerate()
+			{
+				if let Value::Handle(h) = *x { self.handles.insert(h, (*p.add(i)).id()); }
+			},
+			_ => ()
+		}
+	}
+	fn after_call(&mut self, call: &Call, result: &Option<Value>)
+	{
+		let memory = call.arg("memory").and_then(Value::as_u64);
+		match (&call.name[..], memory)
+		{
+			("vkMapMemory", Some(m)) if *result == Some(Value::SInt(VK_SUCCESS as _)) =>
+			{
+				let offset = call.arg("offset").and_then(Value::as_u64).unwrap_or(0);
+				self.mappings.insert(m, (self.last_mapped, offset));
+			},
+			("vkUnmapMemory", Some(m)) | ("vkFreeMemory", Some(m)) => { self.mappings.remove(&m); },
+			_ => ()
+		}
+	}
+
+	unsafe fn restore_elem(&self, arena: &mut Arena, e: Elem, v: &Value, p: *mut u8) -> RestoreResult<()>
+	{
+		match e
+		{
+			Elem::Scalar(k) => write_scalar(k, v, p),
+			Elem::Handle(s) => { write_handle(s, self.translate(v)?, p); Ok(()) },
+			Elem::Struct(s) => self.restore_struct(arena, s, v, p),
+			Elem::Raw(s) => match *v
+			{
+				Value::Bytes(ref b) if b.len() == s => { ptr::copy_nonoverlapping(b.as_ptr(), p, s); Ok(()) },
+				_ => Err(ReplayErrorKind::Malformed)
+			}
+		}
+	}
+	unsafe fn restore_array(&self, arena: &mut Arena, e: Elem, v: &Value) -> RestoreResult<*const u8>
+	{
+		match *v
+		{
+			Value::Null => Ok(null()),
+			Value::Array(ref vs) =>
+			{
+				let p = arena.alloc(vs.len() * e.size());
+				for (i, x) in vs.iter().enumerate() { self.restore_elem(arena, e, x, p.add(i * e.size()))?; }
+				Ok(p)
+			},
+			_ => Err(ReplayErrorKind::Malformed)
+		}
+	}
+	unsafe fn restore_struct(&self, arena: &mut Arena, s: &Schema, v: &Value, p: *mut u8) -> RestoreResult<()>
+	{
+		let members = match *v
+		{
+			Value::Struct(ref n, ref m) if n == s.name && m.len() == s.fields.len() => m,
+			_ => return Err(ReplayErrorKind::Malformed)
+		};
+		for (f, (n, x)) in s.fields.iter().zip(members)
+		{
+			if n != f.name { return Err(ReplayErrorKind::Malformed); }
+			let fp = p.add(f.offset);
+			match f.kind
+			{
+				FieldKind::Value(e) => self.restore_elem(arena, e, x, fp)?,
+				FieldKind::Chain => write_ptr(fp, self.restore_chain(arena, x)?),
+				FieldKind::Str => write_ptr(fp, restore_str(arena, x)?),
+				FieldKind::Strs(_) => match *x
+				{
+					Value::Null => write_ptr(fp, null::<*const c_char>()),
+					Value::Array(ref vs) =>
+					{
+						let a = alloc_out::<*const c_char>(arena, vs.len());
+						for (i, s) in vs.iter().enumerate() { *a.add(i) = restore_str(arena, s)?; }
+						write_ptr(fp, a);
+					},
+					_ => return Err(ReplayErrorKind::Malformed)
+				},
+				FieldKind::Array(_, e) | FieldKind::ArrayIf(_, e, _) | FieldKind::ArrayWith(_, e) =>
+					write_ptr(fp, self.restore_array(arena, e, x)?),
+				FieldKind::Ptr(_) if *x == Value::Null => write_ptr(fp, null::<u8>()),
+				FieldKind::Ptr(e) =>
+				{
+					let q = arena.alloc(e.size());
+					self.restore_elem(arena, e, x, q)?;
+					write_ptr(fp, q);
+				},
+				FieldKind::Bytes(_) => write_ptr(fp, restore_bytes::<u8>(arena, x)?),
+				FieldKind::Fixed(n, e) => match *x
+				{
+					Value::Array(ref vs) if vs.len() == n =>
+						for (i, y) in vs.iter().enumerate() { self.restore_elem(arena, e, y, fp.add(i * e.size()))?; },
+					_ => return Err(ReplayErrorKind::Malformed)
+				},
+				FieldKind::Output => write_ptr(fp, null::<u8>()),
+				FieldKind::Opaque => write_ptr(fp, restore_opaque::<*const u8>(x, f.name)?)
+			}
+		}
+		Ok(())
+	}
+	/// Unknown members are dropped from the chain
+	unsafe fn restore_chain(&self, arena: &mut Arena, v: &Value) -> RestoreResult<*const u8>
+	{
+		let members = match *v
+		{
+			Value::Null => return Ok(null()), Value::Array(ref m) => m, _ => return Err(ReplayErrorKind::Malformed)
+		};
+		let mut head: *const u8 = null();
+		for m in members.iter().rev()
+		{
+			let s = match *m
+			{
+				Value::Struct(ref n, _) => match by_name(n) { Some(s) => s, None => continue },
+				_ => return Err(ReplayErrorKind::Malformed)
+			};
+			let p = arena.alloc(s.size);
+			self.restore_struct(arena, s, m, p)?;
+			(*(p as *mut ChainHeader)).pNext = head as _;
+			head = p;
+		}
+		Ok(head)
+	}
+	unsafe fn restore_input<T: Traced>(&self, arena: &mut Arena, v: &Value) -> RestoreResult<*const T>
+	{
+		if *v == Value::Null { return Ok(null()); }
+		let p = arena.alloc(size_of::<T>());
+		self.restore_struct(arena, T::SCHEMA, v, p).map(|_| p as _)
+	}
+	unsafe fn restore_structs<T: Traced>(&self, arena: &mut Arena, v: &Value) -> RestoreResult<*const T>
+	{
+		self.restore_array(arena, Elem::Struct(T::SCHEMA), v).map(|p| p as _)
+	}
+	unsafe fn restore_handles<T: TraceHandle>(&self, arena: &mut Arena, v: &Value) -> RestoreResult<*const T>
+	{
+		match *v
+		{
+			Value::Null => Ok(null()),
+			Value::Array(ref vs) =>
+			{
+				let p = alloc_out::<T>(arena, vs.len());
+				for (i, x) in vs.iter().enumerate() { *p.add(i) = T::from_id(self.translate(x)?); }
+				Ok(p)
+			},
+			_ => Err(ReplayErrorKind::Malformed)
+		}
+	}
+}
+
+/// Wraps a resolver and records every call into a trace. Clones share the same output
+pub struct Recorder<R: ResolverInterface>(Arc<RecorderShared<R>>);
+impl<R: ResolverInterface> Clone for Recorder<R> { fn clone(&self) -> Self { Recorder(self.0.clone()) } }
+struct RecorderShared<R> { inner: R, state: Mutex<RecorderState> }
+struct Mapping { address: usize, offset: VkDeviceSize, size: usize, shadow: Option<Vec<u8>> }
+struct RecorderState
+{
+	out: Box<dyn Write + Send>, error: Option<io::Error>,
+	allocation_sizes: HashMap<u64, VkDeviceSize>,
+	/// ordered to keep the traces reproducible
+	mappings: BTreeMap<u64, Mapping>
+}
+impl<R: ResolverInterface> Recorder<R>
+{
+	/// Record the calls into `out`. Write errors are reported by `flush`
+	pub fn new<W: Write + Send + 'static>(inner: R, out: W) -> Self
+	{
+		let mut state = RecorderState
+		{
+			out: Box::new(out), error: None, allocation_sizes: HashMap::new(), mappings: BTreeMap::new()
+		};
+		state.error = write_header(&mut state.out).err();
+		Recorder(Arc::new(RecorderShared { inner, state: Mutex::new(state) }))
+	}
+	/// Record the calls into a file
+	pub fn create<P: AsRef<Path>>(inner: R, path: P) -> io::Result<Self>
+	{
+		File::create(path).map(|f| Self::new(inner, BufWriter::new(f)))
+	}
+	pub fn inner(&self) -> &R { &self.0.inner }
+	/// Flush the output, reporting the first write error occured so far.
+	/// The output is also flushed on `vkDestroyInstance`
+	pub fn flush(&self) -> io::Result<()>
+	{
+		let mut s = self.state();
+		if let Some(e) = s.error.take() { return Err(e); }
+		s.out.flush()
+	}
+
+	fn state(&self) -> MutexGuard<'_, RecorderState> { self.0.state.lock().unwrap_or_else(|e| e.into_inner()) }
+	fn before(&self, name: &str)
+	{
+		match name
+		{
+			"vkQueueSubmit" | "vkQueueBindSparse" | "vkQueuePresentKHR" | "vkFlushMappedMemoryRanges" |
+			"vkUnmapMemory" | "vkFreeMemory" => unsafe { self.state().capture_mapped() },
+			n if is_host_sync(n) => unsafe { self.state().capture_mapped() },
+			_ => ()
+		}
+	}
+	fn after(&self, call: Call, succeeded: bool)
+	{
+		let mut s = self.state();
+		// created handles are recorded as arrays
+		let memory = call.arg("memory").and_then(Value::as_u64).or_else(|| match call.arg("pMemory")
+		{
+			Some(Value::Array(hs)) => hs.first().and_then(Value::as_u64), _ => None
+		});
+		match (&call.name[..], memory)
+		{
+			("vkAllocateMemory", Some(m)) if succeeded =>
+			{
+				let size = call.arg("pAllocateInfo").and_then(|i| i.member("allocationSize")).and_then(Value::as_u64);
+				s.allocation_sizes.insert(m, size.unwrap_or(0));
+			},
+			("vkMapMemory", Some(m)) if succeeded => if let Some(&Value::Opaque(address)) = call.arg("ppData")
+			{
+				let offset = call.arg("offset").and_then(Value::as_u64).unwrap_or(0);
+				let size = match call.arg("size").and_then(Value::as_u64).unwrap_or(0)
+				{
+					VK_WHOLE_SIZE => s.allocation_sizes.get(&m).map_or(0, |&a| a.saturating_sub(offset)),
+					n => n
+				};
+				s.mappings.insert(m, Mapping { address: address as _, offset, size: size as _, shadow: None });
+			},
+			// the device may have written the mapped memory; it must not be recorded as a host write
+			(n, _) if succeeded && is_host_sync(n) => unsafe { s.sync_shadows() },
+			("vkUnmapMemory", Some(m)) => { s.mappings.remove(&m); },
+			("vkFreeMemory", Some(m)) => { s.mappings.remove(&m); s.allocation_sizes.remove(&m); },
+			_ => ()
+		}
+		let destroying_instance = call.name == "vkDestroyInstance";
+		s.write(&Record::Call(call));
+		if destroying_instance && s.error.is_none() { s.error = s.out.flush().err(); }
+	}
+}
+impl RecorderState
+{
+	fn write(&mut self, r: &Record)
+	{
+		if self.error.is_none() { self.error = write_record(&mut self.out, r).err(); }
+	}
+	/// Record the ranges of the mapped memories modified since the last capture
+	unsafe fn capture_mapped(&mut self)
+	{
+		let mut writes = Vec::new();
+		for (&memory, m) in &mut self.mappings
+		{
+			let current = slice::from_raw_parts(m.address as *const u8, m.size);
+			let range = match m.shadow
+			{
+				None => Some((0, m.size)),
+				Some(ref prev) => prev.iter().zip(current).position(|(a, b)| a != b)
+					.map(|first| (first, m.size - prev.iter().zip(current).rev().position(|(a, b)| a != b).unwrap_or(0)))
+			};
+			if let Some((first, end)) = range
+			{
+				if first < end
+				{
+					writes.push(Record::MemoryWrite { memory, offset: m.offset + first as VkDeviceSize, data: current[first .. end].to_vec() });
+				}
+			}
+			match m.shadow
+			{
+				Some(ref mut s) => s.copy_from_slice(current),
+				None => m.shadow = Some(current.to_vec())
+			}
+		}
+		for w in &writes { self.write(w); }
+	}
+	unsafe fn sync_shadows(&mut self)
+	{
+		for m in self.mappings.values_mut()
+		{
+			if let Some(ref mut s) = m.shadow { s.copy_from_slice(slice::from_raw_parts(m.address as *const u8, m.size)); }
+		}
+	}
+}
+/// Calls after which the results of the device work become visible to the host
+fn is_host_sync(name: &str) -> bool
+{
+	matches!(name, "vkWaitForFences" | "vkGetFenceStatus" | "vkQueueWaitIdle" | "vkDeviceWaitIdle" | "vkInvalidateMappedMemoryRanges")
+}
+
+macro_rules! TraceArg
+{
+	// value recorded before the call
+	(capture handle, $a: ident) => { Value::Handle(TraceHandle::id($a)) };
+	(capture scalar, $a: ident) => { scalar_value(&$a) };
+	(capture fixed, $a: ident) => { Value::Array($a.iter().map(scalar_value).collect()) };
+	(capture str, $a: ident) => { capture_str($a) };
+	(capture allocator, $a: ident) => { opaque_value($a as _) };
+	(capture opaque, $a: ident) => { opaque_value($a as _) };
+	(capture input, $a: ident) => { capture_input($a) };
+	(capture raw, $a: ident) => { capture_raw($a, false) };
+	(capture (structs $n: ident), $a: ident) => { capture_structs($a, $n as _) };
+	(capture (handles $n: ident), $a: ident) => { capture_handles($a, $n as _) };
+	(capture (scalars $n: ident), $a: ident) => { capture_scalars($a, $n as _) };
+	(capture (bytes $n: ident), $a: ident) => { capture_bytes($a as _, $n as _) };
+	(capture $k: tt, $a: ident) => { Value::Null };
+
+	// value recorded after the call
+	(output count, $a: ident, $ok: ident, $slot: ident) => { $slot.1 = if $a.is_null() { Value::Null } else { scalar_value(&*$a) }; };
+	(output out_handle, $a: ident, $ok: ident, $slot: ident) => { if $ok { $slot.1 = capture_handles($a as *const _, 1); } };
+	(output (out_handles $n: tt), $a: ident, $ok: ident, $slot: ident) => { if $ok { $slot.1 = capture_handles($a as *const _, $n as _); } };
+	(output (enum_handles $c: ident), $a: ident, $ok: ident, $slot: ident) => { if $ok { $slot.1 = capture_handles($a as *const _, *$c as _); } };
+	(output (enum_raw $c: ident), $a: ident, $ok: ident, $slot: ident) => { if $ok { $slot.1 = capture_raw_array($a as *const _, *$c as _, false); } };
+	(output (enum_chain $c: ident), $a: ident, $ok: ident, $slot: ident) => { if $ok { $slot.1 = capture_raw_array($a as *const _, *$c as _, true); } };
+	(output (enum_bytes $c: ident), $a: ident, $ok: ident, $slot: ident) => { if $ok { $slot.1 = capture_bytes($a as _, *$c as _); } };
+	(output out, $a: ident, $ok: ident, $slot: ident) => { if $ok { $slot.1 = capture_raw($a as *const _, false); } };
+	(output outchain, $a: ident, $ok: ident, $slot: ident) => { if $ok { $slot.1 = capture_raw($a as *const _, true); } };
+	(output out_scalar, $a: ident, $ok: ident, $slot: ident) => { if $ok && !$a.is_null() { $slot.1 = scalar_value(&*$a); } };
+	(output (out_bytes $n: ident), $a: ident, $ok: ident, $slot: ident) => { if $ok { $slot.1 = capture_bytes($a as _, $n as _); } };
+	(output mapped, $a: ident, $ok: ident, $slot: ident) => { if $ok { $slot.1 = opaque_value(*$a as _); } };
+	(output $k: tt, $a: ident, $ok: ident, $slot: ident) => { let _ = $slot; };
+
+	// argument rebuilt from the recorded value
+	(restore handle, $s: ident, $ar: ident, $v: ident, $a: ident) => { $s.translate($v).map(TraceHandle::from_id) };
+	(restore scalar, $s: ident, $ar: ident, $v: ident, $a: ident) => { restore_scalar($v) };
+	(restore fixed, $s: ident, $ar: ident, $v: ident, $a: ident) => { restore_fixed($v) };
+	(restore str, $s: ident, $ar: ident, $v: ident, $a: ident) => { restore_str(&mut $ar, $v) };
+	(restore allocator, $s: ident, $ar: ident, $v: ident, $a: ident) => { Ok(null()) };
+	(restore opaque, $s: ident, $ar: ident, $v: ident, $a: ident) => { restore_opaque($v, stringify!($a)) };
+	(restore input, $s: ident, $ar: ident, $v: ident, $a: ident) => { $s.restore_input(&mut $ar, $v) };
+	(restore raw, $s: ident, $ar: ident, $v: ident, $a: ident) => { restore_raw(&mut $ar, $v) };
+	(restore (structs $n: ident), $s: ident, $ar: ident, $v: ident, $a: ident) => { $s.restore_structs(&mut $ar, $v) };
+	(restore (handles $n: ident), $s: ident, $ar: ident, $v: ident, $a: ident) => { $s.restore_handles(&mut $ar, $v) };
+	(restore (scalars $n: ident), $s: ident, $ar: ident, $v: ident, $a: ident) => { restore_scalars(&mut $ar, $v) };
+	(restore (bytes $n: ident), $s: ident, $ar: ident, $v: ident, $a: ident) => { restore_bytes(&mut $ar, $v) };
+	(restore count, $s: ident, $ar: ident, $v: ident, $a: ident) => { restore_count(&mut $ar, $v) };
+	(restore (out_handles $n: tt), $s: ident, $ar: ident, $v: ident, $a: ident) => { Ok(alloc_out(&mut $ar, $n as _)) };
+	(restore (out_bytes $n: ident), $s: ident, $ar: ident, $v: ident, $a: ident) => { Ok(alloc_out(&mut $ar, $n as _)) };
+	(restore (enum_chain $c: ident), $s: ident, $ar: ident, $v: ident, $a: ident) =>
+	{
+		if *$v == Value::Null { Ok(null_mut()) } else { restore_out_chains(&mut $ar, $v, *$c as _) }
+	};
+	(restore ($k: ident $c: ident), $s: ident, $ar: ident, $v: ident, $a: ident) =>
+	{
+		// enum_handles, enum_raw, enum_bytes
+		Ok(if *$v == Value::Null { null_mut() } else { alloc_out(&mut $ar, *$c as _) })
+	};
+	(restore outchain, $s: ident, $ar: ident, $v: ident, $a: ident) => { restore_out_chains(&mut $ar, $v, 1) };
+	(restore $k: tt, $s: ident, $ar: ident, $v: ident, $a: ident) => { Ok(alloc_out(&mut $ar, 1)) };
+
+	// outputs of the replayed call
+	(remap out_handle, $s: ident, $v: ident, $a: ident) => { $s.remap($v, $a, 1) };
+	(remap (out_handles $n: tt), $s: ident, $v: ident, $a: ident) => { $s.remap($v, $a, $n as _) };
+	(remap (enum_handles $c: ident), $s: ident, $v: ident, $a: ident) => { if !$a.is_null() { $s.remap($v, $a, *$c as _) } };
+	(remap mapped, $s: ident, $v: ident, $a: ident) => { $s.last_mapped = *$a as usize };
+	(remap $k: tt, $s: ident, $v: ident, $a: ident) => { let _ = ($v, $a); };
+
+	(is_output_handle out_handle) => { true };
+	(is_output_handle $k: tt) => { false };
+}
+/// Generates the recording wrapper and the replaying dispatcher.
+/// `name: type => kind` tells how each argument is captured and rebuilt
+macro_rules! TraceAPIs
+{
+	($($(#[$attr: meta])* fn $name: ident = $vk: ident ($($an: ident : $at: ty => $k: tt),*) $(-> $rt: ty)*;)*) =>
+	{
+		impl<R: ResolverInterface> ResolverInterface for Recorder<R>
+		{
+			fn provides(&self, name: &str) -> bool { self.0.inner.provides(name) }
+			$(
+				$(#[$attr])*
+				unsafe fn $name(&self, $($an: $at),*) $(-> $rt)*
+				{
+					self.before(stringify!($vk));
+					let mut args: Vec<(String, Value)> = vec![$((stringify!($an).to_owned(), TraceArg!(capture $k, $an))),*];
+					let result = self.0.inner.$name($($an),*);
+					let succeeded = TraceResult::succeeded(&result);
+					{
+						let mut slots = args.iter_mut();
+						$(let slot = slots.next().unwrap(); TraceArg!(output $k, $an, succeeded, slot);)*
+					}
+					self.after(Call { name: stringify!($vk).to_owned(), args, result: TraceResult::value(&result) }, succeeded);
+					result
+				}
+			)*
+		}
+		impl<R: ResolverInterface> Replayer<R>
+		{
+			/// Replay a call. Returns `None` if the call is skipped
+			// not every function needs the arena or the recorded values after restoring
+			#[allow(unused_mut, unused_variables)]
+			fn dispatch(&mut self, call: &Call) -> RestoreResult<Option<Value>>
+			{
+				let replayed = match &call.name[..]
+				{
+					$(
+						$(#[$attr])*
+						stringify!($vk) => unsafe
+						{
+							let outputs: &[bool] = &[$(TraceArg!(is_output_handle $k)),*];
+							if call.args.len() != outputs.len() { return Err(ReplayErrorKind::Malformed); }
+							if self.substituted(call, outputs) { return Ok(None); }
+							let mut arena = Arena(Vec::new());
+							let mut recorded = call.args.iter().map(|(_, v)| v);
+							$(
+								let v = recorded.next().unwrap();
+								let $an: $at = TraceArg!(restore $k, self, arena, v, $an)?;
+							)*
+							let result = self.resolver.$name($($an),*);
+							let mut recorded = call.args.iter().map(|(_, v)| v);
+							$(let v = recorded.next().unwrap(); TraceArg!(remap $k, self, v, $an);)*
+							Some(TraceResult::value(&result))
+						},
+					)*
+					_ => return Err(ReplayErrorKind::UnknownCall)
+				};
+				self.after_call(call, &replayed);
+				Ok(replayed)
+			}
+		}
+	}
+}
+
+TraceAPIs!
+{
+	fn create_instance = vkCreateInstance(pCreateInfo: *const VkInstanceCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pInstance: *mut VkInstance => out_handle) -> VkResult;
+	fn destroy_instance = vkDestroyInstance(instance: VkInstance => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn enumerate_physical_devices = vkEnumeratePhysicalDevices(instance: VkInstance => handle, pPhysicalDeviceCount: *mut u32 => count, pPhysicalDevices: *mut VkPhysicalDevice => (enum_handles pPhysicalDeviceCount)) -> VkResult;
+	fn get_physical_device_features = vkGetPhysicalDeviceFeatures(physicalDevice: VkPhysicalDevice => handle, pFeatures: *mut VkPhysicalDeviceFeatures => out);
+	fn get_physical_device_format_properties = vkGetPhysicalDeviceFormatProperties(physicalDevice: VkPhysicalDevice => handle, format: VkFormat => scalar, pFormatProperties: *mut VkFormatProperties => out);
+	fn get_physical_device_image_format_properties = vkGetPhysicalDeviceImageFormatProperties(physicalDevice: VkPhysicalDevice => handle, format: VkFormat => scalar, itype: VkImageType => scalar, tiling: VkImageTiling => scalar, usage: VkImageUsageFlags => scalar, flags: VkImageCreateFlags => scalar, pImageFormatProperties: *mut VkImageFormatProperties => out) -> VkResult;
+	fn get_physical_device_properties = vkGetPhysicalDeviceProperties(physicalDevice: VkPhysicalDevice => handle, pProperties: *mut VkPhysicalDeviceProperties => out);
+	fn get_physical_device_queue_family_properties = vkGetPhysicalDeviceQueueFamilyProperties(physicalDevice: VkPhysicalDevice => handle, pQueueFamilyPropertyCount: *mut u32 => count, pQueueFamilyProperties: *mut VkQueueFamilyProperties => (enum_raw pQueueFamilyPropertyCount));
+	fn get_physical_device_memory_properties = vkGetPhysicalDeviceMemoryProperties(physicalDevice: VkPhysicalDevice => handle, pMemoryProperties: *mut VkPhysicalDeviceMemoryProperties => out);
+	fn get_instance_proc_addr = vkGetInstanceProcAddr(instance: VkInstance => handle, pName: *const c_char => str) -> Option<PFN_vkVoidFunction>;
+	fn get_device_proc_addr = vkGetDeviceProcAddr(device: VkDevice => handle, pName: *const c_char => str) -> Option<PFN_vkVoidFunction>;
+	fn create_device = vkCreateDevice(physicalDevice: VkPhysicalDevice => handle, pCreateInfo: *const VkDeviceCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pDevice: *mut VkDevice => out_handle) -> VkResult;
+	fn destroy_device = vkDestroyDevice(device: VkDevice => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn enumerate_instance_extension_properties = vkEnumerateInstanceExtensionProperties(pLayerName: *const c_char => str, pPropertyCount: *mut u32 => count, pProperties: *mut VkExtensionProperties => (enum_raw pPropertyCount)) -> VkResult;
+	fn enumerate_device_extension_properties = vkEnumerateDeviceExtensionProperties(physicalDevice: VkPhysicalDevice => handle, pLayerName: *const c_char => str, pPropertyCount: *mut u32 => count, pProperties: *mut VkExtensionProperties => (enum_raw pPropertyCount)) -> VkResult;
+	fn enumerate_instance_layer_properties = vkEnumerateInstanceLayerProperties(pPropertyCount: *mut u32 => count, pProperties: *mut VkLayerProperties => (enum_raw pPropertyCount)) -> VkResult;
+	fn enumerate_device_layer_properties = vkEnumerateDeviceLayerProperties(physicalDevice: VkPhysicalDevice => handle, pPropertyCount: *mut u32 => count, pProperties: *mut VkLayerProperties => (enum_raw pPropertyCount)) -> VkResult;
+	fn get_device_queue = vkGetDeviceQueue(device: VkDevice => handle, queueFamilyIndex: u32 => scalar, queueIndex: u32 => scalar, pQueue: *mut VkQueue => out_handle);
+	fn queue_submit = vkQueueSubmit(queue: VkQueue => handle, submitCount: u32 => scalar, pSubmits: *const VkSubmitInfo => (structs submitCount), fence: VkFence => handle) -> VkResult;
+	fn queue_wait_idle = vkQueueWaitIdle(queue: VkQueue => handle) -> VkResult;
+	fn device_wait_idle = vkDeviceWaitIdle(device: VkDevice => handle) -> VkResult;
+	fn allocate_memory = vkAllocateMemory(device: VkDevice => handle, pAllocateInfo: *const VkMemoryAllocateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pMemory: *mut VkDeviceMemory => out_handle) -> VkResult;
+	fn free_memory = vkFreeMemory(device: VkDevice => handle, memory: VkDeviceMemory => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn map_memory = vkMapMemory(device: VkDevice => handle, memory: VkDeviceMemory => handle, offset: VkDeviceSize => scalar, size: VkDeviceSize => scalar, flags: VkMemoryMapFlags => scalar, ppData: *mut *mut c_void => mapped) -> VkResult;
+	fn unmap_memory = vkUnmapMemory(device: VkDevice => handle, memory: VkDeviceMemory => handle);
+	fn flush_mapped_memory_ranges = vkFlushMappedMemoryRanges(device: VkDevice => handle, memoryRangeCount: u32 => scalar, pMemoryRanges: *const VkMappedMemoryRange => (structs memoryRangeCount)) -> VkResult;
+	fn invalidate_mapped_memory_ranges = vkInvalidateMappedMemoryRanges(device: VkDevice => handle, memoryRangeCount: u32 => scalar, pMemoryRanges: *const VkMappedMemoryRange => (structs memoryRangeCount)) -> VkResult;
+	fn get_device_memory_commitment = vkGetDeviceMemoryCommitment(device: VkDevice => handle, memory: VkDeviceMemory => handle, pCommittedMemoryInBytes: *mut VkDeviceSize => out_scalar);
+	fn bind_buffer_memory = vkBindBufferMemory(device: VkDevice => handle, buffer: VkBuffer => handle, memory: VkDeviceMemory => handle, memoryOffset: VkDeviceSize => scalar) -> VkResult;
+	fn bind_image_memory = vkBindImageMemory(device: VkDevice => handle, image: VkImage => handle, memory: VkDeviceMemory => handle, memoryOffset: VkDeviceSize => scalar) -> VkResult;
+	fn get_buffer_memory_requirements = vkGetBufferMemoryRequirements(device: VkDevice => handle, buffer: VkBuffer => handle, pMemoryRequirements: *mut VkMemoryRequirements => out);
+	fn get_image_memory_requirements = vkGetImageMemoryRequirements(device: VkDevice => handle, image: VkImage => handle, pMemoryRequirements: *mut VkMemoryRequirements => out);
+	fn get_image_sparse_memory_requirements = vkGetImageSparseMemoryRequirements(device: VkDevice => handle, image: VkImage => handle, pSparseMemoryRequirementCount: *mut u32 => count, pSparseMemoryRequirements: *mut VkSparseImageMemoryRequirements => (enum_raw pSparseMemoryRequirementCount));
+	fn get_physical_device_sparse_image_format_properties = vkGetPhysicalDeviceSparseImageFormatProperties(physicalDevice: VkPhysicalDevice => handle, format: VkFormat => scalar, _type: VkImageType => scalar, samples: VkSampleCountFlags => scalar, usage: VkImageUsageFlags => scalar, tiling: VkImageTiling => scalar, pPropertyCount: *mut u32 => count, pProperties: *mut VkSparseImageFormatProperties => (enum_raw pPropertyCount));
+	fn queue_bind_sparse = vkQueueBindSparse(queue: VkQueue => handle, bindInfoCount: u32 => scalar, pBindInfo: *const VkBindSparseInfo => (structs bindInfoCount), fence: VkFence => handle) -> VkResult;
+	fn create_fence = vkCreateFence(device: VkDevice => handle, pCreateInfo: *const VkFenceCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pFence: *mut VkFence => out_handle) -> VkResult;
+	fn destroy_fence = vkDestroyFence(device: VkDevice => handle, fence: VkFence => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn reset_fences = vkResetFences(device: VkDevice => handle, fenceCount: u32 => scalar, pFences: *const VkFence => (handles fenceCount)) -> VkResult;
+	fn get_fence_status = vkGetFenceStatus(device: VkDevice => handle, fence: VkFence => handle) -> VkResult;
+	fn wait_for_fences = vkWaitForFences(device: VkDevice => handle, fenceCount: u32 => scalar, pFences: *const VkFence => (handles fenceCount), waitAll: VkBool32 => scalar, timeout: u64 => scalar) -> VkResult;
+	fn create_semaphore = vkCreateSemaphore(device: VkDevice => handle, pCreateInfo: *const VkSemaphoreCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pSemaphore: *mut VkSemaphore => out_handle) -> VkResult;
+	fn destroy_semaphore = vkDestroySemaphore(device: VkDevice => handle, semaphore: VkSemaphore => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn create_event = vkCreateEvent(device: VkDevice => handle, pCreateInfo: *const VkEventCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pEvent: *mut VkEvent => out_handle) -> VkResult;
+	fn destroy_event = vkDestroyEvent(device: VkDevice => handle, event: VkEvent => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn get_event_status = vkGetEventStatus(device: VkDevice => handle, event: VkEvent => handle) -> VkResult;
+	fn set_event = vkSetEvent(device: VkDevice => handle, event: VkEvent => handle) -> VkResult;
+	fn reset_event = vkResetEvent(device: VkDevice => handle, event: VkEvent => handle) -> VkResult;
+	fn create_query_pool = vkCreateQueryPool(device: VkDevice => handle, pCreateInfo: *const VkQueryPoolCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pQueryPool: *mut VkQueryPool => out_handle) -> VkResult;
+	fn destroy_query_pool = vkDestroyQueryPool(device: VkDevice => handle, queryPool: VkQueryPool => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn get_query_pool_results = vkGetQueryPoolResults(device: VkDevice => handle, queryPool: VkQueryPool => handle, firstQuery: u32 => scalar, queryCount: u32 => scalar, dataSize: size_t => scalar, pData: *mut c_void => (out_bytes dataSize), stride: VkDeviceSize => scalar, flags: VkQueryResultFlags => scalar) -> VkResult;
+	fn create_buffer = vkCreateBuffer(device: VkDevice => handle, pCreateInfo: *const VkBufferCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pBuffer: *mut VkBuffer => out_handle) -> VkResult;
+	fn destroy_buffer = vkDestroyBuffer(device: VkDevice => handle, buffer: VkBuffer => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn create_buffer_view = vkCreateBufferView(device: VkDevice => handle, pCreateInfo: *const VkBufferViewCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pView: *mut VkBufferView => out_handle) -> VkResult;
+	fn destroy_buffer_view = vkDestroyBufferView(device: VkDevice => handle, bufferView: VkBufferView => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn create_image = vkCreateImage(device: VkDevice => handle, pCreateInfo: *const VkImageCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pImage: *mut VkImage => out_handle) -> VkResult;
+	fn destroy_image = vkDestroyImage(device: VkDevice => handle, image: VkImage => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn get_image_subresource_layout = vkGetImageSubresourceLayout(device: VkDevice => handle, image: VkImage => handle, pSubresource: *const VkImageSubresource => input, pLayout: *mut VkSubresourceLayout => out);
+	fn create_image_view = vkCreateImageView(device: VkDevice => handle, pCreateInfo: *const VkImageViewCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pView: *mut VkImageView => out_handle) -> VkResult;
+	fn destroy_image_view = vkDestroyImageView(device: VkDevice => handle, imageView: VkImageView => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn create_shader_module = vkCreateShaderModule(device: VkDevice => handle, pCreateInfo: *const VkShaderModuleCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pShaderModule: *mut VkShaderModule => out_handle) -> VkResult;
+	fn destroy_shader_module = vkDestroyShaderModule(device: VkDevice => handle, shaderModule: VkShaderModule => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn create_pipeline_cache = vkCreatePipelineCache(device: VkDevice => handle, pCreateInfo: *const VkPipelineCacheCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pPipelineCache: *mut VkPipelineCache => out_handle) -> VkResult;
+	fn destroy_pipeline_cache = vkDestroyPipelineCache(device: VkDevice => handle, pipelineCache: VkPipelineCache => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn get_pipeline_cache_data = vkGetPipelineCacheData(device: VkDevice => handle, pipelineCache: VkPipelineCache => handle, pDataSize: *mut size_t => count, pData: *mut c_void => (enum_bytes pDataSize)) -> VkResult;
+	fn merge_pipeline_caches = vkMergePipelineCaches(device: VkDevice => handle, dstCache: VkPipelineCache => handle, srcCacheCount: u32 => scalar, pSrcCaches: *const VkPipelineCache => (handles srcCacheCount)) -> VkResult;
+	fn create_graphics_pipelines = vkCreateGraphicsPipelines(device: VkDevice => handle, pipelineCache: VkPipelineCache => handle, createInfoCount: u32 => scalar, pCreateInfos: *const VkGraphicsPipelineCreateInfo => (structs createInfoCount), pAllocator: *const VkAllocationCallbacks => allocator, pPipelines: *mut VkPipeline => (out_handles createInfoCount)) -> VkResult;
+	fn create_compute_pipelines = vkCreateComputePipelines(device: VkDevice => handle, pipelineCache: VkPipelineCache => handle, createInfoCount: u32 => scalar, pCreateInfos: *const VkComputePipelineCreateInfo => (structs createInfoCount), pAllocator: *const VkAllocationCallbacks => allocator, pPipelines: *mut VkPipeline => (out_handles createInfoCount)) -> VkResult;
+	fn destroy_pipeline = vkDestroyPipeline(device: VkDevice => handle, pipeline: VkPipeline => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn create_pipeline_layout = vkCreatePipelineLayout(device: VkDevice => handle, pCreateInfo: *const VkPipelineLayoutCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pPipelineLayout: *mut VkPipelineLayout => out_handle) -> VkResult;
+	fn destroy_pipeline_layout = vkDestroyPipelineLayout(device: VkDevice => handle, pipelineLayout: VkPipelineLayout => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn create_sampler = vkCreateSampler(device: VkDevice => handle, pCreateInfo: *const VkSamplerCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pSampler: *mut VkSampler => out_handle) -> VkResult;
+	fn destroy_sampler = vkDestroySampler(device: VkDevice => handle, sampler: VkSampler => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn create_descriptor_set_layout = vkCreateDescriptorSetLayout(device: VkDevice => handle, pCreateInfo: *const VkDescriptorSetLayoutCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pSetLayout: *mut VkDescriptorSetLayout => out_handle) -> VkResult;
+	fn destroy_descriptor_set_layout = vkDestroyDescriptorSetLayout(device: VkDevice => handle, descriptorSetLayout: VkDescriptorSetLayout => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn create_descriptor_pool = vkCreateDescriptorPool(device: VkDevice => handle, pCreateInfo: *const VkDescriptorPoolCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pDescriptorPool: *mut VkDescriptorPool => out_handle) -> VkResult;
+	fn destroy_descriptor_pool = vkDestroyDescriptorPool(device: VkDevice => handle, descriptorPool: VkDescriptorPool => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn reset_descriptor_pool = vkResetDescriptorPool(device: VkDevice => handle, descriptorPool: VkDescriptorPool => handle, flags: VkDescriptorPoolResetFlags => scalar) -> VkResult;
+	fn allocate_descriptor_sets = vkAllocateDescriptorSets(device: VkDevice => handle, pAllocateInfo: *const VkDescriptorSetAllocateInfo => input, pDescriptorSets: *mut VkDescriptorSet => (out_handles { (*pAllocateInfo).descriptorSetCount })) -> VkResult;
+	fn free_descriptor_sets = vkFreeDescriptorSets(device: VkDevice => handle, descriptorPool: VkDescriptorPool => handle, descriptorSetCount: u32 => scalar, pDescriptorSets: *const VkDescriptorSet => (handles descriptorSetCount)) -> VkResult;
+	fn update_descriptor_sets = vkUpdateDescriptorSets(device: VkDevice => handle, descriptorWriteCount: u32 => scalar, pDescriptorWrites: *const VkWriteDescriptorSet => (structs descriptorWriteCount), descriptorCopyCount: u32 => scalar, pDescriptorCopies: *const VkCopyDescriptorSet => (structs descriptorCopyCount));
+	fn create_framebuffer = vkCreateFramebuffer(device: VkDevice => handle, pCreateInfo: *const VkFramebufferCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pFramebuffer: *mut VkFramebuffer => out_handle) -> VkResult;
+	fn destroy_framebuffer = vkDestroyFramebuffer(device: VkDevice => handle, framebuffer: VkFramebuffer => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn create_render_pass = vkCreateRenderPass(device: VkDevice => handle, pCreateInfo: *const VkRenderPassCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pRenderPass: *mut VkRenderPass => out_handle) -> VkResult;
+	fn destroy_render_pass = vkDestroyRenderPass(device: VkDevice => handle, renderPass: VkRenderPass => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn get_render_area_granularity = vkGetRenderAreaGranularity(device: VkDevice => handle, renderPass: VkRenderPass => handle, pGranularity: *mut VkExtent2D => out);
+	fn create_command_pool = vkCreateCommandPool(device: VkDevice => handle, pCreateInfo: *const VkCommandPoolCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pCommandPool: *mut VkCommandPool => out_handle) -> VkResult;
+	fn destroy_command_pool = vkDestroyCommandPool(device: VkDevice => handle, commandPool: VkCommandPool => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn reset_command_pool = vkResetCommandPool(device: VkDevice => handle, commandPool: VkCommandPool => handle, flags: VkCommandPoolResetFlags => scalar) -> VkResult;
+	fn allocate_command_buffers = vkAllocateCommandBuffers(device: VkDevice => handle, pAllocateInfo: *const VkCommandBufferAllocateInfo => input, pCommandBuffers: *mut VkCommandBuffer => (out_handles { (*pAllocateInfo).commandBufferCount })) -> VkResult;
+	fn free_command_buffers = vkFreeCommandBuffers(device: VkDevice => handle, commandPool: VkCommandPool => handle, commandBufferCount: u32 => scalar, pCommandBuffers: *const VkCommandBuffer => (handles commandBufferCount));
+	fn begin_command_buffer = vkBeginCommandBuffer(commandBuffer: VkCommandBuffer => handle, pBeginInfo: *const VkCommandBufferBeginInfo => input) -> VkResult;
+	fn end_command_buffer = vkEndCommandBuffer(commandBuffer: VkCommandBuffer => handle) -> VkResult;
+	fn reset_command_buffer = vkResetCommandBuffer(commandBuffer: VkCommandBuffer => handle, flags: VkCommandBufferResetFlags => scalar) -> VkResult;
+	fn cmd_bind_pipeline = vkCmdBindPipeline(commandBuffer: VkCommandBuffer => handle, pipelineBindPoint: VkPipelineBindPoint => scalar, pipeline: VkPipeline => handle);
+	fn cmd_set_viewport = vkCmdSetViewport(commandBuffer: VkCommandBuffer => handle, firstViewport: u32 => scalar, viewportCount: u32 => scalar, pViewports: *const VkViewport => (structs viewportCount));
+	fn cmd_set_scissor = vkCmdSetScissor(commandBuffer: VkCommandBuffer => handle, firstScissor: u32 => scalar, scissorCount: u32 => scalar, pScissors: *const VkRect2D => (structs scissorCount));
+	fn cmd_set_line_width = vkCmdSetLineWidth(commandBuffer: VkCommandBuffer => handle, lineWidth: c_float => scalar);
+	fn cmd_set_depth_bias = vkCmdSetDepthBias(commandBuffer: VkCommandBuffer => handle, depthBiasConstantFactor: c_float => scalar, depthBiasClamp: c_float => scalar, depthBiasSlopeFactor: c_float => scalar);
+	fn cmd_set_blend_constants = vkCmdSetBlendConstants(commandBuffer: VkCommandBuffer => handle, blendConstants: [c_float; 4] => fixed);
+	fn cmd_set_depth_bounds = vkCmdSetDepthBounds(commandBuffer: VkCommandBuffer => handle, minDepthBounds: c_float => scalar, maxDepthBounds: c_float => scalar);
+	fn cmd_set_stencil_compare_mask = vkCmdSetStencilCompareMask(commandBuffer: VkCommandBuffer => handle, faceMask: VkStencilFaceFlags => scalar, compareMask: u32 => scalar);
+	fn cmd_set_stencil_write_mask = vkCmdSetStencilWriteMask(commandBuffer: VkCommandBuffer => handle, faceMask: VkStencilFaceFlags => scalar, writeMask: u32 => scalar);
+	fn cmd_set_stencil_reference = vkCmdSetStencilReference(commandBuffer: VkCommandBuffer => handle, faceMask: VkStencilFaceFlags => scalar, reference: u32 => scalar);
+	fn cmd_bind_descriptor_sets = vkCmdBindDescriptorSets(commandBuffer: VkCommandBuffer => handle, pipelineBindPoint: VkPipelineBindPoint => scalar, layout: VkPipelineLayout => handle, firstSet: u32 => scalar, descriptorSetCount: u32 => scalar, pDescriptorSets: *const VkDescriptorSet => (handles descriptorSetCount), dynamicOffsetCount: u32 => scalar, pDynamicOffsets: *const u32 => (scalars dynamicOffsetCount));
+	fn cmd_bind_index_buffer = vkCmdBindIndexBuffer(commandBuffer: VkCommandBuffer => handle, buffer: VkBuffer => handle, offset: VkDeviceSize => scalar, indexType: VkIndexType => scalar);
+	fn cmd_bind_vertex_buffers = vkCmdBindVertexBuffers(commandBuffer: VkCommandBuffer => handle, firstBinding: u32 => scalar, bindingCount: u32 => scalar, pBuffers: *const VkBuffer => (handles bindingCount), pOffsets: *const VkDeviceSize => (scalars bindingCount));
+	fn cmd_draw = vkCmdDraw(commandBuffer: VkCommandBuffer => handle, vertexCount: u32 => scalar, instanceCount: u32 => scalar, firstVertex: u32 => scalar, firstInstance: u32 => scalar);
+	fn cmd_draw_indexed = vkCmdDrawIndexed(commandBuffer: VkCommandBuffer => handle, indexCount: u32 => scalar, instanceCount: u32 => scalar, firstIndex: u32 => scalar, vertexOffset: i32 => scalar, firstInstance: u32 => scalar);
+	fn cmd_draw_indirect = vkCmdDrawIndirect(commandBuffer: VkCommandBuffer => handle, buffer: VkBuffer => handle, offset: VkDeviceSize => scalar, drawCount: u32 => scalar, stride: u32 => scalar);
+	fn cmd_draw_indexed_indirect = vkCmdDrawIndexedIndirect(commandBuffer: VkCommandBuffer => handle, buffer: VkBuffer => handle, offset: VkDeviceSize => scalar, drawCount: u32 => scalar, stride: u32 => scalar);
+	fn cmd_dispatch = vkCmdDispatch(commandBuffer: VkCommandBuffer => handle, groupCountX: u32 => scalar, groupCountY: u32 => scalar, groupCountZ: u32 => scalar);
+	fn cmd_dispatch_indirect = vkCmdDispatchIndirect(commandBuffer: VkCommandBuffer => handle, buffer: VkBuffer => handle, offset: VkDeviceSize => scalar);
+	fn cmd_copy_buffer = vkCmdCopyBuffer(commandBuffer: VkCommandBuffer => handle, srcBuffer: VkBuffer => handle, dstBuffer: VkBuffer => handle, regionCount: u32 => scalar, pRegions: *const VkBufferCopy => (structs regionCount));
+	fn cmd_copy_image = vkCmdCopyImage(commandBuffer: VkCommandBuffer => handle, srcImage: VkImage => handle, srcImageLayout: VkImageLayout => scalar, dstImage: VkImage => handle, dstImageLayout: VkImageLayout => scalar, regionCount: u32 => scalar, pRegions: *const VkImageCopy => (structs regionCount));
+	fn cmd_blit_image = vkCmdBlitImage(commandBuffer: VkCommandBuffer => handle, srcImage: VkImage => handle, srcImageLayout: VkImageLayout => scalar, dstImage: VkImage => handle, dstImageLayout: VkImageLayout => scalar, regionCount: u32 => scalar, pRegions: *const VkImageBlit => (structs regionCount), filter: VkFilter => scalar);
+	fn cmd_copy_buffer_to_image = vkCmdCopyBufferToImage(commandBuffer: VkCommandBuffer => handle, srcBuffer: VkBuffer => handle, dstImage: VkImage => handle, dstImageLayout: VkImageLayout => scalar, regionCount: u32 => scalar, pRegions: *const VkBufferImageCopy => (structs regionCount));
+	fn cmd_copy_image_to_buffer = vkCmdCopyImageToBuffer(commandBuffer: VkCommandBuffer => handle, srcImage: VkImage => handle, srcImageLayout: VkImageLayout => scalar, dstBuffer: VkBuffer => handle, regionCount: u32 => scalar, pRegions: *const VkBufferImageCopy => (structs regionCount));
+	fn cmd_update_buffer = vkCmdUpdateBuffer(commandBuffer: VkCommandBuffer => handle, dstBuffer: VkBuffer => handle, dstOffset: VkDeviceSize => scalar, dataSize: VkDeviceSize => scalar, pData: *const c_void => (bytes dataSize));
+	fn cmd_fill_buffer = vkCmdFillBuffer(commandBuffer: VkCommandBuffer => handle, dstBuffer: VkBuffer => handle, dstOffset: VkDeviceSize => scalar, size: VkDeviceSize => scalar, data: u32 => scalar);
+	fn cmd_clear_color_image = vkCmdClearColorImage(commandBuffer: VkCommandBuffer => handle, image: VkImage => handle, imageLayout: VkImageLayout => scalar, pColor: *const VkClearColorValue => raw, rangeCount: u32 => scalar, pRanges: *const VkImageSubresourceRange => (structs rangeCount));
+	fn cmd_clear_depth_stencil_image = vkCmdClearDepthStencilImage(commandBuffer: VkCommandBuffer => handle, image: VkImage => handle, imageLayout: VkImageLayout => scalar, pDepthStencil: *const VkClearDepthStencilValue => input, rangeCount: u32 => scalar, pRanges: *const VkImageSubresourceRange => (structs rangeCount));
+	fn cmd_clear_attachments = vkCmdClearAttachments(commandBuffer: VkCommandBuffer => handle, attachmentCount: u32 => scalar, pAttachments: *const VkClearAttachment => (structs attachmentCount), rectCount: u32 => scalar, pRects: *const VkClearRect => (structs rectCount));
+	fn cmd_resolve_image = vkCmdResolveImage(commandBuffer: VkCommandBuffer => handle, srcImage: VkImage => handle, srcImageLayout: VkImageLayout => scalar, dstImage: VkImage => handle, dstImageLayout: VkImageLayout => scalar, regionCount: u32 => scalar, pRegions: *const VkImageResolve => (structs regionCount));
+	fn cmd_set_event = vkCmdSetEvent(commandBuffer: VkCommandBuffer => handle, event: VkEvent => handle, stageMask: VkPipelineStageFlags => scalar);
+	fn cmd_reset_event = vkCmdResetEvent(commandBuffer: VkCommandBuffer => handle, event: VkEvent => handle, stageMask: VkPipelineStageFlags => scalar);
+	fn cmd_wait_events = vkCmdWaitEvents(commandBuffer: VkCommandBuffer => handle, eventCount: u32 => scalar, pEvents: *const VkEvent => (handles eventCount), srcStageMask: VkPipelineStageFlags => scalar, dstStageMask: VkPipelineStageFlags => scalar, memoryBarrierCount: u32 => scalar, pMemoryBarriers: *const VkMemoryBarrier => (structs memoryBarrierCount), bufferMemoryBarrierCount: u32 => scalar, pBufferMemoryBarriers: *const VkBufferMemoryBarrier => (structs bufferMemoryBarrierCount), imageMemoryBarrierCount: u32 => scalar, pImageMemoryBarriers: *const VkImageMemoryBarrier => (structs imageMemoryBarrierCount));
+	fn cmd_pipeline_barrier = vkCmdPipelineBarrier(commandBuffer: VkCommandBuffer => handle, srcStageMask: VkPipelineStageFlags => scalar, dstStageMask: VkPipelineStageFlags => scalar, dependencyFlags: VkDependencyFlags => scalar, memoryBarrierCount: u32 => scalar, pMemoryBarriers: *const VkMemoryBarrier => (structs memoryBarrierCount), bufferMemoryBarrierCount: u32 => scalar, pBufferMemoryBarriers: *const VkBufferMemoryBarrier => (structs bufferMemoryBarrierCount), imageMemoryBarrierCount: u32 => scalar, pImageMemoryBarriers: *const VkImageMemoryBarrier => (structs imageMemoryBarrierCount));
+	fn cmd_begin_query = vkCmdBeginQuery(commandBuffer: VkCommandBuffer => handle, queryPool: VkQueryPool => handle, query: u32 => scalar, flags: VkQueryControlFlags => scalar);
+	fn cmd_end_query = vkCmdEndQuery(commandBuffer: VkCommandBuffer => handle, queryPool: VkQueryPool => handle, query: u32 => scalar);
+	fn cmd_reset_query_pool = vkCmdResetQueryPool(commandBuffer: VkCommandBuffer => handle, queryPool: VkQueryPool => handle, firstQuery: u32 => scalar, queryCount: u32 => scalar);
+	fn cmd_write_timestamp = vkCmdWriteTimestamp(commandBuffer: VkCommandBuffer => handle, pipelineStage: VkPipelineStageFlags => scalar, queryPool: VkQueryPool => handle, query: u32 => scalar);
+	fn cmd_copy_query_pool_results = vkCmdCopyQueryPoolResults(commandBuffer: VkCommandBuffer => handle, queryPool: VkQueryPool => handle, firstQuery: u32 => scalar, queryCount: u32 => scalar, dstBuffer: VkBuffer => handle, dstOffset: VkDeviceSize => scalar, stride: VkDeviceSize => scalar, flags: VkQueryResultFlags => scalar);
+	fn cmd_push_constants = vkCmdPushConstants(commandBuffer: VkCommandBuffer => handle, layout: VkPipelineLayout => handle, stageFlags: VkShaderStageFlags => scalar, offset: u32 => scalar, size: u32 => scalar, pValues: *const c_void => (bytes size));
+	fn cmd_begin_render_pass = vkCmdBeginRenderPass(commandBuffer: VkCommandBuffer => handle, pRenderPassBegin: *const VkRenderPassBeginInfo => input, contents: VkSubpassContents => scalar);
+	fn cmd_next_subpass = vkCmdNextSubpass(commandBuffer: VkCommandBuffer => handle, contents: VkSubpassContents => scalar);
+	fn cmd_end_render_pass = vkCmdEndRenderPass(commandBuffer: VkCommandBuffer => handle);
+	fn cmd_execute_commands = vkCmdExecuteCommands(commandBuffer: VkCommandBuffer => handle, commandBufferCount: u32 => scalar, pCommandBuffers: *const VkCommandBuffer => (handles commandBufferCount));
+	#[cfg(feature = "VK_KHR_push_descriptor")] fn cmd_push_descriptor_set_khr = vkCmdPushDescriptorSetKHR(commandBuffer: VkCommandBuffer => handle, pipelineBindPoint: VkPipelineBindPoint => scalar, layout: VkPipelineLayout => handle, set: u32 => scalar, descriptorWriteCount: u32 => scalar, pDescriptorWrites: *const VkWriteDescriptorSet => (structs descriptorWriteCount));
+	#[cfg(feature = "VK_KHR_descriptor_update_template")] fn push_descriptor_set_with_template_khr = vkCmdPushDescriptorSetWithTemplateKHR(commandBuffer: VkCommandBuffer => handle, descriptorUpdateTemplate: VkDescriptorUpdateTemplateKHR => handle, layout: VkPipelineLayout => handle, set: u32 => scalar, pData: *const c_void => opaque);
+	#[cfg(feature = "VK_KHR_get_memory_requirements2")] fn get_image_memory_requirements2_khr = vkGetImageMemoryRequirements2KHR(device: VkDevice => handle, pInfo: *const VkImageMemoryRequirementsInfo2 => input, pMemoryRequirements: *mut VkMemoryRequirements2 => outchain);
+	#[cfg(feature = "VK_KHR_get_memory_requirements2")] fn get_buffer_memory_requirements2_khr = vkGetBufferMemoryRequirements2KHR(device: VkDevice => handle, pInfo: *const VkBufferMemoryRequirementsInfo2 => input, pMemoryRequirements: *mut VkMemoryRequirements2 => outchain);
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")] fn get_physical_device_features2_khr = vkGetPhysicalDeviceFeatures2KHR(physicalDevice: VkPhysicalDevice => handle, pFeatures: *mut VkPhysicalDeviceFeatures2 => outchain);
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")] fn get_physical_device_properties2_khr = vkGetPhysicalDeviceProperties2KHR(physicalDevice: VkPhysicalDevice => handle, pProperties: *mut VkPhysicalDeviceProperties2 => outchain);
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")] fn get_physical_device_format_properties2_khr = vkGetPhysicalDeviceFormatProperties2KHR(physicalDevice: VkPhysicalDevice => handle, format: VkFormat => scalar, pFormatProperties: *mut VkFormatProperties2 => outchain);
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")] fn get_physical_device_queue_family_properties2_khr = vkGetPhysicalDeviceQueueFamilyProperties2KHR(physicalDevice: VkPhysicalDevice => handle, pQueueFamilyPropertyCount: *mut u32 => count, pQueueFamilyProperties: *mut VkQueueFamilyProperties2 => (enum_chain pQueueFamilyPropertyCount));
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")] fn get_physical_device_memory_properties2_khr = vkGetPhysicalDeviceMemoryProperties2KHR(physicalDevice: VkPhysicalDevice => handle, pMemoryProperties: *mut VkPhysicalDeviceMemoryProperties2 => outchain);
+	#[cfg(feature = "VK_EXT_debug_marker")] fn debug_marker_set_object_name_ext = vkDebugMarkerSetObjectNameEXT(device: VkDevice => handle, pNameInfo: *const VkDebugMarkerObjectNameInfoEXT => input) -> VkResult;
+	#[cfg(feature = "VK_EXT_debug_marker")] fn debug_marker_set_object_tag_ext = vkDebugMarkerSetObjectTagEXT(device: VkDevice => handle, pTagInfo: *const VkDebugMarkerObjectTagInfoEXT => input) -> VkResult;
+	#[cfg(feature = "VK_EXT_debug_marker")] fn cmd_debug_marker_begin_ext = vkCmdDebugMarkerBeginEXT(commandBuffer: VkCommandBuffer => handle, pMarkerInfo: *const VkDebugMarkerMarkerInfoEXT => input);
+	#[cfg(feature = "VK_EXT_debug_marker")] fn cmd_debug_marker_end_ext = vkCmdDebugMarkerEndEXT(commandBuffer: VkCommandBuffer => handle);
+	#[cfg(feature = "VK_EXT_debug_marker")] fn cmd_debug_marker_insert_ext = vkCmdDebugMarkerInsertEXT(commandBuffer: VkCommandBuffer => handle, pMarkerInfo: *const VkDebugMarkerMarkerInfoEXT => input);
+	#[cfg(feature = "VK_EXT_debug_utils")] fn set_debug_utils_object_name_ext = vkSetDebugUtilsObjectNameEXT(device: VkDevice => handle, pNameInfo: *const VkDebugUtilsObjectNameInfoEXT => input) -> VkResult;
+	#[cfg(feature = "VK_EXT_debug_utils")] fn set_debug_utils_object_tag_ext = vkSetDebugUtilsObjectTagEXT(device: VkDevice => handle, pTagInfo: *const VkDebugUtilsObjectTagInfoEXT => input) -> VkResult;
+	#[cfg(feature = "VK_EXT_debug_utils")] fn queue_begin_debug_utils_label_ext = vkQueueBeginDebugUtilsLabelEXT(queue: VkQueue => handle, pLabelInfo: *const VkDebugUtilsLabelEXT => input);
+	#[cfg(feature = "VK_EXT_debug_utils")] fn queue_end_debug_utils_label_ext = vkQueueEndDebugUtilsLabelEXT(queue: VkQueue => handle);
+	#[cfg(feature = "VK_EXT_debug_utils")] fn queue_insert_debug_utils_label_ext = vkQueueInsertDebugUtilsLabelEXT(queue: VkQueue => handle, pLabelInfo: *const VkDebugUtilsLabelEXT => input);
+	#[cfg(feature = "VK_EXT_debug_utils")] fn cmd_begin_debug_utils_label_ext = vkCmdBeginDebugUtilsLabelEXT(commandBuffer: VkCommandBuffer => handle, pLabelInfo: *const VkDebugUtilsLabelEXT => input);
+	#[cfg(feature = "VK_EXT_debug_utils")] fn cmd_end_debug_utils_label_ext = vkCmdEndDebugUtilsLabelEXT(commandBuffer: VkCommandBuffer => handle);
+	#[cfg(feature = "VK_EXT_debug_utils")] fn cmd_insert_debug_utils_label_ext = vkCmdInsertDebugUtilsLabelEXT(commandBuffer: VkCommandBuffer => handle, pLabelInfo: *const VkDebugUtilsLabelEXT => input);
+	#[cfg(feature = "VK_AMD_draw_indirect_count")] fn cmd_draw_indirect_count_amd = vkCmdDrawIndirectCountAMD(commandBuffer: VkCommandBuffer => handle, buffer: VkBuffer => handle, offset: VkDeviceSize => scalar, countBuffer: VkBuffer => handle, countBufferOffset: VkDeviceSize => scalar, maxDrawCount: u32 => scalar, stride: u32 => scalar);
+	#[cfg(feature = "VK_AMD_draw_indirect_count")] fn cmd_draw_indexed_indirect_count_amd = vkCmdDrawIndexedIndirectCountAMD(commandBuffer: VkCommandBuffer => handle, buffer: VkBuffer => handle, offset: VkDeviceSize => scalar, countBuffer: VkBuffer => handle, countBufferOffset: VkDeviceSize => scalar, maxDrawCount: u32 => scalar, stride: u32 => scalar);
+	#[cfg(feature = "VK_KHX_device_group")] fn cmd_set_device_mask_khx = vkCmdSetDeviceMaskKHX(commandBuffer: VkCommandBuffer => handle, deviceMask: u32 => scalar);
+	#[cfg(feature = "VK_KHX_device_group")] fn cmd_dispatch_base_khx = vkCmdDispatchBaseKHX(commandBuffer: VkCommandBuffer => handle, baseGroupX: u32 => scalar, baseGroupY: u32 => scalar, baseGroupZ: u32 => scalar, groupCountX: u32 => scalar, groupCountY: u32 => scalar, groupCountZ: u32 => scalar);
+	#[cfg(feature = "VK_NVX_device_generated_commands")] fn cmd_process_commands_nvx = vkCmdProcessCommandsNVX(commandBuffer: VkCommandBuffer => handle, pProcessCommandsInfo: *const VkCmdProcessCommandsInfoNVX => input);
+	#[cfg(feature = "VK_NVX_device_generated_commands")] fn cmd_reserve_space_for_commands_nvx = vkCmdReserveSpaceForCommandsNVX(commandBuffer: VkCommandBuffer => handle, pReserveSpaceInfo: *const VkCmdReserveSpaceForCommandsInfoNVX => input);
+	#[cfg(feature = "VK_NV_clip_space_w_scaling")] fn cmd_set_viewport_w_scaling_nv = vkCmdSetViewportWScalingNV(commandBuffer: VkCommandBuffer => handle, firstViewport: u32 => scalar, viewportCount: u32 => scalar, pViewportWScalings: *const VkViewportWScalingNV => (structs viewportCount));
+	#[cfg(feature = "VK_EXT_discard_rectangles")] fn cmd_discard_rectangle_ext = vkCmdDiscardRectangleEXT(commandBuffer: VkCommandBuffer => handle, firstDiscardRectangle: u32 => scalar, discardRectangleCount: u32 => scalar, pDiscardRectangles: *const VkRect2D => (structs discardRectangleCount));
+	fn enumerate_instance_version = vkEnumerateInstanceVersion(pApiVersion: *mut u32 => out_scalar) -> VkResult;
+	fn enumerate_physical_device_groups = vkEnumeratePhysicalDeviceGroups(instance: VkInstance => handle, pPhysicalDeviceGroupCount: *mut u32 => count, pPhysicalDeviceGroupProperties: *mut VkPhysicalDeviceGroupProperties => (enum_chain pPhysicalDeviceGroupCount)) -> VkResult;
+	fn bind_buffer_memory2 = vkBindBufferMemory2(device: VkDevice => handle, bindInfoCount: u32 => scalar, pBindInfos: *const VkBindBufferMemoryInfo => (structs bindInfoCount)) -> VkResult;
+	fn bind_image_memory2 = vkBindImageMemory2(device: VkDevice => handle, bindInfoCount: u32 => scalar, pBindInfos: *const VkBindImageMemoryInfo => (structs bindInfoCount)) -> VkResult;
+	fn get_device_group_peer_memory_features = vkGetDeviceGroupPeerMemoryFeatures(device: VkDevice => handle, heapIndex: u32 => scalar, localDeviceIndex: u32 => scalar, remoteDeviceIndex: u32 => scalar, pPeerMemoryFeatures: *mut VkPeerMemoryFeatureFlags => out_scalar);
+	fn cmd_set_device_mask = vkCmdSetDeviceMask(commandBuffer: VkCommandBuffer => handle, deviceMask: u32 => scalar);
+	fn cmd_dispatch_base = vkCmdDispatchBase(commandBuffer: VkCommandBuffer => handle, baseGroupX: u32 => scalar, baseGroupY: u32 => scalar, baseGroupZ: u32 => scalar, groupCountX: u32 => scalar, groupCountY: u32 => scalar, groupCountZ: u32 => scalar);
+	fn get_image_memory_requirements2 = vkGetImageMemoryRequirements2(device: VkDevice => handle, pInfo: *const VkImageMemoryRequirementsInfo2 => input, pMemoryRequirements: *mut VkMemoryRequirements2 => outchain);
+	fn get_buffer_memory_requirements2 = vkGetBufferMemoryRequirements2(device: VkDevice => handle, pInfo: *const VkBufferMemoryRequirementsInfo2 => input, pMemoryRequirements: *mut VkMemoryRequirements2 => outchain);
+	fn get_image_sparse_memory_requirements2 = vkGetImageSparseMemoryRequirements2(device: VkDevice => handle, pInfo: *const VkImageSparseMemoryRequirementsInfo2 => input, pSparseMemoryRequirementCount: *mut u32 => count, pSparseMemoryRequirements: *mut VkSparseImageMemoryRequirements2 => (enum_chain pSparseMemoryRequirementCount));
+	fn get_physical_device_features2 = vkGetPhysicalDeviceFeatures2(physicalDevice: VkPhysicalDevice => handle, pFeatures: *mut VkPhysicalDeviceFeatures2 => outchain);
+	fn get_physical_device_properties2 = vkGetPhysicalDeviceProperties2(physicalDevice: VkPhysicalDevice => handle, pProperties: *mut VkPhysicalDeviceProperties2 => outchain);
+	fn get_physical_device_format_properties2 = vkGetPhysicalDeviceFormatProperties2(physicalDevice: VkPhysicalDevice => handle, format: VkFormat => scalar, pFormatProperties: *mut VkFormatProperties2 => outchain);
+	fn get_physical_device_image_format_properties2 = vkGetPhysicalDeviceImageFormatProperties2(physicalDevice: VkPhysicalDevice => handle, pImageFormatInfo: *const VkPhysicalDeviceImageFormatInfo2 => input, pImageFormatProperties: *mut VkImageFormatProperties2 => outchain) -> VkResult;
+	fn get_physical_device_queue_family_properties2 = vkGetPhysicalDeviceQueueFamilyProperties2(physicalDevice: VkPhysicalDevice => handle, pQueueFamilyPropertyCount: *mut u32 => count, pQueueFamilyProperties: *mut VkQueueFamilyProperties2 => (enum_chain pQueueFamilyPropertyCount));
+	fn get_physical_device_memory_properties2 = vkGetPhysicalDeviceMemoryProperties2(physicalDevice: VkPhysicalDevice => handle, pMemoryProperties: *mut VkPhysicalDeviceMemoryProperties2 => outchain);
+	fn get_physical_device_sparse_image_format_properties2 = vkGetPhysicalDeviceSparseImageFormatProperties2(physicalDevice: VkPhysicalDevice => handle, pFormatInfo: *const VkPhysicalDeviceSparseImageFormatInfo2 => input, pPropertyCount: *mut u32 => count, pProperties: *mut VkSparseImageFormatProperties2 => (enum_chain pPropertyCount));
+	fn trim_command_pool = vkTrimCommandPool(device: VkDevice => handle, commandPool: VkCommandPool => handle, flags: VkCommandPoolTrimFlags => scalar);
+	fn get_device_queue2 = vkGetDeviceQueue2(device: VkDevice => handle, pQueueInfo: *const VkDeviceQueueInfo2 => input, pQueue: *mut VkQueue => out_handle);
+	fn create_sampler_ycbcr_conversion = vkCreateSamplerYcbcrConversion(device: VkDevice => handle, pCreateInfo: *const VkSamplerYcbcrConversionCreateInfo => input, pAllocator: *const VkAllocationCallbacks => allocator, pYcbcrConversion: *mut VkSamplerYcbcrConversion => out_handle) -> VkResult;
+	fn destroy_sampler_ycbcr_conversion = vkDestroySamplerYcbcrConversion(device: VkDevice => handle, ycbcrConversion: VkSamplerYcbcrConversion => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	fn update_descriptor_set_with_template = vkUpdateDescriptorSetWithTemplate(device: VkDevice => handle, descriptorSet: VkDescriptorSet => handle, descriptorUpdateTemplate: VkDescriptorUpdateTemplate => handle, pData: *const c_void => opaque);
+	fn get_physical_device_external_buffer_properties = vkGetPhysicalDeviceExternalBufferProperties(physicalDevice: VkPhysicalDevice => handle, pExternalBufferInfo: *const VkPhysicalDeviceExternalBufferInfo => input, pExternalBufferProperties: *mut VkExternalBufferProperties => outchain);
+	fn get_physical_device_external_fence_properties = vkGetPhysicalDeviceExternalFenceProperties(physicalDevice: VkPhysicalDevice => handle, pExternalFenceInfo: *const VkPhysicalDeviceExternalFenceInfo => input, pExternalFenceProperties: *mut VkExternalFenceProperties => outchain);
+	fn get_physical_device_external_semaphore_properties = vkGetPhysicalDeviceExternalSemaphoreProperties(physicalDevice: VkPhysicalDevice => handle, pExternalSemaphoreInfo: *const VkPhysicalDeviceExternalSemaphoreInfo => input, pExternalSemaphoreProperties: *mut VkExternalSemaphoreProperties => outchain);
+	fn get_descriptor_set_layout_support = vkGetDescriptorSetLayoutSupport(device: VkDevice => handle, pCreateInfo: *const VkDescriptorSetLayoutCreateInfo => input, pSupport: *mut VkDescriptorSetLayoutSupport => outchain);
+	#[cfg(feature = "VK_KHR_surface")] fn destroy_surface_khr = vkDestroySurfaceKHR(instance: VkInstance => handle, surface: VkSurfaceKHR => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	#[cfg(feature = "VK_KHR_surface")] fn get_physical_device_surface_support_khr = vkGetPhysicalDeviceSurfaceSupportKHR(physicalDevice: VkPhysicalDevice => handle, queueFamilyIndex: u32 => scalar, surface: VkSurfaceKHR => handle, pSupported: *mut VkBool32 => out_scalar) -> VkResult;
+	#[cfg(feature = "VK_KHR_surface")] fn get_physical_device_surface_capabilities_khr = vkGetPhysicalDeviceSurfaceCapabilitiesKHR(physicalDevice: VkPhysicalDevice => handle, surface: VkSurfaceKHR => handle, pSurfaceCapabilities: *mut VkSurfaceCapabilitiesKHR => out) -> VkResult;
+	#[cfg(feature = "VK_KHR_surface")] fn get_physical_device_surface_formats_khr = vkGetPhysicalDeviceSurfaceFormatsKHR(physicalDevice: VkPhysicalDevice => handle, surface: VkSurfaceKHR => handle, pSurfaceFormatCount: *mut u32 => count, pSurfaceFormats: *mut VkSurfaceFormatKHR => (enum_raw pSurfaceFormatCount)) -> VkResult;
+	#[cfg(feature = "VK_KHR_surface")] fn get_physical_device_surface_present_modes_khr = vkGetPhysicalDeviceSurfacePresentModesKHR(physicalDevice: VkPhysicalDevice => handle, surface: VkSurfaceKHR => handle, pPresentModeCount: *mut u32 => count, pPresentModes: *mut VkPresentModeKHR => (enum_raw pPresentModeCount)) -> VkResult;
+	#[cfg(feature = "VK_KHR_get_surface_capabilities2")] fn get_physical_device_surface_capabilities2_khr = vkGetPhysicalDeviceSurfaceCapabilities2KHR(physicalDevice: VkPhysicalDevice => handle, pSurfaceInfo: *const VkPhysicalDeviceSurfaceInfo2KHR => input, pSurfaceCapabilities: *mut VkSurfaceCapabilities2KHR => outchain) -> VkResult;
+	#[cfg(feature = "VK_KHR_swapchain")] fn create_swapchain_khr = vkCreateSwapchainKHR(device: VkDevice => handle, pCreateInfo: *const VkSwapchainCreateInfoKHR => input, pAllocator: *const VkAllocationCallbacks => allocator, pSwapchain: *mut VkSwapchainKHR => out_handle) -> VkResult;
+	#[cfg(feature = "VK_KHR_swapchain")] fn destroy_swapchain_khr = vkDestroySwapchainKHR(device: VkDevice => handle, swapchain: VkSwapchainKHR => handle, pAllocator: *const VkAllocationCallbacks => allocator);
+	#[cfg(feature = "VK_KHR_swapchain")] fn get_swapchain_images_khr = vkGetSwapchainImagesKHR(device: VkDevice => handle, swapchain: VkSwapchainKHR => handle, pSwapchainImageCount: *mut u32 => count, pSwapchainImages: *mut VkImage => (enum_handles pSwapchainImageCount)) -> VkResult;
+	#[cfg(feature = "VK_KHR_swapchain")] fn acquire_next_image_khr = vkAcquireNextImageKHR(device: VkDevice => handle, swapchain: VkSwapchainKHR => handle, timeout: u64 => scalar, semaphore: VkSemaphore => handle, fence: VkFence => handle, pImageIndex: *mut u32 => out_scalar) -> VkResult;
+	#[cfg(feature = "VK_KHR_swapchain")] fn queue_present_khr = vkQueuePresentKHR(queue: VkQueue => handle, pPresentInfo: *const VkPresentInfoKHR => input) -> VkResult;
+	#[cfg(feature = "VK_KHR_xlib_surface")] fn create_xlib_surface_khr = vkCreateXlibSurfaceKHR(instance: VkInstance => handle, pCreateInfo: *const VkXlibSurfaceCreateInfoKHR => opaque, pAllocator: *const VkAllocationCallbacks => allocator, pSurface: *mut VkSurfaceKHR => out_handle) -> VkResult;
+	#[cfg(feature = "VK_KHR_xlib_surface")] fn get_physical_device_xlib_presentation_support_khr = vkGetPhysicalDeviceXlibPresentationSupportKHR(physicalDevice: VkPhysicalDevice => handle, queueFamilyIndex: u32 => scalar, dpy: *mut Display => opaque, visualID: VisualID => scalar) -> VkBool32;
+	#[cfg(feature = "VK_KHR_xcb_surface")] fn create_xcb_surface_khr = vkCreateXcbSurfaceKHR(instance: VkInstance => handle, pCreateInfo: *const VkXcbSurfaceCreateInfoKHR => opaque, pAllocator: *const VkAllocationCallbacks => allocator, pSurface: *mut VkSurfaceKHR => out_handle) -> VkResult;
+	#[cfg(feature = "VK_KHR_xcb_surface")] fn get_physical_device_xcb_presentation_support_khr = vkGetPhysicalDeviceXcbPresentationSupportKHR(physicalDevice: VkPhysicalDevice => handle, queueFamilyIndex: u32 => scalar, connection: *mut xcb_connection_t => opaque, visualID: xcb_visualid_t => scalar) -> VkBool32;
+	#[cfg(feature = "VK_KHR_android_surface")] fn create_android_surface_khr = vkCreateAndroidSurfaceKHR(instance: VkInstance => handle, pCreateInfo: *const VkAndroidSurfaceCreateInfoKHR => opaque, pAllocator: *const VkAllocationCallbacks => allocator, pSurface: *mut VkSurfaceKHR => out_handle) -> VkResult;
+	#[cfg(feature = "VK_KHR_win32_surface")] fn create_win32_surface_khr = vkCreateWin32SurfaceKHR(instance: VkInstance => handle, pCreateInfo: *const VkWin32SurfaceCreateInfoKHR => opaque, pAllocator: *const VkAllocationCallbacks => allocator, pSurface: *mut VkSurfaceKHR => out_handle) -> VkResult;
+	#[cfg(feature = "VK_KHR_win32_surface")] fn get_physical_device_win32_presentation_support_khr = vkGetPhysicalDeviceWin32PresentationSupportKHR(physicalDevice: VkPhysicalDevice => handle, queueFamilyIndex: u32 => scalar) -> VkBool32;
+	#[cfg(feature = "VK_MVK_macos_surface")] fn create_macos_surface_mvk = vkCreateMacOSSurfaceMVK(instance: VkInstance => handle, pCreateInfo: *const VkMacOSSurfaceCreateInfoMVK => opaque, pAllocator: *const VkAllocationCallbacks => allocator, pSurface: *mut VkSurfaceKHR => out_handle) -> VkResult;
+	#[cfg(feature = "VK_KHR_display")] fn get_physical_device_display_properties_khr = vkGetPhysicalDeviceDisplayPropertiesKHR(physicalDevice: VkPhysicalDevice => handle, pPropertyCount: *mut u32 => count, pProperties: *mut VkDisplayPropertiesKHR => (enum_raw pPropertyCount)) -> VkResult;
+	#[cfg(feature = "VK_KHR_display")] fn get_physical_device_display_plane_properties_khr = vkGetPhysicalDeviceDisplayPlanePropertiesKHR(physicalDevice: VkPhysicalDevice => handle, pPropertyCount: *mut u32 => count, pProperties: *mut VkDisplayPlanePropertiesKHR => (enum_raw pPropertyCount)) -> VkResult;
+	#[cfg(feature = "VK_KHR_display")] fn get_display_plane_supported_displays_khr = vkGetDisplayPlaneSupportedDisplaysKHR(physicalDevice: VkPhysicalDevice => handle, planeIndex: u32 => scalar, pDisplayCount: *mut u32 => count, pDisplays: *mut VkDisplayKHR => (enum_handles pDisplayCount)) -> VkResult;
+	#[cfg(feature = "VK_KHR_display")] fn get_display_mode_properties_khr = vkGetDisplayModePropertiesKHR(physicalDevice: VkPhysicalDevice => handle, display: VkDisplayKHR => handle, pPropertyCount: *mut u32 => count, pProperties: *mut VkDisplayModePropertiesKHR => (enum_raw pPropertyCount)) -> VkResult;
+	#[cfg(feature = "VK_KHR_display")] fn create_display_mode_khr = vkCreateDisplayModeKHR(physicalDevice: VkPhysicalDevice => handle, display: VkDisplayKHR => handle, pCreateInfo: *const VkDisplayModeCreateInfoKHR => input, pAllocator: *const VkAllocationCallbacks => allocator, pMode: *mut VkDisplayModeKHR => out_handle) -> VkResult;
+	#[cfg(feature = "VK_KHR_display")] fn get_display_plane_capabilities_khr = vkGetDisplayPlaneCapabilitiesKHR(physicalDevice: VkPhysicalDevice => handle, mode: VkDisplayModeKHR => handle, planeIndex: u32 => scalar, pCapabilities: *mut VkDisplayPlaneCapabilitiesKHR => out) -> VkResult;
+	#[cfg(feature = "VK_KHR_display")] fn create_display_plane_surface_khr = vkCreateDisplayPlaneSurfaceKHR(instance: VkInstance => handle, pCreateInfo: *const VkDisplaySurfaceCreateInfoKHR => input, pAllocator: *const VkAllocationCallbacks => allocator, pSurface: *mut VkSurfaceKHR => out_handle) -> VkResult;
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use fakedrv::FakeDriver;
+
+	/// Output shared with the test after the recorder has taken it
+	#[derive(Clone, Default)]
+	struct SharedBuffer(Arc<Mutex<Vec<u8>>>);
+	impl Write for SharedBuffer
+	{
+		fn write(&mut self, buf: &[u8]) -> io::Result<usize> { self.0.lock().unwrap().extend_from_slice(buf); Ok(buf.len()) }
+		fn flush(&mut self) -> io::Result<()> { Ok(()) }
+	}
+
+	/// Creates a device, writes a mapped memory and polls a fence through `r`. Returns the memory handle
+	unsafe fn run<R: ResolverInterface>(r: &R) -> VkDeviceMemory
+	{
+		let mut instance = null_mut();
+		assert_eq!(r.create_instance(&Default::default(), null(), &mut instance), VK_SUCCESS);
+		let (mut count, mut pd) = (1, null_mut());
+		assert_eq!(r.enumerate_physical_devices(instance, &mut count, &mut pd), VK_SUCCESS);
+		let priority = 0.0f32;
+		let q = VkDeviceQueueCreateInfo { queueFamilyIndex: 0, queueCount: 1, pQueuePriorities: &priority, .. Default::default() };
+		let ci = VkDeviceCreateInfo { queueCreateInfoCount: 1, pQueueCreateInfos: &q, .. Default::default() };
+		let mut device = null_mut();
+		assert_eq!(r.create_device(pd, &ci, null(), &mut device), VK_SUCCESS);
+
+		let ai = VkMemoryAllocateInfo { allocationSize: 256, memoryTypeIndex: 1, .. Default::default() };
+		let mut memory = VK_NULL_HANDLE as _;
+		assert_eq!(r.allocate_memory(device, &ai, null(), &mut memory), VK_SUCCESS);
+		let mut p = null_mut();
+		assert_eq!(r.map_memory(device, memory, 16, VK_WHOLE_SIZE, 0, &mut p), VK_SUCCESS);
+		ptr::copy_nonoverlapping(b"bedrock".as_ptr(), p as *mut u8, 7);
+		r.unmap_memory(device, memory);
+
+		let mut fence = VK_NULL_HANDLE as _;
+		assert_eq!(r.create_fence(device, &VkFenceCreateInfo { flags: VK_FENCE_CREATE_SIGNALED_BIT, .. Default::default() },
+			null(), &mut fence), VK_SUCCESS);
+		assert_eq!(r.get_fence_status(device, fence), VK_SUCCESS);
+		r.destroy_fence(device, fence, null());
+		memory
+	}
+	/// Reads the first bytes of the memory after the replay
+	unsafe fn read_back(d: &FakeDriver, device: VkDevice, memory: VkDeviceMemory) -> Vec<u8>
+	{
+		let mut p = null_mut();
+		assert_eq!(d.map_memory(device, memory, 16, 7, 0, &mut p), VK_SUCCESS);
+		let bytes = slice::from_raw_parts(p as *const u8, 7).to_vec();
+		d.unmap_memory(device, memory);
+		bytes
+	}
+
+	#[test]
+	fn round_trip()
+	{
+		let out = SharedBuffer::default();
+		let recorder = Recorder::new(FakeDriver::new(), out.clone());
+		let memory = unsafe { run(&recorder) };
+		recorder.flush().unwrap();
+		assert!(recorder.inner().misuses().is_empty());
+
+		let trace = Trace::read(&out.0.lock().unwrap()[..]).unwrap();
+		let names = trace.calls().map(|c| &c.name[..]).collect::<Vec<_>>();
+		assert_eq!(names, ["vkCreateInstance", "vkEnumeratePhysicalDevices", "vkCreateDevice", "vkAllocateMemory", "vkMapMemory",
+			"vkUnmapMemory", "vkCreateFence", "vkGetFenceStatus", "vkDestroyFence"]);
+		// the whole mapped range is captured on the first unmap
+		match trace.records.iter().find(|r| matches!(**r, Record::MemoryWrite { .. }))
+		{
+			Some(&Record::MemoryWrite { memory: m, offset: 16, ref data }) => { assert_eq!(m, memory as u64); assert_eq!(&data[..7], b"bedrock"); },
+			r => panic!("unexpected record: {:?}", r)
+		}
+		// written and read back without any loss
+		let mut bytes = Vec::new();
+		trace.write(&mut bytes).unwrap();
+		assert_eq!(Trace::read(&bytes[..]).unwrap(), trace);
+
+		let mut replayer = Replayer::new(FakeDriver::new());
+		replayer.replay(&trace).unwrap();
+		assert!(replayer.divergences().is_empty(), "{:?}", replayer.divergences());
+		let device = match trace.calls().find(|c| c.name == "vkCreateDevice").and_then(|c| c.arg("pDevice"))
+		{
+			Some(Value::Array(hs)) => hs[0].as_u64().unwrap(), v => panic!("unexpected value: {:?}", v)
+		};
+		let (device, replayed_memory) = (replayer.handle(device).unwrap(), replayer.handle(memory as _).unwrap());
+		let d = replayer.resolver();
+		assert_eq!(unsafe { read_back(d, device as usize as _, replayed_memory as _) }, b"bedrock");
+		assert!(d.misuses().is_empty());
+	}
+}
@@ -0,0 +1,1752 @@
+//! Memory layouts of the structures deep-copied by the tracer
+
+#![allow(non_snake_case)]
+
+use vk::*;
+use libc::{c_float, size_t};
+use std::mem::size_of;
+
+/// Primitive representation of a scalar member
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScalarKind { U8, I8, U16, I16, U32, I32, U64, I64, F32, F64 }
+pub trait TraceScalar: Copy { const KIND: ScalarKind; }
+macro_rules! TraceScalars
+{
+	($($t: ty = $k: ident),*) => { $(impl TraceScalar for $t { const KIND: ScalarKind = ScalarKind::$k; })* }
+}
+TraceScalars!(u8 = U8, i8 = I8, u16 = U16, i16 = I16, u32 = U32, i32 = I32, u64 = U64, i64 = I64, f32 = F32, f64 = F64);
+#[cfg(target_pointer_width = "64")] TraceScalars!(usize = U64);
+#[cfg(target_pointer_width = "32")] TraceScalars!(usize = U32);
+impl ScalarKind
+{
+	pub fn size(self) -> usize
+	{
+		match self
+		{
+			ScalarKind::U8 | ScalarKind::I8 => 1, ScalarKind::U16 | ScalarKind::I16 => 2,
+			ScalarKind::U32 | ScalarKind::I32 | ScalarKind::F32 => 4,
+			ScalarKind::U64 | ScalarKind::I64 | ScalarKind::F64 => 8
+		}
+	}
+}
+
+/// Element of a member, an array or a pointed value
+#[derive(Clone, Copy)]
+pub enum Elem
+{
+	Scalar(ScalarKind),
+	/// Handle with its size in bytes
+	Handle(usize),
+	Struct(&'static Schema),
+	/// Union or other plain bytes
+	Raw(usize)
+}
+impl Elem
+{
+	pub fn size(&self) -> usize
+	{
+		match *self
+		{
+			Elem::Scalar(k) => k.size(), Elem::Handle(s) | Elem::Raw(s) => s, Elem::Struct(s) => s.size
+		}
+	}
+}
+/// How a member is captured. Counts are referred by the member names
+#[derive(Clone, Copy)]
+#[allow(dead_code)] // some kinds appear only in the extension structures
+pub enum FieldKind
+{
+	Value(Elem),
+	/// `pNext`
+	Chain,
+	Str, Strs(&'static str),
+	Array(&'static str, Elem),
+	/// Array only valid when the predicate holds on the containing structure
+	ArrayIf(&'static str, Elem, fn(*const u8) -> bool),
+	/// Array whose length is computed from the containing structure
+	ArrayWith(fn(*const u8) -> usize, Elem),
+	Ptr(Elem),
+	/// `*const c_void` with the byte length
+	Bytes(&'static str),
+	Fixed(usize, Elem),
+	/// Pointer written by the implementation. Replayed as null
+	Output,
+	/// Host-only pointer(callbacks, user data, ...). Cannot be replayed unless null
+	Opaque
+}
+pub struct Field { pub name: &'static str, pub offset: usize, pub kind: FieldKind }
+pub struct Schema
+{
+	pub name: &'static str, pub stype: Option<VkStructureType>, pub size: usize, pub fields: &'static [Field]
+}
+impl Schema
+{
+	pub fn field(&self, name: &str) -> Option<&'static Field> { self.fields.iter().find(|f| f.name == name) }
+}
+pub trait Traced { const SCHEMA: &'static Schema; }
+
+macro_rules! TraceElem
+{
+	(scalar $t: ty) => { Elem::Scalar(<$t as TraceScalar>::KIND) };
+	(handle $t: ty) => { Elem::Handle(size_of::<$t>()) };
+	(struct $t: ty) => { Elem::Struct(<$t as Traced>::SCHEMA) };
+	(raw $t: ty) => { Elem::Raw(size_of::<$t>()) };
+}
+macro_rules! TraceField
+{
+	(chain) => { FieldKind::Chain };
+	(str) => { FieldKind::Str };
+	(strs $c: ident) => { FieldKind::Strs(stringify!($c)) };
+	(array $c: ident ($($e: tt)*)) => { FieldKind::Array(stringify!($c), TraceElem!($($e)*)) };
+	(array_if $c: ident $p: ident ($($e: tt)*)) => { FieldKind::ArrayIf(stringify!($c), TraceElem!($($e)*), $p) };
+	(array_with $f: ident ($($e: tt)*)) => { FieldKind::ArrayWith($f, TraceElem!($($e)*)) };
+	(ptr ($($e: tt)*)) => { FieldKind::Ptr(TraceElem!($($e)*)) };
+	(bytes $c: ident) => { FieldKind::Bytes(stringify!($c)) };
+	(fixed $n: tt ($($e: tt)*)) => { FieldKind::Fixed($n, TraceElem!($($e)*)) };
+	(output) => { FieldKind::Output };
+	(opaque) => { FieldKind::Opaque };
+	($($e: tt)*) => { FieldKind::Value(TraceElem!($($e)*)) };
+}
+macro_rules! TraceSchema
+{
+	($name: ident $(= $stype: ident)* { $($f: ident : [$($k: tt)*]),* }) =>
+	{
+		impl Traced for $name
+		{
+			const SCHEMA: &'static Schema = &Schema
+			{
+				name: stringify!($name), stype: TraceSchema!(@stype $($stype)*), size: size_of::<$name>(),
+				fields: &[$(Field { name: stringify!($f), offset: ::std::mem::offset_of!($name, $f), kind: TraceField!($($k)*) }),*]
+			};
+		}
+	};
+	(@stype) => { None };
+	(@stype $s: ident) => { Some($s) };
+}
+/// Lookup tables for the members of `pNext` chains
+macro_rules! TraceChainMembers
+{
+	($($(#[$attr: meta])* $name: ident),*) =>
+	{
+		pub fn by_stype(stype: VkStructureType) -> Option<&'static Schema>
+		{
+			match stype
+			{
+				$($(#[$attr])* _ if Some(stype) == <$name as Traced>::SCHEMA.stype => Some(<$name as Traced>::SCHEMA),)*
+				_ => None
+			}
+		}
+		pub fn by_name(name: &str) -> Option<&'static Schema>
+		{
+			match name
+			{
+				$($(#[$attr])* stringify!($name) => Some(<$name as Traced>::SCHEMA),)*
+				_ => None
+			}
+		}
+	}
+}
+
+fn immutable_samplers_used(p: *const u8) -> bool
+{
+	let b = unsafe { &*(p as *const VkDescriptorSetLayoutBinding) };
+	b.descriptorType == VK_DESCRIPTOR_TYPE_SAMPLER || b.descriptorType == VK_DESCRIPTOR_TYPE_COMBINED_IMAGE_SAMPLER
+}
+fn image_info_used(p: *const u8) -> bool
+{
+	matches!(unsafe { (*(p as *const VkWriteDescriptorSet)).descriptorType },
+		VK_DESCRIPTOR_TYPE_SAMPLER | VK_DESCRIPTOR_TYPE_COMBINED_IMAGE_SAMPLER | VK_DESCRIPTOR_TYPE_SAMPLED_IMAGE |
+		VK_DESCRIPTOR_TYPE_STORAGE_IMAGE | VK_DESCRIPTOR_TYPE_INPUT_ATTACHMENT)
+}
+fn buffer_info_used(p: *const u8) -> bool
+{
+	matches!(unsafe { (*(p as *const VkWriteDescriptorSet)).descriptorType },
+		VK_DESCRIPTOR_TYPE_UNIFORM_BUFFER | VK_DESCRIPTOR_TYPE_STORAGE_BUFFER |
+		VK_DESCRIPTOR_TYPE_UNIFORM_BUFFER_DYNAMIC | VK_DESCRIPTOR_TYPE_STORAGE_BUFFER_DYNAMIC)
+}
+fn texel_buffer_view_used(p: *const u8) -> bool
+{
+	matches!(unsafe { (*(p as *const VkWriteDescriptorSet)).descriptorType },
+		VK_DESCRIPTOR_TYPE_UNIFORM_TEXEL_BUFFER | VK_DESCRIPTOR_TYPE_STORAGE_TEXEL_BUFFER)
+}
+/// `pSampleMask` has ceil(rasterizationSamples / 32) words
+fn sample_mask_words(p: *const u8) -> usize
+{
+	let samples = unsafe { (*(p as *const VkPipelineMultisampleStateCreateInfo)).rasterizationSamples };
+	((samples + 31) / 32) as usize
+}
+
+TraceSchema!(VkApplicationInfo = VK_STRUCTURE_TYPE_APPLICATION_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	pApplicationName: [str],
+	applicationVersion: [scalar u32],
+	pEngineName: [str],
+	engineVersion: [scalar u32],
+	apiVersion: [scalar u32]
+});
+TraceSchema!(VkInstanceCreateInfo = VK_STRUCTURE_TYPE_INSTANCE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkInstanceCreateFlags],
+	pApplicationInfo: [ptr (struct VkApplicationInfo)],
+	enabledLayerCount: [scalar u32],
+	ppEnabledLayerNames: [strs enabledLayerCount],
+	enabledExtensionCount: [scalar u32],
+	ppEnabledExtensionNames: [strs enabledExtensionCount]
+});
+TraceSchema!(VkPhysicalDeviceFeatures {
+	robustBufferAccess: [scalar VkBool32],
+	fullDrawIndexUint32: [scalar VkBool32],
+	imageCubeArray: [scalar VkBool32],
+	independentBlend: [scalar VkBool32],
+	geometryShader: [scalar VkBool32],
+	tessellationShader: [scalar VkBool32],
+	sampleRateShading: [scalar VkBool32],
+	dualSrcBlend: [scalar VkBool32],
+	logicOp: [scalar VkBool32],
+	multiDrawIndirect: [scalar VkBool32],
+	drawIndirectFirstInstance: [scalar VkBool32],
+	depthClamp: [scalar VkBool32],
+	depthBiasClamp: [scalar VkBool32],
+	fillModeNonSolid: [scalar VkBool32],
+	depthBounds: [scalar VkBool32],
+	wideLines: [scalar VkBool32],
+	largePoints: [scalar VkBool32],
+	alphaToOne: [scalar VkBool32],
+	multiViewport: [scalar VkBool32],
+	samplerAnisotropy: [scalar VkBool32],
+	textureCompressionETC2: [scalar VkBool32],
+	textureCompressionASTC_LDR: [scalar VkBool32],
+	textureCompressionBC: [scalar VkBool32],
+	occlusionQueryPrecise: [scalar VkBool32],
+	pipelineStatisticsQuery: [scalar VkBool32],
+	vertexPipelineStoresAndAtomics: [scalar VkBool32],
+	fragmentStoresAndAtomics: [scalar VkBool32],
+	shaderTessellationAndGeometryPointSize: [scalar VkBool32],
+	shaderImageGatherExtended: [scalar VkBool32],
+	shaderStorageImageExtendedFormats: [scalar VkBool32],
+	shaderStorageImageMultisample: [scalar VkBool32],
+	shaderStorageImageReadWithoutFormat: [scalar VkBool32],
+	shaderStorageImageWriteWithoutFormat: [scalar VkBool32],
+	shaderUniformBufferArrayDynamicIndexing: [scalar VkBool32],
+	shaderSampledImageArrayDynamicIndexing: [scalar VkBool32],
+	shaderStorageBufferArrayDynamicIndexing: [scalar VkBool32],
+	shaderStorageImageArrayDynamicIndexing: [scalar VkBool32],
+	shaderClipDistance: [scalar VkBool32],
+	shaderCullDistance: [scalar VkBool32],
+	shaderFloat64: [scalar VkBool32],
+	shaderInt64: [scalar VkBool32],
+	shaderInt16: [scalar VkBool32],
+	shaderResourceResidency: [scalar VkBool32],
+	shaderResourceMinLod: [scalar VkBool32],
+	sparseBinding: [scalar VkBool32],
+	sparseResidencyBuffer: [scalar VkBool32],
+	sparseResidencyImage2D: [scalar VkBool32],
+	sparseResidencyImage3D: [scalar VkBool32],
+	sparseResidency2Samples: [scalar VkBool32],
+	sparseResidency4Samples: [scalar VkBool32],
+	sparseResidency8Samples: [scalar VkBool32],
+	sparseResidency16Samples: [scalar VkBool32],
+	sparseResidencyAliased: [scalar VkBool32],
+	variableMultisampleRate: [scalar VkBool32],
+	inheritedQueries: [scalar VkBool32]
+});
+TraceSchema!(VkExtent3D {
+	width: [scalar u32],
+	height: [scalar u32],
+	depth: [scalar u32]
+});
+TraceSchema!(VkDeviceQueueCreateInfo = VK_STRUCTURE_TYPE_DEVICE_QUEUE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkDeviceQueueCreateFlags],
+	queueFamilyIndex: [scalar u32],
+	queueCount: [scalar u32],
+	pQueuePriorities: [array queueCount (scalar c_float)]
+});
+TraceSchema!(VkDeviceCreateInfo = VK_STRUCTURE_TYPE_DEVICE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkDeviceCreateFlags],
+	queueCreateInfoCount: [scalar u32],
+	pQueueCreateInfos: [array queueCreateInfoCount (struct VkDeviceQueueCreateInfo)],
+	enabledLayerCount: [scalar u32],
+	ppEnabledLayerNames: [strs enabledLayerCount],
+	enabledExtensionCount: [scalar u32],
+	ppEnabledExtensionNames: [strs enabledExtensionCount],
+	pEnabledFeatures: [ptr (struct VkPhysicalDeviceFeatures)]
+});
+TraceSchema!(VkSubmitInfo = VK_STRUCTURE_TYPE_SUBMIT_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	waitSemaphoreCount: [scalar u32],
+	pWaitSemaphores: [array waitSemaphoreCount (handle VkSemaphore)],
+	pWaitDstStageMask: [array waitSemaphoreCount (scalar VkPipelineStageFlags)],
+	commandBufferCount: [scalar u32],
+	pCommandBuffers: [array commandBufferCount (handle VkCommandBuffer)],
+	signalSemaphoreCount: [scalar u32],
+	pSignalSemaphores: [array signalSemaphoreCount (handle VkSemaphore)]
+});
+TraceSchema!(VkMemoryAllocateInfo = VK_STRUCTURE_TYPE_MEMORY_ALLOCATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	allocationSize: [scalar VkDeviceSize],
+	memoryTypeIndex: [scalar u32]
+});
+TraceSchema!(VkMappedMemoryRange = VK_STRUCTURE_TYPE_MAPPED_MEMORY_RANGE {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	memory: [handle VkDeviceMemory],
+	offset: [scalar VkDeviceSize],
+	size: [scalar VkDeviceSize]
+});
+TraceSchema!(VkSparseMemoryBind {
+	resourceOffset: [scalar VkDeviceSize],
+	size: [scalar VkDeviceSize],
+	memory: [handle VkDeviceMemory],
+	memoryOffset: [scalar VkDeviceSize],
+	flags: [scalar VkSparseMemoryBindFlags]
+});
+TraceSchema!(VkSparseBufferMemoryBindInfo {
+	buffer: [handle VkBuffer],
+	bindCount: [scalar u32],
+	pBinds: [array bindCount (struct VkSparseMemoryBind)]
+});
+TraceSchema!(VkSparseImageOpaqueMemoryBindInfo {
+	image: [handle VkImage],
+	bindCount: [scalar u32],
+	pBinds: [array bindCount (struct VkSparseMemoryBind)]
+});
+TraceSchema!(VkImageSubresource {
+	aspectMask: [scalar VkImageAspectFlags],
+	mipLevel: [scalar u32],
+	arrayLayer: [scalar u32]
+});
+TraceSchema!(VkOffset3D {
+	x: [scalar i32],
+	y: [scalar i32],
+	z: [scalar i32]
+});
+TraceSchema!(VkSparseImageMemoryBind {
+	subresource: [struct VkImageSubresource],
+	offset: [struct VkOffset3D],
+	extent: [struct VkExtent3D],
+	memory: [handle VkDeviceMemory],
+	memoryOffset: [scalar VkDeviceSize],
+	flags: [scalar VkSparseMemoryBindFlags]
+});
+TraceSchema!(VkSparseImageMemoryBindInfo {
+	image: [handle VkImage],
+	bindCount: [scalar u32],
+	pBinds: [array bindCount (struct VkSparseImageMemoryBind)]
+});
+TraceSchema!(VkBindSparseInfo = VK_STRUCTURE_TYPE_BIND_SPARSE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	waitSemaphoreCount: [scalar u32],
+	pWaitSemaphores: [array waitSemaphoreCount (handle VkSemaphore)],
+	bufferBindCount: [scalar u32],
+	pBufferBinds: [array bufferBindCount (struct VkSparseBufferMemoryBindInfo)],
+	imageOpaqueBindCount: [scalar u32],
+	pImageOpaqueBinds: [array imageOpaqueBindCount (struct VkSparseImageOpaqueMemoryBindInfo)],
+	imageBindCount: [scalar u32],
+	pImageBinds: [array imageBindCount (struct VkSparseImageMemoryBindInfo)],
+	signalSemaphoreCount: [scalar u32],
+	pSignalSemaphores: [array signalSemaphoreCount (handle VkSemaphore)]
+});
+TraceSchema!(VkFenceCreateInfo = VK_STRUCTURE_TYPE_FENCE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkFenceCreateFlags]
+});
+TraceSchema!(VkSemaphoreCreateInfo = VK_STRUCTURE_TYPE_SEMAPHORE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkSemaphoreCreateFlags]
+});
+TraceSchema!(VkEventCreateInfo = VK_STRUCTURE_TYPE_EVENT_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkEventCreateFlags]
+});
+TraceSchema!(VkQueryPoolCreateInfo = VK_STRUCTURE_TYPE_QUERY_POOL_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkQueryPoolCreateFlags],
+	queryType: [scalar VkQueryType],
+	queryCount: [scalar u32],
+	pipelineStatistics: [scalar VkQueryPipelineStatisticFlags]
+});
+TraceSchema!(VkBufferCreateInfo = VK_STRUCTURE_TYPE_BUFFER_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkBufferCreateFlags],
+	size: [scalar VkDeviceSize],
+	usage: [scalar VkBufferUsageFlags],
+	sharingMode: [scalar VkSharingMode],
+	queueFamilyIndexCount: [scalar u32],
+	pQueueFamilyIndices: [array queueFamilyIndexCount (scalar u32)]
+});
+TraceSchema!(VkBufferViewCreateInfo = VK_STRUCTURE_TYPE_BUFFER_VIEW_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkBufferCreateFlags],
+	buffer: [handle VkBuffer],
+	format: [scalar VkFormat],
+	offset: [scalar VkDeviceSize],
+	range: [scalar VkDeviceSize]
+});
+TraceSchema!(VkImageCreateInfo = VK_STRUCTURE_TYPE_IMAGE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkImageCreateFlags],
+	imageType: [scalar VkImageType],
+	format: [scalar VkFormat],
+	extent: [struct VkExtent3D],
+	mipLevels: [scalar u32],
+	arrayLayers: [scalar u32],
+	samples: [scalar VkSampleCountFlags],
+	tiling: [scalar VkImageTiling],
+	usage: [scalar VkImageUsageFlags],
+	sharingMode: [scalar VkSharingMode],
+	queueFamilyIndexCount: [scalar u32],
+	pQueueFamilyIndices: [array queueFamilyIndexCount (scalar u32)],
+	initialLayout: [scalar VkImageLayout]
+});
+TraceSchema!(VkComponentMapping {
+	r: [scalar VkComponentSwizzle],
+	g: [scalar VkComponentSwizzle],
+	b: [scalar VkComponentSwizzle],
+	a: [scalar VkComponentSwizzle]
+});
+TraceSchema!(VkImageSubresourceRange {
+	aspectMask: [scalar VkImageAspectFlags],
+	baseMipLevel: [scalar u32],
+	levelCount: [scalar u32],
+	baseArrayLayer: [scalar u32],
+	layerCount: [scalar u32]
+});
+TraceSchema!(VkImageViewCreateInfo = VK_STRUCTURE_TYPE_IMAGE_VIEW_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkImageViewCreateFlags],
+	image: [handle VkImage],
+	viewType: [scalar VkImageViewType],
+	format: [scalar VkFormat],
+	components: [struct VkComponentMapping],
+	subresourceRange: [struct VkImageSubresourceRange]
+});
+TraceSchema!(VkShaderModuleCreateInfo = VK_STRUCTURE_TYPE_SHADER_MODULE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkShaderModuleCreateFlags],
+	codeSize: [scalar size_t],
+	pCode: [bytes codeSize]
+});
+TraceSchema!(VkPipelineCacheCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_CACHE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineCacheCreateFlags],
+	initialDataSize: [scalar size_t],
+	pInitialData: [bytes initialDataSize]
+});
+TraceSchema!(VkSpecializationMapEntry {
+	constantID: [scalar u32],
+	offset: [scalar u32],
+	size: [scalar size_t]
+});
+TraceSchema!(VkSpecializationInfo {
+	mapEntryCount: [scalar u32],
+	pMapEntries: [array mapEntryCount (struct VkSpecializationMapEntry)],
+	dataSize: [scalar size_t],
+	pData: [bytes dataSize]
+});
+TraceSchema!(VkPipelineShaderStageCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_SHADER_STAGE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineShaderStageCreateFlags],
+	stage: [scalar VkShaderStageFlags],
+	module: [handle VkShaderModule],
+	pName: [str],
+	pSpecializationInfo: [ptr (struct VkSpecializationInfo)]
+});
+TraceSchema!(VkVertexInputBindingDescription {
+	binding: [scalar u32],
+	stride: [scalar u32],
+	inputRate: [scalar VkVertexInputRate]
+});
+TraceSchema!(VkVertexInputAttributeDescription {
+	location: [scalar u32],
+	binding: [scalar u32],
+	format: [scalar VkFormat],
+	offset: [scalar u32]
+});
+TraceSchema!(VkPipelineVertexInputStateCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_VERTEX_INPUT_STAGE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineVertexInputStateCreateFlags],
+	vertexBindingDescriptionCount: [scalar u32],
+	pVertexBindingDescriptions: [array vertexBindingDescriptionCount (struct VkVertexInputBindingDescription)],
+	vertexAttributeDescriptionCount: [scalar u32],
+	pVertexAttributeDescriptions: [array vertexAttributeDescriptionCount (struct VkVertexInputAttributeDescription)]
+});
+TraceSchema!(VkPipelineInputAssemblyStateCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_INPUT_ASSEMBLY_STATE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineInputAssemblyStateCreateFlags],
+	topology: [scalar VkPrimitiveTopology],
+	primitiveRestartEnable: [scalar VkBool32]
+});
+TraceSchema!(VkPipelineTessellationStateCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_TESSELLATION_STATE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineTessellationStateCreateFlags],
+	patchControlPoints: [scalar u32]
+});
+TraceSchema!(VkViewport {
+	x: [scalar c_float],
+	y: [scalar c_float],
+	width: [scalar c_float],
+	height: [scalar c_float],
+	minDepth: [scalar c_float],
+	maxDepth: [scalar c_float]
+});
+TraceSchema!(VkOffset2D {
+	x: [scalar i32],
+	y: [scalar i32]
+});
+TraceSchema!(VkExtent2D {
+	width: [scalar u32],
+	height: [scalar u32]
+});
+TraceSchema!(VkRect2D {
+	offset: [struct VkOffset2D],
+	extent: [struct VkExtent2D]
+});
+TraceSchema!(VkPipelineViewportStateCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_VIEWPORT_STATE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineViewportStateCreateFlags],
+	viewportCount: [scalar u32],
+	pViewports: [array viewportCount (struct VkViewport)],
+	scissorCount: [scalar u32],
+	pScissors: [array scissorCount (struct VkRect2D)]
+});
+TraceSchema!(VkPipelineRasterizationStateCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_RASTERIZATION_STATE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineRasterizationStateCreateFlags],
+	depthClampEnable: [scalar VkBool32],
+	rasterizerDiscardEnable: [scalar VkBool32],
+	polygonMode: [scalar VkPolygonMode],
+	cullMode: [scalar VkCullModeFlags],
+	frontFace: [scalar VkFrontFace],
+	depthBiasEnable: [scalar VkBool32],
+	depthBiasConstantFactor: [scalar c_float],
+	depthBiasClamp: [scalar c_float],
+	depthBiasSlopeFactor: [scalar c_float],
+	lineWidth: [scalar c_float]
+});
+TraceSchema!(VkPipelineMultisampleStateCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_MULTISAMPLE_STATE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineMultisampleStateCreateFlags],
+	rasterizationSamples: [scalar VkSampleCountFlags],
+	sampleShadingEnable: [scalar VkBool32],
+	minSampleShading: [scalar c_float],
+	pSampleMask: [array_with sample_mask_words (scalar VkSampleMask)],
+	alphaToCoverageEnable: [scalar VkBool32],
+	alphaToOneEnable: [scalar VkBool32]
+});
+TraceSchema!(VkStencilOpState {
+	failOp: [scalar VkStencilOp],
+	passOp: [scalar VkStencilOp],
+	depthFailOp: [scalar VkStencilOp],
+	compareOp: [scalar VkCompareOp],
+	compareMask: [scalar u32],
+	writeMask: [scalar u32],
+	reference: [scalar u32]
+});
+TraceSchema!(VkPipelineDepthStencilStateCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_DEPTH_STENCIL_STATE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineDepthStencilStateCreateFlags],
+	depthTestEnable: [scalar VkBool32],
+	depthWriteEnable: [scalar VkBool32],
+	depthCompareOp: [scalar VkCompareOp],
+	depthBoundsTestEnable: [scalar VkBool32],
+	stencilTestEnable: [scalar VkBool32],
+	front: [struct VkStencilOpState],
+	back: [struct VkStencilOpState],
+	minDepthBounds: [scalar c_float],
+	maxDepthBounds: [scalar c_float]
+});
+TraceSchema!(VkPipelineColorBlendAttachmentState {
+	blendEnable: [scalar VkBool32],
+	srcColorBlendFactor: [scalar VkBlendFactor],
+	dstColorBlendFactor: [scalar VkBlendFactor],
+	colorBlendOp: [scalar VkBlendOp],
+	srcAlphaBlendFactor: [scalar VkBlendFactor],
+	dstAlphaBlendFactor: [scalar VkBlendFactor],
+	alphaBlendOp: [scalar VkBlendOp],
+	colorWriteMask: [scalar VkColorComponentFlags]
+});
+TraceSchema!(VkPipelineColorBlendStateCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_COLOR_BLEND_STATE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineColorBlendStateCreateFlags],
+	logicOpEnable: [scalar VkBool32],
+	logicOp: [scalar VkLogicOp],
+	attachmentCount: [scalar u32],
+	pAttachments: [array attachmentCount (struct VkPipelineColorBlendAttachmentState)],
+	blendConstants: [fixed 4 (scalar c_float)]
+});
+TraceSchema!(VkPipelineDynamicStateCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_DYNAMIC_STATE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineDynamicStateCreateFlags],
+	dynamicStateCount: [scalar u32],
+	pDynamicStates: [array dynamicStateCount (scalar VkDynamicState)]
+});
+TraceSchema!(VkGraphicsPipelineCreateInfo = VK_STRUCTURE_TYPE_GRAPHICS_PIPELINE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineCreateFlags],
+	stageCount: [scalar u32],
+	pStages: [array stageCount (struct VkPipelineShaderStageCreateInfo)],
+	pVertexInputState: [ptr (struct VkPipelineVertexInputStateCreateInfo)],
+	pInputAssemblyState: [ptr (struct VkPipelineInputAssemblyStateCreateInfo)],
+	pTessellationState: [ptr (struct VkPipelineTessellationStateCreateInfo)],
+	pViewportState: [ptr (struct VkPipelineViewportStateCreateInfo)],
+	pRasterizationState: [ptr (struct VkPipelineRasterizationStateCreateInfo)],
+	pMultisampleState: [ptr (struct VkPipelineMultisampleStateCreateInfo)],
+	pDepthStencilState: [ptr (struct VkPipelineDepthStencilStateCreateInfo)],
+	pColorBlendState: [ptr (struct VkPipelineColorBlendStateCreateInfo)],
+	pDynamicState: [ptr (struct VkPipelineDynamicStateCreateInfo)],
+	layout: [handle VkPipelineLayout],
+	renderPass: [handle VkRenderPass],
+	subpass: [scalar u32],
+	basePipelineHandle: [handle VkPipeline],
+	basePipelineIndex: [scalar i32]
+});
+TraceSchema!(VkComputePipelineCreateInfo = VK_STRUCTURE_TYPE_COMPUTE_PIPELINE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineCreateFlags],
+	stage: [struct VkPipelineShaderStageCreateInfo],
+	layout: [handle VkPipelineLayout],
+	basePipelineHandle: [handle VkPipeline],
+	basePipelineIndex: [scalar i32]
+});
+TraceSchema!(VkPushConstantRange {
+	stageFlags: [scalar VkShaderStageFlags],
+	offset: [scalar u32],
+	size: [scalar u32]
+});
+TraceSchema!(VkPipelineLayoutCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_LAYOUT_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineLayoutCreateFlags],
+	setLayoutCount: [scalar u32],
+	pSetLayouts: [array setLayoutCount (handle VkDescriptorSetLayout)],
+	pushConstantRangeCount: [scalar u32],
+	pPushConstantRanges: [array pushConstantRangeCount (struct VkPushConstantRange)]
+});
+TraceSchema!(VkSamplerCreateInfo = VK_STRUCTURE_TYPE_SAMPLER_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkSamplerCreateFlags],
+	magFilter: [scalar VkFilter],
+	minFilter: [scalar VkFilter],
+	mipmapMode: [scalar VkSamplerMipmapMode],
+	addressModeU: [scalar VkSamplerAddressMode],
+	addressModeV: [scalar VkSamplerAddressMode],
+	addressModeW: [scalar VkSamplerAddressMode],
+	mipLodBias: [scalar c_float],
+	anisotropyEnable: [scalar VkBool32],
+	maxAnisotropy: [scalar c_float],
+	compareEnable: [scalar VkBool32],
+	compareOp: [scalar VkCompareOp],
+	minLod: [scalar c_float],
+	maxLod: [scalar c_float],
+	borderColor: [scalar VkBorderColor],
+	unnormalizedCoordinates: [scalar VkBool32]
+});
+TraceSchema!(VkDescriptorSetLayoutBinding {
+	binding: [scalar u32],
+	descriptorType: [scalar VkDescriptorType],
+	descriptorCount: [scalar u32],
+	stageFlags: [scalar VkShaderStageFlags],
+	pImmutableSamplers: [array_if descriptorCount immutable_samplers_used (handle VkSampler)]
+});
+TraceSchema!(VkDescriptorSetLayoutCreateInfo = VK_STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkDescriptorSetLayoutCreateFlags],
+	bindingCount: [scalar u32],
+	pBindings: [array bindingCount (struct VkDescriptorSetLayoutBinding)]
+});
+TraceSchema!(VkDescriptorPoolSize {
+	_type: [scalar VkDescriptorType],
+	descriptorCount: [scalar u32]
+});
+TraceSchema!(VkDescriptorPoolCreateInfo = VK_STRUCTURE_TYPE_DESCRIPTOR_POOL_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkDescriptorPoolCreateFlags],
+	maxSets: [scalar u32],
+	poolSizeCount: [scalar u32],
+	pPoolSizes: [array poolSizeCount (struct VkDescriptorPoolSize)]
+});
+TraceSchema!(VkDescriptorSetAllocateInfo = VK_STRUCTURE_TYPE_DESCRIPTOR_SET_ALLOCATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	descriptorPool: [handle VkDescriptorPool],
+	descriptorSetCount: [scalar u32],
+	pSetLayouts: [array descriptorSetCount (handle VkDescriptorSetLayout)]
+});
+TraceSchema!(VkDescriptorImageInfo {
+	sampler: [handle VkSampler],
+	imageView: [handle VkImageView],
+	imageLayout: [scalar VkImageLayout]
+});
+TraceSchema!(VkDescriptorBufferInfo {
+	buffer: [handle VkBuffer],
+	offset: [scalar VkDeviceSize],
+	range: [scalar VkDeviceSize]
+});
+TraceSchema!(VkWriteDescriptorSet = VK_STRUCTURE_TYPE_WRITE_DESCRIPTOR_SET {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	dstSet: [handle VkDescriptorSet],
+	dstBinding: [scalar u32],
+	dstArrayElement: [scalar u32],
+	descriptorCount: [scalar u32],
+	descriptorType: [scalar VkDescriptorType],
+	pImageInfo: [array_if descriptorCount image_info_used (struct VkDescriptorImageInfo)],
+	pBufferInfo: [array_if descriptorCount buffer_info_used (struct VkDescriptorBufferInfo)],
+	pTexelBufferView: [array_if descriptorCount texel_buffer_view_used (handle VkBufferView)]
+});
+TraceSchema!(VkCopyDescriptorSet = VK_STRUCTURE_TYPE_COPY_DESCRIPTOR_SET {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	srcSet: [handle VkDescriptorSet],
+	srcBinding: [scalar u32],
+	srcArrayElement: [scalar u32],
+	dstSet: [handle VkDescriptorSet],
+	dstBinding: [scalar u32],
+	dstArrayElement: [scalar u32],
+	descriptorCount: [scalar u32]
+});
+TraceSchema!(VkFramebufferCreateInfo = VK_STRUCTURE_TYPE_FRAMEBUFFER_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkFramebufferCreateFlags],
+	renderPass: [handle VkRenderPass],
+	attachmentCount: [scalar u32],
+	pAttachments: [array attachmentCount (handle VkImageView)],
+	width: [scalar u32],
+	height: [scalar u32],
+	layers: [scalar u32]
+});
+TraceSchema!(VkAttachmentDescription {
+	flags: [scalar VkAttachmentDescriptionFlags],
+	format: [scalar VkFormat],
+	samples: [scalar VkSampleCountFlags],
+	loadOp: [scalar VkAttachmentLoadOp],
+	storeOp: [scalar VkAttachmentStoreOp],
+	stencilLoadOp: [scalar VkAttachmentLoadOp],
+	stencilStoreOp: [scalar VkAttachmentStoreOp],
+	initialLayout: [scalar VkImageLayout],
+	finalLayout: [scalar VkImageLayout]
+});
+TraceSchema!(VkAttachmentReference {
+	attachment: [scalar u32],
+	layout: [scalar VkImageLayout]
+});
+TraceSchema!(VkSubpassDescription {
+	flags: [scalar VkSubpassDescriptionFlags],
+	pipelineBindPoint: [scalar VkPipelineBindPoint],
+	inputAttachmentCount: [scalar u32],
+	pInputAttachments: [array inputAttachmentCount (struct VkAttachmentReference)],
+	colorAttachmentCount: [scalar u32],
+	pColorAttachments: [array colorAttachmentCount (struct VkAttachmentReference)],
+	pResolveAttachments: [array colorAttachmentCount (struct VkAttachmentReference)],
+	pDepthStencilAttachment: [ptr (struct VkAttachmentReference)],
+	preserveAttachmentCount: [scalar u32],
+	pPreserveAttachments: [array preserveAttachmentCount (scalar u32)]
+});
+TraceSchema!(VkSubpassDependency {
+	srcSubpass: [scalar u32],
+	dstSubpass: [scalar u32],
+	srcStageMask: [scalar VkPipelineStageFlags],
+	dstStageMask: [scalar VkPipelineStageFlags],
+	srcAccessMask: [scalar VkAccessFlags],
+	dstAccessMask: [scalar VkAccessFlags],
+	dependencyFlags: [scalar VkDependencyFlags]
+});
+TraceSchema!(VkRenderPassCreateInfo = VK_STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkRenderPassCreateFlags],
+	attachmentCount: [scalar u32],
+	pAttachments: [array attachmentCount (struct VkAttachmentDescription)],
+	subpassCount: [scalar u32],
+	pSubpasses: [array subpassCount (struct VkSubpassDescription)],
+	dependencyCount: [scalar u32],
+	pDependencies: [array dependencyCount (struct VkSubpassDependency)]
+});
+TraceSchema!(VkCommandPoolCreateInfo = VK_STRUCTURE_TYPE_COMMAND_POOL_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkCommandPoolCreateFlags],
+	queueFamilyIndex: [scalar u32]
+});
+TraceSchema!(VkCommandBufferAllocateInfo = VK_STRUCTURE_TYPE_COMMAND_BUFFER_ALLOCATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	commandPool: [handle VkCommandPool],
+	level: [scalar VkCommandBufferLevel],
+	commandBufferCount: [scalar u32]
+});
+TraceSchema!(VkCommandBufferInheritanceInfo = VK_STRUCTURE_TYPE_COMMAND_BUFFER_INHERITANCE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	renderPass: [handle VkRenderPass],
+	subpass: [scalar u32],
+	framebuffer: [handle VkFramebuffer],
+	occlusionQueryEnable: [scalar VkBool32],
+	queryFlags: [scalar VkQueryControlFlags],
+	pipelineStatistics: [scalar VkQueryPipelineStatisticFlags]
+});
+TraceSchema!(VkCommandBufferBeginInfo = VK_STRUCTURE_TYPE_COMMAND_BUFFER_BEGIN_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkCommandBufferUsageFlags],
+	pInheritanceInfo: [ptr (struct VkCommandBufferInheritanceInfo)]
+});
+TraceSchema!(VkBufferCopy {
+	srcOffset: [scalar VkDeviceSize],
+	dstOffset: [scalar VkDeviceSize],
+	size: [scalar VkDeviceSize]
+});
+TraceSchema!(VkImageSubresourceLayers {
+	aspectMask: [scalar VkImageAspectFlags],
+	mipLevel: [scalar u32],
+	baseArrayLayer: [scalar u32],
+	layerCount: [scalar u32]
+});
+TraceSchema!(VkImageCopy {
+	srcSubresource: [struct VkImageSubresourceLayers],
+	srcOffset: [struct VkOffset3D],
+	dstSubresource: [struct VkImageSubresourceLayers],
+	dstOffset: [struct VkOffset3D],
+	extent: [struct VkExtent3D]
+});
+TraceSchema!(VkImageBlit {
+	srcSubresource: [struct VkImageSubresourceLayers],
+	srcOffsets: [fixed 2 (struct VkOffset3D)],
+	dstSubresource: [struct VkImageSubresourceLayers],
+	dstOffsets: [fixed 2 (struct VkOffset3D)]
+});
+TraceSchema!(VkBufferImageCopy {
+	bufferOffset: [scalar VkDeviceSize],
+	bufferRowLength: [scalar u32],
+	bufferImageHeight: [scalar u32],
+	imageSubresource: [struct VkImageSubresourceLayers],
+	imageOffset: [struct VkOffset3D],
+	imageExtent: [struct VkExtent3D]
+});
+TraceSchema!(VkClearDepthStencilValue {
+	depth: [scalar c_float],
+	stencil: [scalar u32]
+});
+TraceSchema!(VkClearAttachment {
+	aspectMask: [scalar VkImageAspectFlags],
+	colorAttachment: [scalar u32],
+	clearValue: [raw VkClearValue]
+});
+TraceSchema!(VkClearRect {
+	rect: [struct VkRect2D],
+	baseArrayLayer: [scalar u32],
+	layerCount: [scalar u32]
+});
+TraceSchema!(VkImageResolve {
+	srcSubresource: [struct VkImageSubresourceLayers],
+	srcOffset: [struct VkOffset3D],
+	dstSubresource: [struct VkImageSubresourceLayers],
+	dstOffset: [struct VkOffset3D],
+	extent: [struct VkExtent3D]
+});
+TraceSchema!(VkMemoryBarrier = VK_STRUCTURE_TYPE_MEMORY_BARRIER {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	srcAccessMask: [scalar VkAccessFlags],
+	dstAccessMask: [scalar VkAccessFlags]
+});
+TraceSchema!(VkBufferMemoryBarrier = VK_STRUCTURE_TYPE_BUFFER_MEMORY_BARRIER {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	srcAccessMask: [scalar VkAccessFlags],
+	dstAccessMask: [scalar VkAccessFlags],
+	srcQueueFamilyIndex: [scalar u32],
+	dstQueueFamilyIndex: [scalar u32],
+	buffer: [handle VkBuffer],
+	offset: [scalar VkDeviceSize],
+	size: [scalar VkDeviceSize]
+});
+TraceSchema!(VkImageMemoryBarrier = VK_STRUCTURE_TYPE_IMAGE_MEMORY_BARRIER {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	srcAccessMask: [scalar VkAccessFlags],
+	dstAccessMask: [scalar VkAccessFlags],
+	oldLayout: [scalar VkImageLayout],
+	newLayout: [scalar VkImageLayout],
+	srcQueueFamilyIndex: [scalar u32],
+	dstQueueFamilyIndex: [scalar u32],
+	image: [handle VkImage],
+	subresourceRange: [struct VkImageSubresourceRange]
+});
+TraceSchema!(VkRenderPassBeginInfo = VK_STRUCTURE_TYPE_RENDER_PASS_BEGIN_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	renderPass: [handle VkRenderPass],
+	framebuffer: [handle VkFramebuffer],
+	renderArea: [struct VkRect2D],
+	clearValueCount: [scalar u32],
+	pClearValues: [array clearValueCount (raw VkClearValue)]
+});
+TraceSchema!(VkBindBufferMemoryInfo = VK_STRUCTURE_TYPE_BIND_BUFFER_MEMORY_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	buffer: [handle VkBuffer],
+	memory: [handle VkDeviceMemory],
+	memoryOffset: [scalar VkDeviceSize]
+});
+TraceSchema!(VkBindImageMemoryInfo = VK_STRUCTURE_TYPE_BIND_IMAGE_MEMORY_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	image: [handle VkImage],
+	memory: [handle VkDeviceMemory],
+	memoryOffset: [scalar VkDeviceSize]
+});
+TraceSchema!(VkMemoryDedicatedAllocateInfo = VK_STRUCTURE_TYPE_MEMORY_DEDICATED_ALLOCATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	image: [handle VkImage],
+	buffer: [handle VkBuffer]
+});
+TraceSchema!(VkMemoryAllocateFlagsInfo = VK_STRUCTURE_TYPE_MEMORY_ALLOCATE_FLAGS_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkMemoryAllocateFlags],
+	deviceMask: [scalar u32]
+});
+TraceSchema!(VkDeviceGroupRenderPassBeginInfo = VK_STRUCTURE_TYPE_DEVICE_GROUP_RENDER_PASS_BEGIN_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	deviceMask: [scalar u32],
+	deviceRenderAreaCount: [scalar u32],
+	pDeviceRenderAreas: [array deviceRenderAreaCount (struct VkRect2D)]
+});
+TraceSchema!(VkDeviceGroupCommandBufferBeginInfo = VK_STRUCTURE_TYPE_DEVICE_GROUP_COMMAND_BUFFER_BEGIN_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	deviceMask: [scalar u32]
+});
+TraceSchema!(VkDeviceGroupSubmitInfo = VK_STRUCTURE_TYPE_DEVICE_GROUP_SUBMIT_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	waitSemaphoreCount: [scalar u32],
+	pWaitSemaphoreDeviceIndices: [array waitSemaphoreCount (scalar u32)],
+	commandBufferCount: [scalar u32],
+	pCommandBufferDeviceMasks: [array commandBufferCount (scalar u32)],
+	signalSemaphoreCount: [scalar u32],
+	pSignalSemaphoreDeviceIndices: [array signalSemaphoreCount (scalar u32)]
+});
+TraceSchema!(VkDeviceGroupBindSparseInfo = VK_STRUCTURE_TYPE_DEVICE_GROUP_BIND_SPARSE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	resourceDeviceIndex: [scalar u32],
+	memoryDeviceIndex: [scalar u32]
+});
+TraceSchema!(VkBindBufferMemoryDeviceGroupInfo = VK_STRUCTURE_TYPE_BIND_BUFFER_MEMORY_DEVICE_GROUP_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	deviceIndexCount: [scalar u32],
+	pDeviceIndices: [array deviceIndexCount (scalar u32)]
+});
+TraceSchema!(VkBindImageMemoryDeviceGroupInfo = VK_STRUCTURE_TYPE_BIND_IMAGE_MEMORY_DEVICE_GROUP_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	deviceIndexCount: [scalar u32],
+	pDeviceIndices: [array deviceIndexCount (scalar u32)],
+	splitInstanceBindRegionCount: [scalar u32],
+	pSplitInstanceBindRegions: [array splitInstanceBindRegionCount (struct VkRect2D)]
+});
+TraceSchema!(VkDeviceGroupDeviceCreateInfo = VK_STRUCTURE_TYPE_DEVICE_GROUP_DEVICE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	physicalDeviceCount: [scalar u32],
+	pPhysicalDevices: [array physicalDeviceCount (handle VkPhysicalDevice)]
+});
+TraceSchema!(VkBufferMemoryRequirementsInfo2 = VK_STRUCTURE_TYPE_BUFFER_MEMORY_REQUIREMENTS_INFO_2 {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	buffer: [handle VkBuffer]
+});
+TraceSchema!(VkImageMemoryRequirementsInfo2 = VK_STRUCTURE_TYPE_IMAGE_MEMORY_REQUIREMENTS_INFO_2 {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	image: [handle VkImage]
+});
+TraceSchema!(VkImageSparseMemoryRequirementsInfo2 = VK_STRUCTURE_TYPE_IMAGE_SPARSE_MEMORY_REQUIREMENTS_INFO_2 {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	image: [handle VkImage]
+});
+TraceSchema!(VkPhysicalDeviceImageFormatInfo2 = VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_IMAGE_FORMAT_INFO_2 {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	format: [scalar VkFormat],
+	_type: [scalar VkImageType],
+	tiling: [scalar VkImageTiling],
+	usage: [scalar VkImageUsageFlags],
+	flags: [scalar VkImageCreateFlags]
+});
+TraceSchema!(VkPhysicalDeviceSparseImageFormatInfo2 = VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SPARSE_IMAGE_FORMAT_INFO_2 {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	format: [scalar VkFormat],
+	_type: [scalar VkImageType],
+	samples: [scalar VkSampleCountFlags],
+	usage: [scalar VkImageUsageFlags],
+	tiling: [scalar VkImageTiling]
+});
+TraceSchema!(VkInputAttachmentAspectReference {
+	subpass: [scalar u32],
+	inputAttachmentIndex: [scalar u32],
+	aspectMask: [scalar VkImageAspectFlags]
+});
+TraceSchema!(VkRenderPassInputAttachmentAspectCreateInfo = VK_STRUCTURE_TYPE_RENDER_PASS_INPUT_ATTACHMENT_ASPECT_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	aspectReferenceCount: [scalar u32],
+	pAspectReferneces: [array aspectReferenceCount (struct VkInputAttachmentAspectReference)]
+});
+TraceSchema!(VkImageViewUsageCreateInfo = VK_STRUCTURE_TYPE_IMAGE_VIEW_USAGE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	usage: [scalar VkImageUsageFlags]
+});
+TraceSchema!(VkPipelineTessellationDomainOriginStateCreateInfo = VK_STRUCTURE_TYPE_PIPELINE_TESSELLATION_DOMAIN_ORIGIN_STATE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	domainOrigin: [scalar VkTessellationDomainOrigin]
+});
+TraceSchema!(VkRenderPassMultiviewCreateInfo = VK_STRUCTURE_TYPE_RENDER_PASS_MULTIVIEW_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	subpassCount: [scalar u32],
+	pViewMasks: [array subpassCount (scalar u32)],
+	dependencyCount: [scalar u32],
+	pViewOffsets: [array dependencyCount (scalar i32)],
+	correlationMaskCount: [scalar u32],
+	pCorrelationMasks: [array correlationMaskCount (scalar u32)]
+});
+TraceSchema!(VkDeviceQueueInfo2 = VK_STRUCTURE_TYPE_DEVICE_QUEUE_INFO_2 {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkDeviceQueueCreateFlags],
+	queueFamilyIndex: [scalar u32],
+	queueIndex: [scalar u32]
+});
+TraceSchema!(VkProtectedSubmitInfo = VK_STRUCTURE_TYPE_PROTECTED_SUBMIT_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	protectedSubmit: [scalar VkBool32]
+});
+TraceSchema!(VkSamplerYcbcrConversionCreateInfo = VK_STRUCTURE_TYPE_SAMPLER_YCBCR_CONVERSION_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	format: [scalar VkFormat],
+	ycbcrModel: [scalar VkSamplerYcbcrModelConversion],
+	ycbcrRange: [scalar VkSamplerYcbcrRange],
+	components: [struct VkComponentMapping],
+	xChromaOffset: [scalar VkChromaLocation],
+	yChromaOffset: [scalar VkChromaLocation],
+	chromaFilter: [scalar VkFilter],
+	forceExplicitReconstruction: [scalar VkBool32]
+});
+TraceSchema!(VkSamplerYcbcrConversionInfo = VK_STRUCTURE_TYPE_SAMPLER_YCBCR_CONVERSION_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	conversion: [handle VkSamplerYcbcrConversion]
+});
+TraceSchema!(VkBindImagePlaneMemoryInfo = VK_STRUCTURE_TYPE_BIND_IMAGE_PLANE_MEMORY_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	planeAspect: [scalar VkImageAspectFlags]
+});
+TraceSchema!(VkImagePlaneMemoryRequirementsInfo = VK_STRUCTURE_TYPE_IMAGE_PLANE_MEMORY_REQUIREMENTS_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	planeAspect: [scalar VkImageAspectFlags]
+});
+TraceSchema!(VkPhysicalDeviceExternalImageFormatInfo = VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTERNAL_IMAGE_FORMAT_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	handleType: [scalar VkExternalMemoryHandleTypeFlags]
+});
+TraceSchema!(VkPhysicalDeviceExternalBufferInfo = VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTERNAL_BUFFER_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkBufferCreateFlags],
+	usage: [scalar VkBufferUsageFlags],
+	handleType: [scalar VkExternalMemoryHandleTypeFlags]
+});
+TraceSchema!(VkExternalMemoryImageCreateInfo = VK_STRUCTURE_TYPE_EXTERNAL_MEMORY_IMAGE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	handleTypes: [scalar VkExternalMemoryHandleTypeFlags]
+});
+TraceSchema!(VkExternalMemoryBufferCreateInfo = VK_STRUCTURE_TYPE_EXTERNAL_MEMORY_BUFFER_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	handleTypes: [scalar VkExternalMemoryHandleTypeFlags]
+});
+TraceSchema!(VkExportMemoryAllocateInfo = VK_STRUCTURE_TYPE_EXPORT_MEMORY_ALLOCATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	handleTypes: [scalar VkExternalMemoryHandleTypeFlags]
+});
+TraceSchema!(VkPhysicalDeviceExternalFenceInfo = VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTERNAL_FENCE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	handleType: [scalar VkExternalFenceHandleTypeFlags]
+});
+TraceSchema!(VkExportFenceCreateInfo = VK_STRUCTURE_TYPE_EXPORT_FENCE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	handleTypes: [scalar VkExternalFenceHandleTypeFlags]
+});
+TraceSchema!(VkExportSemaphoreCreateInfo = VK_STRUCTURE_TYPE_EXPORT_SEMAPHORE_CREATE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	handleTypes: [scalar VkExternalSemaphoreHandleTypeFlags]
+});
+TraceSchema!(VkPhysicalDeviceExternalSemaphoreInfo = VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTERNAL_SEMAPHORE_INFO {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	handleType: [scalar VkExternalSemaphoreHandleTypeFlags]
+});
+#[cfg(feature = "VK_EXT_blend_operation_advanced")] TraceSchema!(VkPipelineColorBlendAdvancedStateCreateInfoEXT = VK_STRUCTURE_TYPE_PIPELINE_COLOR_BLEND_ADVANCED_STATE_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	srcPremultiplied: [scalar VkBool32],
+	dstPremultiplied: [scalar VkBool32],
+	blendOverlap: [scalar VkBlendOverlapEXT]
+});
+#[cfg(feature = "VK_NV_clip_space_w_scaling")] TraceSchema!(VkViewportWScalingNV {
+	xcoeff: [scalar c_float],
+	ycoeff: [scalar c_float]
+});
+#[cfg(feature = "VK_NV_clip_space_w_scaling")] TraceSchema!(VkPipelineViewportWScalingStateCreateInfoNV = VK_STRUCTURE_TYPE_PIPELINE_VIEWPORT_W_SCALING_STATE_CREATE_INFO_NV {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	viewportWScalingEnable: [scalar VkBool32],
+	viewportCount: [scalar u32],
+	pViewportWScalings: [array viewportCount (struct VkViewportWScalingNV)]
+});
+#[cfg(feature = "VK_EXT_conservative_rasterization")] TraceSchema!(VkPipelineRasterizationConservativeStateCreateInfoEXT = VK_STRUCTURE_TYPE_PIPELINE_RASTERIZATION_CONSERVATIVE_STATE_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineRasterizationConservativeStateCreateFlagsEXT],
+	conservativeRasterizationMode: [scalar VkConservativeRasterizationModeEXT],
+	extraPrimitiveOverestimationSize: [scalar c_float]
+});
+#[cfg(feature = "VK_EXT_debug_marker")] TraceSchema!(VkDebugMarkerObjectNameInfoEXT = VK_STRUCTURE_TYPE_DEBUG_MARKER_OBJECT_NAME_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	objectType: [scalar VkDebugReportObjectTypeEXT],
+	object: [scalar u64],
+	pObjectName: [str]
+});
+#[cfg(feature = "VK_EXT_debug_marker")] TraceSchema!(VkDebugMarkerObjectTagInfoEXT = VK_STRUCTURE_TYPE_DEBUG_MARKER_OBJECT_TAG_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	objectType: [scalar VkDebugReportObjectTypeEXT],
+	object: [scalar u64],
+	tagName: [scalar u64],
+	tagSize: [scalar size_t],
+	pTag: [bytes tagSize]
+});
+#[cfg(feature = "VK_EXT_debug_marker")] TraceSchema!(VkDebugMarkerMarkerInfoEXT = VK_STRUCTURE_TYPE_DEBUG_MARKER_MARKER_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	pMarkerName: [str],
+	color: [fixed 4 (scalar c_float)]
+});
+#[cfg(feature = "VK_EXT_debug_report")] TraceSchema!(VkDebugReportCallbackCreateInfoEXT = VK_STRUCTURE_TYPE_DEBUG_REPORT_CALLBACK_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkDebugReportFlagsEXT],
+	pfnCallback: [opaque],
+	pUserData: [opaque]
+});
+#[cfg(feature = "VK_EXT_debug_utils")] TraceSchema!(VkDebugUtilsObjectNameInfoEXT = VK_STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_NAME_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	objectType: [scalar VkObjectType],
+	objectHandle: [scalar u64],
+	pObjectName: [str]
+});
+#[cfg(feature = "VK_EXT_debug_utils")] TraceSchema!(VkDebugUtilsObjectTagInfoEXT = VK_STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_TAG_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	objectType: [scalar VkObjectType],
+	objectHandle: [scalar u64],
+	tagName: [scalar u64],
+	tagSize: [scalar size_t],
+	pTag: [bytes tagSize]
+});
+#[cfg(feature = "VK_EXT_debug_utils")] TraceSchema!(VkDebugUtilsLabelEXT = VK_STRUCTURE_TYPE_DEBUG_UTILS_LABEL_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	pLabelName: [str],
+	color: [fixed 4 (scalar c_float)]
+});
+#[cfg(feature = "VK_EXT_debug_utils")] TraceSchema!(VkDebugUtilsMessengerCreateInfoEXT = VK_STRUCTURE_TYPE_DEBUG_UTILS_MESSENGER_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkDebugUtilsMessengerCreateFlagsEXT],
+	messageSeverity: [scalar VkDebugUtilsMessageSeverityFlagsEXT],
+	messageType: [scalar VkDebugUtilsMessageTypeFlagsEXT],
+	pfnUserCallback: [opaque],
+	pUserData: [opaque]
+});
+#[cfg(feature = "VK_NV_dedicated_allocation")] TraceSchema!(VkDedicatedAllocationImageCreateInfoNV = VK_STRUCTURE_TYPE_DEDICATED_ALLOCATION_IMAGE_CREATE_INFO_NV {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	dedicatedAllocation: [scalar VkBool32]
+});
+#[cfg(feature = "VK_NV_dedicated_allocation")] TraceSchema!(VkDedicatedAllocationBufferCreateInfoNV = VK_STRUCTURE_TYPE_DEDICATED_ALLOCATION_BUFFER_CREATE_INFO_NV {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	dedicatedAllocation: [scalar VkBool32]
+});
+#[cfg(feature = "VK_NV_dedicated_allocation")] TraceSchema!(VkDedicatedAllocationMemoryAllocateInfoNV = VK_STRUCTURE_TYPE_DEDICATED_ALLOCATION_MEMORY_ALLOCATE_INFO_NV {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	image: [handle VkImage],
+	buffer: [handle VkBuffer]
+});
+#[cfg(feature = "VK_NVX_device_generated_commands")] TraceSchema!(VkDeviceGeneratedCommandsFeaturesNVX = VK_STRUCTURE_TYPE_DEVICE_GENERATED_COMMANDS_FEATURES_NVX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	computeBindingPointSupport: [scalar VkBool32]
+});
+#[cfg(feature = "VK_NVX_device_generated_commands")] TraceSchema!(VkDeviceGeneratedCommandsLimitsNVX = VK_STRUCTURE_TYPE_DEVICE_GENERATED_COMMANDS_LIMITS_NVX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	maxIndirectCommandsLayoutTokenCount: [scalar u32],
+	maxObjectEntryCounts: [scalar u32],
+	minSequenceCountBufferOffsetAlignment: [scalar u32],
+	minSequenceIndexBufferOffsetAlignment: [scalar u32],
+	minCommandsTokenBufferOffsetAlignment: [scalar u32]
+});
+#[cfg(feature = "VK_NVX_device_generated_commands")] TraceSchema!(VkIndirectCommandsTokenNVX {
+	tokenType: [scalar VkIndirectCommandsTokenTypeNVX],
+	buffer: [handle VkBuffer],
+	offset: [scalar VkDeviceSize]
+});
+#[cfg(feature = "VK_NVX_device_generated_commands")] TraceSchema!(VkIndirectCommandsLayoutTokenNVX {
+	tokenType: [scalar VkIndirectCommandsTokenTypeNVX],
+	bindingUnit: [scalar u32],
+	dynamicCount: [scalar u32],
+	divisor: [scalar u32]
+});
+#[cfg(feature = "VK_NVX_device_generated_commands")] TraceSchema!(VkIndirectCommandsLayoutCreateInfoNVX = VK_STRUCTURE_TYPE_INDIRECT_COMMANDS_LAYOUT_CREATE_INFO_NVX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	pipelineBindPoint: [scalar VkPipelineBindPoint],
+	flags: [scalar VkIndirectCommandsLayoutUsageFlagsNVX],
+	tokenCount: [scalar u32],
+	pTokens: [array tokenCount (struct VkIndirectCommandsLayoutTokenNVX)]
+});
+#[cfg(feature = "VK_NVX_device_generated_commands")] TraceSchema!(VkCmdProcessCommandsInfoNVX = VK_STRUCTURE_TYPE_CMD_PROCESS_COMMANDS_INFO_NVX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	objectTable: [handle VkObjectTableNVX],
+	indirectCommandsLayout: [handle VkIndirectCommandsLayoutNVX],
+	indirectCommandsTokenCount: [scalar u32],
+	pIndirectCommandsTokens: [array indirectCommandsTokenCount (struct VkIndirectCommandsTokenNVX)],
+	maxSequencesCount: [scalar u32],
+	targetCommandBuffer: [handle VkCommandBuffer],
+	sequencesCountBuffer: [handle VkBuffer],
+	sequencesCountOffset: [scalar VkDeviceSize],
+	sequencesIndexBuffer: [handle VkBuffer],
+	sequencesIndexOffset: [scalar VkDeviceSize]
+});
+#[cfg(feature = "VK_NVX_device_generated_commands")] TraceSchema!(VkCmdReserveSpaceForCommandsInfoNVX = VK_STRUCTURE_TYPE_CMD_RESERVE_SPACE_FOR_COMMANDS_INFO_NVX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	objectTable: [handle VkObjectTableNVX],
+	indirectCommandsLayout: [handle VkIndirectCommandsLayoutNVX],
+	maxSequencesCount: [scalar u32]
+});
+#[cfg(feature = "VK_NVX_device_generated_commands")] TraceSchema!(VkObjectTableCreateInfoNVX = VK_STRUCTURE_TYPE_OBJECT_TABLE_CREATE_INFO_NVX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	objectCount: [scalar u32],
+	pObjectEntryTypes: [array objectCount (scalar VkObjectEntryTypeNVX)],
+	pObjectEntryCounts: [array objectCount (scalar u32)],
+	pObjectEntryUsageFlags: [array objectCount (scalar VkObjectEntryUsageFlagsNVX)],
+	maxUniformBuffersPerDescriptor: [scalar u32],
+	maxStorageBuffersPerDescriptor: [scalar u32],
+	maxStorageImagesPerDescriptor: [scalar u32],
+	maxSampledImagesPerDescriptor: [scalar u32],
+	maxPipelineLayouts: [scalar u32]
+});
+#[cfg(feature = "VK_KHX_device_group_creation")] TraceSchema!(VkDeviceGroupDeviceCreateInfoKHX = VK_STRUCTURE_TYPE_DEVICE_GROUP_DEVICE_CREATE_INFO_KHX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	physicalDeviceCount: [scalar u32],
+	pPhysicalDevices: [array physicalDeviceCount (handle VkPhysicalDevice)]
+});
+#[cfg(feature = "VK_KHX_device_group")] TraceSchema!(VkBindBufferMemoryInfoKHX = VK_STRUCTURE_TYPE_BIND_BUFFER_MEMORY_INFO_KHX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	buffer: [handle VkBuffer],
+	memory: [handle VkDeviceMemory],
+	memoryOffset: [scalar VkDeviceSize],
+	deviceIndexCount: [scalar u32],
+	pDeviceIndices: [array deviceIndexCount (scalar u32)]
+});
+#[cfg(feature = "VK_KHX_device_group")] TraceSchema!(VkBindImageMemoryInfoKHX = VK_STRUCTURE_TYPE_BIND_IMAGE_MEMORY_INFO_KHX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	image: [handle VkImage],
+	memory: [handle VkDeviceMemory],
+	memoryOffset: [scalar VkDeviceSize],
+	deviceIndexCount: [scalar u32],
+	pDeviceIndices: [array deviceIndexCount (scalar u32)],
+	SFRRectCount: [scalar u32],
+	pSFRRects: [array SFRRectCount (struct VkRect2D)]
+});
+#[cfg(feature = "VK_KHX_device_group")] TraceSchema!(VkDeviceGroupPresentCapabilitiesKHX = VK_STRUCTURE_TYPE_DEVICE_GROUP_PRESENT_CAPABILITIES_KHX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	presentMask: [fixed VK_MAX_DEVICE_GROUP_SIZE_KHX (scalar u32)],
+	modes: [scalar VkDeviceGroupPresentModeFlagsKHX]
+});
+#[cfg(feature = "VK_KHX_device_group")] TraceSchema!(VkImageSwapchainCreateInfoKHX = VK_STRUCTURE_TYPE_IMAGE_SWAPCHAIN_CREATE_INFO_KHX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	swapchain: [handle VkSwapchainKHR]
+});
+#[cfg(feature = "VK_KHX_device_group")] TraceSchema!(VkBindImageMemorySwapchainInfoKHX = VK_STRUCTURE_TYPE_BIND_IMAGE_MEMORY_SWAPCHAIN_INFO_KHX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	swapchain: [handle VkSwapchainKHR],
+	imageIndex: [scalar u32]
+});
+#[cfg(feature = "VK_KHX_device_group")] TraceSchema!(VkAcquireNextImageInfoKHX = VK_STRUCTURE_TYPE_ACQUIRE_NEXT_IMAGE_INFO_KHX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	swapchain: [handle VkSwapchainKHR],
+	timeout: [scalar u64],
+	semaphore: [handle VkSemaphore],
+	fence: [handle VkFence],
+	deviceMask: [scalar u32]
+});
+#[cfg(feature = "VK_KHX_device_group")] TraceSchema!(VkDeviceGroupPresentInfoKHX = VK_STRUCTURE_TYPE_DEVICE_GROUP_PRESENT_INFO_KHX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	swapchainCount: [scalar u32],
+	pDeviceMasks: [array swapchainCount (scalar u32)],
+	mode: [scalar VkDeviceGroupPresentModeFlagsKHX]
+});
+#[cfg(feature = "VK_KHX_device_group")] TraceSchema!(VkDeviceGroupSwapchainCreateInfoKHX = VK_STRUCTURE_TYPE_DEVICE_GROUP_SWAPCHAIN_CREATE_INFO_KHX {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	modes: [scalar VkDeviceGroupPresentModeFlagsKHX]
+});
+#[cfg(feature = "VK_EXT_discard_rectangles")] TraceSchema!(VkPipelineDiscardRectangleStateCreateInfoEXT = VK_STRUCTURE_TYPE_PIPELINE_DISCARD_RECTANGLE_STATE_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineDiscardRectangleStateCreateFlagsEXT],
+	discardRectangleMode: [scalar VkDiscardRectangleModeEXT],
+	discardRectangleCount: [scalar u32],
+	pDiscardRectangles: [array discardRectangleCount (struct VkRect2D)]
+});
+#[cfg(feature = "VK_EXT_display_control")] TraceSchema!(VkDisplayPowerInfoEXT = VK_STRUCTURE_TYPE_DISPLAY_POWER_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	powerState: [scalar VkDisplayPowerStateEXT]
+});
+#[cfg(feature = "VK_EXT_display_control")] TraceSchema!(VkDeviceEventInfoEXT = VK_STRUCTURE_TYPE_DEVICE_EVENT_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	deviceEvent: [scalar VkDeviceEventTypeEXT]
+});
+#[cfg(feature = "VK_EXT_display_control")] TraceSchema!(VkDisplayEventInfoEXT = VK_STRUCTURE_TYPE_DISPLAY_EVENT_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	displayEvent: [scalar VkDisplayEventTypeEXT]
+});
+#[cfg(feature = "VK_EXT_display_control")] TraceSchema!(VkSwapchainCounterCreateInfoEXT = VK_STRUCTURE_TYPE_SWAPCHAIN_COUNTER_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	surfaceCounters: [scalar VkSurfaceCounterFlagsEXT]
+});
+#[cfg(feature = "VK_KHR_display")] TraceSchema!(VkDisplayModeParametersKHR {
+	visibleRegion: [struct VkExtent2D],
+	refreshRate: [scalar u32]
+});
+#[cfg(feature = "VK_KHR_display")] TraceSchema!(VkDisplayModeCreateInfoKHR = VK_STRUCTURE_TYPE_DISPLAY_MODE_CREATE_INFO_KHR {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkDisplayModeCreateFlagsKHR],
+	parameters: [struct VkDisplayModeParametersKHR]
+});
+#[cfg(feature = "VK_KHR_display")] TraceSchema!(VkDisplaySurfaceCreateInfoKHR = VK_STRUCTURE_TYPE_DISPLAY_SURFACE_CREATE_INFO_KHR {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkDisplaySurfaceCreateFlagsKHR],
+	displayMode: [handle VkDisplayModeKHR],
+	planeIndex: [scalar u32],
+	planeStackIndex: [scalar u32],
+	transform: [scalar VkSurfaceTransformFlagsKHR],
+	globalAlpha: [scalar c_float],
+	alphaMode: [scalar VkDisplayPlaneAlphaFlagsKHR],
+	imageExtent: [struct VkExtent2D]
+});
+#[cfg(feature = "VK_KHR_display_swapchain")] TraceSchema!(VkDisplayPresentInfoKHR = VK_STRUCTURE_TYPE_DISPLAY_PRESENT_INFO_KHR {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	srcRect: [struct VkRect2D],
+	dstRect: [struct VkRect2D],
+	persistent: [scalar VkBool32]
+});
+#[cfg(feature = "VK_GOOGLE_display_timing")] TraceSchema!(VkPresentTimesInfoGOOGLE = VK_STRUCTURE_TYPE_PRESENT_TIMES_INFO_GOOGLE {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	swapchainCount: [scalar u32]
+});
+#[cfg(feature = "VK_EXT_external_memory_host")] TraceSchema!(VkMemoryHostPointerPropertiesEXT = VK_STRUCTURE_TYPE_MEMORY_HOST_POINTER_PROPERTIES_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	memoryTypeBits: [scalar u32]
+});
+#[cfg(feature = "VK_NV_external_memory")] TraceSchema!(VkExternalMemoryImageCreateInfoNV = VK_STRUCTURE_TYPE_EXTERNAL_MEMORY_IMAGE_CREATE_INFO_NV {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	handleTypes: [scalar VkExternalMemoryHandleTypeFlagsNV]
+});
+#[cfg(feature = "VK_NV_external_memory")] TraceSchema!(VkExportMemoryAllocateInfoNV = VK_STRUCTURE_TYPE_EXPORT_MEMORY_ALLOCATE_INFO_NV {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	handleTypes: [scalar VkExternalMemoryHandleTypeFlagsNV]
+});
+#[cfg(feature = "VK_NV_fragment_coverage_to_color")] TraceSchema!(VkPipelineCoverageToColorStateCreateInfoNV = VK_STRUCTURE_TYPE_PIPELINE_COVERAGE_TO_COLOR_STATE_CREATE_INFO_NV {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineCoverageToColorStateCreateFlagsNV],
+	coverageToColorEnable: [scalar VkBool32],
+	coverageToColorLocation: [scalar u32]
+});
+#[cfg(feature = "VK_NV_framebuffer_mixed_samples")] TraceSchema!(VkPipelineCoverageModulationStateCreateInfoNV = VK_STRUCTURE_TYPE_PIPELINE_COVERAGE_MODULATION_STATE_CREATE_INFO_NV {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineCoverageModulationStateCreateFlagsNV],
+	coverageModulationMode: [scalar VkCoverageModulationModeNV],
+	coverageModulationTableEnable: [scalar VkBool32],
+	coverageModulationTableCount: [scalar u32],
+	pCoverageModulationTable: [array coverageModulationTableCount (scalar c_float)]
+});
+#[cfg(feature = "VK_KHR_get_surface_capabilities2")] TraceSchema!(VkPhysicalDeviceSurfaceInfo2KHR = VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SURFACE_INFO_2_KHR {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	surface: [handle VkSurfaceKHR]
+});
+#[cfg(feature = "VK_EXT_global_priority")] TraceSchema!(VkDeviceQueueGlobalPriorityCreateInfoEXT = VK_STRUCTURE_TYPE_DEVICE_QUEUE_GLOBAL_PRIORITY_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	globalPriority: [scalar VkQueueGlobalPriorityEXT]
+});
+#[cfg(feature = "VK_EXT_hdr_metadata")] TraceSchema!(VkXYColorEXT {
+	x: [scalar c_float],
+	y: [scalar c_float]
+});
+#[cfg(feature = "VK_EXT_hdr_metadata")] TraceSchema!(VkHdrMetadataEXT = VK_STRUCTURE_TYPE_HDR_METADATA_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	displayPrimaryRed: [struct VkXYColorEXT],
+	displayPrimaryGreen: [struct VkXYColorEXT],
+	displayPrimaryBlur: [struct VkXYColorEXT],
+	whitePoint: [struct VkXYColorEXT],
+	maxLuminance: [scalar c_float],
+	minLuminance: [scalar c_float],
+	maxContentLightLevel: [scalar c_float],
+	maxFrameAverageLightLevel: [scalar c_float]
+});
+#[cfg(feature = "VK_AMD_rasterization_order")] TraceSchema!(VkPipelineRasterizationStateRasterizationOrderAMD = VK_STRUCTURE_TYPE_PIPELINE_RASTERIZATION_STATE_RASTERIZATION_ORDER_AMD {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	rasterizationOrder: [scalar VkRasterizationOrderAMD]
+});
+#[cfg(feature = "VK_EXT_sample_locations")] TraceSchema!(VkSampleLocationEXT {
+	x: [scalar c_float],
+	y: [scalar c_float]
+});
+#[cfg(feature = "VK_EXT_sample_locations")] TraceSchema!(VkSampleLocationsInfoEXT = VK_STRUCTURE_TYPE_SAMPLE_LOCATIONS_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	sampleLocationsPerPixel: [scalar VkSampleCountFlags],
+	sampleLocationGridSize: [struct VkExtent2D],
+	sampleLocationsCount: [scalar u32],
+	pSampleLocations: [array sampleLocationsCount (struct VkSampleLocationEXT)]
+});
+#[cfg(feature = "VK_EXT_sample_locations")] TraceSchema!(VkAttachmentSampleLocationsEXT {
+	attachmentIndex: [scalar u32],
+	sampleLocationsInfo: [struct VkSampleLocationsInfoEXT]
+});
+#[cfg(feature = "VK_EXT_sample_locations")] TraceSchema!(VkSubpassSampleLocationsEXT {
+	subpassIndex: [scalar u32],
+	sampleLocationsInfo: [struct VkSampleLocationsInfoEXT]
+});
+#[cfg(feature = "VK_EXT_sample_locations")] TraceSchema!(VkRenderPassSampleLocationsBeginInfoEXT = VK_STRUCTURE_TYPE_RENDER_PASS_SAMPLE_LOCATIONS_BEGIN_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	attachmentInitialSampleLocationsCount: [scalar u32],
+	pAttachmentInitialSampleLocations: [array attachmentInitialSampleLocationsCount (struct VkAttachmentSampleLocationsEXT)],
+	postSubpassSampleLocationsCount: [scalar u32],
+	pPostSubpassSampleLocations: [array postSubpassSampleLocationsCount (struct VkSubpassSampleLocationsEXT)]
+});
+#[cfg(feature = "VK_EXT_sample_locations")] TraceSchema!(VkPipelineSampleLocationsStateCreateInfoEXT = VK_STRUCTURE_TYPE_PIPELINE_SAMPLE_LOCATIONS_STATE_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	sampleLocationsEnable: [scalar VkBool32],
+	sampleLocationsInfo: [struct VkSampleLocationsInfoEXT]
+});
+#[cfg(feature = "VK_EXT_sampler_filter_minmax")] TraceSchema!(VkSamplerReductionModeCreateInfoEXT = VK_STRUCTURE_TYPE_SAMPLER_REDUCTION_MODE_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	reductionMode: [scalar VkSamplerReductionModeEXT]
+});
+#[cfg(feature = "VK_KHR_swapchain")] TraceSchema!(VkSwapchainCreateInfoKHR = VK_STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkSwapchainCreateFlagsKHR],
+	surface: [handle VkSurfaceKHR],
+	minImageCount: [scalar u32],
+	imageFormat: [scalar VkFormat],
+	imageColorSpace: [scalar VkColorSpaceKHR],
+	imageExtent: [struct VkExtent2D],
+	imageArrayLayers: [scalar u32],
+	imageUsage: [scalar VkImageUsageFlags],
+	imageSharingMode: [scalar VkSharingMode],
+	queueFamilyIndexCount: [scalar u32],
+	pQueueFamilyIndices: [array queueFamilyIndexCount (scalar u32)],
+	preTransform: [scalar VkSurfaceTransformFlagsKHR],
+	compositeAlpha: [scalar VkCompositeAlphaFlagsKHR],
+	presentMode: [scalar VkPresentModeKHR],
+	clipped: [scalar VkBool32],
+	oldSwapchain: [handle VkSwapchainKHR]
+});
+#[cfg(feature = "VK_KHR_swapchain")] TraceSchema!(VkPresentInfoKHR = VK_STRUCTURE_TYPE_PRESENT_INFO_KHR {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	waitSemaphoreCount: [scalar u32],
+	pWaitSemaphores: [array waitSemaphoreCount (handle VkSemaphore)],
+	swapchainCount: [scalar u32],
+	pSwapchains: [array swapchainCount (handle VkSwapchainKHR)],
+	pImageIndices: [array swapchainCount (scalar u32)],
+	pResults: [output]
+});
+#[cfg(feature = "VK_EXT_validation_cache")] TraceSchema!(VkValidationCacheCreateInfoEXT = VK_STRUCTURE_TYPE_VALIDATION_CACHE_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkValidationCacheCreateFlagsEXT],
+	initialDataSize: [scalar size_t],
+	pInitialData: [bytes initialDataSize]
+});
+#[cfg(feature = "VK_EXT_validation_cache")] TraceSchema!(VkShaderModuleValidationCacheCreateInfoEXT = VK_STRUCTURE_TYPE_SHADER_MODULE_VALIDATION_CACHE_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	validationCache: [handle VkValidationCacheEXT]
+});
+#[cfg(feature = "VK_EXT_validation_flags")] TraceSchema!(VkValidationFlagsEXT = VK_STRUCTURE_TYPE_VALIDATION_FLAGS_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	disabledValidationCheckCount: [scalar u32],
+	pDisabledValidationChecks: [array disabledValidationCheckCount (scalar VkValidationCheckEXT)]
+});
+#[cfg(feature = "VK_EXT_vertex_attribute_divisor")] TraceSchema!(VkVertexInputBindingDivisorDescriptionEXT {
+	binding: [scalar u32],
+	divisor: [scalar u32]
+});
+#[cfg(feature = "VK_EXT_vertex_attribute_divisor")] TraceSchema!(VkPipelineVertexInputDivisorStateCreateInfoEXT = VK_STRUCTURE_TYPE_PIPELINE_VERTEX_INPUT_DIVISOR_STATE_CREATE_INFO_EXT {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	vertexBindingDivisorCount: [scalar u32],
+	pVertexBindingDivisors: [array vertexBindingDivisorCount (struct VkVertexInputBindingDivisorDescriptionEXT)]
+});
+#[cfg(feature = "VK_NV_viewport_swizzle")] TraceSchema!(VkViewportSwizzleNV {
+	x: [scalar VkViewportCoordinateSwizzleNV],
+	y: [scalar VkViewportCoordinateSwizzleNV],
+	z: [scalar VkViewportCoordinateSwizzleNV],
+	w: [scalar VkViewportCoordinateSwizzleNV]
+});
+#[cfg(feature = "VK_NV_viewport_swizzle")] TraceSchema!(VkPipelineViewportSwizzleStateCreateInfoNV = VK_STRUCTURE_TYPE_PIPELINE_VIEWPORT_SWIZZLE_STATE_CREATE_INFO_NV {
+	sType: [scalar VkStructureType],
+	pNext: [chain],
+	flags: [scalar VkPipelineViewportSwizzleStateCreateFlagsNV],
+	viewportCount: [scalar u32],
+	pViewportSwizzles: [array viewportCount (struct VkViewportSwizzleNV)]
+});
+
+TraceChainMembers!(
+	VkApplicationInfo,
+	VkInstanceCreateInfo,
+	VkDeviceQueueCreateInfo,
+	VkDeviceCreateInfo,
+	VkSubmitInfo,
+	VkMemoryAllocateInfo,
+	VkMappedMemoryRange,
+	VkBindSparseInfo,
+	VkFenceCreateInfo,
+	VkSemaphoreCreateInfo,
+	VkEventCreateInfo,
+	VkQueryPoolCreateInfo,
+	VkBufferCreateInfo,
+	VkBufferViewCreateInfo,
+	VkImageCreateInfo,
+	VkImageViewCreateInfo,
+	VkShaderModuleCreateInfo,
+	VkPipelineCacheCreateInfo,
+	VkPipelineShaderStageCreateInfo,
+	VkPipelineVertexInputStateCreateInfo,
+	VkPipelineInputAssemblyStateCreateInfo,
+	VkPipelineTessellationStateCreateInfo,
+	VkPipelineViewportStateCreateInfo,
+	VkPipelineRasterizationStateCreateInfo,
+	VkPipelineMultisampleStateCreateInfo,
+	VkPipelineDepthStencilStateCreateInfo,
+	VkPipelineColorBlendStateCreateInfo,
+	VkPipelineDynamicStateCreateInfo,
+	VkGraphicsPipelineCreateInfo,
+	VkComputePipelineCreateInfo,
+	VkPipelineLayoutCreateInfo,
+	VkSamplerCreateInfo,
+	VkDescriptorSetLayoutCreateInfo,
+	VkDescriptorPoolCreateInfo,
+	VkDescriptorSetAllocateInfo,
+	VkWriteDescriptorSet,
+	VkCopyDescriptorSet,
+	VkFramebufferCreateInfo,
+	VkRenderPassCreateInfo,
+	VkCommandPoolCreateInfo,
+	VkCommandBufferAllocateInfo,
+	VkCommandBufferInheritanceInfo,
+	VkCommandBufferBeginInfo,
+	VkMemoryBarrier,
+	VkBufferMemoryBarrier,
+	VkImageMemoryBarrier,
+	VkRenderPassBeginInfo,
+	VkBindBufferMemoryInfo,
+	VkBindImageMemoryInfo,
+	VkMemoryDedicatedAllocateInfo,
+	VkMemoryAllocateFlagsInfo,
+	VkDeviceGroupRenderPassBeginInfo,
+	VkDeviceGroupCommandBufferBeginInfo,
+	VkDeviceGroupSubmitInfo,
+	VkDeviceGroupBindSparseInfo,
+	VkBindBufferMemoryDeviceGroupInfo,
+	VkBindImageMemoryDeviceGroupInfo,
+	VkDeviceGroupDeviceCreateInfo,
+	VkBufferMemoryRequirementsInfo2,
+	VkImageMemoryRequirementsInfo2,
+	VkImageSparseMemoryRequirementsInfo2,
+	VkPhysicalDeviceImageFormatInfo2,
+	VkPhysicalDeviceSparseImageFormatInfo2,
+	VkRenderPassInputAttachmentAspectCreateInfo,
+	VkImageViewUsageCreateInfo,
+	VkPipelineTessellationDomainOriginStateCreateInfo,
+	VkRenderPassMultiviewCreateInfo,
+	VkDeviceQueueInfo2,
+	VkProtectedSubmitInfo,
+	VkSamplerYcbcrConversionCreateInfo,
+	VkSamplerYcbcrConversionInfo,
+	VkBindImagePlaneMemoryInfo,
+	VkImagePlaneMemoryRequirementsInfo,
+	VkPhysicalDeviceExternalImageFormatInfo,
+	VkPhysicalDeviceExternalBufferInfo,
+	VkExternalMemoryImageCreateInfo,
+	VkExternalMemoryBufferCreateInfo,
+	VkExportMemoryAllocateInfo,
+	VkPhysicalDeviceExternalFenceInfo,
+	VkExportFenceCreateInfo,
+	VkExportSemaphoreCreateInfo,
+	VkPhysicalDeviceExternalSemaphoreInfo,
+	#[cfg(feature = "VK_EXT_blend_operation_advanced")] VkPipelineColorBlendAdvancedStateCreateInfoEXT,
+	#[cfg(feature = "VK_NV_clip_space_w_scaling")] VkPipelineViewportWScalingStateCreateInfoNV,
+	#[cfg(feature = "VK_EXT_conservative_rasterization")] VkPipelineRasterizationConservativeStateCreateInfoEXT,
+	#[cfg(feature = "VK_EXT_debug_marker")] VkDebugMarkerObjectNameInfoEXT,
+	#[cfg(feature = "VK_EXT_debug_marker")] VkDebugMarkerObjectTagInfoEXT,
+	#[cfg(feature = "VK_EXT_debug_marker")] VkDebugMarkerMarkerInfoEXT,
+	#[cfg(feature = "VK_EXT_debug_report")] VkDebugReportCallbackCreateInfoEXT,
+	#[cfg(feature = "VK_EXT_debug_utils")] VkDebugUtilsObjectNameInfoEXT,
+	#[cfg(feature = "VK_EXT_debug_utils")] VkDebugUtilsObjectTagInfoEXT,
+	#[cfg(feature = "VK_EXT_debug_utils")] VkDebugUtilsLabelEXT,
+	#[cfg(feature = "VK_EXT_debug_utils")] VkDebugUtilsMessengerCreateInfoEXT,
+	#[cfg(feature = "VK_NV_dedicated_allocation")] VkDedicatedAllocationImageCreateInfoNV,
+	#[cfg(feature = "VK_NV_dedicated_allocation")] VkDedicatedAllocationBufferCreateInfoNV,
+	#[cfg(feature = "VK_NV_dedicated_allocation")] VkDedicatedAllocationMemoryAllocateInfoNV,
+	#[cfg(feature = "VK_NVX_device_generated_commands")] VkDeviceGeneratedCommandsFeaturesNVX,
+	#[cfg(feature = "VK_NVX_device_generated_commands")] VkDeviceGeneratedCommandsLimitsNVX,
+	#[cfg(feature = "VK_NVX_device_generated_commands")] VkIndirectCommandsLayoutCreateInfoNVX,
+	#[cfg(feature = "VK_NVX_device_generated_commands")] VkCmdProcessCommandsInfoNVX,
+	#[cfg(feature = "VK_NVX_device_generated_commands")] VkCmdReserveSpaceForCommandsInfoNVX,
+	#[cfg(feature = "VK_NVX_device_generated_commands")] VkObjectTableCreateInfoNVX,
+	#[cfg(feature = "VK_KHX_device_group_creation")] VkDeviceGroupDeviceCreateInfoKHX,
+	#[cfg(feature = "VK_KHX_device_group")] VkBindBufferMemoryInfoKHX,
+	#[cfg(feature = "VK_KHX_device_group")] VkBindImageMemoryInfoKHX,
+	#[cfg(feature = "VK_KHX_device_group")] VkDeviceGroupPresentCapabilitiesKHX,
+	#[cfg(feature = "VK_KHX_device_group")] VkImageSwapchainCreateInfoKHX,
+	#[cfg(feature = "VK_KHX_device_group")] VkBindImageMemorySwapchainInfoKHX,
+	#[cfg(feature = "VK_KHX_device_group")] VkAcquireNextImageInfoKHX,
+	#[cfg(feature = "VK_KHX_device_group")] VkDeviceGroupPresentInfoKHX,
+	#[cfg(feature = "VK_KHX_device_group")] VkDeviceGroupSwapchainCreateInfoKHX,
+	#[cfg(feature = "VK_EXT_discard_rectangles")] VkPipelineDiscardRectangleStateCreateInfoEXT,
+	#[cfg(feature = "VK_EXT_display_control")] VkDisplayPowerInfoEXT,
+	#[cfg(feature = "VK_EXT_display_control")] VkDeviceEventInfoEXT,
+	#[cfg(feature = "VK_EXT_display_control")] VkDisplayEventInfoEXT,
+	#[cfg(feature = "VK_EXT_display_control")] VkSwapchainCounterCreateInfoEXT,
+	#[cfg(feature = "VK_KHR_display")] VkDisplayModeCreateInfoKHR,
+	#[cfg(feature = "VK_KHR_display")] VkDisplaySurfaceCreateInfoKHR,
+	#[cfg(feature = "VK_KHR_display_swapchain")] VkDisplayPresentInfoKHR,
+	#[cfg(feature = "VK_GOOGLE_display_timing")] VkPresentTimesInfoGOOGLE,
+	#[cfg(feature = "VK_EXT_external_memory_host")] VkMemoryHostPointerPropertiesEXT,
+	#[cfg(feature = "VK_NV_external_memory")] VkExternalMemoryImageCreateInfoNV,
+	#[cfg(feature = "VK_NV_external_memory")] VkExportMemoryAllocateInfoNV,
+	#[cfg(feature = "VK_NV_fragment_coverage_to_color")] VkPipelineCoverageToColorStateCreateInfoNV,
+	#[cfg(feature = "VK_NV_framebuffer_mixed_samples")] VkPipelineCoverageModulationStateCreateInfoNV,
+	#[cfg(feature = "VK_KHR_get_surface_capabilities2")] VkPhysicalDeviceSurfaceInfo2KHR,
+	#[cfg(feature = "VK_EXT_global_priority")] VkDeviceQueueGlobalPriorityCreateInfoEXT,
+	#[cfg(feature = "VK_EXT_hdr_metadata")] VkHdrMetadataEXT,
+	#[cfg(feature = "VK_AMD_rasterization_order")] VkPipelineRasterizationStateRasterizationOrderAMD,
+	#[cfg(feature = "VK_EXT_sample_locations")] VkSampleLocationsInfoEXT,
+	#[cfg(feature = "VK_EXT_sample_locations")] VkRenderPassSampleLocationsBeginInfoEXT,
+	#[cfg(feature = "VK_EXT_sample_locations")] VkPipelineSampleLocationsStateCreateInfoEXT,
+	#[cfg(feature = "VK_EXT_sampler_filter_minmax")] VkSamplerReductionModeCreateInfoEXT,
+	#[cfg(feature = "VK_KHR_swapchain")] VkSwapchainCreateInfoKHR,
+	#[cfg(feature = "VK_KHR_swapchain")] VkPresentInfoKHR,
+	#[cfg(feature = "VK_EXT_validation_cache")] VkValidationCacheCreateInfoEXT,
+	#[cfg(feature = "VK_EXT_validation_cache")] VkShaderModuleValidationCacheCreateInfoEXT,
+	#[cfg(feature = "VK_EXT_validation_flags")] VkValidationFlagsEXT,
+	#[cfg(feature = "VK_EXT_vertex_attribute_divisor")] VkPipelineVertexInputDivisorStateCreateInfoEXT,
+	#[cfg(feature = "VK_NV_viewport_swizzle")] VkPipelineViewportSwizzleStateCreateInfoNV
+);
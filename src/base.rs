@@ -5,13 +5,17 @@
 use vk::*;
 use std::ffi::CString;
 use VkHandle;
-#[cfg(feature = "Implements")] use ::vkresolve::{Resolver, ResolverInterface};
+use hostalloc::HostAllocator;
+use extensions::{Requests, Requirement};
+#[cfg(feature = "Implements")] use extensions::{ExtensionScope, extension_name, layer_name};
+#[cfg(feature = "Implements")] use ::vkresolve::{Resolver, DispatchRef, instance_dispatch};
+#[cfg(all(feature = "Implements", not(feature = "CustomResolver")))] use ::vkresolve::ResolverInterface;
 #[cfg(feature = "Implements")] use VkResultHandler;
+#[cfg(feature = "Implements")] use Chain;
 #[cfg(feature = "Implements")] use std::ptr::{null, null_mut};
 #[cfg(feature = "Implements")] use std::mem::MaybeUninit;
 #[cfg(feature = "Implements")] use crate::fnconv::FnTransmute;
-#[cfg(    feature = "Multithreaded") ] use std::sync::Arc as RefCounter;
-#[cfg(not(feature = "Multithreaded"))] use std::rc::Rc as RefCounter;
+use RefCounter;
 #[cfg(    feature = "Multithreaded") ] use std::sync::RwLock as InternallyMutable;
 #[cfg(not(feature = "Multithreaded"))] use std::cell::RefCell as InternallyMutable;
 
@@ -42,12 +46,13 @@ impl<T> LazyCell<T>
 
 struct InstanceCell
 {
-	n: VkInstance, vk_create_descriptor_update_template: LazyCell<PFN_vkCreateDescriptorUpdateTemplate>,
+	n: VkInstance, #[cfg(feature = "Implements")] dispatch: DispatchRef, allocator: Option<HostAllocator>,
+	api_version: u32, extensions: Vec<String>, layers: Vec<String>,
+	vk_create_descriptor_update_template: LazyCell<PFN_vkCreateDescriptorUpdateTemplate>,
 	vk_destroy_descriptor_update_template: LazyCell<PFN_vkDestroyDescriptorUpdateTemplate>
 }
 /// Opaque handle to a instance object
 #[derive(Clone)] pub struct Instance(RefCounter<InstanceCell>);
-#[cfg(feature = "Multithreaded")] unsafe impl Sync for Instance {}
 /// Opaque handle to a physical device object
 /// 
 /// ## Platform Dependent Methods: Presentation Support checking functions
@@ -57,7 +62,17 @@ struct InstanceCell
 /// * `wayland_presentation_support(&self, queue_family: u32, display: *mut wayland_client::sys::wl_display) -> bool`: VK_KHR_wayland_surface
 /// * `win32_presentation_support(&self, queue_family: u32) -> bool`: VK_KHR_win32_surface
 /// * Methods for Android and Mir surfaces are not implemented
+#[derive(Clone)]
 pub struct PhysicalDevice(VkPhysicalDevice, Instance);
+ThreadSafe!(Send + Sync: InstanceCell, PhysicalDevice);
+/// Set of physical devices which can be used to create a single logical device
+pub struct PhysicalDeviceGroup
+{
+	/// Physical devices in the group
+	pub physical_devices: Vec<PhysicalDevice>,
+	/// Whether the memory can be allocated on a subset of the devices
+	pub subset_allocation: bool
+}
 
 pub struct IterPhysicalDevices<'i>(Vec<VkPhysicalDevice>, usize, &'i Instance);
 impl<'i> Iterator for IterPhysicalDevices<'i>
@@ -85,7 +100,15 @@ impl<'i> DoubleEndedIterator for IterPhysicalDevices<'i>
 }
 
 #[cfg(feature = "Implements")]
-impl Drop for InstanceCell { fn drop(&mut self) { unsafe { Resolver::get().destroy_instance(self.n, ::std::ptr::null()); } } }
+impl Drop for InstanceCell { fn drop(&mut self) { unsafe { self.dispatch.destroy_instance(self.n, HostAllocator::callbacks(self.allocator.as_ref())); } } }
+
+/// The resolver for the global commands, loading the library with `DynamicLoaded`
+#[cfg(all(feature = "DynamicLoaded", not(feature = "CustomResolver")))]
+fn global_resolver() -> ::Result<&'static Resolver> { Ok(Resolver::try_get()?) }
+#[cfg(all(feature = "Implements", not(feature = "DynamicLoaded"), not(feature = "CustomResolver")))]
+fn global_resolver() -> ::Result<&'static Resolver> { Ok(Resolver::get()) }
+#[cfg(all(feature = "Implements", feature = "CustomResolver"))]
+fn global_resolver() -> ::Result<&'static (dyn (::ResolverInterface) + Sync)> { Ok(Resolver::get()) }
 
 impl VkHandle for Instance { type Handle = VkInstance; fn native_ptr(&self) -> VkInstance { self.0.n } }
 impl VkHandle for PhysicalDevice { type Handle = VkPhysicalDevice; fn native_ptr(&self) -> VkPhysicalDevice { self.0 } }
@@ -94,7 +117,7 @@ impl VkHandle for PhysicalDevice { type Handle = VkPhysicalDevice; fn native_ptr
 pub struct InstanceBuilder
 {
 	app_name: CString, engine_name: CString, extensions: Vec<CString>, layers: Vec<CString>,
-	appinfo: VkApplicationInfo, cinfo: VkInstanceCreateInfo
+	appinfo: VkApplicationInfo, cinfo: VkInstanceCreateInfo, allocator: Option<HostAllocator>, requests: Requests
 }
 impl InstanceBuilder
 {
@@ -108,7 +131,7 @@ impl InstanceBuilder
 				applicationVersion: VK_MAKE_VERSION!(app_version.0, app_version.1, app_version.2),
 				engineVersion: VK_MAKE_VERSION!(engine_version.0, engine_version.1, engine_version.2),
 				.. Default::default()
-			}, cinfo: VkInstanceCreateInfo { .. Default::default() }
+			}, cinfo: VkInstanceCreateInfo { .. Default::default() }, allocator: None, requests: Requests::default()
 		}
 	}
 	pub fn add_extension(&mut self, extension: &str) -> &mut Self
@@ -131,6 +154,36 @@ impl InstanceBuilder
 	{
 		for l in layers { self.add_layer(l); } self
 	}
+	/// Request an extension checked against `Instance::enumerate_extension_properties` at `create`.
+	/// The extensions it depends on(see `KNOWN_EXTENSIONS`) are requested together.
+	/// Unlike `add_extension`, a missing required extension is reported as `Error::MissingExtension` before creating the instance
+	pub fn request_extension(&mut self, name: &str, requirement: Requirement) -> &mut Self
+	{
+		self.requests.extension(name, requirement); self
+	}
+	/// Request a layer checked against `Instance::enumerate_layer_properties` at `create`.
+	/// Extensions provided by the enabled layers are also available to `request_extension`
+	pub fn request_layer(&mut self, name: &str, requirement: Requirement) -> &mut Self
+	{
+		self.requests.layer(name, requirement); self
+	}
+	/// Request the instance extensions whose cargo features are enabled
+	pub fn request_feature_extensions(&mut self, requirement: Requirement) -> &mut Self
+	{
+		for e in ::extensions::feature_extensions(::ExtensionScope::Instance) { self.requests.extension(e, requirement); }
+		self
+	}
+	/// Set the highest version of Vulkan the application uses(e.g. `VK_API_VERSION_1_1`). Defaults to `VK_API_VERSION_1_0`
+	pub fn api_version(&mut self, version: u32) -> &mut Self
+	{
+		self.appinfo.apiVersion = version; self
+	}
+	/// Allocate the host memory for the instance and its children through `callbacks`.
+	/// Devices created from the instance use the same callbacks unless `DeviceBuilder::allocation_callbacks` is set
+	pub fn allocation_callbacks<A: ::AllocationCallbacks + 'static>(&mut self, callbacks: A) -> &mut Self
+	{
+		self.allocator = Some(HostAllocator::new(callbacks)); self
+	}
 	/// [feature = "Implements"] Create a new Vulkan instance
 	/// # Failures
 	/// On failure, this command returns
@@ -141,27 +194,93 @@ impl InstanceBuilder
 	/// * `VK_ERROR_LAYER_NOT_PRESENT`
 	/// * `VK_ERROR_EXTENSION_NOT_PRESENT`
 	/// * `VK_ERROR_INCOMPATIBLE_DRIVER`
+	/// * `Error::MissingExtension`/`Error::MissingLayer` for the unavailable required extensions/layers
 	#[cfg(feature = "Implements")]
 	pub fn create(&mut self) -> ::Result<Instance>
 	{
-		let layers: Vec<_> = self.layers.iter().map(|x| x.as_ptr()).collect();
-		let extensions: Vec<_> = self.extensions.iter().map(|x| x.as_ptr()).collect();
+		let api_version = if self.appinfo.apiVersion == 0 { VK_API_VERSION_1_0 } else { self.appinfo.apiVersion };
+		let (layer_names, extension_names) = self.negotiate(api_version)?;
+		let layer_names = layer_names.into_iter().map(|x| CString::new(x).unwrap()).collect::<Vec<_>>();
+		let extension_names = extension_names.into_iter().map(|x| CString::new(x).unwrap()).collect::<Vec<_>>();
+		let layers: Vec<_> = layer_names.iter().map(|x| x.as_ptr()).collect();
+		let extensions: Vec<_> = extension_names.iter().map(|x| x.as_ptr()).collect();
 		self.appinfo.pApplicationName = self.app_name.as_ptr(); self.appinfo.pEngineName = self.engine_name.as_ptr();
 		self.cinfo.enabledLayerCount = layers.len() as _; self.cinfo.enabledExtensionCount = extensions.len() as _;
 		self.cinfo.ppEnabledLayerNames = if layers.is_empty() { 0 as _ } else { layers.as_ptr() };
 		self.cinfo.ppEnabledExtensionNames = if extensions.is_empty() { 0 as _ } else { extensions.as_ptr() };
 		self.cinfo.pApplicationInfo = &self.appinfo;
 		let mut h = VK_NULL_HANDLE as _;
-		Ok(unsafe { Resolver::get().create_instance(&self.cinfo, ::std::ptr::null(), &mut h) }.into_result().map(|_| Instance(RefCounter::new(InstanceCell
+		let allocator = HostAllocator::callbacks(self.allocator.as_ref());
+		let r = global_resolver()?;
+		unsafe { r.create_instance(&self.cinfo, allocator, &mut h) }.into_result_in("vkCreateInstance", "the loader")?;
+		let dispatch = match unsafe { instance_dispatch(h) }
 		{
-			n: h, vk_create_descriptor_update_template: LazyCell::new(), vk_destroy_descriptor_update_template: LazyCell::new()
-		}))).unwrap())
+			Ok(d) => d,
+			Err(_) =>
+			{
+				// the driver lacks a core command
+				unsafe { r.destroy_instance(h, allocator); }
+				return Err(::Error::IncompatibleDriver.in_command("vkCreateInstance", "the loader"));
+			}
+		};
+		Ok(Instance(RefCounter::new(InstanceCell
+		{
+			n: h, dispatch, allocator: self.allocator.clone(), api_version,
+			extensions: extension_names.into_iter().map(|x| x.into_string().unwrap()).collect(),
+			layers: layer_names.into_iter().map(|x| x.into_string().unwrap()).collect(),
+			vk_create_descriptor_update_template: LazyCell::new(), vk_destroy_descriptor_update_template: LazyCell::new()
+		})))
+	}
+	/// Layers and extensions to be enabled: the requested ones available with their dependencies, followed by the added ones
+	#[cfg(feature = "Implements")]
+	fn negotiate(&self, api_version: u32) -> ::Result<(Vec<String>, Vec<String>)>
+	{
+		let mut layers = Vec::new();
+		let mut extensions = Vec::new();
+		if !self.requests.is_empty()
+		{
+			let available_layers = Instance::enumerate_layer_properties()?;
+			layers = self.requests.resolve_layers(&available_layers.iter().map(layer_name).collect::<Vec<_>>())?;
+			let mut available = Instance::enumerate_extension_properties(None)?;
+			for l in &layers { available.extend(Instance::enumerate_extension_properties(Some(l))?); }
+			extensions = self.requests.resolve_extensions(ExtensionScope::Instance, api_version,
+				&available.iter().map(extension_name).collect::<Vec<_>>(), &[])?;
+		}
+		merge_names(&mut layers, &self.layers); merge_names(&mut extensions, &self.extensions);
+		Ok((layers, extensions))
 	}
 }
+/// Append the names not in `names` yet
+pub(crate) fn merge_names(names: &mut Vec<String>, added: &[CString])
+{
+	for a in added
+	{
+		let a = a.to_string_lossy();
+		if !names.iter().any(|n| *n == a) { names.push(a.into_owned()); }
+	}
+}
+impl Instance
+{
+	/// The version of Vulkan requested by `InstanceBuilder::api_version`
+	pub fn api_version(&self) -> u32 { self.0.api_version }
+	/// Extensions enabled on this instance, including the dependencies pulled in by `InstanceBuilder::request_extension`
+	pub fn enabled_extensions(&self) -> &[String] { &self.0.extensions }
+	/// Layers enabled on this instance
+	pub fn enabled_layers(&self) -> &[String] { &self.0.layers }
+	/// Whether the extension is enabled on this instance
+	pub fn is_extension_enabled(&self, name: &str) -> bool { self.0.extensions.iter().any(|e| e == name) }
+}
 /// Following methods are enabled with [feature = "Implements"]
 #[cfg(feature = "Implements")]
 impl Instance
 {
+	/// Entry points loaded for this instance
+	pub fn dispatch(&self) -> &::Dispatch { ::vkresolve::dispatch_of(&self.0.dispatch) }
+	pub(crate) fn dispatch_ref(&self) -> &DispatchRef { &self.0.dispatch }
+	/// `pAllocator` for the objects created from this instance
+	#[cfg(any(feature = "Presentation", feature = "VK_EXT_debug_report", feature = "VK_EXT_debug_utils"))]
+	pub(crate) fn allocation_callbacks(&self) -> *const VkAllocationCallbacks { HostAllocator::callbacks(self.0.allocator.as_ref()) }
+	pub(crate) fn host_allocator(&self) -> Option<&HostAllocator> { self.0.allocator.as_ref() }
 	/// Return a function pointer for a command
 	/// # Failures
 	/// If function is not provided by instance or `name` is empty, returns `None`
@@ -171,7 +290,7 @@ impl Instance
 
 		unsafe
 		{
-			Resolver::get().get_instance_proc_addr(self.native_ptr(), CString::new(name).unwrap().as_ptr())
+			self.dispatch().get_instance_proc_addr(self.native_ptr(), CString::new(name).unwrap().as_ptr())
 				.map(|f| FnTransmute::from_fn(f))
 		}
 	}
@@ -198,11 +317,45 @@ impl Instance
 	pub fn iter_physical_devices(&self) -> ::Result<IterPhysicalDevices>
 	{
 		let mut n = 0;
-		unsafe { Resolver::get().enumerate_physical_devices(self.native_ptr(), &mut n, null_mut()).into_result()?; }
+		unsafe { self.dispatch().enumerate_physical_devices(self.native_ptr(), &mut n, null_mut()).into_result_in("vkEnumeratePhysicalDevices", "VkInstance")?; }
 		let mut v = Vec::with_capacity(n as _); unsafe { v.set_len(n as _); }
-		unsafe { Resolver::get().enumerate_physical_devices(self.native_ptr(), &mut n, v.as_mut_ptr()).into_result()?; }
+		unsafe { self.dispatch().enumerate_physical_devices(self.native_ptr(), &mut n, v.as_mut_ptr()).into_result_in("vkEnumeratePhysicalDevices", "VkInstance")?; }
 		Ok(IterPhysicalDevices(v, 0, self))
 	}
+	/// [Vulkan 1.1] Enumerates groups of physical devices that can be used to create a single logical device
+	/// # Failures
+	/// On failure, this command returns
+	/// 
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_INITIALIZATION_FAILED`
+	pub fn enumerate_physical_device_groups(&self) -> ::Result<Vec<PhysicalDeviceGroup>>
+	{
+		let mut n = 0;
+		unsafe { self.dispatch().enumerate_physical_device_groups(self.native_ptr(), &mut n, null_mut()).into_result_in("vkEnumeratePhysicalDeviceGroups", "VkInstance")?; }
+		let mut v = vec![VkPhysicalDeviceGroupProperties::default(); n as _];
+		unsafe { self.dispatch().enumerate_physical_device_groups(self.native_ptr(), &mut n, v.as_mut_ptr()).into_result_in("vkEnumeratePhysicalDeviceGroups", "VkInstance")?; }
+		Ok(v.into_iter().take(n as _).map(|g| PhysicalDeviceGroup
+		{
+			physical_devices: g.physicalDevices[..g.physicalDeviceCount as usize].iter()
+				.map(|&p| PhysicalDevice(p, self.clone())).collect(),
+			subset_allocation: g.subsetAllocation != 0
+		}).collect())
+	}
+	/// Returns the version of instance-level functionality supported by the implementation.
+	/// `VK_API_VERSION_1_0` is returned if the implementation does not provide `vkEnumerateInstanceVersion`
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	pub fn enumerate_version() -> ::Result<u32>
+	{
+		let r = global_resolver()?;
+		let provided = unsafe { r.get_instance_proc_addr(VK_NULL_HANDLE as _, b"vkEnumerateInstanceVersion\0".as_ptr() as _) }.is_some();
+		if !provided { return Ok(VK_API_VERSION_1_0); }
+		let mut v = 0;
+		unsafe { r.enumerate_instance_version(&mut v) }.into_result_in("vkEnumerateInstanceVersion", "the loader").map(|_| v)
+	}
 	/// Returns up to all of global layer properties
 	/// # Failures
 	/// On failure, this command returns
@@ -211,10 +364,10 @@ impl Instance
 	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
 	pub fn enumerate_layer_properties() -> ::Result<Vec<VkLayerProperties>>
 	{
-		let mut n = 0;
-		unsafe { Resolver::get().enumerate_instance_layer_properties(&mut n, null_mut()) }.into_result()?;
+		let (r, mut n) = (global_resolver()?, 0);
+		unsafe { r.enumerate_instance_layer_properties(&mut n, null_mut()) }.into_result_in("vkEnumerateInstanceLayerProperties", "the loader")?;
 		let mut v = Vec::with_capacity(n as _); unsafe { v.set_len(n as _) };
-		unsafe { Resolver::get().enumerate_instance_layer_properties(&mut n, v.as_mut_ptr()) }.into_result().map(|_| v)
+		unsafe { r.enumerate_instance_layer_properties(&mut n, v.as_mut_ptr()) }.into_result_in("vkEnumerateInstanceLayerProperties", "the loader").map(|_| v)
 	}
 	/// Returns up to all of global extension properties
 	/// # Failures
@@ -227,11 +380,12 @@ impl Instance
 	{
 		let cn = layer_name.map(|s| CString::new(s).unwrap());
 		let cptr = cn.as_ref().map(|s| s.as_ptr()).unwrap_or(null());
+		let r = global_resolver()?;
 		unsafe {
 			let mut n = 0;
-			Resolver::get().enumerate_instance_extension_properties(cptr, &mut n, null_mut()).into_result()?;
+			r.enumerate_instance_extension_properties(cptr, &mut n, null_mut()).into_result_in("vkEnumerateInstanceExtensionProperties", "the loader")?;
 			let mut v = Vec::with_capacity(n as _); v.set_len(n as _);
-			Resolver::get().enumerate_instance_extension_properties(cptr, &mut n, v.as_mut_ptr()).into_result()?;
+			r.enumerate_instance_extension_properties(cptr, &mut n, v.as_mut_ptr()).into_result_in("vkEnumerateInstanceExtensionProperties", "the loader")?;
 			return Ok(v);
 		}
 	}
@@ -259,12 +413,53 @@ impl Instance
 impl PhysicalDevice
 {
 	pub fn parent(&self) -> &Instance { &self.1 }
+	/// Returns properties of the device extensions provided by the implementation or a layer
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_LAYER_NOT_PRESENT`
+	pub fn enumerate_extension_properties(&self, layer_name: Option<&str>) -> ::Result<Vec<VkExtensionProperties>>
+	{
+		let cn = layer_name.map(|s| CString::new(s).unwrap());
+		let cptr = cn.as_ref().map(|s| s.as_ptr()).unwrap_or(null());
+		unsafe
+		{
+			let mut n = 0;
+			self.1.dispatch().enumerate_device_extension_properties(self.0, cptr, &mut n, null_mut())
+				.into_result_in("vkEnumerateDeviceExtensionProperties", "VkPhysicalDevice")?;
+			let mut v = Vec::with_capacity(n as _);
+			self.1.dispatch().enumerate_device_extension_properties(self.0, cptr, &mut n, v.as_mut_ptr())
+				.into_result_in("vkEnumerateDeviceExtensionProperties", "VkPhysicalDevice")?;
+			v.set_len(n as _); Ok(v)
+		}
+	}
+	/// Returns properties of the device layers
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn enumerate_layer_properties(&self) -> ::Result<Vec<VkLayerProperties>>
+	{
+		unsafe
+		{
+			let mut n = 0;
+			self.1.dispatch().enumerate_device_layer_properties(self.0, &mut n, null_mut())
+				.into_result_in("vkEnumerateDeviceLayerProperties", "VkPhysicalDevice")?;
+			let mut v = Vec::with_capacity(n as _);
+			self.1.dispatch().enumerate_device_layer_properties(self.0, &mut n, v.as_mut_ptr())
+				.into_result_in("vkEnumerateDeviceLayerProperties", "VkPhysicalDevice")?;
+			v.set_len(n as _); Ok(v)
+		}
+	}
 	/// Reports capabilities of a physical device.
 	pub fn features(&self) -> VkPhysicalDeviceFeatures
 	{
 		unsafe {
 			let mut p = MaybeUninit::uninit();
-			Resolver::get().get_physical_device_features(self.0, p.as_mut_ptr());
+			self.1.dispatch().get_physical_device_features(self.0, p.as_mut_ptr());
 			
 			p.assume_init()
 		}
@@ -274,7 +469,7 @@ impl PhysicalDevice
 	{
 		unsafe {
 			let mut p = MaybeUninit::uninit();
-			Resolver::get().get_physical_device_format_properties(self.0, format, p.as_mut_ptr());
+			self.1.dispatch().get_physical_device_format_properties(self.0, format, p.as_mut_ptr());
 			
 			p.assume_init()
 		}
@@ -291,8 +486,8 @@ impl PhysicalDevice
 	{
 		unsafe {
 			let mut p = MaybeUninit::uninit();
-			Resolver::get().get_physical_device_image_format_properties(self.0, format, itype,
-				tiling, usage.0, flags.0, p.as_mut_ptr()).into_result()?;
+			self.1.dispatch().get_physical_device_image_format_properties(self.0, format, itype,
+				tiling, usage.0, flags.0, p.as_mut_ptr()).into_result_in("vkGetPhysicalDeviceImageFormatProperties", "VkPhysicalDevice")?;
 			
 			Ok(p.assume_init())
 		}
@@ -301,7 +496,7 @@ impl PhysicalDevice
 	pub fn properties(&self) -> VkPhysicalDeviceProperties {
 		unsafe {
 			let mut p = MaybeUninit::uninit();
-			Resolver::get().get_physical_device_properties(self.0, p.as_mut_ptr());
+			self.1.dispatch().get_physical_device_properties(self.0, p.as_mut_ptr());
 			
 			p.assume_init()
 		}
@@ -311,9 +506,9 @@ impl PhysicalDevice
 	{
 		unsafe {
 			let mut n = 0;
-			Resolver::get().get_physical_device_queue_family_properties(self.0, &mut n, null_mut());
+			self.1.dispatch().get_physical_device_queue_family_properties(self.0, &mut n, null_mut());
 			let mut v = ::preserve(n as _);
-			Resolver::get().get_physical_device_queue_family_properties(self.0, &mut n, v.as_mut_ptr());
+			self.1.dispatch().get_physical_device_queue_family_properties(self.0, &mut n, v.as_mut_ptr());
 			return ::QueueFamilies(v);
 		}
 	}
@@ -323,7 +518,7 @@ impl PhysicalDevice
 		unsafe
 		{
 			let mut p = MaybeUninit::uninit();
-			Resolver::get().get_physical_device_memory_properties(self.0, p.as_mut_ptr());
+			self.1.dispatch().get_physical_device_memory_properties(self.0, p.as_mut_ptr());
 			return MemoryProperties(p.assume_init());
 		}
 	}
@@ -334,10 +529,10 @@ impl PhysicalDevice
 		unsafe
 		{ 
 			let mut n = 0;
-			Resolver::get().get_physical_device_sparse_image_format_properties(self.0, format, itype, samples,
+			self.1.dispatch().get_physical_device_sparse_image_format_properties(self.0, format, itype, samples,
 				usage.0, tiling, &mut n, null_mut());
 			let mut v = ::preserve(n as _);
-			Resolver::get().get_physical_device_sparse_image_format_properties(self.0, format, itype, samples,
+			self.1.dispatch().get_physical_device_sparse_image_format_properties(self.0, format, itype, samples,
 				usage.0, tiling, &mut n, v.as_mut_ptr());
 			return v;
 		}
@@ -349,13 +544,140 @@ impl PhysicalDevice
 	{
 		unsafe {
 			let mut r = MaybeUninit::uninit();
-			Resolver::get().get_physical_device_multisample_properties_ext(self.0, samples, r.as_mut_ptr());
+			self.1.dispatch().get_physical_device_multisample_properties_ext(self.0, samples, r.as_mut_ptr());
 			
 			r.assume_init()
 		}
 	}
 }
 
+/// Entry points which the queries with extensible output chains go through
+#[cfg(feature = "Implements")]
+#[derive(Clone, Copy)]
+enum Properties2Route
+{
+	/// Vulkan 1.1 core
+	Core,
+	/// `VK_KHR_get_physical_device_properties2`
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")] Khr
+}
+/// [Vulkan 1.1 or VK_KHR_get_physical_device_properties2] Queries with extensible output chains.
+/// The core entry points are used if the instance is created with `VK_API_VERSION_1_1` or later,
+/// otherwise the ones of `VK_KHR_get_physical_device_properties2`(the extension must be enabled on the instance)
+/// # Failures
+/// Each query returns `Error::MissingExtension` if neither the API version nor the extension is available
+#[cfg(feature = "Implements")]
+impl PhysicalDevice
+{
+	/// Reports capabilities of a physical device, and fills the structures in `chain`
+	pub fn features2(&self, chain: Chain<VkPhysicalDeviceFeatures2>) -> ::Result<VkPhysicalDeviceFeatures>
+	{
+		let route = self.properties2_route()?;
+		let mut p = VkPhysicalDeviceFeatures2 { pNext: chain.head(), .. Default::default() };
+		unsafe
+		{
+			match route
+			{
+				Properties2Route::Core => self.1.dispatch().get_physical_device_features2(self.0, &mut p)?,
+				#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+				Properties2Route::Khr => self.1.dispatch().get_physical_device_features2_khr(self.0, &mut p)?
+			}
+		}
+		Ok(p.features)
+	}
+	/// Returns properties of a physical device, and fills the structures in `chain`
+	pub fn properties2(&self, chain: Chain<VkPhysicalDeviceProperties2>) -> ::Result<VkPhysicalDeviceProperties>
+	{
+		let route = self.properties2_route()?;
+		let mut p = VkPhysicalDeviceProperties2 { pNext: chain.head(), .. Default::default() };
+		unsafe
+		{
+			match route
+			{
+				Properties2Route::Core => self.1.dispatch().get_physical_device_properties2(self.0, &mut p)?,
+				#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+				Properties2Route::Khr => self.1.dispatch().get_physical_device_properties2_khr(self.0, &mut p)?
+			}
+		}
+		Ok(p.properties)
+	}
+	/// Lists physical device's format capabilities, and fills the structures in `chain`
+	pub fn format_properties2(&self, format: VkFormat, chain: Chain<VkFormatProperties2>) -> ::Result<VkFormatProperties>
+	{
+		let route = self.properties2_route()?;
+		let mut p = VkFormatProperties2 { pNext: chain.head(), .. Default::default() };
+		unsafe
+		{
+			match route
+			{
+				Properties2Route::Core => self.1.dispatch().get_physical_device_format_properties2(self.0, format, &mut p)?,
+				#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+				Properties2Route::Khr => self.1.dispatch().get_physical_device_format_properties2_khr(self.0, format, &mut p)?
+			}
+		}
+		Ok(p.formatProperties)
+	}
+	/// Reports memory information for the specified physical device, and fills the structures in `chain`
+	pub fn memory_properties2(&self, chain: Chain<VkPhysicalDeviceMemoryProperties2>) -> ::Result<MemoryProperties>
+	{
+		let route = self.properties2_route()?;
+		let mut p = VkPhysicalDeviceMemoryProperties2 { pNext: chain.head(), .. Default::default() };
+		unsafe
+		{
+			match route
+			{
+				Properties2Route::Core => self.1.dispatch().get_physical_device_memory_properties2(self.0, &mut p)?,
+				#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+				Properties2Route::Khr => self.1.dispatch().get_physical_device_memory_properties2_khr(self.0, &mut p)?
+			}
+		}
+		Ok(MemoryProperties(p.memoryProperties))
+	}
+	/// Reports properties of the queues of the specified physical device.
+	/// The n-th chain in `chains` is filled for the n-th queue family. The families without a chain are reported alone
+	pub fn queue_family_properties2<'c, Chains>(&self, chains: Chains) -> ::Result<::QueueFamilies>
+		where Chains: IntoIterator<Item = Chain<'c, VkQueueFamilyProperties2>>
+	{
+		let route = self.properties2_route()?;
+		let query = |n: &mut u32, p: *mut VkQueueFamilyProperties2| unsafe
+		{
+			match route
+			{
+				Properties2Route::Core => self.1.dispatch().get_physical_device_queue_family_properties2(self.0, n, p),
+				#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+				Properties2Route::Khr => self.1.dispatch().get_physical_device_queue_family_properties2_khr(self.0, n, p)
+			}
+		};
+		let mut n = 0;
+		query(&mut n, null_mut())?;
+		let mut chains = chains.into_iter();
+		let mut v: Vec<_> = (0 .. n).map(|_| VkQueueFamilyProperties2
+		{
+			pNext: chains.next().map_or(null_mut(), |c| c.head()), .. Default::default()
+		}).collect();
+		query(&mut n, v.as_mut_ptr())?;
+		Ok(::QueueFamilies(v.into_iter().take(n as _).map(|p| p.queueFamilyProperties).collect()))
+	}
+	/// Returns the subgroup operations supported by a physical device
+	pub fn subgroup_properties(&self) -> ::Result<VkPhysicalDeviceSubgroupProperties>
+	{
+		let mut sp = VkPhysicalDeviceSubgroupProperties::default();
+		self.properties2(Chain::new().push(&mut sp))?;
+		sp.pNext = null_mut(); Ok(sp)
+	}
+
+	/// Picks the core or the `VK_KHR_get_physical_device_properties2` entry points of the queries by the API version and the extensions of the instance
+	fn properties2_route(&self) -> ::Result<Properties2Route>
+	{
+		if self.1.api_version() >= VK_API_VERSION_1_1 { return Ok(Properties2Route::Core); }
+		#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+		{
+			if self.1.is_extension_enabled("VK_KHR_get_physical_device_properties2") { return Ok(Properties2Route::Khr); }
+		}
+		Err(::Error::MissingExtension { name: "VK_KHR_get_physical_device_properties2".to_owned(), required_by: None })
+	}
+}
+
 /// [feature = "VK_KHR_surface" and feature = "Implements"] Surface functions
 #[cfg(all(feature = "Implements", feature = "VK_KHR_surface"))]
 impl PhysicalDevice
@@ -370,8 +692,8 @@ impl PhysicalDevice
 	pub fn surface_support(&self, queue_family: u32, surface: &::Surface) -> ::Result<bool> {
 		let mut f = false as _;
 		unsafe {
-			Resolver::get().get_physical_device_surface_support_khr(self.0, queue_family, surface.native_ptr(), &mut f)
-				.into_result()?;
+			self.1.dispatch().get_physical_device_surface_support_khr(self.0, queue_family, surface.native_ptr(), &mut f)
+				.into_result_in("vkGetPhysicalDeviceSurfaceSupportKHR", "VkPhysicalDevice")?;
 			return Ok(f != 0);
 		}
 	}
@@ -387,9 +709,9 @@ impl PhysicalDevice
 		let mut s = MaybeUninit::uninit();
 		unsafe
 		{
-			Resolver::get()
+			self.1.dispatch()
 				.get_physical_device_surface_capabilities_khr(self.0, surface.native_ptr(), s.as_mut_ptr())
-				.into_result()
+				.into_result_in("vkGetPhysicalDeviceSurfaceCapabilitiesKHR", "VkPhysicalDevice")
 				.map(move |_| s.assume_init())
 		}
 	}
@@ -405,10 +727,10 @@ impl PhysicalDevice
 		unsafe
 		{
 			let mut n = 0;
-			Resolver::get().get_physical_device_surface_formats_khr(self.0, surface.native_ptr(), &mut n, null_mut()).into_result()?;
+			self.1.dispatch().get_physical_device_surface_formats_khr(self.0, surface.native_ptr(), &mut n, null_mut()).into_result_in("vkGetPhysicalDeviceSurfaceFormatsKHR", "VkPhysicalDevice")?;
 			let mut v = ::preserve(n as _);
-			Resolver::get().get_physical_device_surface_formats_khr(self.0, surface.native_ptr(), &mut n, v.as_mut_ptr())
-				.into_result()
+			self.1.dispatch().get_physical_device_surface_formats_khr(self.0, surface.native_ptr(), &mut n, v.as_mut_ptr())
+				.into_result_in("vkGetPhysicalDeviceSurfaceFormatsKHR", "VkPhysicalDevice")
 				.map(move |_| v)
 		}
 	}
@@ -422,9 +744,9 @@ impl PhysicalDevice
 	pub fn surface_present_modes(&self, surface: &::Surface) -> ::Result<Vec<::PresentMode>>
 	{
 		let mut n = 0;
-		unsafe { Resolver::get().get_physical_device_surface_present_modes_khr(self.0, surface.native_ptr(), &mut n, ::std::ptr::null_mut()) }.into_result()?;
+		unsafe { self.1.dispatch().get_physical_device_surface_present_modes_khr(self.0, surface.native_ptr(), &mut n, ::std::ptr::null_mut()) }.into_result_in("vkGetPhysicalDeviceSurfacePresentModesKHR", "VkPhysicalDevice")?;
 		let mut v = Vec::with_capacity(n as _); unsafe { v.set_len(n as _) };
-		unsafe { Resolver::get().get_physical_device_surface_present_modes_khr(self.0, surface.native_ptr(), &mut n, v.as_mut_ptr()) }.into_result()
+		unsafe { self.1.dispatch().get_physical_device_surface_present_modes_khr(self.0, surface.native_ptr(), &mut n, v.as_mut_ptr()) }.into_result_in("vkGetPhysicalDeviceSurfacePresentModesKHR", "VkPhysicalDevice")
 			.map(|_| unsafe { ::std::mem::transmute(v) })
 	}
 	
@@ -435,8 +757,8 @@ impl PhysicalDevice
 		let mut d = unsafe { std::mem::uninitialized() };
 		unsafe
 		{
-			Resolver::get().get_physical_device_surface_capabilities2_khr(self.0, info as *const _, &mut d as *mut _)
-				.into_result()?;
+			self.1.dispatch().get_physical_device_surface_capabilities2_khr(self.0, info as *const _, &mut d as *mut _)
+				.into_result_in("vkGetPhysicalDeviceSurfaceCapabilities2KHR", "VkPhysicalDevice")?;
 		}
 		
 		return Ok(d);
@@ -449,25 +771,25 @@ impl PhysicalDevice {
 	/// [feature = "VK_KHR_xlib_surface"] Query physical device for presentation to X11 server using Xlib
 	#[cfg(feature = "VK_KHR_xlib_surface")]
 	pub fn xlib_presentation_support(&self, queue_family: u32, display: *mut ::x11::xlib::Display, visual: ::x11::xlib::VisualID) -> bool {
-		unsafe { Resolver::get().get_physical_device_xlib_presentation_support_khr(self.0, queue_family, display, visual) != 0 }
+		unsafe { self.1.dispatch().get_physical_device_xlib_presentation_support_khr(self.0, queue_family, display, visual) != 0 }
 	}
 	/// [feature = "VK_KHR_xcb_surface"] Query physical device for presentation to X11 server using XCB
 	#[cfg(feature = "VK_KHR_xcb_surface")]
 	pub fn xcb_presentation_support(&self, queue_family: u32, connection: *mut ::xcb::ffi::xcb_connection_t, visual: ::xcb::ffi::xcb_visualid_t) -> bool
 	{
-		unsafe { Resolver::get().get_physical_device_xcb_presentation_support_khr(self.0, queue_family, connection, visual) != 0 }
+		unsafe { self.1.dispatch().get_physical_device_xcb_presentation_support_khr(self.0, queue_family, connection, visual) != 0 }
 	}
 	/// [feature = "VK_KHR_wayland_surface"] Query physical device for presentation to Wayland
 	#[cfg(feature = "VK_KHR_wayland_surface")]
 	pub fn wayland_presentation_support(&self, queue_family: u32, display: *mut ::wayland_client::sys::wl_display) -> bool
 	{
-		unsafe { Resolver::get().get_physical_device_wayland_presentation_support_khr(self.0, queue_family, display) != 0 }
+		unsafe { self.1.dispatch().get_physical_device_wayland_presentation_support_khr(self.0, queue_family, display) != 0 }
 	}
 	/// [feature = "VK_KHR_win32_surface"] Query queue family support for presentation on a Win32 display
 	#[cfg(feature = "VK_KHR_win32_surface")]
 	pub fn win32_presentation_support(&self, queue_family: u32) -> bool
 	{
-		unsafe { Resolver::get().get_physical_device_win32_presentation_support_khr(self.0, queue_family) != 0 }
+		unsafe { self.1.dispatch().get_physical_device_win32_presentation_support_khr(self.0, queue_family) != 0 }
 	}
 }
 
@@ -486,10 +808,10 @@ impl PhysicalDevice
 		unsafe 
 		{
 			let mut n = 0;
-			Resolver::get().get_physical_device_display_properties_khr(self.0, &mut n, null_mut()).into_result()?;
+			self.1.dispatch().get_physical_device_display_properties_khr(self.0, &mut n, null_mut()).into_result_in("vkGetPhysicalDeviceDisplayPropertiesKHR", "VkPhysicalDevice")?;
 			let mut v = ::preserve(n as _);
-			Resolver::get().get_physical_device_display_properties_khr(self.0, &mut n, v.as_mut_ptr())
-				.into_result()
+			self.1.dispatch().get_physical_device_display_properties_khr(self.0, &mut n, v.as_mut_ptr())
+				.into_result_in("vkGetPhysicalDeviceDisplayPropertiesKHR", "VkPhysicalDevice")
 				.map(move |_| v)
 		}
 	}
@@ -504,10 +826,10 @@ impl PhysicalDevice
 		unsafe
 		{
 			let mut n = 0;
-			Resolver::get().get_physical_device_display_plane_properties_khr(self.0, &mut n, null_mut()).into_result()?;
+			self.1.dispatch().get_physical_device_display_plane_properties_khr(self.0, &mut n, null_mut()).into_result_in("vkGetPhysicalDeviceDisplayPlanePropertiesKHR", "VkPhysicalDevice")?;
 			let mut v = ::preserve(n as _);
-			Resolver::get().get_physical_device_display_plane_properties_khr(self.0, &mut n, v.as_mut_ptr())
-				.into_result()
+			self.1.dispatch().get_physical_device_display_plane_properties_khr(self.0, &mut n, v.as_mut_ptr())
+				.into_result_in("vkGetPhysicalDeviceDisplayPlanePropertiesKHR", "VkPhysicalDevice")
 				.map(move |_| v)
 		}
 	}
@@ -522,10 +844,10 @@ impl PhysicalDevice
 		unsafe
 		{
 			let mut n = 0;
-			Resolver::get().get_display_plane_supported_displays_khr(self.0, index, &mut n, null_mut()).into_result()?;
+			self.1.dispatch().get_display_plane_supported_displays_khr(self.0, index, &mut n, null_mut()).into_result_in("vkGetDisplayPlaneSupportedDisplaysKHR", "VkPhysicalDevice")?;
 			let mut v = ::preserve(n as _);
-			Resolver::get().get_display_plane_supported_displays_khr(self.0, index, &mut n, v.as_mut_ptr())
-				.into_result()
+			self.1.dispatch().get_display_plane_supported_displays_khr(self.0, index, &mut n, v.as_mut_ptr())
+				.into_result_in("vkGetDisplayPlaneSupportedDisplaysKHR", "VkPhysicalDevice")
 				.map(move |_| v)
 		}
 	}
@@ -540,10 +862,10 @@ impl PhysicalDevice
 		unsafe
 		{
 			let mut n = 0;
-			Resolver::get().get_display_mode_properties_khr(self.0, display, &mut n, null_mut()).into_result()?;
+			self.1.dispatch().get_display_mode_properties_khr(self.0, display, &mut n, null_mut()).into_result_in("vkGetDisplayModePropertiesKHR", "VkPhysicalDevice")?;
 			let mut v = ::preserve(n as _);
-			Resolver::get().get_display_mode_properties_khr(self.0, display, &mut n, v.as_mut_ptr())
-				.into_result()
+			self.1.dispatch().get_display_mode_properties_khr(self.0, display, &mut n, v.as_mut_ptr())
+				.into_result_in("vkGetDisplayModePropertiesKHR", "VkPhysicalDevice")
 				.map(move |_| v)
 		}
 	}
@@ -564,8 +886,8 @@ impl PhysicalDevice
 		let mut h = VK_NULL_HANDLE as _;
 		unsafe
 		{
-			Resolver::get().create_display_mode_khr(self.0, display, &cinfo, ::std::ptr::null(), &mut h)
-				.into_result()
+			self.1.dispatch().create_display_mode_khr(self.0, display, &cinfo, self.1.allocation_callbacks(), &mut h)
+				.into_result_in("vkCreateDisplayModeKHR", "VkPhysicalDevice")
 				.map(move |_| h)
 		}
 	}
@@ -580,8 +902,8 @@ impl PhysicalDevice
 		let mut s = MaybeUninit::uninit();
 		unsafe
 		{
-			Resolver::get().get_display_plane_capabilities_khr(self.0, mode, plane_index, s.as_mut_ptr())
-				.into_result()
+			self.1.dispatch().get_display_plane_capabilities_khr(self.0, mode, plane_index, s.as_mut_ptr())
+				.into_result_in("vkGetDisplayPlaneCapabilitiesKHR", "VkPhysicalDevice")
 				.map(move |_| s.assume_init())
 		}
 	}
@@ -684,3 +1006,82 @@ impl MemoryPropertyFlags {
 
 	pub fn bits(self) -> VkMemoryPropertyFlags { self.0 }
 }
+
+#[cfg(all(test, feature = "Implements", feature = "CustomResolver"))]
+mod tests
+{
+	use super::*;
+	use fakedrv::{FakeDriver, assert_no_misuse};
+
+	#[test]
+	fn properties2_route()
+	{
+		let driver = FakeDriver::install();
+		let instance = InstanceBuilder::new("t", (0, 1, 0), "t", (0, 1, 0)).api_version(VK_API_VERSION_1_1).create().unwrap();
+		let pd = instance.iter_physical_devices().unwrap().next().unwrap();
+		let sp = pd.subgroup_properties().unwrap();
+		assert_eq!(sp.subgroupSize, 1);
+		assert!(!pd.queue_family_properties2(None).unwrap().0.is_empty());
+		assert_no_misuse(&driver, &instance);
+
+		// Vulkan 1.0 without the extension
+		let instance = InstanceBuilder::new("t", (0, 1, 0), "t", (0, 1, 0)).create().unwrap();
+		let pd = instance.iter_physical_devices().unwrap().next().unwrap();
+		match pd.features2(Chain::new())
+		{
+			Err(::Error::MissingExtension { ref name, .. }) if name == "VK_KHR_get_physical_device_properties2" => (),
+			r => panic!("unexpected result: {:?}", r.map(|_| ()))
+		}
+		assert_no_misuse(&driver, &instance);
+	}
+	#[test]
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+	fn properties2_through_extension()
+	{
+		let driver = FakeDriver::install();
+		let instance = InstanceBuilder::new("t", (0, 1, 0), "t", (0, 1, 0))
+			.add_extension("VK_KHR_get_physical_device_properties2").create().unwrap();
+		let pd = instance.iter_physical_devices().unwrap().next().unwrap();
+		assert_eq!(pd.memory_properties2(Chain::new()).unwrap().types().count(), 4);
+		pd.format_properties2(VK_FORMAT_R8G8B8A8_UNORM, Chain::new()).unwrap();
+		assert_eq!(pd.subgroup_properties().unwrap().subgroupSize, 1);
+		assert_no_misuse(&driver, &instance);
+	}
+}
+
+#[cfg(all(test, feature = "DynamicLoaded", not(feature = "CustomResolver")))]
+mod loader_tests
+{
+	use super::*;
+	use LoadError;
+
+	#[test]
+	fn unload_and_reload()
+	{
+		match Resolver::load_global(["/nonexistent/libvulkan.so.1"])
+		{
+			Err(LoadError::LibraryNotFound(ref failures)) => assert_eq!(failures.len(), 1),
+			r => panic!("unexpected result: {:?}", r.err())
+		}
+		assert!(!unsafe { Resolver::unload_global() });
+		match Resolver::try_get()
+		{
+			// no Vulkan library on this machine: global commands report why, and nothing is left loaded
+			Err(e) =>
+			{
+				assert!(matches!(Instance::enumerate_version(), Err(::Error::Load(ref l)) if **l == e));
+				assert!(!unsafe { Resolver::unload_global() });
+			},
+			Ok(_) =>
+			{
+				assert!(Instance::enumerate_version().is_ok());
+				assert_eq!(Resolver::load_global(Resolver::default_candidates()).err(), Some(LoadError::AlreadyLoaded));
+				assert!(unsafe { Resolver::unload_global() });
+				assert!(!unsafe { Resolver::unload_global() });
+				assert!(Resolver::load_global(Resolver::default_candidates()).is_ok());
+				assert!(Instance::enumerate_version().is_ok());
+				assert!(unsafe { Resolver::unload_global() });
+			}
+		}
+	}
+}
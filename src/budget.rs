@@ -0,0 +1,264 @@
+//! Device memory usage tracking per heap and memory type
+//!
+//! `DeviceBuilder::track_memory` makes the device count every `DeviceMemory` allocated and freed through it.
+//!
+//! ```rust,ignore
+//! let device = DeviceBuilder::new(&adapter).add_queue(DeviceQueueCreateInfo(0, vec![0.0])).track_memory().create()?;
+//! let tracker = device.memory_tracker().unwrap();
+//! tracker.set_budget(0, Some(512 << 20));
+//! tracker.on_budget_exceeded(|e| eprintln!("heap {} is over budget: {} bytes", e.heap_index, e.bytes));
+//! println!("{:?}", tracker.snapshot().heaps[0]);
+//! ```
+
+#![cfg_attr(not(feature = "Implements"), allow(dead_code))]
+
+use vk::*;
+use std::sync::{Arc, Mutex};
+
+/// Bytes and number of the live `DeviceMemory` objects
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoryUsage
+{
+	pub bytes: VkDeviceSize,
+	pub count: u32,
+	/// The highest `bytes` ever reached
+	pub peak_bytes: VkDeviceSize
+}
+impl MemoryUsage
+{
+	fn add(&mut self, size: VkDeviceSize)
+	{
+		self.bytes += size; self.count += 1;
+		self.peak_bytes = self.peak_bytes.max(self.bytes);
+	}
+	fn remove(&mut self, size: VkDeviceSize) { self.bytes -= size; self.count -= 1; }
+}
+/// Usage and budgets of a memory heap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeapUsage
+{
+	/// Allocations made through the device
+	pub usage: MemoryUsage,
+	/// Size of the heap
+	pub size: VkDeviceSize,
+	/// Budget set by `MemoryTracker::set_budget`
+	pub budget: Option<VkDeviceSize>,
+	/// [VK_EXT_memory_budget] Bytes the process can allocate from the heap, as estimated by the driver.
+	/// Reported only if the instance is created with `VK_API_VERSION_1_1` or enables `VK_KHR_get_physical_device_properties2`
+	pub driver_budget: Option<VkDeviceSize>,
+	/// [VK_EXT_memory_budget] Bytes the process is using in the heap, including the allocations not made through the device
+	pub driver_usage: Option<VkDeviceSize>
+}
+impl HeapUsage
+{
+	/// The tighter one of `budget` and `driver_budget`
+	pub fn effective_budget(&self) -> Option<VkDeviceSize>
+	{
+		match (self.budget, self.driver_budget)
+		{
+			(Some(a), Some(b)) => Some(a.min(b)), (a, b) => a.or(b)
+		}
+	}
+	/// Whether the usage(`driver_usage` if reported) exceeds `effective_budget`
+	pub fn is_over_budget(&self) -> bool
+	{
+		let used = self.driver_usage.unwrap_or(self.usage.bytes);
+		self.effective_budget().map_or(false, |b| used > b)
+	}
+}
+/// Usage of the memory heaps and the memory types at a point
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemorySnapshot
+{
+	pub heaps: Vec<HeapUsage>,
+	pub types: Vec<MemoryUsage>
+}
+/// Reported when an allocation pushes a heap over the budget set by `MemoryTracker::set_budget`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BudgetExceeded
+{
+	pub heap_index: u32,
+	pub type_index: u32,
+	/// Size of the allocation
+	pub size: VkDeviceSize,
+	/// Allocated bytes of the heap including the allocation
+	pub bytes: VkDeviceSize,
+	pub budget: VkDeviceSize
+}
+
+type BudgetCallback = Arc<dyn Fn(&BudgetExceeded) + Send + Sync>;
+struct TrackerState
+{
+	heaps: Vec<MemoryUsage>, types: Vec<MemoryUsage>, budgets: Vec<Option<VkDeviceSize>>,
+	callback: Option<BudgetCallback>
+}
+/// Counts the `DeviceMemory` objects of a device(enabled by `DeviceBuilder::track_memory`)
+pub struct MemoryTracker
+{
+	type_heaps: Vec<u32>, heap_sizes: Vec<VkDeviceSize>,
+	/// Queried for the budget if `VK_EXT_memory_budget` is enabled on the device
+	#[cfg(feature = "VK_EXT_memory_budget")] budget_source: Option<::PhysicalDevice>,
+	state: Mutex<TrackerState>
+}
+impl MemoryTracker
+{
+	/// Set or clear the budget of a heap. The budget is checked on each allocation
+	/// # Panics
+	/// Panics if `heap_index` is not less than the number of the memory heaps
+	pub fn set_budget(&self, heap_index: u32, budget: Option<VkDeviceSize>)
+	{
+		assert!((heap_index as usize) < self.heap_sizes.len(), "heap index {} is out of range(the device has {} heaps)",
+			heap_index, self.heap_sizes.len());
+		self.state.lock().unwrap().budgets[heap_index as usize] = budget;
+	}
+	/// Call `callback` whenever an allocation makes a heap exceed its budget.
+	/// The callback is called after the allocation succeeded, once per crossing
+	pub fn on_budget_exceeded<F: Fn(&BudgetExceeded) + Send + Sync + 'static>(&self, callback: F)
+	{
+		self.state.lock().unwrap().callback = Some(Arc::new(callback));
+	}
+
+	pub(crate) fn allocated(&self, type_index: u32, size: VkDeviceSize)
+	{
+		let heap_index = self.type_heaps[type_index as usize];
+		let exceeded =
+		{
+			let mut s = self.state.lock().unwrap();
+			s.types[type_index as usize].add(size);
+			let heap = &mut s.heaps[heap_index as usize];
+			heap.add(size);
+			let bytes = heap.bytes;
+			match s.budgets[heap_index as usize]
+			{
+				Some(budget) if bytes > budget && bytes - size <= budget => s.callback.clone()
+					.map(|c| (c, BudgetExceeded { heap_index, type_index, size, bytes, budget })),
+				_ => None
+			}
+		};
+		// called without the lock so that the callback can take a snapshot
+		if let Some((callback, e)) = exceeded { callback(&e); }
+	}
+	pub(crate) fn freed(&self, type_index: u32, size: VkDeviceSize)
+	{
+		let mut s = self.state.lock().unwrap();
+		s.types[type_index as usize].remove(size);
+		let heap = self.type_heaps[type_index as usize] as usize;
+		s.heaps[heap].remove(size);
+	}
+}
+/// Following methods are enabled with [feature = "Implements"]
+#[cfg(feature = "Implements")]
+impl MemoryTracker
+{
+	#[cfg_attr(not(feature = "VK_EXT_memory_budget"), allow(unused_variables))]
+	pub(crate) fn new(adapter: &::PhysicalDevice, driver_budget: bool) -> Self
+	{
+		let props = adapter.memory_properties();
+		let type_heaps: Vec<_> = props.types().map(|t| t.heapIndex).collect();
+		let heap_sizes: Vec<_> = props.heaps().map(|h| h.size).collect();
+		MemoryTracker
+		{
+			#[cfg(feature = "VK_EXT_memory_budget")] budget_source: if driver_budget { Some(adapter.clone()) } else { None },
+			state: Mutex::new(TrackerState
+			{
+				heaps: vec![MemoryUsage::default(); heap_sizes.len()], types: vec![MemoryUsage::default(); type_heaps.len()],
+				budgets: vec![None; heap_sizes.len()], callback: None
+			}),
+			type_heaps, heap_sizes
+		}
+	}
+	/// Current usage. The budget and the usage reported by the driver are merged when `VK_EXT_memory_budget` is enabled
+	pub fn snapshot(&self) -> MemorySnapshot
+	{
+		let driver = self.driver_budget();
+		let s = self.state.lock().unwrap();
+		MemorySnapshot
+		{
+			heaps: s.heaps.iter().enumerate().map(|(n, &usage)| HeapUsage
+			{
+				usage, size: self.heap_sizes[n], budget: s.budgets[n],
+				driver_budget: driver.as_ref().map(|d| d.0[n]), driver_usage: driver.as_ref().map(|d| d.1[n])
+			}).collect(),
+			types: s.types.clone()
+		}
+	}
+
+	/// Budgets and usages of the heaps reported by the driver.
+	/// `None` if neither Vulkan 1.1 nor `VK_KHR_get_physical_device_properties2` is available to query them
+	#[cfg(feature = "VK_EXT_memory_budget")]
+	fn driver_budget(&self) -> Option<(Vec<VkDeviceSize>, Vec<VkDeviceSize>)>
+	{
+		let adapter = self.budget_source.as_ref()?;
+		let mut b = VkPhysicalDeviceMemoryBudgetPropertiesEXT::default();
+		adapter.memory_properties2(::Chain::new().push(&mut b)).ok()?;
+		let n = self.heap_sizes.len();
+		Some((b.heapBudget[..n].to_vec(), b.heapUsage[..n].to_vec()))
+	}
+	#[cfg(not(feature = "VK_EXT_memory_budget"))]
+	fn driver_budget(&self) -> Option<(Vec<VkDeviceSize>, Vec<VkDeviceSize>)> { None }
+}
+
+#[cfg(all(test, feature = "Implements", feature = "CustomResolver"))]
+mod tests
+{
+	use super::*;
+	use DeviceMemory;
+	use fakedrv::{test_device, assert_no_misuse};
+
+	#[test]
+	fn tracking()
+	{
+		// the budget of the driver is queried through the extension on Vulkan 1.0
+		let (driver, instance, device) = test_device(|_b|
+		{
+			#[cfg(feature = "VK_EXT_memory_budget")] _b.add_extension("VK_KHR_get_physical_device_properties2");
+		}, |b|
+		{
+			b.track_memory();
+			#[cfg(feature = "VK_EXT_memory_budget")] b.add_extension("VK_EXT_memory_budget");
+		});
+		let tracker = device.memory_tracker().unwrap();
+		let hits = Arc::new(Mutex::new(Vec::new()));
+		let h2 = hits.clone();
+		tracker.set_budget(0, Some(3000));
+		tracker.on_budget_exceeded(move |e| h2.lock().unwrap().push(*e));
+
+		let m1 = DeviceMemory::allocate(&device, 2000, 0).unwrap();
+		let m2 = DeviceMemory::allocate(&device, 2000, 3).unwrap();
+		let m3 = DeviceMemory::allocate(&device, 100, 3).unwrap();
+		let h = DeviceMemory::allocate(&device, 500, 1).unwrap();
+		// reported once per crossing
+		assert_eq!(hits.lock().unwrap().len(), 1);
+		let e = hits.lock().unwrap()[0];
+		assert_eq!((e.heap_index, e.type_index, e.bytes, e.budget), (0, 3, 4000, 3000));
+		let s = tracker.snapshot();
+		assert_eq!(s.heaps[0].usage, MemoryUsage { bytes: 4100, count: 3, peak_bytes: 4100 });
+		assert_eq!(s.heaps[1].usage.bytes, 500);
+		assert_eq!(s.types[3].count, 2);
+		assert!(s.heaps[0].is_over_budget());
+		#[cfg(feature = "VK_EXT_memory_budget")]
+		{
+			assert_eq!(s.heaps[0].driver_usage, Some(4100));
+			assert_eq!(s.heaps[0].driver_budget, Some((1 << 30) / 4 * 3));
+			assert_eq!(s.heaps[0].effective_budget(), Some(3000));
+		}
+		#[cfg(not(feature = "VK_EXT_memory_budget"))] assert_eq!(s.heaps[0].driver_usage, None);
+
+		drop((m1, m2));
+		let s = tracker.snapshot();
+		assert_eq!(s.heaps[0].usage, MemoryUsage { bytes: 100, count: 1, peak_bytes: 4100 });
+		assert!(!s.heaps[0].is_over_budget());
+		drop((m3, h));
+		let m = DeviceMemory::allocate(&device, 3500, 0).unwrap();
+		assert_eq!(hits.lock().unwrap().len(), 2);
+		drop(m);
+		assert_no_misuse(&driver, &instance);
+	}
+	#[test]
+	#[should_panic(expected = "heap index 2 is out of range")]
+	fn budget_of_missing_heap()
+	{
+		let (_driver, _instance, device) = test_device(|_| (), |b| { b.track_memory(); });
+		device.memory_tracker().unwrap().set_budget(2, Some(0));
+	}
+}
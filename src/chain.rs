@@ -0,0 +1,113 @@
+//! Extension Chains(structures linked through `pNext`)
+
+use vk::*;
+use libc::c_void;
+use std::marker::PhantomData;
+
+/// Marker for the structures which can be chained onto `Base` through its `pNext`
+/// # Safety
+/// Implementors must be `#[repr(C)]` structures beginning with `sType` and `pNext`
+pub unsafe trait Extends<Base> {}
+
+#[repr(C)] #[allow(non_snake_case)]
+struct ChainHeader { sType: VkStructureType, pNext: *mut c_void }
+
+/// Structures chained onto `Base`. Every structure is borrowed until the command taking this chain returns
+pub struct Chain<'a, Base> { head: *mut c_void, ph: PhantomData<&'a mut Base> }
+impl<'a, Base> Chain<'a, Base>
+{
+	/// An empty chain
+	pub fn new() -> Self { Chain { head: ::std::ptr::null_mut(), ph: PhantomData } }
+	/// Link `s` in front of the structures already chained
+	pub fn push<S: Extends<Base>>(mut self, s: &'a mut S) -> Self
+	{
+		self.head = link(self.head, s); self
+	}
+	/// Pointer to the first structure, to be set to `pNext` of `Base`
+	pub fn head(&self) -> *mut c_void { self.head }
+	pub fn is_empty(&self) -> bool { self.head.is_null() }
+}
+impl<'a, Base> Default for Chain<'a, Base> { fn default() -> Self { Self::new() } }
+/// The clones share the structures, which are only read by the commands
+impl<'a, Base> Clone for Chain<'a, Base> { fn clone(&self) -> Self { Chain { head: self.head, ph: PhantomData } } }
+
+/// Link `s` in front of `head`(`pNext` of a `Base`) and return the new head.
+/// The caller keeps `s` borrowed while the head is in use
+pub(crate) fn link<Base, S: Extends<Base>>(head: *mut c_void, s: &mut S) -> *mut c_void
+{
+	let p = s as *mut S as *mut ChainHeader;
+	unsafe { (*p).pNext = head; }
+	p as _
+}
+
+macro_rules! DeclareExtends
+{
+	($base: ty: $($s: ty),*) => { $(unsafe impl Extends<$base> for $s {})* }
+}
+
+// Vulkan 1.1 Core //
+DeclareExtends!(VkPhysicalDeviceFeatures2: VkPhysicalDevice16BitStorageFeatures, VkPhysicalDeviceMultiviewFeatures,
+	VkPhysicalDeviceVariablePointerFeatures, VkPhysicalDeviceProtectedMemoryFeatures,
+	VkPhysicalDeviceSamplerYcbcrConversionFeatures, VkPhysicalDeviceShaderDrawParameterFeatures);
+DeclareExtends!(VkPhysicalDeviceProperties2: VkPhysicalDeviceSubgroupProperties, VkPhysicalDevicePointClippingProperties,
+	VkPhysicalDeviceMultiviewProperties, VkPhysicalDeviceProtectedMemoryProperties, VkPhysicalDeviceIDProperties,
+	VkPhysicalDeviceMaintenance3Properties);
+DeclareExtends!(VkImageFormatProperties2: VkExternalImageFormatProperties, VkSamplerYcbcrConversionImageFormatProperties);
+DeclareExtends!(VkMemoryRequirements2: VkMemoryDedicatedRequirements);
+DeclareExtends!(VkBindBufferMemoryInfo: VkBindBufferMemoryDeviceGroupInfo);
+DeclareExtends!(VkBindImageMemoryInfo: VkBindImageMemoryDeviceGroupInfo, VkBindImagePlaneMemoryInfo);
+
+// Create Infos //
+DeclareExtends!(VkMemoryAllocateInfo: VkMemoryDedicatedAllocateInfo, VkMemoryAllocateFlagsInfo, VkExportMemoryAllocateInfo);
+DeclareExtends!(VkImageCreateInfo: VkExternalMemoryImageCreateInfo);
+DeclareExtends!(VkBufferCreateInfo: VkExternalMemoryBufferCreateInfo);
+#[cfg(feature = "VK_NV_dedicated_allocation")]
+DeclareExtends!(VkImageCreateInfo: VkDedicatedAllocationImageCreateInfoNV);
+#[cfg(feature = "VK_NV_dedicated_allocation")]
+DeclareExtends!(VkBufferCreateInfo: VkDedicatedAllocationBufferCreateInfoNV);
+#[cfg(feature = "VK_NV_dedicated_allocation")]
+DeclareExtends!(VkMemoryAllocateInfo: VkDedicatedAllocationMemoryAllocateInfoNV);
+#[cfg(feature = "VK_NV_external_memory")]
+DeclareExtends!(VkImageCreateInfo: VkExternalMemoryImageCreateInfoNV);
+#[cfg(feature = "VK_NV_external_memory")]
+DeclareExtends!(VkMemoryAllocateInfo: VkExportMemoryAllocateInfoNV);
+#[cfg(feature = "VK_KHR_external_memory_fd")]
+DeclareExtends!(VkMemoryAllocateInfo: VkImportMemoryFdInfoKHR);
+#[cfg(feature = "VK_KHR_external_memory_win32")]
+DeclareExtends!(VkMemoryAllocateInfo: VkImportMemoryWin32HandleInfoKHR, VkExportMemoryWin32HandleInfoKHR);
+#[cfg(feature = "VK_EXT_external_memory_host")]
+DeclareExtends!(VkMemoryAllocateInfo: VkImportMemoryHostPointerInfoEXT);
+#[cfg(feature = "VK_EXT_external_memory_host")]
+DeclareExtends!(VkPhysicalDeviceProperties2: VkPhysicalDeviceExternalMemoryHostPropertiesEXT);
+#[cfg(feature = "VK_EXT_display_control")]
+DeclareExtends!(VkSwapchainCreateInfoKHR: VkSwapchainCounterCreateInfoEXT);
+#[cfg(feature = "VK_EXT_full_screen_exclusive")]
+DeclareExtends!(VkSwapchainCreateInfoKHR: VkSurfaceFullScreenExclusiveInfoEXT);
+#[cfg(all(feature = "VK_EXT_full_screen_exclusive", feature = "VK_EXT_full_screen_exclusive_win32"))]
+DeclareExtends!(VkSwapchainCreateInfoKHR: VkSurfaceFullScreenExclusiveWin32InfoEXT);
+
+// Pipeline States //
+#[cfg(feature = "VK_EXT_sample_locations")]
+DeclareExtends!(VkPipelineMultisampleStateCreateInfo: VkPipelineSampleLocationsStateCreateInfoEXT);
+#[cfg(feature = "VK_EXT_sample_locations")]
+DeclareExtends!(VkImageMemoryBarrier: VkSampleLocationsInfoEXT);
+#[cfg(feature = "VK_EXT_sample_locations")]
+DeclareExtends!(VkRenderPassBeginInfo: VkRenderPassSampleLocationsBeginInfoEXT);
+#[cfg(feature = "VK_EXT_sample_locations")]
+DeclareExtends!(VkPhysicalDeviceProperties2: VkPhysicalDeviceSampleLocationsPropertiesEXT);
+#[cfg(feature = "VK_EXT_conservative_rasterization")]
+DeclareExtends!(VkPipelineRasterizationStateCreateInfo: VkPipelineRasterizationConservativeStateCreateInfoEXT);
+#[cfg(feature = "VK_EXT_conservative_rasterization")]
+DeclareExtends!(VkPhysicalDeviceProperties2: VkPhysicalDeviceConservativeRasterizationPropertiesEXT);
+#[cfg(feature = "VK_EXT_discard_rectangles")]
+DeclareExtends!(VkGraphicsPipelineCreateInfo: VkPipelineDiscardRectangleStateCreateInfoEXT);
+#[cfg(feature = "VK_EXT_discard_rectangles")]
+DeclareExtends!(VkPhysicalDeviceProperties2: VkPhysicalDeviceDiscardRectanglePropertiesEXT);
+#[cfg(feature = "VK_EXT_blend_operation_advanced")]
+DeclareExtends!(VkPhysicalDeviceFeatures2: VkPhysicalDeviceBlendOperationAdvancedFeaturesEXT);
+#[cfg(feature = "VK_EXT_blend_operation_advanced")]
+DeclareExtends!(VkPhysicalDeviceProperties2: VkPhysicalDeviceBlendOperationAdvancedPropertiesEXT);
+#[cfg(feature = "VK_EXT_vertex_attribute_divisor")]
+DeclareExtends!(VkPhysicalDeviceProperties2: VkPhysicalDeviceVertexAttributeDivisorPropertiesEXT);
+#[cfg(feature = "VK_EXT_memory_budget")]
+DeclareExtends!(VkPhysicalDeviceMemoryProperties2: VkPhysicalDeviceMemoryBudgetPropertiesEXT);
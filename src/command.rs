@@ -6,32 +6,59 @@ use {VkHandle, Device, DeviceChild};
 #[cfg(feature = "Implements")] use std::mem::{size_of, transmute};
 use std::ops::Range;
 use std::borrow::Borrow;
-use {Image, Buffer, ImageLayout};
+use {Image, Buffer, ImageLayout, BufferSlice};
 #[cfg(feature = "Implements")] use {Framebuffer, RenderPass, Pipeline, PipelineLayout, PipelineStageFlags, ShaderStage};
 #[cfg(feature = "Implements")] use {StencilFaceMask, FilterMode, Event};
 #[cfg(feature = "Implements")] use {QueryPipelineStatisticFlags, QueryPool, QueryResultFlags};
-#[cfg(feature = "Implements")] use ::vkresolve::{Resolver, ResolverInterface};
+#[cfg(all(feature = "Implements", feature = "VK_KHR_push_descriptor"))] use DescriptorSetWriteInfo;
+#[cfg(feature = "Implements")] use ::vkresolve::{DispatchRef, command_buffer_dispatch, register_command_buffer_dispatch, forget_command_buffer_dispatch};
+use std::sync::Arc;
+use extsync::PoolSync;
+#[cfg(all(feature = "Implements", any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils")))] use std::sync::RwLock;
+#[cfg(feature = "Implements")] use extsync::{PoolGuard, register_command_buffers, forget_command_buffers, forget_pool, lock_command_buffer};
 
-/// Opaque handle to a command pool object
-#[derive(Clone)] pub struct CommandPool(VkCommandPool, ::Device);
+/// Opaque handle to a command pool object.
+/// With `Multithreaded`, the pool is locked while it is used and while a command buffer allocated from it is recorded
+pub struct CommandPool(VkCommandPool, ::Device, Arc<PoolSync>);
 /// Opaque handle to a command buffer object
 #[repr(C)] #[derive(Clone, Copy)] pub struct CommandBuffer(VkCommandBuffer);
+ThreadSafe!(Send + Sync: CommandPool);
+// recording locks the pool the buffer was allocated from until the CmdRecord, which cannot leave the thread, is dropped
+ThreadSafe!(Send + Sync: CommandBuffer);
 
-#[cfg(feature = "Implements")] DeviceChildCommonDrop!{ for CommandPool[destroy_command_pool] }
+#[cfg(feature = "Implements")]
+impl Drop for CommandPool
+{
+	fn drop(&mut self)
+	{
+		forget_pool(&self.2);
+		forget_command_buffer_dispatch(self.0, None);
+		forget_region_markers(self.0, None);
+		unsafe { self.1.dispatch().destroy_command_pool(self.1.native_ptr(), self.0, self.1.allocation_callbacks()) };
+	}
+}
 impl VkHandle for CommandPool   { type Handle = VkCommandPool;   fn native_ptr(&self) -> VkCommandPool   { self.0 } }
 impl VkHandle for CommandBuffer { type Handle = VkCommandBuffer; fn native_ptr(&self) -> VkCommandBuffer { self.0 } }
 impl DeviceChild for CommandPool { fn device(&self) -> &Device { &self.1 } }
 
 /// The recording state of commandbuffers
 #[cfg(feature = "Implements")]
-pub struct CmdRecord<'d> { ptr: &'d CommandBuffer, layout: [Option<VkPipelineLayout>; 2] }
+pub struct CmdRecord<'d>
+{
+	ptr: &'d CommandBuffer, layout: [Option<VkPipelineLayout>; 2], dispatch: DispatchRef, _pool: PoolGuard,
+	/// Number of the debug regions opened by `debug_region` and not closed yet
+	regions: u32
+}
 
 /// Implicitly closing the recording state. This may cause a panic when there are errors in commands
 #[cfg(feature = "Implements")]
 impl<'d> Drop for CmdRecord<'d> {
 	fn drop(&mut self) {
+		if !::std::thread::panicking() {
+			debug_assert!(self.regions == 0, "{} debug region(s) are not closed at the end of recording", self.regions);
+		}
 		unsafe {
-			Resolver::get().end_command_buffer(self.ptr.native_ptr()).into_result()
+			self.dispatch.end_command_buffer(self.ptr.native_ptr()).into_result_in("vkEndCommandBuffer", "VkCommandBuffer")
 				.expect("Error closing command recording state");
 		}
 	}
@@ -54,8 +81,8 @@ impl CommandPool {
 		};
 		let mut h = VK_NULL_HANDLE as _;
 		unsafe {
-			Resolver::get().create_command_pool(device.native_ptr(), &cinfo, ::std::ptr::null(), &mut h)
-				.into_result().map(|_| CommandPool(h, device.clone()))
+			device.dispatch().create_command_pool(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h)
+				.into_result_in("vkCreateCommandPool", "VkDevice").map(|_| CommandPool(h, device.clone(), Arc::new(PoolSync::default())))
 		}
 	}
 	/// Allocate command buffers from an existing command pool
@@ -70,10 +97,14 @@ impl CommandPool {
 			commandPool: self.0, .. Default::default()
 		};
 		let mut hs = vec![VK_NULL_HANDLE as _; count as _];
+		let _g = PoolSync::lock(&self.2);
 		unsafe {
-			Resolver::get().allocate_command_buffers(self.1.native_ptr(), &ainfo, hs.as_mut_ptr()).into_result()
-				.map(|_| transmute(hs))
+			self.1.dispatch().allocate_command_buffers(self.1.native_ptr(), &ainfo, hs.as_mut_ptr()).into_result_in("vkAllocateCommandBuffers", "VkDevice")?;
 		}
+		register_command_buffers(&self.2, &hs);
+		register_command_buffer_dispatch(self.1.dispatch_ref(), self.0, &hs);
+		register_region_markers(self, &hs);
+		Ok(unsafe { transmute(hs) })
 	}
     /// Resets a command pool
     /// # Safety
@@ -85,11 +116,24 @@ impl CommandPool {
     /// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
 	pub fn reset(&self, release_resources: bool) -> ::Result<()> {
 		let flags = if release_resources { VK_COMMAND_POOL_RESET_RELEASE_RESOURCES_BIT } else { 0 };
-		unsafe { Resolver::get().reset_command_pool(self.1.native_ptr(), self.0, flags).into_result() }
+		let _g = PoolSync::lock(&self.2);
+		unsafe { self.1.dispatch().reset_command_pool(self.1.native_ptr(), self.0, flags).into_result_in("vkResetCommandPool", "VkDevice") }
 	}
 	/// Free command buffers
 	pub fn free(&self, buffers: &[CommandBuffer]) {
-		unsafe { Resolver::get().free_command_buffers(self.1.native_ptr(), self.0, buffers.len() as _, buffers.as_ptr() as *const _) };
+		let _g = PoolSync::lock(&self.2);
+		let hs = buffers.iter().map(VkHandle::native_ptr).collect::<Vec<_>>();
+		forget_command_buffers(hs.iter().cloned());
+		forget_command_buffer_dispatch(self.0, Some(&hs));
+		forget_region_markers(self.0, Some(buffers));
+		unsafe { self.1.dispatch().free_command_buffers(self.1.native_ptr(), self.0, buffers.len() as _, buffers.as_ptr() as *const _) };
+	}
+	/// [Vulkan 1.1] Return unused memory of the pool to the system
+	/// # Failures
+	/// Returns `Error::MissingEntryPoint` if the driver does not provide `vkTrimCommandPool`
+	pub fn trim(&self) -> ::Result<()> {
+		let _g = PoolSync::lock(&self.2);
+		unsafe { self.1.dispatch().trim_command_pool(self.1.native_ptr(), self.0, 0) }
 	}
 }
 
@@ -105,8 +149,10 @@ impl CommandBuffer
 	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
 	pub fn begin(&self) -> ::Result<CmdRecord> {
 		unsafe {
-			Resolver::get().begin_command_buffer(self.0, &Default::default()).into_result()
-				.map(|_| CmdRecord { ptr: self, layout: [None, None] })
+			let pool = lock_command_buffer(self.0)?;
+			let dispatch = command_buffer_dispatch(self.0)?;
+			dispatch.begin_command_buffer(self.0, &Default::default()).into_result_in("vkBeginCommandBuffer", "VkCommandBuffer")
+				.map(|_| CmdRecord { ptr: self, layout: [None, None], dispatch, _pool: pool, regions: 0 })
 		}
 	}
 	/// Start recording a primary command buffer that will be submitted once
@@ -120,7 +166,9 @@ impl CommandBuffer
 		let info = VkCommandBufferBeginInfo { flags: VK_COMMAND_BUFFER_USAGE_ONE_TIME_SUBMIT_BIT, .. Default::default() };
 		unsafe
 		{
-			Resolver::get().begin_command_buffer(self.0, &info).into_result().map(|_| CmdRecord { ptr: self, layout: [None, None] })
+			let pool = lock_command_buffer(self.0)?;
+			let dispatch = command_buffer_dispatch(self.0)?;
+			dispatch.begin_command_buffer(self.0, &info).into_result_in("vkBeginCommandBuffer", "VkCommandBuffer").map(|_| CmdRecord { ptr: self, layout: [None, None], dispatch, _pool: pool, regions: 0 })
 		}
 	}
 	/// Start recording a secondary command buffer
@@ -145,8 +193,10 @@ impl CommandBuffer
 		let binfo = VkCommandBufferBeginInfo { pInheritanceInfo: &inherit, flags, .. Default::default() };
 		unsafe
 		{
-			Resolver::get().begin_command_buffer(self.0, &binfo).into_result()
-				.map(|_| CmdRecord { ptr: self, layout: [None, None] })
+			let pool = lock_command_buffer(self.0)?;
+			let dispatch = command_buffer_dispatch(self.0)?;
+			dispatch.begin_command_buffer(self.0, &binfo).into_result_in("vkBeginCommandBuffer", "VkCommandBuffer")
+				.map(|_| CmdRecord { ptr: self, layout: [None, None], dispatch, _pool: pool, regions: 0 })
 		}
 	}
 }
@@ -172,7 +222,7 @@ impl<'d> CmdRecord<'d>
 		};
 		unsafe
 		{
-			Resolver::get().cmd_begin_render_pass(self.ptr.native_ptr(), &binfo,
+			self.dispatch.cmd_begin_render_pass(self.ptr.native_ptr(), &binfo,
 				if inline_commands { VK_SUBPASS_CONTENTS_INLINE } else { VK_SUBPASS_CONTENTS_SECONDARY_COMMAND_BUFFERS })
 		};
 
@@ -183,7 +233,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_next_subpass(self.ptr.native_ptr(),
+			self.dispatch.cmd_next_subpass(self.ptr.native_ptr(),
 				if inline_commands { VK_SUBPASS_CONTENTS_INLINE } else { VK_SUBPASS_CONTENTS_SECONDARY_COMMAND_BUFFERS })
 		};
 		
@@ -192,7 +242,7 @@ impl<'d> CmdRecord<'d>
 	/// End the current render pass
 	pub fn end_render_pass(&mut self) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_end_render_pass(self.ptr.native_ptr()) };
+		unsafe { self.dispatch.cmd_end_render_pass(self.ptr.native_ptr()) };
 		
 		self
 	}
@@ -205,14 +255,14 @@ impl<'d> CmdRecord<'d>
 	/// Bind a pipeline object to a command buffer
 	pub fn bind_graphics_pipeline(&mut self, pipeline: &Pipeline) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_bind_pipeline(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_GRAPHICS, pipeline.native_ptr()) };
+		unsafe { self.dispatch.cmd_bind_pipeline(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_GRAPHICS, pipeline.native_ptr()) };
 		
 		self
 	}
 	/// Bind a pipeline object to a command buffer
 	pub fn bind_compute_pipeline(&mut self, pipeline: &Pipeline) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_bind_pipeline(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_COMPUTE, pipeline.native_ptr()) };
+		unsafe { self.dispatch.cmd_bind_pipeline(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_COMPUTE, pipeline.native_ptr()) };
 		
 		self
 	}
@@ -254,7 +304,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_bind_descriptor_sets(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_GRAPHICS,
+			self.dispatch.cmd_bind_descriptor_sets(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_GRAPHICS,
 				self.current_pipeline_layout_g(),
 				first, descriptor_sets.len() as _, descriptor_sets.as_ptr(),
 				dynamic_offsets.len() as _, dynamic_offsets.as_ptr())
@@ -268,7 +318,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{ 
-			Resolver::get().cmd_bind_descriptor_sets(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_COMPUTE,
+			self.dispatch.cmd_bind_descriptor_sets(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_COMPUTE,
 				self.current_pipeline_layout_c(),
 				first, descriptor_sets.len() as _, descriptor_sets.as_ptr(),
 				dynamic_offsets.len() as _, dynamic_offsets.as_ptr())
@@ -281,7 +331,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_push_constants(self.ptr.native_ptr(), self.current_pipeline_layout_g(),
+			self.dispatch.cmd_push_constants(self.ptr.native_ptr(), self.current_pipeline_layout_g(),
 				stage.0, offset, size_of::<T>() as _, value as *const T as *const _);
 		}
 		
@@ -292,7 +342,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_push_constants(self.ptr.native_ptr(), self.current_pipeline_layout_c(),
+			self.dispatch.cmd_push_constants(self.ptr.native_ptr(), self.current_pipeline_layout_c(),
 				stage.0, offset, size_of::<T>() as _, value as *const T as *const _);
 		}
 		
@@ -300,8 +350,10 @@ impl<'d> CmdRecord<'d>
 	}
 
 	/// Push descriptor updates into a command buffer
+	/// # Failures
+	/// Returns `Error::MissingEntryPoint` if the driver does not provide `vkCmdPushDescriptorSetKHR`
 	#[cfg(feature = "VK_KHR_push_descriptor")]
-	pub fn push_graphics_descriptor_set(&mut self, set: u32, writes: &[DescriptorSetWriteInfo]) -> &mut Self
+	pub fn push_graphics_descriptor_set(&mut self, set: u32, writes: &[DescriptorSetWriteInfo]) -> ::Result<&mut Self>
 	{
 		// save flatten results
 		let wt = writes.iter().map(|x|
@@ -323,15 +375,17 @@ impl<'d> CmdRecord<'d>
 		}).collect::<Vec<_>>();
 		unsafe
 		{
-			Resolver::get().cmd_push_descriptor_set_khr(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_GRAPHICS,
-				self.current_pipeline_layout_g(), set, w.len() as _, w.as_ptr())
+			self.dispatch.cmd_push_descriptor_set_khr(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_GRAPHICS,
+				self.current_pipeline_layout_g(), set, w.len() as _, w.as_ptr())?
 		};
 	
-		self
+		Ok(self)
 	}
 	/// Push descriptor updates into a command buffer
+	/// # Failures
+	/// Returns `Error::MissingEntryPoint` if the driver does not provide `vkCmdPushDescriptorSetKHR`
 	#[cfg(feature = "VK_KHR_push_descriptor")]
-	pub fn push_compute_descriptor_set(&mut self, set: u32, writes: &[DescriptorSetWriteInfo]) -> &mut Self
+	pub fn push_compute_descriptor_set(&mut self, set: u32, writes: &[DescriptorSetWriteInfo]) -> ::Result<&mut Self>
 	{
 		// save flatten results
 		let wt = writes.iter().map(|x|
@@ -353,11 +407,11 @@ impl<'d> CmdRecord<'d>
 		}).collect::<Vec<_>>();
 		unsafe
 		{
-			Resolver::get().cmd_push_descriptor_set_khr(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_COMPUTE,
-				self.current_pipeline_layout_c(), set, w.len() as _, w.as_ptr())
+			self.dispatch.cmd_push_descriptor_set_khr(self.ptr.native_ptr(), VK_PIPELINE_BIND_POINT_COMPUTE,
+				self.current_pipeline_layout_c(), set, w.len() as _, w.as_ptr())?
 		};
 
-		self
+		Ok(self)
 	}
 }
 
@@ -368,63 +422,63 @@ impl<'d> CmdRecord<'d>
 	/// Set the viewport on a command buffer
 	pub fn set_viewport(&mut self, first: u32, viewports: &[VkViewport]) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_set_viewport(self.ptr.native_ptr(), first, viewports.len() as _, viewports.as_ptr()) };
+		unsafe { self.dispatch.cmd_set_viewport(self.ptr.native_ptr(), first, viewports.len() as _, viewports.as_ptr()) };
 		
 		self
 	}
 	/// Set the dynamic scissor rectangles on a command buffer
 	pub fn set_scissor(&mut self, first: u32, scissors: &[VkRect2D]) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_set_scissor(self.ptr.native_ptr(), first, scissors.len() as _, scissors.as_ptr()) };
+		unsafe { self.dispatch.cmd_set_scissor(self.ptr.native_ptr(), first, scissors.len() as _, scissors.as_ptr()) };
 	
 		self
 	}
 	/// Set the dynamic line width state
 	pub fn set_line_width(&mut self, w: f32) -> &Self
 	{
-		unsafe { Resolver::get().cmd_set_line_width(self.ptr.native_ptr(), w) };
+		unsafe { self.dispatch.cmd_set_line_width(self.ptr.native_ptr(), w) };
 		
 		self
 	}
 	/// Set the depth bias dynamic state
 	pub fn set_depth_bias(&mut self, constant_factor: f32, clamp: f32, slope_factor: f32) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_set_depth_bias(self.ptr.native_ptr(), constant_factor, clamp, slope_factor) };
+		unsafe { self.dispatch.cmd_set_depth_bias(self.ptr.native_ptr(), constant_factor, clamp, slope_factor) };
 		
 		self
 	}
 	/// Set the values of blend constants
 	pub fn set_blend_constants(&mut self, blend_constants: [f32; 4]) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_set_blend_constants(self.ptr.native_ptr(), blend_constants) };
+		unsafe { self.dispatch.cmd_set_blend_constants(self.ptr.native_ptr(), blend_constants) };
 		
 		self
 	}
 	/// Set the depth bounds test values for a command buffer
 	pub fn set_depth_bounds(&mut self, bounds: Range<f32>) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_set_depth_bounds(self.ptr.native_ptr(), bounds.start, bounds.end) };
+		unsafe { self.dispatch.cmd_set_depth_bounds(self.ptr.native_ptr(), bounds.start, bounds.end) };
 		
 		self
 	}
 	/// Set the stencil compare mask dynamic state
 	pub fn set_stencil_compare_mask(&mut self, face_mask: StencilFaceMask, compare_mask: u32) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_set_stencil_compare_mask(self.ptr.native_ptr(), face_mask as _, compare_mask) };
+		unsafe { self.dispatch.cmd_set_stencil_compare_mask(self.ptr.native_ptr(), face_mask as _, compare_mask) };
 		
 		self
 	}
 	/// Set the stencil write mask dynamic state
 	pub fn set_stencil_write_mask(&mut self, face_mask: StencilFaceMask, write_mask: u32) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_set_stencil_write_mask(self.ptr.native_ptr(), face_mask as _, write_mask) };
+		unsafe { self.dispatch.cmd_set_stencil_write_mask(self.ptr.native_ptr(), face_mask as _, write_mask) };
 		
 		self
 	}
 	/// Set the stencil reference dynamic state
 	pub fn set_stencil_reference(&mut self, face_mask: StencilFaceMask, reference: u32) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_set_stencil_reference(self.ptr.native_ptr(), face_mask as _, reference) };
+		unsafe { self.dispatch.cmd_set_stencil_reference(self.ptr.native_ptr(), face_mask as _, reference) };
 		
 		self
 	}
@@ -433,7 +487,7 @@ impl<'d> CmdRecord<'d>
 	#[cfg(feature = "VK_EXT_sample_locations")]
 	pub fn set_sample_locations(&mut self, info: &VkSampleLocationsInfoEXT) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_set_sample_locations_ext(self.ptr.native_ptr(), info as _); }
+		unsafe { self.dispatch.cmd_set_sample_locations_ext(self.ptr.native_ptr(), info as _); }
 		
 		self
 	}
@@ -446,7 +500,7 @@ impl<'d> CmdRecord<'d>
 	/// Bind an index buffer to a command buffer
 	pub fn bind_index_buffer(&mut self, buffer: &Buffer, offset: usize, index_type: IndexType) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_bind_index_buffer(self.ptr.native_ptr(), buffer.native_ptr(), offset as _, index_type as _) };
+		unsafe { self.dispatch.cmd_bind_index_buffer(self.ptr.native_ptr(), buffer.native_ptr(), offset as _, index_type as _) };
 		
 		self
 	}
@@ -455,10 +509,20 @@ impl<'d> CmdRecord<'d>
 	{
 		let (bufs, ofs): (Vec<_>, Vec<_>) =
 			buffers.into_iter().map(|&(b, o)| (b.native_ptr(), o as VkDeviceSize)).unzip();
-		unsafe { Resolver::get().cmd_bind_vertex_buffers(self.ptr.native_ptr(), first, bufs.len() as _, bufs.as_ptr(), ofs.as_ptr()) };
+		unsafe { self.dispatch.cmd_bind_vertex_buffers(self.ptr.native_ptr(), first, bufs.len() as _, bufs.as_ptr(), ofs.as_ptr()) };
 		
 		self
 	}
+	/// Bind the elements of `indices` as the index buffer. The index type is determined by `T`
+	pub fn bind_index_slice<T: IndexElement>(&mut self, indices: BufferSlice<T>) -> &mut Self
+	{
+		self.bind_index_buffer(indices.buffer(), indices.offset(), T::INDEX_TYPE)
+	}
+	/// Bind the elements of `vertices` to a vertex input binding
+	pub fn bind_vertex_slice<T>(&mut self, binding: u32, vertices: BufferSlice<T>) -> &mut Self
+	{
+		self.bind_vertex_buffers(binding, &[(vertices.buffer(), vertices.offset())])
+	}
 }
 
 /// [feature = "Implements"] Graphics Commands: Inside a Render Pass
@@ -468,7 +532,7 @@ impl<'d> CmdRecord<'d>
 	/// Draw primitives
 	pub fn draw(&mut self, vertex_count: u32, instance_count: u32, first_vertex: u32, first_instance: u32) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_draw(self.ptr.native_ptr(), vertex_count, instance_count, first_vertex, first_instance) };
+		unsafe { self.dispatch.cmd_draw(self.ptr.native_ptr(), vertex_count, instance_count, first_vertex, first_instance) };
 		
 		self
 	}
@@ -477,7 +541,7 @@ impl<'d> CmdRecord<'d>
 		first_index: u32, vertex_offset: i32, first_instance: u32) -> &mut Self
 	{
 		unsafe {
-			Resolver::get().cmd_draw_indexed(self.ptr.native_ptr(), index_count, instance_count,
+			self.dispatch.cmd_draw_indexed(self.ptr.native_ptr(), index_count, instance_count,
 				first_index, vertex_offset, first_instance)
 		};
 		
@@ -486,7 +550,7 @@ impl<'d> CmdRecord<'d>
 	/// Issue an indirect draw into a command buffer
 	pub fn draw_indirect(&mut self, buffer: &Buffer, offset: usize, draw_count: u32, stride: u32) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_draw_indirect(self.ptr.native_ptr(), buffer.native_ptr(), offset as _, draw_count, stride) };
+		unsafe { self.dispatch.cmd_draw_indirect(self.ptr.native_ptr(), buffer.native_ptr(), offset as _, draw_count, stride) };
 		
 		self
 	}
@@ -495,11 +559,21 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_draw_indexed_indirect(self.ptr.native_ptr(), buffer.native_ptr(), offset as _, draw_count, stride)
+			self.dispatch.cmd_draw_indexed_indirect(self.ptr.native_ptr(), buffer.native_ptr(), offset as _, draw_count, stride)
 		};
 		
 		self
 	}
+	/// Issue an indirect draw for each element of `commands`
+	pub fn draw_indirect_slice(&mut self, commands: BufferSlice<VkDrawIndirectCommand>) -> &mut Self
+	{
+		self.draw_indirect(commands.buffer(), commands.offset(), commands.len() as _, commands.stride() as _)
+	}
+	/// Perform an indexed indirect draw for each element of `commands`
+	pub fn draw_indexed_indirect_slice(&mut self, commands: BufferSlice<VkDrawIndexedIndirectCommand>) -> &mut Self
+	{
+		self.draw_indexed_indirect(commands.buffer(), commands.offset(), commands.len() as _, commands.stride() as _)
+	}
 }
 
 /// [feature = "Implements"] Compute Commands: Dispatching kernels
@@ -509,17 +583,47 @@ impl<'d> CmdRecord<'d>
 	/// Dispatch compute work items
 	pub fn dispatch(&mut self, group_count_x: u32, group_count_y: u32, group_count_z: u32) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_dispatch(self.ptr.native_ptr(), group_count_x, group_count_y, group_count_z) };
+		unsafe { self.dispatch.cmd_dispatch(self.ptr.native_ptr(), group_count_x, group_count_y, group_count_z) };
 		
 		self
 	}
 	/// Dispatch compute work items using indirect parameters
 	pub fn dispatch_indirect(&mut self, buffer: &Buffer, offset: usize) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_dispatch_indirect(self.ptr.native_ptr(), buffer.native_ptr(), offset as _) };
+		unsafe { self.dispatch.cmd_dispatch_indirect(self.ptr.native_ptr(), buffer.native_ptr(), offset as _) };
 		
 		self
 	}
+	/// Dispatch compute work items using the indirect parameters in `command`
+	/// # Panics
+	/// Panics if `command` does not have exactly one element
+	pub fn dispatch_indirect_slice(&mut self, command: BufferSlice<VkDispatchIndirectCommand>) -> &mut Self
+	{
+		assert_eq!(command.len(), 1, "dispatch_indirect_slice takes exactly one command");
+		self.dispatch_indirect(command.buffer(), command.offset())
+	}
+	/// [Vulkan 1.1] Dispatch compute work items with non-zero base values for the workgroup IDs
+	/// # Failures
+	/// Returns `Error::MissingEntryPoint` if the driver does not provide `vkCmdDispatchBase`
+	pub fn dispatch_base(&mut self, base_group: (u32, u32, u32), group_count: (u32, u32, u32)) -> ::Result<&mut Self>
+	{
+		unsafe
+		{
+			self.dispatch.cmd_dispatch_base(self.ptr.native_ptr(), base_group.0, base_group.1, base_group.2,
+				group_count.0, group_count.1, group_count.2)?
+		};
+		
+		Ok(self)
+	}
+	/// [Vulkan 1.1] Modify device mask of a command buffer
+	/// # Failures
+	/// Returns `Error::MissingEntryPoint` if the driver does not provide `vkCmdSetDeviceMask`
+	pub fn set_device_mask(&mut self, mask: u32) -> ::Result<&mut Self>
+	{
+		unsafe { self.dispatch.cmd_set_device_mask(self.ptr.native_ptr(), mask)? };
+		
+		Ok(self)
+	}
 }
 
 /// [feature = "Implements"] Transfer Commands: Copying resources
@@ -531,7 +635,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_copy_buffer(self.ptr.native_ptr(), src.native_ptr(),
+			self.dispatch.cmd_copy_buffer(self.ptr.native_ptr(), src.native_ptr(),
 				dst.native_ptr(), regions.len() as _, regions.as_ptr())
 		};
 		
@@ -543,7 +647,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_copy_image(self.ptr.native_ptr(), src.native_ptr(), src_layout as _,
+			self.dispatch.cmd_copy_image(self.ptr.native_ptr(), src.native_ptr(), src_layout as _,
 				dst.native_ptr(), dst_layout as _, regions.len() as _, regions.as_ptr())
 		};
 		
@@ -555,7 +659,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_blit_image(self.ptr.native_ptr(), src.native_ptr(), src_layout as _, dst.native_ptr(), dst_layout as _,
+			self.dispatch.cmd_blit_image(self.ptr.native_ptr(), src.native_ptr(), src_layout as _, dst.native_ptr(), dst_layout as _,
 				regions.len() as _, regions.as_ptr(), filter as _)
 		};
 
@@ -567,7 +671,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_copy_buffer_to_image(self.ptr.native_ptr(), src_buffer.native_ptr(),
+			self.dispatch.cmd_copy_buffer_to_image(self.ptr.native_ptr(), src_buffer.native_ptr(),
 				dst_image.native_ptr(), dst_layout as _, regions.len() as _, regions.as_ptr())
 		};
 		
@@ -579,7 +683,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_copy_image_to_buffer(self.ptr.native_ptr(), src_image.native_ptr(), src_layout as _,
+			self.dispatch.cmd_copy_image_to_buffer(self.ptr.native_ptr(), src_image.native_ptr(), src_layout as _,
 				dst_buffer.native_ptr(), regions.len() as _, regions.as_ptr())
 		};
 		
@@ -591,10 +695,44 @@ impl<'d> CmdRecord<'d>
 		assert!(size <= size_of::<T>(), "Updated size exceeds size of datatype");
 		unsafe
 		{
-			Resolver::get().cmd_update_buffer(self.ptr.native_ptr(), dst.native_ptr(), dst_offset as _, size as _,
+			self.dispatch.cmd_update_buffer(self.ptr.native_ptr(), dst.native_ptr(), dst_offset as _, size as _,
 				data as *const T as *const _)
 		};
 	
+		self
+	}
+	/// Copy the elements of `src` to `dst`. Nothing is recorded if the slices are empty
+	/// # Panics
+	/// Panics if the slices have different numbers of elements, or they overlap in the same buffer
+	pub fn copy_slice<T>(&mut self, src: BufferSlice<T>, dst: BufferSlice<T>) -> &mut Self
+	{
+		assert_eq!(src.len(), dst.len(), "source and destination slices have different numbers of elements");
+		if src.is_empty() { return self; }
+		// VUID-vkCmdCopyBuffer-pRegions-00117
+		assert!(src.buffer().native_ptr() != dst.buffer().native_ptr() ||
+			src.byte_range().end <= dst.offset() || dst.byte_range().end <= src.offset(),
+			"source and destination slices overlap: {:?} and {:?}", src.byte_range(), dst.byte_range());
+		self.copy_buffer(src.buffer(), dst.buffer(), &[VkBufferCopy
+		{
+			srcOffset: src.offset() as _, dstOffset: dst.offset() as _, size: src.byte_size() as _
+		}])
+	}
+	/// Update the elements of `dst` with `data`. Nothing is recorded if `dst` is empty
+	/// # Panics
+	/// Panics if the numbers of elements differ, or `vkCmdUpdateBuffer` cannot update the range
+	/// (the offset and the size must be multiples of 4, and the size must not exceed 65536 bytes)
+	pub fn update_slice<T: ::PlainData>(&mut self, dst: BufferSlice<T>, data: &[T]) -> &mut Self
+	{
+		assert_eq!(dst.len(), data.len(), "destination slice and data have different numbers of elements");
+		if dst.is_empty() { return self; }
+		assert!(dst.offset() % 4 == 0 && dst.byte_size() % 4 == 0 && dst.byte_size() <= 65536,
+			"{} bytes at offset {} cannot be updated by vkCmdUpdateBuffer", dst.byte_size(), dst.offset());
+		unsafe
+		{
+			self.dispatch.cmd_update_buffer(self.ptr.native_ptr(), dst.buffer().native_ptr(), dst.offset() as _, dst.byte_size() as _,
+				data.as_ptr() as *const _)
+		};
+
 		self
 	}
 }
@@ -607,17 +745,26 @@ impl<'d> CmdRecord<'d>
 	/// `size` is number of bytes to fill
 	pub fn fill_buffer(&mut self, dst: &Buffer, dst_offset: usize, size: usize, data: u32) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_fill_buffer(self.ptr.native_ptr(), dst.native_ptr(), dst_offset as _, size as _, data) };
+		unsafe { self.dispatch.cmd_fill_buffer(self.ptr.native_ptr(), dst.native_ptr(), dst_offset as _, size as _, data) };
 		
 		self
 	}
+	/// Fill the elements of `dst` with repeated `data`. Nothing is recorded if `dst` is empty
+	/// # Panics
+	/// Panics if the offset or the size of `dst` is not a multiple of 4
+	pub fn fill_slice<T>(&mut self, dst: BufferSlice<T>, data: u32) -> &mut Self
+	{
+		if dst.is_empty() { return self; }
+		assert!(dst.offset() % 4 == 0 && dst.byte_size() % 4 == 0, "{} bytes at offset {} are not aligned to 4 bytes", dst.byte_size(), dst.offset());
+		self.fill_buffer(dst.buffer(), dst.offset(), dst.byte_size(), data)
+	}
 	/// Clear regions of a color image
 	pub fn clear_color_image<T: ClearColorValue>(&mut self, image: &Image, layout: ImageLayout,
 		color: &T, ranges: &[VkImageSubresourceRange]) -> &mut Self
 	{
 		unsafe
 		{
-			Resolver::get().cmd_clear_color_image(self.ptr.native_ptr(), image.native_ptr(), layout as _,
+			self.dispatch.cmd_clear_color_image(self.ptr.native_ptr(), image.native_ptr(), layout as _,
 				color.represent(), ranges.len() as _, ranges.as_ptr())
 		};
 		
@@ -629,7 +776,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_clear_depth_stencil_image(self.ptr.native_ptr(), image.native_ptr(),
+			self.dispatch.cmd_clear_depth_stencil_image(self.ptr.native_ptr(), image.native_ptr(),
 				layout as _, &VkClearDepthStencilValue { depth, stencil }, ranges.len() as _, ranges.as_ptr())
 		};
 		
@@ -640,7 +787,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_clear_attachments(self.ptr.native_ptr(), attachments.len() as _,
+			self.dispatch.cmd_clear_attachments(self.ptr.native_ptr(), attachments.len() as _,
 				attachments.as_ptr(), rects.len() as _, rects.as_ptr())
 		};
 	
@@ -658,7 +805,7 @@ impl<'d> CmdRecord<'d>
 	/// Caller must be primary buffer and in the render pass when executing secondary command buffer
 	pub unsafe fn execute_commands(&mut self, buffers: &[VkCommandBuffer]) -> &mut Self
 	{
-		Resolver::get().cmd_execute_commands(self.ptr.native_ptr(), buffers.len() as _, buffers.as_ptr());
+		self.dispatch.cmd_execute_commands(self.ptr.native_ptr(), buffers.len() as _, buffers.as_ptr());
 
 		self
 	}
@@ -674,7 +821,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_resolve_image(self.ptr.native_ptr(), src.native_ptr(), src_layout as _,
+			self.dispatch.cmd_resolve_image(self.ptr.native_ptr(), src.native_ptr(), src_layout as _,
 				dst.native_ptr(), dst_layout as _, regions.len() as _, regions.as_ptr())
 		};
 
@@ -689,14 +836,14 @@ impl<'d> CmdRecord<'d>
 	/// Set an event object to signaled state
 	pub fn set_event(&mut self, event: &Event, stage_mask: PipelineStageFlags) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_set_event(self.ptr.native_ptr(), event.0, stage_mask.0) };
+		unsafe { self.dispatch.cmd_set_event(self.ptr.native_ptr(), event.0, stage_mask.0) };
 		
 		self
 	}
 	/// Reset an event object to non-signaled state
 	pub fn reset_event(&mut self, event: &Event, stage_mask: PipelineStageFlags) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_reset_event(self.ptr.native_ptr(), event.0, stage_mask.0) };
+		unsafe { self.dispatch.cmd_reset_event(self.ptr.native_ptr(), event.0, stage_mask.0) };
 		
 		self
 	}
@@ -709,7 +856,7 @@ impl<'d> CmdRecord<'d>
 		let evs = events.into_iter().map(|x| x.0).collect::<Vec<_>>();
 		unsafe
 		{
-			Resolver::get().cmd_wait_events(self.ptr.native_ptr(), evs.len() as _, evs.as_ptr(), src_stage_mask.0, dst_stage_mask.0,
+			self.dispatch.cmd_wait_events(self.ptr.native_ptr(), evs.len() as _, evs.as_ptr(), src_stage_mask.0, dst_stage_mask.0,
 				memory_barriers.len() as _, memory_barriers.as_ptr(),
 				buffer_memory_barriers.len() as _, buffer_memory_barriers.as_ptr(),
 				image_memory_barriers.len() as _, image_memory_barriers.as_ptr())
@@ -724,7 +871,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_pipeline_barrier(self.ptr.native_ptr(), src_stage_mask.0, dst_stage_mask.0,
+			self.dispatch.cmd_pipeline_barrier(self.ptr.native_ptr(), src_stage_mask.0, dst_stage_mask.0,
 				if by_region { VK_DEPENDENCY_BY_REGION_BIT } else { 0 },
 				memory_barriers.len() as _, memory_barriers.as_ptr(),
 				buffer_memory_barriers.len() as _, buffer_memory_barriers.as_ptr() as _,
@@ -744,7 +891,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_begin_query(self.ptr.native_ptr(), pool.0, query,
+			self.dispatch.cmd_begin_query(self.ptr.native_ptr(), pool.0, query,
 				if precise_query { VK_QUERY_CONTROL_PRECISE_BIT } else { 0 })
 		};
 
@@ -753,21 +900,21 @@ impl<'d> CmdRecord<'d>
 	/// Ends a query
 	pub fn end_query(&mut self, pool: &QueryPool, query: u32) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_end_query(self.ptr.native_ptr(), pool.0, query) };
+		unsafe { self.dispatch.cmd_end_query(self.ptr.native_ptr(), pool.0, query) };
 		
 		self
 	}
 	/// Reset queries in a query pool
 	pub fn reset_query_pool(&mut self, pool: &QueryPool, range: Range<u32>) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_reset_query_pool(self.ptr.native_ptr(), pool.0, range.start, range.end - range.start) };
+		unsafe { self.dispatch.cmd_reset_query_pool(self.ptr.native_ptr(), pool.0, range.start, range.end - range.start) };
 
 		self
 	}
 	/// Write a device timestamp into a query object
 	pub fn write_timestamp(&mut self, stage: PipelineStageFlags, pool: &QueryPool, query: u32) -> &mut Self
 	{
-		unsafe { Resolver::get().cmd_write_timestamp(self.ptr.native_ptr(), stage.0, pool.0, query) };
+		unsafe { self.dispatch.cmd_write_timestamp(self.ptr.native_ptr(), stage.0, pool.0, query) };
 		
 		self
 	}
@@ -777,7 +924,7 @@ impl<'d> CmdRecord<'d>
 	{
 		unsafe
 		{
-			Resolver::get().cmd_copy_query_pool_results(self.ptr.native_ptr(), pool.0, range.start, range.end - range.start,
+			self.dispatch.cmd_copy_query_pool_results(self.ptr.native_ptr(), pool.0, range.start, range.end - range.start,
 				dst.native_ptr(), dst_offset as _, stride as _,
 				flags.0 | if wide_result { VK_QUERY_RESULT_64_BIT } else { 0 })
 		};
@@ -786,6 +933,281 @@ impl<'d> CmdRecord<'d>
 	}
 }
 
+/// [feature = "Implements", "VK_EXT_debug_utils"] Debug Utils: Labeling commands
+///
+/// Each command fails with `Error::MissingEntryPoint` if the driver does not provide it(`VK_EXT_debug_utils` is not enabled)
+#[cfg(all(feature = "Implements", feature = "VK_EXT_debug_utils"))]
+impl<'d> CmdRecord<'d>
+{
+	/// Open a label region on the command buffer. `color` is RGBA, all zeros for no color
+	pub fn begin_label(&mut self, name: &str, color: [f32; 4]) -> ::Result<&mut Self>
+	{
+		::debug_utils::with_label(name, color, |l| unsafe { self.dispatch.cmd_begin_debug_utils_label_ext(self.ptr.native_ptr(), l) })?;
+
+		Ok(self)
+	}
+	/// Close the innermost label region opened by `begin_label`
+	pub fn end_label(&mut self) -> ::Result<&mut Self>
+	{
+		unsafe { self.dispatch.cmd_end_debug_utils_label_ext(self.ptr.native_ptr())? };
+
+		Ok(self)
+	}
+	/// Insert a single label into the command buffer
+	pub fn insert_label(&mut self, name: &str, color: [f32; 4]) -> ::Result<&mut Self>
+	{
+		::debug_utils::with_label(name, color, |l| unsafe { self.dispatch.cmd_insert_debug_utils_label_ext(self.ptr.native_ptr(), l) })?;
+
+		Ok(self)
+	}
+}
+
+/// [feature = "Implements"] Debug Regions
+///
+/// Regions are emitted as `VK_EXT_debug_utils` labels if the extension is enabled on the instance,
+/// otherwise as `VK_EXT_debug_marker` markers if that is enabled on the device.
+/// Without either extension no command is recorded, but the hooks are still called
+#[cfg(feature = "Implements")]
+impl<'d> CmdRecord<'d>
+{
+	/// Open a named region which is closed when the returned guard is dropped. `color` is RGBA, all zeros for no color.
+	/// Regions opened through the guard are nested in this region
+	pub fn debug_region(&mut self, name: &str, color: [f32; 4]) -> DebugRegion<'_, 'd>
+	{
+		DebugRegion::open(self, name, color, None)
+	}
+	/// Open a named region like `debug_region`, calling `hook` at the beginning and the end of this region and the nested ones
+	pub fn debug_region_with<'r>(&'r mut self, name: &str, color: [f32; 4], hook: &'r mut dyn DebugRegionHook) -> DebugRegion<'r, 'd>
+	{
+		DebugRegion::open(self, name, color, Some(hook))
+	}
+
+	/// The markers are chosen from the enabled extensions, so the entry points are provided
+	#[cfg(any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils"))]
+	fn begin_region_marker(&mut self, name: &str, color: [f32; 4])
+	{
+		// the driver would see the name only up to the first NUL
+		let name = name.split('\0').next().unwrap_or("");
+		let r = match region_markers_of(self.ptr.native_ptr())
+		{
+			#[cfg(feature = "VK_EXT_debug_utils")]
+			Some(RegionMarkers::DebugUtils) => ::debug_utils::with_label(name, color,
+				|l| unsafe { self.dispatch.cmd_begin_debug_utils_label_ext(self.ptr.native_ptr(), l) }),
+			#[cfg(feature = "VK_EXT_debug_marker")]
+			Some(RegionMarkers::DebugMarker) =>
+			{
+				let name = ::std::ffi::CString::new(name).unwrap_or_default();
+				let info = VkDebugMarkerMarkerInfoEXT { pMarkerName: name.as_ptr(), color, .. Default::default() };
+				unsafe { self.dispatch.cmd_debug_marker_begin_ext(self.ptr.native_ptr(), &info) }
+			},
+			None => Ok(())
+		};
+		debug_assert!(r.is_ok(), "{:?}", r);
+	}
+	#[cfg(any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils"))]
+	fn end_region_marker(&mut self)
+	{
+		let r = match region_markers_of(self.ptr.native_ptr())
+		{
+			#[cfg(feature = "VK_EXT_debug_utils")]
+			Some(RegionMarkers::DebugUtils) => unsafe { self.dispatch.cmd_end_debug_utils_label_ext(self.ptr.native_ptr()) },
+			#[cfg(feature = "VK_EXT_debug_marker")]
+			Some(RegionMarkers::DebugMarker) => unsafe { self.dispatch.cmd_debug_marker_end_ext(self.ptr.native_ptr()) },
+			None => Ok(())
+		};
+		debug_assert!(r.is_ok(), "{:?}", r);
+	}
+	#[cfg(not(any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils")))]
+	fn begin_region_marker(&mut self, _: &str, _: [f32; 4]) {}
+	#[cfg(not(any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils")))]
+	fn end_region_marker(&mut self) {}
+}
+/// The extension which the debug regions of a command buffer are emitted through
+#[cfg(all(feature = "Implements", any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils")))]
+#[derive(Clone, Copy)]
+enum RegionMarkers
+{
+	/// `VK_EXT_debug_utils` labels(enabled on the instance)
+	#[cfg(feature = "VK_EXT_debug_utils")] DebugUtils,
+	/// `VK_EXT_debug_marker` markers(enabled on the device)
+	#[cfg(feature = "VK_EXT_debug_marker")] DebugMarker
+}
+#[cfg(all(feature = "Implements", any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils")))]
+impl RegionMarkers
+{
+	/// The extension enabled for `device`, preferring `VK_EXT_debug_utils`
+	fn of(device: &Device) -> Option<Self>
+	{
+		#[cfg(feature = "VK_EXT_debug_utils")]
+		{
+			if device.instance().is_extension_enabled("VK_EXT_debug_utils") { return Some(RegionMarkers::DebugUtils); }
+		}
+		#[cfg(feature = "VK_EXT_debug_marker")]
+		{
+			if device.is_extension_enabled("VK_EXT_debug_marker") { return Some(RegionMarkers::DebugMarker); }
+		}
+		None
+	}
+}
+/// Command buffers which emit debug regions, with their pools. The buffers of the devices enabling neither extension are not listed
+#[cfg(all(feature = "Implements", any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils")))]
+static REGION_MARKERS: RwLock<Vec<(usize, usize, RegionMarkers)>> = RwLock::new(Vec::new());
+#[cfg(all(feature = "Implements", any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils")))]
+fn register_region_markers(pool: &CommandPool, buffers: &[VkCommandBuffer])
+{
+	if let Some(m) = RegionMarkers::of(&pool.1)
+	{
+		let mut r = REGION_MARKERS.write().unwrap_or_else(|e| e.into_inner());
+		r.extend(buffers.iter().map(|&b| (b as usize, pool.0 as usize, m)));
+	}
+}
+/// Forget `buffers`, or every buffer of `pool` if `None`
+#[cfg(all(feature = "Implements", any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils")))]
+fn forget_region_markers(pool: VkCommandPool, buffers: Option<&[CommandBuffer]>)
+{
+	let mut r = REGION_MARKERS.write().unwrap_or_else(|e| e.into_inner());
+	if r.is_empty() { return; }
+	match buffers
+	{
+		Some(bs) => r.retain(|&(b, _, _)| bs.iter().all(|x| x.0 as usize != b)),
+		None => r.retain(|&(_, p, _)| p != pool as usize)
+	}
+}
+#[cfg(all(feature = "Implements", any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils")))]
+fn region_markers_of(buffer: VkCommandBuffer) -> Option<RegionMarkers>
+{
+	REGION_MARKERS.read().unwrap_or_else(|e| e.into_inner()).iter().find(|&&(b, _, _)| b == buffer as usize).map(|&(_, _, m)| m)
+}
+#[cfg(all(feature = "Implements", not(any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils"))))]
+fn register_region_markers(_: &CommandPool, _: &[VkCommandBuffer]) {}
+#[cfg(all(feature = "Implements", not(any(feature = "VK_EXT_debug_marker", feature = "VK_EXT_debug_utils"))))]
+fn forget_region_markers(_: VkCommandPool, _: Option<&[CommandBuffer]>) {}
+/// A debug region opened by `CmdRecord::debug_region`. Commands can be recorded through this guard
+#[cfg(feature = "Implements")]
+pub struct DebugRegion<'r, 'd: 'r>
+{
+	rec: &'r mut CmdRecord<'d>, depth: u32, hook: Option<&'r mut dyn DebugRegionHook>
+}
+#[cfg(feature = "Implements")]
+impl<'r, 'd: 'r> DebugRegion<'r, 'd>
+{
+	fn open(rec: &'r mut CmdRecord<'d>, name: &str, color: [f32; 4], mut hook: Option<&'r mut dyn DebugRegionHook>) -> Self
+	{
+		rec.begin_region_marker(name, color);
+		rec.regions += 1;
+		let depth = rec.regions;
+		if let Some(ref mut h) = hook { h.begin(rec, name, depth); }
+		DebugRegion { rec, depth, hook }
+	}
+	/// Open a region nested in this region, sharing the hook of this region
+	pub fn debug_region(&mut self, name: &str, color: [f32; 4]) -> DebugRegion<'_, 'd>
+	{
+		let hook = self.hook.as_mut().map(|h| &mut **h as &mut dyn DebugRegionHook);
+		DebugRegion::open(self.rec, name, color, hook)
+	}
+	/// Nesting level of this region(1 for the outermost region)
+	pub fn depth(&self) -> u32 { self.depth }
+}
+#[cfg(feature = "Implements")]
+impl<'r, 'd: 'r> ::std::ops::Deref for DebugRegion<'r, 'd>
+{
+	type Target = CmdRecord<'d>;
+	fn deref(&self) -> &CmdRecord<'d> { self.rec }
+}
+#[cfg(feature = "Implements")]
+impl<'r, 'd: 'r> ::std::ops::DerefMut for DebugRegion<'r, 'd>
+{
+	fn deref_mut(&mut self) -> &mut CmdRecord<'d> { self.rec }
+}
+#[cfg(feature = "Implements")]
+impl<'r, 'd: 'r> Drop for DebugRegion<'r, 'd>
+{
+	fn drop(&mut self)
+	{
+		if !::std::thread::panicking()
+		{
+			debug_assert!(self.rec.regions == self.depth, "debug region at depth {} is closed while {} region(s) are open",
+				self.depth, self.rec.regions);
+		}
+		if let Some(ref mut h) = self.hook { h.end(self.rec, self.depth); }
+		self.rec.end_region_marker();
+		self.rec.regions -= 1;
+	}
+}
+/// Called at the beginning and the end of debug regions, e.g. to record additional commands around them
+#[cfg(feature = "Implements")]
+pub trait DebugRegionHook
+{
+	/// Called after a region is opened. `depth` is 1 for the outermost region
+	fn begin(&mut self, rec: &mut CmdRecord, name: &str, depth: u32);
+	/// Called before the region at `depth` is closed
+	fn end(&mut self, rec: &mut CmdRecord, depth: u32);
+}
+/// A region timed by `RegionTimestamps`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimedRegion
+{
+	pub name: String,
+	/// Nesting level of the region(1 for the outermost region)
+	pub depth: u32,
+	/// Index of the query written at the beginning of the region. The end is written to the next query
+	pub query: u32
+}
+impl TimedRegion
+{
+	/// Elapsed time of the region in nanoseconds. `timestamps` are the results of `RegionTimestamps::query_range`,
+	/// `timestamp_period` is `VkPhysicalDeviceLimits::timestampPeriod`
+	pub fn elapsed_ns(&self, timestamps: &[u64], first_query: u32, timestamp_period: f32) -> f64
+	{
+		let i = (self.query - first_query) as usize;
+		timestamps[i + 1].wrapping_sub(timestamps[i]) as f64 * timestamp_period as f64
+	}
+}
+/// A `DebugRegionHook` writing timestamps at the beginning and the end of each region, so that regions double as GPU profiling scopes.
+/// Each region consumes two queries of the pool. Regions opened after the queries run out are not timed
+#[cfg(feature = "Implements")]
+pub struct RegionTimestamps<'p>
+{
+	pool: &'p QueryPool, queries: Range<u32>, next: u32, regions: Vec<TimedRegion>,
+	/// End queries of the open regions(`None` for the regions not timed)
+	open: Vec<Option<u32>>
+}
+#[cfg(feature = "Implements")]
+impl<'p> RegionTimestamps<'p>
+{
+	/// Write timestamps to the `queries` of the timestamp query `pool`
+	pub fn new(pool: &'p QueryPool, queries: Range<u32>) -> Self
+	{
+		RegionTimestamps { next: queries.start, pool, queries, regions: Vec::new(), open: Vec::new() }
+	}
+	/// Record a reset of the queries, and forget the regions timed so far
+	pub fn reset(&mut self, rec: &mut CmdRecord)
+	{
+		rec.reset_query_pool(self.pool, self.queries.clone());
+		self.next = self.queries.start; self.regions.clear(); self.open.clear();
+	}
+	/// Regions timed so far, in the order they were opened
+	pub fn regions(&self) -> &[TimedRegion] { &self.regions }
+	/// Queries written so far
+	pub fn query_range(&self) -> Range<u32> { self.queries.start .. self.next }
+}
+#[cfg(feature = "Implements")]
+impl<'p> DebugRegionHook for RegionTimestamps<'p>
+{
+	fn begin(&mut self, rec: &mut CmdRecord, name: &str, depth: u32)
+	{
+		if self.next + 2 > self.queries.end { self.open.push(None); return; }
+		rec.write_timestamp(PipelineStageFlags::TOP_OF_PIPE, self.pool, self.next);
+		self.regions.push(TimedRegion { name: name.to_owned(), depth, query: self.next });
+		self.open.push(Some(self.next + 1));
+		self.next += 2;
+	}
+	fn end(&mut self, rec: &mut CmdRecord, _: u32)
+	{
+		if let Some(Some(q)) = self.open.pop() { rec.write_timestamp(PipelineStageFlags::BOTTOM_OF_PIPE, self.pool, q); }
+	}
+}
+
 /// The trait representation of `VkClearColorValue`
 pub trait ClearColorValue
 {
@@ -822,6 +1244,10 @@ pub enum IndexType
 	/// Indices are 32-bit unsigned integer values
 	U32 = VK_INDEX_TYPE_UINT32 as _
 }
+/// Element types of an index buffer
+pub trait IndexElement: Copy { const INDEX_TYPE: IndexType; }
+impl IndexElement for u16 { const INDEX_TYPE: IndexType = IndexType::U16; }
+impl IndexElement for u32 { const INDEX_TYPE: IndexType = IndexType::U32; }
 
 /// Enabling or disabling the occlusion query
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -980,6 +1406,13 @@ impl BufferMemoryBarrier
 			srcAccessMask: src_access_mask, dstAccessMask: dst_access_mask, .. Default::default()
 		})
 	}
+	/// Construct a new buffer descriptor covering the elements of `slice`,
+	/// or `None` if `slice` is empty(a barrier of zero bytes is invalid, so it should be skipped)
+	pub fn for_slice<T>(slice: BufferSlice<T>, src_access_mask: VkAccessFlags, dst_access_mask: VkAccessFlags) -> Option<Self>
+	{
+		if slice.is_empty() { return None; }
+		Some(Self::new(slice.buffer(), slice.byte_range(), src_access_mask, dst_access_mask))
+	}
 	/// Update the source access mask
 	pub fn src_access_mask(mut self, mask: VkAccessFlags) -> Self { self.0.srcAccessMask = mask; self }
 	/// Update the destination access mask
@@ -991,3 +1424,144 @@ impl BufferMemoryBarrier
 		self
 	}
 }
+
+#[cfg(all(test, feature = "Implements", feature = "CustomResolver"))]
+mod tests
+{
+	use super::*;
+	use QueryType;
+	use fakedrv::{default_test_device, assert_no_misuse};
+
+	/// Names of the regions open in the outermost and the nested region
+	#[cfg(any(feature = "VK_EXT_debug_utils", feature = "VK_EXT_debug_marker"))]
+	fn open_regions(driver: &::fakedrv::FakeDriver, device: &Device) -> (Vec<String>, Vec<String>)
+	{
+		let pool = CommandPool::new(device, 0, false, false).unwrap();
+		let cbs = pool.alloc(1, true).unwrap();
+		let handle = cbs[0].native_ptr() as usize as u64;
+		let mut r = cbs[0].begin().unwrap();
+		let mut outer = r.debug_region("frame", [1.0, 0.0, 0.0, 1.0]);
+		let nested = { let _inner = outer.debug_region("shadow\0ignored", [0.0; 4]); driver.open_labels(handle) };
+		(driver.open_labels(handle), nested)
+	}
+
+	#[test]
+	#[cfg(feature = "VK_EXT_debug_utils")]
+	fn regions_as_labels()
+	{
+		let (driver, instance, device) = ::fakedrv::test_device(|b| { b.add_extension("VK_EXT_debug_utils"); }, |_| ());
+		let (outer, nested) = open_regions(&driver, &device);
+		assert_eq!(nested, vec!["frame".to_owned(), "shadow".to_owned()]);
+		assert_eq!(outer, vec!["frame".to_owned()]);
+		assert_no_misuse(&driver, &instance);
+	}
+	#[test]
+	#[cfg(feature = "VK_EXT_debug_marker")]
+	fn regions_as_markers()
+	{
+		// chosen at runtime even if VK_EXT_debug_utils is also compiled in
+		let (driver, instance, device) = ::fakedrv::test_device(|b| { b.add_extension("VK_EXT_debug_report"); }, |b| { b.add_extension("VK_EXT_debug_marker"); });
+		let (outer, nested) = open_regions(&driver, &device);
+		assert_eq!(nested, vec!["frame".to_owned(), "shadow".to_owned()]);
+		assert_eq!(outer, vec!["frame".to_owned()]);
+		assert_no_misuse(&driver, &instance);
+	}
+	#[test]
+	#[cfg(any(feature = "VK_EXT_debug_utils", feature = "VK_EXT_debug_marker"))]
+	fn regions_without_extensions()
+	{
+		let (driver, instance, device) = default_test_device();
+		let (outer, nested) = open_regions(&driver, &device);
+		assert!(outer.is_empty() && nested.is_empty());
+		assert_no_misuse(&driver, &instance);
+	}
+	#[test]
+	fn timed_regions()
+	{
+		let (driver, instance, device) = default_test_device();
+		let pool = CommandPool::new(&device, 0, false, false).unwrap();
+		let cbs = pool.alloc(1, true).unwrap();
+		let qp = QueryPool::new(&device, QueryType::Timestamp, 5).unwrap();
+		let mut ts = RegionTimestamps::new(&qp, 0 .. 5);
+		{
+			let mut r = cbs[0].begin().unwrap();
+			ts.reset(&mut r);
+			{
+				let mut outer = r.debug_region_with("frame", [0.0; 4], &mut ts);
+				assert_eq!(outer.depth(), 1);
+				{ let inner = outer.debug_region("shadow", [0.0; 4]); assert_eq!(inner.depth(), 2); }
+				// the queries are exhausted
+				let _untimed = outer.debug_region("late", [0.0; 4]);
+			}
+			let _plain = r.debug_region("plain", [0.0; 4]);
+		}
+		let regions = ts.regions().iter().map(|r| (r.name.clone(), r.depth, r.query)).collect::<Vec<_>>();
+		assert_eq!(regions, vec![("frame".to_owned(), 1, 0), ("shadow".to_owned(), 2, 2)]);
+		assert_eq!(ts.query_range(), 0 .. 4);
+		assert_eq!(ts.regions()[1].elapsed_ns(&[10, 50, 20, 30], 0, 2.0), 20.0);
+		assert_no_misuse(&driver, &instance);
+	}
+	#[test]
+	fn missing_entry_point()
+	{
+		let (driver, _instance, device) = default_test_device();
+		let pool = CommandPool::new(&device, 0, false, false).unwrap();
+		assert!(pool.trim().is_ok());
+		driver.withhold("vkTrimCommandPool", true);
+		let r = pool.trim();
+		driver.withhold("vkTrimCommandPool", false);
+		assert!(matches!(r, Err(::Error::MissingEntryPoint("vkTrimCommandPool"))), "{:?}", r);
+	}
+	#[test]
+	fn freed_command_buffers()
+	{
+		let (driver, instance, device) = default_test_device();
+		let pool = CommandPool::new(&device, 0, false, false).unwrap();
+		let cbs = pool.alloc(2, true).unwrap();
+		pool.free(&cbs[..1]);
+		assert!(matches!(cbs[0].begin(), Err(::Error::UnknownCommandBuffer)));
+		assert!(cbs[1].begin().is_ok());
+		drop(pool);
+		assert!(matches!(cbs[1].begin(), Err(::Error::UnknownCommandBuffer)));
+		assert_no_misuse(&driver, &instance);
+	}
+	/// Recording from a pool excludes the other threads, but not the recording thread itself
+	#[test]
+	#[cfg(feature = "Multithreaded")]
+	fn pool_locking()
+	{
+		use std::sync::Arc;
+		use std::sync::atomic::{AtomicBool, Ordering};
+		use std::sync::mpsc;
+		use std::thread;
+		use std::time::Duration;
+
+		let (driver, instance, device) = default_test_device();
+		let pool = Arc::new(CommandPool::new(&device, 0, false, false).unwrap());
+		let primary = pool.alloc(1, true).unwrap()[0];
+		let secondary = pool.alloc(1, false).unwrap()[0];
+		let recording = Arc::new(AtomicBool::new(false));
+		let (started, wait_started) = mpsc::channel();
+
+		let other = { let (pool, recording) = (pool.clone(), recording.clone()); thread::spawn(move ||
+		{
+			wait_started.recv().unwrap();
+			// blocks until the main thread ends recording
+			pool.reset(false).unwrap();
+			assert!(!recording.load(Ordering::SeqCst), "the pool was reset while recording");
+			let cb = pool.alloc(1, true).unwrap()[0];
+			cb.begin().unwrap();
+		}) };
+		{
+			let _r = primary.begin().unwrap();
+			recording.store(true, Ordering::SeqCst);
+			started.send(()).unwrap();
+			// the same thread takes the pool lock again
+			drop(secondary.begin_inherit(None, None).unwrap());
+			thread::sleep(Duration::from_millis(100));
+			recording.store(false, Ordering::SeqCst);
+		}
+		other.join().unwrap();
+		assert_no_misuse(&driver, &instance);
+	}
+}
@@ -3,14 +3,20 @@
 use vk::*;
 use VkHandle;
 #[cfg(feature = "Implements")] use VkResultHandler;
+#[cfg(all(feature = "Implements", feature = "VK_EXT_debug_marker"))] use DeviceChild;
 use Instance;
-#[cfg(feature = "Implements")] use std::ptr::{null, null_mut};
+use libc::{c_char, c_void, size_t};
+use std::ffi::CStr;
+use std::fmt;
+use std::sync::{Arc, Mutex};
 
 /// Opaque object to a debug report callback object
-pub struct DebugReportCallback(VkDebugReportCallbackEXT, ::Instance, PFN_vkDestroyDebugReportCallbackEXT);
+pub struct DebugReportCallback(VkDebugReportCallbackEXT, ::Instance, PFN_vkDestroyDebugReportCallbackEXT,
+	/* the sink referred by pUserData, kept until the callback is destroyed */ #[allow(dead_code)] Option<Box<Arc<dyn DebugReportSink>>>);
+ThreadSafe!(Send + Sync: DebugReportCallback);
 
 #[cfg(feature = "Implements")]
-impl Drop for DebugReportCallback { fn drop(&mut self) { (self.2)(self.1.native_ptr(), self.native_ptr(), null()); } }
+impl Drop for DebugReportCallback { fn drop(&mut self) { (self.2)(self.1.native_ptr(), self.native_ptr(), self.1.allocation_callbacks()); } }
 
 impl VkHandle for DebugReportCallback { type Handle = VkDebugReportCallbackEXT; fn native_ptr(&self) -> VkDebugReportCallbackEXT { self.0 } }
 
@@ -20,14 +26,22 @@ pub struct DebugReportCallbackBuilder<'i>
 	instance: &'i Instance,
 	flags: VkDebugReportFlagsEXT,
 	#[cfg_attr(not(feature = "Implements"), allow(dead_code))]
-	callback: PFN_vkDebugReportCallbackEXT
+	callback: PFN_vkDebugReportCallbackEXT,
+	#[cfg_attr(not(feature = "Implements"), allow(dead_code))]
+	sink: Option<Arc<dyn DebugReportSink>>
 }
 impl<'i> DebugReportCallbackBuilder<'i>
 {
 	/// Create a builder object of DebugReportCallbackBuilder from `instance`, called back to `callback`
 	pub fn new(instance: &'i Instance, callback: PFN_vkDebugReportCallbackEXT) -> Self
 	{
-		DebugReportCallbackBuilder { instance, flags: 0, callback }
+		DebugReportCallbackBuilder { instance, flags: 0, callback, sink: None }
+	}
+	/// Create a builder object of DebugReportCallbackBuilder from `instance`, reporting decoded messages to `sink`
+	/// (a closure taking `&DebugReportMessage`, `DebugReportLogger` or `DebugReportCollector`)
+	pub fn with_sink<S: DebugReportSink + 'static>(instance: &'i Instance, sink: S) -> Self
+	{
+		DebugReportCallbackBuilder { instance, flags: 0, callback: report_to_sink, sink: Some(Arc::new(sink)) }
 	}
 	/// Reports an error that may cause undefined results, including an application crash
 	pub fn report_error(&mut self) -> &mut Self { self.flags |= VK_DEBUG_REPORT_ERROR_BIT_EXT; self }
@@ -47,7 +61,10 @@ impl<'i> DebugReportCallbackBuilder<'i>
 	///
 	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
 	#[cfg(feature = "Implements")]
-	pub fn create(&mut self) -> ::Result<DebugReportCallback> { DebugReportCallback::new(self.instance, self.flags, self.callback) }
+	pub fn create(&mut self) -> ::Result<DebugReportCallback>
+	{
+		DebugReportCallback::new(self.instance, self.flags, self.callback, self.sink.clone())
+	}
 }
 
 #[cfg(feature = "Implements")]
@@ -58,15 +75,25 @@ impl DebugReportCallback
 	/// On failure, this command returns
 	///
 	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
-	fn new(instance: &::Instance, flags: VkDebugReportFlagsEXT, callback: PFN_vkDebugReportCallbackEXT) -> ::Result<Self>
+	fn new(instance: &::Instance, flags: VkDebugReportFlagsEXT, callback: PFN_vkDebugReportCallbackEXT, sink: Option<Arc<dyn DebugReportSink>>)
+		-> ::Result<Self>
 	{
 		let ctor: PFN_vkCreateDebugReportCallbackEXT = instance.extra_procedure("vkCreateDebugReportCallbackEXT")
 			.expect("Requiring vkCreateDebugReportCallbackEXT function");
 		let dtor: PFN_vkDestroyDebugReportCallbackEXT = instance.extra_procedure("vkDestroyDebugReportCallbackEXT")
 			.expect("Requiring vkDestroyDebugReportCallbackEXT function");
-		let s = VkDebugReportCallbackCreateInfoEXT { flags, pfnCallback: callback, .. Default::default() };
+		// boxed once more to pass a thin pointer as pUserData
+		let sink = sink.map(Box::new);
+		let user_data = sink.as_ref().map_or(::std::ptr::null_mut(), |s| &**s as *const Arc<_> as *mut c_void);
+		// spelled out since `Default` would zero-initialize the function pointer
+		let s = VkDebugReportCallbackCreateInfoEXT
+		{
+			sType: VK_STRUCTURE_TYPE_DEBUG_REPORT_CALLBACK_CREATE_INFO_EXT, pNext: ::std::ptr::null(),
+			flags, pfnCallback: callback, pUserData: user_data
+		};
 		let mut h = VK_NULL_HANDLE as _;
-		ctor(instance.native_ptr(), &s, ::std::ptr::null(), &mut h).into_result().map(|_| DebugReportCallback(h, instance.clone(), dtor))
+		ctor(instance.native_ptr(), &s, instance.allocation_callbacks(), &mut h).into_result_in("vkCreateDebugReportCallbackEXT", "VkInstance")
+			.map(|_| DebugReportCallback(h, instance.clone(), dtor, sink))
 	}
 }
 #[cfg(feature = "Implements")]
@@ -85,6 +112,132 @@ impl Instance
 	}
 }
 
+/// A message reported to a `DebugReportSink`
+#[derive(Debug, Clone, Copy)]
+pub struct DebugReportMessage<'a>
+{
+	/// The `VK_DEBUG_REPORT_*_BIT_EXT` bit which triggered this message
+	pub flags: VkDebugReportFlagsEXT,
+	/// The type of the object being used or created at the time the event was triggered
+	pub object_type: DebugReportObjectType,
+	/// The object where the issue was detected, or `0` if there is no object associated with the event
+	pub object: u64,
+	/// A component(layer, driver, loader) defined value indicating the location of the trigger
+	pub location: size_t,
+	/// A layer-defined value indicating what test triggered this message
+	pub message_code: i32,
+	/// An abbreviation of the name of the component making the message
+	pub layer_prefix: &'a str,
+	/// The message detailing the trigger conditions
+	pub message: &'a str
+}
+impl<'a> DebugReportMessage<'a>
+{
+	/// An error that may cause undefined results
+	pub fn is_error(&self) -> bool { (self.flags & VK_DEBUG_REPORT_ERROR_BIT_EXT) != 0 }
+	/// An unexpected or potentially non-optimal use
+	pub fn is_warning(&self) -> bool { (self.flags & (VK_DEBUG_REPORT_WARNING_BIT_EXT | VK_DEBUG_REPORT_PERFORMANCE_WARNING_BIT_EXT)) != 0 }
+	/// Copy the message to an owned record
+	pub fn to_record(&self) -> DebugReportRecord
+	{
+		DebugReportRecord
+		{
+			flags: self.flags, object_type: self.object_type, object: self.object, location: self.location,
+			message_code: self.message_code, layer_prefix: self.layer_prefix.to_owned(), message: self.message.to_owned()
+		}
+	}
+}
+impl<'a> fmt::Display for DebugReportMessage<'a>
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result { write!(fmt, "[{}] {}", self.layer_prefix, self.message) }
+}
+/// An owned copy of `DebugReportMessage`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugReportRecord
+{
+	pub flags: VkDebugReportFlagsEXT, pub object_type: DebugReportObjectType, pub object: u64, pub location: size_t,
+	pub message_code: i32, pub layer_prefix: String, pub message: String
+}
+impl DebugReportRecord
+{
+	/// An error that may cause undefined results
+	pub fn is_error(&self) -> bool { (self.flags & VK_DEBUG_REPORT_ERROR_BIT_EXT) != 0 }
+	/// An unexpected or potentially non-optimal use
+	pub fn is_warning(&self) -> bool { (self.flags & (VK_DEBUG_REPORT_WARNING_BIT_EXT | VK_DEBUG_REPORT_PERFORMANCE_WARNING_BIT_EXT)) != 0 }
+}
+impl fmt::Display for DebugReportRecord
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result { write!(fmt, "[{}] {}", self.layer_prefix, self.message) }
+}
+
+/// Receiver of the messages reported through a `DebugReportCallback`. Called from any thread the driver reports on
+pub trait DebugReportSink: Send + Sync
+{
+	fn report(&self, message: &DebugReportMessage);
+}
+impl<F: Fn(&DebugReportMessage) + Send + Sync> DebugReportSink for F
+{
+	fn report(&self, message: &DebugReportMessage) { self(message) }
+}
+extern "system" fn report_to_sink(flags: VkDebugReportFlagsEXT, object_type: VkDebugReportObjectTypeEXT, object: u64, location: size_t,
+	message_code: i32, layer_prefix: *const c_char, message: *const c_char, user_data: *mut c_void) -> VkBool32
+{
+	unsafe fn text<'a>(p: *const c_char) -> ::std::borrow::Cow<'a, str>
+	{
+		if p.is_null() { "".into() } else { CStr::from_ptr(p).to_string_lossy() }
+	}
+	let sink = unsafe { &*(user_data as *const Arc<dyn DebugReportSink>) };
+	let (layer_prefix, message) = unsafe { (text(layer_prefix), text(message)) };
+	sink.report(&DebugReportMessage
+	{
+		flags, object_type: DebugReportObjectType::from_raw(object_type), object, location, message_code,
+		layer_prefix: &layer_prefix, message: &message
+	});
+	// the call which triggered the message should not be aborted
+	false as _
+}
+
+/// Forwards the messages to the `log` facade: errors as `Error`, (performance) warnings as `Warn`,
+/// informations as `Info` and debug informations as `Debug`
+#[cfg(feature = "log")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DebugReportLogger;
+#[cfg(feature = "log")]
+impl DebugReportSink for DebugReportLogger
+{
+	fn report(&self, message: &DebugReportMessage)
+	{
+		let level = if message.is_error() { ::log::Level::Error }
+			else if message.is_warning() { ::log::Level::Warn }
+			else if (message.flags & VK_DEBUG_REPORT_INFORMATION_BIT_EXT) != 0 { ::log::Level::Info }
+			else { ::log::Level::Debug };
+		::log::log!(level, "{}", message);
+	}
+}
+
+/// Stores the reported messages, e.g. to assert that no validation errors were reported in tests.
+/// Clones share the same storage
+#[derive(Clone, Default)]
+pub struct DebugReportCollector(Arc<Mutex<Vec<DebugReportRecord>>>);
+impl DebugReportCollector
+{
+	pub fn new() -> Self { Self::default() }
+	/// All messages reported so far
+	pub fn messages(&self) -> Vec<DebugReportRecord> { self.lock().clone() }
+	/// Take all messages reported so far
+	pub fn take(&self) -> Vec<DebugReportRecord> { self.lock().drain(..).collect() }
+	/// Error messages reported so far
+	pub fn errors(&self) -> Vec<DebugReportRecord> { self.lock().iter().filter(|m| m.is_error()).cloned().collect() }
+	/// Whether any error has been reported
+	pub fn has_errors(&self) -> bool { self.lock().iter().any(DebugReportRecord::is_error) }
+
+	fn lock(&self) -> ::std::sync::MutexGuard<'_, Vec<DebugReportRecord>> { self.0.lock().unwrap_or_else(|e| e.into_inner()) }
+}
+impl DebugReportSink for DebugReportCollector
+{
+	fn report(&self, message: &DebugReportMessage) { self.lock().push(message.to_record()); }
+}
+
 /// The type of an object passed to the `VkDebugMarkerObjectNameInfoEXT` and `VkDebugMarkerObjectTagInfoEXT` commands
 #[repr(C)] #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum DebugReportObjectType
@@ -146,5 +299,197 @@ pub enum DebugReportObjectType
 	/// A `VkSwapchainKHR`
 	Swapchain = VK_DEBUG_REPORT_OBJECT_TYPE_SWAPCHAIN_KHR_EXT as _,
 	/// A `VkDebugReportCallbackEXT`
-	DebugReport = VK_DEBUG_REPORT_OBJECT_TYPE_DEBUG_REPORT_CALLBACK_EXT_EXT as _
+	DebugReport = VK_DEBUG_REPORT_OBJECT_TYPE_DEBUG_REPORT_CALLBACK_EXT_EXT as _,
+	/// A `VkDescriptorUpdateTemplateKHR`
+	DescriptorUpdateTemplate = VK_DEBUG_REPORT_OBJECT_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_KHR_EXT as _
+}
+impl DebugReportObjectType
+{
+	/// Types not listed here are converted to `Unknown`
+	pub fn from_raw(t: VkDebugReportObjectTypeEXT) -> Self
+	{
+		use self::DebugReportObjectType as T;
+		const TYPES: &[DebugReportObjectType] = &[
+			T::Instance, T::PhysicalDevice, T::Device, T::Queue, T::Semaphore, T::CommandBuffer, T::Fence, T::DeviceMemory,
+			T::Buffer, T::Image, T::Event, T::QueryPool, T::BufferView, T::ImageView, T::ShaderModule, T::PipelineCache,
+			T::PipelineLayout, T::RenderPass, T::Pipeline, T::DescriptorSetLayout, T::Sampler, T::DescriptorPool, T::DescriptorSet, T::Framebuffer,
+			T::CommandPool, T::Surface, T::Swapchain, T::DebugReport, T::DescriptorUpdateTemplate
+		];
+		TYPES.iter().cloned().find(|&x| x as VkDebugReportObjectTypeEXT == t).unwrap_or(T::Unknown)
+	}
+}
+
+/// An object which can be named or tagged through the `VK_EXT_debug_marker` extension
+pub trait DebugMarkerObject: VkHandle
+{
+	/// The type of the object passed to the debug marker commands
+	const OBJECT_TYPE: DebugReportObjectType;
+	/// The underlying handle as a 64-bit value
+	fn object_handle(&self) -> u64;
+
+	/// [feature = "Implements", "VK_EXT_debug_marker"] Give a user-friendly name to this object
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	#[cfg(all(feature = "Implements", feature = "VK_EXT_debug_marker"))]
+	fn set_name(&self, name: &str) -> ::Result<()> where Self: DeviceChild + Sized { self.device().set_object_name(self, name) }
+	/// [feature = "Implements", "VK_EXT_debug_marker"] Attach arbitrary data to this object
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	#[cfg(all(feature = "Implements", feature = "VK_EXT_debug_marker"))]
+	fn set_tag(&self, tag_name: u64, tag: &[u8]) -> ::Result<()> where Self: DeviceChild + Sized
+	{
+		self.device().set_object_tag(self, tag_name, tag)
+	}
+}
+macro_rules! DebugMarkerObjects
+{
+	($($(#[$a: meta])* $t: ty = $k: ident),*) =>
+	{
+		$(
+			$(#[$a])* impl DebugMarkerObject for $t
+			{
+				const OBJECT_TYPE: DebugReportObjectType = DebugReportObjectType::$k;
+				fn object_handle(&self) -> u64 { self.native_ptr() as _ }
+			}
+		)*
+	}
+}
+DebugMarkerObjects!(
+	::Instance = Instance, ::PhysicalDevice = PhysicalDevice, ::Device = Device, ::Queue = Queue, ::CommandBuffer = CommandBuffer,
+	::Fence = Fence, ::Semaphore = Semaphore, ::Event = Event, ::QueryPool = QueryPool,
+	::DeviceMemory = DeviceMemory, ::Buffer = Buffer, ::BufferView = BufferView, ::Image = Image, ::ImageView = ImageView, ::Sampler = Sampler,
+	::ShaderModule = ShaderModule, ::PipelineCache = PipelineCache, ::PipelineLayout = PipelineLayout, ::Pipeline = Pipeline,
+	::RenderPass = RenderPass, ::Framebuffer = Framebuffer, ::CommandPool = CommandPool,
+	::DescriptorSetLayout = DescriptorSetLayout, ::DescriptorPool = DescriptorPool, ::DescriptorUpdateTemplate = DescriptorUpdateTemplate,
+	#[cfg(feature = "Presentation")] ::Surface = Surface, #[cfg(feature = "Presentation")] ::Swapchain = Swapchain,
+	DebugReportCallback = DebugReport
+);
+
+/// Following methods are enabled with [feature = "Implements", "VK_EXT_debug_marker"]
+#[cfg(all(feature = "Implements", feature = "VK_EXT_debug_marker"))]
+impl ::Device
+{
+	/// Give a user-friendly name to an object
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn set_object_name<T: DebugMarkerObject + ?Sized>(&self, object: &T, name: &str) -> ::Result<()>
+	{
+		self.set_object_name_raw(T::OBJECT_TYPE, object.object_handle(), name)
+	}
+	/// Give a user-friendly name to an object specified by a raw handle(e.g. `VkDescriptorSet`)
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn set_object_name_raw(&self, object_type: DebugReportObjectType, object: u64, name: &str) -> ::Result<()>
+	{
+		let name = ::std::ffi::CString::new(name).unwrap();
+		let info = VkDebugMarkerObjectNameInfoEXT { objectType: object_type as _, object, pObjectName: name.as_ptr(), .. Default::default() };
+		unsafe { self.dispatch().debug_marker_set_object_name_ext(self.native_ptr(), &info) }
+			.into_result_in("vkDebugMarkerSetObjectNameEXT", "VkDevice")
+	}
+	/// Attach arbitrary data to an object
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn set_object_tag<T: DebugMarkerObject + ?Sized>(&self, object: &T, tag_name: u64, tag: &[u8]) -> ::Result<()>
+	{
+		self.set_object_tag_raw(T::OBJECT_TYPE, object.object_handle(), tag_name, tag)
+	}
+	/// Attach arbitrary data to an object specified by a raw handle(e.g. `VkDescriptorSet`)
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn set_object_tag_raw(&self, object_type: DebugReportObjectType, object: u64, tag_name: u64, tag: &[u8]) -> ::Result<()>
+	{
+		let info = VkDebugMarkerObjectTagInfoEXT
+		{
+			objectType: object_type as _, object, tagName: tag_name, tagSize: tag.len() as _, pTag: tag.as_ptr() as _, .. Default::default()
+		};
+		unsafe { self.dispatch().debug_marker_set_object_tag_ext(self.native_ptr(), &info) }
+			.into_result_in("vkDebugMarkerSetObjectTagEXT", "VkDevice")
+	}
+}
+/// Name an object just created by a builder, when `VK_EXT_debug_marker` is enabled on its device
+#[cfg(all(feature = "Implements", feature = "VK_EXT_debug_marker"))]
+pub(crate) fn name_created<T: DebugMarkerObject + DeviceChild>(object: &T, name: Option<&str>) -> ::Result<()>
+{
+	match name
+	{
+		Some(name) if object.device().is_extension_enabled("VK_EXT_debug_marker") => object.set_name(name),
+		_ => Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	#[test]
+	fn object_types()
+	{
+		assert_eq!(DebugReportObjectType::from_raw(VK_DEBUG_REPORT_OBJECT_TYPE_BUFFER_EXT), DebugReportObjectType::Buffer);
+		assert_eq!(DebugReportObjectType::from_raw(VK_DEBUG_REPORT_OBJECT_TYPE_SWAPCHAIN_KHR_EXT), DebugReportObjectType::Swapchain);
+		assert_eq!(DebugReportObjectType::from_raw(0x7fff_0000), DebugReportObjectType::Unknown);
+	}
+
+	#[test]
+	#[cfg(all(feature = "Implements", feature = "CustomResolver"))]
+	fn sinks()
+	{
+		use InstanceBuilder;
+		use fakedrv::{FakeDriver, assert_no_misuse};
+		use std::sync::atomic::{AtomicUsize, Ordering};
+
+		let driver = FakeDriver::install();
+		let mut ib = InstanceBuilder::new("t", (0, 1, 0), "t", (0, 1, 0));
+		ib.add_extension("VK_EXT_debug_report");
+		let instance = ib.create().unwrap();
+		let collector = DebugReportCollector::new();
+		let warnings = Arc::new(AtomicUsize::new(0));
+		let counter = warnings.clone();
+		let _errors = DebugReportCallbackBuilder::with_sink(&instance, collector.clone()).report_error().report_warning().create().unwrap();
+		let warning_callback = DebugReportCallbackBuilder::with_sink(&instance, move |m: &DebugReportMessage|
+		{
+			assert!(m.is_warning() && !m.is_error());
+			counter.fetch_add(1, Ordering::SeqCst);
+		}).report_performance_warning().create().unwrap();
+
+		instance.debug_message(VK_DEBUG_REPORT_ERROR_BIT_EXT, DebugReportObjectType::Image, 42, 7, 3, "test", "invalid layout");
+		instance.debug_message(VK_DEBUG_REPORT_PERFORMANCE_WARNING_BIT_EXT, DebugReportObjectType::Unknown, 0, 0, 0, "test", "slow path");
+		// not requested by any callback
+		instance.debug_message(VK_DEBUG_REPORT_INFORMATION_BIT_EXT, DebugReportObjectType::Unknown, 0, 0, 0, "test", "info");
+		assert_eq!(warnings.load(Ordering::SeqCst), 1);
+		assert!(collector.has_errors());
+		assert_eq!(collector.errors(), vec![DebugReportRecord
+		{
+			flags: VK_DEBUG_REPORT_ERROR_BIT_EXT, object_type: DebugReportObjectType::Image, object: 42, location: 7,
+			message_code: 3, layer_prefix: "test".to_owned(), message: "invalid layout".to_owned()
+		}]);
+		assert_eq!(collector.errors()[0].to_string(), "[test] invalid layout");
+
+		// the destroyed callback is no longer called back, and the messages can be taken out
+		drop(warning_callback);
+		instance.debug_message(VK_DEBUG_REPORT_WARNING_BIT_EXT | VK_DEBUG_REPORT_PERFORMANCE_WARNING_BIT_EXT,
+			DebugReportObjectType::Unknown, 0, 0, 0, "test", "warning");
+		assert_eq!(warnings.load(Ordering::SeqCst), 1);
+		assert_eq!(collector.take().iter().map(|m| &m.message[..]).collect::<Vec<_>>(), vec!["invalid layout", "warning"]);
+		assert!(collector.messages().is_empty() && !collector.has_errors());
+		assert_no_misuse(&driver, &instance);
+	}
 }
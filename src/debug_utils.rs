@@ -0,0 +1,316 @@
+//! VK_EXT_debug_utils: Messengers, Object Names and Labels
+
+#![cfg_attr(not(feature = "Implements"), allow(dead_code))]
+
+use vk::*;
+use VkHandle;
+#[cfg(feature = "Implements")] use VkResultHandler;
+use Instance;
+use libc::{c_char, c_void};
+use std::borrow::Cow;
+use std::ffi::CStr;
+#[cfg(feature = "Implements")] use std::ffi::CString;
+use std::fmt;
+use std::sync::Arc;
+
+/// Opaque object to a debug messenger object
+pub struct DebugUtilsMessenger(VkDebugUtilsMessengerEXT, ::Instance, PFN_vkDestroyDebugUtilsMessengerEXT,
+	/* the sink referred by pUserData, kept until the messenger is destroyed */ #[allow(dead_code)] Box<Arc<dyn DebugUtilsSink>>);
+ThreadSafe!(Send + Sync: DebugUtilsMessenger);
+
+#[cfg(feature = "Implements")]
+impl Drop for DebugUtilsMessenger { fn drop(&mut self) { (self.2)(self.1.native_ptr(), self.native_ptr(), self.1.allocation_callbacks()); } }
+
+impl VkHandle for DebugUtilsMessenger { type Handle = VkDebugUtilsMessengerEXT; fn native_ptr(&self) -> VkDebugUtilsMessengerEXT { self.0 } }
+
+/// Builder object of `DebugUtilsMessenger`.
+/// Errors and warnings of all types are reported unless severities or types are specified
+pub struct DebugUtilsMessengerBuilder<'i>
+{
+	instance: &'i Instance,
+	severity: VkDebugUtilsMessageSeverityFlagsEXT, types: VkDebugUtilsMessageTypeFlagsEXT,
+	sink: Arc<dyn DebugUtilsSink>
+}
+impl<'i> DebugUtilsMessengerBuilder<'i>
+{
+	/// Create a builder object of DebugUtilsMessenger from `instance`, reporting decoded messages to `sink`
+	/// (e.g. a closure taking `&DebugUtilsMessage`)
+	pub fn new<S: DebugUtilsSink + 'static>(instance: &'i Instance, sink: S) -> Self
+	{
+		DebugUtilsMessengerBuilder { instance, severity: 0, types: 0, sink: Arc::new(sink) }
+	}
+	/// Reports errors that may cause undefined results, including an application crash
+	pub fn error(&mut self) -> &mut Self { self.severity |= VK_DEBUG_UTILS_MESSAGE_SEVERITY_ERROR_BIT_EXT; self }
+	/// Reports uses which are not necessarily an error but very likely a bug
+	pub fn warning(&mut self) -> &mut Self { self.severity |= VK_DEBUG_UTILS_MESSAGE_SEVERITY_WARNING_BIT_EXT; self }
+	/// Reports informational messages such as resource details
+	pub fn info(&mut self) -> &mut Self { self.severity |= VK_DEBUG_UTILS_MESSAGE_SEVERITY_INFO_BIT_EXT; self }
+	/// Reports diagnostic messages from the loader, layers and drivers
+	pub fn verbose(&mut self) -> &mut Self { self.severity |= VK_DEBUG_UTILS_MESSAGE_SEVERITY_VERBOSE_BIT_EXT; self }
+	/// Reports events unrelated to the specification or performance
+	pub fn general(&mut self) -> &mut Self { self.types |= VK_DEBUG_UTILS_MESSAGE_TYPE_GENERAL_BIT_EXT; self }
+	/// Reports violations of the specification or possible mistakes
+	pub fn validation(&mut self) -> &mut Self { self.types |= VK_DEBUG_UTILS_MESSAGE_TYPE_VALIDATION_BIT_EXT; self }
+	/// Reports potentially non-optimal uses of Vulkan
+	pub fn performance(&mut self) -> &mut Self { self.types |= VK_DEBUG_UTILS_MESSAGE_TYPE_PERFORMANCE_BIT_EXT; self }
+
+	/// Register a debug messenger
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	#[cfg(feature = "Implements")]
+	pub fn create(&mut self) -> ::Result<DebugUtilsMessenger>
+	{
+		let severity = if self.severity == 0
+		{
+			VK_DEBUG_UTILS_MESSAGE_SEVERITY_ERROR_BIT_EXT | VK_DEBUG_UTILS_MESSAGE_SEVERITY_WARNING_BIT_EXT
+		}
+		else { self.severity };
+		let types = if self.types == 0
+		{
+			VK_DEBUG_UTILS_MESSAGE_TYPE_GENERAL_BIT_EXT | VK_DEBUG_UTILS_MESSAGE_TYPE_VALIDATION_BIT_EXT | VK_DEBUG_UTILS_MESSAGE_TYPE_PERFORMANCE_BIT_EXT
+		}
+		else { self.types };
+		DebugUtilsMessenger::new(self.instance, severity, types, self.sink.clone())
+	}
+}
+
+#[cfg(feature = "Implements")]
+impl DebugUtilsMessenger
+{
+	fn new(instance: &::Instance, severity: VkDebugUtilsMessageSeverityFlagsEXT, types: VkDebugUtilsMessageTypeFlagsEXT,
+		sink: Arc<dyn DebugUtilsSink>) -> ::Result<Self>
+	{
+		let ctor: PFN_vkCreateDebugUtilsMessengerEXT = instance.extra_procedure("vkCreateDebugUtilsMessengerEXT")
+			.expect("Requiring vkCreateDebugUtilsMessengerEXT function");
+		let dtor: PFN_vkDestroyDebugUtilsMessengerEXT = instance.extra_procedure("vkDestroyDebugUtilsMessengerEXT")
+			.expect("Requiring vkDestroyDebugUtilsMessengerEXT function");
+		// boxed once more to pass a thin pointer as pUserData
+		let sink = Box::new(sink);
+		let s = VkDebugUtilsMessengerCreateInfoEXT
+		{
+			sType: VK_STRUCTURE_TYPE_DEBUG_UTILS_MESSENGER_CREATE_INFO_EXT, pNext: ::std::ptr::null(), flags: 0,
+			messageSeverity: severity, messageType: types,
+			pfnUserCallback: message_to_sink, pUserData: &*sink as *const Arc<_> as *mut c_void
+		};
+		let mut h = VK_NULL_HANDLE as _;
+		ctor(instance.native_ptr(), &s, instance.allocation_callbacks(), &mut h).into_result_in("vkCreateDebugUtilsMessengerEXT", "VkInstance")
+			.map(|_| DebugUtilsMessenger(h, instance.clone(), dtor, sink))
+	}
+}
+#[cfg(feature = "Implements")]
+impl Instance
+{
+	/// Inject its own message into the debug utils message stream
+	pub fn submit_debug_utils_message(&self, severity: VkDebugUtilsMessageSeverityFlagsEXT, types: VkDebugUtilsMessageTypeFlagsEXT,
+		message_id_name: &str, message_id_number: i32, message: &str)
+	{
+		let (id_name, msg) = (CString::new(message_id_name).unwrap(), CString::new(message).unwrap());
+		let data = VkDebugUtilsMessengerCallbackDataEXT
+		{
+			pMessageIdName: id_name.as_ptr(), messageIdNumber: message_id_number, pMessage: msg.as_ptr(), .. Default::default()
+		};
+		let submit: PFN_vkSubmitDebugUtilsMessageEXT = self.extra_procedure("vkSubmitDebugUtilsMessageEXT")
+			.expect("Requiring vkSubmitDebugUtilsMessageEXT function");
+		submit(self.native_ptr(), severity, types, &data);
+	}
+}
+
+/// A label active on the queue or the command buffer when a message is reported
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DebugUtilsLabel<'a>
+{
+	pub name: &'a str,
+	/// RGBA color of the label, all zeros if unspecified
+	pub color: [f32; 4]
+}
+/// An object related to a reported message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DebugUtilsObjectInfo<'a>
+{
+	pub object_type: VkObjectType,
+	pub handle: u64,
+	/// The name given by the application, if any
+	pub name: Option<&'a str>
+}
+/// A message reported to a `DebugUtilsSink`
+#[derive(Debug, Clone, Copy)]
+pub struct DebugUtilsMessage<'a>
+{
+	/// The `VK_DEBUG_UTILS_MESSAGE_SEVERITY_*_BIT_EXT` bit which triggered this message
+	pub severity: VkDebugUtilsMessageSeverityFlagsEXT,
+	/// The `VK_DEBUG_UTILS_MESSAGE_TYPE_*_BIT_EXT` bits which triggered this message
+	pub types: VkDebugUtilsMessageTypeFlagsEXT,
+	/// The name of the check which triggered this message, or an empty string
+	pub message_id_name: &'a str,
+	/// The ID number of the check which triggered this message
+	pub message_id_number: i32,
+	/// The message detailing the trigger conditions
+	pub message: &'a str,
+	/// Labels active on the current queue
+	pub queue_labels: &'a [DebugUtilsLabel<'a>],
+	/// Labels active on the current command buffer
+	pub cmd_buf_labels: &'a [DebugUtilsLabel<'a>],
+	/// Objects related to this message
+	pub objects: &'a [DebugUtilsObjectInfo<'a>]
+}
+impl<'a> DebugUtilsMessage<'a>
+{
+	/// An error that may cause undefined results
+	pub fn is_error(&self) -> bool { (self.severity & VK_DEBUG_UTILS_MESSAGE_SEVERITY_ERROR_BIT_EXT) != 0 }
+	/// A use which is very likely a bug
+	pub fn is_warning(&self) -> bool { (self.severity & VK_DEBUG_UTILS_MESSAGE_SEVERITY_WARNING_BIT_EXT) != 0 }
+}
+impl<'a> fmt::Display for DebugUtilsMessage<'a>
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result { write!(fmt, "[{}] {}", self.message_id_name, self.message) }
+}
+
+/// Receiver of the messages reported through a `DebugUtilsMessenger`. Called from any thread the driver reports on
+pub trait DebugUtilsSink: Send + Sync
+{
+	fn report(&self, message: &DebugUtilsMessage);
+}
+impl<F: Fn(&DebugUtilsMessage) + Send + Sync> DebugUtilsSink for F
+{
+	fn report(&self, message: &DebugUtilsMessage) { self(message) }
+}
+extern "system" fn message_to_sink(severity: VkDebugUtilsMessageSeverityFlagsEXT, types: VkDebugUtilsMessageTypeFlagsEXT,
+	data: *const VkDebugUtilsMessengerCallbackDataEXT, user_data: *mut c_void) -> VkBool32
+{
+	unsafe fn text<'a>(p: *const c_char) -> Cow<'a, str>
+	{
+		if p.is_null() { "".into() } else { CStr::from_ptr(p).to_string_lossy() }
+	}
+	unsafe fn slice<'a, T>(p: *const T, count: u32) -> &'a [T]
+	{
+		if p.is_null() || count == 0 { &[] } else { ::std::slice::from_raw_parts(p, count as _) }
+	}
+	let sink = unsafe { &*(user_data as *const Arc<dyn DebugUtilsSink>) };
+	let data = unsafe { &*data };
+	// decoded first, then borrowed by the message
+	let (id_name, message) = unsafe { (text(data.pMessageIdName), text(data.pMessage)) };
+	let decode_labels = |p, count| unsafe { slice(p, count) }.iter()
+		.map(|l: &VkDebugUtilsLabelEXT| (unsafe { text(l.pLabelName) }, l.color)).collect::<Vec<_>>();
+	let (queue_labels, cmd_buf_labels) = (decode_labels(data.pQueueLabels, data.queueLabelCount), decode_labels(data.pCmdBufLabels, data.cmdBufLabelCount));
+	let objects = unsafe { slice(data.pObjects, data.objectCount) }.iter()
+		.map(|o| (o.objectType, o.objectHandle, if o.pObjectName.is_null() { None } else { Some(unsafe { text(o.pObjectName) }) }))
+		.collect::<Vec<_>>();
+	fn borrow_labels<'a>(v: &'a [(Cow<str>, [f32; 4])]) -> Vec<DebugUtilsLabel<'a>>
+	{
+		v.iter().map(|&(ref n, c)| DebugUtilsLabel { name: &n[..], color: c }).collect()
+	}
+	let (queue_labels, cmd_buf_labels) = (borrow_labels(&queue_labels), borrow_labels(&cmd_buf_labels));
+	let objects = objects.iter().map(|&(object_type, handle, ref name)| DebugUtilsObjectInfo
+	{
+		object_type, handle, name: name.as_ref().map(|n| &n[..])
+	}).collect::<Vec<_>>();
+	sink.report(&DebugUtilsMessage
+	{
+		severity, types, message_id_name: &id_name, message_id_number: data.messageIdNumber, message: &message,
+		queue_labels: &queue_labels, cmd_buf_labels: &cmd_buf_labels, objects: &objects
+	});
+	// the call which triggered the message should not be aborted
+	false as _
+}
+
+/// Call `f` with a label structure pointing `name`
+#[cfg(feature = "Implements")]
+pub(crate) fn with_label<R, F: FnOnce(&VkDebugUtilsLabelEXT) -> R>(name: &str, color: [f32; 4], f: F) -> R
+{
+	let name = CString::new(name).unwrap();
+	f(&VkDebugUtilsLabelEXT { pLabelName: name.as_ptr(), color, .. Default::default() })
+}
+
+/// An object which can be named or tagged through the `VK_EXT_debug_utils` extension
+pub trait DebugUtilsObject: VkHandle
+{
+	/// The type of the object passed to the debug utils commands
+	const OBJECT_TYPE: VkObjectType;
+	/// The underlying handle as a 64-bit value
+	fn raw_object_handle(&self) -> u64;
+}
+macro_rules! DebugUtilsObjects
+{
+	($($(#[$a: meta])* $t: ty = $k: ident),*) =>
+	{
+		$(
+			$(#[$a])* impl DebugUtilsObject for $t
+			{
+				const OBJECT_TYPE: VkObjectType = $k;
+				fn raw_object_handle(&self) -> u64 { self.native_ptr() as _ }
+			}
+		)*
+	}
+}
+DebugUtilsObjects!(
+	::Instance = VK_OBJECT_TYPE_INSTANCE, ::PhysicalDevice = VK_OBJECT_TYPE_PHYSICAL_DEVICE, ::Device = VK_OBJECT_TYPE_DEVICE,
+	::Queue = VK_OBJECT_TYPE_QUEUE, ::CommandBuffer = VK_OBJECT_TYPE_COMMAND_BUFFER,
+	::Fence = VK_OBJECT_TYPE_FENCE, ::Semaphore = VK_OBJECT_TYPE_SEMAPHORE, ::Event = VK_OBJECT_TYPE_EVENT, ::QueryPool = VK_OBJECT_TYPE_QUERY_POOL,
+	::DeviceMemory = VK_OBJECT_TYPE_DEVICE_MEMORY, ::Buffer = VK_OBJECT_TYPE_BUFFER, ::BufferView = VK_OBJECT_TYPE_BUFFER_VIEW,
+	::Image = VK_OBJECT_TYPE_IMAGE, ::ImageView = VK_OBJECT_TYPE_IMAGE_VIEW, ::Sampler = VK_OBJECT_TYPE_SAMPLER,
+	::ShaderModule = VK_OBJECT_TYPE_SHADER_MODULE, ::PipelineCache = VK_OBJECT_TYPE_PIPELINE_CACHE,
+	::PipelineLayout = VK_OBJECT_TYPE_PIPELINE_LAYOUT, ::Pipeline = VK_OBJECT_TYPE_PIPELINE,
+	::RenderPass = VK_OBJECT_TYPE_RENDER_PASS, ::Framebuffer = VK_OBJECT_TYPE_FRAMEBUFFER, ::CommandPool = VK_OBJECT_TYPE_COMMAND_POOL,
+	::DescriptorSetLayout = VK_OBJECT_TYPE_DESCIPTOR_SET_LAYOUT, ::DescriptorPool = VK_OBJECT_TYPE_DESCRIPTOR_POOL,
+	::DescriptorUpdateTemplate = VK_OBJECT_TYPE_DESCRIPTOR_UPDATE_TEMPLATE,
+	#[cfg(feature = "Presentation")] ::Surface = VK_OBJECT_TYPE_SURFACE_KHR,
+	#[cfg(feature = "Presentation")] ::Swapchain = VK_OBJECT_TYPE_SWAPCHAIN_KHR,
+	DebugUtilsMessenger = VK_OBJECT_TYPE_DEBUG_UTILS_MESSENGER_EXT
+);
+
+/// Following methods are enabled with [feature = "Implements", "VK_EXT_debug_utils"]
+#[cfg(feature = "Implements")]
+impl ::Device
+{
+	/// Give a user-friendly name to an object
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn set_debug_utils_object_name<T: DebugUtilsObject + ?Sized>(&self, object: &T, name: &str) -> ::Result<()>
+	{
+		self.set_debug_utils_object_name_raw(T::OBJECT_TYPE, object.raw_object_handle(), name)
+	}
+	/// Give a user-friendly name to an object specified by a raw handle(e.g. `VkDescriptorSet`)
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn set_debug_utils_object_name_raw(&self, object_type: VkObjectType, handle: u64, name: &str) -> ::Result<()>
+	{
+		let name = CString::new(name).unwrap();
+		let info = VkDebugUtilsObjectNameInfoEXT { objectType: object_type, objectHandle: handle, pObjectName: name.as_ptr(), .. Default::default() };
+		unsafe { self.dispatch().set_debug_utils_object_name_ext(self.native_ptr(), &info) }
+			.into_result_in("vkSetDebugUtilsObjectNameEXT", "VkDevice")
+	}
+	/// Attach arbitrary data to an object
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn set_debug_utils_object_tag<T: DebugUtilsObject + ?Sized>(&self, object: &T, tag_name: u64, tag: &[u8]) -> ::Result<()>
+	{
+		self.set_debug_utils_object_tag_raw(T::OBJECT_TYPE, object.raw_object_handle(), tag_name, tag)
+	}
+	/// Attach arbitrary data to an object specified by a raw handle(e.g. `VkDescriptorSet`)
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn set_debug_utils_object_tag_raw(&self, object_type: VkObjectType, handle: u64, tag_name: u64, tag: &[u8]) -> ::Result<()>
+	{
+		let info = VkDebugUtilsObjectTagInfoEXT
+		{
+			objectType: object_type, objectHandle: handle, tagName: tag_name, tagSize: tag.len() as _, pTag: tag.as_ptr() as _,
+			.. Default::default()
+		};
+		unsafe { self.dispatch().set_debug_utils_object_tag_ext(self.native_ptr(), &info) }
+			.into_result_in("vkSetDebugUtilsObjectTagEXT", "VkDevice")
+	}
+}
@@ -6,12 +6,13 @@ use {VkHandle, DeviceChild};
 use ShaderStage;
 #[cfg(feature = "Implements")] use std::ptr::null;
 #[cfg(feature = "Implements")] use std::mem::zeroed;
-#[cfg(feature = "Implements")] use ::vkresolve::{Resolver, ResolverInterface};
+use extsync::ExternalSync;
 
 /// Opaque handle to a descriptor set layout object
 pub struct DescriptorSetLayout(VkDescriptorSetLayout, ::Device);
 /// Opaque handle to a descriptor pool object
-pub struct DescriptorPool(VkDescriptorPool, ::Device);
+pub struct DescriptorPool(VkDescriptorPool, ::Device, ExternalSync);
+ThreadSafe!(Send + Sync: DescriptorSetLayout, DescriptorPool);
 
 #[cfg(feature = "Implements")] DeviceChildCommonDrop!{ for DescriptorSetLayout[destroy_descriptor_set_layout], DescriptorPool[destroy_descriptor_pool] }
 
@@ -115,8 +116,8 @@ impl DescriptorSetLayout
         {
             bindingCount: n_bindings.len() as _, pBindings: n_bindings.as_ptr(), .. Default::default()
         };
-        unsafe { Resolver::get().create_descriptor_set_layout(device.native_ptr(), &cinfo, null(), &mut h) }
-            .into_result().map(|_| DescriptorSetLayout(h, device.clone()))
+        unsafe { device.dispatch().create_descriptor_set_layout(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h) }
+            .into_result_in("vkCreateDescriptorSetLayout", "VkDevice").map(|_| DescriptorSetLayout(h, device.clone()))
     }
 }
 
@@ -178,8 +179,8 @@ impl DescriptorPool
             maxSets: max_sets, flags: if allow_free { VK_DESCRIPTOR_POOL_CREATE_FREE_DESCRIPTOR_SET_BIT } else { 0 },
             poolSizeCount: pool_sizes.len() as _, pPoolSizes: pool_sizes.as_ptr() as *const _, .. Default::default()
         };
-        unsafe { Resolver::get().create_descriptor_pool(device.native_ptr(), &cinfo, null(), &mut h) }
-            .into_result().map(|_| DescriptorPool(h, device.clone()))
+        unsafe { device.dispatch().create_descriptor_pool(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h) }
+            .into_result_in("vkCreateDescriptorPool", "VkDevice").map(|_| DescriptorPool(h, device.clone(), ExternalSync::default()))
     }
     /// Allocate one or more descriptor sets
     /// # Failures
@@ -196,8 +197,9 @@ impl DescriptorPool
             .. Default::default()
         };
         let mut hs = vec![VK_NULL_HANDLE as _; layout_ptrs.len()];
-        unsafe { Resolver::get().allocate_descriptor_sets(self.1.native_ptr(), &ainfo, hs.as_mut_ptr()) }
-            .into_result().map(|_| hs)
+        let _g = self.2.lock();
+        unsafe { self.1.dispatch().allocate_descriptor_sets(self.1.native_ptr(), &ainfo, hs.as_mut_ptr()) }
+            .into_result_in("vkAllocateDescriptorSets", "VkDevice").map(|_| hs)
     }
     /// Resets a descriptor pool object
     /// # Safety
@@ -208,7 +210,8 @@ impl DescriptorPool
     /// - VK_ERROR_OUT_OF_DEVICE_MEMORY
     pub unsafe fn reset(&self) -> ::Result<()>
     {
-        Resolver::get().reset_descriptor_pool(self.1.native_ptr(), self.0, 0).into_result()
+        let _g = self.2.lock();
+        self.1.dispatch().reset_descriptor_pool(self.1.native_ptr(), self.0, 0).into_result_in("vkResetDescriptorPool", "VkDevice")
     }
     /// Free one or more descriptor sets
     /// # Failures
@@ -217,7 +220,8 @@ impl DescriptorPool
     /// - VK_ERROR_OUT_OF_DEVICE_MEMORY
     pub fn free(&self, sets: &[VkDescriptorSet]) -> ::Result<()>
     {
-        unsafe { Resolver::get().free_descriptor_sets(self.1.native_ptr(), self.0, sets.len() as _, sets.as_ptr()) }.into_result()
+        let _g = self.2.lock();
+        unsafe { self.1.dispatch().free_descriptor_sets(self.1.native_ptr(), self.0, sets.len() as _, sets.as_ptr()) }.into_result_in("vkFreeDescriptorSets", "VkDevice")
     }
 }
 
@@ -298,13 +302,14 @@ macro_rules! DescriptorUpdateTemplateEntries
 }
 
 pub struct DescriptorUpdateTemplate(VkDescriptorUpdateTemplate, ::Device);
+ThreadSafe!(Send + Sync: DescriptorUpdateTemplate);
 #[cfg(feature = "Implements")] impl Drop for DescriptorUpdateTemplate
 {
     fn drop(&mut self)
     {
         unsafe
         {
-            self.1.instance().destroy_descriptor_update_template(self.1.native_ptr(), self.0, null());
+            self.1.instance().destroy_descriptor_update_template(self.1.native_ptr(), self.0, self.1.allocation_callbacks());
         }
     }
 }
@@ -324,15 +329,17 @@ impl DescriptorUpdateTemplate
         let mut handle = unsafe { zeroed() };
         unsafe
         {
-            device.instance().create_descriptor_update_template(device.native_ptr(), &cinfo, null(), &mut handle)
-                .into_result().map(|_| DescriptorUpdateTemplate(handle, device.clone()))
+            device.instance().create_descriptor_update_template(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut handle)
+                .into_result_in("vkCreateDescriptorUpdateTemplate", "VkDevice").map(|_| DescriptorUpdateTemplate(handle, device.clone()))
         }
     }
-    pub fn update_set<T>(&self, set: VkDescriptorSet, data: &T)
+    /// # Failures
+    /// Returns `Error::MissingEntryPoint` if the driver does not provide `vkUpdateDescriptorSetWithTemplate`
+    pub fn update_set<T>(&self, set: VkDescriptorSet, data: &T) -> ::Result<()>
     {
         unsafe
         {
-            Resolver::get().update_descriptor_set_with_template(self.device().native_ptr(), set, self.native_ptr(),
+            self.device().dispatch().update_descriptor_set_with_template(self.device().native_ptr(), set, self.native_ptr(),
                 data as *const T as *const _)
         }
     }
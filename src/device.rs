@@ -7,10 +7,16 @@ use PhysicalDevice;
 use std::ffi::CString;
 use std::borrow::Cow;
 use crate::VkHandle;
-#[cfg(    feature = "Multithreaded") ] use std::sync::Arc as RefCounter;
-#[cfg(not(feature = "Multithreaded"))] use std::rc::Rc as RefCounter;
+use hostalloc::HostAllocator;
+use extensions::{Requests, Requirement};
+use features::{Feature, FeatureSet};
+use budget::MemoryTracker;
+#[cfg(feature = "Implements")] use extensions::{ExtensionScope, extension_name, layer_name};
+use RefCounter;
+use extsync::ExternalSync;
+use std::sync::{Arc, Mutex};
 #[cfg(feature = "Implements")] use VkResultHandler;
-#[cfg(feature = "Implements")] use ::vkresolve::{Resolver, ResolverInterface};
+#[cfg(feature = "Implements")] use ::vkresolve::{DispatchRef, device_dispatch};
 #[cfg(feature = "Implements")] use crate::fnconv::FnTransmute;
 
 /// Set of bit of queue flags
@@ -66,19 +72,116 @@ impl QueueFamilies
 	pub fn minimum_image_transfer_granularity(&self, family_index: u32) -> &VkExtent3D { &self.0[family_index as usize].minImageTransferGranularity }
 }
 
-struct DeviceCell(VkDevice, ::Instance);
+/// Locks of the queues are shared by every `Queue` retrieved for the same family and index
+type QueueLocks = Mutex<Vec<((u32, u32), Arc<ExternalSync>)>>;
+/// Layers, extensions and features enabled on the device
+struct DeviceCell(VkDevice, ::Instance, Option<HostAllocator>, QueueLocks, (Vec<String>, Vec<String>), FeatureSet,
+	Option<MemoryTracker>, HostAccess, #[cfg(feature = "Implements")] DispatchRef);
+/// Properties of the physical device consulted when the host accesses a device memory
+pub(crate) struct HostAccess { pub memory_type_flags: Vec<VkMemoryPropertyFlags>, pub non_coherent_atom_size: VkDeviceSize }
+impl HostAccess
+{
+	/// First memory type allowed by `type_bits` having all of the flags of a candidate. Candidates are tried in order
+	pub fn find_type(&self, type_bits: u32, candidates: &[VkMemoryPropertyFlags]) -> Option<u32>
+	{
+		candidates.iter().filter_map(|&flags| (0 .. self.memory_type_flags.len())
+			.find(|&n| (type_bits & (1 << n)) != 0 && (self.memory_type_flags[n] & flags) == flags)).next().map(|n| n as _)
+	}
+}
 /// Opaque handle to a device object
 #[derive(Clone)]
 pub struct Device(RefCounter<DeviceCell>);
-#[cfg(feature = "Multithreaded")] unsafe impl Sync for Device {}
-/// Opaque handle to a queue object
+/// Opaque handle to a queue object. Submissions through the clones of a queue are serialized with `Multithreaded`
 #[derive(Clone)]
-pub struct Queue(VkQueue, Device);
+pub struct Queue(VkQueue, Device, Arc<ExternalSync>);
+// vkDestroyDevice runs on the last drop, vkDeviceWaitIdle locks every queue, and the mutable parts are behind mutexes
+ThreadSafe!(Send + Sync: DeviceCell);
+// the clones of a queue share the lock taken by every command on it
+ThreadSafe!(Send + Sync: Queue);
 /// Family Index, Queue Priorities
 pub struct DeviceQueueCreateInfo(pub u32, pub Vec<f32>);
 
+/// Purpose of a queue planned by `DeviceBuilder::plan_queue`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum QueueRole
+{
+	/// Graphics(and compute) operations
+	Graphics,
+	/// Presentation to the surface given by `DeviceBuilder::present_to`. Shares the graphics queue if possible
+	Present,
+	/// Compute operations, preferably on a family without graphics support
+	AsyncCompute,
+	/// Transfer operations, preferably on a family without graphics and compute support
+	Transfer,
+	/// Sparse memory binding operations
+	SparseBinding
+}
+impl QueueRole
+{
+	/// Required flags and the flags to be avoided, in the order of preference
+	fn candidates(self) -> &'static [(VkQueueFlags, VkQueueFlags)]
+	{
+		const G: VkQueueFlags = VK_QUEUE_GRAPHICS_BIT;
+		const C: VkQueueFlags = VK_QUEUE_COMPUTE_BIT;
+		const T: VkQueueFlags = VK_QUEUE_TRANSFER_BIT;
+		const S: VkQueueFlags = VK_QUEUE_SPARSE_BINDING_BIT;
+		match self
+		{
+			QueueRole::Graphics => &[(G, 0)],
+			// the support of presentation is checked separately
+			QueueRole::Present => &[(0, 0)],
+			QueueRole::AsyncCompute => &[(C, G), (C, 0)],
+			QueueRole::Transfer => &[(T, G | C), (T, G), (T, 0)],
+			QueueRole::SparseBinding => &[(S, G | C), (S, 0)]
+		}
+	}
+}
+/// Family index and queue index for each role
+type QueuePlan = Vec<(QueueRole, u32, u32)>;
+/// Queues created by `DeviceBuilder::create_with_queues`.
+/// Roles which could not be satisfied(`Requirement::Optional`) or were not planned are `None`
+#[derive(Clone)]
+pub struct Queues
+{
+	pub graphics: Option<Queue>, pub present: Option<Queue>, pub compute: Option<Queue>,
+	pub transfer: Option<Queue>, pub sparse_binding: Option<Queue>,
+	families: Vec<(QueueRole, u32)>
+}
+impl Queues
+{
+	pub fn get(&self, role: QueueRole) -> Option<&Queue>
+	{
+		match role
+		{
+			QueueRole::Graphics => self.graphics.as_ref(), QueueRole::Present => self.present.as_ref(),
+			QueueRole::AsyncCompute => self.compute.as_ref(), QueueRole::Transfer => self.transfer.as_ref(),
+			QueueRole::SparseBinding => self.sparse_binding.as_ref()
+		}
+	}
+	/// Queue family index of the queue for `role`
+	pub fn family_index(&self, role: QueueRole) -> Option<u32>
+	{
+		self.families.iter().find(|r| r.0 == role).map(|r| r.1)
+	}
+	/// Distinct family indices of the queues for `roles`, to be passed to `sharing_queue_families` of the resources
+	/// used by those queues. Empty(exclusive) if they are in a single family
+	pub fn sharing_families(&self, roles: &[QueueRole]) -> Vec<u32>
+	{
+		let mut v = roles.iter().filter_map(|&r| self.family_index(r)).collect::<Vec<_>>();
+		v.sort(); v.dedup();
+		if v.len() < 2 { v.clear(); }
+		v
+	}
+}
+
 #[cfg(feature = "Implements")]
-impl Drop for DeviceCell { fn drop(&mut self) { unsafe { Resolver::get().destroy_device(self.0, ::std::ptr::null()) }; } }
+impl Drop for DeviceCell
+{
+	fn drop(&mut self)
+	{
+		unsafe { self.8.destroy_device(self.0, HostAllocator::callbacks(self.2.as_ref())) };
+	}
+}
 
 impl VkHandle for Device { type Handle = VkDevice; fn native_ptr(&self) -> VkDevice { self.0 .0 } }
 impl VkHandle for Queue  { type Handle = VkQueue;  fn native_ptr(&self) -> VkQueue  { self.0 } }
@@ -88,13 +191,20 @@ impl ::DeviceChild for Queue { fn device(&self) -> &Device { &self.1 } }
 pub struct DeviceBuilder<'p>
 {
 	pdev_ref: &'p PhysicalDevice, queue_infos: Vec<DeviceQueueCreateInfo>,
-	layers: Vec<CString>, extensions: Vec<CString>, features: VkPhysicalDeviceFeatures
+	layers: Vec<CString>, extensions: Vec<CString>, features: VkPhysicalDeviceFeatures, group: Vec<VkPhysicalDevice>,
+	allocator: Option<HostAllocator>, requests: Requests, feature_requests: Vec<(Feature, Requirement)>, track_memory: bool,
+	queue_roles: Vec<(QueueRole, f32, Requirement)>, #[cfg(feature = "Presentation")] present_surface: Option<&'p ::Surface>
 }
 impl<'p> DeviceBuilder<'p>
 {
 	pub fn new(pdev: &'p PhysicalDevice) -> Self
 	{
-		DeviceBuilder { pdev_ref: pdev, queue_infos: Vec::new(), layers: Vec::new(), extensions: Vec::new(), features: VkPhysicalDeviceFeatures::default() }
+		DeviceBuilder
+		{
+			pdev_ref: pdev, queue_infos: Vec::new(), layers: Vec::new(), extensions: Vec::new(), features: VkPhysicalDeviceFeatures::default(),
+			group: Vec::new(), allocator: None, requests: Requests::default(), feature_requests: Vec::new(), track_memory: false,
+			queue_roles: Vec::new(), #[cfg(feature = "Presentation")] present_surface: None
+		}
 	}
 	pub fn add_layer(&mut self, name: &str) -> &mut Self { self.layers.push(CString::new(name).unwrap()); self }
 	pub fn add_extension(&mut self, name: &str) -> &mut Self
@@ -114,12 +224,70 @@ impl<'p> DeviceBuilder<'p>
 	{
 		for e in extensions { self.add_extension(e); } self
 	}
+	/// Request an extension checked against `PhysicalDevice::enumerate_extension_properties` at `create`.
+	/// The device extensions it depends on are requested together, and the instance extensions it depends on
+	/// must be enabled on the parent instance
+	pub fn request_extension(&mut self, name: &str, requirement: Requirement) -> &mut Self
+	{
+		self.requests.extension(name, requirement); self
+	}
+	/// Request a layer checked against `PhysicalDevice::enumerate_layer_properties` at `create`
+	pub fn request_layer(&mut self, name: &str, requirement: Requirement) -> &mut Self
+	{
+		self.requests.layer(name, requirement); self
+	}
+	/// Request the device extensions whose cargo features are enabled
+	pub fn request_feature_extensions(&mut self, requirement: Requirement) -> &mut Self
+	{
+		for e in ::extensions::feature_extensions(::ExtensionScope::Device) { self.requests.extension(e, requirement); }
+		self
+	}
 	pub fn add_queue(&mut self, info: DeviceQueueCreateInfo) -> &mut Self { self.queue_infos.push(info); self }
 	pub fn add_queues<Queues: IntoIterator<Item = DeviceQueueCreateInfo>>(&mut self, queues: Queues) -> &mut Self
 	{
 		for q in queues { self.add_queue(q); } self
 	}
+	/// Plan a queue for `role`, created by `create_with_queues`.
+	/// Each role gets a distinct family and queue where available, and shares them otherwise.
+	/// The queues added by `add_queue` are kept, and the planned ones are created after them
+	pub fn plan_queue(&mut self, role: QueueRole, priority: f32, requirement: Requirement) -> &mut Self
+	{
+		self.queue_roles.retain(|r| r.0 != role);
+		self.queue_roles.push((role, priority, requirement)); self
+	}
+	/// The surface to be presented by the queue for `QueueRole::Present`
+	#[cfg(feature = "Presentation")]
+	pub fn present_to(&mut self, surface: &'p ::Surface) -> &mut Self { self.present_surface = Some(surface); self }
+	/// Features set here are enabled without checking. Prefer `request_feature`
 	pub fn mod_features(&mut self) -> &mut VkPhysicalDeviceFeatures { &mut self.features }
+	/// Request a feature checked against `PhysicalDevice::features` at `create`.
+	/// A `Requirement::Optional` feature is enabled only if supported
+	pub fn request_feature(&mut self, feature: Feature, requirement: Requirement) -> &mut Self
+	{
+		match self.feature_requests.iter_mut().find(|r| r.0 == feature)
+		{
+			Some(r) => if requirement == Requirement::Required { r.1 = requirement; },
+			None => self.feature_requests.push((feature, requirement))
+		}
+		self
+	}
+	pub fn request_features<Features: IntoIterator<Item = Feature>>(&mut self, features: Features, requirement: Requirement) -> &mut Self
+	{
+		for f in features { self.request_feature(f, requirement); } self
+	}
+	/// [Vulkan 1.1] Create the device from a group of physical devices(one of `Instance::enumerate_physical_device_groups`).
+	/// The physical device passed to `new` must be in `devices`
+	pub fn device_group(&mut self, devices: &[PhysicalDevice]) -> &mut Self
+	{
+		self.group = devices.iter().map(VkHandle::native_ptr).collect(); self
+	}
+	/// Allocate the host memory for the device and its children through `callbacks` instead of the instance's
+	pub fn allocation_callbacks<A: ::AllocationCallbacks + 'static>(&mut self, callbacks: A) -> &mut Self
+	{
+		self.allocator = Some(HostAllocator::new(callbacks)); self
+	}
+	/// Count the `DeviceMemory` objects allocated through the device per heap and memory type(`Device::memory_tracker`)
+	pub fn track_memory(&mut self) -> &mut Self { self.track_memory = true; self }
 	/// [feature = "Implements"] Create a new device instance
 	/// # Failures
 	/// On failure, this command returns
@@ -131,25 +299,186 @@ impl<'p> DeviceBuilder<'p>
 	/// * `VK_ERROR_FEATURE_NOT_PRESENT`
 	/// * `VK_ERROR_TOO_MANY_OBJECTS`
 	/// * `VK_ERROR_DEVICE_LOST`
+	/// * `Error::MissingExtension`/`Error::MissingLayer` for the unavailable required extensions/layers
+	/// * `Error::MissingFeature` for the first unsupported required feature
+	/// * `Error::NoQueueFamily` for the first required role of `plan_queue` which no queue family can serve
 	#[cfg(feature = "Implements")]
-	pub fn create(&self) -> ::Result<Device>
+	pub fn create(&self) -> ::Result<Device> { self.create_with_queues().map(|(d, _)| d) }
+	/// [feature = "Implements"] Create a new device instance, with the queues planned by `plan_queue`
+	/// # Failures
+	/// Same as `create`
+	#[cfg(feature = "Implements")]
+	pub fn create_with_queues(&self) -> ::Result<(Device, Queues)>
 	{
-		let qinfos = self.queue_infos.iter().map(|&DeviceQueueCreateInfo(fi, ref ps)| ::vk::VkDeviceQueueCreateInfo
+		let (queue_infos, plan) = self.plan_queues()?;
+		let device = self.create_device(&queue_infos)?;
+		let mut queues = Queues { graphics: None, present: None, compute: None, transfer: None, sparse_binding: None, families: Vec::new() };
+		for (role, family, index) in plan
+		{
+			let q = Some(device.queue(family, index));
+			match role
+			{
+				QueueRole::Graphics => queues.graphics = q, QueueRole::Present => queues.present = q,
+				QueueRole::AsyncCompute => queues.compute = q, QueueRole::Transfer => queues.transfer = q,
+				QueueRole::SparseBinding => queues.sparse_binding = q
+			}
+			queues.families.push((role, family));
+		}
+		Ok((device, queues))
+	}
+	#[cfg(feature = "Implements")]
+	fn create_device(&self, queue_infos: &[DeviceQueueCreateInfo]) -> ::Result<Device>
+	{
+		let (layer_names, extension_names) = self.negotiate()?;
+		let features = self.negotiate_features()?;
+		let layer_names = layer_names.into_iter().map(|x| CString::new(x).unwrap()).collect::<Vec<_>>();
+		let extension_names = extension_names.into_iter().map(|x| CString::new(x).unwrap()).collect::<Vec<_>>();
+		let qinfos = queue_infos.iter().map(|&DeviceQueueCreateInfo(fi, ref ps)| ::vk::VkDeviceQueueCreateInfo
 		{
 			queueFamilyIndex: fi, queueCount: ps.len() as _, pQueuePriorities: ps.as_ptr(), .. Default::default()
 		}).collect::<Vec<_>>();
-		let layers = self.layers.iter().map(|x| x.as_ptr()).collect::<Vec<_>>();
-		let extensions = self.extensions.iter().map(|x| x.as_ptr()).collect::<Vec<_>>();
+		let layers = layer_names.iter().map(|x| x.as_ptr()).collect::<Vec<_>>();
+		let extensions = extension_names.iter().map(|x| x.as_ptr()).collect::<Vec<_>>();
+		let group = ::vk::VkDeviceGroupDeviceCreateInfo
+		{
+			physicalDeviceCount: self.group.len() as _, pPhysicalDevices: self.group.as_ptr(), .. Default::default()
+		};
 		let cinfo = ::vk::VkDeviceCreateInfo
 		{
+			pNext: if self.group.is_empty() { ::std::ptr::null() } else { &group as *const _ as _ },
 			queueCreateInfoCount: qinfos.len() as _, pQueueCreateInfos: qinfos.as_ptr(),
 			enabledLayerCount: layers.len() as _, ppEnabledLayerNames: layers.as_ptr(),
 			enabledExtensionCount: extensions.len() as _, ppEnabledExtensionNames: extensions.as_ptr(),
-			pEnabledFeatures: &self.features, .. Default::default()
+			pEnabledFeatures: features.raw(), .. Default::default()
 		};
+		let instance = self.pdev_ref.parent();
 		let mut h = unsafe { ::std::mem::zeroed() };
-		unsafe { Resolver::get().create_device(self.pdev_ref.native_ptr(), &cinfo, ::std::ptr::null(), &mut h) }.into_result()
-			.map(|_| Device(RefCounter::new(DeviceCell(h, self.pdev_ref.parent().clone()))))
+		let allocator = self.allocator.as_ref().or_else(|| instance.host_allocator()).cloned();
+		unsafe { instance.dispatch().create_device(self.pdev_ref.native_ptr(), &cinfo, HostAllocator::callbacks(allocator.as_ref()), &mut h) }.into_result_in("vkCreateDevice", "VkPhysicalDevice")?;
+		let dispatch = unsafe { device_dispatch(instance.dispatch_ref(), h) };
+		let enabled: (Vec<String>, Vec<String>) = (
+			layer_names.into_iter().map(|x| x.into_string().unwrap()).collect(),
+			extension_names.into_iter().map(|x| x.into_string().unwrap()).collect()
+		);
+		let tracker = if self.track_memory
+		{
+			Some(MemoryTracker::new(self.pdev_ref, enabled.1.iter().any(|e| e == "VK_EXT_memory_budget")))
+		}
+		else { None };
+		let host_access = HostAccess
+		{
+			memory_type_flags: self.pdev_ref.memory_properties().types().map(|t| t.propertyFlags).collect(),
+			non_coherent_atom_size: self.pdev_ref.properties().limits.nonCoherentAtomSize.max(1)
+		};
+		Ok(Device(RefCounter::new(DeviceCell(h, instance.clone(), allocator, Mutex::new(Vec::new()), enabled, features, tracker, host_access, dispatch))))
+	}
+	/// Queues to be created(the added ones followed by the planned ones), and the family and the index of the queue for each role
+	#[cfg(feature = "Implements")]
+	fn plan_queues(&self) -> ::Result<(Vec<DeviceQueueCreateInfo>, QueuePlan)>
+	{
+		let mut priorities: Vec<Vec<f32>> = Vec::new();
+		for &DeviceQueueCreateInfo(f, ref ps) in &self.queue_infos
+		{
+			if priorities.len() <= f as usize { priorities.resize(f as usize + 1, Vec::new()); }
+			priorities[f as usize].extend_from_slice(ps);
+		}
+		let mut plan = QueuePlan::new();
+		if !self.queue_roles.is_empty()
+		{
+			let families = self.pdev_ref.queue_family_properties();
+			if priorities.len() < families.0.len() { priorities.resize(families.0.len(), Vec::new()); }
+			let present = self.present_support(families.count())?;
+			// graphics and compute families support transfer operations implicitly
+			let flags = |f: usize| match families.0[f].queueFlags
+			{
+				q if (q & (VK_QUEUE_GRAPHICS_BIT | VK_QUEUE_COMPUTE_BIT)) != 0 => q | VK_QUEUE_TRANSFER_BIT, q => q
+			};
+			// graphics first so that the presentation can share its queue
+			let mut roles = self.queue_roles.clone();
+			roles.sort_by_key(|r| r.0 != QueueRole::Graphics);
+			for (role, priority, requirement) in roles
+			{
+				if role == QueueRole::Present
+				{
+					let graphics = plan.iter().find(|p| p.0 == QueueRole::Graphics).cloned();
+					if let Some((_, f, i)) = graphics.filter(|&(_, f, _)| present[f as usize])
+					{
+						plan.push((role, f, i)); continue;
+					}
+				}
+				let found = role.candidates().iter().filter_map(|&(required, avoided)|
+				{
+					let matching = (0 .. families.0.len()).filter(|&f| families.0[f].queueCount > 0
+						&& (role != QueueRole::Present || present[f]) && (flags(f) & required) == required && (flags(f) & avoided) == 0)
+						.collect::<Vec<_>>();
+					// prefer the families not used by the other roles
+					matching.iter().cloned().find(|&f| !plan.iter().any(|p| p.1 as usize == f)).or_else(|| matching.first().cloned())
+				}).next();
+				let family = match found
+				{
+					Some(f) => f,
+					None if requirement == Requirement::Optional => continue,
+					None => return Err(::Error::NoQueueFamily(role))
+				};
+				let (ps, count) = (&mut priorities[family], families.0[family].queueCount as usize);
+				let index = if ps.len() < count { ps.push(priority); ps.len() - 1 }
+				else
+				{
+					// share the last queue of the family
+					if ps[count - 1] < priority { ps[count - 1] = priority; }
+					count - 1
+				};
+				plan.push((role, family as _, index as _));
+			}
+		}
+		let infos = priorities.into_iter().enumerate().filter(|(_, ps)| !ps.is_empty()).map(|(f, ps)| DeviceQueueCreateInfo(f as _, ps)).collect();
+		Ok((infos, plan))
+	}
+	/// Whether each queue family can present to the surface given by `present_to`
+	#[cfg(all(feature = "Implements", feature = "Presentation"))]
+	fn present_support(&self, family_count: u32) -> ::Result<Vec<bool>>
+	{
+		match self.present_surface
+		{
+			Some(s) => (0 .. family_count).map(|f| self.pdev_ref.surface_support(f, s)).collect(),
+			None => Ok(vec![false; family_count as _])
+		}
+	}
+	#[cfg(all(feature = "Implements", not(feature = "Presentation")))]
+	fn present_support(&self, family_count: u32) -> ::Result<Vec<bool>> { Ok(vec![false; family_count as _]) }
+	/// Layers and extensions to be enabled: the requested ones available with their dependencies, followed by the added ones
+	#[cfg(feature = "Implements")]
+	fn negotiate(&self) -> ::Result<(Vec<String>, Vec<String>)>
+	{
+		let mut layers = Vec::new();
+		let mut extensions = Vec::new();
+		if !self.requests.is_empty()
+		{
+			let instance = self.pdev_ref.parent();
+			let available_layers = self.pdev_ref.enumerate_layer_properties()?;
+			layers = self.requests.resolve_layers(&available_layers.iter().map(layer_name).collect::<Vec<_>>())?;
+			let mut available = self.pdev_ref.enumerate_extension_properties(None)?;
+			for l in &layers { available.extend(self.pdev_ref.enumerate_extension_properties(Some(l))?); }
+			let api_version = ::std::cmp::min(self.pdev_ref.properties().apiVersion, instance.api_version());
+			extensions = self.requests.resolve_extensions(ExtensionScope::Device, api_version,
+				&available.iter().map(extension_name).collect::<Vec<_>>(), instance.enabled_extensions())?;
+		}
+		::base::merge_names(&mut layers, &self.layers); ::base::merge_names(&mut extensions, &self.extensions);
+		Ok((layers, extensions))
+	}
+	/// Features to be enabled: the ones set by `mod_features` and the requested ones supported
+	#[cfg(feature = "Implements")]
+	fn negotiate_features(&self) -> ::Result<FeatureSet>
+	{
+		let mut enabled = FeatureSet::from(self.features.clone());
+		if self.feature_requests.is_empty() { return Ok(enabled); }
+		let supported = FeatureSet::from(self.pdev_ref.features());
+		for &(f, req) in &self.feature_requests
+		{
+			if supported.contains(f) { enabled.insert(f); }
+			else if req == Requirement::Required { return Err(::Error::MissingFeature(f)); }
+		}
+		Ok(enabled)
 	}
 }
 /// Tweaking features
@@ -179,11 +508,42 @@ impl<'p> DeviceBuilder<'p>
 impl Device
 {
 	pub(crate) fn instance(&self) -> &::Instance { &self.0 .1 }
+	pub(crate) fn host_access(&self) -> &HostAccess { &self.0 .7 }
+	/// Whether the dedicated allocation structures(Vulkan 1.1 or VK_KHR_dedicated_allocation) are recognized by the device
+	pub(crate) fn supports_dedicated_allocation(&self) -> bool
+	{
+		self.instance().api_version() >= VK_API_VERSION_1_1 ||
+			(cfg!(feature = "VK_KHR_dedicated_allocation") && self.is_extension_enabled("VK_KHR_dedicated_allocation"))
+	}
+}
+impl Device
+{
+	/// Extensions enabled on this device, including the dependencies pulled in by `DeviceBuilder::request_extension`
+	pub fn enabled_extensions(&self) -> &[String] { &(self.0 .4).1 }
+	/// Layers enabled on this device
+	pub fn enabled_layers(&self) -> &[String] { &(self.0 .4).0 }
+	/// Whether the extension is enabled on this device
+	pub fn is_extension_enabled(&self, name: &str) -> bool { self.enabled_extensions().iter().any(|e| e == name) }
+	/// Features enabled on this device
+	pub fn enabled_features(&self) -> &FeatureSet { &self.0 .5 }
+	/// The tracker of the device memory, if enabled by `DeviceBuilder::track_memory`
+	pub fn memory_tracker(&self) -> Option<&MemoryTracker> { self.0 .6.as_ref() }
+}
+impl Queue
+{
+	/// Lock held while a command is using the queue
+	#[cfg_attr(not(feature = "Presentation"), allow(dead_code))]
+	pub(crate) fn external_sync(&self) -> &ExternalSync { &self.2 }
 }
 /// Following methods are enabled with [feature = "Implements"]
 #[cfg(feature = "Implements")]
 impl Device
 {
+	/// Entry points loaded for this device
+	pub fn dispatch(&self) -> &::Dispatch { ::vkresolve::dispatch_of(&self.0 .8) }
+	pub(crate) fn dispatch_ref(&self) -> &DispatchRef { &self.0 .8 }
+	/// `pAllocator` for the objects created from this device
+	pub(crate) fn allocation_callbacks(&self) -> *const VkAllocationCallbacks { HostAllocator::callbacks(self.0 .2.as_ref()) }
 	/// Return a function pointer for a command
 	/// # Failures
 	/// If function is not provided by instance or `name` is empty, returns `None`
@@ -193,7 +553,7 @@ impl Device
 
 		unsafe
 		{
-			Resolver::get().get_device_proc_addr(self.native_ptr(), CString::new(name).unwrap().as_ptr())
+			self.dispatch().get_device_proc_addr(self.native_ptr(), CString::new(name).unwrap().as_ptr())
 				.map(|f| FnTransmute::from_fn(f))
 		}
 	}
@@ -201,8 +561,29 @@ impl Device
 	pub fn queue(&self, family_index: u32, queue_index: u32) -> Queue
 	{
 		let mut h = unsafe { ::std::mem::zeroed() };
-		unsafe { Resolver::get().get_device_queue(self.native_ptr(), family_index, queue_index, &mut h) }
-		Queue(h, self.clone())
+		unsafe { self.dispatch().get_device_queue(self.native_ptr(), family_index, queue_index, &mut h) }
+		let key = (family_index, queue_index);
+		let mut locks = self.0 .3.lock().unwrap_or_else(|e| e.into_inner());
+		let lock = match locks.iter().position(|&(k, _)| k == key)
+		{
+			Some(n) => locks[n].1.clone(),
+			None => { let l = Arc::new(ExternalSync::default()); locks.push((key, l.clone())); l }
+		};
+		Queue(h, self.clone(), lock)
+	}
+	/// [Vulkan 1.1] Query supported peer memory features of a device
+	/// # Failures
+	/// Returns `Error::MissingEntryPoint` if the driver does not provide `vkGetDeviceGroupPeerMemoryFeatures`
+	pub fn peer_memory_features(&self, heap_index: u32, local_device_index: u32, remote_device_index: u32)
+		-> ::Result<VkPeerMemoryFeatureFlags>
+	{
+		let mut f = 0;
+		unsafe
+		{
+			self.dispatch().get_device_group_peer_memory_features(self.native_ptr(), heap_index,
+				local_device_index, remote_device_index, &mut f)?
+		};
+		Ok(f)
 	}
 	/// Invalidate `MappedMemoryRange`s
 	/// Invalidating the memory range allows that device writes to the memory ranges
@@ -212,8 +593,8 @@ impl Device
 	/// Memory object in `ranges` must be currently host mapped
 	pub unsafe fn invalidate_memory_range(&self, ranges: &[VkMappedMemoryRange]) -> ::Result<()>
 	{
-		Resolver::get()
-			.invalidate_mapped_memory_ranges(self.native_ptr(), ranges.len() as _, ranges.as_ptr()).into_result()
+		self.dispatch()
+			.invalidate_mapped_memory_ranges(self.native_ptr(), ranges.len() as _, ranges.as_ptr()).into_result_in("vkInvalidateMappedMemoryRanges", "VkDevice")
 	}
 	/// Update the contents of a descriptor set object
 	pub fn update_descriptor_sets(&self, write: &[::DescriptorSetWriteInfo], copy: &[::DescriptorSetCopyInfo])
@@ -242,7 +623,7 @@ impl Device
 			srcSet: x.src.0, srcBinding: x.src.1, srcArrayElement: x.src.2,
 			dstSet: x.dst.0, dstBinding: x.dst.1, dstArrayElement: x.dst.2, descriptorCount: x.count, .. Default::default()
 		}).collect::<Vec<_>>();
-		unsafe { Resolver::get().update_descriptor_sets(self.native_ptr(), w.len() as _, w.as_ptr(), c.len() as _, c.as_ptr()) };
+		unsafe { self.dispatch().update_descriptor_sets(self.native_ptr(), w.len() as _, w.as_ptr(), c.len() as _, c.as_ptr()) };
 	}
 }
 
@@ -254,9 +635,25 @@ pub trait Waitable
 	fn wait(&self) -> ::Result<()>;
 }
 #[cfg(feature = "Implements")]
-impl Waitable for Device { fn wait(&self) -> ::Result<()> { unsafe { Resolver::get().device_wait_idle(self.native_ptr()) }.into_result() } }
+impl Waitable for Device
+{
+	/// Every queue of the device is locked while waiting. Queues are always locked before swapchains
+	fn wait(&self) -> ::Result<()>
+	{
+		let queues = self.0 .3.lock().unwrap_or_else(|e| e.into_inner()).iter().map(|(_, l)| l.clone()).collect::<Vec<_>>();
+		let _g = queues.iter().map(|l| l.lock()).collect::<Vec<_>>();
+		unsafe { self.dispatch().device_wait_idle(self.native_ptr()) }.into_result_in("vkDeviceWaitIdle", "VkDevice")
+	}
+}
 #[cfg(feature = "Implements")]
-impl Waitable for Queue { fn wait(&self) -> ::Result<()> { unsafe { Resolver::get().queue_wait_idle(self.0) }.into_result() } }
+impl Waitable for Queue
+{
+	fn wait(&self) -> ::Result<()>
+	{
+		let _g = self.2.lock();
+		unsafe { self.1.dispatch().queue_wait_idle(self.0) }.into_result_in("vkQueueWaitIdle", "VkQueue")
+	}
+}
 
 /// Sparse Binding operation batch
 pub struct SparseBindingOpBatch<'s>
@@ -309,8 +706,9 @@ impl Queue
 			signalSemaphoreCount: ss.len() as _, pSignalSemaphores: ss.as_ptr(),
 			.. Default::default()
 		}).collect::<Vec<_>>();
-		unsafe { Resolver::get().queue_bind_sparse(self.0, batches.len() as _, batches.as_ptr(), fence.map(|x| x.0).unwrap_or(VK_NULL_HANDLE as _)) }
-			.into_result()
+		let _g = self.2.lock();
+		unsafe { self.1.dispatch().queue_bind_sparse(self.0, batches.len() as _, batches.as_ptr(), fence.map(|x| x.0).unwrap_or(VK_NULL_HANDLE as _)) }
+			.into_result_in("vkQueueBindSparse", "VkQueue")
 	}
 }
 
@@ -357,7 +755,98 @@ impl Queue
 			signalSemaphoreCount: ss.len() as _, pSignalSemaphores: ss.as_ptr(),
 			.. Default::default()
 		}).collect();
-		unsafe { Resolver::get().queue_submit(self.native_ptr(), batches.len() as _, batches.as_ptr(), fence.map(VkHandle::native_ptr).unwrap_or(VK_NULL_HANDLE as _)) }
-			.into_result()
+		let _g = self.2.lock();
+		unsafe { self.1.dispatch().queue_submit(self.native_ptr(), batches.len() as _, batches.as_ptr(), fence.map(VkHandle::native_ptr).unwrap_or(VK_NULL_HANDLE as _)) }
+			.into_result_in("vkQueueSubmit", "VkQueue")
+	}
+}
+/// Following methods are enabled with [feature = "Implements", "VK_EXT_debug_utils"]
+#[cfg(all(feature = "Implements", feature = "VK_EXT_debug_utils"))]
+impl Queue
+{
+	/// Open a label region on the queue. `color` is RGBA, all zeros for no color
+	/// # Failures
+	/// Returns `Error::MissingEntryPoint` if `VK_EXT_debug_utils` is not enabled on the instance
+	pub fn begin_label(&self, name: &str, color: [f32; 4]) -> ::Result<()>
+	{
+		let _g = self.2.lock();
+		::debug_utils::with_label(name, color, |l| unsafe { self.1.dispatch().queue_begin_debug_utils_label_ext(self.0, l) })
+	}
+	/// Close the innermost label region opened by `begin_label`
+	pub fn end_label(&self) -> ::Result<()>
+	{
+		let _g = self.2.lock();
+		unsafe { self.1.dispatch().queue_end_debug_utils_label_ext(self.0) }
+	}
+	/// Insert a single label into the queue
+	pub fn insert_label(&self, name: &str, color: [f32; 4]) -> ::Result<()>
+	{
+		let _g = self.2.lock();
+		::debug_utils::with_label(name, color, |l| unsafe { self.1.dispatch().queue_insert_debug_utils_label_ext(self.0, l) })
+	}
+}
+
+#[cfg(all(test, feature = "Implements", feature = "CustomResolver"))]
+mod tests
+{
+	use super::*;
+	use InstanceBuilder;
+	use fakedrv::{FakeDriver, assert_no_misuse};
+
+	// the fake driver has the families 0: graphics/compute/transfer/sparse x4, 1: compute/transfer x2, 2: transfer x1
+	fn families(infos: &[DeviceQueueCreateInfo]) -> Vec<(u32, Vec<f32>)>
+	{
+		infos.iter().map(|&DeviceQueueCreateInfo(f, ref ps)| (f, ps.clone())).collect()
+	}
+
+	#[test]
+	fn queue_roles()
+	{
+		let driver = FakeDriver::install();
+		let instance = InstanceBuilder::new("t", (0, 1, 0), "t", (0, 1, 0)).create().unwrap();
+		let pd = instance.iter_physical_devices().unwrap().next().unwrap();
+		let mut b = DeviceBuilder::new(&pd);
+		// graphics is planned first regardless of the order
+		b.add_queue(DeviceQueueCreateInfo(0, vec![0.5]))
+			.plan_queue(QueueRole::Transfer, 0.25, Requirement::Required).plan_queue(QueueRole::SparseBinding, 0.75, Requirement::Required)
+			.plan_queue(QueueRole::AsyncCompute, 0.5, Requirement::Required).plan_queue(QueueRole::Graphics, 1.0, Requirement::Required);
+		let (infos, plan) = b.plan_queues().unwrap();
+		// the most dedicated families, after the added queues
+		assert_eq!(plan, vec![
+			(QueueRole::Graphics, 0, 1), (QueueRole::Transfer, 2, 0), (QueueRole::SparseBinding, 0, 2), (QueueRole::AsyncCompute, 1, 0)
+		]);
+		assert_eq!(families(&infos), vec![(0, vec![0.5, 1.0, 0.75]), (1, vec![0.5]), (2, vec![0.25])]);
+
+		let (_device, queues) = b.create_with_queues().unwrap();
+		assert!(queues.graphics.is_some() && queues.get(QueueRole::Transfer).is_some() && queues.get(QueueRole::Present).is_none());
+		assert_eq!(queues.family_index(QueueRole::AsyncCompute), Some(1));
+		assert_eq!(queues.sharing_families(&[QueueRole::Transfer, QueueRole::Graphics, QueueRole::SparseBinding]), vec![0, 2]);
+		assert!(queues.sharing_families(&[QueueRole::Graphics, QueueRole::SparseBinding, QueueRole::Present]).is_empty());
+		assert_no_misuse(&driver, &instance);
+	}
+
+	#[test]
+	fn shared_and_missing_queues()
+	{
+		let _driver = FakeDriver::install();
+		let instance = InstanceBuilder::new("t", (0, 1, 0), "t", (0, 1, 0)).create().unwrap();
+		let pd = instance.iter_physical_devices().unwrap().next().unwrap();
+
+		// the dedicated transfer family has one queue: shared with the higher priority.
+		// planning a role again replaces the previous plan
+		let mut b = DeviceBuilder::new(&pd);
+		b.add_queue(DeviceQueueCreateInfo(2, vec![0.5]))
+			.plan_queue(QueueRole::Transfer, 0.25, Requirement::Required).plan_queue(QueueRole::Transfer, 0.75, Requirement::Required);
+		let (infos, plan) = b.plan_queues().unwrap();
+		assert_eq!(plan, vec![(QueueRole::Transfer, 2, 0)]);
+		assert_eq!(families(&infos), vec![(2, vec![0.75])]);
+
+		// no surface to present to
+		let mut b = DeviceBuilder::new(&pd);
+		b.plan_queue(QueueRole::Graphics, 1.0, Requirement::Required).plan_queue(QueueRole::Present, 1.0, Requirement::Optional);
+		let (_, plan) = b.plan_queues().unwrap();
+		assert_eq!(plan, vec![(QueueRole::Graphics, 0, 0)]);
+		b.plan_queue(QueueRole::Present, 1.0, Requirement::Required);
+		assert_eq!(b.create_with_queues().err(), Some(::Error::NoQueueFamily(QueueRole::Present)));
 	}
 }
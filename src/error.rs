@@ -0,0 +1,419 @@
+//! Error and Success Codes
+
+use vk::*;
+use std::fmt;
+
+pub type Result<T> = ::std::result::Result<T, Error>;
+pub trait VkResultHandler
+{
+	/// `Ok` only for `VK_SUCCESS`. Other success codes are reported as `Error::Unexpected`
+	fn into_result(self) -> Result<()>;
+	/// `into_result` with the command and the type of the handle it was called on, reported through `Error::source`
+	fn into_result_in(self, command: &'static str, object: &'static str) -> Result<()>;
+	/// Split the code into a success code and an error
+	fn into_success(self) -> Result<SuccessCode>;
+	/// `into_success` with the command and the type of the handle it was called on
+	fn into_success_in(self, command: &'static str, object: &'static str) -> Result<SuccessCode>;
+}
+impl VkResultHandler for VkResult
+{
+	fn into_result(self) -> Result<()>
+	{
+		match self.into_success()?
+		{
+			SuccessCode::Success => Ok(()), s => Err(Error::Unexpected(s))
+		}
+	}
+	fn into_result_in(self, command: &'static str, object: &'static str) -> Result<()>
+	{
+		self.into_result().map_err(|e| e.in_command(command, object))
+	}
+	fn into_success(self) -> Result<SuccessCode>
+	{
+		match self
+		{
+			VK_SUCCESS => Ok(SuccessCode::Success), VK_NOT_READY => Ok(SuccessCode::NotReady),
+			VK_TIMEOUT => Ok(SuccessCode::Timeout), VK_EVENT_SET => Ok(SuccessCode::EventSet),
+			VK_EVENT_RESET => Ok(SuccessCode::EventReset), VK_INCOMPLETE => Ok(SuccessCode::Incomplete),
+			VK_SUBOPTIMAL_KHR => Ok(SuccessCode::Suboptimal),
+			e => Err(Error::from(e))
+		}
+	}
+	fn into_success_in(self, command: &'static str, object: &'static str) -> Result<SuccessCode>
+	{
+		self.into_success().map_err(|e| e.in_command(command, object))
+	}
+}
+/// Results of the optional entry points, which fail with `Error::MissingEntryPoint` if the driver does not provide them
+impl VkResultHandler for Result<VkResult>
+{
+	fn into_result(self) -> Result<()> { self.and_then(VkResultHandler::into_result) }
+	fn into_result_in(self, command: &'static str, object: &'static str) -> Result<()>
+	{
+		self.into_result().map_err(|e| e.in_command(command, object))
+	}
+	fn into_success(self) -> Result<SuccessCode> { self.and_then(VkResultHandler::into_success) }
+	fn into_success_in(self, command: &'static str, object: &'static str) -> Result<SuccessCode>
+	{
+		self.into_success().map_err(|e| e.in_command(command, object))
+	}
+}
+
+/// Non-error codes returned by the commands
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum SuccessCode
+{
+	/// `VK_SUCCESS`: Command successfully completed
+	Success,
+	/// `VK_NOT_READY`: A fence or query has not yet completed
+	NotReady,
+	/// `VK_TIMEOUT`: A wait operation has not completed in the specified time
+	Timeout,
+	/// `VK_EVENT_SET`: An event is signaled
+	EventSet,
+	/// `VK_EVENT_RESET`: An event is unsignaled
+	EventReset,
+	/// `VK_INCOMPLETE`: A return array was too small for the result
+	Incomplete,
+	/// `VK_SUBOPTIMAL_KHR`: A swapchain no longer matches the surface properties exactly,
+	/// but can still be used to present to the surface successfully
+	Suboptimal
+}
+impl SuccessCode
+{
+	/// The raw code
+	pub fn raw(self) -> VkResult
+	{
+		match self
+		{
+			SuccessCode::Success => VK_SUCCESS, SuccessCode::NotReady => VK_NOT_READY, SuccessCode::Timeout => VK_TIMEOUT,
+			SuccessCode::EventSet => VK_EVENT_SET, SuccessCode::EventReset => VK_EVENT_RESET,
+			SuccessCode::Incomplete => VK_INCOMPLETE, SuccessCode::Suboptimal => VK_SUBOPTIMAL_KHR
+		}
+	}
+}
+
+/// Errors returned by the commands
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error
+{
+	/// `VK_ERROR_OUT_OF_HOST_MEMORY`
+	OutOfHostMemory,
+	/// `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	OutOfDeviceMemory,
+	/// `VK_ERROR_INITIALIZATION_FAILED`
+	InitializationFailed,
+	/// `VK_ERROR_DEVICE_LOST`
+	DeviceLost,
+	/// `VK_ERROR_MEMORY_MAP_FAILED`
+	MemoryMapFailed,
+	/// `VK_ERROR_LAYER_NOT_PRESENT`
+	LayerNotPresent,
+	/// `VK_ERROR_EXTENSION_NOT_PRESENT`
+	ExtensionNotPresent,
+	/// `VK_ERROR_FEATURE_NOT_PRESENT`
+	FeatureNotPresent,
+	/// `VK_ERROR_INCOMPATIBLE_DRIVER`
+	IncompatibleDriver,
+	/// `VK_ERROR_TOO_MANY_OBJECTS`
+	TooManyObjects,
+	/// `VK_ERROR_FORMAT_NOT_SUPPORTED`
+	FormatNotSupported,
+	/// `VK_ERROR_FRAGMENTED_POOL`
+	FragmentedPool,
+	/// `VK_ERROR_SURFACE_LOST_KHR`
+	SurfaceLost,
+	/// `VK_ERROR_NATIVE_WINDOW_IN_USE_KHR`
+	NativeWindowInUse,
+	/// `VK_ERROR_OUT_OF_DATE_KHR`
+	OutOfDate,
+	/// `VK_ERROR_INCOMPATIBLE_DISPLAY_KHR`
+	IncompatibleDisplay,
+	/// `VK_ERROR_VALIDATION_FAILED_EXT`
+	ValidationFailed,
+	/// `VK_ERROR_INVALID_SHADER_NV`
+	InvalidShader,
+	/// `VK_ERROR_OUT_OF_POOL_MEMORY_KHR`
+	OutOfPoolMemory,
+	/// `VK_ERROR_INVALID_EXTERNAL_HANDLE_KHR`
+	InvalidExternalHandle,
+	/// `VK_ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT`
+	FullScreenExclusiveModeLost,
+	/// An error code not known by this library
+	Unknown(VkResult),
+	/// A success code other than `VK_SUCCESS` returned from the command expected to complete
+	Unexpected(SuccessCode),
+	/// A required extension(or a dependency of `required_by`) is not available. Reported as `VK_ERROR_EXTENSION_NOT_PRESENT`
+	MissingExtension { name: String, required_by: Option<String> },
+	/// A required layer is not available. Reported as `VK_ERROR_LAYER_NOT_PRESENT`
+	MissingLayer(String),
+	/// A required feature is not supported by the physical device. Reported as `VK_ERROR_FEATURE_NOT_PRESENT`
+	MissingFeature(::Feature),
+	/// No physical device satisfies the requirements given to `PhysicalDeviceSelector`, with the reasons for each device.
+	/// Reported as `VK_ERROR_INITIALIZATION_FAILED`
+	NoSuitableDevice(Vec<::Rejection>),
+	/// No queue family can serve a required role planned by `DeviceBuilder::plan_queue`. Reported as `VK_ERROR_INITIALIZATION_FAILED`
+	NoQueueFamily(::QueueRole),
+	/// No memory type allowed by `type_bits` has all of `flags`. Reported as `VK_ERROR_FEATURE_NOT_PRESENT`
+	NoMemoryType { type_bits: u32, flags: ::MemoryPropertyFlags },
+	/// `MemoryAllocator` was requested to allocate zero bytes. Detected on the host, without a raw code
+	EmptyAllocation,
+	/// The memory object is already host mapped. Reported as `VK_ERROR_MEMORY_MAP_FAILED`
+	AlreadyMapped,
+	/// Texels of the format cannot be converted for exporting images. Detected on the host, without a raw code
+	UnexportableFormat(VkFormat),
+	/// Compared images differ in width or height, given as `(width, height)`. Detected on the host, without a raw code
+	ExtentMismatch { left: (u32, u32), right: (u32, u32) },
+	/// The driver does not provide the entry point. Reported as `VK_ERROR_EXTENSION_NOT_PRESENT`
+	MissingEntryPoint(&'static str),
+	/// The command buffer has been freed, or its pool has been destroyed. Reported as `VK_ERROR_INITIALIZATION_FAILED`
+	UnknownCommandBuffer,
+	/// No Vulkan library could be loaded for a global command. Reported as `VK_ERROR_INCOMPATIBLE_DRIVER`
+	#[cfg(feature = "DynamicLoaded")]
+	Load(::std::sync::Arc<::LoadError>),
+	/// `error` returned from `command` called on an object of the type `object`("the loader" for global commands)
+	Command { command: &'static str, object: &'static str, error: Box<Error> }
+}
+impl From<VkResult> for Error
+{
+	fn from(code: VkResult) -> Self
+	{
+		match code
+		{
+			VK_ERROR_OUT_OF_HOST_MEMORY => Error::OutOfHostMemory,
+			VK_ERROR_OUT_OF_DEVICE_MEMORY => Error::OutOfDeviceMemory,
+			VK_ERROR_INITIALIZATION_FAILED => Error::InitializationFailed,
+			VK_ERROR_DEVICE_LOST => Error::DeviceLost,
+			VK_ERROR_MEMORY_MAP_FAILED => Error::MemoryMapFailed,
+			VK_ERROR_LAYER_NOT_PRESENT => Error::LayerNotPresent,
+			VK_ERROR_EXTENSION_NOT_PRESENT => Error::ExtensionNotPresent,
+			VK_ERROR_FEATURE_NOT_PRESENT => Error::FeatureNotPresent,
+			VK_ERROR_INCOMPATIBLE_DRIVER => Error::IncompatibleDriver,
+			VK_ERROR_TOO_MANY_OBJECTS => Error::TooManyObjects,
+			VK_ERROR_FORMAT_NOT_SUPPORTED => Error::FormatNotSupported,
+			VK_ERROR_FRAGMENTED_POOL => Error::FragmentedPool,
+			VK_ERROR_SURFACE_LOST_KHR => Error::SurfaceLost,
+			VK_ERROR_NATIVE_WINDOW_IN_USE_KHR => Error::NativeWindowInUse,
+			VK_ERROR_OUT_OF_DATE_KHR => Error::OutOfDate,
+			VK_ERROR_INCOMPATIBLE_DISPLAY_KHR => Error::IncompatibleDisplay,
+			VK_ERROR_VALIDATION_FAILED_EXT => Error::ValidationFailed,
+			VK_ERROR_INVALID_SHADER_NV => Error::InvalidShader,
+			VK_ERROR_OUT_OF_POOL_MEMORY_KHR => Error::OutOfPoolMemory,
+			VK_ERROR_INVALID_EXTERNAL_HANDLE_KHR => Error::InvalidExternalHandle,
+			VK_ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT => Error::FullScreenExclusiveModeLost,
+			e => Error::Unknown(e)
+		}
+	}
+}
+impl Error
+{
+	/// Attach the command and the type of the handle it was called on
+	pub fn in_command(self, command: &'static str, object: &'static str) -> Self
+	{
+		Error::Command { command, object, error: Box::new(self) }
+	}
+	/// The error without any command context
+	pub fn kind(&self) -> &Error
+	{
+		match *self { Error::Command { ref error, .. } => error.kind(), ref e => e }
+	}
+	/// The raw code, or `None` for the errors detected on the host which no Vulkan code corresponds to
+	pub fn raw(&self) -> Option<VkResult>
+	{
+		Some(match *self.kind()
+		{
+			Error::OutOfHostMemory => VK_ERROR_OUT_OF_HOST_MEMORY,
+			Error::OutOfDeviceMemory => VK_ERROR_OUT_OF_DEVICE_MEMORY,
+			Error::InitializationFailed => VK_ERROR_INITIALIZATION_FAILED,
+			Error::DeviceLost => VK_ERROR_DEVICE_LOST,
+			Error::MemoryMapFailed => VK_ERROR_MEMORY_MAP_FAILED,
+			Error::LayerNotPresent => VK_ERROR_LAYER_NOT_PRESENT,
+			Error::ExtensionNotPresent => VK_ERROR_EXTENSION_NOT_PRESENT,
+			Error::FeatureNotPresent => VK_ERROR_FEATURE_NOT_PRESENT,
+			Error::IncompatibleDriver => VK_ERROR_INCOMPATIBLE_DRIVER,
+			Error::TooManyObjects => VK_ERROR_TOO_MANY_OBJECTS,
+			Error::FormatNotSupported => VK_ERROR_FORMAT_NOT_SUPPORTED,
+			Error::FragmentedPool => VK_ERROR_FRAGMENTED_POOL,
+			Error::SurfaceLost => VK_ERROR_SURFACE_LOST_KHR,
+			Error::NativeWindowInUse => VK_ERROR_NATIVE_WINDOW_IN_USE_KHR,
+			Error::OutOfDate => VK_ERROR_OUT_OF_DATE_KHR,
+			Error::IncompatibleDisplay => VK_ERROR_INCOMPATIBLE_DISPLAY_KHR,
+			Error::ValidationFailed => VK_ERROR_VALIDATION_FAILED_EXT,
+			Error::InvalidShader => VK_ERROR_INVALID_SHADER_NV,
+			Error::OutOfPoolMemory => VK_ERROR_OUT_OF_POOL_MEMORY_KHR,
+			Error::InvalidExternalHandle => VK_ERROR_INVALID_EXTERNAL_HANDLE_KHR,
+			Error::FullScreenExclusiveModeLost => VK_ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT,
+			Error::Unknown(e) => e,
+			Error::Unexpected(s) => s.raw(),
+			Error::MissingExtension { .. } | Error::MissingEntryPoint(_) => VK_ERROR_EXTENSION_NOT_PRESENT,
+			Error::MissingLayer(_) => VK_ERROR_LAYER_NOT_PRESENT,
+			Error::MissingFeature(_) | Error::NoMemoryType { .. } => VK_ERROR_FEATURE_NOT_PRESENT,
+			Error::NoSuitableDevice(_) | Error::NoQueueFamily(_) | Error::UnknownCommandBuffer => VK_ERROR_INITIALIZATION_FAILED,
+			Error::AlreadyMapped => VK_ERROR_MEMORY_MAP_FAILED,
+			Error::UnexportableFormat(_) | Error::ExtentMismatch { .. } | Error::EmptyAllocation => return None,
+			#[cfg(feature = "DynamicLoaded")]
+			Error::Load(_) => VK_ERROR_INCOMPATIBLE_DRIVER,
+			Error::Command { .. } => unreachable!()
+		})
+	}
+	/// The command and the type of the handle it was called on, if recorded
+	pub fn command(&self) -> Option<(&'static str, &'static str)>
+	{
+		match *self { Error::Command { command, object, .. } => Some((command, object)), _ => None }
+	}
+}
+impl ::std::error::Error for Error
+{
+	fn source(&self) -> Option<&(dyn (::std::error::Error) + 'static)>
+	{
+		match *self
+		{
+			Error::Command { ref error, .. } => Some(&**error),
+			#[cfg(feature = "DynamicLoaded")]
+			Error::Load(ref e) => Some(&**e),
+			_ => None
+		}
+	}
+}
+#[cfg(feature = "DynamicLoaded")]
+impl From<::LoadError> for Error
+{
+	fn from(e: ::LoadError) -> Self { Error::Load(::std::sync::Arc::new(e)) }
+}
+impl fmt::Display for Error
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result
+	{
+		let desc = match *self
+		{
+			Error::OutOfHostMemory => "A host memory allocation has failed",
+			Error::OutOfDeviceMemory => "A device memory allocation has failed",
+			Error::InitializationFailed => "Initialization of an object could not be completed for implementation-specific reasons",
+			Error::DeviceLost => "The logical or physical device has been lost",
+			Error::MemoryMapFailed => "Mapping of a memory object has failed",
+			Error::LayerNotPresent => "A requested layer is not presented or could not be loaded",
+			Error::ExtensionNotPresent => "A requested extension is not supported",
+			Error::FeatureNotPresent => "A requested feature is not supported",
+			Error::IncompatibleDriver => "The requested version of Vulkan is not supported by the driver or is otherwise incompatible for implementation-specific reasons",
+			Error::TooManyObjects => "Too many objects of the type have already been created",
+			Error::FormatNotSupported => "A requested format is not supported on this device",
+			Error::FragmentedPool => "A pool allocation has failed due to fragmentation of the pool's memory",
+			Error::SurfaceLost => "Surface lost",
+			Error::NativeWindowInUse => "Native window is in use",
+			Error::OutOfDate => "Out of date",
+			Error::IncompatibleDisplay => "The display used by a swapchain does not use the same presentable image layout",
+			Error::ValidationFailed => "Validation failed",
+			Error::InvalidShader => "Invalid GLSL shader",
+			Error::OutOfPoolMemory => "A pool memory allocation has failed",
+			Error::InvalidExternalHandle => "An external handle is not a valid handle of ths specified type",
+			Error::FullScreenExclusiveModeLost => "Full-screen exclusive mode was lost",
+			Error::AlreadyMapped => "The memory object is already host mapped",
+			Error::EmptyAllocation => "An allocation of zero bytes was requested",
+			Error::UnknownCommandBuffer => "The command buffer has been freed, or its pool has been destroyed",
+			Error::Unknown(e) => return write!(fmt, "Unknown or extension-specific error({})", e),
+			Error::Unexpected(s) => return write!(fmt, "Unexpected success code {:?}", s),
+			Error::MissingExtension { ref name, required_by: None } => return write!(fmt, "Extension {} is not supported", name),
+			Error::MissingExtension { ref name, required_by: Some(ref r) } =>
+				return write!(fmt, "Extension {} required by {} is not supported", name, r),
+			Error::MissingLayer(ref name) => return write!(fmt, "Layer {} is not present", name),
+			Error::MissingFeature(f) => return write!(fmt, "Feature {} is not supported", f.name()),
+			Error::UnexportableFormat(f) => return write!(fmt, "Texels of the format {} cannot be exported", f),
+			Error::ExtentMismatch { left, right } =>
+				return write!(fmt, "Comparing images of different sizes: {}x{} and {}x{}", left.0, left.1, right.0, right.1),
+			Error::MissingEntryPoint(name) =>
+				return write!(fmt, "{} is not provided by the driver. The API version or the extension which provides it must be enabled", name),
+			Error::NoQueueFamily(role) => return write!(fmt, "No queue family for the role {:?}", role),
+			#[cfg(feature = "DynamicLoaded")]
+			Error::Load(ref e) => return write!(fmt, "{}", e),
+			Error::NoMemoryType { type_bits, flags } =>
+				return write!(fmt, "No memory type in {:#b} has the properties {:?}", type_bits, flags),
+			Error::NoSuitableDevice(ref rejections) =>
+			{
+				fmt.write_str("No suitable physical device")?;
+				for r in rejections { write!(fmt, "; {}", r)?; }
+				return Ok(());
+			},
+			Error::Command { command, object, .. } => return write!(fmt, "{} failed on {}", command, object)
+		};
+		fmt.write_str(desc)
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use std::error::Error as StdError;
+
+	#[test]
+	fn success_codes()
+	{
+		for &s in &[SuccessCode::Success, SuccessCode::NotReady, SuccessCode::Timeout, SuccessCode::EventSet,
+			SuccessCode::EventReset, SuccessCode::Incomplete, SuccessCode::Suboptimal]
+		{
+			assert_eq!(s.raw().into_success(), Ok(s));
+		}
+		assert_eq!(VK_SUCCESS.into_result(), Ok(()));
+		assert_eq!(VK_TIMEOUT.into_result(), Err(Error::Unexpected(SuccessCode::Timeout)));
+		assert_eq!(VK_ERROR_DEVICE_LOST.into_success(), Err(Error::DeviceLost));
+	}
+
+	#[test]
+	fn error_codes()
+	{
+		for &c in &[VK_ERROR_OUT_OF_HOST_MEMORY, VK_ERROR_OUT_OF_DEVICE_MEMORY, VK_ERROR_INITIALIZATION_FAILED, VK_ERROR_DEVICE_LOST,
+			VK_ERROR_MEMORY_MAP_FAILED, VK_ERROR_LAYER_NOT_PRESENT, VK_ERROR_EXTENSION_NOT_PRESENT, VK_ERROR_FEATURE_NOT_PRESENT,
+			VK_ERROR_INCOMPATIBLE_DRIVER, VK_ERROR_TOO_MANY_OBJECTS, VK_ERROR_FORMAT_NOT_SUPPORTED, VK_ERROR_FRAGMENTED_POOL,
+			VK_ERROR_SURFACE_LOST_KHR, VK_ERROR_NATIVE_WINDOW_IN_USE_KHR, VK_ERROR_OUT_OF_DATE_KHR, VK_ERROR_INCOMPATIBLE_DISPLAY_KHR,
+			VK_ERROR_VALIDATION_FAILED_EXT, VK_ERROR_INVALID_SHADER_NV, VK_ERROR_OUT_OF_POOL_MEMORY_KHR,
+			VK_ERROR_INVALID_EXTERNAL_HANDLE_KHR, VK_ERROR_FULL_SCREEN_EXCLUSIVE_MODE_LOST_EXT]
+		{
+			let e = Error::from(c);
+			assert_ne!(e, Error::Unknown(c));
+			assert_eq!(e.raw(), Some(c));
+		}
+		assert_eq!(Error::from(-1_000_999_000), Error::Unknown(-1_000_999_000));
+		assert_eq!(Error::Unknown(-1_000_999_000).raw(), Some(-1_000_999_000));
+		assert_eq!(Error::Unexpected(SuccessCode::Incomplete).raw(), Some(VK_INCOMPLETE));
+		assert_eq!(Error::MissingFeature(::Feature::GeometryShader).raw(), Some(VK_ERROR_FEATURE_NOT_PRESENT));
+		assert_eq!(Error::AlreadyMapped.raw(), Some(VK_ERROR_MEMORY_MAP_FAILED));
+		// host-side checks are not disguised as driver results
+		assert_eq!(Error::ExtentMismatch { left: (1, 1), right: (2, 1) }.raw(), None);
+		assert_eq!(Error::UnexportableFormat(VK_FORMAT_R16_UNORM).raw(), None);
+		assert_eq!(Error::EmptyAllocation.raw(), None);
+	}
+
+	#[test]
+	fn command_context()
+	{
+		let e = VK_ERROR_OUT_OF_DEVICE_MEMORY.into_result_in("vkAllocateMemory", "VkDevice").unwrap_err();
+		assert_eq!(e.command(), Some(("vkAllocateMemory", "VkDevice")));
+		assert_eq!(e.kind(), &Error::OutOfDeviceMemory);
+		assert_eq!(e.raw(), Some(VK_ERROR_OUT_OF_DEVICE_MEMORY));
+		assert_eq!(e.to_string(), "vkAllocateMemory failed on VkDevice");
+		assert_eq!(e.source().map(|s| s.to_string()), Some(Error::OutOfDeviceMemory.to_string()));
+		assert_eq!(Error::OutOfDeviceMemory.command(), None);
+		assert!(Error::OutOfDeviceMemory.source().is_none());
+		// nested contexts report the innermost kind
+		assert_eq!(e.clone().in_command("vkCreateBuffer", "VkDevice").kind(), &Error::OutOfDeviceMemory);
+
+		// missing entry points pass through the handlers of the optional commands
+		let missing: Result<VkResult> = Err(Error::MissingEntryPoint("vkTrimCommandPool"));
+		let e = missing.into_result_in("vkTrimCommandPool", "VkCommandPool").unwrap_err();
+		assert_eq!((e.kind(), e.raw()), (&Error::MissingEntryPoint("vkTrimCommandPool"), Some(VK_ERROR_EXTENSION_NOT_PRESENT)));
+		assert_eq!(Ok(VK_SUBOPTIMAL_KHR).into_success_in("vkQueuePresentKHR", "VkQueue"), Ok(SuccessCode::Suboptimal));
+	}
+
+	#[test]
+	fn messages()
+	{
+		assert_eq!(Error::MissingExtension { name: "VK_KHR_swapchain".to_owned(), required_by: None }.to_string(),
+			"Extension VK_KHR_swapchain is not supported");
+		assert_eq!(Error::MissingExtension { name: "VK_KHR_surface".to_owned(), required_by: Some("VK_KHR_swapchain".to_owned()) }.to_string(),
+			"Extension VK_KHR_surface required by VK_KHR_swapchain is not supported");
+		assert_eq!(Error::MissingLayer("VK_LAYER_X".to_owned()).to_string(), "Layer VK_LAYER_X is not present");
+		assert_eq!(Error::Unknown(-13).to_string(), "Unknown or extension-specific error(-13)");
+	}
+}
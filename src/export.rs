@@ -0,0 +1,342 @@
+//! Exporting downloaded images to files and comparing them
+//!
+//! `ImageData` of the common 8-bit and floating point formats is converted into sRGB-encoded 8-bit RGBA texels,
+//! then written as PNG(with the `png` feature), binary PPM or a raw dump of the downloaded bytes.
+//! `compare` diffs two images with a per-channel tolerance, producing a diff image for the visual regression tests.
+//!
+//! ```rust,ignore
+//! let shot = readback.image(&backbuffer, ImageLayout::PresentSrc, &subresource)?;
+//! shot.save("frame.png", &ExportOptions::default())?;
+//! let diff = shot.compare(&ImageData::read_png(File::open("golden/frame.png")?)?, 2, &ExportOptions::default())?;
+//! if !diff.is_match() { diff.image.save("frame.diff.png", &ExportOptions::default())?; }
+//! ```
+//!
+//! 8-bit SRGB texels are written as they are. 8-bit UNORM texels are regarded as already display-encoded(as the usual swapchain images)
+//! unless `ExportOptions::linear_unorm` is set. Floating point texels are linear; they are scaled by the exposure, tonemapped and sRGB-encoded.
+//! Components missing in the format are filled as the Vulkan conversion does: 0 for green and blue, 1 for alpha.
+//! Only the first depth slice of the first array layer is exported.
+
+use vk::*;
+use ImageData;
+use std::io::{self, Write};
+use std::path::Path;
+#[cfg(feature = "png")] use std::io::Read;
+
+/// Operator mapping the exposed linear values of floating point texels into [0, 1]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tonemap
+{
+	/// Values out of [0, 1] are clamped
+	Clamp,
+	/// `x / (1 + x)`
+	Reinhard
+}
+/// How texels are converted into sRGB-encoded 8-bit RGBA
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportOptions
+{
+	/// Tonemapping operator for floating point formats
+	pub tonemap: Tonemap,
+	/// Scale applied to the color components of floating point formats before tonemapping
+	pub exposure: f32,
+	/// sRGB-encode 8-bit UNORM texels, regarding them as linear
+	pub linear_unorm: bool
+}
+impl Default for ExportOptions
+{
+	fn default() -> Self { ExportOptions { tonemap: Tonemap::Clamp, exposure: 1.0, linear_unorm: false } }
+}
+
+/// Result of `ImageData::compare`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageDiff
+{
+	/// Number of the texels having a channel differing more than the tolerance
+	pub differing_texels: usize,
+	/// Largest difference among all the channels, including the ones within the tolerance
+	pub max_difference: u8,
+	/// `VK_FORMAT_R8G8B8A8_SRGB` image marking the differing texels red over the dimmed grayscale of the compared image
+	pub image: ImageData
+}
+impl ImageDiff
+{
+	/// Whether every texel is within the tolerance
+	pub fn is_match(&self) -> bool { self.differing_texels == 0 }
+}
+
+/// Where the components of a format are converted from
+enum Source
+{
+	/// 8-bit normalized components. `order` gives the byte indices of R, G, B and A in a texel
+	Byte { order: [Option<usize>; 4], srgb: bool },
+	/// Linear floating point components of `size` bytes each, in RGBA order
+	Float { components: usize, size: usize }
+}
+fn source(format: VkFormat) -> Option<Source>
+{
+	let (order, srgb) = match format
+	{
+		VK_FORMAT_R8_UNORM | VK_FORMAT_R8_SRGB => ([Some(0), None, None, None], format == VK_FORMAT_R8_SRGB),
+		VK_FORMAT_R8G8_UNORM | VK_FORMAT_R8G8_SRGB => ([Some(0), Some(1), None, None], format == VK_FORMAT_R8G8_SRGB),
+		VK_FORMAT_R8G8B8_UNORM | VK_FORMAT_R8G8B8_SRGB => ([Some(0), Some(1), Some(2), None], format == VK_FORMAT_R8G8B8_SRGB),
+		VK_FORMAT_B8G8R8_UNORM | VK_FORMAT_B8G8R8_SRGB => ([Some(2), Some(1), Some(0), None], format == VK_FORMAT_B8G8R8_SRGB),
+		VK_FORMAT_R8G8B8A8_UNORM | VK_FORMAT_R8G8B8A8_SRGB => ([Some(0), Some(1), Some(2), Some(3)], format == VK_FORMAT_R8G8B8A8_SRGB),
+		VK_FORMAT_B8G8R8A8_UNORM | VK_FORMAT_B8G8R8A8_SRGB => ([Some(2), Some(1), Some(0), Some(3)], format == VK_FORMAT_B8G8R8A8_SRGB),
+		// packed into a little-endian 32-bit word from the lowest bits: R, G, B, A
+		VK_FORMAT_A8B8G8R8_UNORM_PACK32 | VK_FORMAT_A8B8G8R8_SRGB_PACK32 =>
+			([Some(0), Some(1), Some(2), Some(3)], format == VK_FORMAT_A8B8G8R8_SRGB_PACK32),
+		VK_FORMAT_R16_SFLOAT => return Some(Source::Float { components: 1, size: 2 }),
+		VK_FORMAT_R16G16_SFLOAT => return Some(Source::Float { components: 2, size: 2 }),
+		VK_FORMAT_R16G16B16_SFLOAT => return Some(Source::Float { components: 3, size: 2 }),
+		VK_FORMAT_R16G16B16A16_SFLOAT => return Some(Source::Float { components: 4, size: 2 }),
+		VK_FORMAT_R32_SFLOAT | VK_FORMAT_D32_SFLOAT => return Some(Source::Float { components: 1, size: 4 }),
+		VK_FORMAT_R32G32_SFLOAT => return Some(Source::Float { components: 2, size: 4 }),
+		VK_FORMAT_R32G32B32_SFLOAT => return Some(Source::Float { components: 3, size: 4 }),
+		VK_FORMAT_R32G32B32A32_SFLOAT => return Some(Source::Float { components: 4, size: 4 }),
+		_ => return None
+	};
+	Some(Source::Byte { order, srgb })
+}
+
+/// IEEE 754 binary16 to f32
+fn half_to_f32(h: u16) -> f32
+{
+	let (e, m) = (((h >> 10) & 0x1f) as u32, (h & 0x3ff) as u32);
+	let v = match e
+	{
+		0 => m as f32 / (1 << 24) as f32,
+		0x1f => if m == 0 { f32::INFINITY } else { f32::NAN },
+		_ => f32::from_bits(((e + 112) << 23) | (m << 13))
+	};
+	if (h & 0x8000) != 0 { -v } else { v }
+}
+/// Linear [0, 1] to the sRGB-encoded 8-bit value
+fn encode_srgb(v: f32) -> u8
+{
+	let v = if v.is_nan() { 0.0 } else { v.clamp(0.0, 1.0) };
+	let e = if v <= 0.003_130_8 { v * 12.92 } else { 1.055 * v.powf(1.0 / 2.4) - 0.055 };
+	(e * 255.0).round() as u8
+}
+
+impl ImageData
+{
+	/// Convert the texels of the first depth slice of the first layer into sRGB-encoded 8-bit RGBA, row by row
+	/// # Failures
+	/// Returns `Error::UnexportableFormat` if the format is not an 8-bit UNORM/SRGB or a 16/32-bit SFLOAT color format
+	pub fn to_rgba8(&self, options: &ExportOptions) -> ::Result<Vec<u8>>
+	{
+		let src = source(self.format).ok_or(::Error::UnexportableFormat(self.format))?;
+		let texels = self.data[.. self.depth_pitch()].chunks(self.texel_size);
+		let mut out = Vec::with_capacity(self.extent.width as usize * self.extent.height as usize * 4);
+		match src
+		{
+			Source::Byte { order, srgb } => for t in texels
+			{
+				for &o in &order[.. 3]
+				{
+					let v = o.map_or(0, |i| t[i]);
+					out.push(if !srgb && options.linear_unorm { encode_srgb(v as f32 / 255.0) } else { v });
+				}
+				out.push(order[3].map_or(255, |i| t[i]));
+			},
+			Source::Float { components, size } => for t in texels
+			{
+				let c = |n: usize| if n >= components { if n == 3 { 1.0 } else { 0.0 } }
+					else if size == 2 { half_to_f32(u16::from_le_bytes([t[n * 2], t[n * 2 + 1]])) }
+					else { f32::from_le_bytes([t[n * 4], t[n * 4 + 1], t[n * 4 + 2], t[n * 4 + 3]]) };
+				for n in 0 .. 3
+				{
+					let v = c(n) * options.exposure;
+					out.push(encode_srgb(match options.tonemap { Tonemap::Clamp => v, Tonemap::Reinhard => v / (1.0 + v.max(0.0)) }));
+				}
+				let a = c(3);
+				out.push((if a.is_nan() { 0.0 } else { a.clamp(0.0, 1.0) } * 255.0).round() as u8);
+			}
+		}
+		Ok(out)
+	}
+	/// Write the image as a binary PPM(P6). Alpha is dropped
+	pub fn write_ppm<W: Write>(&self, mut writer: W, options: &ExportOptions) -> io::Result<()>
+	{
+		let rgba = self.to_rgba8(options).map_err(to_io_error)?;
+		write!(writer, "P6\n{} {}\n255\n", self.extent.width, self.extent.height)?;
+		let rgb: Vec<u8> = rgba.chunks(4).flat_map(|t| t[.. 3].iter().cloned()).collect();
+		writer.write_all(&rgb)
+	}
+	/// Dump the downloaded bytes as they are, without any header
+	pub fn write_raw<W: Write>(&self, mut writer: W) -> io::Result<()> { writer.write_all(&self.data) }
+	/// [feature = "png"] Write the image as an 8-bit RGBA PNG with the sRGB chunk
+	#[cfg(feature = "png")]
+	pub fn write_png<W: Write>(&self, writer: W, options: &ExportOptions) -> io::Result<()>
+	{
+		let rgba = self.to_rgba8(options).map_err(to_io_error)?;
+		let mut e = ::png::Encoder::new(writer, self.extent.width, self.extent.height);
+		e.set_color(::png::ColorType::Rgba);
+		e.set_depth(::png::BitDepth::Eight);
+		e.set_source_srgb(::png::SrgbRenderingIntent::Perceptual);
+		e.write_header()?.write_image_data(&rgba)?;
+		Ok(())
+	}
+	/// [feature = "png"] Read an 8-bit(or expanded to 8-bit) PNG as a `VK_FORMAT_R8G8B8A8_SRGB` image, e.g. a golden image
+	#[cfg(feature = "png")]
+	pub fn read_png<R: Read>(reader: R) -> io::Result<Self>
+	{
+		let mut d = ::png::Decoder::new(reader);
+		d.set_transformations(::png::Transformations::normalize_to_color8());
+		let mut r = d.read_info()?;
+		let mut buf = vec![0; r.output_buffer_size()];
+		let info = r.next_frame(&mut buf)?;
+		let rgba: Vec<u8> = match info.color_type
+		{
+			::png::ColorType::Rgba => buf[.. info.buffer_size()].to_vec(),
+			::png::ColorType::Rgb => buf[.. info.buffer_size()].chunks(3).flat_map(|t| vec![t[0], t[1], t[2], 255]).collect(),
+			::png::ColorType::GrayscaleAlpha => buf[.. info.buffer_size()].chunks(2).flat_map(|t| vec![t[0], t[0], t[0], t[1]]).collect(),
+			::png::ColorType::Grayscale => buf[.. info.buffer_size()].iter().flat_map(|&l| vec![l, l, l, 255]).collect(),
+			c => return Err(io::Error::new(io::ErrorKind::InvalidData, format!("unexpected color type {:?}", c)))
+		};
+		Ok(ImageData
+		{
+			format: VK_FORMAT_R8G8B8A8_SRGB, extent: VkExtent3D { width: info.width, height: info.height, depth: 1 },
+			layers: 1, texel_size: 4, data: rgba
+		})
+	}
+	/// Write the image to `path` in the format chosen by the extension: `png`(requires the `png` feature), `ppm`, or `raw`/`bin`
+	pub fn save<P: AsRef<Path>>(&self, path: P, options: &ExportOptions) -> io::Result<()>
+	{
+		let path = path.as_ref();
+		let ext = path.extension().and_then(|e| e.to_str()).map(str::to_ascii_lowercase);
+		let open = || ::std::fs::File::create(path).map(io::BufWriter::new);
+		match ext.as_ref().map(|e| &e[..])
+		{
+			#[cfg(feature = "png")]
+			Some("png") => self.write_png(open()?, options),
+			Some("ppm") => self.write_ppm(open()?, options),
+			Some("raw") | Some("bin") => self.write_raw(open()?),
+			_ => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("cannot choose the image file format for {}", path.display())))
+		}
+	}
+
+	/// Compare the converted 8-bit RGBA texels with `other`.
+	/// A texel differs if any of its channels differs more than `tolerance`
+	/// # Failures
+	/// Returns `Error::UnexportableFormat` if either format cannot be converted,
+	/// or `Error::ExtentMismatch` if the widths or the heights differ
+	pub fn compare(&self, other: &ImageData, tolerance: u8, options: &ExportOptions) -> ::Result<ImageDiff>
+	{
+		let (left, right) = ((self.extent.width, self.extent.height), (other.extent.width, other.extent.height));
+		if left != right { return Err(::Error::ExtentMismatch { left, right }); }
+		let (a, b) = (self.to_rgba8(options)?, other.to_rgba8(options)?);
+		let (mut differing_texels, mut max_difference) = (0, 0);
+		let mut data = Vec::with_capacity(a.len());
+		for (ta, tb) in a.chunks(4).zip(b.chunks(4))
+		{
+			let d = ta.iter().zip(tb).map(|(&x, &y)| x.abs_diff(y)).max().unwrap_or(0);
+			max_difference = max_difference.max(d);
+			if d > tolerance
+			{
+				differing_texels += 1;
+				data.extend_from_slice(&[255, 0, 0, 255]);
+			}
+			else
+			{
+				let l = ((ta[0] as u32 * 54 + ta[1] as u32 * 183 + ta[2] as u32 * 19) >> 8) / 3;
+				data.extend_from_slice(&[l as u8, l as u8, l as u8, 255]);
+			}
+		}
+		Ok(ImageDiff
+		{
+			differing_texels, max_difference, image: ImageData
+			{
+				format: VK_FORMAT_R8G8B8A8_SRGB, extent: VkExtent3D { width: self.extent.width, height: self.extent.height, depth: 1 },
+				layers: 1, texel_size: 4, data
+			}
+		})
+	}
+}
+
+fn to_io_error(e: ::Error) -> io::Error { io::Error::new(io::ErrorKind::InvalidInput, e.to_string()) }
+
+#[cfg(all(test, feature = "Implements", feature = "CustomResolver"))]
+mod tests
+{
+	use super::*;
+
+	fn image(format: VkFormat, width: u32, texel_size: usize, data: Vec<u8>) -> ImageData
+	{
+		ImageData { format, extent: VkExtent3D { width, height: 1, depth: 1 }, layers: 1, texel_size, data }
+	}
+	fn floats(v: &[f32]) -> Vec<u8> { v.iter().flat_map(|x| x.to_le_bytes().to_vec()).collect() }
+
+	#[test]
+	fn half_floats()
+	{
+		assert_eq!(half_to_f32(0x3c00), 1.0);
+		assert_eq!(half_to_f32(0x3800), 0.5);
+		assert_eq!(half_to_f32(0xc000), -2.0);
+		assert_eq!(half_to_f32(0x7bff), 65504.0);
+		assert_eq!(half_to_f32(0x0001), 1.0 / (1 << 24) as f32);
+		assert_eq!(half_to_f32(0x8000), 0.0);
+		assert_eq!(half_to_f32(0x7c00), f32::INFINITY);
+		assert_eq!(half_to_f32(0xfc00), f32::NEG_INFINITY);
+		assert!(half_to_f32(0x7e00).is_nan());
+	}
+
+	#[test]
+	fn srgb_encoding()
+	{
+		assert_eq!(encode_srgb(0.0), 0);
+		assert_eq!(encode_srgb(0.003_130_8), 10);
+		assert_eq!(encode_srgb(0.25), 137);
+		assert_eq!(encode_srgb(0.5), 188);
+		assert_eq!(encode_srgb(1.0), 255);
+		assert_eq!(encode_srgb(-1.0), 0);
+		assert_eq!(encode_srgb(2.0), 255);
+		assert_eq!(encode_srgb(f32::NAN), 0);
+	}
+
+	#[test]
+	fn byte_swizzles()
+	{
+		let o = ExportOptions::default();
+		let texel = vec![10, 20, 30, 40];
+		assert_eq!(image(VK_FORMAT_R8G8B8A8_UNORM, 1, 4, texel.clone()).to_rgba8(&o), Ok(vec![10, 20, 30, 40]));
+		assert_eq!(image(VK_FORMAT_B8G8R8A8_SRGB, 1, 4, texel.clone()).to_rgba8(&o), Ok(vec![30, 20, 10, 40]));
+		// 0x40302010 stored little-endian: R in the lowest byte
+		assert_eq!(image(VK_FORMAT_A8B8G8R8_UNORM_PACK32, 1, 4, 0x4030_2010u32.to_le_bytes().to_vec()).to_rgba8(&o), Ok(vec![0x10, 0x20, 0x30, 0x40]));
+		assert_eq!(image(VK_FORMAT_B8G8R8_UNORM, 1, 3, texel[.. 3].to_vec()).to_rgba8(&o), Ok(vec![30, 20, 10, 255]));
+		assert_eq!(image(VK_FORMAT_R8_UNORM, 2, 1, vec![7, 8]).to_rgba8(&o), Ok(vec![7, 0, 0, 255, 8, 0, 0, 255]));
+		assert_eq!(image(VK_FORMAT_R8_UNORM, 1, 1, vec![128]).to_rgba8(&ExportOptions { linear_unorm: true, .. o.clone() }), Ok(vec![188, 0, 0, 255]));
+		assert_eq!(image(VK_FORMAT_R8_SRGB, 1, 1, vec![128]).to_rgba8(&ExportOptions { linear_unorm: true, .. o.clone() }), Ok(vec![128, 0, 0, 255]));
+		assert_eq!(image(VK_FORMAT_R16_UNORM, 1, 2, vec![0, 0]).to_rgba8(&o), Err(::Error::UnexportableFormat(VK_FORMAT_R16_UNORM)));
+	}
+
+	#[test]
+	fn float_tonemaps()
+	{
+		let texel = floats(&[1.0, 3.0, -1.0, 0.5]);
+		let clamp = ExportOptions::default();
+		let reinhard = ExportOptions { tonemap: Tonemap::Reinhard, .. clamp.clone() };
+		assert_eq!(image(VK_FORMAT_R32G32B32A32_SFLOAT, 1, 16, texel.clone()).to_rgba8(&clamp), Ok(vec![255, 255, 0, 128]));
+		// 1 / 2 = 0.5, 3 / 4 = 0.75
+		assert_eq!(image(VK_FORMAT_R32G32B32A32_SFLOAT, 1, 16, texel.clone()).to_rgba8(&reinhard), Ok(vec![188, 225, 0, 128]));
+		// 2 / 3 after the exposure
+		let exposed = ExportOptions { exposure: 2.0, .. reinhard.clone() };
+		assert_eq!(image(VK_FORMAT_R32_SFLOAT, 1, 4, floats(&[1.0])).to_rgba8(&exposed), Ok(vec![213, 0, 0, 255]));
+		assert_eq!(image(VK_FORMAT_R16G16_SFLOAT, 1, 4, vec![0x00, 0x38, 0x00, 0x3c]).to_rgba8(&clamp), Ok(vec![188, 255, 0, 255]));
+	}
+
+	#[test]
+	fn comparison()
+	{
+		let o = ExportOptions::default();
+		let a = image(VK_FORMAT_R8G8B8A8_SRGB, 3, 4, vec![255, 255, 255, 255, 0, 0, 0, 255, 100, 100, 100, 255]);
+		let b = image(VK_FORMAT_B8G8R8A8_SRGB, 3, 4, vec![255, 255, 255, 255, 0, 0, 2, 255, 130, 100, 100, 255]);
+		let d = a.compare(&b, 2, &o).unwrap();
+		assert_eq!((d.differing_texels, d.max_difference, d.is_match()), (1, 30, false));
+		assert_eq!(d.image.data, vec![85, 85, 85, 255, 0, 0, 0, 255, 255, 0, 0, 255]);
+		assert!(a.compare(&b, 30, &o).unwrap().is_match());
+
+		let narrow = image(VK_FORMAT_R8G8B8A8_SRGB, 2, 4, vec![0; 8]);
+		assert_eq!(a.compare(&narrow, 0, &o), Err(::Error::ExtentMismatch { left: (3, 1), right: (2, 1) }));
+	}
+}
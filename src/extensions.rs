@@ -0,0 +1,342 @@
+//! Extension/Layer Negotiation
+
+#![cfg_attr(not(feature = "Implements"), allow(dead_code))]
+
+use vk::*;
+use std::ffi::CStr;
+
+/// Whether an extension or a layer must be enabled
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Requirement
+{
+	/// Fail the creation if it is not available
+	Required,
+	/// Enable it only if it and all of its dependencies are available
+	Optional
+}
+/// The level of an extension
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ExtensionScope { Instance, Device }
+/// An extension known by this library
+#[derive(Debug, Clone, Copy)]
+pub struct KnownExtension
+{
+	pub name: &'static str,
+	pub scope: ExtensionScope,
+	/// Extensions which must be enabled together. Instance extensions are required for a device extension
+	/// when they are enabled on the parent instance
+	pub requires: &'static [&'static str],
+	/// The version of Vulkan the extension was promoted to the core, if any
+	pub promoted: Option<u32>,
+	/// Whether the cargo feature for the extension is enabled(always `false` for the extensions without features)
+	pub feature_enabled: bool
+}
+
+macro_rules! KnownExtensions
+{
+	($($scope: ident $name: tt $(as $feature: tt)* [$($req: expr),*] $(core $promoted: expr)*;)*) =>
+	{
+		/// Extensions known by this library, with their dependencies
+		pub const KNOWN_EXTENSIONS: &[KnownExtension] = &[$(KnownExtension
+		{
+			name: $name, scope: ExtensionScope::$scope, requires: &[$($req),*],
+			promoted: KnownExtensions!(@promoted $($promoted)*),
+			feature_enabled: KnownExtensions!(@feature $name $(, $feature)*)
+		}),*];
+	};
+	(@promoted) => { None };
+	(@promoted $v: expr) => { Some($v) };
+	(@feature $name: tt) => { cfg!(feature = $name) };
+	(@feature $name: tt, _) => { false };
+	(@feature $name: tt, $feature: tt) => { cfg!(feature = $feature) };
+}
+KnownExtensions!
+{
+	Instance "VK_KHR_surface" [];
+	Instance "VK_KHR_display" ["VK_KHR_surface"];
+	Instance "VK_KHR_xlib_surface" ["VK_KHR_surface"];
+	Instance "VK_KHR_xcb_surface" ["VK_KHR_surface"];
+	Instance "VK_KHR_wayland_surface" ["VK_KHR_surface"];
+	Instance "VK_KHR_android_surface" ["VK_KHR_surface"];
+	Instance "VK_KHR_win32_surface" ["VK_KHR_surface"];
+	Instance "VK_NN_vi_surface" ["VK_KHR_surface"];
+	Instance "VK_MVK_ios_surface" ["VK_KHR_surface"];
+	Instance "VK_MVK_macos_surface" ["VK_KHR_surface"];
+	Instance "VK_EXT_debug_utils" [];
+	Instance "VK_KHR_get_physical_device_properties2" [] core VK_API_VERSION_1_1;
+	Instance "VK_KHR_get_surface_capabilities2" ["VK_KHR_surface"];
+	Instance "VK_KHR_external_memory_capabilities" as _ ["VK_KHR_get_physical_device_properties2"] core VK_API_VERSION_1_1;
+	Instance "VK_KHR_external_semaphore_capabilities" as _ ["VK_KHR_get_physical_device_properties2"] core VK_API_VERSION_1_1;
+	Instance "VK_KHR_external_fence_capabilities" as _ ["VK_KHR_get_physical_device_properties2"] core VK_API_VERSION_1_1;
+	Instance "VK_KHX_device_group_creation" [];
+	Instance "VK_EXT_debug_report" [];
+	Instance "VK_EXT_validation_flags" [];
+	Instance "VK_NV_external_memory_capabilities" [];
+	Instance "VK_EXT_direct_mode_display" ["VK_KHR_display"];
+	Instance "VK_EXT_acquire_xlib_display" ["VK_EXT_direct_mode_display"];
+	Instance "VK_EXT_display_surface_counter" ["VK_KHR_display"];
+	Instance "VK_EXT_swapchain_colorspace" ["VK_KHR_surface"];
+
+	Device "VK_KHR_swapchain" ["VK_KHR_surface"];
+	Device "VK_KHR_display_swapchain" ["VK_KHR_swapchain", "VK_KHR_display"];
+	Device "VK_KHR_sampler_mirror_clamp_to_edge" [];
+	Device "VK_KHR_shader_draw_parameters" [] core VK_API_VERSION_1_1;
+	Device "VK_KHR_maintenance1" [] core VK_API_VERSION_1_1;
+	Device "VK_KHR_multiview" as _ ["VK_KHR_get_physical_device_properties2"] core VK_API_VERSION_1_1;
+	Device "VK_KHR_external_memory" as _ ["VK_KHR_external_memory_capabilities"] core VK_API_VERSION_1_1;
+	Device "VK_KHR_external_memory_win32" ["VK_KHR_external_memory"];
+	Device "VK_KHR_external_memory_fd" ["VK_KHR_external_memory"];
+	Device "VK_KHR_win32_keyed_mutex" ["VK_KHR_external_memory_win32"];
+	Device "VK_KHR_external_semaphore" as _ ["VK_KHR_external_semaphore_capabilities"] core VK_API_VERSION_1_1;
+	Device "VK_KHR_external_semaphore_win32" ["VK_KHR_external_semaphore"];
+	Device "VK_KHR_external_semaphore_fd" ["VK_KHR_external_semaphore"];
+	Device "VK_KHR_external_fence" as _ ["VK_KHR_external_fence_capabilities"] core VK_API_VERSION_1_1;
+	Device "VK_KHR_external_fence_win32" ["VK_KHR_external_fence"];
+	Device "VK_KHR_external_fence_fd" ["VK_KHR_external_fence"];
+	Device "VK_KHR_push_descriptor" ["VK_KHR_get_physical_device_properties2"];
+	Device "VK_KHR_get_memory_requirements2" [] core VK_API_VERSION_1_1;
+	Device "VK_KHR_dedicated_allocation" ["VK_KHR_get_memory_requirements2"] core VK_API_VERSION_1_1;
+	Device "VK_KHR_incremental_present" ["VK_KHR_swapchain"];
+	Device "VK_KHR_descriptor_update_template" [] core VK_API_VERSION_1_1;
+	Device "VK_KHR_shared_presentable_image"
+		["VK_KHR_swapchain", "VK_KHR_get_physical_device_properties2", "VK_KHR_get_surface_capabilities2"];
+	Device "VK_KHR_storage_buffer_storage_class" [] core VK_API_VERSION_1_1;
+	Device "VK_KHR_relaxed_block_layout" [] core VK_API_VERSION_1_1;
+	Device "VK_EXT_full_screen_exclusive"
+		["VK_KHR_get_physical_device_properties2", "VK_KHR_surface", "VK_KHR_get_surface_capabilities2", "VK_KHR_swapchain"];
+	Device "VK_NV_glsl_shader" [];
+	Device "VK_EXT_depth_range_unrestricted" [];
+	Device "VK_IMG_filter_cubic" [];
+	Device "VK_AMD_rasterization_order" [];
+	Device "VK_AMD_shader_trinary_minmax" [];
+	Device "VK_AMD_shader_explicit_vertex_parameter" [];
+	Device "VK_EXT_debug_marker" ["VK_EXT_debug_report"];
+	Device "VK_AMD_gcn_shader" [];
+	Device "VK_NV_dedicated_allocation" [];
+	Device "VK_AMD_draw_indirect_count" [];
+	Device "VK_AMD_negative_viewport_height" [];
+	Device "VK_AMD_gpu_shader_half_float" [];
+	Device "VK_AMD_shader_ballot" [];
+	Device "VK_AMD_texture_gather_bias_lod" ["VK_KHR_get_physical_device_properties2"];
+	Device "VK_AMD_shader_info" [];
+	Device "VK_AMD_shader_image_load_store_lod" [];
+	Device "VK_IMG_format_pvrtc" [];
+	Device "VK_NV_external_memory" ["VK_NV_external_memory_capabilities"];
+	Device "VK_NV_external_memory_win32" ["VK_NV_external_memory"];
+	Device "VK_NV_win32_keyed_mutex" ["VK_NV_external_memory_win32"];
+	Device "VK_KHX_device_group" ["VK_KHX_device_group_creation"];
+	Device "VK_EXT_shader_subgroup_ballot" as "VK_EXT_shader_group_ballot" [];
+	Device "VK_EXT_shader_subgroup_vote" [];
+	Device "VK_NVX_device_generated_commands" [];
+	Device "VK_NV_clip_space_w_scaling" [];
+	Device "VK_EXT_display_control" ["VK_EXT_display_surface_counter", "VK_KHR_swapchain"];
+	Device "VK_GOOGLE_display_timing" ["VK_KHR_swapchain"];
+	Device "VK_NV_sample_mask_override_coverage" [];
+	Device "VK_NV_geometry_shader_passthrough" [];
+	Device "VK_NV_viewport_array2" [];
+	Device "VK_NVX_multiview_per_view_attributes" ["VK_KHR_multiview"];
+	Device "VK_NV_viewport_swizzle" [];
+	Device "VK_EXT_discard_rectangles" ["VK_KHR_get_physical_device_properties2"];
+	Device "VK_EXT_conservative_rasterization" ["VK_KHR_get_physical_device_properties2"];
+	Device "VK_EXT_hdr_metadata" ["VK_KHR_swapchain"];
+	Device "VK_EXT_external_memory_dma_buf" ["VK_KHR_external_memory_fd"];
+	Device "VK_EXT_queue_family_foreign" ["VK_KHR_external_memory"];
+	Device "VK_EXT_sampler_filter_minmax" ["VK_KHR_get_physical_device_properties2"];
+	Device "VK_AMD_gpu_shader_int16" [];
+	Device "VK_AMD_mixed_attachment_samples" [];
+	Device "VK_AMD_shader_fragment_mask" [];
+	Device "VK_EXT_sample_locations" ["VK_KHR_get_physical_device_properties2"];
+	Device "VK_EXT_blend_operation_advanced" [];
+	Device "VK_NV_fragment_coverage_to_color" [];
+	Device "VK_NV_framebuffer_mixed_samples" [];
+	Device "VK_NV_fill_rectangle" [];
+	Device "VK_EXT_post_depth_coverage" [];
+	Device "VK_EXT_validation_cache" [];
+	Device "VK_EXT_shader_viewport_index_layer" [];
+	Device "VK_EXT_global_priority" [];
+	Device "VK_EXT_external_memory_host" ["VK_KHR_external_memory"];
+	Device "VK_AMD_buffer_marker" [];
+	Device "VK_EXT_vertex_attribute_divisor" ["VK_KHR_get_physical_device_properties2"];
+	Device "VK_EXT_memory_budget" ["VK_KHR_get_physical_device_properties2"];
+}
+/// Look up an extension in `KNOWN_EXTENSIONS`
+pub fn known_extension(name: &str) -> Option<&'static KnownExtension>
+{
+	KNOWN_EXTENSIONS.iter().find(|e| e.name == name)
+}
+/// Names of the extensions in `scope` whose cargo features are enabled
+pub fn feature_extensions(scope: ExtensionScope) -> impl Iterator<Item = &'static str>
+{
+	KNOWN_EXTENSIONS.iter().filter(move |e| e.scope == scope && e.feature_enabled).map(|e| e.name)
+}
+
+pub(crate) fn extension_name(p: &VkExtensionProperties) -> &str
+{
+	unsafe { CStr::from_ptr(p.extensionName.as_ptr()) }.to_str().unwrap_or("")
+}
+pub(crate) fn layer_name(p: &VkLayerProperties) -> &str
+{
+	unsafe { CStr::from_ptr(p.layerName.as_ptr()) }.to_str().unwrap_or("")
+}
+
+/// Extensions and layers requested through `request_extension`/`request_layer` of the builders
+#[derive(Default, Clone)]
+pub(crate) struct Requests { extensions: Vec<(String, Requirement)>, layers: Vec<(String, Requirement)> }
+impl Requests
+{
+	pub fn extension(&mut self, name: &str, req: Requirement) { Self::push(&mut self.extensions, name, req); }
+	pub fn layer(&mut self, name: &str, req: Requirement) { Self::push(&mut self.layers, name, req); }
+	fn push(list: &mut Vec<(String, Requirement)>, name: &str, req: Requirement)
+	{
+		match list.iter_mut().find(|x| x.0 == name)
+		{
+			// the strongest requirement wins
+			Some(x) => if req == Requirement::Required { x.1 = req; },
+			None => list.push((name.to_owned(), req))
+		}
+	}
+	pub fn is_empty(&self) -> bool { self.extensions.is_empty() && self.layers.is_empty() }
+
+	/// Requested layers which are available
+	pub fn resolve_layers(&self, available: &[&str]) -> ::Result<Vec<String>>
+	{
+		let mut enabled = Vec::new();
+		for &(ref name, req) in &self.layers
+		{
+			if available.contains(&name.as_str()) { enabled.push(name.clone()); }
+			else if req == Requirement::Required { return Err(::Error::MissingLayer(name.clone())); }
+		}
+		Ok(enabled)
+	}
+	/// Requested extensions in `scope` with their dependencies which are available.
+	/// Dependencies in the other scope must be in `parent_enabled`(the extensions enabled on the instance)
+	pub fn resolve_extensions(&self, scope: ExtensionScope, api_version: u32, available: &[&str], parent_enabled: &[String])
+		-> ::Result<Vec<String>>
+	{
+		let mut enabled = Vec::new();
+		for &(ref name, req) in &self.extensions
+		{
+			let mut closure = Vec::new();
+			match Self::collect(name, None, scope, api_version, available, parent_enabled, &mut closure)
+			{
+				Ok(()) => for e in closure { if !enabled.contains(&e) { enabled.push(e); } },
+				Err(e) => if req == Requirement::Required { return Err(e); }
+			}
+		}
+		Ok(enabled)
+	}
+	fn collect(name: &str, required_by: Option<&str>, scope: ExtensionScope, api_version: u32, available: &[&str], parent_enabled: &[String],
+		sink: &mut Vec<String>) -> ::Result<()>
+	{
+		let known = known_extension(name);
+		if required_by.is_some() && known.and_then(|k| k.promoted).map_or(false, |v| v <= api_version) { return Ok(()); }
+		let missing = || ::Error::MissingExtension { name: name.to_owned(), required_by: required_by.map(ToOwned::to_owned) };
+		if known.map_or(false, |k| k.scope != scope)
+		{
+			return if parent_enabled.iter().any(|e| e == name) { Ok(()) } else { Err(missing()) };
+		}
+		if sink.iter().any(|e| e == name) { return Ok(()); }
+		if !available.contains(&name) { return Err(missing()); }
+		sink.push(name.to_owned());
+		for r in known.map_or(&[][..], |k| k.requires)
+		{
+			Self::collect(r, Some(name), scope, api_version, available, parent_enabled, sink)?;
+		}
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	fn missing(name: &str, required_by: Option<&str>) -> ::Error
+	{
+		::Error::MissingExtension { name: name.to_owned(), required_by: required_by.map(ToOwned::to_owned) }
+	}
+
+	#[test]
+	fn dependencies()
+	{
+		let available = ["VK_KHR_surface", "VK_KHR_display", "VK_KHR_get_physical_device_properties2", "VK_KHR_get_surface_capabilities2"];
+		let mut r = Requests::default();
+		r.extension("VK_KHR_get_surface_capabilities2", Requirement::Required);
+		r.extension("VK_KHR_display", Requirement::Required);
+		// dependencies follow their dependents, and are enabled once
+		assert_eq!(r.resolve_extensions(ExtensionScope::Instance, VK_API_VERSION_1_0, &available, &[]).unwrap(),
+			vec!["VK_KHR_get_surface_capabilities2", "VK_KHR_surface", "VK_KHR_display"]);
+
+		// a dependency missing from the driver is reported with its dependent
+		let mut r = Requests::default();
+		r.extension("VK_KHR_display", Requirement::Required);
+		assert_eq!(r.resolve_extensions(ExtensionScope::Instance, VK_API_VERSION_1_0, &["VK_KHR_display"], &[]),
+			Err(missing("VK_KHR_surface", Some("VK_KHR_display"))));
+		assert_eq!(r.resolve_extensions(ExtensionScope::Instance, VK_API_VERSION_1_0, &[], &[]), Err(missing("VK_KHR_display", None)));
+	}
+
+	#[test]
+	fn promoted_dependencies()
+	{
+		let mut r = Requests::default();
+		r.extension("VK_KHR_dedicated_allocation", Requirement::Required);
+		// VK_KHR_get_memory_requirements2 is in the core of Vulkan 1.1
+		assert_eq!(r.resolve_extensions(ExtensionScope::Device, VK_API_VERSION_1_1, &["VK_KHR_dedicated_allocation"], &[]).unwrap(),
+			vec!["VK_KHR_dedicated_allocation"]);
+		assert_eq!(r.resolve_extensions(ExtensionScope::Device, VK_API_VERSION_1_0, &["VK_KHR_dedicated_allocation"], &[]),
+			Err(missing("VK_KHR_get_memory_requirements2", Some("VK_KHR_dedicated_allocation"))));
+		// a promoted extension requested by itself is still enabled
+		let mut r = Requests::default();
+		r.extension("VK_KHR_maintenance1", Requirement::Required);
+		assert_eq!(r.resolve_extensions(ExtensionScope::Device, VK_API_VERSION_1_1, &["VK_KHR_maintenance1"], &[]).unwrap(),
+			vec!["VK_KHR_maintenance1"]);
+	}
+
+	#[test]
+	fn instance_dependencies_of_device_extensions()
+	{
+		let mut r = Requests::default();
+		r.extension("VK_KHR_swapchain", Requirement::Required);
+		let enabled = r.resolve_extensions(ExtensionScope::Device, VK_API_VERSION_1_0, &["VK_KHR_swapchain"], &["VK_KHR_surface".to_owned()]);
+		assert_eq!(enabled.unwrap(), vec!["VK_KHR_swapchain"]);
+		// the instance extension is not looked up among the device extensions
+		assert_eq!(r.resolve_extensions(ExtensionScope::Device, VK_API_VERSION_1_0, &["VK_KHR_swapchain", "VK_KHR_surface"], &[]),
+			Err(missing("VK_KHR_surface", Some("VK_KHR_swapchain"))));
+	}
+
+	#[test]
+	fn requirements()
+	{
+		let mut r = Requests::default();
+		r.extension("VK_KHR_display_swapchain", Requirement::Optional);
+		r.extension("VK_KHR_swapchain", Requirement::Optional);
+		// the optional extension is dropped with its dependencies, leaving the others
+		let parent = ["VK_KHR_surface".to_owned()];
+		assert_eq!(r.resolve_extensions(ExtensionScope::Device, VK_API_VERSION_1_0, &["VK_KHR_display_swapchain", "VK_KHR_swapchain"], &parent).unwrap(),
+			vec!["VK_KHR_swapchain"]);
+		// the strongest requirement wins
+		r.extension("VK_KHR_display_swapchain", Requirement::Required);
+		r.extension("VK_KHR_display_swapchain", Requirement::Optional);
+		assert_eq!(r.resolve_extensions(ExtensionScope::Device, VK_API_VERSION_1_0, &["VK_KHR_display_swapchain", "VK_KHR_swapchain"], &parent),
+			Err(missing("VK_KHR_display", Some("VK_KHR_display_swapchain"))));
+
+		let mut r = Requests::default();
+		assert!(r.is_empty());
+		r.layer("VK_LAYER_a", Requirement::Optional);
+		r.layer("VK_LAYER_b", Requirement::Required);
+		assert_eq!(r.resolve_layers(&["VK_LAYER_b"]).unwrap(), vec!["VK_LAYER_b"]);
+		assert_eq!(r.resolve_layers(&["VK_LAYER_a"]), Err(::Error::MissingLayer("VK_LAYER_b".to_owned())));
+	}
+
+	#[test]
+	fn known_extensions()
+	{
+		let swapchain = known_extension("VK_KHR_swapchain").unwrap();
+		assert_eq!((swapchain.scope, swapchain.requires), (ExtensionScope::Device, &["VK_KHR_surface"][..]));
+		assert_eq!(known_extension("VK_KHR_get_physical_device_properties2").unwrap().promoted, Some(VK_API_VERSION_1_1));
+		assert!(known_extension("VK_EXT_unknown").is_none());
+		// every dependency is known too
+		for e in KNOWN_EXTENSIONS { for r in e.requires { assert!(known_extension(r).is_some(), "{} requires unknown {}", e.name, r); } }
+		assert_eq!(feature_extensions(ExtensionScope::Instance).any(|e| e == "VK_KHR_surface"), cfg!(feature = "VK_KHR_surface"));
+	}
+}
@@ -0,0 +1,106 @@
+//! Guards for the handles which the commands require to be externally synchronized
+//!
+//! Without `Multithreaded` no object can be shared between threads and the guards do nothing.
+//! Without `Implements` no command is issued, so only the empty locks embedded in the handle wrappers are compiled.
+
+#[cfg(all(feature = "Implements", feature = "Multithreaded"))] use std::sync::{Mutex, MutexGuard, Condvar, RwLock};
+#[cfg(all(feature = "Implements", feature = "Multithreaded"))] use std::collections::BTreeMap;
+#[cfg(all(feature = "Implements", feature = "Multithreaded"))] use std::thread::{self, ThreadId};
+#[cfg(feature = "Implements")] use std::sync::Arc;
+#[cfg(feature = "Implements")] use std::marker::PhantomData;
+#[cfg(feature = "Implements")] use vk::*;
+
+/// Lock held while a command is using the handle
+#[derive(Default)]
+pub(crate) struct ExternalSync(#[cfg(all(feature = "Implements", feature = "Multithreaded"))] Mutex<()>);
+#[cfg(feature = "Implements")]
+pub(crate) struct SyncGuard<'s>(#[cfg(feature = "Multithreaded")] #[allow(dead_code)] MutexGuard<'s, ()>, PhantomData<&'s ()>);
+impl ExternalSync
+{
+	#[cfg(all(feature = "Implements", feature = "Multithreaded"))]
+	pub fn lock(&self) -> SyncGuard<'_> { SyncGuard(self.0.lock().unwrap_or_else(|e| e.into_inner()), PhantomData) }
+	#[cfg(all(feature = "Implements", not(feature = "Multithreaded")))]
+	pub fn lock(&self) -> SyncGuard<'_> { SyncGuard(PhantomData) }
+}
+
+/// Lock of a command pool, held while the pool is used and while a command buffer allocated from it is recorded.
+/// The same thread may take it again(e.g. recording a secondary command buffer while recording a primary one)
+#[derive(Default)]
+pub(crate) struct PoolSync
+{
+	#[cfg(all(feature = "Implements", feature = "Multithreaded"))] owner: Mutex<(Option<ThreadId>, usize)>,
+	#[cfg(all(feature = "Implements", feature = "Multithreaded"))] released: Condvar
+}
+/// Bound to the thread which took the lock(the owner is recorded by its id), so this and `CmdRecord` holding it are not `Send`
+#[cfg(feature = "Implements")]
+pub(crate) struct PoolGuard(#[cfg(feature = "Multithreaded")] Arc<PoolSync>, PhantomData<*const ()>);
+impl PoolSync
+{
+	#[cfg(all(feature = "Implements", feature = "Multithreaded"))]
+	pub fn lock(this: &Arc<Self>) -> PoolGuard
+	{
+		let id = thread::current().id();
+		let mut o = this.owner.lock().unwrap_or_else(|e| e.into_inner());
+		while o.0.is_some() && o.0 != Some(id) { o = this.released.wait(o).unwrap_or_else(|e| e.into_inner()); }
+		o.0 = Some(id); o.1 += 1;
+		PoolGuard(this.clone(), PhantomData)
+	}
+	#[cfg(all(feature = "Implements", not(feature = "Multithreaded")))]
+	pub fn lock(_: &Arc<Self>) -> PoolGuard { PoolGuard(PhantomData) }
+}
+#[cfg(all(feature = "Implements", feature = "Multithreaded"))]
+impl Drop for PoolGuard
+{
+	fn drop(&mut self)
+	{
+		let mut o = (self.0).owner.lock().unwrap_or_else(|e| e.into_inner());
+		o.1 -= 1;
+		if o.1 == 0 { o.0 = None; (self.0).released.notify_one(); }
+	}
+}
+
+/// Pools of the live command buffers. Created on the first registration
+#[cfg(all(feature = "Implements", feature = "Multithreaded"))]
+static COMMAND_BUFFER_POOLS: RwLock<Option<BTreeMap<usize, Arc<PoolSync>>>> = RwLock::new(None);
+#[cfg(all(feature = "Implements", feature = "Multithreaded"))]
+pub(crate) fn register_command_buffers(pool: &Arc<PoolSync>, buffers: &[VkCommandBuffer])
+{
+	let mut m = COMMAND_BUFFER_POOLS.write().unwrap_or_else(|e| e.into_inner());
+	let m = m.get_or_insert_with(BTreeMap::new);
+	for &b in buffers { m.insert(b as usize, pool.clone()); }
+}
+#[cfg(all(feature = "Implements", feature = "Multithreaded"))]
+pub(crate) fn forget_command_buffers<I: IntoIterator<Item = VkCommandBuffer>>(buffers: I)
+{
+	if let Some(m) = COMMAND_BUFFER_POOLS.write().unwrap_or_else(|e| e.into_inner()).as_mut()
+	{
+		for b in buffers { m.remove(&(b as usize)); }
+	}
+}
+/// Forget every command buffer allocated from a pool being destroyed
+#[cfg(all(feature = "Implements", feature = "Multithreaded"))]
+pub(crate) fn forget_pool(pool: &Arc<PoolSync>)
+{
+	if let Some(m) = COMMAND_BUFFER_POOLS.write().unwrap_or_else(|e| e.into_inner()).as_mut()
+	{
+		m.retain(|_, p| !Arc::ptr_eq(p, pool));
+	}
+}
+/// Lock the pool which `buffer` was allocated from
+/// # Failures
+/// Returns `Error::UnknownCommandBuffer` if the command buffer has been freed or its pool has been destroyed
+#[cfg(all(feature = "Implements", feature = "Multithreaded"))]
+pub(crate) fn lock_command_buffer(buffer: VkCommandBuffer) -> ::Result<PoolGuard>
+{
+	let pool = COMMAND_BUFFER_POOLS.read().unwrap_or_else(|e| e.into_inner()).as_ref()
+		.and_then(|m| m.get(&(buffer as usize)).cloned()).ok_or(::Error::UnknownCommandBuffer)?;
+	Ok(PoolSync::lock(&pool))
+}
+#[cfg(all(feature = "Implements", not(feature = "Multithreaded")))]
+pub(crate) fn register_command_buffers(_: &Arc<PoolSync>, _: &[VkCommandBuffer]) {}
+#[cfg(all(feature = "Implements", not(feature = "Multithreaded")))]
+pub(crate) fn forget_command_buffers<I: IntoIterator<Item = VkCommandBuffer>>(_: I) {}
+#[cfg(all(feature = "Implements", not(feature = "Multithreaded")))]
+pub(crate) fn forget_pool(_: &Arc<PoolSync>) {}
+#[cfg(all(feature = "Implements", not(feature = "Multithreaded")))]
+pub(crate) fn lock_command_buffer(_: VkCommandBuffer) -> ::Result<PoolGuard> { Ok(PoolGuard(PhantomData)) }
@@ -0,0 +1,2806 @@
+//! In-process software Vulkan driver for GPU-less testing
+//!
+//! `FakeDriver` implements `ResolverInterface` without any Vulkan implementation.
+//! Every object created through it is tracked in memory, a single fake physical device with plausible
+//! properties/limits is reported, and misuse of the API(double destruction, use after destroy, objects leaked at
+//! `vkDestroyDevice`/`vkDestroyInstance`, invalid command buffer state transitions, ...) is recorded as `Misuse`
+//! instead of crashing the process.
+//!
+//! Transfer commands(`copy_buffer`, `fill_buffer`, `update_buffer` and copies between buffers and images)
+//! are executed on `vkQueueSubmit`, so the contents of host-visible memory can be checked after waiting a fence.
+//! `delay_completion` leaves the fences of a device unsignaled until they are waited, to test the code polling them.
+//! Images are stored tightly packed(each array layer contains its whole mip chain), except that the rows of linear images
+//! are padded to 64 bytes as the usual drivers do.
+//! With `VK_EXT_debug_report`, the driver returned by `install` also provides the debug report callback entry points,
+//! and `vkDebugReportMessageEXT` calls back the registered callbacks.
+//! `VK_EXT_debug_utils` messengers are provided in the same way, and the labels opened on queues and command buffers are tracked.
+//! The regions opened by `VK_EXT_debug_marker` are tracked as the labels of the command buffers.
+//! `VK_EXT_memory_budget` reports the memory allocated in the instance as the usage, and 3/4 of each heap as the budget.
+//! Color and depth/stencil attachment images prefer a dedicated allocation, and binding a dedicated memory to another resource is a misuse.
+//!
+//! ```rust,ignore
+//! let driver = bedrock::fakedrv::FakeDriver::install();
+//! let instance = br::InstanceBuilder::new("test", (0, 1, 0), "test", (0, 1, 0)).create().unwrap();
+//! // ...
+//! assert!(driver.misuses_of(instance.native_ptr()).is_empty());
+//! ```
+
+#![allow(non_snake_case)]
+
+use vk::*;
+use libc::{c_char, c_void, c_float, size_t};
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, MutexGuard, Once};
+use std::sync::atomic::{AtomicPtr, Ordering};
+use std::ffi::CStr;
+use std::mem::zeroed;
+use std::ptr::null_mut;
+use std::fmt;
+use vkresolve::{ResolverInterface, set_custom_resolver};
+use PixelFormat;
+
+#[cfg(feature = "VK_KHR_xlib_surface")] use x11::xlib::{Display, VisualID};
+#[cfg(feature = "VK_KHR_xcb_surface")] use xcb::ffi::{xcb_connection_t, xcb_visualid_t};
+
+const DEVICE_LOCAL_HEAP_SIZE: VkDeviceSize = 1 << 30;
+const HOST_HEAP_SIZE: VkDeviceSize = 256 << 20;
+const MAX_MEMORY_ALLOCATION_COUNT: u32 = 4096;
+const BUFFER_IMAGE_GRANULARITY: VkDeviceSize = 1024;
+const NON_COHERENT_ATOM_SIZE: VkDeviceSize = 64;
+const BUFFER_ALIGNMENT: VkDeviceSize = 256;
+const IMAGE_ALIGNMENT: VkDeviceSize = 512;
+const LINEAR_ROW_PITCH_ALIGNMENT: VkDeviceSize = 64;
+/// (flags, heap index) of the memory types
+const MEMORY_TYPES: [(VkMemoryPropertyFlags, u32); 4] = [
+	(VK_MEMORY_PROPERTY_DEVICE_LOCAL_BIT, 0),
+	(VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT | VK_MEMORY_PROPERTY_HOST_COHERENT_BIT, 1),
+	(VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT | VK_MEMORY_PROPERTY_HOST_CACHED_BIT, 1),
+	(VK_MEMORY_PROPERTY_DEVICE_LOCAL_BIT | VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT | VK_MEMORY_PROPERTY_HOST_COHERENT_BIT, 0)
+];
+/// (flags, queue count) of the queue families
+const QUEUE_FAMILIES: [(VkQueueFlags, u32); 3] = [
+	(VK_QUEUE_GRAPHICS_BIT | VK_QUEUE_COMPUTE_BIT | VK_QUEUE_TRANSFER_BIT | VK_QUEUE_SPARSE_BINDING_BIT, 4),
+	(VK_QUEUE_COMPUTE_BIT | VK_QUEUE_TRANSFER_BIT, 2),
+	(VK_QUEUE_TRANSFER_BIT, 1)
+];
+const INSTANCE_EXTENSIONS: &[(&str, u32)] = &[
+	("VK_KHR_surface", 25), ("VK_KHR_get_physical_device_properties2", 1), ("VK_EXT_debug_report", 9), ("VK_EXT_debug_utils", 1)
+];
+const DEVICE_EXTENSIONS: &[(&str, u32)] = &[
+	("VK_KHR_swapchain", 70), ("VK_KHR_maintenance1", 1), ("VK_EXT_debug_marker", 4), ("VK_EXT_memory_budget", 1),
+	("VK_KHR_get_memory_requirements2", 1), ("VK_KHR_dedicated_allocation", 3)
+];
+
+/// Kind of the objects tracked by `FakeDriver`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ObjectKind
+{
+	Instance, PhysicalDevice, Device, Queue, DeviceMemory, Buffer, BufferView, Image, ImageView,
+	ShaderModule, PipelineCache, PipelineLayout, Pipeline, Sampler, DescriptorSetLayout, DescriptorPool, DescriptorSet,
+	Framebuffer, RenderPass, CommandPool, CommandBuffer, Fence, Semaphore, Event, QueryPool,
+	SamplerYcbcrConversion, DescriptorUpdateTemplate, Surface, Swapchain, DisplayMode, DebugReportCallback, DebugUtilsMessenger
+}
+#[cfg(feature = "VK_EXT_debug_marker")]
+impl ObjectKind
+{
+	/// The object type passed to the debug marker commands for this kind
+	fn debug_report_type(self) -> VkDebugReportObjectTypeEXT
+	{
+		match self
+		{
+			ObjectKind::Instance => VK_DEBUG_REPORT_OBJECT_TYPE_INSTANCE_EXT,
+			ObjectKind::PhysicalDevice => VK_DEBUG_REPORT_OBJECT_TYPE_PHYSICAL_DEVICE_EXT,
+			ObjectKind::Device => VK_DEBUG_REPORT_OBJECT_TYPE_DEVICE_EXT,
+			ObjectKind::Queue => VK_DEBUG_REPORT_OBJECT_TYPE_QUEUE_EXT,
+			ObjectKind::DeviceMemory => VK_DEBUG_REPORT_OBJECT_TYPE_DEVICE_MEMORY_EXT,
+			ObjectKind::Buffer => VK_DEBUG_REPORT_OBJECT_TYPE_BUFFER_EXT,
+			ObjectKind::BufferView => VK_DEBUG_REPORT_OBJECT_TYPE_BUFFER_VIEW_EXT,
+			ObjectKind::Image => VK_DEBUG_REPORT_OBJECT_TYPE_IMAGE_EXT,
+			ObjectKind::ImageView => VK_DEBUG_REPORT_OBJECT_TYPE_IMAGE_VIEW_EXT,
+			ObjectKind::ShaderModule => VK_DEBUG_REPORT_OBJECT_TYPE_SHADER_MODULE_EXT,
+			ObjectKind::PipelineCache => VK_DEBUG_REPORT_OBJECT_TYPE_PIPELINE_CACHE_EXT,
+			ObjectKind::PipelineLayout => VK_DEBUG_REPORT_OBJECT_TYPE_PIPELINE_LAYOUT_EXT,
+			ObjectKind::Pipeline => VK_DEBUG_REPORT_OBJECT_TYPE_PIPELINE_EXT,
+			ObjectKind::Sampler => VK_DEBUG_REPORT_OBJECT_TYPE_SAMPLER_EXT,
+			ObjectKind::DescriptorSetLayout => VK_DEBUG_REPORT_OBJECT_TYPE_DESCRIPTOR_SET_LAYOUT_EXT,
+			ObjectKind::DescriptorPool => VK_DEBUG_REPORT_OBJECT_TYPE_DESCRIPTOR_POOL_EXT,
+			ObjectKind::DescriptorSet => VK_DEBUG_REPORT_OBJECT_TYPE_DESCRIPTOR_SET_EXT,
+			ObjectKind::Framebuffer => VK_DEBUG_REPORT_OBJECT_TYPE_FRAMEBUFFER_EXT,
+			ObjectKind::RenderPass => VK_DEBUG_REPORT_OBJECT_TYPE_RENDER_PASS_EXT,
+			ObjectKind::CommandPool => VK_DEBUG_REPORT_OBJECT_TYPE_COMMAND_POOL_EXT,
+			ObjectKind::CommandBuffer => VK_DEBUG_REPORT_OBJECT_TYPE_COMMAND_BUFFER_EXT,
+			ObjectKind::Fence => VK_DEBUG_REPORT_OBJECT_TYPE_FENCE_EXT,
+			ObjectKind::Semaphore => VK_DEBUG_REPORT_OBJECT_TYPE_SEMAPHORE_EXT,
+			ObjectKind::Event => VK_DEBUG_REPORT_OBJECT_TYPE_EVENT_EXT,
+			ObjectKind::QueryPool => VK_DEBUG_REPORT_OBJECT_TYPE_QUERY_POOL_EXT,
+			ObjectKind::SamplerYcbcrConversion => VK_DEBUG_REPORT_OBJECT_TYPE_SAMPLE_YCBCR_CONVERSION_KHR_EXT,
+			ObjectKind::DescriptorUpdateTemplate => VK_DEBUG_REPORT_OBJECT_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_KHR_EXT,
+			ObjectKind::Surface => VK_DEBUG_REPORT_OBJECT_TYPE_SURFACE_KHR_EXT,
+			ObjectKind::Swapchain => VK_DEBUG_REPORT_OBJECT_TYPE_SWAPCHAIN_KHR_EXT,
+			ObjectKind::DisplayMode => VK_DEBUG_REPORT_OBJECT_TYPE_DISPLAY_MODE_KHR_EXT,
+			ObjectKind::DebugReportCallback => VK_DEBUG_REPORT_OBJECT_TYPE_DEBUG_REPORT_CALLBACK_EXT_EXT,
+			ObjectKind::DebugUtilsMessenger => VK_DEBUG_REPORT_OBJECT_TYPE_UNKNOWN_EXT
+		}
+	}
+}
+#[cfg(feature = "VK_EXT_debug_utils")]
+impl ObjectKind
+{
+	/// The object type passed to the debug utils commands for this kind
+	fn object_type(self) -> VkObjectType
+	{
+		match self
+		{
+			ObjectKind::Instance => VK_OBJECT_TYPE_INSTANCE,
+			ObjectKind::PhysicalDevice => VK_OBJECT_TYPE_PHYSICAL_DEVICE,
+			ObjectKind::Device => VK_OBJECT_TYPE_DEVICE,
+			ObjectKind::Queue => VK_OBJECT_TYPE_QUEUE,
+			ObjectKind::DeviceMemory => VK_OBJECT_TYPE_DEVICE_MEMORY,
+			ObjectKind::Buffer => VK_OBJECT_TYPE_BUFFER,
+			ObjectKind::BufferView => VK_OBJECT_TYPE_BUFFER_VIEW,
+			ObjectKind::Image => VK_OBJECT_TYPE_IMAGE,
+			ObjectKind::ImageView => VK_OBJECT_TYPE_IMAGE_VIEW,
+			ObjectKind::ShaderModule => VK_OBJECT_TYPE_SHADER_MODULE,
+			ObjectKind::PipelineCache => VK_OBJECT_TYPE_PIPELINE_CACHE,
+			ObjectKind::PipelineLayout => VK_OBJECT_TYPE_PIPELINE_LAYOUT,
+			ObjectKind::Pipeline => VK_OBJECT_TYPE_PIPELINE,
+			ObjectKind::Sampler => VK_OBJECT_TYPE_SAMPLER,
+			ObjectKind::DescriptorSetLayout => VK_OBJECT_TYPE_DESCIPTOR_SET_LAYOUT,
+			ObjectKind::DescriptorPool => VK_OBJECT_TYPE_DESCRIPTOR_POOL,
+			ObjectKind::DescriptorSet => VK_OBJECT_TYPE_DESCRIPTOR_SET,
+			ObjectKind::Framebuffer => VK_OBJECT_TYPE_FRAMEBUFFER,
+			ObjectKind::RenderPass => VK_OBJECT_TYPE_RENDER_PASS,
+			ObjectKind::CommandPool => VK_OBJECT_TYPE_COMMAND_POOL,
+			ObjectKind::CommandBuffer => VK_OBJECT_TYPE_COMMAND_BUFFER,
+			ObjectKind::Fence => VK_OBJECT_TYPE_FENCE,
+			ObjectKind::Semaphore => VK_OBJECT_TYPE_SEMAPHORE,
+			ObjectKind::Event => VK_OBJECT_TYPE_EVENT,
+			ObjectKind::QueryPool => VK_OBJECT_TYPE_QUERY_POOL,
+			ObjectKind::SamplerYcbcrConversion => VK_OBJECT_TYPE_SAMPLE_YCBCR_CONVERSION,
+			ObjectKind::DescriptorUpdateTemplate => VK_OBJECT_TYPE_DESCRIPTOR_UPDATE_TEMPLATE,
+			ObjectKind::Surface => VK_OBJECT_TYPE_SURFACE_KHR,
+			ObjectKind::Swapchain => VK_OBJECT_TYPE_SWAPCHAIN_KHR,
+			ObjectKind::DisplayMode => VK_OBJECT_TYPE_DISPLAY_MODE_KHR,
+			ObjectKind::DebugReportCallback => VK_OBJECT_TYPE_DEBUG_REPORT_CALLBACK_EXT,
+			ObjectKind::DebugUtilsMessenger => VK_OBJECT_TYPE_DEBUG_UTILS_MESSENGER_EXT
+		}
+	}
+}
+
+/// An API misuse detected by `FakeDriver`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Misuse
+{
+	/// An object was destroyed(or freed) twice
+	DoubleFree { kind: ObjectKind, handle: u64, call: &'static str },
+	/// An object was used after it has been destroyed
+	UseAfterDestroy { kind: ObjectKind, handle: u64, call: &'static str },
+	/// A handle which is not created by this driver, or is not an object of the expected kind
+	UnknownHandle { kind: ObjectKind, handle: u64, call: &'static str },
+	/// An object was still alive when its parent was destroyed
+	Leaked { kind: ObjectKind, handle: u64, parent: ObjectKind },
+	/// Other invalid usage
+	InvalidUsage { call: &'static str, message: String }
+}
+impl fmt::Display for Misuse
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result
+	{
+		match *self
+		{
+			Misuse::DoubleFree { kind, handle, call } => write!(fmt, "{}: {:?}({:#x}) is already destroyed", call, kind, handle),
+			Misuse::UseAfterDestroy { kind, handle, call } => write!(fmt, "{}: {:?}({:#x}) is used after destroyed", call, kind, handle),
+			Misuse::UnknownHandle { kind, handle, call } => write!(fmt, "{}: {:#x} is not a valid {:?} handle", call, handle, kind),
+			Misuse::Leaked { kind, handle, parent } => write!(fmt, "{:?}({:#x}) is leaked at destruction of the parent {:?}", kind, handle, parent),
+			Misuse::InvalidUsage { call, ref message } => write!(fmt, "{}: {}", call, message)
+		}
+	}
+}
+
+/// Conversion between Vulkan handles and object ids
+trait FakeHandle: Copy
+{
+	fn from_id(id: u64) -> Self;
+	fn id(self) -> u64;
+}
+impl<T> FakeHandle for *mut T
+{
+	fn from_id(id: u64) -> Self { id as usize as _ }
+	fn id(self) -> u64 { self as usize as _ }
+}
+impl FakeHandle for u64
+{
+	fn from_id(id: u64) -> Self { id }
+	fn id(self) -> u64 { self }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommandBufferState { Initial, Recording, Executable, Invalid }
+/// Commands which have effects on the memory contents
+#[derive(Clone)]
+enum Command
+{
+	CopyBuffer { src: u64, dst: u64, regions: Vec<VkBufferCopy> },
+	FillBuffer { dst: u64, offset: VkDeviceSize, size: VkDeviceSize, data: u32 },
+	UpdateBuffer { dst: u64, offset: VkDeviceSize, data: Vec<u8> },
+	CopyBufferToImage { src: u64, dst: u64, regions: Vec<VkBufferImageCopy> },
+	CopyImageToBuffer { src: u64, dst: u64, regions: Vec<VkBufferImageCopy> },
+	ExecuteCommands(Vec<u64>)
+}
+struct ImageShape { format: VkFormat, extent: VkExtent3D, mip_levels: u32, array_layers: u32, tiling: VkImageTiling, usage: VkImageUsageFlags }
+enum ObjectData
+{
+	None,
+	Instance { physical_device: u64, api_version: u32 },
+	Device { queues: Vec<(u32, u32, u64)> },
+	/// `dedicated`: the resource given by `VkMemoryDedicatedAllocateInfo`
+	Memory { size: VkDeviceSize, type_index: u32, mapped: bool, contents: Option<Box<[u8]>>, dedicated: Option<u64> },
+	Buffer { size: VkDeviceSize, binding: Option<(u64, VkDeviceSize)> },
+	Image { shape: ImageShape, binding: Option<(u64, VkDeviceSize)> },
+	Fence(bool), Semaphore(bool), Event(bool),
+	CommandPool { flags: VkCommandPoolCreateFlags },
+	CommandBuffer { level: VkCommandBufferLevel, state: CommandBufferState, one_time: bool, commands: Vec<Command> },
+	#[cfg(feature = "VK_KHR_swapchain")] Swapchain { images: Vec<u64>, next: usize },
+	#[cfg(feature = "VK_EXT_debug_report")] DebugReportCallback { flags: VkDebugReportFlagsEXT, callback: PFN_vkDebugReportCallbackEXT, user_data: usize },
+	#[cfg(feature = "VK_EXT_debug_utils")] DebugUtilsMessenger
+	{
+		severity: VkDebugUtilsMessageSeverityFlagsEXT, types: VkDebugUtilsMessageTypeFlagsEXT,
+		callback: PFN_vkDebugUtilsMessengerCallbackEXT, user_data: usize
+	}
+}
+struct Object { kind: ObjectKind, parent: u64, instance: u64, alive: bool, name: Option<String>, data: ObjectData }
+
+struct State
+{
+	next_id: u64, objects: HashMap<u64, Object>, misuses: Vec<(u64, Misuse)>, panic_on_misuse: bool,
+	/// Entry points reported as missing by `provides`
+	withheld: Vec<String>,
+	/// Devices whose submissions complete only when waited, and the fences of such submissions not waited yet
+	delayed_devices: Vec<u64>, delayed_fences: Vec<u64>,
+	/// Labels opened on the queues and the command buffers
+	#[cfg(any(feature = "VK_EXT_debug_utils", feature = "VK_EXT_debug_marker"))] labels: HashMap<u64, Vec<String>>
+}
+
+/// The driver installed by `FakeDriver::install`
+static INSTALLED: AtomicPtr<FakeDriver> = AtomicPtr::new(::std::ptr::null_mut());
+#[cfg(any(feature = "VK_EXT_debug_report", feature = "VK_EXT_debug_utils"))]
+fn installed() -> FakeDriver
+{
+	let p = INSTALLED.load(Ordering::Acquire);
+	if p.is_null() { panic!("FakeDriver is not installed"); }
+	unsafe { (*p).clone() }
+}
+
+/// Software implementation of `ResolverInterface`. Clones share the same state
+#[derive(Clone)]
+pub struct FakeDriver(Arc<Mutex<State>>);
+impl FakeDriver
+{
+	/// Create a fresh driver. Use `set_custom_resolver` to route the calls to it, or `install` to do both at once
+	pub fn new() -> Self
+	{
+		FakeDriver(Arc::new(Mutex::new(State
+		{
+			next_id: 0x1000, objects: HashMap::new(), misuses: Vec::new(), panic_on_misuse: false, withheld: Vec::new(),
+			delayed_devices: Vec::new(), delayed_fences: Vec::new(),
+			#[cfg(any(feature = "VK_EXT_debug_utils", feature = "VK_EXT_debug_marker"))] labels: HashMap::new()
+		})))
+	}
+	/// Install a process-wide driver as the custom resolver(only once), and return the installed one
+	/// # Panics
+	/// Panics if another resolver has already been installed through `set_custom_resolver`
+	pub fn install() -> Self
+	{
+		static INSTALL: Once = Once::new();
+		INSTALL.call_once(||
+		{
+			let d = FakeDriver::new();
+			if set_custom_resolver(Box::new(d.clone())) { INSTALLED.store(Box::into_raw(Box::new(d)), Ordering::Release); }
+		});
+		let p = INSTALLED.load(Ordering::Acquire);
+		if p.is_null() { panic!("Another resolver has already been installed"); }
+		unsafe { (*p).clone() }
+	}
+	/// Create a surface on `instance` as a window system extension would, so that swapchains can be tested without one
+	#[cfg(feature = "VK_KHR_surface")]
+	pub fn headless_surface(&self, instance: VkInstance) -> VkSurfaceKHR
+	{
+		let mut h = VK_NULL_HANDLE as _;
+		unsafe { self.state().create_surface("vkCreateHeadlessSurfaceEXT", instance.id(), &mut h) };
+		h
+	}
+	/// Panic as soon as a misuse is detected, instead of recording it
+	pub fn set_panic_on_misuse(&self, enable: bool) { self.state().panic_on_misuse = enable; }
+	/// Report the entry point named `name`(e.g. `"vkTrimCommandPool"`) as missing, or provide it again
+	pub fn withhold(&self, name: &str, withheld: bool)
+	{
+		let mut s = self.state();
+		s.withheld.retain(|n| n != name);
+		if withheld { s.withheld.push(name.to_owned()); }
+	}
+
+	/// Keep the fences of the submissions to the queues of `device` unsignaled until they are waited,
+	/// as if the device were still executing them(the commands are executed on submit anyway).
+	/// Disabling it completes the delayed submissions of `device`
+	pub fn delay_completion(&self, device: VkDevice, delayed: bool)
+	{
+		let mut s = self.state();
+		s.delayed_devices.retain(|&d| d != device.id());
+		if delayed { s.delayed_devices.push(device.id()); return; }
+		let (done, rest): (Vec<u64>, Vec<u64>) = s.delayed_fences.iter().partition(|f| s.objects[f].parent == device.id());
+		s.delayed_fences = rest;
+		for f in done { s.signal_fence("vkQueueSubmit", f); }
+	}
+
+	/// All misuses recorded so far
+	pub fn misuses(&self) -> Vec<Misuse> { self.state().misuses.iter().map(|(_, m)| m.clone()).collect() }
+	/// Misuses recorded on the objects under the instance
+	pub fn misuses_of(&self, instance: VkInstance) -> Vec<Misuse>
+	{
+		self.state().misuses.iter().filter(|&&(i, _)| i == instance.id()).map(|(_, m)| m.clone()).collect()
+	}
+	/// Take all misuses recorded so far
+	pub fn take_misuses(&self) -> Vec<Misuse> { self.state().misuses.drain(..).map(|(_, m)| m).collect() }
+	/// Number of alive objects of the kind
+	pub fn live_objects(&self, kind: ObjectKind) -> usize
+	{
+		self.state().objects.values().filter(|o| o.alive && o.kind == kind).count()
+	}
+	/// The name given to the object through `vkDebugMarkerSetObjectNameEXT` or `vkSetDebugUtilsObjectNameEXT`
+	pub fn object_name(&self, handle: u64) -> Option<String>
+	{
+		self.state().objects.get(&handle).and_then(|o| o.name.clone())
+	}
+	/// Labels(or debug marker regions) opened on the queue or the command buffer and not closed yet, outermost first
+	#[cfg(any(feature = "VK_EXT_debug_utils", feature = "VK_EXT_debug_marker"))]
+	pub fn open_labels(&self, handle: u64) -> Vec<String>
+	{
+		self.state().labels.get(&handle).cloned().unwrap_or_default()
+	}
+
+	fn state(&self) -> MutexGuard<'_, State> { self.0.lock().unwrap_or_else(|e| e.into_inner()) }
+}
+impl Default for FakeDriver { fn default() -> Self { Self::new() } }
+
+/// Install the driver and create an instance and a device with a queue of the family 0, for the tests of the other modules.
+/// `instance` and `device` configure the builders(extensions, memory tracking, ...) before the creation
+#[cfg(test)]
+pub(crate) fn test_device<I, D>(instance: I, device: D) -> (FakeDriver, ::Instance, ::Device)
+	where I: FnOnce(&mut ::InstanceBuilder), D: FnOnce(&mut ::DeviceBuilder)
+{
+	let driver = FakeDriver::install();
+	let mut ib = ::InstanceBuilder::new("t", (0, 1, 0), "t", (0, 1, 0));
+	instance(&mut ib);
+	let i = ib.create().unwrap();
+	let pd = i.iter_physical_devices().unwrap().next().unwrap();
+	let mut db = ::DeviceBuilder::new(&pd);
+	db.add_queue(::DeviceQueueCreateInfo(0, vec![0.0]));
+	device(&mut db);
+	let d = db.create().unwrap();
+	(driver, i, d)
+}
+/// `test_device` without any extra configuration
+#[cfg(test)]
+pub(crate) fn default_test_device() -> (FakeDriver, ::Instance, ::Device) { test_device(|_| (), |_| ()) }
+/// Fail the test with the descriptions of the misuses recorded for `instance`, if any
+#[cfg(test)]
+pub(crate) fn assert_no_misuse(driver: &FakeDriver, instance: &::Instance)
+{
+	let ms = driver.misuses_of(::VkHandle::native_ptr(instance));
+	assert!(ms.is_empty(), "{:?}", ms.iter().map(|m| m.to_string()).collect::<Vec<_>>());
+}
+
+impl State
+{
+	fn report(&mut self, instance: u64, m: Misuse)
+	{
+		if self.panic_on_misuse { panic!("Vulkan API misuse: {}", m); }
+		self.misuses.push((instance, m));
+	}
+	fn invalid(&mut self, instance: u64, call: &'static str, message: String)
+	{
+		self.report(instance, Misuse::InvalidUsage { call, message });
+	}
+
+	fn create(&mut self, kind: ObjectKind, parent: u64, data: ObjectData) -> u64
+	{
+		let id = self.next_id; self.next_id += 0x10;
+		let instance = if kind == ObjectKind::Instance { id } else { self.objects.get(&parent).map_or(0, |o| o.instance) };
+		self.objects.insert(id, Object { kind, parent, instance, alive: true, name: None, data });
+		id
+	}
+	/// Check whether the handle refers an alive object of the kind
+	fn check(&mut self, call: &'static str, kind: ObjectKind, handle: u64) -> bool
+	{
+		let (instance, m) = match self.objects.get(&handle)
+		{
+			Some(o) if o.kind == kind && o.alive => return true,
+			Some(o) if o.kind == kind => (o.instance, Misuse::UseAfterDestroy { kind, handle, call }),
+			_ => (0, Misuse::UnknownHandle { kind, handle, call })
+		};
+		self.report(instance, m); false
+	}
+	/// Check an optional handle(null handles are valid)
+	fn check_opt(&mut self, call: &'static str, kind: ObjectKind, handle: u64) -> bool
+	{
+		handle == 0 || self.check(call, kind, handle)
+	}
+	/// Check the target object of the debug marker commands
+	#[cfg(feature = "VK_EXT_debug_marker")]
+	fn check_debug_marker_object(&mut self, call: &'static str, device: u64, object_type: VkDebugReportObjectTypeEXT, object: u64) -> bool
+	{
+		if !self.check(call, ObjectKind::Device, device) { return false; }
+		let message = match self.objects.get(&object)
+		{
+			None => format!("object {:#x} is not a valid handle", object),
+			Some(o) if o.kind.debug_report_type() != object_type =>
+				format!("objectType {} does not match the type of {:?}({:#x})", object_type, o.kind, object),
+			Some(o) if !o.alive => format!("{:?}({:#x}) has already been destroyed", o.kind, object),
+			_ => return true
+		};
+		let instance = self.instance_of(device);
+		self.invalid(instance, call, message); false
+	}
+	/// Check the target object of the debug utils commands
+	#[cfg(feature = "VK_EXT_debug_utils")]
+	fn check_debug_utils_object(&mut self, call: &'static str, device: u64, object_type: VkObjectType, object: u64) -> bool
+	{
+		if !self.check(call, ObjectKind::Device, device) { return false; }
+		let message = match self.objects.get(&object)
+		{
+			None => format!("objectHandle {:#x} is not a valid handle", object),
+			Some(o) if o.kind.object_type() != object_type =>
+				format!("objectType {} does not match the type of {:?}({:#x})", object_type, o.kind, object),
+			Some(o) if !o.alive => format!("{:?}({:#x}) has already been destroyed", o.kind, object),
+			_ => return true
+		};
+		let instance = self.instance_of(device);
+		self.invalid(instance, call, message); false
+	}
+	/// Close the innermost label on the queue or the command buffer. Returns false if no label is open
+	#[cfg(any(feature = "VK_EXT_debug_utils", feature = "VK_EXT_debug_marker"))]
+	fn end_label(&mut self, handle: u64) -> bool
+	{
+		self.labels.get_mut(&handle).and_then(|l| l.pop()).is_some()
+	}
+	fn instance_of(&self, handle: u64) -> u64 { self.objects.get(&handle).map_or(0, |o| o.instance) }
+	/// Report `call` of the core API `version` made for an object of an instance created for an older version
+	fn require_version(&mut self, call: &'static str, handle: u64, version: u32)
+	{
+		let instance = self.instance_of(handle);
+		let api_version = match self.objects.get(&instance).map(|o| &o.data)
+		{
+			Some(&ObjectData::Instance { api_version, .. }) => api_version, _ => return
+		};
+		if api_version < version
+		{
+			self.invalid(instance, call, format!("requires Vulkan {}.{} but the instance is created for {}.{}",
+				version >> 22, (version >> 12) & 0x3ff, api_version >> 22, (api_version >> 12) & 0x3ff));
+		}
+	}
+	fn data(&mut self, handle: u64) -> &mut ObjectData { &mut self.objects.get_mut(&handle).expect("untracked object").data }
+
+	fn create_child(&mut self, call: &'static str, parent_kind: ObjectKind, parent: u64, kind: ObjectKind, data: ObjectData)
+		-> Result<u64, VkResult>
+	{
+		if !self.check(call, parent_kind, parent) { return Err(VK_ERROR_DEVICE_LOST); }
+		Ok(self.create(kind, parent, data))
+	}
+	fn destroy_child(&mut self, call: &'static str, parent_kind: ObjectKind, parent: u64, kind: ObjectKind, handle: u64)
+	{
+		if !self.check(call, parent_kind, parent) || handle == 0 { return; }
+		let (instance, m) = match self.objects.get(&handle)
+		{
+			Some(o) if o.kind == kind && o.alive && o.parent == parent => { self.kill(handle); return; },
+			Some(o) if o.kind == kind && o.alive =>
+				(o.instance, Misuse::InvalidUsage { call, message: format!("{:?}({:#x}) is not created from the {:?}", kind, handle, parent_kind) }),
+			Some(o) if o.kind == kind => (o.instance, Misuse::DoubleFree { kind, handle, call }),
+			_ => (0, Misuse::UnknownHandle { kind, handle, call })
+		};
+		self.report(instance, m);
+	}
+	/// Mark the object and all its descendants as destroyed
+	fn kill(&mut self, handle: u64)
+	{
+		let mut stack = vec![handle];
+		while let Some(h) = stack.pop()
+		{
+			if let Some(o) = self.objects.get_mut(&h) { o.alive = false; if let ObjectData::Memory { ref mut contents, .. } = o.data { *contents = None; } }
+			stack.extend(self.objects.iter().filter(|&(_, o)| o.alive && o.parent == h).map(|(&k, _)| k));
+		}
+	}
+	/// Report alive children except for the implicitly owned kinds
+	fn report_leaks(&mut self, parent: u64, owned: &[ObjectKind])
+	{
+		let parent_kind = self.objects[&parent].kind;
+		let mut leaks = self.objects.iter().filter(|&(_, o)| o.alive && o.parent == parent && !owned.contains(&o.kind))
+			.map(|(&h, o)| (o.instance, o.kind, h)).collect::<Vec<_>>();
+		leaks.sort_by_key(|&(_, _, h)| h);
+		for (instance, kind, handle) in leaks { self.report(instance, Misuse::Leaked { kind, handle, parent: parent_kind }); }
+	}
+
+	fn memory_contents(&mut self, memory: u64) -> &mut [u8]
+	{
+		match *self.data(memory)
+		{
+			ObjectData::Memory { size, ref mut contents, .. } =>
+				contents.get_or_insert_with(|| vec![0u8; size as usize].into_boxed_slice()),
+			_ => unreachable!()
+		}
+	}
+	/// Resolve a range in a bound buffer to (memory, offset)
+	fn buffer_range(&mut self, call: &'static str, buffer: u64, offset: VkDeviceSize, size: VkDeviceSize) -> Option<(u64, VkDeviceSize)>
+	{
+		let instance = self.instance_of(buffer);
+		let (bsize, binding) = match *self.data(buffer) { ObjectData::Buffer { size, binding } => (size, binding), _ => unreachable!() };
+		if offset.checked_add(size).map_or(true, |e| e > bsize)
+		{
+			self.invalid(instance, call, format!("range {}..+{} is out of the buffer({} bytes)", offset, size, bsize));
+			return None;
+		}
+		match binding
+		{
+			Some((m, o)) if self.objects[&m].alive => Some((m, o + offset)),
+			_ => { self.invalid(instance, call, format!("Buffer({:#x}) is not bound to an alive memory", buffer)); None }
+		}
+	}
+	fn image_binding(&mut self, call: &'static str, image: u64) -> Option<(u64, VkDeviceSize)>
+	{
+		let instance = self.instance_of(image);
+		let binding = match *self.data(image) { ObjectData::Image { binding, .. } => binding, _ => unreachable!() };
+		match binding
+		{
+			Some((m, o)) if self.objects[&m].alive => Some((m, o)),
+			_ => { self.invalid(instance, call, format!("Image({:#x}) is not bound to an alive memory", image)); None }
+		}
+	}
+	fn image_shape(&self, image: u64) -> &ImageShape
+	{
+		match self.objects[&image].data { ObjectData::Image { ref shape, .. } => shape, _ => unreachable!() }
+	}
+
+	fn recording(&mut self, call: &'static str, cb: VkCommandBuffer) -> bool
+	{
+		if !self.check(call, ObjectKind::CommandBuffer, cb.id()) { return false; }
+		let instance = self.instance_of(cb.id());
+		match *self.data(cb.id())
+		{
+			ObjectData::CommandBuffer { state: CommandBufferState::Recording, .. } => true,
+			ObjectData::CommandBuffer { state, .. } =>
+			{
+				self.invalid(instance, call, format!("CommandBuffer({:#x}) is not in the recording state({:?})", cb.id(), state));
+				false
+			},
+			_ => unreachable!()
+		}
+	}
+	fn push_command(&mut self, cb: VkCommandBuffer, c: Command)
+	{
+		if let ObjectData::CommandBuffer { ref mut commands, .. } = *self.data(cb.id()) { commands.push(c); }
+	}
+	/// Execute the recorded commands
+	fn execute(&mut self, cb: u64)
+	{
+		let commands = match *self.data(cb) { ObjectData::CommandBuffer { ref commands, .. } => commands.clone(), _ => unreachable!() };
+		for c in commands
+		{
+			match c
+			{
+				Command::CopyBuffer { src, dst, regions } => for r in regions
+				{
+					if !self.check("vkQueueSubmit", ObjectKind::Buffer, src) || !self.check("vkQueueSubmit", ObjectKind::Buffer, dst) { break; }
+					let s = self.buffer_range("vkCmdCopyBuffer", src, r.srcOffset, r.size);
+					let d = self.buffer_range("vkCmdCopyBuffer", dst, r.dstOffset, r.size);
+					if let (Some((sm, so)), Some((dm, doff))) = (s, d)
+					{
+						let bytes = self.memory_contents(sm)[so as usize .. (so + r.size) as usize].to_vec();
+						self.memory_contents(dm)[doff as usize .. (doff + r.size) as usize].copy_from_slice(&bytes);
+					}
+				},
+				Command::FillBuffer { dst, offset, size, data } =>
+				{
+					if !self.check("vkQueueSubmit", ObjectKind::Buffer, dst) { continue; }
+					let bsize = match *self.data(dst) { ObjectData::Buffer { size, .. } => size, _ => unreachable!() };
+					let size = if size == VK_WHOLE_SIZE { bsize.saturating_sub(offset) & !3 } else { size };
+					if let Some((m, o)) = self.buffer_range("vkCmdFillBuffer", dst, offset, size)
+					{
+						for (n, b) in self.memory_contents(m)[o as usize .. (o + size) as usize].iter_mut().enumerate()
+						{
+							*b = (data >> ((n & 3) * 8)) as u8;
+						}
+					}
+				},
+				Command::UpdateBuffer { dst, offset, data } =>
+				{
+					if !self.check("vkQueueSubmit", ObjectKind::Buffer, dst) { continue; }
+					if let Some((m, o)) = self.buffer_range("vkCmdUpdateBuffer", dst, offset, data.len() as _)
+					{
+						self.memory_contents(m)[o as usize .. o as usize + data.len()].copy_from_slice(&data);
+					}
+				},
+				Command::CopyBufferToImage { src, dst, regions } =>
+				{
+					if !self.check("vkQueueSubmit", ObjectKind::Buffer, src) || !self.check("vkQueueSubmit", ObjectKind::Image, dst) { continue; }
+					for r in &regions { self.copy_buffer_image(src, dst, r, true); }
+				},
+				Command::CopyImageToBuffer { src, dst, regions } =>
+				{
+					if !self.check("vkQueueSubmit", ObjectKind::Image, src) || !self.check("vkQueueSubmit", ObjectKind::Buffer, dst) { continue; }
+					for r in &regions { self.copy_buffer_image(dst, src, r, false); }
+				},
+				Command::ExecuteCommands(secondaries) => for s in secondaries
+				{
+					if self.check("vkQueueSubmit", ObjectKind::CommandBuffer, s) { self.execute(s); }
+				}
+			}
+		}
+	}
+	/// Copy between a buffer and an image(`to_image` selects the direction)
+	fn copy_buffer_image(&mut self, buffer: u64, image: u64, r: &VkBufferImageCopy, to_image: bool)
+	{
+		let call = if to_image { "vkCmdCopyBufferToImage" } else { "vkCmdCopyImageToBuffer" };
+		let instance = self.instance_of(image);
+		let (format, extent, layers) = { let s = self.image_shape(image); (s.format, s.extent.clone(), s.array_layers) };
+		let texel = texel_size(format);
+		let sub = &r.imageSubresource;
+		let (w, h, d) = mip_extent(&extent, sub.mipLevel);
+		if sub.baseArrayLayer + sub.layerCount > layers || r.imageOffset.x < 0 || r.imageOffset.y < 0 || r.imageOffset.z < 0
+			|| r.imageOffset.x as u32 + r.imageExtent.width > w || r.imageOffset.y as u32 + r.imageExtent.height > h
+			|| r.imageOffset.z as u32 + r.imageExtent.depth > d
+		{
+			self.invalid(instance, call, format!("copy region is out of the Image({:#x})", image));
+			return;
+		}
+		if r.bufferOffset % texel != 0 || r.bufferOffset % 4 != 0
+		{
+			self.invalid(instance, call, format!("bufferOffset {} is not a multiple of the texel size({} bytes) and 4", r.bufferOffset, texel));
+			return;
+		}
+		let row_length = if r.bufferRowLength == 0 { r.imageExtent.width } else { r.bufferRowLength } as VkDeviceSize;
+		let image_height = if r.bufferImageHeight == 0 { r.imageExtent.height } else { r.bufferImageHeight } as VkDeviceSize;
+		let slices = sub.layerCount as VkDeviceSize * r.imageExtent.depth as VkDeviceSize;
+		let row_bytes = r.imageExtent.width as VkDeviceSize * texel;
+		let buffer_bytes = if slices == 0 || r.imageExtent.height == 0 { 0 }
+			else { ((slices - 1) * image_height + r.imageExtent.height as VkDeviceSize - 1) * row_length * texel + row_bytes };
+		let (bm, bo) = match self.buffer_range(call, buffer, r.bufferOffset, buffer_bytes) { Some(v) => v, None => return };
+		let (im, io) = match self.image_binding(call, image) { Some(v) => v, None => return };
+		for l in 0 .. sub.layerCount
+		{
+			let (sub_offset, row_pitch, depth_pitch) = subresource_placement(self.image_shape(image), sub.baseArrayLayer + l, sub.mipLevel);
+			for z in 0 .. r.imageExtent.depth as VkDeviceSize
+			{
+				for y in 0 .. r.imageExtent.height as VkDeviceSize
+				{
+					let bpos = bo + (((l as VkDeviceSize * r.imageExtent.depth as VkDeviceSize + z) * image_height + y) * row_length) * texel;
+					let ipos = io + sub_offset + (r.imageOffset.z as VkDeviceSize + z) * depth_pitch
+						+ (r.imageOffset.y as VkDeviceSize + y) * row_pitch + r.imageOffset.x as VkDeviceSize * texel;
+					let (sm, so, dm, dof) = if to_image { (bm, bpos, im, ipos) } else { (im, ipos, bm, bpos) };
+					if (so + row_bytes) as usize > self.memory_contents(sm).len() || (dof + row_bytes) as usize > self.memory_contents(dm).len()
+					{
+						self.invalid(instance, call, format!("Image({:#x}) exceeds the bound memory", image));
+						return;
+					}
+					let bytes = self.memory_contents(sm)[so as usize .. (so + row_bytes) as usize].to_vec();
+					self.memory_contents(dm)[dof as usize .. (dof + row_bytes) as usize].copy_from_slice(&bytes);
+				}
+			}
+		}
+	}
+
+	fn allocate_memory(&mut self, device: u64, size: VkDeviceSize, type_index: u32, dedicated: Option<(ObjectKind, u64)>) -> Result<u64, VkResult>
+	{
+		if !self.check("vkAllocateMemory", ObjectKind::Device, device) { return Err(VK_ERROR_DEVICE_LOST); }
+		let instance = self.instance_of(device);
+		let heap = match MEMORY_TYPES.get(type_index as usize)
+		{
+			Some(&(_, heap)) => heap,
+			None =>
+			{
+				self.invalid(instance, "vkAllocateMemory", format!("memory type {} does not exist", type_index));
+				return Err(VK_ERROR_OUT_OF_DEVICE_MEMORY);
+			}
+		};
+		let (mut count, mut used) = (0, 0);
+		for o in self.objects.values().filter(|o| o.alive && o.kind == ObjectKind::DeviceMemory && o.parent == device)
+		{
+			count += 1;
+			if let ObjectData::Memory { size, type_index, .. } = o.data
+			{
+				if MEMORY_TYPES[type_index as usize].1 == heap { used += size; }
+			}
+		}
+		if count >= MAX_MEMORY_ALLOCATION_COUNT { return Err(VK_ERROR_TOO_MANY_OBJECTS); }
+		if let Some((kind, resource)) = dedicated
+		{
+			if !self.check("vkAllocateMemory", kind, resource) { return Err(VK_ERROR_OUT_OF_DEVICE_MEMORY); }
+			let req = self.requirements("vkAllocateMemory", device, kind, resource);
+			if req.size != size
+			{
+				self.invalid(instance, "vkAllocateMemory", format!("allocationSize {} of the dedicated allocation differs from the size {} required by the {:?}", size, req.size, kind));
+				return Err(VK_ERROR_OUT_OF_DEVICE_MEMORY);
+			}
+		}
+		let heap_size = if heap == 0 { DEVICE_LOCAL_HEAP_SIZE } else { HOST_HEAP_SIZE };
+		if size == 0 || used + size > heap_size
+		{
+			return Err(if heap == 0 { VK_ERROR_OUT_OF_DEVICE_MEMORY } else { VK_ERROR_OUT_OF_HOST_MEMORY });
+		}
+		Ok(self.create(ObjectKind::DeviceMemory, device, ObjectData::Memory { size, type_index, mapped: false, contents: None, dedicated: dedicated.map(|d| d.1) }))
+	}
+	fn check_mapped_ranges(&mut self, call: &'static str, device: u64, ranges: &[VkMappedMemoryRange])
+	{
+		if !self.check(call, ObjectKind::Device, device) { return; }
+		for r in ranges
+		{
+			let memory = r.memory.id();
+			if !self.check(call, ObjectKind::DeviceMemory, memory) { continue; }
+			let instance = self.instance_of(memory);
+			let (size, mapped) = match *self.data(memory) { ObjectData::Memory { size, mapped, .. } => (size, mapped), _ => unreachable!() };
+			if !mapped { self.invalid(instance, call, format!("DeviceMemory({:#x}) is not mapped", memory)); continue; }
+			let end = if r.size == VK_WHOLE_SIZE { size } else { r.offset + r.size };
+			if r.offset % NON_COHERENT_ATOM_SIZE != 0 || (end != size && end % NON_COHERENT_ATOM_SIZE != 0) || end > size
+			{
+				self.invalid(instance, call, format!("range {}..{} is not aligned to nonCoherentAtomSize or out of the memory", r.offset, end));
+			}
+		}
+	}
+	fn requirements(&mut self, call: &'static str, device: u64, kind: ObjectKind, resource: u64) -> VkMemoryRequirements
+	{
+		if !self.check(call, ObjectKind::Device, device) || !self.check(call, kind, resource)
+		{
+			return VkMemoryRequirements { size: 0, alignment: 1, memoryTypeBits: 0 };
+		}
+		match *self.data(resource)
+		{
+			ObjectData::Buffer { size, .. } => VkMemoryRequirements
+			{
+				size: round_up(size, BUFFER_ALIGNMENT), alignment: BUFFER_ALIGNMENT, memoryTypeBits: 0b1111
+			},
+			ObjectData::Image { ref shape, .. } => VkMemoryRequirements
+			{
+				size: round_up(image_bytes(shape), IMAGE_ALIGNMENT), alignment: IMAGE_ALIGNMENT,
+				memoryTypeBits: if shape.tiling == VK_IMAGE_TILING_LINEAR { 0b1111 } else { 0b1001 }
+			},
+			_ => unreachable!()
+		}
+	}
+	/// Render targets prefer a dedicated allocation, as some implementations do
+	fn prefers_dedicated(&mut self, resource: u64) -> bool
+	{
+		match *self.data(resource)
+		{
+			ObjectData::Image { ref shape, .. } =>
+				shape.usage & (VK_IMAGE_USAGE_COLOR_ATTACHMENT_BIT | VK_IMAGE_USAGE_DEPTH_STENCIL_ATTACHMENT_BIT) != 0,
+			_ => false
+		}
+	}
+	fn bind(&mut self, call: &'static str, device: u64, kind: ObjectKind, resource: u64, memory: u64, offset: VkDeviceSize) -> VkResult
+	{
+		if !self.check(call, ObjectKind::Device, device) || !self.check(call, kind, resource) || !self.check(call, ObjectKind::DeviceMemory, memory)
+		{
+			return VK_ERROR_DEVICE_LOST;
+		}
+		let instance = self.instance_of(resource);
+		let req = self.requirements(call, device, kind, resource);
+		let (msize, type_index, dedicated) = match *self.data(memory)
+		{
+			ObjectData::Memory { size, type_index, dedicated, .. } => (size, type_index, dedicated), _ => unreachable!()
+		};
+		let message = match *self.data(resource)
+		{
+			ObjectData::Buffer { binding: Some(_), .. } | ObjectData::Image { binding: Some(_), .. } =>
+				Some(format!("{:?}({:#x}) is already bound to a memory", kind, resource)),
+			_ if dedicated.map_or(false, |d| d != resource || offset != 0) =>
+				Some(format!("DeviceMemory({:#x}) is dedicated to another resource or bound at non-zero offset", memory)),
+			_ if req.memoryTypeBits & (1 << type_index) == 0 => Some(format!("memory type {} is not allowed for the {:?}", type_index, kind)),
+			_ if offset % req.alignment != 0 => Some(format!("offset {} is not aligned to {}", offset, req.alignment)),
+			_ if offset + req.size > msize => Some(format!("{} bytes from offset {} exceeds the memory({} bytes)", req.size, offset, msize)),
+			_ => None
+		};
+		if let Some(m) = message { self.invalid(instance, call, m); return VK_ERROR_OUT_OF_DEVICE_MEMORY; }
+		match *self.data(resource)
+		{
+			ObjectData::Buffer { ref mut binding, .. } | ObjectData::Image { ref mut binding, .. } => *binding = Some((memory, offset)),
+			_ => unreachable!()
+		}
+		VK_SUCCESS
+	}
+
+	fn signal_fence(&mut self, call: &'static str, fence: u64)
+	{
+		if fence == 0 || !self.check(call, ObjectKind::Fence, fence) { return; }
+		let instance = self.instance_of(fence);
+		if let ObjectData::Fence(true) = *self.data(fence)
+		{
+			self.invalid(instance, call, format!("Fence({:#x}) is already signaled", fence));
+		}
+		*self.data(fence) = ObjectData::Fence(true);
+	}
+	fn signal_semaphore(&mut self, call: &'static str, semaphore: u64)
+	{
+		if semaphore == 0 || !self.check(call, ObjectKind::Semaphore, semaphore) { return; }
+		let instance = self.instance_of(semaphore);
+		if let ObjectData::Semaphore(true) = *self.data(semaphore)
+		{
+			self.invalid(instance, call, format!("Semaphore({:#x}) is already signaled", semaphore));
+		}
+		*self.data(semaphore) = ObjectData::Semaphore(true);
+	}
+	fn wait_semaphore(&mut self, call: &'static str, semaphore: u64)
+	{
+		if !self.check(call, ObjectKind::Semaphore, semaphore) { return; }
+		let instance = self.instance_of(semaphore);
+		if let ObjectData::Semaphore(false) = *self.data(semaphore)
+		{
+			self.invalid(instance, call, format!("Semaphore({:#x}) has no way to be signaled", semaphore));
+		}
+		*self.data(semaphore) = ObjectData::Semaphore(false);
+	}
+	fn submit_command_buffer(&mut self, cb: u64)
+	{
+		if !self.check("vkQueueSubmit", ObjectKind::CommandBuffer, cb) { return; }
+		let instance = self.instance_of(cb);
+		let (level, state, one_time) = match *self.data(cb)
+		{
+			ObjectData::CommandBuffer { level, state, one_time, .. } => (level, state, one_time), _ => unreachable!()
+		};
+		if level != VK_COMMAND_BUFFER_LEVEL_PRIMARY
+		{
+			self.invalid(instance, "vkQueueSubmit", format!("CommandBuffer({:#x}) is not a primary command buffer", cb));
+			return;
+		}
+		if state != CommandBufferState::Executable
+		{
+			self.invalid(instance, "vkQueueSubmit", format!("CommandBuffer({:#x}) is not in the executable state({:?})", cb, state));
+			return;
+		}
+		self.execute(cb);
+		if one_time
+		{
+			if let ObjectData::CommandBuffer { ref mut state, .. } = *self.data(cb) { *state = CommandBufferState::Invalid; }
+		}
+	}
+	unsafe fn create_pipelines(&mut self, call: &'static str, device: u64, cache: u64, count: u32, out: *mut VkPipeline) -> VkResult
+	{
+		if !self.check(call, ObjectKind::Device, device) { return VK_ERROR_DEVICE_LOST; }
+		self.check_opt(call, ObjectKind::PipelineCache, cache);
+		for n in 0 .. count as usize { *out.add(n) = FakeHandle::from_id(self.create(ObjectKind::Pipeline, device, ObjectData::None)); }
+		VK_SUCCESS
+	}
+	#[cfg(any(feature = "VK_KHR_surface", feature = "VK_KHR_xlib_surface", feature = "VK_KHR_xcb_surface", feature = "VK_KHR_android_surface",
+		feature = "VK_KHR_win32_surface", feature = "VK_MVK_macos_surface"))]
+	unsafe fn create_surface(&mut self, call: &'static str, instance: u64, out: *mut VkSurfaceKHR) -> VkResult
+	{
+		match self.create_child(call, ObjectKind::Instance, instance, ObjectKind::Surface, ObjectData::None)
+		{
+			Ok(h) => { *out = FakeHandle::from_id(h); VK_SUCCESS },
+			Err(_) => VK_ERROR_INITIALIZATION_FAILED
+		}
+	}
+	fn set_event(&mut self, call: &'static str, device: u64, event: u64, set: bool) -> VkResult
+	{
+		if !self.check(call, ObjectKind::Device, device) || !self.check(call, ObjectKind::Event, event) { return VK_ERROR_DEVICE_LOST; }
+		*self.data(event) = ObjectData::Event(set);
+		VK_SUCCESS
+	}
+}
+
+fn texel_size(format: VkFormat) -> VkDeviceSize { ::std::cmp::max(format.bit_width() / 8, 1) as _ }
+fn mip_extent(extent: &VkExtent3D, level: u32) -> (u32, u32, u32)
+{
+	let f = |v: u32| ::std::cmp::max(v.checked_shr(level).unwrap_or(0), 1);
+	(f(extent.width), f(extent.height), f(extent.depth))
+}
+/// (offset, row pitch, depth pitch) of a subresource in the packed layout
+fn subresource_placement(shape: &ImageShape, layer: u32, level: u32) -> (VkDeviceSize, VkDeviceSize, VkDeviceSize)
+{
+	let texel = texel_size(shape.format);
+	let row_pitch = |w: u32| if shape.tiling == VK_IMAGE_TILING_LINEAR { round_up(w as VkDeviceSize * texel, LINEAR_ROW_PITCH_ALIGNMENT) }
+		else { w as VkDeviceSize * texel };
+	let mip_bytes = |m| { let (w, h, d) = mip_extent(&shape.extent, m); row_pitch(w) * h as VkDeviceSize * d as VkDeviceSize };
+	let layer_bytes: VkDeviceSize = (0 .. shape.mip_levels).map(&mip_bytes).sum();
+	let offset = layer as VkDeviceSize * layer_bytes + (0 .. level).map(&mip_bytes).sum::<VkDeviceSize>();
+	let (w, h, _) = mip_extent(&shape.extent, level);
+	(offset, row_pitch(w), row_pitch(w) * h as VkDeviceSize)
+}
+fn image_bytes(shape: &ImageShape) -> VkDeviceSize { subresource_placement(shape, shape.array_layers, 0).0 }
+fn round_up(v: VkDeviceSize, a: VkDeviceSize) -> VkDeviceSize { (v + a - 1) / a * a }
+fn is_depth_stencil(format: VkFormat) -> bool
+{
+	matches!(format, VK_FORMAT_D16_UNORM | VK_FORMAT_X8_D24_UNORM_PACK32 | VK_FORMAT_D32_SFLOAT | VK_FORMAT_S8_UINT |
+		VK_FORMAT_D16_UNORM_S8_UINT | VK_FORMAT_D24_UNORM_S8_UINT | VK_FORMAT_D32_SFLOAT_S8_UINT)
+}
+
+fn format_properties(format: VkFormat) -> VkFormatProperties
+{
+	let transfer = VK_FORMAT_FEATURE_TRANSFER_SRC_BIT | VK_FORMAT_FEATURE_TRANSFER_DST_BIT
+		| VK_FORMAT_FEATURE_BLIT_SRC_BIT | VK_FORMAT_FEATURE_BLIT_DST_BIT;
+	let (linear, optimal, buffer) = if format == VK_FORMAT_UNDEFINED { (0, 0, 0) }
+	else if is_depth_stencil(format)
+	{
+		(0, transfer | VK_FORMAT_FEATURE_SAMPLED_IMAGE_BIT | VK_FORMAT_FEATURE_DEPTH_STENCIL_ATTACHMENT_BIT, 0)
+	}
+	else
+	{
+		let color = transfer | VK_FORMAT_FEATURE_SAMPLED_IMAGE_BIT | VK_FORMAT_FEATURE_SAMPLED_IMAGE_FILTER_LINEAR_BIT
+			| VK_FORMAT_FEATURE_STORAGE_IMAGE_BIT | VK_FORMAT_FEATURE_COLOR_ATTACHMENT_BIT | VK_FORMAT_FEATURE_COLOR_ATTACHMENT_BLEND_BIT;
+		let buffer = VK_FORMAT_FEATURE_VERTEX_BUFFER_BIT | VK_FORMAT_FEATURE_UNIFORM_TEXEL_BUFFER_BIT | VK_FORMAT_FEATURE_STORAGE_TEXEL_BUFFER_BIT;
+		(color, color, buffer)
+	};
+	VkFormatProperties { linearTilingFeatures: linear, optimalTilingFeatures: optimal, bufferFeatures: buffer }
+}
+fn image_format_properties(format: VkFormat, tiling: VkImageTiling) -> Result<VkImageFormatProperties, VkResult>
+{
+	let features = format_properties(format);
+	let supported = if tiling == VK_IMAGE_TILING_LINEAR { features.linearTilingFeatures } else { features.optimalTilingFeatures };
+	if supported == 0 { return Err(VK_ERROR_FORMAT_NOT_SUPPORTED); }
+	Ok(if tiling == VK_IMAGE_TILING_LINEAR
+	{
+		VkImageFormatProperties
+		{
+			// 3D linear images are supported as some drivers do
+			maxExtent: VkExtent3D::new(16384, 16384, 16), maxMipLevels: 1, maxArrayLayers: 1,
+			sampleCounts: VK_SAMPLE_COUNT_1_BIT, maxResourceSize: 1 << 31
+		}
+	}
+	else
+	{
+		VkImageFormatProperties
+		{
+			maxExtent: VkExtent3D::new(16384, 16384, 2048), maxMipLevels: 15, maxArrayLayers: 2048,
+			sampleCounts: VK_SAMPLE_COUNT_1_BIT | VK_SAMPLE_COUNT_4_BIT, maxResourceSize: 1 << 31
+		}
+	})
+}
+fn features() -> VkPhysicalDeviceFeatures
+{
+	let mut f = VkPhysicalDeviceFeatures::default();
+	let n = ::std::mem::size_of::<VkPhysicalDeviceFeatures>() / ::std::mem::size_of::<VkBool32>();
+	for b in unsafe { ::std::slice::from_raw_parts_mut(&mut f as *mut _ as *mut VkBool32, n) } { *b = VK_TRUE; }
+	// no sparse residency support
+	f.sparseResidencyBuffer = VK_FALSE; f.sparseResidencyImage2D = VK_FALSE; f.sparseResidencyImage3D = VK_FALSE;
+	f.sparseResidency2Samples = VK_FALSE; f.sparseResidency4Samples = VK_FALSE; f.sparseResidency8Samples = VK_FALSE;
+	f.sparseResidency16Samples = VK_FALSE; f.sparseResidencyAliased = VK_FALSE;
+	f.textureCompressionETC2 = VK_FALSE; f.textureCompressionASTC_LDR = VK_FALSE;
+	f
+}
+fn properties() -> VkPhysicalDeviceProperties
+{
+	let mut p: VkPhysicalDeviceProperties = unsafe { zeroed() };
+	p.apiVersion = VK_API_VERSION_1_1; p.driverVersion = VK_MAKE_VERSION!(0, 1, 0);
+	p.vendorID = 0x10005; p.deviceID = 0xfa4e; p.deviceType = VK_PHYSICAL_DEVICE_TYPE_CPU;
+	for (d, s) in p.deviceName.iter_mut().zip(b"bedrock fake device".iter()) { *d = *s as _; }
+	p.pipelineCacheUUID = *b"bedrock-fakedrv\0";
+	let l = &mut p.limits;
+	l.maxImageDimension1D = 16384; l.maxImageDimension2D = 16384; l.maxImageDimension3D = 2048;
+	l.maxImageDimensionCube = 16384; l.maxImageArrayLayers = 2048; l.maxTexelBufferElements = 1 << 27;
+	l.maxUniformBufferRange = 65536; l.maxStorageBufferRange = 1 << 30; l.maxPushConstantsSize = 256;
+	l.maxMemoryAllocationCount = MAX_MEMORY_ALLOCATION_COUNT; l.maxSamplerAlocationCount = 4000;
+	l.bufferImageGranularity = BUFFER_IMAGE_GRANULARITY; l.sparseAddressSparseSize = 1 << 40;
+	l.maxBoudnDescriptorSets = 8; l.maxPerStageDescriptorSample = 1024;
+	l.maxPerStageDescriptorUniformBuffers = 15; l.maxPerStageDescriptorStorageBuffers = 1024;
+	l.maxPerStageDescriptorSampledImages = 1024; l.maxPerStageDescriptorStorageImages = 1024;
+	l.maxPerStageDescriptorInputAttachments = 8; l.maxPerStageResources = 4096;
+	l.maxDescriptorSetSamples = 4000; l.maxDescriptorSetUniformBuffers = 90; l.maxDescriptorSetUniformBuffersDynamic = 8;
+	l.maxDescriptorSetStorageBuffers = 4096; l.maxDescriptorSetStorageBuffersDynamic = 8;
+	l.maxDescriptorSetSampledImages = 4096; l.maxDescriptorSetStorageImages = 4096; l.maxDescriptorSetInputAttachments = 8;
+	l.maxVertexInputAttributes = 32; l.maxVertexInputBindings = 32; l.maxVertexInputAttributeOffset = 2047;
+	l.maxVertexInputBindingStride = 2048; l.maxVertexOutputComponents = 128;
+	l.maxTessellationGenerationLevel = 64; l.maxTessellationPatchSize = 32;
+	l.maxTessellationControlPerVertexInputComponents = 128; l.maxTessellationControlPerVertexOutputComponents = 128;
+	l.maxTessellationControlPerPatchOutputComponents = 120; l.maxTessellationControlTotalOutputComponents = 4096;
+	l.maxTessellationEvaluationInputComponents = 128; l.maxTessellationEvaluationOutputComponents = 128;
+	l.maxGeometryShaderInvocations = 32; l.maxGeometryInputComponents = 64; l.maxGeometryOutputComponents = 128;
+	l.maxGeometryOutputVertices = 256; l.maxGeometryTotalOutputComponents = 1024;
+	l.maxFragmentInputComponents = 128; l.maxFragmentOutputAttachments = 8; l.maxFragmentDualSrcAttachments = 1;
+	l.maxFragmentCombinedOutputResources = 16; l.maxComputeSharedMemorySize = 32768;
+	l.maxComputeWorkGroupCount = [65535; 3]; l.maxComputeWorkGroupInvocations = 1024; l.maxComputeWorkGroupSize = [1024, 1024, 64];
+	l.subPixelPrecisionBits = 8; l.subTexelPrecisionBits = 8; l.mipmapPrecisionBits = 8;
+	l.maxDrawIndexedIndexValue = u32::MAX; l.maxDrawIndirectCount = u32::MAX;
+	l.maxSamplerLodBias = 15.0; l.maxSamplerAnisotropy = 16.0;
+	l.maxViewports = 16; l.maxViewportDimensions = [16384; 2]; l.viewportBoundsRange = [-32768.0, 32767.0];
+	l.viewportSubPixelBits = 8; l.minMemoryMapAlignment = 64;
+	l.minTexelBufferOffsetAlignment = 16; l.minUniformBufferOffsetAlignment = 256; l.minStorageBufferOffsetAlignment = 16;
+	l.minTexelOffset = -8; l.maxTexelOffset = 7; l.minTexelGatherOffset = -32; l.maxTexelGatherOffset = 31;
+	l.minInterpolationOffset = -0.5; l.maxInterpolationOffset = 0.4375; l.subPixelInterpolationOffsetBits = 4;
+	l.maxFramebufferWidth = 16384; l.maxFramebufferHeight = 16384; l.maxFramebufferLayers = 2048;
+	let samples = VK_SAMPLE_COUNT_1_BIT | VK_SAMPLE_COUNT_4_BIT;
+	l.framebufferColorSampleCounts = samples; l.framebufferDepthSampleCounts = samples;
+	l.framebufferStencilSampleCounts = samples; l.framebufferNoAttachmentsSampleCounts = samples;
+	l.maxColorAttachments = 8; l.sampledImageColorSampleCounts = samples; l.sampledImageIntegerSampleCounts = samples;
+	l.sampledImageDepthSampleCounts = samples; l.sampledImageStencilSampleCounts = samples;
+	l.storageImageSampleCounts = VK_SAMPLE_COUNT_1_BIT; l.maxSampleMaskWords = 1;
+	l.timestampComputeAndGraphics = VK_TRUE; l.timestampPeriod = 1.0;
+	l.maxClipDistances = 8; l.maxCullDistances = 8; l.maxCombinedClipAndCullDistances = 8; l.discreteQueuePriorities = 2;
+	l.pointSizeRange = [1.0, 64.0]; l.lineWidthRange = [1.0, 8.0]; l.pointSizeGranularity = 0.125; l.lineWidthGranularity = 0.125;
+	l.strictLines = VK_FALSE; l.standardSampleLocations = VK_TRUE;
+	l.optimalBufferCopyOffsetAlignment = 4; l.optimalBufferCopyRowPitchAlignment = 4; l.nonCoherentAtomSize = NON_COHERENT_ATOM_SIZE;
+	p
+}
+fn queue_family_properties() -> Vec<VkQueueFamilyProperties>
+{
+	QUEUE_FAMILIES.iter().map(|&(queueFlags, queueCount)| VkQueueFamilyProperties
+	{
+		queueFlags, queueCount, timestampValidBits: 64, minImageTransferGranularity: VkExtent3D::new(1, 1, 1)
+	}).collect()
+}
+fn memory_properties() -> VkPhysicalDeviceMemoryProperties
+{
+	let mut p: VkPhysicalDeviceMemoryProperties = unsafe { zeroed() };
+	p.memoryTypeCount = MEMORY_TYPES.len() as _;
+	for (d, &(propertyFlags, heapIndex)) in p.memoryTypes.iter_mut().zip(MEMORY_TYPES.iter())
+	{
+		*d = VkMemoryType { propertyFlags, heapIndex };
+	}
+	p.memoryHeapCount = 2;
+	p.memoryHeaps[0] = VkMemoryHeap { size: DEVICE_LOCAL_HEAP_SIZE, flags: VK_MEMORY_HEAP_DEVICE_LOCAL_BIT };
+	p.memoryHeaps[1] = VkMemoryHeap { size: HOST_HEAP_SIZE, flags: 0 };
+	p
+}
+
+/// Two-call idiom for the enumeration commands
+unsafe fn enumerate_into<T: Clone>(src: &[T], count: *mut u32, out: *mut T) -> VkResult
+{
+	if out.is_null() { *count = src.len() as _; return VK_SUCCESS; }
+	let n = ::std::cmp::min(*count as usize, src.len());
+	for (i, v) in src[..n].iter().enumerate() { ::std::ptr::write(out.add(i), v.clone()); }
+	*count = n as _;
+	if n < src.len() { VK_INCOMPLETE } else { VK_SUCCESS }
+}
+fn extension_properties(list: &[(&str, u32)]) -> Vec<ExtensionProperties>
+{
+	list.iter().map(|&(name, specVersion)|
+	{
+		let mut p = VkExtensionProperties { extensionName: [0; VK_MAX_EXTENSION_NAME_SIZE], specVersion };
+		for (d, s) in p.extensionName.iter_mut().zip(name.bytes()) { *d = s as _; }
+		ExtensionProperties(p)
+	}).collect()
+}
+/// `VkExtensionProperties` does not implement `Clone`
+#[repr(C)]
+struct ExtensionProperties(VkExtensionProperties);
+impl Clone for ExtensionProperties
+{
+	fn clone(&self) -> Self
+	{
+		ExtensionProperties(VkExtensionProperties { extensionName: self.0.extensionName, specVersion: self.0.specVersion })
+	}
+}
+unsafe fn names<'a>(count: u32, names: *const *const c_char) -> Vec<&'a str>
+{
+	(0 .. count as usize).map(|i| CStr::from_ptr(*names.add(i)).to_str().unwrap_or("<invalid>")).collect()
+}
+#[cfg(feature = "VK_EXT_debug_utils")]
+unsafe fn label_name(label: *const VkDebugUtilsLabelEXT) -> String
+{
+	let p = (*label).pLabelName;
+	if p.is_null() { String::new() } else { CStr::from_ptr(p).to_string_lossy().into_owned() }
+}
+unsafe fn slice<'a, T>(ptr: *const T, count: u32) -> &'a [T]
+{
+	if count == 0 { &[] } else { ::std::slice::from_raw_parts(ptr, count as _) }
+}
+
+#[cfg(feature = "VK_EXT_debug_report")]
+extern "system" fn fake_create_debug_report_callback(instance: VkInstance, pCreateInfo: *const VkDebugReportCallbackCreateInfoEXT,
+	_: *const VkAllocationCallbacks, pCallback: *mut VkDebugReportCallbackEXT) -> VkResult
+{
+	let ci = unsafe { &*pCreateInfo };
+	let data = ObjectData::DebugReportCallback { flags: ci.flags, callback: ci.pfnCallback, user_data: ci.pUserData as usize };
+	match installed().state().create_child("vkCreateDebugReportCallbackEXT", ObjectKind::Instance, instance.id(), ObjectKind::DebugReportCallback, data)
+	{
+		Ok(h) => { unsafe { *pCallback = FakeHandle::from_id(h); } VK_SUCCESS },
+		Err(e) => e
+	}
+}
+#[cfg(feature = "VK_EXT_debug_report")]
+extern "system" fn fake_destroy_debug_report_callback(instance: VkInstance, callback: VkDebugReportCallbackEXT, _: *const VkAllocationCallbacks)
+{
+	installed().state().destroy_child("vkDestroyDebugReportCallbackEXT", ObjectKind::Instance, instance.id(), ObjectKind::DebugReportCallback, callback.id());
+}
+#[cfg(feature = "VK_EXT_debug_report")]
+extern "system" fn fake_debug_report_message(instance: VkInstance, flags: VkDebugReportFlagsEXT, objectType: VkDebugReportObjectTypeEXT, object: u64,
+	location: size_t, messageCode: i32, pLayerPrefix: *const c_char, pMessage: *const c_char)
+{
+	let targets = {
+		let d = installed(); let mut s = d.state();
+		if !s.check("vkDebugReportMessageEXT", ObjectKind::Instance, instance.id()) { return; }
+		s.objects.values().filter_map(|o| match o.data
+		{
+			ObjectData::DebugReportCallback { flags: f, callback, user_data } if o.alive && o.parent == instance.id() && (f & flags) != 0 =>
+				Some((callback, user_data)),
+			_ => None
+		}).collect::<Vec<_>>()
+	};
+	// called back without the lock, so that the callbacks can call into the driver
+	for (callback, user_data) in targets
+	{
+		callback(flags, objectType, object, location, messageCode, pLayerPrefix, pMessage, user_data as *mut c_void);
+	}
+}
+
+#[cfg(feature = "VK_EXT_debug_utils")]
+extern "system" fn fake_create_debug_utils_messenger(instance: VkInstance, pCreateInfo: *const VkDebugUtilsMessengerCreateInfoEXT,
+	_: *const VkAllocationCallbacks, pMessenger: *mut VkDebugUtilsMessengerEXT) -> VkResult
+{
+	let ci = unsafe { &*pCreateInfo };
+	let data = ObjectData::DebugUtilsMessenger
+	{
+		severity: ci.messageSeverity, types: ci.messageType, callback: ci.pfnUserCallback, user_data: ci.pUserData as usize
+	};
+	match installed().state().create_child("vkCreateDebugUtilsMessengerEXT", ObjectKind::Instance, instance.id(), ObjectKind::DebugUtilsMessenger, data)
+	{
+		Ok(h) => { unsafe { *pMessenger = FakeHandle::from_id(h); } VK_SUCCESS },
+		Err(e) => e
+	}
+}
+#[cfg(feature = "VK_EXT_debug_utils")]
+extern "system" fn fake_destroy_debug_utils_messenger(instance: VkInstance, messenger: VkDebugUtilsMessengerEXT, _: *const VkAllocationCallbacks)
+{
+	installed().state().destroy_child("vkDestroyDebugUtilsMessengerEXT", ObjectKind::Instance, instance.id(), ObjectKind::DebugUtilsMessenger, messenger.id());
+}
+#[cfg(feature = "VK_EXT_debug_utils")]
+extern "system" fn fake_submit_debug_utils_message(instance: VkInstance, messageSeverity: VkDebugUtilsMessageSeverityFlagsEXT,
+	messageTypes: VkDebugUtilsMessageTypeFlagsEXT, pCallbackData: *const VkDebugUtilsMessengerCallbackDataEXT)
+{
+	let targets = {
+		let d = installed(); let mut s = d.state();
+		if !s.check("vkSubmitDebugUtilsMessageEXT", ObjectKind::Instance, instance.id()) { return; }
+		s.objects.values().filter_map(|o| match o.data
+		{
+			ObjectData::DebugUtilsMessenger { severity, types, callback, user_data }
+				if o.alive && o.parent == instance.id() && (severity & messageSeverity) != 0 && (types & messageTypes) != 0 =>
+				Some((callback, user_data)),
+			_ => None
+		}).collect::<Vec<_>>()
+	};
+	for (callback, user_data) in targets { callback(messageSeverity, messageTypes, pCallbackData, user_data as *mut c_void); }
+}
+
+macro_rules! FakeDeviceChild
+{
+	($create: ident / $destroy: ident ($ci: ty, $h: ty) = $kind: ident [$cname: expr, $dname: expr]) =>
+	{
+		unsafe fn $create(&self, device: VkDevice, _: *const $ci, _: *const VkAllocationCallbacks, p: *mut $h) -> VkResult
+		{
+			match self.state().create_child($cname, ObjectKind::Device, device.id(), ObjectKind::$kind, ObjectData::None)
+			{
+				Ok(h) => { *p = FakeHandle::from_id(h); VK_SUCCESS },
+				Err(e) => e
+			}
+		}
+		unsafe fn $destroy(&self, device: VkDevice, h: $h, _: *const VkAllocationCallbacks)
+		{
+			self.state().destroy_child($dname, ObjectKind::Device, device.id(), ObjectKind::$kind, h.id());
+		}
+	}
+}
+macro_rules! FakeCommand
+{
+	($name: ident = $vk: expr; $($an: ident : $at: ty),*) =>
+	{
+		unsafe fn $name(&self, commandBuffer: VkCommandBuffer $(, $an: $at)*)
+		{
+			$(let _ = $an;)*
+			self.state().recording($vk, commandBuffer);
+		}
+	}
+}
+
+/// Bodies of the queries shared by Vulkan 1.1 and VK_KHR_get_physical_device_properties2
+#[allow(non_snake_case)]
+impl FakeDriver
+{
+	unsafe fn physical_device_features2(&self, physicalDevice: VkPhysicalDevice, pFeatures: *mut VkPhysicalDeviceFeatures2)
+	{
+		self.get_physical_device_features(physicalDevice, &mut (*pFeatures).features);
+	}
+	unsafe fn physical_device_properties2(&self, physicalDevice: VkPhysicalDevice, pProperties: *mut VkPhysicalDeviceProperties2)
+	{
+		self.get_physical_device_properties(physicalDevice, &mut (*pProperties).properties);
+		let mut p = (*pProperties).pNext as *mut ChainHeader;
+		while !p.is_null()
+		{
+			if (*p).sType == VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SUBGROUP_PROPERTIES
+			{
+				let sp = &mut *(p as *mut VkPhysicalDeviceSubgroupProperties);
+				sp.subgroupSize = 1;
+				sp.supportedStages = VK_SHADER_STAGE_ALL;
+				sp.supportedOperations = VK_SUBGROUP_FEATURE_BASIC_BIT;
+				sp.quadOperationsInAllStages = false as _;
+			}
+			p = (*p).pNext as *mut ChainHeader;
+		}
+	}
+	unsafe fn physical_device_format_properties2(&self, physicalDevice: VkPhysicalDevice, format: VkFormat, pFormatProperties: *mut VkFormatProperties2)
+	{
+		self.get_physical_device_format_properties(physicalDevice, format, &mut (*pFormatProperties).formatProperties);
+	}
+	unsafe fn physical_device_queue_family_properties2(&self, physicalDevice: VkPhysicalDevice, pQueueFamilyPropertyCount: *mut u32,
+		pQueueFamilyProperties: *mut VkQueueFamilyProperties2)
+	{
+		self.state().check("vkGetPhysicalDeviceQueueFamilyProperties2", ObjectKind::PhysicalDevice, physicalDevice.id());
+		let src = queue_family_properties();
+		if pQueueFamilyProperties.is_null() { *pQueueFamilyPropertyCount = src.len() as _; return; }
+		let n = ::std::cmp::min(*pQueueFamilyPropertyCount as usize, src.len());
+		for (i, p) in src.into_iter().take(n).enumerate() { (*pQueueFamilyProperties.add(i)).queueFamilyProperties = p; }
+		*pQueueFamilyPropertyCount = n as _;
+	}
+	unsafe fn physical_device_memory_properties2(&self, physicalDevice: VkPhysicalDevice, pMemoryProperties: *mut VkPhysicalDeviceMemoryProperties2)
+	{
+		self.get_physical_device_memory_properties(physicalDevice, &mut (*pMemoryProperties).memoryProperties);
+		#[cfg(feature = "VK_EXT_memory_budget")]
+		{
+			let mut p = (*pMemoryProperties).pNext as *mut ChainHeader;
+			while !p.is_null()
+			{
+				if (*p).sType == VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MEMORY_BUDGET_PROPERTIES_EXT
+				{
+					let b = &mut *(p as *mut VkPhysicalDeviceMemoryBudgetPropertiesEXT);
+					let s = self.state();
+					let instance = s.instance_of(physicalDevice.id());
+					b.heapUsage = [0; VK_MAX_MEMORY_HEAPS];
+					for o in s.objects.values().filter(|o| o.alive && o.kind == ObjectKind::DeviceMemory && o.instance == instance)
+					{
+						if let ObjectData::Memory { size, type_index, .. } = o.data { b.heapUsage[MEMORY_TYPES[type_index as usize].1 as usize] += size; }
+					}
+					b.heapBudget = [0; VK_MAX_MEMORY_HEAPS];
+					b.heapBudget[0] = DEVICE_LOCAL_HEAP_SIZE / 4 * 3; b.heapBudget[1] = HOST_HEAP_SIZE / 4 * 3;
+				}
+				p = (*p).pNext as *mut ChainHeader;
+			}
+		}
+	}
+}
+
+impl ResolverInterface for FakeDriver
+{
+	fn provides(&self, name: &str) -> bool { !self.state().withheld.iter().any(|n| n == name) }
+
+	unsafe fn create_instance(&self, create_info: *const VkInstanceCreateInfo, _: *const VkAllocationCallbacks, instance: *mut VkInstance) -> VkResult
+	{
+		let ci = &*create_info;
+		if names(ci.enabledExtensionCount, ci.ppEnabledExtensionNames).iter().any(|n| INSTANCE_EXTENSIONS.iter().all(|&(e, _)| e != *n))
+		{
+			return VK_ERROR_EXTENSION_NOT_PRESENT;
+		}
+		if ci.enabledLayerCount > 0 { return VK_ERROR_LAYER_NOT_PRESENT; }
+		let api_version = ci.pApplicationInfo.as_ref().map_or(0, |a| a.apiVersion).max(VK_API_VERSION_1_0);
+		let mut s = self.state();
+		let h = s.create(ObjectKind::Instance, 0, ObjectData::Instance { physical_device: 0, api_version });
+		let pd = s.create(ObjectKind::PhysicalDevice, h, ObjectData::None);
+		if let ObjectData::Instance { ref mut physical_device, .. } = *s.data(h) { *physical_device = pd; }
+		*instance = FakeHandle::from_id(h);
+		VK_SUCCESS
+	}
+	unsafe fn destroy_instance(&self, instance: VkInstance, _: *const VkAllocationCallbacks)
+	{
+		let mut s = self.state();
+		if instance.is_null() { return; }
+		if !s.objects.get(&instance.id()).map_or(false, |o| o.kind == ObjectKind::Instance && !o.alive)
+			&& s.check("vkDestroyInstance", ObjectKind::Instance, instance.id())
+		{
+			s.report_leaks(instance.id(), &[ObjectKind::PhysicalDevice]);
+			// devices are children of the physical device
+			let pd = match *s.data(instance.id()) { ObjectData::Instance { physical_device, .. } => physical_device, _ => unreachable!() };
+			s.report_leaks(pd, &[ObjectKind::DisplayMode]);
+			s.kill(instance.id());
+		}
+		else if s.objects.contains_key(&instance.id())
+		{
+			s.report(instance.id(), Misuse::DoubleFree { kind: ObjectKind::Instance, handle: instance.id(), call: "vkDestroyInstance" });
+		}
+	}
+	unsafe fn enumerate_physical_devices(&self, instance: VkInstance, physical_device_count: *mut u32, physical_devices: *mut VkPhysicalDevice) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkEnumeratePhysicalDevices", ObjectKind::Instance, instance.id()) { return VK_ERROR_INITIALIZATION_FAILED; }
+		let pd = match *s.data(instance.id()) { ObjectData::Instance { physical_device, .. } => physical_device, _ => unreachable!() };
+		enumerate_into(&[FakeHandle::from_id(pd)], physical_device_count, physical_devices)
+	}
+	unsafe fn get_physical_device_features(&self, physicalDevice: VkPhysicalDevice, pFeatures: *mut VkPhysicalDeviceFeatures)
+	{
+		self.state().check("vkGetPhysicalDeviceFeatures", ObjectKind::PhysicalDevice, physicalDevice.id());
+		*pFeatures = features();
+	}
+	unsafe fn get_physical_device_format_properties(&self, physicalDevice: VkPhysicalDevice, format: VkFormat, pFormatProperties: *mut VkFormatProperties)
+	{
+		self.state().check("vkGetPhysicalDeviceFormatProperties", ObjectKind::PhysicalDevice, physicalDevice.id());
+		*pFormatProperties = format_properties(format);
+	}
+	unsafe fn get_physical_device_image_format_properties(&self, physicalDevice: VkPhysicalDevice, format: VkFormat, _: VkImageType,
+		tiling: VkImageTiling, _: VkImageUsageFlags, _: VkImageCreateFlags, pImageFormatProperties: *mut VkImageFormatProperties) -> VkResult
+	{
+		self.state().check("vkGetPhysicalDeviceImageFormatProperties", ObjectKind::PhysicalDevice, physicalDevice.id());
+		match image_format_properties(format, tiling) { Ok(p) => { *pImageFormatProperties = p; VK_SUCCESS }, Err(e) => e }
+	}
+	unsafe fn get_physical_device_properties(&self, physicalDevice: VkPhysicalDevice, pProperties: *mut VkPhysicalDeviceProperties)
+	{
+		self.state().check("vkGetPhysicalDeviceProperties", ObjectKind::PhysicalDevice, physicalDevice.id());
+		*pProperties = properties();
+	}
+	unsafe fn get_physical_device_queue_family_properties(&self, physicalDevice: VkPhysicalDevice, pQueueFamilyPropertyCount: *mut u32, pQueueFamilyProperties: *mut VkQueueFamilyProperties)
+	{
+		self.state().check("vkGetPhysicalDeviceQueueFamilyProperties", ObjectKind::PhysicalDevice, physicalDevice.id());
+		enumerate_into(&queue_family_properties(), pQueueFamilyPropertyCount, pQueueFamilyProperties);
+	}
+	unsafe fn get_physical_device_memory_properties(&self, physicalDevice: VkPhysicalDevice, pMemoryProperties: *mut VkPhysicalDeviceMemoryProperties)
+	{
+		self.state().check("vkGetPhysicalDeviceMemoryProperties", ObjectKind::PhysicalDevice, physicalDevice.id());
+		*pMemoryProperties = memory_properties();
+	}
+	#[cfg_attr(not(any(feature = "VK_EXT_debug_report", feature = "VK_EXT_debug_utils")), allow(unused_variables))]
+	unsafe fn get_instance_proc_addr(&self, _: VkInstance, pName: *const c_char) -> Option<PFN_vkVoidFunction>
+	{
+		// the extension entries find the driver through `install`
+		#[cfg(any(feature = "VK_EXT_debug_report", feature = "VK_EXT_debug_utils"))]
+		{
+			let p = INSTALLED.load(Ordering::Acquire);
+			if !p.is_null() && Arc::ptr_eq(&(*p).0, &self.0)
+			{
+				match CStr::from_ptr(pName).to_bytes()
+				{
+					#[cfg(feature = "VK_EXT_debug_report")]
+					b"vkCreateDebugReportCallbackEXT" => return Some(::std::mem::transmute::<PFN_vkCreateDebugReportCallbackEXT, PFN_vkVoidFunction>(
+						fake_create_debug_report_callback)),
+					#[cfg(feature = "VK_EXT_debug_report")]
+					b"vkDestroyDebugReportCallbackEXT" => return Some(::std::mem::transmute::<PFN_vkDestroyDebugReportCallbackEXT, PFN_vkVoidFunction>(
+						fake_destroy_debug_report_callback)),
+					#[cfg(feature = "VK_EXT_debug_report")]
+					b"vkDebugReportMessageEXT" => return Some(::std::mem::transmute::<PFN_vkDebugReportMessageEXT, PFN_vkVoidFunction>(
+						fake_debug_report_message)),
+					#[cfg(feature = "VK_EXT_debug_utils")]
+					b"vkCreateDebugUtilsMessengerEXT" => return Some(::std::mem::transmute::<PFN_vkCreateDebugUtilsMessengerEXT, PFN_vkVoidFunction>(
+						fake_create_debug_utils_messenger)),
+					#[cfg(feature = "VK_EXT_debug_utils")]
+					b"vkDestroyDebugUtilsMessengerEXT" => return Some(::std::mem::transmute::<PFN_vkDestroyDebugUtilsMessengerEXT, PFN_vkVoidFunction>(
+						fake_destroy_debug_utils_messenger)),
+					#[cfg(feature = "VK_EXT_debug_utils")]
+					b"vkSubmitDebugUtilsMessageEXT" => return Some(::std::mem::transmute::<PFN_vkSubmitDebugUtilsMessageEXT, PFN_vkVoidFunction>(
+						fake_submit_debug_utils_message)),
+					_ => ()
+				}
+			}
+		}
+		None
+	}
+	unsafe fn get_device_proc_addr(&self, _: VkDevice, _: *const c_char) -> Option<PFN_vkVoidFunction> { None }
+	unsafe fn create_device(&self, physicalDevice: VkPhysicalDevice, pCreateInfo: *const VkDeviceCreateInfo, _: *const VkAllocationCallbacks, pDevice: *mut VkDevice) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkCreateDevice", ObjectKind::PhysicalDevice, physicalDevice.id()) { return VK_ERROR_INITIALIZATION_FAILED; }
+		let ci = &*pCreateInfo;
+		if names(ci.enabledExtensionCount, ci.ppEnabledExtensionNames).iter().any(|n| DEVICE_EXTENSIONS.iter().all(|&(e, _)| e != *n))
+		{
+			return VK_ERROR_EXTENSION_NOT_PRESENT;
+		}
+		if ci.enabledLayerCount > 0 { return VK_ERROR_LAYER_NOT_PRESENT; }
+		let instance = s.instance_of(physicalDevice.id());
+		let mut queue_requests = Vec::new();
+		for q in slice(ci.pQueueCreateInfos, ci.queueCreateInfoCount)
+		{
+			let available = QUEUE_FAMILIES.get(q.queueFamilyIndex as usize).map_or(0, |&(_, c)| c);
+			if q.queueCount == 0 || q.queueCount > available || queue_requests.iter().any(|&(f, _)| f == q.queueFamilyIndex)
+			{
+				s.invalid(instance, "vkCreateDevice", format!("invalid queue request: family {} x {}", q.queueFamilyIndex, q.queueCount));
+				return VK_ERROR_INITIALIZATION_FAILED;
+			}
+			queue_requests.push((q.queueFamilyIndex, q.queueCount));
+		}
+		if !ci.pEnabledFeatures.is_null()
+		{
+			let (requested, supported) = (&*ci.pEnabledFeatures, features());
+			let n = ::std::mem::size_of::<VkPhysicalDeviceFeatures>() / ::std::mem::size_of::<VkBool32>();
+			let requested = slice(requested as *const _ as *const VkBool32, n as _);
+			let supported = slice(&supported as *const _ as *const VkBool32, n as _);
+			if requested.iter().zip(supported).any(|(&r, &s)| r != 0 && s == 0) { return VK_ERROR_FEATURE_NOT_PRESENT; }
+		}
+		let h = s.create(ObjectKind::Device, physicalDevice.id(), ObjectData::Device { queues: Vec::new() });
+		let mut queues = Vec::new();
+		for (family, count) in queue_requests
+		{
+			for index in 0 .. count { queues.push((family, index, s.create(ObjectKind::Queue, h, ObjectData::None))); }
+		}
+		if let ObjectData::Device { queues: ref mut q } = *s.data(h) { *q = queues; }
+		*pDevice = FakeHandle::from_id(h);
+		VK_SUCCESS
+	}
+	unsafe fn destroy_device(&self, device: VkDevice, _: *const VkAllocationCallbacks)
+	{
+		let mut s = self.state();
+		if device.is_null() { return; }
+		let instance = s.instance_of(device.id());
+		match s.objects.get(&device.id()).map(|o| (o.kind, o.alive))
+		{
+			Some((ObjectKind::Device, true)) => { s.report_leaks(device.id(), &[ObjectKind::Queue]); s.kill(device.id()); },
+			Some((ObjectKind::Device, false)) =>
+				s.report(instance, Misuse::DoubleFree { kind: ObjectKind::Device, handle: device.id(), call: "vkDestroyDevice" }),
+			_ => s.report(0, Misuse::UnknownHandle { kind: ObjectKind::Device, handle: device.id(), call: "vkDestroyDevice" })
+		}
+	}
+	unsafe fn enumerate_instance_extension_properties(&self, pLayerName: *const c_char, pPropertyCount: *mut u32, pProperties: *mut VkExtensionProperties) -> VkResult
+	{
+		if !pLayerName.is_null() { return VK_ERROR_LAYER_NOT_PRESENT; }
+		enumerate_into(&extension_properties(INSTANCE_EXTENSIONS), pPropertyCount, pProperties as *mut ExtensionProperties)
+	}
+	unsafe fn enumerate_device_extension_properties(&self, physicalDevice: VkPhysicalDevice, pLayerName: *const c_char, pPropertyCount: *mut u32, pProperties: *mut VkExtensionProperties) -> VkResult
+	{
+		self.state().check("vkEnumerateDeviceExtensionProperties", ObjectKind::PhysicalDevice, physicalDevice.id());
+		if !pLayerName.is_null() { return VK_ERROR_LAYER_NOT_PRESENT; }
+		enumerate_into(&extension_properties(DEVICE_EXTENSIONS), pPropertyCount, pProperties as *mut ExtensionProperties)
+	}
+	unsafe fn enumerate_instance_layer_properties(&self, pPropertyCount: *mut u32, _: *mut VkLayerProperties) -> VkResult
+	{
+		*pPropertyCount = 0; VK_SUCCESS
+	}
+	unsafe fn enumerate_device_layer_properties(&self, _: VkPhysicalDevice, pPropertyCount: *mut u32, _: *mut VkLayerProperties) -> VkResult
+	{
+		*pPropertyCount = 0; VK_SUCCESS
+	}
+	unsafe fn get_device_queue(&self, device: VkDevice, queueFamilyIndex: u32, queueIndex: u32, pQueue: *mut VkQueue)
+	{
+		let mut s = self.state();
+		if !s.check("vkGetDeviceQueue", ObjectKind::Device, device.id()) { *pQueue = null_mut(); return; }
+		let found = match *s.data(device.id())
+		{
+			ObjectData::Device { ref queues } => queues.iter().find(|&&(f, i, _)| f == queueFamilyIndex && i == queueIndex).map(|&(_, _, h)| h),
+			_ => unreachable!()
+		};
+		match found
+		{
+			Some(h) => *pQueue = FakeHandle::from_id(h),
+			None =>
+			{
+				let instance = s.instance_of(device.id());
+				s.invalid(instance, "vkGetDeviceQueue", format!("queue (family {}, index {}) is not created with the device", queueFamilyIndex, queueIndex));
+				*pQueue = null_mut();
+			}
+		}
+	}
+	unsafe fn queue_submit(&self, queue: VkQueue, submitCount: u32, pSubmits: *const VkSubmitInfo, fence: VkFence) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkQueueSubmit", ObjectKind::Queue, queue.id()) { return VK_ERROR_DEVICE_LOST; }
+		for si in slice(pSubmits, submitCount)
+		{
+			for &w in slice(si.pWaitSemaphores, si.waitSemaphoreCount) { s.wait_semaphore("vkQueueSubmit", w.id()); }
+			for &cb in slice(si.pCommandBuffers, si.commandBufferCount) { s.submit_command_buffer(cb.id()); }
+			for &sg in slice(si.pSignalSemaphores, si.signalSemaphoreCount) { s.signal_semaphore("vkQueueSubmit", sg.id()); }
+		}
+		let device = s.objects[&queue.id()].parent;
+		if fence.id() != 0 && s.delayed_devices.contains(&device) { s.delayed_fences.push(fence.id()); }
+		else { s.signal_fence("vkQueueSubmit", fence.id()); }
+		VK_SUCCESS
+	}
+	unsafe fn queue_wait_idle(&self, queue: VkQueue) -> VkResult
+	{
+		if self.state().check("vkQueueWaitIdle", ObjectKind::Queue, queue.id()) { VK_SUCCESS } else { VK_ERROR_DEVICE_LOST }
+	}
+	unsafe fn device_wait_idle(&self, device: VkDevice) -> VkResult
+	{
+		if self.state().check("vkDeviceWaitIdle", ObjectKind::Device, device.id()) { VK_SUCCESS } else { VK_ERROR_DEVICE_LOST }
+	}
+	unsafe fn allocate_memory(&self, device: VkDevice, pAllocateInfo: *const VkMemoryAllocateInfo, _: *const VkAllocationCallbacks, pMemory: *mut VkDeviceMemory) -> VkResult
+	{
+		let mut s = self.state();
+		let info = &*pAllocateInfo;
+		let mut dedicated = None;
+		let mut p = info.pNext as *const ChainHeader;
+		while !p.is_null()
+		{
+			if (*p).sType == VK_STRUCTURE_TYPE_MEMORY_DEDICATED_ALLOCATE_INFO
+			{
+				let d = &*(p as *const VkMemoryDedicatedAllocateInfo);
+				if d.image != VK_NULL_HANDLE as _ { dedicated = Some((ObjectKind::Image, d.image.id())); }
+				else if d.buffer != VK_NULL_HANDLE as _ { dedicated = Some((ObjectKind::Buffer, d.buffer.id())); }
+			}
+			p = (*p).pNext as _;
+		}
+		match s.allocate_memory(device.id(), info.allocationSize, info.memoryTypeIndex, dedicated)
+		{
+			Ok(h) => { *pMemory = FakeHandle::from_id(h); VK_SUCCESS },
+			Err(e) => e
+		}
+	}
+	unsafe fn free_memory(&self, device: VkDevice, memory: VkDeviceMemory, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkFreeMemory", ObjectKind::Device, device.id(), ObjectKind::DeviceMemory, memory.id());
+	}
+	unsafe fn map_memory(&self, device: VkDevice, memory: VkDeviceMemory, offset: VkDeviceSize, size: VkDeviceSize, _: VkMemoryMapFlags, ppData: *mut *mut c_void) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkMapMemory", ObjectKind::Device, device.id()) || !s.check("vkMapMemory", ObjectKind::DeviceMemory, memory.id())
+		{
+			return VK_ERROR_MEMORY_MAP_FAILED;
+		}
+		let instance = s.instance_of(memory.id());
+		let (msize, type_index, already) = match *s.data(memory.id())
+		{
+			ObjectData::Memory { size, type_index, mapped, .. } => (size, type_index, mapped), _ => unreachable!()
+		};
+		let size = if size == VK_WHOLE_SIZE { msize.saturating_sub(offset) } else { size };
+		if MEMORY_TYPES[type_index as usize].0 & VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT == 0
+		{
+			s.invalid(instance, "vkMapMemory", format!("memory type {} is not host visible", type_index));
+			return VK_ERROR_MEMORY_MAP_FAILED;
+		}
+		if already
+		{
+			s.invalid(instance, "vkMapMemory", format!("DeviceMemory({:#x}) is already mapped", memory.id()));
+			return VK_ERROR_MEMORY_MAP_FAILED;
+		}
+		if size == 0 || offset + size > msize
+		{
+			s.invalid(instance, "vkMapMemory", format!("range {}..+{} is out of the memory({} bytes)", offset, size, msize));
+			return VK_ERROR_MEMORY_MAP_FAILED;
+		}
+		if let ObjectData::Memory { ref mut mapped, .. } = *s.data(memory.id()) { *mapped = true; }
+		*ppData = s.memory_contents(memory.id()).as_mut_ptr().add(offset as usize) as *mut _;
+		VK_SUCCESS
+	}
+	unsafe fn unmap_memory(&self, device: VkDevice, memory: VkDeviceMemory)
+	{
+		let mut s = self.state();
+		if !s.check("vkUnmapMemory", ObjectKind::Device, device.id()) || !s.check("vkUnmapMemory", ObjectKind::DeviceMemory, memory.id()) { return; }
+		let instance = s.instance_of(memory.id());
+		let was_mapped = match *s.data(memory.id())
+		{
+			ObjectData::Memory { ref mut mapped, .. } => ::std::mem::replace(mapped, false), _ => unreachable!()
+		};
+		if !was_mapped { s.invalid(instance, "vkUnmapMemory", format!("DeviceMemory({:#x}) is not mapped", memory.id())); }
+	}
+	unsafe fn flush_mapped_memory_ranges(&self, device: VkDevice, memoryRangeCount: u32, pMemoryRanges: *const VkMappedMemoryRange) -> VkResult
+	{
+		self.state().check_mapped_ranges("vkFlushMappedMemoryRanges", device.id(), slice(pMemoryRanges, memoryRangeCount));
+		VK_SUCCESS
+	}
+	unsafe fn invalidate_mapped_memory_ranges(&self, device: VkDevice, memoryRangeCount: u32, pMemoryRanges: *const VkMappedMemoryRange) -> VkResult
+	{
+		self.state().check_mapped_ranges("vkInvalidateMappedMemoryRanges", device.id(), slice(pMemoryRanges, memoryRangeCount));
+		VK_SUCCESS
+	}
+	unsafe fn get_device_memory_commitment(&self, device: VkDevice, memory: VkDeviceMemory, pCommittedMemoryInBytes: *mut VkDeviceSize)
+	{
+		let mut s = self.state();
+		*pCommittedMemoryInBytes = 0;
+		if s.check("vkGetDeviceMemoryCommitment", ObjectKind::Device, device.id())
+			&& s.check("vkGetDeviceMemoryCommitment", ObjectKind::DeviceMemory, memory.id())
+		{
+			if let ObjectData::Memory { size, .. } = *s.data(memory.id()) { *pCommittedMemoryInBytes = size; }
+		}
+	}
+	unsafe fn bind_buffer_memory(&self, device: VkDevice, buffer: VkBuffer, memory: VkDeviceMemory, memoryOffset: VkDeviceSize) -> VkResult
+	{
+		self.state().bind("vkBindBufferMemory", device.id(), ObjectKind::Buffer, buffer.id(), memory.id(), memoryOffset)
+	}
+	unsafe fn bind_image_memory(&self, device: VkDevice, image: VkImage, memory: VkDeviceMemory, memoryOffset: VkDeviceSize) -> VkResult
+	{
+		self.state().bind("vkBindImageMemory", device.id(), ObjectKind::Image, image.id(), memory.id(), memoryOffset)
+	}
+	unsafe fn get_buffer_memory_requirements(&self, device: VkDevice, buffer: VkBuffer, pMemoryRequirements: *mut VkMemoryRequirements)
+	{
+		*pMemoryRequirements = self.state().requirements("vkGetBufferMemoryRequirements", device.id(), ObjectKind::Buffer, buffer.id());
+	}
+	unsafe fn get_image_memory_requirements(&self, device: VkDevice, image: VkImage, pMemoryRequirements: *mut VkMemoryRequirements)
+	{
+		*pMemoryRequirements = self.state().requirements("vkGetImageMemoryRequirements", device.id(), ObjectKind::Image, image.id());
+	}
+	unsafe fn get_image_sparse_memory_requirements(&self, _: VkDevice, _: VkImage, pSparseMemoryRequirementCount: *mut u32, _: *mut VkSparseImageMemoryRequirements)
+	{
+		*pSparseMemoryRequirementCount = 0;
+	}
+	unsafe fn get_physical_device_sparse_image_format_properties(&self, _: VkPhysicalDevice, _: VkFormat, _: VkImageType, _: VkSampleCountFlags,
+		_: VkImageUsageFlags, _: VkImageTiling, pPropertyCount: *mut u32, _: *mut VkSparseImageFormatProperties)
+	{
+		*pPropertyCount = 0;
+	}
+	unsafe fn queue_bind_sparse(&self, queue: VkQueue, _: u32, _: *const VkBindSparseInfo, fence: VkFence) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkQueueBindSparse", ObjectKind::Queue, queue.id()) { return VK_ERROR_DEVICE_LOST; }
+		s.signal_fence("vkQueueBindSparse", fence.id());
+		VK_SUCCESS
+	}
+	unsafe fn create_fence(&self, device: VkDevice, pCreateInfo: *const VkFenceCreateInfo, _: *const VkAllocationCallbacks, pFence: *mut VkFence) -> VkResult
+	{
+		let signaled = (*pCreateInfo).flags & VK_FENCE_CREATE_SIGNALED_BIT != 0;
+		match self.state().create_child("vkCreateFence", ObjectKind::Device, device.id(), ObjectKind::Fence, ObjectData::Fence(signaled))
+		{
+			Ok(h) => { *pFence = FakeHandle::from_id(h); VK_SUCCESS },
+			Err(e) => e
+		}
+	}
+	unsafe fn destroy_fence(&self, device: VkDevice, fence: VkFence, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkDestroyFence", ObjectKind::Device, device.id(), ObjectKind::Fence, fence.id());
+	}
+	unsafe fn reset_fences(&self, device: VkDevice, fenceCount: u32, pFences: *const VkFence) -> VkResult
+	{
+		let mut s = self.state();
+		s.check("vkResetFences", ObjectKind::Device, device.id());
+		for &f in slice(pFences, fenceCount)
+		{
+			if s.check("vkResetFences", ObjectKind::Fence, f.id()) { *s.data(f.id()) = ObjectData::Fence(false); }
+		}
+		VK_SUCCESS
+	}
+	unsafe fn get_fence_status(&self, device: VkDevice, fence: VkFence) -> VkResult
+	{
+		let mut s = self.state();
+		s.check("vkGetFenceStatus", ObjectKind::Device, device.id());
+		if !s.check("vkGetFenceStatus", ObjectKind::Fence, fence.id()) { return VK_ERROR_DEVICE_LOST; }
+		match *s.data(fence.id()) { ObjectData::Fence(true) => VK_SUCCESS, _ => VK_NOT_READY }
+	}
+	unsafe fn wait_for_fences(&self, device: VkDevice, fenceCount: u32, pFences: *const VkFence, waitAll: VkBool32, timeout: u64) -> VkResult
+	{
+		let mut s = self.state();
+		s.check("vkWaitForFences", ObjectKind::Device, device.id());
+		let mut states = Vec::with_capacity(fenceCount as _);
+		for &f in slice(pFences, fenceCount)
+		{
+			if !s.check("vkWaitForFences", ObjectKind::Fence, f.id()) { return VK_ERROR_DEVICE_LOST; }
+			if let Some(n) = s.delayed_fences.iter().position(|&d| d == f.id())
+			{
+				s.delayed_fences.remove(n);
+				s.signal_fence("vkWaitForFences", f.id());
+			}
+			states.push(match *s.data(f.id()) { ObjectData::Fence(v) => v, _ => unreachable!() });
+		}
+		let satisfied = if waitAll != 0 { states.iter().all(|&v| v) } else { states.iter().any(|&v| v) };
+		if satisfied { return VK_SUCCESS; }
+		// every submission has completed by now(the delayed ones above), so an unsignaled fence will never be signaled
+		if timeout == u64::MAX
+		{
+			let instance = s.instance_of(device.id());
+			s.invalid(instance, "vkWaitForFences", "waiting infinitely for fences which are never signaled".to_owned());
+		}
+		VK_TIMEOUT
+	}
+	unsafe fn create_semaphore(&self, device: VkDevice, _: *const VkSemaphoreCreateInfo, _: *const VkAllocationCallbacks, pSemaphore: *mut VkSemaphore) -> VkResult
+	{
+		match self.state().create_child("vkCreateSemaphore", ObjectKind::Device, device.id(), ObjectKind::Semaphore, ObjectData::Semaphore(false))
+		{
+			Ok(h) => { *pSemaphore = FakeHandle::from_id(h); VK_SUCCESS },
+			Err(e) => e
+		}
+	}
+	unsafe fn destroy_semaphore(&self, device: VkDevice, semaphore: VkSemaphore, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkDestroySemaphore", ObjectKind::Device, device.id(), ObjectKind::Semaphore, semaphore.id());
+	}
+	unsafe fn create_event(&self, device: VkDevice, _: *const VkEventCreateInfo, _: *const VkAllocationCallbacks, pEvent: *mut VkEvent) -> VkResult
+	{
+		match self.state().create_child("vkCreateEvent", ObjectKind::Device, device.id(), ObjectKind::Event, ObjectData::Event(false))
+		{
+			Ok(h) => { *pEvent = FakeHandle::from_id(h); VK_SUCCESS },
+			Err(e) => e
+		}
+	}
+	unsafe fn destroy_event(&self, device: VkDevice, event: VkEvent, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkDestroyEvent", ObjectKind::Device, device.id(), ObjectKind::Event, event.id());
+	}
+	unsafe fn get_event_status(&self, device: VkDevice, event: VkEvent) -> VkResult
+	{
+		let mut s = self.state();
+		s.check("vkGetEventStatus", ObjectKind::Device, device.id());
+		if !s.check("vkGetEventStatus", ObjectKind::Event, event.id()) { return VK_ERROR_DEVICE_LOST; }
+		match *s.data(event.id()) { ObjectData::Event(true) => VK_EVENT_SET, _ => VK_EVENT_RESET }
+	}
+	unsafe fn set_event(&self, device: VkDevice, event: VkEvent) -> VkResult { self.state().set_event("vkSetEvent", device.id(), event.id(), true) }
+	unsafe fn reset_event(&self, device: VkDevice, event: VkEvent) -> VkResult { self.state().set_event("vkResetEvent", device.id(), event.id(), false) }
+	unsafe fn create_query_pool(&self, device: VkDevice, _: *const VkQueryPoolCreateInfo, _: *const VkAllocationCallbacks, pQueryPool: *mut VkQueryPool) -> VkResult
+	{
+		match self.state().create_child("vkCreateQueryPool", ObjectKind::Device, device.id(), ObjectKind::QueryPool, ObjectData::None)
+		{
+			Ok(h) => { *pQueryPool = FakeHandle::from_id(h); VK_SUCCESS },
+			Err(e) => e
+		}
+	}
+	unsafe fn destroy_query_pool(&self, device: VkDevice, queryPool: VkQueryPool, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkDestroyQueryPool", ObjectKind::Device, device.id(), ObjectKind::QueryPool, queryPool.id());
+	}
+	unsafe fn get_query_pool_results(&self, device: VkDevice, queryPool: VkQueryPool, _: u32, _: u32, dataSize: size_t, pData: *mut c_void,
+		_: VkDeviceSize, _: VkQueryResultFlags) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkGetQueryPoolResults", ObjectKind::Device, device.id()) || !s.check("vkGetQueryPoolResults", ObjectKind::QueryPool, queryPool.id())
+		{
+			return VK_ERROR_DEVICE_LOST;
+		}
+		// nothing is rendered, so every query reports zero
+		::std::ptr::write_bytes(pData as *mut u8, 0, dataSize);
+		VK_SUCCESS
+	}
+	unsafe fn create_buffer(&self, device: VkDevice, pCreateInfo: *const VkBufferCreateInfo, _: *const VkAllocationCallbacks, pBuffer: *mut VkBuffer) -> VkResult
+	{
+		let mut s = self.state();
+		let size = (*pCreateInfo).size;
+		if size == 0
+		{
+			let instance = s.instance_of(device.id());
+			s.invalid(instance, "vkCreateBuffer", "size must be greater than 0".to_owned());
+		}
+		match s.create_child("vkCreateBuffer", ObjectKind::Device, device.id(), ObjectKind::Buffer, ObjectData::Buffer { size, binding: None })
+		{
+			Ok(h) => { *pBuffer = FakeHandle::from_id(h); VK_SUCCESS },
+			Err(e) => e
+		}
+	}
+	unsafe fn destroy_buffer(&self, device: VkDevice, buffer: VkBuffer, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkDestroyBuffer", ObjectKind::Device, device.id(), ObjectKind::Buffer, buffer.id());
+	}
+	FakeDeviceChild!(create_buffer_view / destroy_buffer_view (VkBufferViewCreateInfo, VkBufferView) = BufferView ["vkCreateBufferView", "vkDestroyBufferView"]);
+	unsafe fn create_image(&self, device: VkDevice, pCreateInfo: *const VkImageCreateInfo, _: *const VkAllocationCallbacks, pImage: *mut VkImage) -> VkResult
+	{
+		let ci = &*pCreateInfo;
+		let mut s = self.state();
+		if let Err(e) = image_format_properties(ci.format, ci.tiling)
+		{
+			let instance = s.instance_of(device.id());
+			s.invalid(instance, "vkCreateImage", format!("format {} with tiling {} is not supported", ci.format, ci.tiling));
+			return e;
+		}
+		let shape = ImageShape { format: ci.format, extent: ci.extent.clone(), mip_levels: ci.mipLevels, array_layers: ci.arrayLayers, tiling: ci.tiling, usage: ci.usage };
+		match s.create_child("vkCreateImage", ObjectKind::Device, device.id(), ObjectKind::Image, ObjectData::Image { shape, binding: None })
+		{
+			Ok(h) => { *pImage = FakeHandle::from_id(h); VK_SUCCESS },
+			Err(e) => e
+		}
+	}
+	unsafe fn destroy_image(&self, device: VkDevice, image: VkImage, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkDestroyImage", ObjectKind::Device, device.id(), ObjectKind::Image, image.id());
+	}
+	unsafe fn get_image_subresource_layout(&self, device: VkDevice, image: VkImage, pSubresource: *const VkImageSubresource, pLayout: *mut VkSubresourceLayout)
+	{
+		let mut s = self.state();
+		*pLayout = zeroed();
+		if !s.check("vkGetImageSubresourceLayout", ObjectKind::Device, device.id())
+			|| !s.check("vkGetImageSubresourceLayout", ObjectKind::Image, image.id()) { return; }
+		let sub = &*pSubresource;
+		let (offset, row_pitch, depth_pitch, array_pitch, end) =
+		{
+			let shape = s.image_shape(image.id());
+			let (offset, row_pitch, depth_pitch) = subresource_placement(shape, sub.arrayLayer, sub.mipLevel);
+			let end = if sub.mipLevel + 1 < shape.mip_levels { subresource_placement(shape, sub.arrayLayer, sub.mipLevel + 1).0 }
+				else { subresource_placement(shape, sub.arrayLayer + 1, 0).0 };
+			(offset, row_pitch, depth_pitch, subresource_placement(shape, 1, 0).0, end)
+		};
+		*pLayout = VkSubresourceLayout { offset, size: end - offset, rowPitch: row_pitch, arrayPitch: array_pitch, depthPitch: depth_pitch };
+	}
+	FakeDeviceChild!(create_image_view / destroy_image_view (VkImageViewCreateInfo, VkImageView) = ImageView ["vkCreateImageView", "vkDestroyImageView"]);
+	FakeDeviceChild!(create_shader_module / destroy_shader_module (VkShaderModuleCreateInfo, VkShaderModule) = ShaderModule ["vkCreateShaderModule", "vkDestroyShaderModule"]);
+	FakeDeviceChild!(create_pipeline_cache / destroy_pipeline_cache (VkPipelineCacheCreateInfo, VkPipelineCache) = PipelineCache ["vkCreatePipelineCache", "vkDestroyPipelineCache"]);
+	unsafe fn get_pipeline_cache_data(&self, device: VkDevice, pipelineCache: VkPipelineCache, pDataSize: *mut size_t, _: *mut c_void) -> VkResult
+	{
+		let mut s = self.state();
+		s.check("vkGetPipelineCacheData", ObjectKind::Device, device.id());
+		s.check("vkGetPipelineCacheData", ObjectKind::PipelineCache, pipelineCache.id());
+		*pDataSize = 0;
+		VK_SUCCESS
+	}
+	unsafe fn merge_pipeline_caches(&self, device: VkDevice, dstCache: VkPipelineCache, srcCacheCount: u32, pSrcCaches: *const VkPipelineCache) -> VkResult
+	{
+		let mut s = self.state();
+		s.check("vkMergePipelineCaches", ObjectKind::Device, device.id());
+		s.check("vkMergePipelineCaches", ObjectKind::PipelineCache, dstCache.id());
+		for &c in slice(pSrcCaches, srcCacheCount) { s.check("vkMergePipelineCaches", ObjectKind::PipelineCache, c.id()); }
+		VK_SUCCESS
+	}
+	unsafe fn create_graphics_pipelines(&self, device: VkDevice, pipelineCache: VkPipelineCache, createInfoCount: u32, _: *const VkGraphicsPipelineCreateInfo,
+		_: *const VkAllocationCallbacks, pPipelines: *mut VkPipeline) -> VkResult
+	{
+		self.state().create_pipelines("vkCreateGraphicsPipelines", device.id(), pipelineCache.id(), createInfoCount, pPipelines)
+	}
+	unsafe fn create_compute_pipelines(&self, device: VkDevice, pipelineCache: VkPipelineCache, createInfoCount: u32, _: *const VkComputePipelineCreateInfo,
+		_: *const VkAllocationCallbacks, pPipelines: *mut VkPipeline) -> VkResult
+	{
+		self.state().create_pipelines("vkCreateComputePipelines", device.id(), pipelineCache.id(), createInfoCount, pPipelines)
+	}
+	unsafe fn destroy_pipeline(&self, device: VkDevice, pipeline: VkPipeline, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkDestroyPipeline", ObjectKind::Device, device.id(), ObjectKind::Pipeline, pipeline.id());
+	}
+	FakeDeviceChild!(create_pipeline_layout / destroy_pipeline_layout (VkPipelineLayoutCreateInfo, VkPipelineLayout) = PipelineLayout ["vkCreatePipelineLayout", "vkDestroyPipelineLayout"]);
+	FakeDeviceChild!(create_sampler / destroy_sampler (VkSamplerCreateInfo, VkSampler) = Sampler ["vkCreateSampler", "vkDestroySampler"]);
+	FakeDeviceChild!(create_descriptor_set_layout / destroy_descriptor_set_layout (VkDescriptorSetLayoutCreateInfo, VkDescriptorSetLayout) = DescriptorSetLayout
+		["vkCreateDescriptorSetLayout", "vkDestroyDescriptorSetLayout"]);
+	FakeDeviceChild!(create_descriptor_pool / destroy_descriptor_pool (VkDescriptorPoolCreateInfo, VkDescriptorPool) = DescriptorPool ["vkCreateDescriptorPool", "vkDestroyDescriptorPool"]);
+	unsafe fn reset_descriptor_pool(&self, device: VkDevice, descriptorPool: VkDescriptorPool, _: VkDescriptorPoolResetFlags) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkResetDescriptorPool", ObjectKind::Device, device.id()) || !s.check("vkResetDescriptorPool", ObjectKind::DescriptorPool, descriptorPool.id())
+		{
+			return VK_ERROR_DEVICE_LOST;
+		}
+		let sets = s.objects.iter().filter(|&(_, o)| o.alive && o.parent == descriptorPool.id()).map(|(&h, _)| h).collect::<Vec<_>>();
+		for h in sets { s.kill(h); }
+		VK_SUCCESS
+	}
+	unsafe fn allocate_descriptor_sets(&self, device: VkDevice, pAllocateInfo: *const VkDescriptorSetAllocateInfo, pDescriptorSets: *mut VkDescriptorSet) -> VkResult
+	{
+		let mut s = self.state();
+		let info = &*pAllocateInfo;
+		if !s.check("vkAllocateDescriptorSets", ObjectKind::Device, device.id()) { return VK_ERROR_DEVICE_LOST; }
+		for &l in slice(info.pSetLayouts, info.descriptorSetCount) { s.check("vkAllocateDescriptorSets", ObjectKind::DescriptorSetLayout, l.id()); }
+		for n in 0 .. info.descriptorSetCount as usize
+		{
+			match s.create_child("vkAllocateDescriptorSets", ObjectKind::DescriptorPool, info.descriptorPool.id(), ObjectKind::DescriptorSet, ObjectData::None)
+			{
+				Ok(h) => *pDescriptorSets.add(n) = FakeHandle::from_id(h),
+				Err(e) => return e
+			}
+		}
+		VK_SUCCESS
+	}
+	unsafe fn free_descriptor_sets(&self, device: VkDevice, descriptorPool: VkDescriptorPool, descriptorSetCount: u32, pDescriptorSets: *const VkDescriptorSet) -> VkResult
+	{
+		let mut s = self.state();
+		s.check("vkFreeDescriptorSets", ObjectKind::Device, device.id());
+		for &d in slice(pDescriptorSets, descriptorSetCount)
+		{
+			s.destroy_child("vkFreeDescriptorSets", ObjectKind::DescriptorPool, descriptorPool.id(), ObjectKind::DescriptorSet, d.id());
+		}
+		VK_SUCCESS
+	}
+	unsafe fn update_descriptor_sets(&self, device: VkDevice, descriptorWriteCount: u32, pDescriptorWrites: *const VkWriteDescriptorSet,
+		descriptorCopyCount: u32, pDescriptorCopies: *const VkCopyDescriptorSet)
+	{
+		let mut s = self.state();
+		s.check("vkUpdateDescriptorSets", ObjectKind::Device, device.id());
+		for w in slice(pDescriptorWrites, descriptorWriteCount) { s.check("vkUpdateDescriptorSets", ObjectKind::DescriptorSet, w.dstSet.id()); }
+		for c in slice(pDescriptorCopies, descriptorCopyCount)
+		{
+			s.check("vkUpdateDescriptorSets", ObjectKind::DescriptorSet, c.srcSet.id());
+			s.check("vkUpdateDescriptorSets", ObjectKind::DescriptorSet, c.dstSet.id());
+		}
+	}
+	FakeDeviceChild!(create_framebuffer / destroy_framebuffer (VkFramebufferCreateInfo, VkFramebuffer) = Framebuffer ["vkCreateFramebuffer", "vkDestroyFramebuffer"]);
+	FakeDeviceChild!(create_render_pass / destroy_render_pass (VkRenderPassCreateInfo, VkRenderPass) = RenderPass ["vkCreateRenderPass", "vkDestroyRenderPass"]);
+	unsafe fn get_render_area_granularity(&self, device: VkDevice, renderPass: VkRenderPass, pGranularity: *mut VkExtent2D)
+	{
+		let mut s = self.state();
+		s.check("vkGetRenderAreaGranularity", ObjectKind::Device, device.id());
+		s.check("vkGetRenderAreaGranularity", ObjectKind::RenderPass, renderPass.id());
+		*pGranularity = VkExtent2D { width: 1, height: 1 };
+	}
+	unsafe fn create_command_pool(&self, device: VkDevice, pCreateInfo: *const VkCommandPoolCreateInfo, _: *const VkAllocationCallbacks, pCommandPool: *mut VkCommandPool) -> VkResult
+	{
+		let mut s = self.state();
+		let ci = &*pCreateInfo;
+		if ci.queueFamilyIndex as usize >= QUEUE_FAMILIES.len()
+		{
+			let instance = s.instance_of(device.id());
+			s.invalid(instance, "vkCreateCommandPool", format!("queue family {} does not exist", ci.queueFamilyIndex));
+			return VK_ERROR_INITIALIZATION_FAILED;
+		}
+		let data = ObjectData::CommandPool { flags: ci.flags };
+		match s.create_child("vkCreateCommandPool", ObjectKind::Device, device.id(), ObjectKind::CommandPool, data)
+		{
+			Ok(h) => { *pCommandPool = FakeHandle::from_id(h); VK_SUCCESS },
+			Err(e) => e
+		}
+	}
+	unsafe fn destroy_command_pool(&self, device: VkDevice, commandPool: VkCommandPool, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkDestroyCommandPool", ObjectKind::Device, device.id(), ObjectKind::CommandPool, commandPool.id());
+	}
+	unsafe fn reset_command_pool(&self, device: VkDevice, commandPool: VkCommandPool, _: VkCommandPoolResetFlags) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkResetCommandPool", ObjectKind::Device, device.id()) || !s.check("vkResetCommandPool", ObjectKind::CommandPool, commandPool.id())
+		{
+			return VK_ERROR_DEVICE_LOST;
+		}
+		for o in s.objects.values_mut().filter(|o| o.alive && o.parent == commandPool.id())
+		{
+			if let ObjectData::CommandBuffer { ref mut state, ref mut commands, .. } = o.data
+			{
+				*state = CommandBufferState::Initial; commands.clear();
+			}
+		}
+		VK_SUCCESS
+	}
+	unsafe fn allocate_command_buffers(&self, device: VkDevice, pAllocateInfo: *const VkCommandBufferAllocateInfo, pCommandBuffers: *mut VkCommandBuffer) -> VkResult
+	{
+		let mut s = self.state();
+		let info = &*pAllocateInfo;
+		if !s.check("vkAllocateCommandBuffers", ObjectKind::Device, device.id()) { return VK_ERROR_DEVICE_LOST; }
+		for n in 0 .. info.commandBufferCount as usize
+		{
+			let data = ObjectData::CommandBuffer { level: info.level, state: CommandBufferState::Initial, one_time: false, commands: Vec::new() };
+			match s.create_child("vkAllocateCommandBuffers", ObjectKind::CommandPool, info.commandPool.id(), ObjectKind::CommandBuffer, data)
+			{
+				Ok(h) => *pCommandBuffers.add(n) = FakeHandle::from_id(h),
+				Err(e) => return e
+			}
+		}
+		VK_SUCCESS
+	}
+	unsafe fn free_command_buffers(&self, device: VkDevice, commandPool: VkCommandPool, commandBufferCount: u32, pCommandBuffers: *const VkCommandBuffer)
+	{
+		let mut s = self.state();
+		s.check("vkFreeCommandBuffers", ObjectKind::Device, device.id());
+		for &cb in slice(pCommandBuffers, commandBufferCount)
+		{
+			s.destroy_child("vkFreeCommandBuffers", ObjectKind::CommandPool, commandPool.id(), ObjectKind::CommandBuffer, cb.id());
+		}
+	}
+	unsafe fn begin_command_buffer(&self, commandBuffer: VkCommandBuffer, pBeginInfo: *const VkCommandBufferBeginInfo) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkBeginCommandBuffer", ObjectKind::CommandBuffer, commandBuffer.id()) { return VK_ERROR_DEVICE_LOST; }
+		let instance = s.instance_of(commandBuffer.id());
+		let pool = s.objects[&commandBuffer.id()].parent;
+		let resettable = match *s.data(pool) { ObjectData::CommandPool { flags, .. } => flags & VK_COMMAND_POOL_CREATE_RESET_COMMAND_BUFFER_BIT != 0, _ => unreachable!() };
+		let current = match *s.data(commandBuffer.id()) { ObjectData::CommandBuffer { state, .. } => state, _ => unreachable!() };
+		match current
+		{
+			CommandBufferState::Initial => (),
+			CommandBufferState::Recording =>
+			{
+				s.invalid(instance, "vkBeginCommandBuffer", format!("CommandBuffer({:#x}) is already in the recording state", commandBuffer.id()));
+				return VK_ERROR_VALIDATION_FAILED_EXT;
+			},
+			_ if !resettable => s.invalid(instance, "vkBeginCommandBuffer",
+				format!("CommandBuffer({:#x}) cannot be reset implicitly(the pool is created without RESET_COMMAND_BUFFER flag)", commandBuffer.id())),
+			_ => ()
+		}
+		let one = (*pBeginInfo).flags & VK_COMMAND_BUFFER_USAGE_ONE_TIME_SUBMIT_BIT != 0;
+		if let ObjectData::CommandBuffer { ref mut state, ref mut one_time, ref mut commands, .. } = *s.data(commandBuffer.id())
+		{
+			*state = CommandBufferState::Recording; *one_time = one; commands.clear();
+		}
+		#[cfg(any(feature = "VK_EXT_debug_utils", feature = "VK_EXT_debug_marker"))] s.labels.remove(&commandBuffer.id());
+		VK_SUCCESS
+	}
+	unsafe fn end_command_buffer(&self, commandBuffer: VkCommandBuffer) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.recording("vkEndCommandBuffer", commandBuffer) { return VK_ERROR_VALIDATION_FAILED_EXT; }
+		if let ObjectData::CommandBuffer { ref mut state, .. } = *s.data(commandBuffer.id()) { *state = CommandBufferState::Executable; }
+		VK_SUCCESS
+	}
+	unsafe fn reset_command_buffer(&self, commandBuffer: VkCommandBuffer, _: VkCommandBufferResetFlags) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkResetCommandBuffer", ObjectKind::CommandBuffer, commandBuffer.id()) { return VK_ERROR_DEVICE_LOST; }
+		let instance = s.instance_of(commandBuffer.id());
+		let pool = s.objects[&commandBuffer.id()].parent;
+		if let ObjectData::CommandPool { flags, .. } = *s.data(pool)
+		{
+			if flags & VK_COMMAND_POOL_CREATE_RESET_COMMAND_BUFFER_BIT == 0
+			{
+				s.invalid(instance, "vkResetCommandBuffer", "the pool is created without RESET_COMMAND_BUFFER flag".to_owned());
+			}
+		}
+		if let ObjectData::CommandBuffer { ref mut state, ref mut commands, .. } = *s.data(commandBuffer.id())
+		{
+			*state = CommandBufferState::Initial; commands.clear();
+		}
+		VK_SUCCESS
+	}
+	FakeCommand!(cmd_bind_pipeline = "vkCmdBindPipeline"; pipelineBindPoint: VkPipelineBindPoint, pipeline: VkPipeline);
+	FakeCommand!(cmd_set_viewport = "vkCmdSetViewport"; firstViewport: u32, viewportCount: u32, pViewports: *const VkViewport);
+	FakeCommand!(cmd_set_scissor = "vkCmdSetScissor"; firstScissor: u32, scissorCount: u32, pScissors: *const VkRect2D);
+	FakeCommand!(cmd_set_line_width = "vkCmdSetLineWidth"; lineWidth: c_float);
+	FakeCommand!(cmd_set_depth_bias = "vkCmdSetDepthBias"; depthBiasConstantFactor: c_float, depthBiasClamp: c_float, depthBiasSlopeFactor: c_float);
+	FakeCommand!(cmd_set_blend_constants = "vkCmdSetBlendConstants"; blendConstants: [c_float; 4]);
+	FakeCommand!(cmd_set_depth_bounds = "vkCmdSetDepthBounds"; minDepthBounds: c_float, maxDepthBounds: c_float);
+	FakeCommand!(cmd_set_stencil_compare_mask = "vkCmdSetStencilCompareMask"; faceMask: VkStencilFaceFlags, compareMask: u32);
+	FakeCommand!(cmd_set_stencil_write_mask = "vkCmdSetStencilWriteMask"; faceMask: VkStencilFaceFlags, writeMask: u32);
+	FakeCommand!(cmd_set_stencil_reference = "vkCmdSetStencilReference"; faceMask: VkStencilFaceFlags, reference: u32);
+	FakeCommand!(cmd_bind_descriptor_sets = "vkCmdBindDescriptorSets"; pipelineBindPoint: VkPipelineBindPoint, layout: VkPipelineLayout, firstSet: u32,
+		descriptorSetCount: u32, pDescriptorSets: *const VkDescriptorSet, dynamicOffsetCount: u32, pDynamicOffsets: *const u32);
+	FakeCommand!(cmd_bind_index_buffer = "vkCmdBindIndexBuffer"; buffer: VkBuffer, offset: VkDeviceSize, indexType: VkIndexType);
+	FakeCommand!(cmd_bind_vertex_buffers = "vkCmdBindVertexBuffers"; firstBinding: u32, bindingCount: u32, pBuffers: *const VkBuffer, pOffsets: *const VkDeviceSize);
+	FakeCommand!(cmd_draw = "vkCmdDraw"; vertexCount: u32, instanceCount: u32, firstVertex: u32, firstInstance: u32);
+	FakeCommand!(cmd_draw_indexed = "vkCmdDrawIndexed"; indexCount: u32, instanceCount: u32, firstIndex: u32, vertexOffset: i32, firstInstance: u32);
+	FakeCommand!(cmd_draw_indirect = "vkCmdDrawIndirect"; buffer: VkBuffer, offset: VkDeviceSize, drawCount: u32, stride: u32);
+	FakeCommand!(cmd_draw_indexed_indirect = "vkCmdDrawIndexedIndirect"; buffer: VkBuffer, offset: VkDeviceSize, drawCount: u32, stride: u32);
+	FakeCommand!(cmd_dispatch = "vkCmdDispatch"; groupCountX: u32, groupCountY: u32, groupCountZ: u32);
+	FakeCommand!(cmd_dispatch_indirect = "vkCmdDispatchIndirect"; buffer: VkBuffer, offset: VkDeviceSize);
+	unsafe fn cmd_copy_buffer(&self, commandBuffer: VkCommandBuffer, srcBuffer: VkBuffer, dstBuffer: VkBuffer, regionCount: u32, pRegions: *const VkBufferCopy)
+	{
+		let mut s = self.state();
+		if s.recording("vkCmdCopyBuffer", commandBuffer) && s.check("vkCmdCopyBuffer", ObjectKind::Buffer, srcBuffer.id())
+			&& s.check("vkCmdCopyBuffer", ObjectKind::Buffer, dstBuffer.id())
+		{
+			s.push_command(commandBuffer, Command::CopyBuffer { src: srcBuffer.id(), dst: dstBuffer.id(), regions: slice(pRegions, regionCount).to_vec() });
+		}
+	}
+	FakeCommand!(cmd_copy_image = "vkCmdCopyImage"; srcImage: VkImage, srcImageLayout: VkImageLayout, dstImage: VkImage, dstImageLayout: VkImageLayout,
+		regionCount: u32, pRegions: *const VkImageCopy);
+	FakeCommand!(cmd_blit_image = "vkCmdBlitImage"; srcImage: VkImage, srcImageLayout: VkImageLayout, dstImage: VkImage, dstImageLayout: VkImageLayout,
+		regionCount: u32, pRegions: *const VkImageBlit, filter: VkFilter);
+	unsafe fn cmd_copy_buffer_to_image(&self, commandBuffer: VkCommandBuffer, srcBuffer: VkBuffer, dstImage: VkImage, _: VkImageLayout,
+		regionCount: u32, pRegions: *const VkBufferImageCopy)
+	{
+		let mut s = self.state();
+		if s.recording("vkCmdCopyBufferToImage", commandBuffer) && s.check("vkCmdCopyBufferToImage", ObjectKind::Buffer, srcBuffer.id())
+			&& s.check("vkCmdCopyBufferToImage", ObjectKind::Image, dstImage.id())
+		{
+			s.push_command(commandBuffer, Command::CopyBufferToImage { src: srcBuffer.id(), dst: dstImage.id(), regions: slice(pRegions, regionCount).to_vec() });
+		}
+	}
+	unsafe fn cmd_copy_image_to_buffer(&self, commandBuffer: VkCommandBuffer, srcImage: VkImage, _: VkImageLayout, dstBuffer: VkBuffer,
+		regionCount: u32, pRegions: *const VkBufferImageCopy)
+	{
+		let mut s = self.state();
+		if s.recording("vkCmdCopyImageToBuffer", commandBuffer) && s.check("vkCmdCopyImageToBuffer", ObjectKind::Image, srcImage.id())
+			&& s.check("vkCmdCopyImageToBuffer", ObjectKind::Buffer, dstBuffer.id())
+		{
+			s.push_command(commandBuffer, Command::CopyImageToBuffer { src: srcImage.id(), dst: dstBuffer.id(), regions: slice(pRegions, regionCount).to_vec() });
+		}
+	}
+	unsafe fn cmd_update_buffer(&self, commandBuffer: VkCommandBuffer, dstBuffer: VkBuffer, dstOffset: VkDeviceSize, dataSize: VkDeviceSize, pData: *const c_void)
+	{
+		let mut s = self.state();
+		if s.recording("vkCmdUpdateBuffer", commandBuffer) && s.check("vkCmdUpdateBuffer", ObjectKind::Buffer, dstBuffer.id())
+		{
+			if dataSize == 0
+			{
+				let instance = s.instance_of(commandBuffer.id());
+				s.invalid(instance, "vkCmdUpdateBuffer", "dataSize must be greater than 0".to_owned()); return;
+			}
+			let data = slice(pData as *const u8, dataSize as _).to_vec();
+			s.push_command(commandBuffer, Command::UpdateBuffer { dst: dstBuffer.id(), offset: dstOffset, data });
+		}
+	}
+	unsafe fn cmd_fill_buffer(&self, commandBuffer: VkCommandBuffer, dstBuffer: VkBuffer, dstOffset: VkDeviceSize, size: VkDeviceSize, data: u32)
+	{
+		let mut s = self.state();
+		if s.recording("vkCmdFillBuffer", commandBuffer) && s.check("vkCmdFillBuffer", ObjectKind::Buffer, dstBuffer.id())
+		{
+			if size == 0
+			{
+				let instance = s.instance_of(commandBuffer.id());
+				s.invalid(instance, "vkCmdFillBuffer", "size must be greater than 0".to_owned()); return;
+			}
+			s.push_command(commandBuffer, Command::FillBuffer { dst: dstBuffer.id(), offset: dstOffset, size, data });
+		}
+	}
+	FakeCommand!(cmd_clear_color_image = "vkCmdClearColorImage"; image: VkImage, imageLayout: VkImageLayout, pColor: *const VkClearColorValue,
+		rangeCount: u32, pRanges: *const VkImageSubresourceRange);
+	FakeCommand!(cmd_clear_depth_stencil_image = "vkCmdClearDepthStencilImage"; image: VkImage, imageLayout: VkImageLayout,
+		pDepthStencil: *const VkClearDepthStencilValue, rangeCount: u32, pRanges: *const VkImageSubresourceRange);
+	FakeCommand!(cmd_clear_attachments = "vkCmdClearAttachments"; attachmentCount: u32, pAttachments: *const VkClearAttachment, rectCount: u32, pRects: *const VkClearRect);
+	FakeCommand!(cmd_resolve_image = "vkCmdResolveImage"; srcImage: VkImage, srcImageLayout: VkImageLayout, dstImage: VkImage, dstImageLayout: VkImageLayout,
+		regionCount: u32, pRegions: *const VkImageResolve);
+	FakeCommand!(cmd_set_event = "vkCmdSetEvent"; event: VkEvent, stageMask: VkPipelineStageFlags);
+	FakeCommand!(cmd_reset_event = "vkCmdResetEvent"; event: VkEvent, stageMask: VkPipelineStageFlags);
+	FakeCommand!(cmd_wait_events = "vkCmdWaitEvents"; eventCount: u32, pEvents: *const VkEvent, srcStageMask: VkPipelineStageFlags, dstStageMask: VkPipelineStageFlags,
+		memoryBarrierCount: u32, pMemoryBarriers: *const VkMemoryBarrier, bufferMemoryBarrierCount: u32, pBufferMemoryBarriers: *const VkBufferMemoryBarrier,
+		imageMemoryBarrierCount: u32, pImageMemoryBarriers: *const VkImageMemoryBarrier);
+	FakeCommand!(cmd_pipeline_barrier = "vkCmdPipelineBarrier"; srcStageMask: VkPipelineStageFlags, dstStageMask: VkPipelineStageFlags, dependencyFlags: VkDependencyFlags,
+		memoryBarrierCount: u32, pMemoryBarriers: *const VkMemoryBarrier, bufferMemoryBarrierCount: u32, pBufferMemoryBarriers: *const VkBufferMemoryBarrier,
+		imageMemoryBarrierCount: u32, pImageMemoryBarriers: *const VkImageMemoryBarrier);
+	FakeCommand!(cmd_begin_query = "vkCmdBeginQuery"; queryPool: VkQueryPool, query: u32, flags: VkQueryControlFlags);
+	FakeCommand!(cmd_end_query = "vkCmdEndQuery"; queryPool: VkQueryPool, query: u32);
+	FakeCommand!(cmd_reset_query_pool = "vkCmdResetQueryPool"; queryPool: VkQueryPool, firstQuery: u32, queryCount: u32);
+	FakeCommand!(cmd_write_timestamp = "vkCmdWriteTimestamp"; pipelineStage: VkPipelineStageFlags, queryPool: VkQueryPool, query: u32);
+	FakeCommand!(cmd_copy_query_pool_results = "vkCmdCopyQueryPoolResults"; queryPool: VkQueryPool, firstQuery: u32, queryCount: u32,
+		dstBuffer: VkBuffer, dstOffset: VkDeviceSize, stride: VkDeviceSize, flags: VkQueryResultFlags);
+	FakeCommand!(cmd_push_constants = "vkCmdPushConstants"; layout: VkPipelineLayout, stageFlags: VkShaderStageFlags, offset: u32, size: u32, pValues: *const c_void);
+	FakeCommand!(cmd_begin_render_pass = "vkCmdBeginRenderPass"; pRenderPassBegin: *const VkRenderPassBeginInfo, contents: VkSubpassContents);
+	FakeCommand!(cmd_next_subpass = "vkCmdNextSubpass"; contents: VkSubpassContents);
+	FakeCommand!(cmd_end_render_pass = "vkCmdEndRenderPass";);
+	unsafe fn cmd_execute_commands(&self, commandBuffer: VkCommandBuffer, commandBufferCount: u32, pCommandBuffers: *const VkCommandBuffer)
+	{
+		let mut s = self.state();
+		if !s.recording("vkCmdExecuteCommands", commandBuffer) { return; }
+		let instance = s.instance_of(commandBuffer.id());
+		let mut secondaries = Vec::with_capacity(commandBufferCount as _);
+		for &cb in slice(pCommandBuffers, commandBufferCount)
+		{
+			if !s.check("vkCmdExecuteCommands", ObjectKind::CommandBuffer, cb.id()) { continue; }
+			match *s.data(cb.id())
+			{
+				ObjectData::CommandBuffer { level: VK_COMMAND_BUFFER_LEVEL_SECONDARY, state: CommandBufferState::Executable, .. } => secondaries.push(cb.id()),
+				_ => s.invalid(instance, "vkCmdExecuteCommands", format!("CommandBuffer({:#x}) is not an executable secondary command buffer", cb.id()))
+			}
+		}
+		s.push_command(commandBuffer, Command::ExecuteCommands(secondaries));
+	}
+	#[cfg(feature = "VK_KHR_push_descriptor")]
+	FakeCommand!(cmd_push_descriptor_set_khr = "vkCmdPushDescriptorSetKHR"; pipelineBindPoint: VkPipelineBindPoint, layout: VkPipelineLayout, set: u32,
+		descriptorWriteCount: u32, pDescriptorWrites: *const VkWriteDescriptorSet);
+	#[cfg(feature = "VK_KHR_descriptor_update_template")]
+	FakeCommand!(push_descriptor_set_with_template_khr = "vkCmdPushDescriptorSetWithTemplateKHR"; descriptorUpdateTemplate: VkDescriptorUpdateTemplateKHR,
+		layout: VkPipelineLayout, set: u32, pData: *const c_void);
+	#[cfg(feature = "VK_KHR_get_memory_requirements2")]
+	unsafe fn get_image_memory_requirements2_khr(&self, device: VkDevice, pInfo: *const VkImageMemoryRequirementsInfo2, pMemoryRequirements: *mut VkMemoryRequirements2)
+	{
+		self.get_image_memory_requirements2(device, pInfo, pMemoryRequirements);
+	}
+	#[cfg(feature = "VK_KHR_get_memory_requirements2")]
+	unsafe fn get_buffer_memory_requirements2_khr(&self, device: VkDevice, pInfo: *const VkBufferMemoryRequirementsInfo2, pMemoryRequirements: *mut VkMemoryRequirements2)
+	{
+		self.get_buffer_memory_requirements2(device, pInfo, pMemoryRequirements);
+	}
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+	unsafe fn get_physical_device_features2_khr(&self, physicalDevice: VkPhysicalDevice, pFeatures: *mut VkPhysicalDeviceFeatures2)
+	{
+		self.physical_device_features2(physicalDevice, pFeatures);
+	}
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+	unsafe fn get_physical_device_properties2_khr(&self, physicalDevice: VkPhysicalDevice, pProperties: *mut VkPhysicalDeviceProperties2)
+	{
+		self.physical_device_properties2(physicalDevice, pProperties);
+	}
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+	unsafe fn get_physical_device_format_properties2_khr(&self, physicalDevice: VkPhysicalDevice, format: VkFormat, pFormatProperties: *mut VkFormatProperties2)
+	{
+		self.physical_device_format_properties2(physicalDevice, format, pFormatProperties);
+	}
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+	unsafe fn get_physical_device_queue_family_properties2_khr(&self, physicalDevice: VkPhysicalDevice, pQueueFamilyPropertyCount: *mut u32,
+		pQueueFamilyProperties: *mut VkQueueFamilyProperties2)
+	{
+		self.physical_device_queue_family_properties2(physicalDevice, pQueueFamilyPropertyCount, pQueueFamilyProperties);
+	}
+	#[cfg(feature = "VK_KHR_get_physical_device_properties2")]
+	unsafe fn get_physical_device_memory_properties2_khr(&self, physicalDevice: VkPhysicalDevice, pMemoryProperties: *mut VkPhysicalDeviceMemoryProperties2)
+	{
+		self.physical_device_memory_properties2(physicalDevice, pMemoryProperties);
+	}
+	#[cfg(feature = "VK_EXT_debug_marker")]
+	unsafe fn debug_marker_set_object_name_ext(&self, device: VkDevice, pNameInfo: *const VkDebugMarkerObjectNameInfoEXT) -> VkResult
+	{
+		let mut s = self.state();
+		let info = &*pNameInfo;
+		if !s.check_debug_marker_object("vkDebugMarkerSetObjectNameEXT", device.id(), info.objectType, info.object) { return VK_SUCCESS; }
+		let name = if info.pObjectName.is_null() { None } else { Some(CStr::from_ptr(info.pObjectName).to_string_lossy().into_owned()) };
+		s.objects.get_mut(&info.object).expect("untracked object").name = name;
+		VK_SUCCESS
+	}
+	#[cfg(feature = "VK_EXT_debug_marker")]
+	unsafe fn debug_marker_set_object_tag_ext(&self, device: VkDevice, pTagInfo: *const VkDebugMarkerObjectTagInfoEXT) -> VkResult
+	{
+		let mut s = self.state();
+		let info = &*pTagInfo;
+		if s.check_debug_marker_object("vkDebugMarkerSetObjectTagEXT", device.id(), info.objectType, info.object) && (info.tagSize == 0 || info.pTag.is_null())
+		{
+			let instance = s.instance_of(device.id());
+			s.invalid(instance, "vkDebugMarkerSetObjectTagEXT", "tagSize must be greater than 0 and pTag must be a valid pointer".to_owned());
+		}
+		VK_SUCCESS
+	}
+	#[cfg(feature = "VK_EXT_debug_marker")]
+	unsafe fn cmd_debug_marker_begin_ext(&self, commandBuffer: VkCommandBuffer, pMarkerInfo: *const VkDebugMarkerMarkerInfoEXT)
+	{
+		let mut s = self.state();
+		if !s.recording("vkCmdDebugMarkerBeginEXT", commandBuffer) { return; }
+		let p = (*pMarkerInfo).pMarkerName;
+		let name = if p.is_null() { String::new() } else { CStr::from_ptr(p).to_string_lossy().into_owned() };
+		s.labels.entry(commandBuffer.id()).or_default().push(name);
+	}
+	#[cfg(feature = "VK_EXT_debug_marker")]
+	unsafe fn cmd_debug_marker_end_ext(&self, commandBuffer: VkCommandBuffer)
+	{
+		let mut s = self.state();
+		// marker regions may be closed in another command buffer
+		if s.recording("vkCmdDebugMarkerEndEXT", commandBuffer) { s.end_label(commandBuffer.id()); }
+	}
+	#[cfg(feature = "VK_EXT_debug_marker")]
+	FakeCommand!(cmd_debug_marker_insert_ext = "vkCmdDebugMarkerInsertEXT"; pMarkerInfo: *const VkDebugMarkerMarkerInfoEXT);
+	#[cfg(feature = "VK_EXT_debug_utils")]
+	unsafe fn set_debug_utils_object_name_ext(&self, device: VkDevice, pNameInfo: *const VkDebugUtilsObjectNameInfoEXT) -> VkResult
+	{
+		let mut s = self.state();
+		let info = &*pNameInfo;
+		if !s.check_debug_utils_object("vkSetDebugUtilsObjectNameEXT", device.id(), info.objectType, info.objectHandle) { return VK_SUCCESS; }
+		let name = if info.pObjectName.is_null() { None } else { Some(CStr::from_ptr(info.pObjectName).to_string_lossy().into_owned()) };
+		s.objects.get_mut(&info.objectHandle).expect("untracked object").name = name;
+		VK_SUCCESS
+	}
+	#[cfg(feature = "VK_EXT_debug_utils")]
+	unsafe fn set_debug_utils_object_tag_ext(&self, device: VkDevice, pTagInfo: *const VkDebugUtilsObjectTagInfoEXT) -> VkResult
+	{
+		let mut s = self.state();
+		let info = &*pTagInfo;
+		if s.check_debug_utils_object("vkSetDebugUtilsObjectTagEXT", device.id(), info.objectType, info.objectHandle) && (info.tagSize == 0 || info.pTag.is_null())
+		{
+			let instance = s.instance_of(device.id());
+			s.invalid(instance, "vkSetDebugUtilsObjectTagEXT", "tagSize must be greater than 0 and pTag must be a valid pointer".to_owned());
+		}
+		VK_SUCCESS
+	}
+	#[cfg(feature = "VK_EXT_debug_utils")]
+	unsafe fn queue_begin_debug_utils_label_ext(&self, queue: VkQueue, pLabelInfo: *const VkDebugUtilsLabelEXT)
+	{
+		let mut s = self.state();
+		if !s.check("vkQueueBeginDebugUtilsLabelEXT", ObjectKind::Queue, queue.id()) { return; }
+		s.labels.entry(queue.id()).or_default().push(label_name(pLabelInfo));
+	}
+	#[cfg(feature = "VK_EXT_debug_utils")]
+	unsafe fn queue_end_debug_utils_label_ext(&self, queue: VkQueue)
+	{
+		let mut s = self.state();
+		if s.check("vkQueueEndDebugUtilsLabelEXT", ObjectKind::Queue, queue.id()) && !s.end_label(queue.id())
+		{
+			let instance = s.instance_of(queue.id());
+			s.invalid(instance, "vkQueueEndDebugUtilsLabelEXT", format!("no label is open on Queue({:#x})", queue.id()));
+		}
+	}
+	#[cfg(feature = "VK_EXT_debug_utils")]
+	unsafe fn queue_insert_debug_utils_label_ext(&self, queue: VkQueue, _: *const VkDebugUtilsLabelEXT)
+	{
+		self.state().check("vkQueueInsertDebugUtilsLabelEXT", ObjectKind::Queue, queue.id());
+	}
+	#[cfg(feature = "VK_EXT_debug_utils")]
+	unsafe fn cmd_begin_debug_utils_label_ext(&self, commandBuffer: VkCommandBuffer, pLabelInfo: *const VkDebugUtilsLabelEXT)
+	{
+		let mut s = self.state();
+		if !s.recording("vkCmdBeginDebugUtilsLabelEXT", commandBuffer) { return; }
+		s.labels.entry(commandBuffer.id()).or_default().push(label_name(pLabelInfo));
+	}
+	#[cfg(feature = "VK_EXT_debug_utils")]
+	unsafe fn cmd_end_debug_utils_label_ext(&self, commandBuffer: VkCommandBuffer)
+	{
+		let mut s = self.state();
+		if !s.recording("vkCmdEndDebugUtilsLabelEXT", commandBuffer) || s.end_label(commandBuffer.id()) { return; }
+		// primary command buffers may close a label opened by a preceding command buffer in the same queue
+		let secondary = match *s.data(commandBuffer.id())
+		{
+			ObjectData::CommandBuffer { level, .. } => level == VK_COMMAND_BUFFER_LEVEL_SECONDARY, _ => unreachable!()
+		};
+		if secondary
+		{
+			let instance = s.instance_of(commandBuffer.id());
+			s.invalid(instance, "vkCmdEndDebugUtilsLabelEXT", format!("no label is open in the secondary CommandBuffer({:#x})", commandBuffer.id()));
+		}
+	}
+	#[cfg(feature = "VK_EXT_debug_utils")]
+	FakeCommand!(cmd_insert_debug_utils_label_ext = "vkCmdInsertDebugUtilsLabelEXT"; pLabelInfo: *const VkDebugUtilsLabelEXT);
+	#[cfg(feature = "VK_AMD_draw_indirect_count")]
+	FakeCommand!(cmd_draw_indirect_count_amd = "vkCmdDrawIndirectCountAMD"; buffer: VkBuffer, offset: VkDeviceSize, countBuffer: VkBuffer,
+		countBufferOffset: VkDeviceSize, maxDrawCount: u32, stride: u32);
+	#[cfg(feature = "VK_AMD_draw_indirect_count")]
+	FakeCommand!(cmd_draw_indexed_indirect_count_amd = "vkCmdDrawIndexedIndirectCountAMD"; buffer: VkBuffer, offset: VkDeviceSize, countBuffer: VkBuffer,
+		countBufferOffset: VkDeviceSize, maxDrawCount: u32, stride: u32);
+	#[cfg(feature = "VK_KHX_device_group")]
+	FakeCommand!(cmd_set_device_mask_khx = "vkCmdSetDeviceMaskKHX"; deviceMask: u32);
+	#[cfg(feature = "VK_KHX_device_group")]
+	FakeCommand!(cmd_dispatch_base_khx = "vkCmdDispatchBaseKHX"; baseGroupX: u32, baseGroupY: u32, baseGroupZ: u32, groupCountX: u32, groupCountY: u32, groupCountZ: u32);
+	#[cfg(feature = "VK_NVX_device_generated_commands")]
+	FakeCommand!(cmd_process_commands_nvx = "vkCmdProcessCommandsNVX"; pProcessCommandsInfo: *const VkCmdProcessCommandsInfoNVX);
+	#[cfg(feature = "VK_NVX_device_generated_commands")]
+	FakeCommand!(cmd_reserve_space_for_commands_nvx = "vkCmdReserveSpaceForCommandsNVX"; pReserveSpaceInfo: *const VkCmdReserveSpaceForCommandsInfoNVX);
+	#[cfg(feature = "VK_NV_clip_space_w_scaling")]
+	FakeCommand!(cmd_set_viewport_w_scaling_nv = "vkCmdSetViewportWScalingNV"; firstViewport: u32, viewportCount: u32, pViewportWScalings: *const VkViewportWScalingNV);
+	#[cfg(feature = "VK_EXT_discard_rectangles")]
+	FakeCommand!(cmd_discard_rectangle_ext = "vkCmdSetDiscardRectangleEXT"; firstDiscardRectangle: u32, discardRectangleCount: u32, pDiscardRectangles: *const VkRect2D);
+
+	unsafe fn enumerate_instance_version(&self, pApiVersion: *mut u32) -> VkResult { *pApiVersion = VK_API_VERSION_1_1; VK_SUCCESS }
+	unsafe fn bind_buffer_memory2(&self, device: VkDevice, bindInfoCount: u32, pBindInfos: *const VkBindBufferMemoryInfo) -> VkResult
+	{
+		let mut s = self.state();
+		for b in slice(pBindInfos, bindInfoCount)
+		{
+			let r = s.bind("vkBindBufferMemory2", device.id(), ObjectKind::Buffer, b.buffer.id(), b.memory.id(), b.memoryOffset);
+			if r != VK_SUCCESS { return r; }
+		}
+		VK_SUCCESS
+	}
+	unsafe fn bind_image_memory2(&self, device: VkDevice, bindInfoCount: u32, pBindInfos: *const VkBindImageMemoryInfo) -> VkResult
+	{
+		let mut s = self.state();
+		for b in slice(pBindInfos, bindInfoCount)
+		{
+			let r = s.bind("vkBindImageMemory2", device.id(), ObjectKind::Image, b.image.id(), b.memory.id(), b.memoryOffset);
+			if r != VK_SUCCESS { return r; }
+		}
+		VK_SUCCESS
+	}
+	unsafe fn enumerate_physical_device_groups(&self, instance: VkInstance, pPhysicalDeviceGroupCount: *mut u32,
+		pPhysicalDeviceGroupProperties: *mut VkPhysicalDeviceGroupProperties) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkEnumeratePhysicalDeviceGroups", ObjectKind::Instance, instance.id()) { return VK_ERROR_INITIALIZATION_FAILED; }
+		let pd = match *s.data(instance.id()) { ObjectData::Instance { physical_device, .. } => physical_device, _ => unreachable!() };
+		// every physical device forms a group by itself
+		if pPhysicalDeviceGroupProperties.is_null() { *pPhysicalDeviceGroupCount = 1; return VK_SUCCESS; }
+		if *pPhysicalDeviceGroupCount == 0 { return VK_INCOMPLETE; }
+		let g = &mut *pPhysicalDeviceGroupProperties;
+		g.physicalDeviceCount = 1; g.physicalDevices[0] = FakeHandle::from_id(pd); g.subsetAllocation = false as _;
+		*pPhysicalDeviceGroupCount = 1;
+		VK_SUCCESS
+	}
+	unsafe fn get_device_group_peer_memory_features(&self, _: VkDevice, _: u32, _: u32, _: u32, pPeerMemoryFeatures: *mut VkPeerMemoryFeatureFlags)
+	{
+		// only one physical device exists in the group
+		*pPeerMemoryFeatures = 0;
+	}
+	FakeCommand!(cmd_set_device_mask = "vkCmdSetDeviceMask"; deviceMask: u32);
+	FakeCommand!(cmd_dispatch_base = "vkCmdDispatchBase"; baseGroupX: u32, baseGroupY: u32, baseGroupZ: u32, groupCountX: u32, groupCountY: u32, groupCountZ: u32);
+	unsafe fn get_image_memory_requirements2(&self, device: VkDevice, pInfo: *const VkImageMemoryRequirementsInfo2, pMemoryRequirements: *mut VkMemoryRequirements2)
+	{
+		let r = &mut *pMemoryRequirements;
+		let mut s = self.state();
+		r.memoryRequirements = s.requirements("vkGetImageMemoryRequirements2", device.id(), ObjectKind::Image, (*pInfo).image.id());
+		fill_dedicated_requirements(r.pNext, r.memoryRequirements.size != 0 && s.prefers_dedicated((*pInfo).image.id()));
+	}
+	unsafe fn get_buffer_memory_requirements2(&self, device: VkDevice, pInfo: *const VkBufferMemoryRequirementsInfo2, pMemoryRequirements: *mut VkMemoryRequirements2)
+	{
+		let r = &mut *pMemoryRequirements;
+		r.memoryRequirements = self.state().requirements("vkGetBufferMemoryRequirements2", device.id(), ObjectKind::Buffer, (*pInfo).buffer.id());
+		fill_dedicated_requirements(r.pNext, false);
+	}
+	unsafe fn get_image_sparse_memory_requirements2(&self, _: VkDevice, _: *const VkImageSparseMemoryRequirementsInfo2, pSparseMemoryRequirementCount: *mut u32,
+		_: *mut VkSparseImageMemoryRequirements2)
+	{
+		*pSparseMemoryRequirementCount = 0;
+	}
+	unsafe fn get_physical_device_features2(&self, physicalDevice: VkPhysicalDevice, pFeatures: *mut VkPhysicalDeviceFeatures2)
+	{
+		self.state().require_version("vkGetPhysicalDeviceFeatures2", physicalDevice.id(), VK_API_VERSION_1_1);
+		self.physical_device_features2(physicalDevice, pFeatures);
+	}
+	unsafe fn get_physical_device_properties2(&self, physicalDevice: VkPhysicalDevice, pProperties: *mut VkPhysicalDeviceProperties2)
+	{
+		self.state().require_version("vkGetPhysicalDeviceProperties2", physicalDevice.id(), VK_API_VERSION_1_1);
+		self.physical_device_properties2(physicalDevice, pProperties);
+	}
+	unsafe fn get_physical_device_format_properties2(&self, physicalDevice: VkPhysicalDevice, format: VkFormat, pFormatProperties: *mut VkFormatProperties2)
+	{
+		self.state().require_version("vkGetPhysicalDeviceFormatProperties2", physicalDevice.id(), VK_API_VERSION_1_1);
+		self.physical_device_format_properties2(physicalDevice, format, pFormatProperties);
+	}
+	unsafe fn get_physical_device_image_format_properties2(&self, physicalDevice: VkPhysicalDevice, pImageFormatInfo: *const VkPhysicalDeviceImageFormatInfo2,
+		pImageFormatProperties: *mut VkImageFormatProperties2) -> VkResult
+	{
+		let i = &*pImageFormatInfo;
+		self.get_physical_device_image_format_properties(physicalDevice, i.format, i._type, i.tiling, i.usage, i.flags,
+			&mut (*pImageFormatProperties).imageFormatProperties)
+	}
+	unsafe fn get_physical_device_queue_family_properties2(&self, physicalDevice: VkPhysicalDevice, pQueueFamilyPropertyCount: *mut u32,
+		pQueueFamilyProperties: *mut VkQueueFamilyProperties2)
+	{
+		self.state().require_version("vkGetPhysicalDeviceQueueFamilyProperties2", physicalDevice.id(), VK_API_VERSION_1_1);
+		self.physical_device_queue_family_properties2(physicalDevice, pQueueFamilyPropertyCount, pQueueFamilyProperties);
+	}
+	unsafe fn get_physical_device_memory_properties2(&self, physicalDevice: VkPhysicalDevice, pMemoryProperties: *mut VkPhysicalDeviceMemoryProperties2)
+	{
+		self.state().require_version("vkGetPhysicalDeviceMemoryProperties2", physicalDevice.id(), VK_API_VERSION_1_1);
+		self.physical_device_memory_properties2(physicalDevice, pMemoryProperties);
+	}
+	unsafe fn get_physical_device_sparse_image_format_properties2(&self, _: VkPhysicalDevice, _: *const VkPhysicalDeviceSparseImageFormatInfo2,
+		pPropertyCount: *mut u32, _: *mut VkSparseImageFormatProperties2)
+	{
+		*pPropertyCount = 0;
+	}
+	unsafe fn trim_command_pool(&self, device: VkDevice, commandPool: VkCommandPool, _: VkCommandPoolTrimFlags)
+	{
+		let mut s = self.state();
+		s.check("vkTrimCommandPool", ObjectKind::Device, device.id());
+		s.check("vkTrimCommandPool", ObjectKind::CommandPool, commandPool.id());
+	}
+	unsafe fn get_device_queue2(&self, device: VkDevice, pQueueInfo: *const VkDeviceQueueInfo2, pQueue: *mut VkQueue)
+	{
+		self.get_device_queue(device, (*pQueueInfo).queueFamilyIndex, (*pQueueInfo).queueIndex, pQueue);
+	}
+	FakeDeviceChild!(create_sampler_ycbcr_conversion / destroy_sampler_ycbcr_conversion (VkSamplerYcbcrConversionCreateInfo, VkSamplerYcbcrConversion)
+		= SamplerYcbcrConversion ["vkCreateSamplerYcbcrConversion", "vkDestroySamplerYcbcrConversion"]);
+	unsafe fn update_descriptor_set_with_template(&self, device: VkDevice, descriptorSet: VkDescriptorSet, descriptorUpdateTemplate: VkDescriptorUpdateTemplate, _: *const c_void)
+	{
+		let mut s = self.state();
+		s.check("vkUpdateDescriptorSetWithTemplate", ObjectKind::Device, device.id());
+		s.check("vkUpdateDescriptorSetWithTemplate", ObjectKind::DescriptorSet, descriptorSet.id());
+		s.check("vkUpdateDescriptorSetWithTemplate", ObjectKind::DescriptorUpdateTemplate, descriptorUpdateTemplate.id());
+	}
+	unsafe fn get_physical_device_external_buffer_properties(&self, _: VkPhysicalDevice, _: *const VkPhysicalDeviceExternalBufferInfo,
+		pExternalBufferProperties: *mut VkExternalBufferProperties)
+	{
+		(*pExternalBufferProperties).externalMemoryProperties = zeroed();
+	}
+	unsafe fn get_physical_device_external_fence_properties(&self, _: VkPhysicalDevice, _: *const VkPhysicalDeviceExternalFenceInfo,
+		pExternalFenceProperties: *mut VkExternalFenceProperties)
+	{
+		let p = &mut *pExternalFenceProperties;
+		p.exportFromImportedHandleTypes = 0; p.compatibleHandleTypes = 0; p.externalFenceFeatures = 0;
+	}
+	unsafe fn get_physical_device_external_semaphore_properties(&self, _: VkPhysicalDevice, _: *const VkPhysicalDeviceExternalSemaphoreInfo,
+		pExternalSemaphoreProperties: *mut VkExternalSemaphoreProperties)
+	{
+		let p = &mut *pExternalSemaphoreProperties;
+		p.exportFromImportedHandleType = 0; p.compatibleHandleTypes = 0; p.externalSemaphoreFeatures = 0;
+	}
+	unsafe fn get_descriptor_set_layout_support(&self, device: VkDevice, _: *const VkDescriptorSetLayoutCreateInfo, pSupport: *mut VkDescriptorSetLayoutSupport)
+	{
+		self.state().check("vkGetDescriptorSetLayoutSupport", ObjectKind::Device, device.id());
+		(*pSupport).supported = true as _;
+	}
+
+	#[cfg(feature = "VK_KHR_surface")]
+	unsafe fn destroy_surface_khr(&self, instance: VkInstance, surface: VkSurfaceKHR, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkDestroySurfaceKHR", ObjectKind::Instance, instance.id(), ObjectKind::Surface, surface.id());
+	}
+	#[cfg(feature = "VK_KHR_surface")]
+	unsafe fn get_physical_device_surface_support_khr(&self, physicalDevice: VkPhysicalDevice, queueFamilyIndex: u32, surface: VkSurfaceKHR, pSupported: *mut VkBool32) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkGetPhysicalDeviceSurfaceSupportKHR", ObjectKind::PhysicalDevice, physicalDevice.id())
+			|| !s.check("vkGetPhysicalDeviceSurfaceSupportKHR", ObjectKind::Surface, surface.id()) { return VK_ERROR_SURFACE_LOST_KHR; }
+		*pSupported = (queueFamilyIndex == 0) as _;
+		VK_SUCCESS
+	}
+	#[cfg(feature = "VK_KHR_surface")]
+	unsafe fn get_physical_device_surface_capabilities_khr(&self, physicalDevice: VkPhysicalDevice, surface: VkSurfaceKHR, pSurfaceCapabilities: *mut VkSurfaceCapabilitiesKHR) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkGetPhysicalDeviceSurfaceCapabilitiesKHR", ObjectKind::PhysicalDevice, physicalDevice.id())
+			|| !s.check("vkGetPhysicalDeviceSurfaceCapabilitiesKHR", ObjectKind::Surface, surface.id()) { return VK_ERROR_SURFACE_LOST_KHR; }
+		*pSurfaceCapabilities = VkSurfaceCapabilitiesKHR
+		{
+			minImageCount: 2, maxImageCount: 8,
+			currentExtent: VkExtent2D { width: 640, height: 480 },
+			minImageExtent: VkExtent2D { width: 640, height: 480 }, maxImageExtent: VkExtent2D { width: 640, height: 480 },
+			maxImageArrayLayers: 1, supportedTransforms: VK_SURFACE_TRANSFORM_IDENTITY_BIT_KHR, currentTransform: VK_SURFACE_TRANSFORM_IDENTITY_BIT_KHR,
+			supportedCompositeAlpha: VK_COMPOSITE_ALPHA_OPAQUE_BIT_KHR,
+			supportedUsageFlags: VK_IMAGE_USAGE_COLOR_ATTACHMENT_BIT | VK_IMAGE_USAGE_TRANSFER_SRC_BIT | VK_IMAGE_USAGE_TRANSFER_DST_BIT
+		};
+		VK_SUCCESS
+	}
+	#[cfg(feature = "VK_KHR_surface")]
+	unsafe fn get_physical_device_surface_formats_khr(&self, physicalDevice: VkPhysicalDevice, surface: VkSurfaceKHR, pSurfaceFormatCount: *mut u32,
+		pSurfaceFormats: *mut VkSurfaceFormatKHR) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkGetPhysicalDeviceSurfaceFormatsKHR", ObjectKind::PhysicalDevice, physicalDevice.id())
+			|| !s.check("vkGetPhysicalDeviceSurfaceFormatsKHR", ObjectKind::Surface, surface.id()) { return VK_ERROR_SURFACE_LOST_KHR; }
+		enumerate_into(&[
+			VkSurfaceFormatKHR { format: VK_FORMAT_B8G8R8A8_UNORM, colorSpace: VK_COLOR_SPACE_SRGB_NONLINEAR_KHR },
+			VkSurfaceFormatKHR { format: VK_FORMAT_B8G8R8A8_SRGB, colorSpace: VK_COLOR_SPACE_SRGB_NONLINEAR_KHR }
+		], pSurfaceFormatCount, pSurfaceFormats)
+	}
+	#[cfg(feature = "VK_KHR_surface")]
+	unsafe fn get_physical_device_surface_present_modes_khr(&self, physicalDevice: VkPhysicalDevice, surface: VkSurfaceKHR, pPresentModeCount: *mut u32,
+		pPresentModes: *mut VkPresentModeKHR) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkGetPhysicalDeviceSurfacePresentModesKHR", ObjectKind::PhysicalDevice, physicalDevice.id())
+			|| !s.check("vkGetPhysicalDeviceSurfacePresentModesKHR", ObjectKind::Surface, surface.id()) { return VK_ERROR_SURFACE_LOST_KHR; }
+		enumerate_into(&[VK_PRESENT_MODE_FIFO_KHR], pPresentModeCount, pPresentModes)
+	}
+	#[cfg(feature = "VK_KHR_get_surface_capabilities2")]
+	unsafe fn get_physical_device_surface_capabilities2_khr(&self, physicalDevice: VkPhysicalDevice, surface_info: *const VkPhysicalDeviceSurfaceInfo2KHR,
+		surface_capabilities: *mut VkSurfaceCapabilities2KHR) -> VkResult
+	{
+		self.get_physical_device_surface_capabilities_khr(physicalDevice, (*surface_info).surface, &mut (*surface_capabilities).surfaceCapabilities)
+	}
+	#[cfg(feature = "VK_KHR_swapchain")]
+	unsafe fn create_swapchain_khr(&self, device: VkDevice, pCreateInfo: *const VkSwapchainCreateInfoKHR, _: *const VkAllocationCallbacks, pSwapchain: *mut VkSwapchainKHR) -> VkResult
+	{
+		let mut s = self.state();
+		let ci = &*pCreateInfo;
+		if !s.check("vkCreateSwapchainKHR", ObjectKind::Device, device.id()) { return VK_ERROR_DEVICE_LOST; }
+		if !s.check("vkCreateSwapchainKHR", ObjectKind::Surface, ci.surface.id()) { return VK_ERROR_SURFACE_LOST_KHR; }
+		if !s.check_opt("vkCreateSwapchainKHR", ObjectKind::Swapchain, ci.oldSwapchain.id()) { return VK_ERROR_NATIVE_WINDOW_IN_USE_KHR; }
+		let count = ::std::cmp::max(ci.minImageCount, 2);
+		let h = s.create(ObjectKind::Swapchain, device.id(), ObjectData::Swapchain { images: Vec::new(), next: 0 });
+		let mut images = Vec::with_capacity(count as _);
+		for _ in 0 .. count
+		{
+			// presentable images are owned by the swapchain, and backed by a memory invisible to the application
+			let shape = ImageShape
+			{
+				format: ci.imageFormat, extent: VkExtent3D { width: ci.imageExtent.width, height: ci.imageExtent.height, depth: 1 },
+				mip_levels: 1, array_layers: ci.imageArrayLayers, tiling: VK_IMAGE_TILING_OPTIMAL, usage: ci.imageUsage
+			};
+			let size = image_bytes(&shape);
+			let memory = s.create(ObjectKind::DeviceMemory, h, ObjectData::Memory { size, type_index: 0, mapped: false, contents: None, dedicated: None });
+			images.push(s.create(ObjectKind::Image, h, ObjectData::Image { shape, binding: Some((memory, 0)) }));
+		}
+		if let ObjectData::Swapchain { images: ref mut i, .. } = *s.data(h) { *i = images; }
+		*pSwapchain = FakeHandle::from_id(h);
+		VK_SUCCESS
+	}
+	#[cfg(feature = "VK_KHR_swapchain")]
+	unsafe fn destroy_swapchain_khr(&self, device: VkDevice, swapchain: VkSwapchainKHR, _: *const VkAllocationCallbacks)
+	{
+		self.state().destroy_child("vkDestroySwapchainKHR", ObjectKind::Device, device.id(), ObjectKind::Swapchain, swapchain.id());
+	}
+	#[cfg(feature = "VK_KHR_swapchain")]
+	unsafe fn get_swapchain_images_khr(&self, device: VkDevice, swapchain: VkSwapchainKHR, pSwapchainImageCount: *mut u32, pSwapchainImages: *mut VkImage) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkGetSwapchainImagesKHR", ObjectKind::Device, device.id())
+			|| !s.check("vkGetSwapchainImagesKHR", ObjectKind::Swapchain, swapchain.id()) { return VK_ERROR_OUT_OF_DATE_KHR; }
+		let images = match *s.data(swapchain.id())
+		{
+			ObjectData::Swapchain { ref images, .. } => images.iter().map(|&h| FakeHandle::from_id(h)).collect::<Vec<VkImage>>(),
+			_ => unreachable!()
+		};
+		enumerate_into(&images, pSwapchainImageCount, pSwapchainImages)
+	}
+	#[cfg(feature = "VK_KHR_swapchain")]
+	unsafe fn acquire_next_image_khr(&self, device: VkDevice, swapchain: VkSwapchainKHR, _: u64, semaphore: VkSemaphore, fence: VkFence, pImageIndex: *mut u32) -> VkResult
+	{
+		let mut s = self.state();
+		if !s.check("vkAcquireNextImageKHR", ObjectKind::Device, device.id())
+			|| !s.check("vkAcquireNextImageKHR", ObjectKind::Swapchain, swapchain.id()) { return VK_ERROR_OUT_OF_DATE_KHR; }
+		*pImageIndex = match *s.data(swapchain.id())
+		{
+			ObjectData::Swapchain { ref images, ref mut next } => { let n = *next; *next = (n + 1) % images.len(); n as _ },
+			_ => unreachable!()
+		};
+		s.signal_semaphore("vkAcquireNextImageKHR", semaphore.id());
+		s.signal_fence("vkAcquireNextImageKHR", fence.id());
+		VK_SUCCESS
+	}
+	#[cfg(feature = "VK_KHR_swapchain")]
+	unsafe fn queue_present_khr(&self, queue: VkQueue, pPresentInfo: *const VkPresentInfoKHR) -> VkResult
+	{
+		let mut s = self.state();
+		let info = &*pPresentInfo;
+		if !s.check("vkQueuePresentKHR", ObjectKind::Queue, queue.id()) { return VK_ERROR_DEVICE_LOST; }
+		for &w in slice(info.pWaitSemaphores, info.waitSemaphoreCount) { s.wait_semaphore("vkQueuePresentKHR", w.id()); }
+		for (n, &sc) in slice(info.pSwapchains, info.swapchainCount).iter().enumerate()
+		{
+			let r = if s.check("vkQueuePresentKHR", ObjectKind::Swapchain, sc.id()) { VK_SUCCESS } else { VK_ERROR_OUT_OF_DATE_KHR };
+			if !info.pResults.is_null() { *info.pResults.add(n) = r; }
+		}
+		VK_SUCCESS
+	}
+	#[cfg(feature = "VK_KHR_xlib_surface")]
+	unsafe fn create_xlib_surface_khr(&self, instance: VkInstance, _: *const VkXlibSurfaceCreateInfoKHR, _: *const VkAllocationCallbacks, pSurface: *mut VkSurfaceKHR) -> VkResult
+	{
+		self.state().create_surface("vkCreateXlibSurfaceKHR", instance.id(), pSurface)
+	}
+	#[cfg(feature = "VK_KHR_xlib_surface")]
+	unsafe fn get_physical_device_xlib_presentation_support_khr(&self, _: VkPhysicalDevice, queueFamilyIndex: u32, _: *mut Display, _: VisualID) -> VkBool32
+	{
+		(queueFamilyIndex == 0) as _
+	}
+	#[cfg(feature = "VK_KHR_xcb_surface")]
+	unsafe fn create_xcb_surface_khr(&self, instance: VkInstance, _: *const VkXcbSurfaceCreateInfoKHR, _: *const VkAllocationCallbacks, pSurface: *mut VkSurfaceKHR) -> VkResult
+	{
+		self.state().create_surface("vkCreateXcbSurfaceKHR", instance.id(), pSurface)
+	}
+	#[cfg(feature = "VK_KHR_xcb_surface")]
+	unsafe fn get_physical_device_xcb_presentation_support_khr(&self, _: VkPhysicalDevice, queueFamilyIndex: u32, _: *mut xcb_connection_t, _: xcb_visualid_t) -> VkBool32
+	{
+		(queueFamilyIndex == 0) as _
+	}
+	#[cfg(feature = "VK_KHR_android_surface")]
+	unsafe fn create_android_surface_khr(&self, instance: VkInstance, _: *const VkAndroidSurfaceCreateInfoKHR, _: *const VkAllocationCallbacks, pSurface: *mut VkSurfaceKHR) -> VkResult
+	{
+		self.state().create_surface("vkCreateAndroidSurfaceKHR", instance.id(), pSurface)
+	}
+	#[cfg(feature = "VK_KHR_win32_surface")]
+	unsafe fn create_win32_surface_khr(&self, instance: VkInstance, _: *const VkWin32SurfaceCreateInfoKHR, _: *const VkAllocationCallbacks, pSurface: *mut VkSurfaceKHR) -> VkResult
+	{
+		self.state().create_surface("vkCreateWin32SurfaceKHR", instance.id(), pSurface)
+	}
+	#[cfg(feature = "VK_KHR_win32_surface")]
+	unsafe fn get_physical_device_win32_presentation_support_khr(&self, _: VkPhysicalDevice, queueFamilyIndex: u32) -> VkBool32
+	{
+		(queueFamilyIndex == 0) as _
+	}
+	#[cfg(feature = "VK_MVK_macos_surface")]
+	unsafe fn create_macos_surface_mvk(&self, instance: VkInstance, _: *const VkMacOSSurfaceCreateInfoMVK, _: *const VkAllocationCallbacks, pSurface: *mut VkSurfaceKHR) -> VkResult
+	{
+		self.state().create_surface("vkCreateMacOSSurfaceMVK", instance.id(), pSurface)
+	}
+	// no displays are connected to the fake device
+	#[cfg(feature = "VK_KHR_display")]
+	unsafe fn get_physical_device_display_properties_khr(&self, _: VkPhysicalDevice, pPropertyCount: *mut u32, _: *mut VkDisplayPropertiesKHR) -> VkResult
+	{
+		*pPropertyCount = 0; VK_SUCCESS
+	}
+	#[cfg(feature = "VK_KHR_display")]
+	unsafe fn get_physical_device_display_plane_properties_khr(&self, _: VkPhysicalDevice, pPropertyCount: *mut u32, _: *mut VkDisplayPlanePropertiesKHR) -> VkResult
+	{
+		*pPropertyCount = 0; VK_SUCCESS
+	}
+	#[cfg(feature = "VK_KHR_display")]
+	unsafe fn get_display_plane_supported_displays_khr(&self, _: VkPhysicalDevice, _: u32, pDisplayCount: *mut u32, _: *mut VkDisplayKHR) -> VkResult
+	{
+		*pDisplayCount = 0; VK_SUCCESS
+	}
+	#[cfg(feature = "VK_KHR_display")]
+	unsafe fn get_display_mode_properties_khr(&self, _: VkPhysicalDevice, _: VkDisplayKHR, pPropertyCount: *mut u32, _: *mut VkDisplayModePropertiesKHR) -> VkResult
+	{
+		*pPropertyCount = 0; VK_SUCCESS
+	}
+	#[cfg(feature = "VK_KHR_display")]
+	unsafe fn create_display_mode_khr(&self, physicalDevice: VkPhysicalDevice, display: VkDisplayKHR, _: *const VkDisplayModeCreateInfoKHR,
+		_: *const VkAllocationCallbacks, _: *mut VkDisplayModeKHR) -> VkResult
+	{
+		let mut s = self.state();
+		let instance = s.instance_of(physicalDevice.id());
+		s.invalid(instance, "vkCreateDisplayModeKHR", format!("Display({:#x}) is not connected", display.id()));
+		VK_ERROR_INITIALIZATION_FAILED
+	}
+	#[cfg(feature = "VK_KHR_display")]
+	unsafe fn get_display_plane_capabilities_khr(&self, physicalDevice: VkPhysicalDevice, mode: VkDisplayModeKHR, _: u32, _: *mut VkDisplayPlaneCapabilitiesKHR) -> VkResult
+	{
+		let mut s = self.state();
+		s.check("vkGetDisplayPlaneCapabilitiesKHR", ObjectKind::PhysicalDevice, physicalDevice.id());
+		s.check("vkGetDisplayPlaneCapabilitiesKHR", ObjectKind::DisplayMode, mode.id());
+		VK_ERROR_INITIALIZATION_FAILED
+	}
+	#[cfg(feature = "VK_KHR_display")]
+	unsafe fn create_display_plane_surface_khr(&self, instance: VkInstance, _: *const VkDisplaySurfaceCreateInfoKHR, _: *const VkAllocationCallbacks, _: *mut VkSurfaceKHR) -> VkResult
+	{
+		let mut s = self.state();
+		s.invalid(instance.id(), "vkCreateDisplayPlaneSurfaceKHR", "no display planes are available".to_owned());
+		VK_ERROR_INITIALIZATION_FAILED
+	}
+}
+
+#[repr(C)]
+struct ChainHeader { sType: VkStructureType, pNext: *mut c_void }
+/// Fill `VkMemoryDedicatedRequirements` in the chain, if exists
+unsafe fn fill_dedicated_requirements(mut p: *mut c_void, prefers: bool)
+{
+	while !p.is_null()
+	{
+		let h = p as *mut ChainHeader;
+		if (*h).sType == VK_STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS
+		{
+			let r = &mut *(p as *mut VkMemoryDedicatedRequirements);
+			r.prefersDedicatedAllocation = prefers as _; r.requiresDedicatedAllocaion = false as _;
+		}
+		p = (*h).pNext;
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+	use std::ptr::null;
+
+	/// An instance and a device with one queue, created directly on the driver
+	unsafe fn device(d: &FakeDriver) -> (VkInstance, VkDevice)
+	{
+		let mut instance = null_mut();
+		assert_eq!(d.create_instance(&Default::default(), null(), &mut instance), VK_SUCCESS);
+		let (mut count, mut pd) = (1, null_mut());
+		assert_eq!(d.enumerate_physical_devices(instance, &mut count, &mut pd), VK_SUCCESS);
+		let priority = 0.0f32;
+		let q = VkDeviceQueueCreateInfo { queueFamilyIndex: 0, queueCount: 1, pQueuePriorities: &priority, .. Default::default() };
+		let ci = VkDeviceCreateInfo { queueCreateInfoCount: 1, pQueueCreateInfos: &q, .. Default::default() };
+		let mut device = null_mut();
+		assert_eq!(d.create_device(pd, &ci, null(), &mut device), VK_SUCCESS);
+		(instance, device)
+	}
+	unsafe fn fence(d: &FakeDriver, device: VkDevice) -> VkFence
+	{
+		let mut f = VK_NULL_HANDLE as _;
+		assert_eq!(d.create_fence(device, &Default::default(), null(), &mut f), VK_SUCCESS);
+		f
+	}
+
+	#[test]
+	fn double_free()
+	{
+		let d = FakeDriver::new();
+		unsafe
+		{
+			let (instance, device) = device(&d);
+			let f = fence(&d, device);
+			d.destroy_fence(device, f, null());
+			d.destroy_fence(device, f, null());
+			d.destroy_device(device, null());
+			d.destroy_device(device, null());
+			d.destroy_instance(instance, null());
+			d.destroy_instance(instance, null());
+			assert_eq!(d.misuses_of(instance), vec![
+				Misuse::DoubleFree { kind: ObjectKind::Fence, handle: f.id(), call: "vkDestroyFence" },
+				Misuse::DoubleFree { kind: ObjectKind::Device, handle: device.id(), call: "vkDestroyDevice" },
+				Misuse::DoubleFree { kind: ObjectKind::Instance, handle: instance.id(), call: "vkDestroyInstance" }
+			]);
+		}
+	}
+	#[test]
+	fn use_after_destroy()
+	{
+		let d = FakeDriver::new();
+		unsafe
+		{
+			let (instance, device) = device(&d);
+			let f = fence(&d, device);
+			d.destroy_fence(device, f, null());
+			assert_eq!(d.get_fence_status(device, f), VK_ERROR_DEVICE_LOST);
+			d.destroy_device(device, null());
+			// the children die with their parent
+			let mut f2 = VK_NULL_HANDLE as _;
+			assert_eq!(d.create_fence(device, &Default::default(), null(), &mut f2), VK_ERROR_DEVICE_LOST);
+			d.destroy_instance(instance, null());
+			assert_eq!(d.misuses_of(instance), vec![
+				Misuse::UseAfterDestroy { kind: ObjectKind::Fence, handle: f.id(), call: "vkGetFenceStatus" },
+				Misuse::UseAfterDestroy { kind: ObjectKind::Device, handle: device.id(), call: "vkCreateFence" }
+			]);
+		}
+	}
+	#[test]
+	fn unknown_handle()
+	{
+		let d = FakeDriver::new();
+		unsafe
+		{
+			let (instance, device) = device(&d);
+			// the device is not a fence
+			d.destroy_fence(device, device.id() as usize as _, null());
+			let stray: VkDevice = 0xdead0 as _;
+			d.destroy_device(stray, null());
+			d.destroy_device(device, null());
+			d.destroy_instance(instance, null());
+			// handles of no object of the kind belong to no instance
+			assert!(d.misuses_of(instance).is_empty());
+			assert_eq!(d.misuses(), vec![
+				Misuse::UnknownHandle { kind: ObjectKind::Fence, handle: device.id(), call: "vkDestroyFence" },
+				Misuse::UnknownHandle { kind: ObjectKind::Device, handle: 0xdead0, call: "vkDestroyDevice" }
+			]);
+		}
+	}
+	#[test]
+	fn leaks()
+	{
+		let d = FakeDriver::new();
+		unsafe
+		{
+			let (instance, device) = device(&d);
+			let f = fence(&d, device);
+			// queues are owned by the device, and are not leaks
+			d.destroy_device(device, null());
+			assert_eq!(d.live_objects(ObjectKind::Fence), 0);
+			d.destroy_instance(instance, null());
+			assert_eq!(d.misuses_of(instance), vec![Misuse::Leaked { kind: ObjectKind::Fence, handle: f.id(), parent: ObjectKind::Device }]);
+		}
+	}
+	#[test]
+	fn leaked_device()
+	{
+		let d = FakeDriver::new();
+		unsafe
+		{
+			let (instance, device) = device(&d);
+			d.destroy_instance(instance, null());
+			assert_eq!(d.misuses_of(instance), vec![Misuse::Leaked { kind: ObjectKind::Device, handle: device.id(), parent: ObjectKind::PhysicalDevice }]);
+		}
+	}
+	#[test]
+	fn invalid_usage()
+	{
+		let d = FakeDriver::new();
+		unsafe
+		{
+			let (instance, device) = device(&d);
+			let mut count = 1; let mut pd = null_mut();
+			d.enumerate_physical_devices(instance, &mut count, &mut pd);
+			let priority = 0.0f32;
+			let q = VkDeviceQueueCreateInfo { queueFamilyIndex: 2, queueCount: 2, pQueuePriorities: &priority, .. Default::default() };
+			let ci = VkDeviceCreateInfo { queueCreateInfoCount: 1, pQueueCreateInfos: &q, .. Default::default() };
+			let mut d2 = null_mut();
+			assert_eq!(d.create_device(pd, &ci, null(), &mut d2), VK_ERROR_INITIALIZATION_FAILED);
+			d.destroy_device(device, null());
+			d.destroy_instance(instance, null());
+			assert_eq!(d.take_misuses(), vec![
+				Misuse::InvalidUsage { call: "vkCreateDevice", message: "invalid queue request: family 2 x 2".to_owned() }
+			]);
+			assert!(d.misuses().is_empty());
+		}
+	}
+	#[test]
+	#[should_panic(expected = "Vulkan API misuse: vkDestroyFence: Fence")]
+	fn panic_on_misuse()
+	{
+		let d = FakeDriver::new();
+		d.set_panic_on_misuse(true);
+		unsafe
+		{
+			let (_, device) = device(&d);
+			let f = fence(&d, device);
+			d.destroy_fence(device, f, null());
+			d.destroy_fence(device, f, null());
+		}
+	}
+}
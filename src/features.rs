@@ -0,0 +1,128 @@
+//! Device Features
+
+use vk::*;
+
+macro_rules! DeviceFeatures
+{
+	($($variant: ident = $field: ident),*) =>
+	{
+		/// A member of `VkPhysicalDeviceFeatures`
+		#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+		pub enum Feature { $($variant),* }
+		impl Feature
+		{
+			/// Every feature, in the order of the members of `VkPhysicalDeviceFeatures`
+			pub const ALL: &'static [Feature] = &[$(Feature::$variant),*];
+			/// The name of the member
+			pub fn name(self) -> &'static str { match self { $(Feature::$variant => stringify!($field)),* } }
+			fn member(self, f: &VkPhysicalDeviceFeatures) -> VkBool32 { match self { $(Feature::$variant => f.$field),* } }
+			fn member_mut(self, f: &mut VkPhysicalDeviceFeatures) -> &mut VkBool32
+			{
+				match self { $(Feature::$variant => &mut f.$field),* }
+			}
+		}
+	}
+}
+DeviceFeatures!
+{
+	RobustBufferAccess = robustBufferAccess, FullDrawIndexUint32 = fullDrawIndexUint32, ImageCubeArray = imageCubeArray,
+	IndependentBlend = independentBlend, GeometryShader = geometryShader, TessellationShader = tessellationShader,
+	SampleRateShading = sampleRateShading, DualSrcBlend = dualSrcBlend, LogicOp = logicOp, MultiDrawIndirect = multiDrawIndirect,
+	DrawIndirectFirstInstance = drawIndirectFirstInstance, DepthClamp = depthClamp, DepthBiasClamp = depthBiasClamp,
+	FillModeNonSolid = fillModeNonSolid, DepthBounds = depthBounds, WideLines = wideLines, LargePoints = largePoints,
+	AlphaToOne = alphaToOne, MultiViewport = multiViewport, SamplerAnisotropy = samplerAnisotropy,
+	TextureCompressionEtc2 = textureCompressionETC2, TextureCompressionAstcLdr = textureCompressionASTC_LDR,
+	TextureCompressionBc = textureCompressionBC, OcclusionQueryPrecise = occlusionQueryPrecise,
+	PipelineStatisticsQuery = pipelineStatisticsQuery, VertexPipelineStoresAndAtomics = vertexPipelineStoresAndAtomics,
+	FragmentStoresAndAtomics = fragmentStoresAndAtomics, ShaderTessellationAndGeometryPointSize = shaderTessellationAndGeometryPointSize,
+	ShaderImageGatherExtended = shaderImageGatherExtended, ShaderStorageImageExtendedFormats = shaderStorageImageExtendedFormats,
+	ShaderStorageImageMultisample = shaderStorageImageMultisample,
+	ShaderStorageImageReadWithoutFormat = shaderStorageImageReadWithoutFormat,
+	ShaderStorageImageWriteWithoutFormat = shaderStorageImageWriteWithoutFormat,
+	ShaderUniformBufferArrayDynamicIndexing = shaderUniformBufferArrayDynamicIndexing,
+	ShaderSampledImageArrayDynamicIndexing = shaderSampledImageArrayDynamicIndexing,
+	ShaderStorageBufferArrayDynamicIndexing = shaderStorageBufferArrayDynamicIndexing,
+	ShaderStorageImageArrayDynamicIndexing = shaderStorageImageArrayDynamicIndexing,
+	ShaderClipDistance = shaderClipDistance, ShaderCullDistance = shaderCullDistance, ShaderFloat64 = shaderFloat64,
+	ShaderInt64 = shaderInt64, ShaderInt16 = shaderInt16, ShaderResourceResidency = shaderResourceResidency,
+	ShaderResourceMinLod = shaderResourceMinLod, SparseBinding = sparseBinding, SparseResidencyBuffer = sparseResidencyBuffer,
+	SparseResidencyImage2D = sparseResidencyImage2D, SparseResidencyImage3D = sparseResidencyImage3D,
+	SparseResidency2Samples = sparseResidency2Samples, SparseResidency4Samples = sparseResidency4Samples,
+	SparseResidency8Samples = sparseResidency8Samples, SparseResidency16Samples = sparseResidency16Samples,
+	SparseResidencyAliased = sparseResidencyAliased, VariableMultisampleRate = variableMultisampleRate,
+	InheritedQueries = inheritedQueries
+}
+
+/// Set of `Feature`s
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct FeatureSet(VkPhysicalDeviceFeatures);
+impl FeatureSet
+{
+	/// Empty set
+	pub fn new() -> Self { Self::default() }
+	pub fn contains(&self, feature: Feature) -> bool { feature.member(&self.0) != VK_FALSE }
+	pub fn insert(&mut self, feature: Feature) -> &mut Self { *feature.member_mut(&mut self.0) = VK_TRUE; self }
+	pub fn remove(&mut self, feature: Feature) -> &mut Self { *feature.member_mut(&mut self.0) = VK_FALSE; self }
+	/// Features in the set
+	pub fn iter(&self) -> impl Iterator<Item = Feature> + '_ { Feature::ALL.iter().cloned().filter(move |&f| self.contains(f)) }
+	/// Add all features in `other`
+	pub fn union(&mut self, other: &FeatureSet) -> &mut Self
+	{
+		for f in other.iter() { self.insert(f); } self
+	}
+	pub fn raw(&self) -> &VkPhysicalDeviceFeatures { &self.0 }
+}
+impl From<VkPhysicalDeviceFeatures> for FeatureSet { fn from(f: VkPhysicalDeviceFeatures) -> Self { FeatureSet(f) } }
+impl From<FeatureSet> for VkPhysicalDeviceFeatures { fn from(f: FeatureSet) -> Self { f.0 } }
+impl ::std::iter::FromIterator<Feature> for FeatureSet
+{
+	fn from_iter<I: IntoIterator<Item = Feature>>(iter: I) -> Self
+	{
+		let mut s = FeatureSet::new(); for f in iter { s.insert(f); } s
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	#[test]
+	fn sets()
+	{
+		let mut s: FeatureSet = vec![Feature::GeometryShader, Feature::ShaderInt64].into_iter().collect();
+		assert!(s.contains(Feature::GeometryShader) && !s.contains(Feature::RobustBufferAccess));
+		assert_eq!(s.raw().shaderInt64, VK_TRUE);
+		s.remove(Feature::ShaderInt64).union(&vec![Feature::RobustBufferAccess].into_iter().collect());
+		// in the order of the members
+		assert_eq!(s.iter().collect::<Vec<_>>(), vec![Feature::RobustBufferAccess, Feature::GeometryShader]);
+		assert_eq!(Feature::GeometryShader.name(), "geometryShader");
+		assert_eq!(Feature::ALL.len() * ::std::mem::size_of::<VkBool32>(), ::std::mem::size_of::<VkPhysicalDeviceFeatures>());
+	}
+
+	#[test]
+	#[cfg(all(feature = "Implements", feature = "CustomResolver"))]
+	fn requests()
+	{
+		use {InstanceBuilder, DeviceBuilder, DeviceQueueCreateInfo, Requirement};
+		use fakedrv::{test_device, assert_no_misuse};
+
+		// the fake driver supports no sparse residency
+		let (driver, instance, device) = test_device(|_| (), |b|
+		{
+			b.request_feature(Feature::GeometryShader, Requirement::Required)
+				.request_feature(Feature::SparseResidencyBuffer, Requirement::Optional);
+		});
+		assert_eq!(device.enabled_features().iter().collect::<Vec<_>>(), vec![Feature::GeometryShader]);
+		assert_no_misuse(&driver, &instance);
+
+		let instance = InstanceBuilder::new("t", (0, 1, 0), "t", (0, 1, 0)).create().unwrap();
+		let pd = instance.iter_physical_devices().unwrap().next().unwrap();
+		let mut b = DeviceBuilder::new(&pd);
+		b.add_queue(DeviceQueueCreateInfo(0, vec![0.0]));
+		// the stronger requirement wins regardless of the order
+		b.request_feature(Feature::SparseResidencyBuffer, Requirement::Required).request_feature(Feature::SparseResidencyBuffer, Requirement::Optional);
+		assert_eq!(b.create().err(), Some(::Error::MissingFeature(Feature::SparseResidencyBuffer)));
+		assert_no_misuse(&driver, &instance);
+	}
+}
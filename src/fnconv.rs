@@ -61,5 +61,11 @@ FnTransmuteImpl!(for PFN_vkDestroySurfaceKHR, PFN_vkGetPhysicalDeviceSurfaceSupp
 #[cfg(feature = "VK_EXT_debug_report")]
 FnTransmuteImpl!(for PFN_vkCreateDebugReportCallbackEXT, PFN_vkDebugReportMessageEXT, PFN_vkDestroyDebugReportCallbackEXT);
 
+#[cfg(feature = "VK_EXT_debug_utils")]
+FnTransmuteImpl!(for PFN_vkCreateDebugUtilsMessengerEXT, PFN_vkDestroyDebugUtilsMessengerEXT, PFN_vkSubmitDebugUtilsMessageEXT);
+
 #[cfg(feature = "VK_KHR_get_surface_capabilities2")]
 FnTransmuteImpl!(for PFN_vkGetPhysicalDeviceSurfaceCapabilities2KHR, PFN_vkGetPhysicalDeviceSurfaceFormats2KHR);
+
+#[cfg(feature = "VK_EXT_hdr_metadata")]
+FnTransmuteImpl!(for PFN_vkSetHdrMetadataEXT);
@@ -5,12 +5,12 @@ use {VkHandle, DeviceChild};
 #[cfg(feature = "Implements")] use VkResultHandler;
 use std::borrow::{Borrow, BorrowMut};
 use ImageLayout;
-#[cfg(feature = "Implements")] use ::vkresolve::{Resolver, ResolverInterface};
 
 /// Opaque handle to a render pass object
 pub struct RenderPass(VkRenderPass, ::Device);
 /// Opaque handle to a framebuffer object
 pub struct Framebuffer(VkFramebuffer, ::Device, Vec<::ImageView>, ::Extent2D);
+ThreadSafe!(Send + Sync: RenderPass, Framebuffer);
 
 #[cfg(feature = "Implements")] DeviceChildCommonDrop!{
 	for RenderPass[destroy_render_pass], Framebuffer[destroy_framebuffer]
@@ -252,7 +252,7 @@ impl RenderPassBuilder
 			.. Default::default()
 		};
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_render_pass(device.native_ptr(), &cinfo, ::std::ptr::null(), &mut h) }.into_result()
+		unsafe { device.dispatch().create_render_pass(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h) }.into_result_in("vkCreateRenderPass", "VkDevice")
 			.map(|_| RenderPass(h, device.clone()))
 	}
 }
@@ -274,7 +274,7 @@ impl Framebuffer
 			width: size.as_ref().0, height: size.as_ref().1, layers, .. Default::default()
 		};
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_framebuffer(mold.1.native_ptr(), &cinfo, ::std::ptr::null(), &mut h) }.into_result()
+		unsafe { mold.1.dispatch().create_framebuffer(mold.1.native_ptr(), &cinfo, mold.1.allocation_callbacks(), &mut h) }.into_result_in("vkCreateFramebuffer", "VkDevice")
 			.map(|_| Framebuffer(h, mold.1.clone(), attachment_objects.iter().map(|&x| x.clone()).collect(), size.as_ref().clone()))
 	}
 }
@@ -291,6 +291,6 @@ impl RenderPass
 	pub fn optimal_granularity(&self) -> ::Extent2D
 	{
 		let mut e = ::Extent2D(0, 0);
-		unsafe { Resolver::get().get_render_area_granularity(self.1.native_ptr(), self.0, ::std::mem::transmute(&mut e)) }; e
+		unsafe { self.1.dispatch().get_render_area_granularity(self.1.native_ptr(), self.0, ::std::mem::transmute(&mut e)) }; e
 	}
 }
@@ -0,0 +1,253 @@
+//! Host Memory Allocation(`VkAllocationCallbacks`)
+
+use vk::*;
+use libc::c_void;
+#[cfg(feature = "Implements")] use libc::size_t;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::alloc::{alloc, dealloc, Layout};
+
+/// Host memory allocator used by the implementation for the objects created from an `Instance` or a `Device`.
+/// Methods may be called from any threads
+pub trait AllocationCallbacks: Send + Sync
+{
+	/// Allocate `size` bytes aligned to `alignment`. Returns null on failure
+	fn allocate(&self, size: usize, alignment: usize, scope: VkSystemAllocationScope) -> *mut c_void;
+	/// Resize `original`(never null) to `size`(never zero) bytes, keeping its contents. Returns null on failure
+	/// # Safety
+	/// `original` is a live block returned by this allocator
+	unsafe fn reallocate(&self, original: *mut c_void, size: usize, alignment: usize, scope: VkSystemAllocationScope) -> *mut c_void;
+	/// Release `memory`(never null) allocated by `allocate` or `reallocate`
+	/// # Safety
+	/// `memory` is a live block returned by this allocator
+	unsafe fn free(&self, memory: *mut c_void);
+	/// The implementation allocated `size` bytes of its own
+	fn internal_allocated(&self, _size: usize, _ty: VkInternalAllocationType, _scope: VkSystemAllocationScope) {}
+	/// The implementation freed `size` bytes of its own
+	fn internal_freed(&self, _size: usize, _ty: VkInternalAllocationType, _scope: VkSystemAllocationScope) {}
+}
+impl<A: AllocationCallbacks + ?Sized> AllocationCallbacks for Arc<A>
+{
+	fn allocate(&self, size: usize, alignment: usize, scope: VkSystemAllocationScope) -> *mut c_void
+	{
+		(**self).allocate(size, alignment, scope)
+	}
+	unsafe fn reallocate(&self, original: *mut c_void, size: usize, alignment: usize, scope: VkSystemAllocationScope) -> *mut c_void
+	{
+		(**self).reallocate(original, size, alignment, scope)
+	}
+	unsafe fn free(&self, memory: *mut c_void) { (**self).free(memory) }
+	fn internal_allocated(&self, size: usize, ty: VkInternalAllocationType, scope: VkSystemAllocationScope)
+	{
+		(**self).internal_allocated(size, ty, scope)
+	}
+	fn internal_freed(&self, size: usize, ty: VkInternalAllocationType, scope: VkSystemAllocationScope)
+	{
+		(**self).internal_freed(size, ty, scope)
+	}
+}
+
+/// `VkAllocationCallbacks` bound to an `AllocationCallbacks` object, shared by the objects allocated through it
+#[derive(Clone)]
+pub(crate) struct HostAllocator(#[cfg(feature = "Implements")] Arc<HostAllocatorCell>);
+#[cfg(feature = "Implements")]
+struct HostAllocatorCell { callbacks: VkAllocationCallbacks, _object: Box<Box<dyn AllocationCallbacks>> }
+// pUserData only points to the object owned by the cell
+#[cfg(feature = "Implements")] unsafe impl Send for HostAllocatorCell {}
+#[cfg(feature = "Implements")] unsafe impl Sync for HostAllocatorCell {}
+impl HostAllocator
+{
+	/// Nothing is allocated without [feature = "Implements"], so `object` is just dropped
+	#[cfg(not(feature = "Implements"))]
+	pub fn new<A: AllocationCallbacks + 'static>(_object: A) -> Self { HostAllocator() }
+	#[cfg(feature = "Implements")]
+	pub fn new<A: AllocationCallbacks + 'static>(object: A) -> Self
+	{
+		let object: Box<Box<dyn AllocationCallbacks>> = Box::new(Box::new(object));
+		let callbacks = VkAllocationCallbacks
+		{
+			pUserData: &*object as *const Box<dyn AllocationCallbacks> as *mut _,
+			pfnAllocation: allocation, pfnReallocation: reallocation, pfnFree: free,
+			pfnInternalAllocation: Some(internal_allocation), pfnInternalFree: Some(internal_free)
+		};
+		HostAllocator(Arc::new(HostAllocatorCell { callbacks, _object: object }))
+	}
+	/// `pAllocator` for the commands
+	#[cfg(feature = "Implements")]
+	pub fn callbacks(this: Option<&Self>) -> *const VkAllocationCallbacks
+	{
+		this.map_or(::std::ptr::null(), |a| &a.0.callbacks as *const _)
+	}
+}
+
+#[cfg(feature = "Implements")]
+unsafe fn object<'a>(p: *mut c_void) -> &'a dyn AllocationCallbacks { &**(p as *const Box<dyn AllocationCallbacks>) }
+#[cfg(feature = "Implements")]
+extern "system" fn allocation(p: *mut c_void, size: size_t, alignment: size_t, scope: VkSystemAllocationScope) -> *mut c_void
+{
+	unsafe { object(p) }.allocate(size, alignment, scope)
+}
+#[cfg(feature = "Implements")]
+extern "system" fn reallocation(p: *mut c_void, original: *mut c_void, size: size_t, alignment: size_t, scope: VkSystemAllocationScope)
+	-> *mut c_void
+{
+	let o = unsafe { object(p) };
+	if original.is_null() { o.allocate(size, alignment, scope) }
+	else if size == 0 { unsafe { o.free(original); } ::std::ptr::null_mut() }
+	else { unsafe { o.reallocate(original, size, alignment, scope) } }
+}
+#[cfg(feature = "Implements")]
+extern "system" fn free(p: *mut c_void, memory: *mut c_void)
+{
+	if !memory.is_null() { unsafe { object(p).free(memory) } }
+}
+#[cfg(feature = "Implements")]
+extern "system" fn internal_allocation(p: *mut c_void, size: size_t, ty: VkInternalAllocationType, scope: VkSystemAllocationScope)
+{
+	unsafe { object(p) }.internal_allocated(size, ty, scope)
+}
+#[cfg(feature = "Implements")]
+extern "system" fn internal_free(p: *mut c_void, size: size_t, ty: VkInternalAllocationType, scope: VkSystemAllocationScope)
+{
+	unsafe { object(p) }.internal_freed(size, ty, scope)
+}
+
+/// An allocator counting the live bytes per `VkSystemAllocationScope`, allocating from the global allocator.
+/// Wrap in an `Arc` to read the counts while attached
+#[derive(Default)]
+pub struct CountingAllocator { allocated: [AtomicUsize; 5], internal: [AtomicUsize; 5] }
+/// Bookkeeping placed in front of each block
+#[repr(C)] struct BlockHeader { size: usize, alignment: usize, scope: VkSystemAllocationScope }
+impl CountingAllocator
+{
+	pub fn new() -> Self { Self::default() }
+	/// Bytes currently allocated through this allocator in `scope`
+	pub fn allocated(&self, scope: VkSystemAllocationScope) -> usize { self.allocated[scope as usize].load(Ordering::Relaxed) }
+	/// Bytes currently allocated by the implementation itself in `scope`
+	pub fn internal_allocated(&self, scope: VkSystemAllocationScope) -> usize { self.internal[scope as usize].load(Ordering::Relaxed) }
+	/// Bytes currently allocated through this allocator in all scopes
+	pub fn total_allocated(&self) -> usize { self.allocated.iter().map(|x| x.load(Ordering::Relaxed)).sum() }
+
+	fn header_size(alignment: usize) -> usize
+	{
+		let h = ::std::mem::size_of::<BlockHeader>();
+		(h + alignment - 1) & !(alignment - 1)
+	}
+	fn layout(size: usize, alignment: usize) -> Option<Layout>
+	{
+		let alignment = alignment.max(::std::mem::align_of::<BlockHeader>());
+		Layout::from_size_align(Self::header_size(alignment) + size, alignment).ok()
+	}
+	unsafe fn header<'a>(memory: *mut c_void) -> &'a BlockHeader
+	{
+		&*(memory as *const BlockHeader).offset(-1)
+	}
+}
+impl AllocationCallbacks for CountingAllocator
+{
+	fn allocate(&self, size: usize, alignment: usize, scope: VkSystemAllocationScope) -> *mut c_void
+	{
+		let layout = match Self::layout(size, alignment) { Some(l) => l, None => return ::std::ptr::null_mut() };
+		unsafe
+		{
+			let base = alloc(layout);
+			if base.is_null() { return ::std::ptr::null_mut(); }
+			let p = base.add(Self::header_size(layout.align()));
+			(p as *mut BlockHeader).offset(-1).write(BlockHeader { size, alignment: layout.align(), scope });
+			self.allocated[scope as usize].fetch_add(size, Ordering::Relaxed);
+			p as _
+		}
+	}
+	unsafe fn reallocate(&self, original: *mut c_void, size: usize, alignment: usize, scope: VkSystemAllocationScope) -> *mut c_void
+	{
+		let p = self.allocate(size, alignment, scope);
+		if !p.is_null()
+		{
+			let old_size = Self::header(original).size;
+			::std::ptr::copy_nonoverlapping(original as *const u8, p as *mut u8, old_size.min(size));
+			self.free(original);
+		}
+		p
+	}
+	unsafe fn free(&self, memory: *mut c_void)
+	{
+		let (size, alignment, scope) = { let h = Self::header(memory); (h.size, h.alignment, h.scope) };
+		self.allocated[scope as usize].fetch_sub(size, Ordering::Relaxed);
+		let layout = Self::layout(size, alignment).expect("corrupted block header");
+		dealloc((memory as *mut u8).sub(Self::header_size(alignment)), layout);
+	}
+	fn internal_allocated(&self, size: usize, _ty: VkInternalAllocationType, scope: VkSystemAllocationScope)
+	{
+		self.internal[scope as usize].fetch_add(size, Ordering::Relaxed);
+	}
+	fn internal_freed(&self, size: usize, _ty: VkInternalAllocationType, scope: VkSystemAllocationScope)
+	{
+		self.internal[scope as usize].fetch_sub(size, Ordering::Relaxed);
+	}
+}
+
+#[cfg(test)]
+mod tests
+{
+	use super::*;
+
+	#[test]
+	fn counting()
+	{
+		let a = CountingAllocator::new();
+		let p = a.allocate(100, 64, VK_SYSTEM_ALLOCATION_SCOPE_OBJECT);
+		assert!(!p.is_null());
+		assert_eq!(p as usize % 64, 0);
+		let q = a.allocate(8, 1, VK_SYSTEM_ALLOCATION_SCOPE_DEVICE);
+		assert_eq!((a.allocated(VK_SYSTEM_ALLOCATION_SCOPE_OBJECT), a.allocated(VK_SYSTEM_ALLOCATION_SCOPE_DEVICE), a.total_allocated()), (100, 8, 108));
+
+		unsafe
+		{
+			::std::ptr::write_bytes(p as *mut u8, 0xab, 100);
+			// resized into another scope, keeping the contents
+			let r = a.reallocate(p, 200, 128, VK_SYSTEM_ALLOCATION_SCOPE_CACHE);
+			assert_eq!(r as usize % 128, 0);
+			assert!(::std::slice::from_raw_parts(r as *const u8, 100).iter().all(|&b| b == 0xab));
+			assert_eq!((a.allocated(VK_SYSTEM_ALLOCATION_SCOPE_OBJECT), a.allocated(VK_SYSTEM_ALLOCATION_SCOPE_CACHE)), (0, 200));
+			a.free(r);
+			a.free(q);
+		}
+		assert_eq!(a.total_allocated(), 0);
+
+		AllocationCallbacks::internal_allocated(&a, 32, VK_INTERNAL_ALLOCATION_TYPE_EXECUTABLE, VK_SYSTEM_ALLOCATION_SCOPE_INSTANCE);
+		assert_eq!(a.internal_allocated(VK_SYSTEM_ALLOCATION_SCOPE_INSTANCE), 32);
+		AllocationCallbacks::internal_freed(&a, 32, VK_INTERNAL_ALLOCATION_TYPE_EXECUTABLE, VK_SYSTEM_ALLOCATION_SCOPE_INSTANCE);
+		assert_eq!(a.internal_allocated(VK_SYSTEM_ALLOCATION_SCOPE_INSTANCE), 0);
+		// internal allocations are not counted as allocated through the allocator
+		assert_eq!(a.total_allocated(), 0);
+	}
+
+	/// The callbacks handed to the implementation follow the `vkReallocationFunction` rules for null and zero-sized requests
+	#[test]
+	#[cfg(feature = "Implements")]
+	fn callbacks()
+	{
+		let counts = Arc::new(CountingAllocator::new());
+		let host = HostAllocator::new(counts.clone());
+		let cb = unsafe { &*HostAllocator::callbacks(Some(&host)) };
+		assert!(HostAllocator::callbacks(None).is_null());
+
+		let p = (cb.pfnAllocation)(cb.pUserData, 16, 8, VK_SYSTEM_ALLOCATION_SCOPE_COMMAND);
+		assert_eq!(counts.allocated(VK_SYSTEM_ALLOCATION_SCOPE_COMMAND), 16);
+		let p = (cb.pfnReallocation)(cb.pUserData, p, 48, 8, VK_SYSTEM_ALLOCATION_SCOPE_COMMAND);
+		assert_eq!(counts.allocated(VK_SYSTEM_ALLOCATION_SCOPE_COMMAND), 48);
+		// null original allocates, zero size frees
+		let q = (cb.pfnReallocation)(cb.pUserData, ::std::ptr::null_mut(), 4, 4, VK_SYSTEM_ALLOCATION_SCOPE_OBJECT);
+		assert_eq!(counts.allocated(VK_SYSTEM_ALLOCATION_SCOPE_OBJECT), 4);
+		assert!((cb.pfnReallocation)(cb.pUserData, q, 0, 4, VK_SYSTEM_ALLOCATION_SCOPE_OBJECT).is_null());
+		assert_eq!(counts.allocated(VK_SYSTEM_ALLOCATION_SCOPE_OBJECT), 0);
+		(cb.pfnFree)(cb.pUserData, p);
+		(cb.pfnFree)(cb.pUserData, ::std::ptr::null_mut());
+		assert_eq!(counts.total_allocated(), 0);
+
+		(cb.pfnInternalAllocation.unwrap())(cb.pUserData, 24, VK_INTERNAL_ALLOCATION_TYPE_EXECUTABLE, VK_SYSTEM_ALLOCATION_SCOPE_DEVICE);
+		assert_eq!(CountingAllocator::internal_allocated(&counts, VK_SYSTEM_ALLOCATION_SCOPE_DEVICE), 24);
+		(cb.pfnInternalFree.unwrap())(cb.pUserData, 24, VK_INTERNAL_ALLOCATION_TYPE_EXECUTABLE, VK_SYSTEM_ALLOCATION_SCOPE_DEVICE);
+		assert_eq!(CountingAllocator::internal_allocated(&counts, VK_SYSTEM_ALLOCATION_SCOPE_DEVICE), 0);
+	}
+}
@@ -6,8 +6,12 @@
 //!
 //! # Compile Options
 //! - `Implements`: Enable Vulkan implementations(functions)
-//! - `Multithreaded`: Enables to use objects from some threads(experimental)
+//! - `DynamicLoaded`: Load the Vulkan library at runtime(`Resolver::load_global`/`Resolver::try_get`) instead of linking to it
+//! - `Multithreaded`: Share objects between threads(`Arc` reference counting, `Send`/`Sync` objects and locks for the externally synchronized handles)
+//! - `CustomResolver`: Route every Vulkan call through a resolver installed by `set_custom_resolver`(e.g. `fakedrv::FakeDriver`, `apitrace::Recorder`)
 //! - `Presentation`: Enable rendering features to Window/Display(`VK_KHR_surface`/`VK_KHR_swapchain`/`VK_KHR_display`)
+//! - `log`: Forward debug report messages to the `log` facade(`DebugReportLogger`)
+//! - `png`: Encode downloaded images into PNG(`ImageData::write_png`)
 //! - `VK_***`: Enable Vulkan extensions(same name as each extensions)
 //!   - Pseudo Extension: `VK_EXT_full_screen_exclusive_win32` for using `VK_EXT_full_screen_exclusive` on Win32 platform
 #![warn(clippy::all)]
@@ -30,6 +34,10 @@ extern crate wayland_client;
 extern crate android;
 #[cfg(feature = "DynamicLoaded")]
 extern crate libloading;
+#[cfg(feature = "log")]
+extern crate log;
+#[cfg(feature = "png")]
+extern crate png;
 
 #[macro_use]
 pub mod vk;
@@ -37,80 +45,28 @@ use vk::*;
 #[cfg(feature = "Implements")]
 mod vkresolve;
 #[cfg(feature = "Implements")]
-pub use vkresolve::{Resolver, ResolverInterface};
-
-use std::error::Error;
+pub use vkresolve::{Resolver, ResolverInterface, Dispatch};
+#[cfg(all(feature = "Implements", any(not(feature = "CustomResolver"), feature = "DynamicLoaded")))]
+pub use vkresolve::DispatchTable;
+#[cfg(feature = "DynamicLoaded")]
+pub use vkresolve::LoadError;
+#[cfg(all(feature = "Implements", feature = "CustomResolver"))]
+pub use vkresolve::set_custom_resolver;
 
 #[cfg(feature = "Implements")] mod fnconv;
 
-pub type Result<T> = std::result::Result<T, VkResultBox>;
-pub trait VkResultHandler
-{
-	fn into_result(self) -> Result<()>;
-}
-impl VkResultHandler for VkResult
-{
-	fn into_result(self) -> Result<()> { if self == VK_SUCCESS { Ok(()) } else { Err(VkResultBox(self)) } }
-}
-/// Boxed version of `VkResult`
-#[derive(Clone, Copy, PartialEq, Eq)]
-pub struct VkResultBox(pub VkResult);
-impl Error for VkResultBox
-{
-    fn description(&self) -> &str
-    {
-        match self.0
-        {
-            // Success Codes //
-            VK_SUCCESS => "Command successfully completed", VK_NOT_READY => "A fence or query has not yet completed",
-            VK_TIMEOUT => "A wait operation has not completed in the specified time", VK_EVENT_SET => "An event is signaled",
-            VK_EVENT_RESET => "An event is unsignaled", VK_INCOMPLETE => "A return array was too small for the result",
-            #[cfg(feature = "VK_KHR_swapchain")]
-            VK_SUBOPTIMAL_KHR => "Sub-optimal swapchain",
-            // Error Codes //
-            VK_ERROR_OUT_OF_HOST_MEMORY => "A host memory allocation has failed",
-            VK_ERROR_OUT_OF_DEVICE_MEMORY => "A device memory allocation has failed",
-            VK_ERROR_INITIALIZATION_FAILED => "Initialization of an object could not be completed for implementation-specific reasons",
-            VK_ERROR_DEVICE_LOST => "The logical or physical device has been lost",
-            VK_ERROR_MEMORY_MAP_FAILED => "Mapping of a memory object has failed",
-            VK_ERROR_LAYER_NOT_PRESENT => "A requested layer is not presented or could not be loaded",
-            VK_ERROR_EXTENSION_NOT_PRESENT => "A requested extension is not supported",
-            VK_ERROR_FEATURE_NOT_PRESENT => "A requested feature is not supported",
-            VK_ERROR_INCOMPATIBLE_DRIVER => "The requested version of Vulkan is not supported by the driver or is otherwise incompatible for implementation-specific reasons",
-            VK_ERROR_TOO_MANY_OBJECTS => "Too many objects of the type have already been created",
-            VK_ERROR_FORMAT_NOT_SUPPORTED => "A requested format is not supported on this device",
-            VK_ERROR_FRAGMENTED_POOL => "A pool allocation has failed due to fragmentation of the pool's memory",
-            #[cfg(feature = "VK_KHR_surface")]
-            VK_ERROR_SURFACE_LOST_KHR => "Surface lost",
-            #[cfg(feature = "VK_KHR_surface")]
-            VK_ERROR_NATIVE_WINDOW_IN_USE_KHR => "Native window is in use",
-            #[cfg(feature = "VK_KHR_swapchain")]
-            VK_ERROR_OUT_OF_DATE_KHR => "Out of date",
-            #[cfg(feature = "VK_KHR_display_swapchain")]
-            VK_ERROR_INCOMPATIBLE_DISPLAY_KHR => "The display used by a swapchain does not use the same presentable image layout",
-            #[cfg(feature = "VK_EXT_debug_report")]
-            VK_ERROR_VALIDATION_FAILED_EXT => "Validation failed",
-            #[cfg(feature = "VK_NV_glsl_shader")]
-            VK_ERROR_INVALID_SHADER_NV => "Invalid GLSL shader",
-            #[cfg(feature = "VK_KHR_maintenance1")]
-            VK_ERROR_OUT_OF_POOL_MEMORY_KHR => "A pool memory allocation has failed",
-            #[cfg(feature = "VK_KHR_external_memory_capabilities")]
-            VK_ERROR_INVALID_EXTERNAL_HANDLE_KHR => "An external handle is not a valid handle of ths specified type",
-            _ => "Unknown or extension-specific error"
-        }
-    }
-}
-impl std::fmt::Debug for VkResultBox
-{
-    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result
-    {
-        write!(fmt, "[{:?}] {}", self.0, self.description())
-    }
-}
-impl std::fmt::Display for VkResultBox
-{
-    fn fmt(&self, fmt: &mut std::fmt::Formatter) -> std::fmt::Result { std::fmt::Debug::fmt(self, fmt) }
-}
+mod error;
+pub use error::*;
+mod hostalloc;
+pub use hostalloc::{AllocationCallbacks, CountingAllocator};
+mod extsync;
+mod extensions;
+pub use extensions::{Requirement, ExtensionScope, KnownExtension, KNOWN_EXTENSIONS, known_extension, feature_extensions};
+mod features;
+pub use features::{Feature, FeatureSet};
+
+#[cfg(feature = "Multithreaded")] pub(crate) use std::sync::Arc as RefCounter;
+#[cfg(not(feature = "Multithreaded"))] pub(crate) use std::rc::Rc as RefCounter;
 
 /// Wrapping a Vulkan Dispatchable/Nondispatchable Handler
 pub trait VkHandle
@@ -140,11 +96,24 @@ macro_rules! DeviceChildCommonDrop
 	{ for $($t: ty [$d: ident]),* } =>
 	{
 		$(
-			impl Drop for $t { fn drop(&mut self) { unsafe { Resolver::get().$d(self.1.native_ptr(), self.0, ::std::ptr::null()) }; } }
+			impl Drop for $t { fn drop(&mut self) { unsafe { self.1.dispatch().$d(self.1.native_ptr(), self.0, self.1.allocation_callbacks()) }; } }
 		)*
 	}
 }
 
+/// Opt the handle wrappers into `Send`/`Sync` with `Multithreaded`
+macro_rules! ThreadSafe
+{
+	(Send + Sync: $($t: ty),*) =>
+	{
+		$(
+			#[cfg(feature = "Multithreaded")] unsafe impl Send for $t {}
+			#[cfg(feature = "Multithreaded")] unsafe impl Sync for $t {}
+		)*
+	};
+	(Send: $($t: ty),*) => { $(#[cfg(feature = "Multithreaded")] unsafe impl Send for $t {})* }
+}
+
 // A single Number or a Range
 pub trait AnalogNumRange<T>
 {
@@ -166,7 +135,7 @@ impl<T> AnalogNumRange<T> for std::ops::Range<T> where T: Copy
 /// All of traits
 pub mod traits
 {
-    pub use super::{VkResultBox, VkHandle, DeviceChild, ClearColorValue, ImageSize, AnalogNumRange};
+    pub use super::{VkResultHandler, VkHandle, DeviceChild, ClearColorValue, ImageSize, AnalogNumRange};
     #[cfg(feature = "Implements")]
     pub use super::{MemoryBound, Status, Waitable};
 }
@@ -316,15 +285,29 @@ mod base; pub use base::*;
 mod device; pub use device::*;
 mod sync; pub use sync::*;
 pub mod resources; pub use resources::*;
+mod allocator; pub use allocator::*;
+mod budget; pub use budget::*;
+#[cfg(feature = "Implements")] mod uploader;
+#[cfg(feature = "Implements")] pub use uploader::*;
+#[cfg(feature = "Implements")] mod readback;
+#[cfg(feature = "Implements")] pub use readback::*;
+#[cfg(feature = "Implements")] mod export;
+#[cfg(feature = "Implements")] pub use export::*;
+mod chain; pub use chain::*;
 #[macro_use]
 mod descriptor; pub use descriptor::*;
 mod framebuffer; pub use framebuffer::*;
 mod shading; pub use shading::*;
 mod command; pub use command::*;
+mod selector; pub use selector::*;
 #[cfg(feature = "Presentation")] mod surface;
 #[cfg(feature = "Presentation")] pub use surface::*;
 #[cfg(feature = "VK_EXT_debug_report")] mod debug;
 #[cfg(feature = "VK_EXT_debug_report")] pub use debug::*;
+#[cfg(feature = "VK_EXT_debug_utils")] mod debug_utils;
+#[cfg(feature = "VK_EXT_debug_utils")] pub use debug_utils::*;
+#[cfg(all(feature = "Implements", feature = "CustomResolver"))] pub mod fakedrv;
+#[cfg(all(feature = "Implements", feature = "CustomResolver"))] pub mod apitrace;
 
 /// Unsafe Utils: Preserving a typed buffer
 #[cfg(feature = "Implements")]
@@ -332,7 +315,10 @@ pub(self) unsafe fn preserve<T>(n: usize) -> Vec<T> { let mut v = Vec::with_capa
 
 /// Opaque handle to a query pool object
 pub struct QueryPool(VkQueryPool, Device);
+// only vkDestroyQueryPool needs external synchronization, and it runs on drop with exclusive access
+ThreadSafe!(Send + Sync: QueryPool);
 impl VkHandle for QueryPool { type Handle = VkQueryPool; fn native_ptr(&self) -> VkQueryPool { self.0 } }
+impl DeviceChild for QueryPool { fn device(&self) -> &Device { &self.1 } }
 #[cfg(feature = "Implements")]
 impl QueryPool
 {
@@ -352,8 +338,8 @@ impl QueryPool
         };
         let cinfo = VkQueryPoolCreateInfo { queryType: qtype, queryCount: count, pipelineStatistics: stats, .. Default::default() };
         let mut h = VK_NULL_HANDLE as _;
-        unsafe { Resolver::get().create_query_pool(device.native_ptr(), &cinfo, std::ptr::null(), &mut h) }
-            .into_result().map(|_| QueryPool(h, device.clone()))
+        unsafe { device.dispatch().create_query_pool(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h) }
+            .into_result_in("vkCreateQueryPool", "VkDevice").map(|_| QueryPool(h, device.clone()))
     }
     /// Copy results of queries in a query pool to a host memory region
     /// # Failure
@@ -365,9 +351,9 @@ impl QueryPool
     pub fn results64(&self, query_range: std::ops::Range<u32>, flags: QueryResultFlags) -> Result<Vec<u64>>
     {
         let mut v = Vec::with_capacity(query_range.len()); unsafe { v.set_len(query_range.len()) };
-        unsafe { Resolver::get().get_query_pool_results(self.1.native_ptr(), self.0, query_range.start, query_range.len() as _,
+        unsafe { self.1.dispatch().get_query_pool_results(self.1.native_ptr(), self.0, query_range.start, query_range.len() as _,
             8 * query_range.len(), v.as_mut_ptr() as *mut _, 8, flags.0 | VK_QUERY_RESULT_64_BIT) }
-            .into_result().map(|_| v)
+            .into_result_in("vkGetQueryPoolResults", "VkDevice").map(|_| v)
     }
     /// Copy results of queries in a query pool to a host memory region
     /// # Failure
@@ -379,8 +365,8 @@ impl QueryPool
     pub fn results32(&self, query_range: std::ops::Range<u32>, flags: QueryResultFlags) -> Result<Vec<u32>>
     {
         let mut v = Vec::with_capacity(query_range.len()); unsafe { v.set_len(query_range.len()) };
-        unsafe { Resolver::get().get_query_pool_results(self.1.native_ptr(), self.0, query_range.start, query_range.len() as _,
-            4 * query_range.len(), v.as_mut_ptr() as *mut _, 4, flags.0) }.into_result().map(|_| v)
+        unsafe { self.1.dispatch().get_query_pool_results(self.1.native_ptr(), self.0, query_range.start, query_range.len() as _,
+            4 * query_range.len(), v.as_mut_ptr() as *mut _, 4, flags.0) }.into_result_in("vkGetQueryPoolResults", "VkDevice").map(|_| v)
     }
 }
 #[cfg(feature = "Implements")] DeviceChildCommonDrop!{ for QueryPool[destroy_query_pool] }
@@ -0,0 +1,372 @@
+//! Reading back the contents of images and buffers to the host
+//!
+//! `Readback` records a copy into a host-visible staging buffer, submits it to its queue and waits,
+//! returning the bytes in a tightly packed `Vec<u8>`. Images are downloaded with their format and extent(`ImageData`).
+//!
+//! ```rust,ignore
+//! let readback = Readback::new(&device, device.queue(family, 0), family)?;
+//! let subresource = VkImageSubresourceLayers { aspectMask: AspectMask::COLOR.0, mipLevel: 0, baseArrayLayer: 0, layerCount: 1 };
+//! let pixels = readback.image(&render_target, ImageLayout::ColorAttachmentOpt, &subresource)?;
+//! assert_eq!(pixels.texel(0, 0), &[255, 0, 0, 255]);
+//! ```
+
+use vk::*;
+use {Device, DeviceMemory, Buffer, BufferDesc, BufferUsage, Image, ImageLayout, AspectMask, MemoryBound, MemoryPropertyFlags, PixelFormat};
+use {CommandPool, CommandBuffer, Queue, SubmissionBatch, Fence, Waitable, PipelineStageFlags, ImageMemoryBarrier, BufferMemoryBarrier};
+use DeviceChild;
+use std::ops::Range;
+use std::sync::Mutex;
+
+/// Texels of an image subresource downloaded to the host. Rows, depth slices and array layers are tightly packed in this order
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImageData
+{
+	/// Format of the texels. For a depth or stencil aspect, the format of the aspect(e.g. `VK_FORMAT_S8_UINT` for the stencil)
+	pub format: VkFormat,
+	/// Extent of the mip level
+	pub extent: VkExtent3D,
+	/// Number of the array layers
+	pub layers: u32,
+	/// Bytes of a texel
+	pub texel_size: usize,
+	/// The texels
+	pub data: Vec<u8>
+}
+impl ImageData
+{
+	/// Bytes of a row
+	pub fn row_pitch(&self) -> usize { self.extent.width as usize * self.texel_size }
+	/// Bytes of a depth slice
+	pub fn depth_pitch(&self) -> usize { self.row_pitch() * self.extent.height as usize }
+	/// Bytes of an array layer
+	pub fn layer_pitch(&self) -> usize { self.depth_pitch() * self.extent.depth as usize }
+	/// Bytes of the texel at (`x`, `y`) of the first depth slice of the first layer
+	pub fn texel(&self, x: u32, y: u32) -> &[u8] { self.texel_at(x, y, 0, 0) }
+	/// Bytes of the texel at (`x`, `y`, `z`) of the array layer `layer`
+	/// # Panics
+	/// Panics if the position is out of the image
+	pub fn texel_at(&self, x: u32, y: u32, z: u32, layer: u32) -> &[u8]
+	{
+		assert!(x < self.extent.width && y < self.extent.height && z < self.extent.depth && layer < self.layers,
+			"texel ({}, {}, {}) of layer {} is out of the image", x, y, z, layer);
+		let o = layer as usize * self.layer_pitch() + z as usize * self.depth_pitch() + y as usize * self.row_pitch() + x as usize * self.texel_size;
+		&self.data[o .. o + self.texel_size]
+	}
+
+	/// Read the subresource of a `VK_IMAGE_TILING_LINEAR` image directly from its memory, bound at `offset` of `memory`.
+	/// Row, depth and array pitches are taken from `image_subresource_layout`.
+	/// The device writes to the image must have been completed and made available to the host
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_MEMORY_MAP_FAILED`
+	/// * `Error::AlreadyMapped` if `memory` is already mapped
+	/// # Panics
+	/// Panics if the format is block-compressed or `subresource` has more than one aspect
+	pub fn read_linear(image: &Image, memory: &DeviceMemory, offset: usize, subresource: &VkImageSubresourceLayers) -> ::Result<Self>
+	{
+		let (format, texel_size) = aspect_format(image.format(), subresource.aspectMask);
+		let extent = mip_extent(image, subresource.mipLevel);
+		let layouts: Vec<_> = (subresource.baseArrayLayer .. subresource.baseArrayLayer + subresource.layerCount)
+			.map(|l| image.image_subresource_layout(AspectMask(subresource.aspectMask), subresource.mipLevel, l)).collect();
+		let mut data = Vec::with_capacity(extent.width as usize * extent.height as usize * extent.depth as usize * layouts.len() * texel_size);
+		{
+			let mut m = memory.map_scoped(offset .. offset + image.requirements().size as usize)?;
+			m.invalidate()?;
+			let row_bytes = extent.width as usize * texel_size;
+			for l in &layouts
+			{
+				for z in 0 .. extent.depth as u64
+				{
+					for y in 0 .. extent.height as u64
+					{
+						data.extend_from_slice(m.slice::<u8>((l.offset + z * l.depthPitch + y * l.rowPitch) as usize, row_bytes));
+					}
+				}
+			}
+		}
+		Ok(ImageData { format, extent, layers: subresource.layerCount, texel_size, data })
+	}
+}
+
+/// Downloads images and buffers through a staging buffer, submitting the copies to a queue and waiting for them.
+/// The staging buffer is kept for the next download, and recreated only when a larger one is needed.
+/// Downloads from multiple threads are serialized, as they share the staging buffer and the command buffer
+pub struct Readback
+{
+	queue: Queue, pool: CommandPool, command: CommandBuffer, fence: Fence,
+	/// The staging buffer, its memory and its size
+	staging: Mutex<Option<(Buffer, DeviceMemory, VkDeviceSize)>>
+}
+impl Readback
+{
+	/// Create a readback helper submitting to `queue` of the `queue_family`
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn new(device: &Device, queue: Queue, queue_family: u32) -> ::Result<Self>
+	{
+		let pool = CommandPool::new(device, queue_family, true, true)?;
+		let command = pool.alloc(1, true)?.remove(0);
+		Ok(Readback { queue, pool, command, fence: Fence::new(device, false)?, staging: Mutex::new(None) })
+	}
+
+	/// Download `range` bytes of `src`. Writes to the buffer by the preceding commands are waited
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `Error::NoMemoryType` if no host-visible memory type can hold the staging buffer
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_DEVICE_LOST`
+	pub fn buffer(&self, src: &Buffer, range: Range<VkDeviceSize>) -> ::Result<Vec<u8>>
+	{
+		let size = range.end - range.start;
+		if size == 0 { return Ok(Vec::new()); }
+		self.with_staging(size, |staging, memory| self.execute(|rec|
+		{
+			let before = BufferMemoryBarrier::new(src, range.start as usize .. range.end as usize, VK_ACCESS_MEMORY_WRITE_BIT, VK_ACCESS_TRANSFER_READ_BIT);
+			rec.pipeline_barrier(PipelineStageFlags::ALL_COMMANDS, PipelineStageFlags::TRANSFER, false, &[], &[before], &[]);
+			rec.copy_buffer(src, staging, &[VkBufferCopy { srcOffset: range.start, dstOffset: 0, size }]);
+			let after = BufferMemoryBarrier::new(staging, 0 .. size as usize, VK_ACCESS_TRANSFER_WRITE_BIT, VK_ACCESS_HOST_READ_BIT);
+			rec.pipeline_barrier(PipelineStageFlags::TRANSFER, PipelineStageFlags::HOST, false, &[], &[after], &[]);
+		}).and_then(|_| Self::read_staging(memory, size as _)))
+	}
+	/// Download a mip level of the array layers of `src`, currently in `layout`.
+	/// The image is transitioned back to `layout` after the copy, or left in `TransferSrcOpt` if `layout` is `Preinitialized`
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `Error::NoMemoryType` if no host-visible memory type can hold the staging buffer
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_DEVICE_LOST`
+	/// # Panics
+	/// Panics if the format is block-compressed, `subresource` has more than one aspect,
+	/// or `layout` is `Undefined`(the transition from it would discard the contents to be downloaded)
+	pub fn image(&self, src: &Image, layout: ImageLayout, subresource: &VkImageSubresourceLayers) -> ::Result<ImageData>
+	{
+		assert!(layout != ImageLayout::Undefined, "the contents of an image in the Undefined layout cannot be downloaded");
+		let (format, texel_size) = aspect_format(src.format(), subresource.aspectMask);
+		let extent = mip_extent(src, subresource.mipLevel);
+		let size = extent.width as VkDeviceSize * extent.height as VkDeviceSize * extent.depth as VkDeviceSize
+			* subresource.layerCount as VkDeviceSize * texel_size as VkDeviceSize;
+		let range = VkImageSubresourceRange
+		{
+			aspectMask: subresource.aspectMask, baseMipLevel: subresource.mipLevel, levelCount: 1,
+			baseArrayLayer: subresource.baseArrayLayer, layerCount: subresource.layerCount
+		};
+		let restored = if layout == ImageLayout::Preinitialized { ImageLayout::TransferSrcOpt } else { layout };
+		let data = self.with_staging(size, |staging, memory| self.execute(|rec|
+		{
+			let before = ImageMemoryBarrier::new_raw(src, &range, layout, ImageLayout::TransferSrcOpt).src_access_mask(VK_ACCESS_MEMORY_WRITE_BIT);
+			rec.pipeline_barrier(PipelineStageFlags::ALL_COMMANDS, PipelineStageFlags::TRANSFER, false, &[], &[], &[before]);
+			rec.copy_image_to_buffer(src, ImageLayout::TransferSrcOpt, staging, &[VkBufferImageCopy
+			{
+				bufferOffset: 0, bufferRowLength: 0, bufferImageHeight: 0, imageSubresource: subresource.clone(),
+				imageOffset: VkOffset3D { x: 0, y: 0, z: 0 }, imageExtent: extent.clone()
+			}]);
+			let image_after = ImageMemoryBarrier::new_raw(src, &range, ImageLayout::TransferSrcOpt, restored);
+			let staging_after = BufferMemoryBarrier::new(staging, 0 .. size as usize, VK_ACCESS_TRANSFER_WRITE_BIT, VK_ACCESS_HOST_READ_BIT);
+			rec.pipeline_barrier(PipelineStageFlags::TRANSFER, PipelineStageFlags::ALL_COMMANDS.host(), false, &[], &[staging_after], &[image_after]);
+		}).and_then(|_| Self::read_staging(memory, size as _)))?;
+		Ok(ImageData { format, extent, layers: subresource.layerCount, texel_size, data })
+	}
+
+	/// Run `f` with the staging buffer of at least `size` bytes and its memory, holding them for this download
+	fn with_staging<R, F: FnOnce(&Buffer, &DeviceMemory) -> ::Result<R>>(&self, size: VkDeviceSize, f: F) -> ::Result<R>
+	{
+		let mut cached = self.staging.lock().unwrap_or_else(|e| e.into_inner());
+		let (buffer, memory, capacity) = match cached.take()
+		{
+			Some((b, m, c)) if c >= size => (b, m, c),
+			_ => { let (b, m) = self.create_staging(size)?; (b, m, size) }
+		};
+		let r = f(&buffer, &memory);
+		*cached = Some((buffer, memory, capacity));
+		r
+	}
+	/// Create a `TRANSFER_DEST` buffer of `size` bytes bound to a host-visible memory, `HOST_CACHED` ones preferred
+	fn create_staging(&self, size: VkDeviceSize) -> ::Result<(Buffer, DeviceMemory)>
+	{
+		let device = self.pool.device();
+		let buffer = BufferDesc::new(size as _, BufferUsage::TRANSFER_DEST).create(device)?;
+		let req = buffer.requirements();
+		let type_index = device.host_access().find_type(req.memoryTypeBits,
+				&[VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT | VK_MEMORY_PROPERTY_HOST_CACHED_BIT, VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT])
+			.ok_or(::Error::NoMemoryType { type_bits: req.memoryTypeBits, flags: MemoryPropertyFlags::HOST_VISIBLE })?;
+		let memory = DeviceMemory::allocate(device, req.size as _, type_index)?;
+		buffer.bind(&memory, 0)?;
+		Ok((buffer, memory))
+	}
+	/// Record commands with `record`, submit them and wait for the completion
+	fn execute<F: FnOnce(&mut ::CmdRecord)>(&self, record: F) -> ::Result<()>
+	{
+		record(&mut self.command.begin_once()?);
+		self.queue.submit(&[SubmissionBatch { command_buffers: (&[self.command][..]).into(), .. Default::default() }], Some(&self.fence))?;
+		self.fence.wait()?;
+		self.fence.reset()
+	}
+	fn read_staging(memory: &DeviceMemory, size: usize) -> ::Result<Vec<u8>>
+	{
+		let mut m = memory.map_scoped(0 .. size)?;
+		m.invalidate()?;
+		Ok(m.slice::<u8>(0, size).to_vec())
+	}
+}
+
+/// Extent of the mip level `level` of `image`
+fn mip_extent(image: &Image, level: u32) -> VkExtent3D
+{
+	let &::Extent3D(w, h, d) = image.size();
+	let f = |v: u32| ::std::cmp::max(v.checked_shr(level).unwrap_or(0), 1);
+	VkExtent3D { width: f(w), height: f(h), depth: f(d) }
+}
+/// Format and texel size of an aspect of `format`, as laid out in a buffer by the copy commands
+fn aspect_format(format: VkFormat, aspect: VkImageAspectFlags) -> (VkFormat, usize)
+{
+	assert!(aspect.is_power_of_two(), "exactly one aspect must be read at once");
+	let format = match (format, aspect)
+	{
+		(VK_FORMAT_D16_UNORM_S8_UINT, VK_IMAGE_ASPECT_DEPTH_BIT) => VK_FORMAT_D16_UNORM,
+		(VK_FORMAT_D24_UNORM_S8_UINT, VK_IMAGE_ASPECT_DEPTH_BIT) => VK_FORMAT_X8_D24_UNORM_PACK32,
+		(VK_FORMAT_D32_SFLOAT_S8_UINT, VK_IMAGE_ASPECT_DEPTH_BIT) => VK_FORMAT_D32_SFLOAT,
+		(VK_FORMAT_D16_UNORM_S8_UINT, VK_IMAGE_ASPECT_STENCIL_BIT) | (VK_FORMAT_D24_UNORM_S8_UINT, VK_IMAGE_ASPECT_STENCIL_BIT)
+			| (VK_FORMAT_D32_SFLOAT_S8_UINT, VK_IMAGE_ASPECT_STENCIL_BIT) => VK_FORMAT_S8_UINT,
+		(f, _) => f
+	};
+	let bits = format.bit_width();
+	assert!(bits != 0, "texels of the format {} cannot be read back", format);
+	(format, bits / 8)
+}
+
+#[cfg(all(test, feature = "Implements", feature = "CustomResolver"))]
+mod tests
+{
+	use super::*;
+	use {ImageDesc, ImageUsage, VkHandle, Extent2D, Extent3D};
+	use fakedrv::{default_test_device, assert_no_misuse};
+
+	/// Index of the host-visible, host-coherent memory type of the fake driver
+	const HOST_COHERENT_TYPE: u32 = 1;
+	const COLOR: VkImageSubresourceLayers = VkImageSubresourceLayers { aspectMask: VK_IMAGE_ASPECT_COLOR_BIT, mipLevel: 0, baseArrayLayer: 0, layerCount: 1 };
+
+	/// A transfer source buffer holding `data` in a host-coherent memory
+	fn source_buffer(device: &Device, data: &[u8]) -> (Buffer, DeviceMemory)
+	{
+		let buffer = BufferDesc::new(data.len(), BufferUsage::TRANSFER_SRC).create(device).unwrap();
+		let memory = DeviceMemory::allocate(device, buffer.requirements().size as _, HOST_COHERENT_TYPE).unwrap();
+		buffer.bind(&memory, 0).unwrap();
+		memory.map_scoped(0 .. data.len()).unwrap().write_slice(0, data);
+		(buffer, memory)
+	}
+	fn staging_of(readback: &Readback) -> Option<(VkBuffer, VkDeviceSize)>
+	{
+		readback.staging.lock().unwrap().as_ref().map(|&(ref b, _, size)| (b.native_ptr(), size))
+	}
+
+	#[test]
+	fn buffer_download()
+	{
+		let (driver, instance, device) = default_test_device();
+		let readback = Readback::new(&device, device.queue(0, 0), 0).unwrap();
+		let bytes: Vec<u8> = (0 .. 64).collect();
+		let (src, _memory) = source_buffer(&device, &bytes);
+
+		assert_eq!(readback.buffer(&src, 8 .. 24).unwrap(), &bytes[8 .. 24]);
+		let first = staging_of(&readback).unwrap();
+		assert_eq!(first.1, 16);
+		// a smaller download reuses the staging buffer
+		assert_eq!(readback.buffer(&src, 0 .. 4).unwrap(), &bytes[.. 4]);
+		assert_eq!(staging_of(&readback), Some(first));
+		// a larger one replaces it
+		assert_eq!(readback.buffer(&src, 0 .. 64).unwrap(), bytes);
+		assert_eq!(staging_of(&readback).map(|s| s.1), Some(64));
+		assert!(readback.buffer(&src, 4 .. 4).unwrap().is_empty());
+		assert_no_misuse(&driver, &instance);
+	}
+
+	#[test]
+	fn image_download()
+	{
+		let (driver, instance, device) = default_test_device();
+		let readback = Readback::new(&device, device.queue(0, 0), 0).unwrap();
+		let image = ImageDesc::new(&Extent2D(3, 2), VK_FORMAT_R8G8B8A8_UNORM, ImageUsage::TRANSFER_SRC.transfer_dest(), ImageLayout::Undefined)
+			.array_layers(2).mip_levels(2).create(&device).unwrap();
+		let memory = DeviceMemory::allocate(&device, image.requirements().size as _, 0).unwrap();
+		image.bind(&memory, 0).unwrap();
+		// layer 0 and 1 of the mip level 0, then the mip level 1 of the layer 1
+		let texels: Vec<u8> = (0 .. 3 * 2 * 4 * 2 + 4).map(|x| x as u8).collect();
+		let (src, _src_memory) = source_buffer(&device, &texels);
+		readback.execute(|rec|
+		{
+			let range = VkImageSubresourceRange { aspectMask: VK_IMAGE_ASPECT_COLOR_BIT, baseMipLevel: 0, levelCount: 2, baseArrayLayer: 0, layerCount: 2 };
+			let barrier = ImageMemoryBarrier::new_raw(&image, &range, ImageLayout::Undefined, ImageLayout::TransferDestOpt);
+			rec.pipeline_barrier(PipelineStageFlags::TOP_OF_PIPE, PipelineStageFlags::TRANSFER, false, &[], &[], &[barrier]);
+			let copy = |offset: VkDeviceSize, sub: VkImageSubresourceLayers, extent: VkExtent3D| VkBufferImageCopy
+			{
+				bufferOffset: offset, bufferRowLength: 0, bufferImageHeight: 0, imageSubresource: sub,
+				imageOffset: VkOffset3D { x: 0, y: 0, z: 0 }, imageExtent: extent
+			};
+			rec.copy_buffer_to_image(&src, &image, ImageLayout::TransferDestOpt, &[
+				copy(0, VkImageSubresourceLayers { layerCount: 2, .. COLOR }, VkExtent3D { width: 3, height: 2, depth: 1 }),
+				copy(48, VkImageSubresourceLayers { mipLevel: 1, baseArrayLayer: 1, .. COLOR }, VkExtent3D { width: 1, height: 1, depth: 1 })
+			]);
+		}).unwrap();
+
+		let whole = readback.image(&image, ImageLayout::TransferDestOpt, &VkImageSubresourceLayers { layerCount: 2, .. COLOR }).unwrap();
+		assert_eq!((whole.format, whole.extent.clone(), whole.layers, whole.texel_size),
+			(VK_FORMAT_R8G8B8A8_UNORM, VkExtent3D { width: 3, height: 2, depth: 1 }, 2, 4));
+		assert_eq!(whole.data, &texels[.. 48]);
+		assert_eq!(whole.texel_at(2, 1, 0, 1), &[44, 45, 46, 47]);
+		let mip = readback.image(&image, ImageLayout::TransferSrcOpt, &VkImageSubresourceLayers { mipLevel: 1, baseArrayLayer: 1, .. COLOR }).unwrap();
+		assert_eq!((mip.extent.clone(), mip.data), (VkExtent3D { width: 1, height: 1, depth: 1 }, texels[48 ..].to_vec()));
+		assert_no_misuse(&driver, &instance);
+	}
+	#[test]
+	#[should_panic(expected = "in the Undefined layout cannot be downloaded")]
+	fn undefined_layout_download()
+	{
+		let (_driver, _instance, device) = default_test_device();
+		let readback = Readback::new(&device, device.queue(0, 0), 0).unwrap();
+		let image = ImageDesc::new(&Extent2D(3, 2), VK_FORMAT_R8G8B8A8_UNORM, ImageUsage::TRANSFER_SRC, ImageLayout::Undefined)
+			.create(&device).unwrap();
+		let memory = DeviceMemory::allocate(&device, image.requirements().size as _, 0).unwrap();
+		image.bind(&memory, 0).unwrap();
+		let _ = readback.image(&image, ImageLayout::Undefined, &COLOR);
+	}
+
+	#[test]
+	fn linear_padded_rows()
+	{
+		let (driver, instance, device) = default_test_device();
+		let image = ImageDesc::new(&Extent3D(3, 2, 2), VK_FORMAT_R8G8B8A8_UNORM, ImageUsage::TRANSFER_SRC, ImageLayout::Preinitialized)
+			.use_linear_tiling().create(&device).unwrap();
+		// bound past the head of the memory, to check that the offset is honored too
+		let offset = 512;
+		let memory = DeviceMemory::allocate(&device, offset + image.requirements().size as usize, HOST_COHERENT_TYPE).unwrap();
+		image.bind(&memory, offset).unwrap();
+		let layout = image.image_subresource_layout(AspectMask::COLOR, 0, 0);
+		assert!(layout.rowPitch > 3 * 4, "the fake driver pads the rows of linear images");
+		assert_eq!(layout.depthPitch, layout.rowPitch * 2);
+		let expected: Vec<u8> = (0 .. 3 * 2 * 2 * 4).map(|x| x as u8).collect();
+		{
+			let mut m = memory.map_scoped(offset .. offset + image.requirements().size as usize).unwrap();
+			for (n, row) in expected.chunks(3 * 4).enumerate()
+			{
+				let (z, y) = (n as VkDeviceSize / 2, n as VkDeviceSize % 2);
+				m.write_slice((layout.offset + z * layout.depthPitch + y * layout.rowPitch) as usize, row);
+			}
+		}
+
+		let data = ImageData::read_linear(&image, &memory, offset, &COLOR).unwrap();
+		assert_eq!(data.extent, VkExtent3D { width: 3, height: 2, depth: 2 });
+		assert_eq!(data.data, expected);
+		assert_eq!(data.texel_at(0, 1, 1, 0), &[36, 37, 38, 39]);
+		assert_no_misuse(&driver, &instance);
+	}
+}
@@ -115,18 +115,34 @@
 //!   - テクスチャが`VK_MEMORY_PROPERTY_LAZILY_ALLOCATED_BIT`が指定された[`DeviceMemory`]にバインドされることを想定している
 //!   - パス間の中間バッファなどで、一時的に確保される必要があるバッファに指定するとメモリ使用量が少なくて済むかもしれない？
 //! 
+//! ## メモリのマッピング
+//! 
+//! [`DeviceMemory::map_scoped`](struct.DeviceMemory.html#method.map_scoped)で得た[`MemoryMapping`]はdropでアンマップされる。
+//! `HOST_COHERENT`でないメモリタイプの場合は、書き込んだ範囲が`nonCoherentAtomSize`に丸められてdrop時にフラッシュされる
+//! 
+//! ```rust,ignore
+//! let mut m = memory.map_scoped(0 .. 256)?;
+//! m.write_slice(0, &[0.0f32, 1.0, 2.0, 3.0]);
+//! m.flush()?;      // 明示的にフラッシュしてもよい
+//! m.invalidate()?; // デバイスからの書き込みを読む前に
+//! let v: [f32; 4] = m.read(0);
+//! ```
+//! 
 
 use vk::*;
-use std::rc::Rc as RefCounter;
-use std::ops::{Deref, BitOr, BitOrAssign};
-use {VkHandle, DeviceChild, Device};
+use std::ops::{Deref, BitOr, BitOrAssign, RangeBounds, Bound};
+use std::mem::size_of;
+use std::marker::PhantomData;
+use {VkHandle, DeviceChild, Device, RefCounter};
+use extsync::ExternalSync;
 #[cfg(feature = "Implements")] use VkResultHandler;
-#[cfg(feature = "Implements")] use std::ptr::{null, null_mut};
+#[cfg(feature = "Implements")] use std::ptr::null_mut;
 #[cfg(feature = "Implements")] use std::mem::MaybeUninit;
-#[cfg(feature = "Implements")] use ::vkresolve::{Resolver, ResolverInterface};
 use std::borrow::Borrow;
+use std::sync::atomic::{AtomicBool, Ordering};
 
-struct DeviceMemoryCell(VkDeviceMemory, ::Device);
+/// Handle, Parent, Lock for mapping, Size, Memory type index, Whether host mapped
+struct DeviceMemoryCell(VkDeviceMemory, ::Device, ExternalSync, VkDeviceSize, u32, AtomicBool);
 struct BufferCell(VkBuffer, ::Device);
 #[cfg(feature = "VK_KHR_swapchain")]
 pub enum ImageCell
@@ -148,11 +164,24 @@ struct ImageViewCell(VkImageView, Image);
 /// Opaque handle to a image view object
 #[derive(Clone)]
 pub struct ImageView(RefCounter<ImageViewCell>);
+ThreadSafe!(Send + Sync: DeviceMemoryCell, BufferView, ImageViewCell);
+// vkDestroyBuffer runs on the last drop. vkBindBufferMemory also needs external synchronization, but is valid only once per buffer
+ThreadSafe!(Send + Sync: BufferCell);
+// same as BufferCell for the images of a device. The ones owned by a swapchain are never destroyed or bound through this
+ThreadSafe!(Send + Sync: ImageCell);
 
 impl Deref for BufferView { type Target = Buffer; fn deref(&self) -> &Buffer { &self.1 } }
 impl Deref for ImageView { type Target = Image; fn deref(&self) -> &Image { &self.0 .1 } }
 
-#[cfg(feature = "Implements")] DeviceChildCommonDrop! { for DeviceMemoryCell[free_memory], BufferCell[destroy_buffer] }
+#[cfg(feature = "Implements")] DeviceChildCommonDrop! { for BufferCell[destroy_buffer] }
+#[cfg(feature = "Implements")] impl Drop for DeviceMemoryCell
+{
+	fn drop(&mut self)
+	{
+		unsafe { self.1.dispatch().free_memory(self.1.native_ptr(), self.0, self.1.allocation_callbacks()) };
+		if let Some(t) = self.1.memory_tracker() { t.freed(self.4, self.3); }
+	}
+}
 #[cfg(feature = "Implements")] impl Drop for ImageCell
 {
 	fn drop(&mut self)
@@ -160,23 +189,32 @@ impl Deref for ImageView { type Target = Image; fn deref(&self) -> &Image { &sel
 		#[cfg(feature = "VK_KHR_swapchain")]
 		match *self
 		{
-			ImageCell::DeviceChild { obj, ref dev, .. } => unsafe { Resolver::get().destroy_image(dev.native_ptr(), obj, null()); },
+			ImageCell::DeviceChild { obj, ref dev, .. } => unsafe { dev.dispatch().destroy_image(dev.native_ptr(), obj, dev.allocation_callbacks()); },
 			_ => (/* No destroying performed */)
 		}
 		#[cfg(not(feature = "VK_KHR_swapchain"))]
-		unsafe { Resolver::get().destroy_image(self.dev.native_ptr(), self.obj, null()); }
+		unsafe { self.dev.dispatch().destroy_image(self.dev.native_ptr(), self.obj, self.dev.allocation_callbacks()); }
 	}
 }
 #[cfg(feature = "Implements")]
-impl Drop for BufferView { fn drop(&mut self) { unsafe { Resolver::get().destroy_buffer_view(self.device().native_ptr(), self.native_ptr(), null()) }; } }
+impl Drop for BufferView { fn drop(&mut self) { unsafe { self.device().dispatch().destroy_buffer_view(self.device().native_ptr(), self.native_ptr(), self.device().allocation_callbacks()) }; } }
 #[cfg(feature = "Implements")]
-impl Drop for ImageViewCell { fn drop(&mut self) { unsafe { Resolver::get().destroy_image_view(self.1.device().native_ptr(), self.0, null()) }; } }
+impl Drop for ImageViewCell { fn drop(&mut self) { unsafe { self.1.device().dispatch().destroy_image_view(self.1.device().native_ptr(), self.0, self.1.device().allocation_callbacks()) }; } }
 
 impl VkHandle for DeviceMemory { type Handle = VkDeviceMemory; fn native_ptr(&self) -> VkDeviceMemory { self.0 .0 } }
 impl VkHandle for Buffer { type Handle = VkBuffer; fn native_ptr(&self) -> VkBuffer { self.0 .0 } }
 impl VkHandle for BufferView { type Handle = VkBufferView; fn native_ptr(&self) -> VkBufferView { self.0 } }
 impl VkHandle for ImageView  { type Handle = VkImageView;  fn native_ptr(&self) -> VkImageView  { self.0 .0 } }
 impl DeviceChild for DeviceMemory { fn device(&self) -> &::Device { &self.0 .1 } }
+impl DeviceMemory
+{
+	/// Size of the memory object in bytes
+	pub fn size(&self) -> VkDeviceSize { self.0 .3 }
+	/// Index of the memory type the memory object was allocated from
+	pub fn type_index(&self) -> u32 { self.0 .4 }
+	/// Whether the memory object is currently mapped into application address space
+	pub fn is_mapped(&self) -> bool { self.0 .5.load(Ordering::Acquire) }
+}
 impl DeviceChild for Buffer { fn device(&self) -> &::Device { &self.0 .1 } }
 impl DeviceChild for BufferView { fn device(&self) -> &::Device { self.deref().device() } }
 impl DeviceChild for ImageView  { fn device(&self) -> &::Device { self.deref().device() } }
@@ -222,10 +260,36 @@ impl DeviceMemory
 	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
 	/// * `VK_ERROR_TOO_MANY_OBJECTS`
 	pub fn allocate(device: &::Device, size: usize, type_index: u32) -> ::Result<Self>
+	{
+		Self::allocate_with(device, &VkMemoryAllocateInfo { allocationSize: size as _, memoryTypeIndex: type_index, .. Default::default() })
+	}
+	/// [Vulkan 1.1/VK_KHR_dedicated_allocation] Allocate GPU memory only for `resource`.
+	/// The size is taken from the requirements of `resource`, and the memory must be bound to it at offset 0
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_TOO_MANY_OBJECTS`
+	pub fn allocate_dedicated<R: MemoryBound + ?Sized>(device: &::Device, resource: &R, type_index: u32) -> ::Result<Self>
+	{
+		let dedicated = resource.dedicated_allocate_info();
+		let info = VkMemoryAllocateInfo
+		{
+			allocationSize: resource.requirements().size, memoryTypeIndex: type_index,
+			pNext: if device.supports_dedicated_allocation() { &dedicated as *const _ as _ } else { ::std::ptr::null() },
+			.. Default::default()
+		};
+		Self::allocate_with(device, &info)
+	}
+
+	fn allocate_with(device: &::Device, info: &VkMemoryAllocateInfo) -> ::Result<Self>
 	{
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().allocate_memory(device.native_ptr(), &VkMemoryAllocateInfo { allocationSize: size as _, memoryTypeIndex: type_index, .. Default::default() },
-			::std::ptr::null(), &mut h) }.into_result().map(|_| DeviceMemory(RefCounter::new(DeviceMemoryCell(h, device.clone()))))
+		unsafe { device.dispatch().allocate_memory(device.native_ptr(), info, device.allocation_callbacks(), &mut h) }
+			.into_result_in("vkAllocateMemory", "VkDevice")?;
+		if let Some(t) = device.memory_tracker() { t.allocated(info.memoryTypeIndex, info.allocationSize); }
+		Ok(DeviceMemory(RefCounter::new(DeviceMemoryCell(h, device.clone(), ExternalSync::default(), info.allocationSize, info.memoryTypeIndex, AtomicBool::new(false)))))
 	}
 }
 
@@ -327,7 +391,12 @@ impl BitOrAssign for BufferUsage
 	Both = (VK_BUFFER_CREATE_SPARSE_BINDING_BIT | VK_BUFFER_CREATE_SPARSE_RESIDENCY_BIT | VK_BUFFER_CREATE_SPARSE_ALIASED_BIT) as _
 }
 /// Builder structure specifying the parameters of a newly created buffer object
-pub struct BufferDesc { cinfo: VkBufferCreateInfo }
+pub struct BufferDesc
+{
+	cinfo: VkBufferCreateInfo,
+	#[cfg_attr(not(all(feature = "Implements", feature = "VK_EXT_debug_marker")), allow(dead_code))]
+	name: Option<String>
+}
 impl BufferDesc
 {
 	pub fn new(byte_size: usize, usage: BufferUsage) -> Self
@@ -337,7 +406,7 @@ impl BufferDesc
 			cinfo: VkBufferCreateInfo
 			{
 				size: byte_size as _, usage: usage.0, .. Default::default()
-			}
+			}, name: None
 		}
 	}
 	/// A list of queue families that will access this buffer
@@ -353,6 +422,9 @@ impl BufferDesc
 	{
 		self.cinfo.flags = opt as _; self
 	}
+	/// A debug name given to the buffer on creation.
+	/// Ignored unless `VK_EXT_debug_marker` is enabled on the device
+	pub fn name(&mut self, name: &str) -> &mut Self { self.name = Some(name.to_owned()); self }
 	/// [feature = "Implements"] Create a new buffer object
 	/// # Failure
 	/// On failure, this command returns
@@ -360,11 +432,96 @@ impl BufferDesc
 	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
 	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
 	#[cfg(feature = "Implements")]
-	pub fn create(&self, device: &::Device) -> ::Result<Buffer>
+	pub fn create(&self, device: &::Device) -> ::Result<Buffer> { self.create_extended(device, ::Chain::new()) }
+	/// [feature = "Implements"] Create a new buffer object with extension structures(e.g. `VkExternalMemoryBufferCreateInfo`)
+	/// # Failure
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	#[cfg(feature = "Implements")]
+	pub fn create_extended(&self, device: &::Device, chain: ::Chain<VkBufferCreateInfo>) -> ::Result<Buffer>
 	{
+		let cinfo = VkBufferCreateInfo { pNext: chain.head(), .. self.cinfo.clone() };
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_buffer(device.native_ptr(), &self.cinfo, ::std::ptr::null(), &mut h) }
-			.into_result().map(|_| Buffer(RefCounter::new(BufferCell(h, device.clone()))))
+		let b = unsafe { device.dispatch().create_buffer(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h) }
+			.into_result_in("vkCreateBuffer", "VkDevice").map(|_| Buffer(RefCounter::new(BufferCell(h, device.clone()))))?;
+		#[cfg(feature = "VK_EXT_debug_marker")] ::debug::name_created(&b, self.name.as_deref())?;
+		Ok(b)
+	}
+	/// [feature = "Implements"] Create a new buffer object holding elements of `T`.
+	/// The number of the elements is the byte size given to `new` divided by the size of `T`
+	/// # Failure
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// # Panics
+	/// Panics if `T` is zero-sized or the byte size is not a multiple of the size of `T`
+	#[cfg(feature = "Implements")]
+	pub fn create_typed<T>(&self, device: &::Device) -> ::Result<TypedBuffer<T>>
+	{
+		let stride = size_of::<T>() as VkDeviceSize;
+		assert!(stride != 0 && self.cinfo.size % stride == 0, "{} bytes cannot hold elements of {} bytes", self.cinfo.size, stride);
+		self.create(device).map(|b| TypedBuffer { buffer: b, len: (self.cinfo.size / stride) as _, ty: PhantomData })
+	}
+}
+
+/// A `Buffer` holding a number of elements of `T`(created by `BufferDesc::create_typed`)
+pub struct TypedBuffer<T> { buffer: Buffer, len: usize, ty: PhantomData<fn() -> T> }
+/// A range of elements in a `TypedBuffer`
+pub struct BufferSlice<'b, T> { buffer: &'b Buffer, offset: usize, len: usize, ty: PhantomData<fn() -> T> }
+impl<T> Clone for TypedBuffer<T> { fn clone(&self) -> Self { TypedBuffer { buffer: self.buffer.clone(), len: self.len, ty: PhantomData } } }
+impl<'b, T> Clone for BufferSlice<'b, T> { fn clone(&self) -> Self { *self } }
+impl<'b, T> Copy for BufferSlice<'b, T> {}
+impl<T> Deref for TypedBuffer<T> { type Target = Buffer; fn deref(&self) -> &Buffer { &self.buffer } }
+impl<T> TypedBuffer<T>
+{
+	/// Treat `buffer` as an array of `len` elements of `T`
+	/// # Safety
+	/// `buffer` must be created with at least `len * size_of::<T>()` bytes
+	pub unsafe fn from_buffer(buffer: Buffer, len: usize) -> Self { TypedBuffer { buffer, len, ty: PhantomData } }
+	/// The untyped buffer object
+	pub fn buffer(&self) -> &Buffer { &self.buffer }
+	pub fn into_buffer(self) -> Buffer { self.buffer }
+	/// Number of the elements
+	pub fn len(&self) -> usize { self.len }
+	pub fn is_empty(&self) -> bool { self.len == 0 }
+	/// Size of an element in bytes
+	pub fn stride(&self) -> usize { size_of::<T>() }
+	/// Size of the elements in bytes
+	pub fn byte_size(&self) -> usize { self.len * size_of::<T>() }
+	/// All the elements
+	pub fn as_slice(&self) -> BufferSlice<'_, T> { BufferSlice { buffer: &self.buffer, offset: 0, len: self.len, ty: PhantomData } }
+	/// The elements in `range`
+	/// # Panics
+	/// Panics if `range` exceeds the elements
+	pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> BufferSlice<'_, T> { self.as_slice().slice(range) }
+}
+impl<'b, T> BufferSlice<'b, T>
+{
+	/// The buffer containing the elements
+	pub fn buffer(&self) -> &'b Buffer { self.buffer }
+	/// Offset of the first element in bytes
+	pub fn offset(&self) -> usize { self.offset }
+	/// Number of the elements
+	pub fn len(&self) -> usize { self.len }
+	pub fn is_empty(&self) -> bool { self.len == 0 }
+	/// Size of an element in bytes
+	pub fn stride(&self) -> usize { size_of::<T>() }
+	/// Size of the elements in bytes
+	pub fn byte_size(&self) -> usize { self.len * size_of::<T>() }
+	/// Byte range of the elements in the buffer
+	pub fn byte_range(&self) -> ::std::ops::Range<usize> { self.offset .. self.offset + self.byte_size() }
+	/// The elements in `range`, relative to this slice
+	/// # Panics
+	/// Panics if `range` exceeds the elements
+	pub fn slice<R: RangeBounds<usize>>(&self, range: R) -> Self
+	{
+		let start = match range.start_bound() { Bound::Included(&s) => s, Bound::Excluded(&s) => s + 1, Bound::Unbounded => 0 };
+		let end = match range.end_bound() { Bound::Included(&e) => e + 1, Bound::Excluded(&e) => e, Bound::Unbounded => self.len };
+		assert!(start <= end && end <= self.len, "range {}..{} exceeds the {} elements", start, end, self.len);
+		BufferSlice { buffer: self.buffer, offset: self.offset + start * size_of::<T>(), len: end - start, ty: PhantomData }
 	}
 }
 
@@ -447,7 +604,9 @@ impl ImageFlags
 	pub fn cube_compatible(self) -> Self { ImageFlags(self.0 | Self::CUBE_COMPATIBLE.0) }
 }
 /// Builder structure specifying the parameters of a newly created image object
-#[derive(Debug, Clone, PartialEq, Eq)] pub struct ImageDesc(VkImageCreateInfo);
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImageDesc(VkImageCreateInfo,
+	#[cfg_attr(not(all(feature = "Implements", feature = "VK_EXT_debug_marker")), allow(dead_code))] Option<String>);
 impl ImageDesc
 {
 	pub fn new<Size: ImageSize>(size: &Size, format: VkFormat, usage: ImageUsage, initial_layout: ImageLayout) -> Self
@@ -457,7 +616,7 @@ impl ImageDesc
 			imageType: Size::DIMENSION, extent: size.conv(), format, usage: usage.0,
 			mipLevels: 1, arrayLayers:1, samples: 1, initialLayout: initial_layout as _,
 			.. Default::default()
-		})
+		}, None)
 	}
 	/// A list of queue families that will access this image,
 	/// or an empty list if no queue families can access this image simultaneously
@@ -492,6 +651,9 @@ impl ImageDesc
 	/// The number of levels of detail available for minified sampling of the image  
 	/// default: 1
 	pub fn mip_levels(&mut self, levels: u32) -> &mut Self { self.0.mipLevels = levels; self }
+	/// A debug name given to the image on creation.
+	/// Ignored unless `VK_EXT_debug_marker` is enabled on the device
+	pub fn name(&mut self, name: &str) -> &mut Self { self.1 = Some(name.to_owned()); self }
 }
 
 /// Following methods are enabled with [feature = "Implements"]
@@ -499,28 +661,36 @@ impl ImageDesc
 impl ImageDesc
 {
 	/// Create an image
+	pub fn create(&self, device: &::Device) -> ::Result<Image> { self.create_extended(device, ::Chain::new()) }
+	/// Create an image with extension structures(e.g. `VkExternalMemoryImageCreateInfo`)
 	#[cfg(not(feature = "VK_KHR_swapchain"))]
-	pub fn create(&self, device: &::Device) -> ::Result<Image>
+	pub fn create_extended(&self, device: &::Device, chain: ::Chain<VkImageCreateInfo>) -> ::Result<Image>
 	{
+		let cinfo = VkImageCreateInfo { pNext: chain.head(), .. self.0.clone() };
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_image(device.native_ptr(), &self.0, ::std::ptr::null(), &mut h) }
-			.into_result().map(|_| Image(RefCounter::new(ImageCell
+		let img = unsafe { device.dispatch().create_image(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h) }
+			.into_result_in("vkCreateImage", "VkDevice").map(|_| Image(RefCounter::new(ImageCell
 			{
 				obj: h, dev: device.clone(), dim: self.0.imageType, fmt: self.0.format,
 				size: ::Extent3D(self.0.extent.width, self.0.extent.height, self.0.extent.depth)
-			})))
+			})))?;
+		#[cfg(feature = "VK_EXT_debug_marker")] ::debug::name_created(&img, self.1.as_deref())?;
+		Ok(img)
 	}
-	/// Create an image
+	/// Create an image with extension structures(e.g. `VkExternalMemoryImageCreateInfo`)
 	#[cfg(feature = "VK_KHR_swapchain")]
-	pub fn create(&self, device: &::Device) -> ::Result<Image>
+	pub fn create_extended(&self, device: &::Device, chain: ::Chain<VkImageCreateInfo>) -> ::Result<Image>
 	{
+		let cinfo = VkImageCreateInfo { pNext: chain.head(), .. self.0.clone() };
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_image(device.native_ptr(), &self.0, ::std::ptr::null(), &mut h) }
-			.into_result().map(|_| Image(RefCounter::new(ImageCell::DeviceChild
+		let img = unsafe { device.dispatch().create_image(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h) }
+			.into_result_in("vkCreateImage", "VkDevice").map(|_| Image(RefCounter::new(ImageCell::DeviceChild
 			{
 				obj: h, dev: device.clone(), dim: self.0.imageType, fmt: self.0.format,
 				size: ::Extent3D(self.0.extent.width, self.0.extent.height, self.0.extent.depth)
-			})))
+			})))?;
+		#[cfg(feature = "VK_EXT_debug_marker")] ::debug::name_created(&img, self.1.as_deref())?;
+		Ok(img)
 	}
 }
 
@@ -576,8 +746,8 @@ impl Buffer
 			buffer: self.native_ptr(), format, offset: range.start, range: range.end - range.start, .. Default::default()
 		};
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_buffer_view(self.device().native_ptr(), &cinfo, ::std::ptr::null(), &mut h) }
-			.into_result().map(|_| BufferView(h, self.clone()))
+		unsafe { self.device().dispatch().create_buffer_view(self.device().native_ptr(), &cinfo, self.device().allocation_callbacks(), &mut h) }
+			.into_result_in("vkCreateBufferView", "VkDevice").map(|_| BufferView(h, self.clone()))
 	}
 }
 /// Following methods are enabled with [feature = "Implements"]
@@ -595,8 +765,8 @@ impl Image
 			subresourceRange: subresource_range.0.clone(), .. Default::default()
 		};
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_image_view(self.device().native_ptr(), &cinfo, ::std::ptr::null(), &mut h) }
-			.into_result().map(|_| ImageView(RefCounter::new(ImageViewCell(h, self.clone()))))
+		unsafe { self.device().dispatch().create_image_view(self.device().native_ptr(), &cinfo, self.device().allocation_callbacks(), &mut h) }
+			.into_result_in("vkCreateImageView", "VkDevice").map(|_| ImageView(RefCounter::new(ImageViewCell(h, self.clone()))))
 	}
 	/// Retrieve information about an image subresource  
 	/// Subresource: (`aspect`, `mipLevel`, `arrayLayer`)
@@ -606,7 +776,7 @@ impl Image
 		let subres = VkImageSubresource { aspectMask: subres_aspect.0, mipLevel: subres_mip_level, arrayLayer: subres_array_layer };
 		unsafe
 		{
-			Resolver::get()
+			self.device().dispatch()
 				.get_image_subresource_layout(self.device().native_ptr(), self.native_ptr(), &subres, s.as_mut_ptr());
 			
 			s.assume_init()
@@ -625,11 +795,11 @@ impl DeviceMemory
 	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
 	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
 	/// * `VK_ERROR_MEMORY_MAP_FAILED`
+	/// * `Error::AlreadyMapped` if the memory object is already mapped
 	pub fn map(&self, range: ::std::ops::Range<usize>) -> ::Result<MappedMemoryRange>
 	{
-		let mut p = ::std::ptr::null_mut();
-		unsafe { Resolver::get().map_memory(self.device().native_ptr(), self.native_ptr(), range.start as _, (range.end - range.start) as _,
-			0, &mut p) }.into_result().map(|_| MappedMemoryRange(range, p as *mut _, self))
+		let p = self.map_raw(range.start, range.end - range.start)?;
+		Ok(MappedMemoryRange(range, p, self))
 	}
 	/// Unmap a previously mapped memory object
 	/// # Safety
@@ -637,13 +807,51 @@ impl DeviceMemory
 	/// Accessing the mapped memory after this call has undefined behavior
 	pub unsafe fn unmap(&self)
 	{
-		Resolver::get().unmap_memory(self.0 .1.native_ptr(), self.native_ptr());
+		let _g = self.0 .2.lock();
+		self.0 .1.dispatch().unmap_memory(self.0 .1.native_ptr(), self.native_ptr());
+		self.0 .5.store(false, Ordering::Release);
+	}
+	/// Map `range` of a memory object into application address space, until the returned `MemoryMapping` is dropped.
+	/// The mapping is widened to `nonCoherentAtomSize` boundaries if the memory type is not `HOST_COHERENT`
+	/// # Failure
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_MEMORY_MAP_FAILED`
+	/// * `Error::AlreadyMapped` if the memory object is already mapped
+	/// # Panics
+	/// Panics if `range` exceeds the size of the memory object
+	pub fn map_scoped(&self, range: ::std::ops::Range<usize>) -> ::Result<MemoryMapping<'_>>
+	{
+		assert!(range.start <= range.end && range.end as VkDeviceSize <= self.size(),
+			"range {:?} exceeds the memory object({} bytes)", range, self.size());
+		let host = self.device().host_access();
+		let coherent = (host.memory_type_flags[self.type_index() as usize] & VK_MEMORY_PROPERTY_HOST_COHERENT_BIT) != 0;
+		let atom = if coherent { 1 } else { host.non_coherent_atom_size as usize };
+		let mapped = (range.start / atom * atom) .. ::std::cmp::min((range.end + atom - 1) / atom * atom, self.size() as usize);
+		let p = self.map_raw(mapped.start, mapped.end - mapped.start)?;
+		Ok(MemoryMapping
+		{
+			memory: self, ptr: unsafe { p.add(range.start - mapped.start) }, range, mapped, coherent, dirty: None
+		})
+	}
+
+	fn map_raw(&self, offset: usize, size: usize) -> ::Result<*mut u8>
+	{
+		if self.0 .5.compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire).is_err() { return Err(::Error::AlreadyMapped); }
+		let mut p = ::std::ptr::null_mut();
+		let _g = self.0 .2.lock();
+		let r = unsafe { self.device().dispatch().map_memory(self.device().native_ptr(), self.native_ptr(), offset as _, size as _, 0, &mut p) }
+			.into_result_in("vkMapMemory", "VkDevice");
+		if r.is_err() { self.0 .5.store(false, Ordering::Release); }
+		r.map(|_| p as *mut u8)
 	}
 	/// Query the current commitment for a `DeviceMemory`
 	pub fn commitment_bytes(&self) -> VkDeviceSize
 	{
 		let mut b = 0;
-		unsafe { Resolver::get().get_device_memory_commitment(self.device().native_ptr(), self.native_ptr(), &mut b) }; b
+		unsafe { self.device().dispatch().get_device_memory_commitment(self.device().native_ptr(), self.native_ptr(), &mut b) }; b
 	}
 }
 
@@ -659,7 +867,7 @@ impl Device
 		}).collect();
 		unsafe
 		{
-			Resolver::get().bind_buffer_memory2(self.native_ptr(), infos.len() as _, infos.as_ptr()).into_result()
+			self.dispatch().bind_buffer_memory2(self.native_ptr(), infos.len() as _, infos.as_ptr()).into_result_in("vkBindBufferMemory2", "VkDevice")
 		}
 	}
 	/// Multiple Binding for Images
@@ -671,7 +879,45 @@ impl Device
 		}).collect();
 		unsafe
 		{
-			Resolver::get().bind_image_memory2(self.native_ptr(), infos.len() as _, infos.as_ptr()).into_result()
+			self.dispatch().bind_image_memory2(self.native_ptr(), infos.len() as _, infos.as_ptr()).into_result_in("vkBindImageMemory2", "VkDevice")
+		}
+	}
+	/// [Vulkan 1.1] Multiple Binding for Buffers, with the structures chained onto each bind(e.g. `VkBindBufferMemoryDeviceGroupInfo`)
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `Error::MissingEntryPoint` if the device does not provide `vkBindBufferMemory2`
+	pub fn bind_buffers2<'c, Bounds>(&self, bounds: Bounds) -> ::Result<()>
+		where Bounds: IntoIterator<Item = (&'c Buffer, &'c DeviceMemory, VkDeviceSize, ::Chain<'c, VkBindBufferMemoryInfo>)>
+	{
+		let infos: Vec<_> = bounds.into_iter().map(|(b, m, offs, chain)| VkBindBufferMemoryInfo
+		{
+			pNext: chain.head(), buffer: b.native_ptr(), memory: m.native_ptr(), memoryOffset: offs, .. Default::default()
+		}).collect();
+		unsafe
+		{
+			self.dispatch().bind_buffer_memory2(self.native_ptr(), infos.len() as _, infos.as_ptr()).into_result_in("vkBindBufferMemory2", "VkDevice")
+		}
+	}
+	/// [Vulkan 1.1] Multiple Binding for Images, with the structures chained onto each bind(e.g. `VkBindImagePlaneMemoryInfo`)
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `Error::MissingEntryPoint` if the device does not provide `vkBindImageMemory2`
+	pub fn bind_images2<'c, Bounds>(&self, bounds: Bounds) -> ::Result<()>
+		where Bounds: IntoIterator<Item = (&'c Image, &'c DeviceMemory, VkDeviceSize, ::Chain<'c, VkBindImageMemoryInfo>)>
+	{
+		let infos: Vec<_> = bounds.into_iter().map(|(i, m, offs, chain)| VkBindImageMemoryInfo
+		{
+			pNext: chain.head(), image: i.native_ptr(), memory: m.native_ptr(), memoryOffset: offs, .. Default::default()
+		}).collect();
+		unsafe
+		{
+			self.dispatch().bind_image_memory2(self.native_ptr(), infos.len() as _, infos.as_ptr()).into_result_in("vkBindImageMemory2", "VkDevice")
 		}
 	}
 	/// Multiple Binding for both resources
@@ -683,12 +929,80 @@ impl Device
 	}
 }
 
+/// Memory requirements and whether the object wants a `DeviceMemory` of its own(returned by `MemoryBound::dedicated_requirements`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DedicatedMemoryRequirements
+{
+	pub requirements: VkMemoryRequirements,
+	/// The implementation performs better if the object has a dedicated allocation(typically render targets)
+	pub prefers_dedicated: bool,
+	/// The object must be bound to a dedicated allocation(typically imported from an external handle)
+	pub requires_dedicated: bool
+}
+impl DedicatedMemoryRequirements
+{
+	/// Whether the object should be allocated with `DeviceMemory::allocate_dedicated`
+	pub fn should_dedicate(&self) -> bool { self.prefers_dedicated || self.requires_dedicated }
+}
+
+/// Entry points which the `*MemoryRequirements2` queries go through
+#[cfg(feature = "Implements")]
+#[derive(Clone, Copy)]
+enum MemoryRequirements2Route
+{
+	/// Vulkan 1.1 core
+	Core,
+	/// `VK_KHR_get_memory_requirements2`
+	#[cfg(feature = "VK_KHR_get_memory_requirements2")] Khr
+}
+#[cfg(feature = "Implements")]
+impl MemoryRequirements2Route
+{
+	fn of(device: &::Device) -> ::Result<Self>
+	{
+		if device.instance().api_version() >= VK_API_VERSION_1_1 { return Ok(MemoryRequirements2Route::Core); }
+		#[cfg(feature = "VK_KHR_get_memory_requirements2")]
+		{
+			if device.is_extension_enabled("VK_KHR_get_memory_requirements2") { return Ok(MemoryRequirements2Route::Khr); }
+		}
+		Err(::Error::MissingExtension { name: "VK_KHR_get_memory_requirements2".to_owned(), required_by: None })
+	}
+}
+
 /// [feature = "Implements"] Common operations for memory bound objects
 #[cfg(feature = "Implements")]
 pub trait MemoryBound
 {
 	/// Returns the memory requirements for specified Vulkan object
 	fn requirements(&self) -> VkMemoryRequirements;
+	/// [Vulkan 1.1/VK_KHR_get_memory_requirements2] Returns the memory requirements for specified Vulkan object, and fills the structures in `chain`.
+	/// The core entry point is used if the instance is created with `VK_API_VERSION_1_1` or later,
+	/// otherwise the one of `VK_KHR_get_memory_requirements2`(the extension must be enabled on the device)
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `Error::MissingExtension` if neither the API version nor the extension is available
+	/// * `Error::MissingEntryPoint` if the driver does not provide the query
+	fn requirements2(&self, chain: ::Chain<VkMemoryRequirements2>) -> ::Result<VkMemoryRequirements>;
+	/// [Vulkan 1.1/VK_KHR_dedicated_allocation] Returns the memory requirements with the preference for a dedicated allocation.
+	/// Both flags are false on the devices supporting neither
+	/// # Failures
+	/// On failure, this command returns the errors of `requirements2`
+	fn dedicated_requirements(&self) -> ::Result<DedicatedMemoryRequirements> where Self: DeviceChild
+	{
+		if !self.device().supports_dedicated_allocation()
+		{
+			return Ok(DedicatedMemoryRequirements { requirements: self.requirements(), prefers_dedicated: false, requires_dedicated: false });
+		}
+		let mut d = VkMemoryDedicatedRequirements::default();
+		let requirements = self.requirements2(::Chain::new().push(&mut d))?;
+		Ok(DedicatedMemoryRequirements
+		{
+			requirements, prefers_dedicated: d.prefersDedicatedAllocation != 0, requires_dedicated: d.requiresDedicatedAllocaion != 0
+		})
+	}
+	/// The structure to dedicate a memory to this object
+	fn dedicated_allocate_info(&self) -> VkMemoryDedicatedAllocateInfo;
 	/// Bind device memory to the object
 	/// # Failure
 	/// On failure, this command returns
@@ -705,19 +1019,41 @@ impl MemoryBound for Buffer
 		let mut p = MaybeUninit::uninit();
 		unsafe
 		{
-			Resolver::get()
+			self.device().dispatch()
 				.get_buffer_memory_requirements(self.device().native_ptr(), self.native_ptr(), p.as_mut_ptr());
 			
 			p.assume_init()
 		}
 	}
+	fn requirements2(&self, chain: ::Chain<VkMemoryRequirements2>) -> ::Result<VkMemoryRequirements>
+	{
+		let route = MemoryRequirements2Route::of(self.device())?;
+		let info = VkBufferMemoryRequirementsInfo2 { buffer: self.native_ptr(), .. Default::default() };
+		let mut p = VkMemoryRequirements2 { pNext: chain.head(), .. Default::default() };
+		unsafe
+		{
+			match route
+			{
+				MemoryRequirements2Route::Core =>
+					self.device().dispatch().get_buffer_memory_requirements2(self.device().native_ptr(), &info, &mut p)?,
+				#[cfg(feature = "VK_KHR_get_memory_requirements2")]
+				MemoryRequirements2Route::Khr =>
+					self.device().dispatch().get_buffer_memory_requirements2_khr(self.device().native_ptr(), &info, &mut p)?
+			}
+		}
+		Ok(p.memoryRequirements)
+	}
+	fn dedicated_allocate_info(&self) -> VkMemoryDedicatedAllocateInfo
+	{
+		VkMemoryDedicatedAllocateInfo { buffer: self.native_ptr(), .. Default::default() }
+	}
 	fn bind(&self, memory: &DeviceMemory, offset: usize) -> ::Result<()>
 	{
 		unsafe
 		{
-			Resolver::get()
+			self.device().dispatch()
 				.bind_buffer_memory(self.device().native_ptr(), self.native_ptr(), memory.native_ptr(), offset as _)
-				.into_result()
+				.into_result_in("vkBindBufferMemory", "VkDevice")
 		}
 	}
 }
@@ -729,15 +1065,37 @@ impl MemoryBound for Image
 		let mut p = MaybeUninit::uninit();
 		unsafe
 		{
-			Resolver::get()
+			self.device().dispatch()
 				.get_image_memory_requirements(self.device().native_ptr(), self.native_ptr(), p.as_mut_ptr());
 
 			p.assume_init()
 		}
 	}
+	fn requirements2(&self, chain: ::Chain<VkMemoryRequirements2>) -> ::Result<VkMemoryRequirements>
+	{
+		let route = MemoryRequirements2Route::of(self.device())?;
+		let info = VkImageMemoryRequirementsInfo2 { image: self.native_ptr(), .. Default::default() };
+		let mut p = VkMemoryRequirements2 { pNext: chain.head(), .. Default::default() };
+		unsafe
+		{
+			match route
+			{
+				MemoryRequirements2Route::Core =>
+					self.device().dispatch().get_image_memory_requirements2(self.device().native_ptr(), &info, &mut p)?,
+				#[cfg(feature = "VK_KHR_get_memory_requirements2")]
+				MemoryRequirements2Route::Khr =>
+					self.device().dispatch().get_image_memory_requirements2_khr(self.device().native_ptr(), &info, &mut p)?
+			}
+		}
+		Ok(p.memoryRequirements)
+	}
+	fn dedicated_allocate_info(&self) -> VkMemoryDedicatedAllocateInfo
+	{
+		VkMemoryDedicatedAllocateInfo { image: self.native_ptr(), .. Default::default() }
+	}
 	fn bind(&self, memory: &DeviceMemory, offset: usize) -> ::Result<()>
 	{
-		unsafe { Resolver::get().bind_image_memory(self.device().native_ptr(), self.native_ptr(), memory.native_ptr(), offset as _) }.into_result()
+		unsafe { self.device().dispatch().bind_image_memory(self.device().native_ptr(), self.native_ptr(), memory.native_ptr(), offset as _) }.into_result_in("vkBindImageMemory", "VkDevice")
 	}
 }
 /// Following methods are enabled with [feature = "Implements"]
@@ -748,9 +1106,9 @@ impl Image
 	pub fn sparse_requirements(&self) -> Vec<VkSparseImageMemoryRequirements>
 	{
 		let mut n = 0;
-		unsafe { Resolver::get().get_image_sparse_memory_requirements(self.device().native_ptr(), self.native_ptr(), &mut n, null_mut()) };
+		unsafe { self.device().dispatch().get_image_sparse_memory_requirements(self.device().native_ptr(), self.native_ptr(), &mut n, null_mut()) };
 		let mut v = Vec::with_capacity(n as _); unsafe { v.set_len(n as _) };
-		unsafe { Resolver::get().get_image_sparse_memory_requirements(self.device().native_ptr(), self.native_ptr(), &mut n, v.as_mut_ptr()) };
+		unsafe { self.device().dispatch().get_image_sparse_memory_requirements(self.device().native_ptr(), self.native_ptr(), &mut n, v.as_mut_ptr()) };
 		v
 	}
 }
@@ -782,6 +1140,14 @@ pub struct MappedMemoryRange<'m>(std::ops::Range<usize>, *mut u8, &'m DeviceMemo
 #[allow(clippy::mut_from_ref)]
 impl<'m> MappedMemoryRange<'m>
 {
+	/// The byte range of the memory object which is mapped
+	pub fn range(&self) -> ::std::ops::Range<usize> { self.0.clone() }
+	/// The mapped range, to be passed to `Device::flush_mapped_memory_ranges` or `Device::invalidate_memory_range`.
+	/// The range must be aligned to `nonCoherentAtomSize` when the memory is not host coherent
+	pub fn native(&self) -> VkMappedMemoryRange
+	{
+		VkMappedMemoryRange { memory: self.2.native_ptr(), offset: self.0.start as _, size: self.0.len() as _, .. Default::default() }
+	}
 	/// Get a reference in mapped memory with byte offsets
 	/// # Safety
 	/// Caller must guarantee that the pointer and its alignment are valid
@@ -819,6 +1185,137 @@ impl<'m> MappedMemoryRange<'m>
 		*self.get_mut(offset) = src.clone();
 	}
 }
+
+/// Types which can be copied from/to the mapped memory byte by byte: no padding, no pointers, and every bit pattern is valid
+/// # Safety
+/// Implement only for `#[repr(C)]` types consisting of `PlainData` fields without padding
+pub unsafe trait PlainData: Copy + 'static {}
+unsafe impl PlainData for u8 {}
+unsafe impl PlainData for u16 {}
+unsafe impl PlainData for u32 {}
+unsafe impl PlainData for u64 {}
+unsafe impl PlainData for i8 {}
+unsafe impl PlainData for i16 {}
+unsafe impl PlainData for i32 {}
+unsafe impl PlainData for i64 {}
+unsafe impl PlainData for f32 {}
+unsafe impl PlainData for f64 {}
+unsafe impl<T: PlainData, const N: usize> PlainData for [T; N] {}
+unsafe impl PlainData for VkDrawIndirectCommand {}
+unsafe impl PlainData for VkDrawIndexedIndirectCommand {}
+unsafe impl PlainData for VkDispatchIndirectCommand {}
+
+/// A host mapping of a `DeviceMemory`(created by `DeviceMemory::map_scoped`). The memory is unmapped on drop.
+/// The written range is flushed on drop unless the memory type is `HOST_COHERENT`
+#[cfg_attr(not(feature = "Implements"), allow(dead_code))]
+pub struct MemoryMapping<'m>
+{
+	memory: &'m DeviceMemory, ptr: *mut u8,
+	/// Range requested and range actually mapped(rounded to `nonCoherentAtomSize`)
+	range: std::ops::Range<usize>, mapped: std::ops::Range<usize>,
+	coherent: bool,
+	/// Range written since the last flush, relative to `range.start`
+	dirty: Option<std::ops::Range<usize>>
+}
+impl<'m> MemoryMapping<'m>
+{
+	/// The mapped memory object
+	pub fn memory(&self) -> &'m DeviceMemory { self.memory }
+	/// Range of the memory object accessible through this mapping
+	pub fn range(&self) -> std::ops::Range<usize> { self.range.clone() }
+	/// Size of the accessible range in bytes
+	pub fn len(&self) -> usize { self.range.end - self.range.start }
+	pub fn is_empty(&self) -> bool { self.range.start == self.range.end }
+	/// Whether the memory type is `HOST_COHERENT`(flush and invalidate do nothing)
+	pub fn is_coherent(&self) -> bool { self.coherent }
+
+	/// Read a value at the byte offset
+	/// # Panics
+	/// Panics if the value exceeds the mapping or `offset` is not aligned for `T`
+	pub fn read<T: PlainData>(&self, offset: usize) -> T { unsafe { *self.checked::<T>(offset, 1) } }
+	/// A slice of `count` values at the byte offset
+	/// # Panics
+	/// Panics if the values exceed the mapping or `offset` is not aligned for `T`
+	pub fn slice<T: PlainData>(&self, offset: usize, count: usize) -> &[T]
+	{
+		unsafe { ::std::slice::from_raw_parts(self.checked::<T>(offset, count), count) }
+	}
+	/// Write a value at the byte offset
+	/// # Panics
+	/// Panics if the value exceeds the mapping or `offset` is not aligned for `T`
+	pub fn write<T: PlainData>(&mut self, offset: usize, value: &T) { self.slice_mut::<T>(offset, 1)[0] = *value; }
+	/// Write values at the byte offset
+	/// # Panics
+	/// Panics if the values exceed the mapping or `offset` is not aligned for `T`
+	pub fn write_slice<T: PlainData>(&mut self, offset: usize, values: &[T]) { self.slice_mut(offset, values.len()).copy_from_slice(values); }
+	/// A mutable slice of `count` values at the byte offset. The range is flushed as written
+	/// # Panics
+	/// Panics if the values exceed the mapping or `offset` is not aligned for `T`
+	pub fn slice_mut<T: PlainData>(&mut self, offset: usize, count: usize) -> &mut [T]
+	{
+		let p = self.checked::<T>(offset, count);
+		let end = offset + count * ::std::mem::size_of::<T>();
+		self.dirty = Some(match self.dirty.take() { Some(d) => d.start.min(offset) .. d.end.max(end), None => offset .. end });
+		unsafe { ::std::slice::from_raw_parts_mut(p, count) }
+	}
+
+	fn checked<T>(&self, offset: usize, count: usize) -> *mut T
+	{
+		let end = count.checked_mul(::std::mem::size_of::<T>()).and_then(|b| b.checked_add(offset));
+		assert!(end.map_or(false, |e| e <= self.len()), "{} values at offset {} exceed the mapping({} bytes)", count, offset, self.len());
+		let p = unsafe { self.ptr.add(offset) };
+		assert!((p as usize) % ::std::mem::align_of::<T>() == 0, "offset {} is not aligned to {} bytes", offset, ::std::mem::align_of::<T>());
+		p as *mut T
+	}
+}
+/// Following methods are enabled with [feature = "Implements"]
+#[cfg(feature = "Implements")]
+impl<'m> MemoryMapping<'m>
+{
+	/// Make the host writes since the last flush available to the device. Does nothing for `HOST_COHERENT` memory
+	/// # Failure
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn flush(&mut self) -> ::Result<()>
+	{
+		let dirty = match self.dirty.take() { Some(d) => d, None => return Ok(()) };
+		if self.coherent { return Ok(()); }
+		unsafe { self.memory.device().flush_mapped_memory_ranges(&[self.atom_range(dirty)]) }
+	}
+	/// Make the device writes to the whole mapping visible to the host. Does nothing for `HOST_COHERENT` memory
+	/// # Failure
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	pub fn invalidate(&mut self) -> ::Result<()>
+	{
+		if self.coherent { return Ok(()); }
+		let all = self.atom_range(0 .. self.len());
+		unsafe { self.memory.device().invalidate_memory_range(&[all]) }
+	}
+
+	/// `VkMappedMemoryRange` covering `range`(relative to the mapping), rounded to `nonCoherentAtomSize`
+	fn atom_range(&self, range: std::ops::Range<usize>) -> VkMappedMemoryRange
+	{
+		let atom = self.memory.device().host_access().non_coherent_atom_size as usize;
+		let start = (self.range.start + range.start) / atom * atom;
+		let end = ::std::cmp::min((self.range.start + range.end + atom - 1) / atom * atom, self.mapped.end);
+		VkMappedMemoryRange { memory: self.memory.native_ptr(), offset: start as _, size: (end - start) as _, .. Default::default() }
+	}
+}
+#[cfg(feature = "Implements")]
+impl<'m> Drop for MemoryMapping<'m>
+{
+	fn drop(&mut self)
+	{
+		// errors cannot be reported from drop; call `flush` beforehand to handle them
+		let _ = self.flush();
+		unsafe { self.memory.unmap() };
+	}
+}
 #[cfg(feature = "Implements")]
 impl Device {
 	/// Flush `MappedMemoryRange`s
@@ -827,8 +1324,8 @@ impl Device {
 	/// # Safety
 	/// Memory object in `ranges` must be currently host mapped
 	pub unsafe fn flush_mapped_memory_ranges(&self, ranges: &[VkMappedMemoryRange]) -> ::Result<()> {
-		Resolver::get().flush_mapped_memory_ranges(self.native_ptr(), ranges.len() as _, ranges.as_ptr() as *const _)
-			.into_result()
+		self.dispatch().flush_mapped_memory_ranges(self.native_ptr(), ranges.len() as _, ranges.as_ptr() as *const _)
+			.into_result_in("vkFlushMappedMemoryRanges", "VkDevice")
 	}
 }
 
@@ -845,9 +1342,9 @@ impl ::Swapchain
 	pub fn get_images(&self) -> ::Result<Vec<Image>>
 	{
 		let mut n = 0;
-		unsafe { Resolver::get().get_swapchain_images_khr(self.device().native_ptr(), self.native_ptr(), &mut n, ::std::ptr::null_mut()) }.into_result()?;
+		unsafe { self.device().dispatch().get_swapchain_images_khr(self.device().native_ptr(), self.native_ptr(), &mut n, ::std::ptr::null_mut()) }.into_result_in("vkGetSwapchainImagesKHR", "VkDevice")?;
 		let mut v = Vec::with_capacity(n as _); unsafe { v.set_len(n as _) };
-		unsafe { Resolver::get().get_swapchain_images_khr(self.device().native_ptr(), self.native_ptr(), &mut n, v.as_mut_ptr()) }.into_result()
+		unsafe { self.device().dispatch().get_swapchain_images_khr(self.device().native_ptr(), self.native_ptr(), &mut n, v.as_mut_ptr()) }.into_result_in("vkGetSwapchainImagesKHR", "VkDevice")
 			.map(|_| v.into_iter().map(|r| Image(RefCounter::new(ImageCell::SwapchainChild { obj: r, owner: self.clone(), fmt: self.format() }))).collect())
 	}
 }
@@ -1018,6 +1515,7 @@ impl ImageSubresourceRange
 
 /// Opaque handle to a sampler object
 pub struct Sampler(VkSampler, ::Device);
+ThreadSafe!(Send + Sync: Sampler);
 #[cfg(feature = "Implements")] DeviceChildCommonDrop!{ for Sampler[destroy_sampler] }
 
 impl VkHandle for Sampler { type Handle = VkSampler; fn native_ptr(&self) -> VkSampler { self.0 } }
@@ -1036,7 +1534,7 @@ pub enum AddressingMode
     /// The clamp to border wrap mode
     ClampToBorder = VK_SAMPLER_ADDRESS_MODE_CLAMP_TO_BORDER as _,
     /// The mirror clamp to edge wrap mode
-    #[cfg(feature = "VK_KHR_mirror_clamp_to_edge")]
+    #[cfg(feature = "VK_KHR_sampler_mirror_clamp_to_edge")]
     MirrorClampToEdge = VK_SAMPLER_ADDRESS_MODE_MIRROR_CLAMP_TO_EDGE as _
 }
 /// Specify filter used for texture lookups
@@ -1161,7 +1659,248 @@ impl SamplerBuilder
     pub fn create(&self, device: &::Device) -> ::Result<Sampler>
     {
         let mut h = VK_NULL_HANDLE as _;
-        unsafe { Resolver::get().create_sampler(device.native_ptr(), &self.0, ::std::ptr::null(), &mut h) }
-            .into_result().map(|_| Sampler(h, device.clone()))
+        unsafe { device.dispatch().create_sampler(device.native_ptr(), &self.0, device.allocation_callbacks(), &mut h) }
+            .into_result_in("vkCreateSampler", "VkDevice").map(|_| Sampler(h, device.clone()))
     }
 }
+
+#[cfg(all(test, feature = "Implements", feature = "CustomResolver"))]
+mod tests
+{
+	use super::*;
+	use Extent2D;
+	use fakedrv::{test_device, default_test_device, assert_no_misuse};
+
+	/// Memory types of the fake driver, whose `nonCoherentAtomSize` is 64
+	const HOST_COHERENT_TYPE: u32 = 1;
+	const HOST_CACHED_TYPE: u32 = 2;
+
+	fn render_target(device: &Device) -> Image
+	{
+		ImageDesc::new(&Extent2D(4, 4), VK_FORMAT_R8G8B8A8_UNORM, ImageUsage::COLOR_ATTACHMENT, ImageLayout::Undefined).create(device).unwrap()
+	}
+
+	#[test]
+	fn dedicated_allocation()
+	{
+		let (driver, instance, device) = test_device(|b| { b.api_version(VK_API_VERSION_1_1); }, |_| ());
+		// the fake driver prefers dedicated allocations for render targets
+		let (target, other) = (render_target(&device), render_target(&device));
+		let req = target.dedicated_requirements().unwrap();
+		assert!(req.prefers_dedicated && !req.requires_dedicated && req.should_dedicate());
+		assert_eq!(req.requirements, target.requirements());
+		let buffer = BufferDesc::new(256, BufferUsage::VERTEX_BUFFER).create(&device).unwrap();
+		assert!(!buffer.dedicated_requirements().unwrap().should_dedicate());
+
+		let memory = DeviceMemory::allocate_dedicated(&device, &target, 0).unwrap();
+		assert_eq!(memory.size(), req.requirements.size);
+		target.bind(&memory, 0).unwrap();
+		assert_no_misuse(&driver, &instance);
+		// the memory belongs to `target` only
+		assert!(other.bind(&memory, 0).is_err());
+		assert_eq!(driver.misuses_of(instance.native_ptr()).len(), 1);
+	}
+
+	#[test]
+	fn dedicated_allocation_unsupported()
+	{
+		// neither Vulkan 1.1 nor VK_KHR_dedicated_allocation
+		let (driver, instance, device) = default_test_device();
+		let target = render_target(&device);
+		let req = target.dedicated_requirements().unwrap();
+		assert!(!req.should_dedicate());
+		assert_eq!(req.requirements, target.requirements());
+		// allocated without VkMemoryDedicatedAllocateInfo, so that any resource can be bound
+		let memory = DeviceMemory::allocate_dedicated(&device, &target, 0).unwrap();
+		render_target(&device).bind(&memory, 0).unwrap();
+		assert_no_misuse(&driver, &instance);
+	}
+
+	#[test]
+	#[cfg(feature = "VK_KHR_dedicated_allocation")]
+	fn dedicated_allocation_extension()
+	{
+		// a Vulkan 1.0 instance queries the preference through VK_KHR_get_memory_requirements2
+		let (driver, instance, device) = test_device(|_| (),
+			|b| { b.add_extension("VK_KHR_get_memory_requirements2"); b.add_extension("VK_KHR_dedicated_allocation"); });
+		let target = render_target(&device);
+		assert!(target.dedicated_requirements().unwrap().should_dedicate());
+		let memory = DeviceMemory::allocate_dedicated(&device, &target, 0).unwrap();
+		target.bind(&memory, 0).unwrap();
+		assert_no_misuse(&driver, &instance);
+		// the query is not replaced with `requirements` when the driver lacks it
+		driver.withhold("vkGetImageMemoryRequirements2KHR", true);
+		let r = target.dedicated_requirements();
+		driver.withhold("vkGetImageMemoryRequirements2KHR", false);
+		assert!(matches!(r, Err(::Error::MissingEntryPoint("vkGetImageMemoryRequirements2KHR"))), "{:?}", r);
+	}
+
+	#[test]
+	fn requirements2_unavailable()
+	{
+		// neither Vulkan 1.1 nor VK_KHR_get_memory_requirements2
+		let (_driver, _instance, device) = default_test_device();
+		let buffer = BufferDesc::new(256, BufferUsage::VERTEX_BUFFER).create(&device).unwrap();
+		let r = buffer.requirements2(::Chain::new());
+		match r
+		{
+			Err(::Error::MissingExtension { ref name, required_by: None }) => assert_eq!(name, "VK_KHR_get_memory_requirements2"),
+			_ => panic!("{:?}", r)
+		}
+	}
+
+	#[test]
+	fn double_map_refusal()
+	{
+		let (driver, instance, device) = default_test_device();
+		let memory = DeviceMemory::allocate(&device, 256, HOST_COHERENT_TYPE).unwrap();
+		{
+			let mapping = memory.map_scoped(0 .. 16).unwrap();
+			assert!(mapping.is_coherent());
+			assert_eq!(memory.map_scoped(32 .. 64).err(), Some(::Error::AlreadyMapped));
+			assert_eq!(memory.map(0 .. 256).err(), Some(::Error::AlreadyMapped));
+		}
+		// unmapped on drop
+		let range = memory.map(64 .. 128).unwrap();
+		assert_eq!(range.range(), 64 .. 128);
+		let native = range.native();
+		assert_eq!((native.memory, native.offset, native.size), (memory.native_ptr(), 64, 64));
+		assert_eq!(memory.map_scoped(0 .. 16).err(), Some(::Error::AlreadyMapped));
+		unsafe { memory.unmap() };
+		memory.map_scoped(0 .. 16).unwrap();
+		// the refusal is reported before the driver sees the second map
+		assert_no_misuse(&driver, &instance);
+	}
+
+	#[test]
+	fn non_coherent_mapping()
+	{
+		let (driver, instance, device) = default_test_device();
+		let memory = DeviceMemory::allocate(&device, 256, HOST_CACHED_TYPE).unwrap();
+		{
+			let mut mapping = memory.map_scoped(70 .. 100).unwrap();
+			assert!(!mapping.is_coherent());
+			assert_eq!((mapping.range(), mapping.len()), (70 .. 100, 30));
+			// offsets are relative to the requested range, not to the atom boundary
+			mapping.write(2, &0x1234u16);
+			mapping.write_slice(20, &[1u8, 2, 3]);
+			// flushed as 64 .. 128
+			mapping.flush().unwrap();
+			mapping.invalidate().unwrap();
+			assert_eq!(mapping.read::<u16>(2), 0x1234);
+		}
+		{
+			// the end is clamped to the size of the memory
+			let mut mapping = memory.map_scoped(250 .. 256).unwrap();
+			mapping.write(0, &7u8);
+			// flushed on drop
+		}
+		let mapping = memory.map_scoped(0 .. 256).unwrap();
+		assert_eq!(mapping.read::<u16>(72), 0x1234);
+		assert_eq!(mapping.slice::<u8>(90, 3), &[1, 2, 3]);
+		assert_eq!(mapping.read::<u8>(250), 7);
+		// the fake driver reports the ranges not aligned to nonCoherentAtomSize
+		assert_no_misuse(&driver, &instance);
+	}
+
+	#[test]
+	#[should_panic(expected = "is not aligned")]
+	fn misaligned_access()
+	{
+		let (_driver, _instance, device) = default_test_device();
+		let memory = DeviceMemory::allocate(&device, 256, HOST_COHERENT_TYPE).unwrap();
+		memory.map_scoped(0 .. 16).unwrap().read::<u32>(2);
+	}
+
+	#[test]
+	fn typed_slices()
+	{
+		let (_driver, _instance, device) = default_test_device();
+		let buffer = BufferDesc::new(64, BufferUsage::STORAGE_BUFFER).create_typed::<u32>(&device).unwrap();
+		assert_eq!((buffer.len(), buffer.stride(), buffer.byte_size()), (16, 4, 64));
+		let s = buffer.slice(2 .. 5);
+		assert_eq!((s.offset(), s.len(), s.byte_range()), (8, 3, 8 .. 20));
+		// relative to the slice
+		let t = s.slice(1 ..= 1);
+		assert_eq!((t.offset(), t.len(), t.byte_range()), (12, 1, 12 .. 16));
+		assert!(s.slice(3 ..).is_empty());
+		assert_eq!(buffer.slice(..).byte_range(), buffer.as_slice().byte_range());
+		assert_eq!(s.buffer().native_ptr(), buffer.native_ptr());
+	}
+
+	#[test]
+	#[should_panic(expected = "exceeds the 3 elements")]
+	fn slice_out_of_range()
+	{
+		let (_driver, _instance, device) = default_test_device();
+		let buffer = BufferDesc::new(64, BufferUsage::STORAGE_BUFFER).create_typed::<u32>(&device).unwrap();
+		buffer.slice(2 .. 5).slice(1 .. 4);
+	}
+
+	#[test]
+	#[should_panic(expected = "cannot hold elements of 3 bytes")]
+	fn indivisible_typed_buffer()
+	{
+		let (_driver, _instance, device) = default_test_device();
+		let _ = BufferDesc::new(64, BufferUsage::STORAGE_BUFFER).create_typed::<[u8; 3]>(&device);
+	}
+
+	#[test]
+	fn slice_transfers()
+	{
+		use {CommandPool, SubmissionBatch, Fence, Waitable};
+
+		let (driver, instance, device) = default_test_device();
+		let buffer = BufferDesc::new(64, BufferUsage::TRANSFER_SRC.transfer_dest()).create_typed::<u32>(&device).unwrap();
+		let memory = DeviceMemory::allocate(&device, buffer.requirements().size as _, HOST_COHERENT_TYPE).unwrap();
+		buffer.bind(&memory, 0).unwrap();
+		let pool = CommandPool::new(&device, 0, true, false).unwrap();
+		let command = pool.alloc(1, true).unwrap().remove(0);
+		{
+			let mut rec = command.begin_once().unwrap();
+			rec.fill_slice(buffer.as_slice(), 0xffff_ffff).update_slice(buffer.slice(0 .. 4), &[1, 2, 3, 4])
+				.copy_slice(buffer.slice(1 .. 3), buffer.slice(10 .. 12));
+		}
+		let fence = Fence::new(&device, false).unwrap();
+		device.queue(0, 0).submit(&[SubmissionBatch { command_buffers: (&[command][..]).into(), .. Default::default() }], Some(&fence)).unwrap();
+		fence.wait().unwrap();
+
+		let mapping = memory.map_scoped(0 .. 64).unwrap();
+		assert_eq!(mapping.slice::<u32>(0, 16), &[1, 2, 3, 4, !0, !0, !0, !0, !0, !0, 2, 3, !0, !0, !0, !0]);
+		assert_no_misuse(&driver, &instance);
+	}
+
+	#[test]
+	fn empty_slice_transfers()
+	{
+		use {CommandPool, BufferMemoryBarrier};
+
+		let (driver, instance, device) = default_test_device();
+		let buffer = BufferDesc::new(64, BufferUsage::TRANSFER_SRC.transfer_dest()).create_typed::<u32>(&device).unwrap();
+		let pool = CommandPool::new(&device, 0, true, false).unwrap();
+		let command = pool.alloc(1, true).unwrap().remove(0);
+		{
+			// none of them records a command of zero bytes
+			let mut rec = command.begin_once().unwrap();
+			rec.fill_slice(buffer.slice(3 .. 3), 0).update_slice(buffer.slice(5 .. 5), &[])
+				.copy_slice(buffer.slice(1 .. 1), buffer.slice(1 .. 1));
+		}
+		assert!(BufferMemoryBarrier::for_slice(buffer.slice(2 .. 2), VK_ACCESS_TRANSFER_WRITE_BIT, VK_ACCESS_SHADER_READ_BIT).is_none());
+		assert!(BufferMemoryBarrier::for_slice(buffer.slice(2 .. 3), VK_ACCESS_TRANSFER_WRITE_BIT, VK_ACCESS_SHADER_READ_BIT).is_some());
+		assert_no_misuse(&driver, &instance);
+	}
+
+	#[test]
+	#[should_panic(expected = "source and destination slices overlap")]
+	fn overlapping_slice_copy()
+	{
+		use CommandPool;
+
+		let (_driver, _instance, device) = default_test_device();
+		let buffer = BufferDesc::new(64, BufferUsage::TRANSFER_SRC.transfer_dest()).create_typed::<u32>(&device).unwrap();
+		let pool = CommandPool::new(&device, 0, true, false).unwrap();
+		let command = pool.alloc(1, true).unwrap().remove(0);
+		let mut rec = command.begin_once().unwrap();
+		rec.copy_slice(buffer.slice(0 .. 4), buffer.slice(2 .. 6));
+	}
+}
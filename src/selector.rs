@@ -0,0 +1,319 @@
+//! Physical Device Selection
+
+use vk::*;
+use std::fmt;
+use {PhysicalDevice, QueueFlags, Feature, FeatureSet};
+#[cfg(feature = "Implements")] use {Instance, Requirement, ExtensionScope};
+#[cfg(feature = "Implements")] use extensions::{Requests, extension_name};
+
+/// Type of a physical device
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PhysicalDeviceType
+{
+	Other = VK_PHYSICAL_DEVICE_TYPE_OTHER as _,
+	IntegratedGpu = VK_PHYSICAL_DEVICE_TYPE_INTEGRATED_GPU as _,
+	DiscreteGpu = VK_PHYSICAL_DEVICE_TYPE_DISCRETE_GPU as _,
+	VirtualGpu = VK_PHYSICAL_DEVICE_TYPE_VIRTUAL_GPU as _,
+	Cpu = VK_PHYSICAL_DEVICE_TYPE_CPU as _
+}
+impl PhysicalDeviceType
+{
+	pub fn from_raw(t: VkPhysicalDeviceType) -> Self
+	{
+		match t
+		{
+			VK_PHYSICAL_DEVICE_TYPE_INTEGRATED_GPU => PhysicalDeviceType::IntegratedGpu,
+			VK_PHYSICAL_DEVICE_TYPE_DISCRETE_GPU => PhysicalDeviceType::DiscreteGpu,
+			VK_PHYSICAL_DEVICE_TYPE_VIRTUAL_GPU => PhysicalDeviceType::VirtualGpu,
+			VK_PHYSICAL_DEVICE_TYPE_CPU => PhysicalDeviceType::Cpu,
+			_ => PhysicalDeviceType::Other
+		}
+	}
+	/// Score used when no type is preferred: discrete > integrated > virtual > cpu > other
+	#[cfg(feature = "Implements")]
+	fn rank(self) -> u64
+	{
+		match self
+		{
+			PhysicalDeviceType::DiscreteGpu => 1000, PhysicalDeviceType::IntegratedGpu => 500,
+			PhysicalDeviceType::VirtualGpu => 250, PhysicalDeviceType::Cpu => 100, PhysicalDeviceType::Other => 0
+		}
+	}
+}
+
+/// Why a physical device was not selected
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectReason
+{
+	/// No queue family supports all of the flags
+	NoQueueFamily(QueueFlags),
+	/// No queue family can present to the surface
+	NoPresentationSupport,
+	/// The device supports an older version of Vulkan than required
+	ApiVersion { required: u32, supported: u32 },
+	/// A required feature or extension is not supported(`Error::MissingFeature`/`Error::MissingExtension`),
+	/// or a query on the device has failed
+	Unsupported(::Error),
+	/// The limit check with the name has failed
+	Limit(&'static str),
+	/// The largest device-local heap is smaller than required
+	DeviceLocalMemory { required: VkDeviceSize, available: VkDeviceSize }
+}
+impl fmt::Display for RejectReason
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result
+	{
+		match *self
+		{
+			RejectReason::NoQueueFamily(flags) => write!(fmt, "no queue family supports the flags {:#x}", flags.bits()),
+			RejectReason::NoPresentationSupport => fmt.write_str("no queue family can present to the surface"),
+			RejectReason::ApiVersion { required, supported } => write!(fmt, "Vulkan {}.{} is required but the device supports {}.{}",
+				VK_VERSION!(MAJOR required), VK_VERSION!(MINOR required), VK_VERSION!(MAJOR supported), VK_VERSION!(MINOR supported)),
+			RejectReason::Unsupported(ref e) => e.fmt(fmt),
+			RejectReason::Limit(name) => write!(fmt, "limit {} is not satisfied", name),
+			RejectReason::DeviceLocalMemory { required, available } =>
+				write!(fmt, "{} bytes of device-local memory are required but the largest heap has {} bytes", required, available)
+		}
+	}
+}
+/// A physical device rejected by `PhysicalDeviceSelector`, with every reason found
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection { pub device_name: String, pub reasons: Vec<RejectReason> }
+impl fmt::Display for Rejection
+{
+	fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result
+	{
+		write!(fmt, "{}: ", self.device_name)?;
+		for (n, r) in self.reasons.iter().enumerate()
+		{
+			if n > 0 { fmt.write_str(", ")?; }
+			r.fmt(fmt)?;
+		}
+		Ok(())
+	}
+}
+/// The physical device chosen by `PhysicalDeviceSelector`
+pub struct SelectedDevice
+{
+	pub physical_device: PhysicalDevice,
+	/// Queue family index for each `PhysicalDeviceSelector::queue` requirement, in the order of the calls
+	pub queue_families: Vec<u32>,
+	/// Queue family index which can present to the surface given by `PhysicalDeviceSelector::presentation`
+	pub present_family: Option<u32>,
+	pub score: u64
+}
+
+type LimitCheck<'a> = Box<dyn Fn(&VkPhysicalDeviceLimits) -> bool + 'a>;
+type Scorer<'a> = Box<dyn Fn(&PhysicalDevice) -> u64 + 'a>;
+/// Declarative requirements to choose a physical device.
+///
+/// The candidate satisfying all requirements with the highest score is selected.
+/// The score is the rank of the device type(10000 more if it is the preferred one)
+/// plus the size of the largest device-local heap in GiB, and anything added by `score_with`
+pub struct PhysicalDeviceSelector<'a>
+{
+	queues: Vec<QueueFlags>,
+	#[cfg(feature = "Presentation")] surface: Option<&'a ::Surface>,
+	api_version: u32,
+	features: FeatureSet,
+	extensions: Vec<String>,
+	limits: Vec<(&'static str, LimitCheck<'a>)>,
+	device_local_memory: VkDeviceSize,
+	preferred_type: Option<PhysicalDeviceType>,
+	scorer: Option<Scorer<'a>>
+}
+impl<'a> Default for PhysicalDeviceSelector<'a> { fn default() -> Self { Self::new() } }
+impl<'a> PhysicalDeviceSelector<'a>
+{
+	pub fn new() -> Self
+	{
+		PhysicalDeviceSelector
+		{
+			queues: Vec::new(), #[cfg(feature = "Presentation")] surface: None, api_version: 0,
+			features: FeatureSet::new(), extensions: Vec::new(), limits: Vec::new(), device_local_memory: 0,
+			preferred_type: None, scorer: None
+		}
+	}
+	/// Require a queue family which supports all of `flags`
+	pub fn queue(&mut self, flags: QueueFlags) -> &mut Self { self.queues.push(flags); self }
+	/// Require a queue family which can present to `surface`
+	#[cfg(feature = "Presentation")]
+	pub fn presentation(&mut self, surface: &'a ::Surface) -> &mut Self { self.surface = Some(surface); self }
+	/// Require the version of Vulkan(`VK_MAKE_VERSION!`) supported by the device
+	pub fn api_version(&mut self, version: u32) -> &mut Self { self.api_version = version; self }
+	pub fn feature(&mut self, feature: Feature) -> &mut Self { self.features.insert(feature); self }
+	pub fn features<Fs: IntoIterator<Item = Feature>>(&mut self, features: Fs) -> &mut Self
+	{
+		for f in features { self.features.insert(f); } self
+	}
+	/// Require a device extension with its dependencies
+	pub fn extension(&mut self, name: &str) -> &mut Self { self.extensions.push(name.to_owned()); self }
+	/// Require `check` to hold for the limits of the device. `name` is reported on rejection
+	pub fn limit<F: Fn(&VkPhysicalDeviceLimits) -> bool + 'a>(&mut self, name: &'static str, check: F) -> &mut Self
+	{
+		self.limits.push((name, Box::new(check))); self
+	}
+	/// Require a device-local heap of at least `bytes`
+	pub fn device_local_memory(&mut self, bytes: VkDeviceSize) -> &mut Self { self.device_local_memory = bytes; self }
+	/// Prefer devices of the type over the others
+	pub fn prefer_type(&mut self, t: PhysicalDeviceType) -> &mut Self { self.preferred_type = Some(t); self }
+	/// Add the result of `scorer` to the score of each candidate
+	pub fn score_with<F: Fn(&PhysicalDevice) -> u64 + 'a>(&mut self, scorer: F) -> &mut Self
+	{
+		self.scorer = Some(Box::new(scorer)); self
+	}
+}
+#[cfg(feature = "Implements")]
+impl<'a> PhysicalDeviceSelector<'a>
+{
+	/// Choose the best physical device of `instance`
+	/// # Failures
+	/// Returns `Error::NoSuitableDevice` with the reasons for each device if no device satisfies the requirements,
+	/// or the error from `vkEnumeratePhysicalDevices`
+	pub fn select(&self, instance: &Instance) -> ::Result<SelectedDevice>
+	{
+		let mut best: Option<SelectedDevice> = None;
+		let mut rejections = Vec::new();
+		for pd in instance.iter_physical_devices()?
+		{
+			match self.evaluate(pd)
+			{
+				Ok(s) => if best.as_ref().map_or(true, |b| b.score < s.score) { best = Some(s); },
+				Err(r) => rejections.push(r)
+			}
+		}
+		best.ok_or(::Error::NoSuitableDevice(rejections))
+	}
+	/// Check the requirements against `pd`, collecting every reason of rejection
+	pub fn evaluate(&self, pd: PhysicalDevice) -> ::std::result::Result<SelectedDevice, Rejection>
+	{
+		let props = pd.properties();
+		let mut reasons = Vec::new();
+
+		if props.apiVersion < self.api_version
+		{
+			reasons.push(RejectReason::ApiVersion { required: self.api_version, supported: props.apiVersion });
+		}
+		let qf = pd.queue_family_properties();
+		let mut queue_families = Vec::with_capacity(self.queues.len());
+		for &flags in &self.queues
+		{
+			// the family with the fewest extra capabilities is the most dedicated one
+			let found = qf.0.iter().enumerate().filter(|&(_, q)| q.queueCount > 0 && (q.queueFlags & flags.bits()) == flags.bits())
+				.min_by_key(|&(_, q)| (q.queueFlags & !flags.bits()).count_ones()).map(|(n, _)| n as u32);
+			match found
+			{
+				Some(n) => queue_families.push(n),
+				None => reasons.push(RejectReason::NoQueueFamily(flags))
+			}
+		}
+		let present_family = self.present_family(&pd, qf.count(), &queue_families, &mut reasons);
+		let supported = FeatureSet::from(pd.features());
+		reasons.extend(self.features.iter().filter(|&f| !supported.contains(f)).map(|f| RejectReason::Unsupported(::Error::MissingFeature(f))));
+		if !self.extensions.is_empty()
+		{
+			let mut requests = Requests::default();
+			for e in &self.extensions { requests.extension(e, Requirement::Required); }
+			let resolved = pd.enumerate_extension_properties(None).and_then(|available|
+			{
+				let api_version = ::std::cmp::min(props.apiVersion, pd.parent().api_version());
+				requests.resolve_extensions(ExtensionScope::Device, api_version,
+					&available.iter().map(extension_name).collect::<Vec<_>>(), pd.parent().enabled_extensions())
+			});
+			if let Err(e) = resolved { reasons.push(RejectReason::Unsupported(e)); }
+		}
+		reasons.extend(self.limits.iter().filter(|(_, check)| !check(&props.limits)).map(|&(name, _)| RejectReason::Limit(name)));
+		let largest_local_heap = pd.memory_properties().heaps().filter(|h| (h.flags & VK_MEMORY_HEAP_DEVICE_LOCAL_BIT) != 0)
+			.map(|h| h.size).max().unwrap_or(0);
+		if largest_local_heap < self.device_local_memory
+		{
+			reasons.push(RejectReason::DeviceLocalMemory { required: self.device_local_memory, available: largest_local_heap });
+		}
+
+		if !reasons.is_empty()
+		{
+			let device_name = unsafe { ::std::ffi::CStr::from_ptr(props.deviceName.as_ptr()) }.to_string_lossy().into_owned();
+			return Err(Rejection { device_name, reasons });
+		}
+		let dtype = PhysicalDeviceType::from_raw(props.deviceType);
+		let score = if self.preferred_type == Some(dtype) { 10000 } else { 0 } + dtype.rank() + (largest_local_heap >> 30)
+			+ self.scorer.as_ref().map_or(0, |f| f(&pd));
+		Ok(SelectedDevice { physical_device: pd, queue_families, present_family, score })
+	}
+	/// A family which can present to the surface, preferring the ones already chosen for the queues
+	#[cfg(feature = "Presentation")]
+	fn present_family(&self, pd: &PhysicalDevice, family_count: u32, chosen: &[u32], reasons: &mut Vec<RejectReason>) -> Option<u32>
+	{
+		let surface = self.surface?;
+		for n in chosen.iter().cloned().chain(0 .. family_count)
+		{
+			match pd.surface_support(n, surface)
+			{
+				Ok(true) => return Some(n),
+				Ok(false) => (),
+				Err(e) => { reasons.push(RejectReason::Unsupported(e)); return None; }
+			}
+		}
+		reasons.push(RejectReason::NoPresentationSupport); None
+	}
+	#[cfg(not(feature = "Presentation"))]
+	fn present_family(&self, _: &PhysicalDevice, _: u32, _: &[u32], _: &mut Vec<RejectReason>) -> Option<u32> { None }
+}
+
+#[cfg(all(test, feature = "Implements", feature = "CustomResolver"))]
+mod tests
+{
+	use super::*;
+	use {InstanceBuilder, Error};
+	use fakedrv::FakeDriver;
+
+	#[test]
+	fn selection()
+	{
+		let _driver = FakeDriver::install();
+		let instance = InstanceBuilder::new("t", (0, 1, 0), "t", (0, 1, 0)).create().unwrap();
+		let mut selector = PhysicalDeviceSelector::new();
+		selector.queue(QueueFlags::GRAPHICS).queue(QueueFlags::COMPUTE).queue(QueueFlags::TRANSFER)
+			.feature(Feature::GeometryShader).extension("VK_KHR_maintenance1")
+			.limit("maxPushConstantsSize", |l| l.maxPushConstantsSize >= 128).device_local_memory(512 << 20);
+		let s = selector.select(&instance).unwrap();
+		// the most dedicated family for each request
+		assert_eq!(s.queue_families, vec![0, 1, 2]);
+		assert_eq!(s.present_family, None);
+		// a cpu with a 1 GiB device-local heap
+		assert_eq!(s.score, 100 + 1);
+		assert_eq!(selector.prefer_type(PhysicalDeviceType::Cpu).score_with(|_| 7).select(&instance).unwrap().score, 10000 + 100 + 1 + 7);
+	}
+
+	#[test]
+	fn rejection_reasons()
+	{
+		let _driver = FakeDriver::install();
+		let instance = InstanceBuilder::new("t", (0, 1, 0), "t", (0, 1, 0)).create().unwrap();
+		let required_version = VK_MAKE_VERSION!(1, 2, 0);
+		let rejections = match PhysicalDeviceSelector::new().api_version(required_version).queue(QueueFlags::GRAPHICS)
+			.feature(Feature::SparseResidencyBuffer).extension("VK_KHR_unknown")
+			.limit("maxPushConstantsSize", |l| l.maxPushConstantsSize >= 1024).device_local_memory(2 << 30).select(&instance)
+		{
+			Err(Error::NoSuitableDevice(r)) => r,
+			r => panic!("unexpected result: {:?}", r.map(|s| s.score))
+		};
+		// every reason is collected, not only the first one
+		assert_eq!(rejections, vec![Rejection
+		{
+			device_name: "bedrock fake device".to_owned(),
+			reasons: vec![
+				RejectReason::ApiVersion { required: required_version, supported: VK_API_VERSION_1_1 },
+				RejectReason::Unsupported(Error::MissingFeature(Feature::SparseResidencyBuffer)),
+				RejectReason::Unsupported(Error::MissingExtension { name: "VK_KHR_unknown".to_owned(), required_by: None }),
+				RejectReason::Limit("maxPushConstantsSize"),
+				RejectReason::DeviceLocalMemory { required: 2 << 30, available: 1 << 30 }
+			]
+		}]);
+		assert_eq!(rejections[0].to_string(), "bedrock fake device: Vulkan 1.2 is required but the device supports 1.1, \
+			Feature sparseResidencyBuffer is not supported, Extension VK_KHR_unknown is not supported, \
+			limit maxPushConstantsSize is not satisfied, \
+			2147483648 bytes of device-local memory are required but the largest heap has 1073741824 bytes");
+		assert_eq!(RejectReason::NoQueueFamily(QueueFlags::GRAPHICS.compute()).to_string(), "no queue family supports the flags 0x3");
+	}
+}
@@ -6,7 +6,6 @@ use {VkHandle, DeviceChild};
 #[cfg(feature = "Implements")] use VkResultHandler;
 use std::ptr::null;
 use std::marker::PhantomData;
-#[cfg(feature = "Implements")] use crate::vkresolve::{Resolver, ResolverInterface};
 
 /// Bitmask specifying a pipeline stage
 #[derive(Debug, Clone, PartialEq, Eq, Copy, PartialOrd, Ord, Hash)]
@@ -152,6 +151,9 @@ pub struct PipelineCache(VkPipelineCache, ::Device);
 pub struct PipelineLayout(VkPipelineLayout, ::Device);
 /// Opaque handle to a pipeline object
 pub struct Pipeline(VkPipeline, ::Device);
+ThreadSafe!(Send + Sync: ShaderModule, PipelineLayout, Pipeline);
+// the destination of vkMergePipelineCaches needs external synchronization
+ThreadSafe!(Send: PipelineCache);
 
 #[cfg(feature = "Implements")] DeviceChildCommonDrop! {
 	for ShaderModule[destroy_shader_module], PipelineCache[destroy_pipeline_cache], PipelineLayout[destroy_pipeline_layout],
@@ -183,7 +185,7 @@ impl ShaderModule
 			codeSize: buffer.as_ref().len() as _, pCode: buffer.as_ref().as_ptr() as *const _, .. Default::default()
 		};
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_shader_module(device.native_ptr(), &cinfo, ::std::ptr::null(), &mut h) }.into_result()
+		unsafe { device.dispatch().create_shader_module(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h) }.into_result_in("vkCreateShaderModule", "VkDevice")
 			.map(|_| ShaderModule(h, device.clone()))
 	}
 	/// Creates a new shader module object from a file
@@ -218,7 +220,7 @@ impl PipelineCache
 			initialDataSize: initial.as_ref().len() as _, pInitialData: initial.as_ref().as_ptr() as *const _, .. Default::default()
 		};
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_pipeline_cache(device.native_ptr(), &cinfo, ::std::ptr::null(), &mut h) }.into_result()
+		unsafe { device.dispatch().create_pipeline_cache(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h) }.into_result_in("vkCreatePipelineCache", "VkDevice")
 			.map(|_| PipelineCache(h, device.clone()))
 	}
 	/// Get the data store from a pipeline cache
@@ -230,9 +232,9 @@ impl PipelineCache
 	pub fn data(&self) -> ::Result<Vec<u8>>
 	{
 		let mut n = 0;
-		unsafe { Resolver::get().get_pipeline_cache_data(self.1.native_ptr(), self.0, &mut n, ::std::ptr::null_mut()) }.into_result()?;
+		unsafe { self.1.dispatch().get_pipeline_cache_data(self.1.native_ptr(), self.0, &mut n, ::std::ptr::null_mut()) }.into_result_in("vkGetPipelineCacheData", "VkDevice")?;
 		let mut b = Vec::<u8>::with_capacity(n as _); unsafe { b.set_len(n as _) };
-		unsafe { Resolver::get().get_pipeline_cache_data(self.1.native_ptr(), self.0, &mut n, b.as_mut_ptr() as *mut _) }.into_result().map(|_| b)
+		unsafe { self.1.dispatch().get_pipeline_cache_data(self.1.native_ptr(), self.0, &mut n, b.as_mut_ptr() as *mut _) }.into_result_in("vkGetPipelineCacheData", "VkDevice").map(|_| b)
 	}
 	/// Combine the data stores of pipeline caches into `self`
 	/// # Failures
@@ -243,7 +245,7 @@ impl PipelineCache
 	pub fn merge_into(&self, src: &[&PipelineCache]) -> ::Result<()>
 	{
 		let srcs = src.iter().map(|x| x.0).collect::<Vec<_>>();
-		unsafe { Resolver::get().merge_pipeline_caches(self.1.native_ptr(), self.0, srcs.len() as _, srcs.as_ptr()) }.into_result()
+		unsafe { self.1.dispatch().merge_pipeline_caches(self.1.native_ptr(), self.0, srcs.len() as _, srcs.as_ptr()) }.into_result_in("vkMergePipelineCaches", "VkDevice")
 	}
 }
 /// Following methods are enabled with [feature = "Implements"]
@@ -268,7 +270,7 @@ impl PipelineLayout
 			.. Default::default()
 		};
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_pipeline_layout(device.native_ptr(), &cinfo, ::std::ptr::null(), &mut h) }.into_result()
+		unsafe { device.dispatch().create_pipeline_layout(device.native_ptr(), &cinfo, device.allocation_callbacks(), &mut h) }.into_result_in("vkCreatePipelineLayout", "VkDevice")
 			.map(|_| PipelineLayout(h, device.clone()))
 	}
 }
@@ -330,13 +332,16 @@ pub struct GraphicsPipelineBuilder<'d>
 {
 	flags: VkPipelineCreateFlags, _layout: &'d PipelineLayout, rp: &'d ::RenderPass, subpass: u32, _base: BasePipeline<'d>,
 	vp: Option<VertexProcessingStages<'d>>,
-	rasterizer_state: VkPipelineRasterizationStateCreateInfo,
+	rasterizer_state: VkPipelineRasterizationStateCreateInfo, rasterizer_ext: ::Chain<'d, VkPipelineRasterizationStateCreateInfo>,
 	tess_state: Option<Box<VkPipelineTessellationStateCreateInfo>>,
 	viewport_state: Option<Box<VkPipelineViewportStateCreateInfo>>,
-	ms_state: Option<&'d MultisampleState>,
+	ms_state: Option<&'d MultisampleState>, ms_ext: ::Chain<'d, VkPipelineMultisampleStateCreateInfo>,
 	ds_state: Option<Box<VkPipelineDepthStencilStateCreateInfo>>,
 	color_blending: Option<(Box<VkPipelineColorBlendStateCreateInfo>, Vec<VkPipelineColorBlendAttachmentState>)>,
-	dynamic_state_flags: DynamicStateFlags
+	dynamic_state_flags: DynamicStateFlags,
+	ext: ::Chain<'d, VkGraphicsPipelineCreateInfo>,
+	#[cfg_attr(not(all(feature = "Implements", feature = "VK_EXT_debug_marker")), allow(dead_code))]
+	name: Option<&'d str>
 }
 impl<'d, T> DynamicArrayState<'d, T>
 {
@@ -509,11 +514,25 @@ impl<'d> GraphicsPipelineBuilder<'d>
 		GraphicsPipelineBuilder
 		{
 			flags: 0, _layout: layout, rp: rpsp.0, subpass: rpsp.1, _base: BasePipeline::None,
-			vp: None, rasterizer_state: Default::default(),
-			tess_state: None, viewport_state: None, ms_state: None, ds_state: None, color_blending: None,
-			dynamic_state_flags: unsafe { ::std::mem::zeroed() }
+			vp: None, rasterizer_state: Default::default(), rasterizer_ext: ::Chain::new(),
+			tess_state: None, viewport_state: None, ms_state: None, ms_ext: ::Chain::new(), ds_state: None, color_blending: None,
+			dynamic_state_flags: unsafe { ::std::mem::zeroed() }, ext: ::Chain::new(), name: None
 		}
 	}
+	/// Chain an extension structure(e.g. `VkPipelineDiscardRectangleStateCreateInfoEXT`) onto the pipeline create info,
+	/// borrowed while the builder is used
+	pub fn extend<S: ::Extends<VkGraphicsPipelineCreateInfo>>(&mut self, s: &'d mut S) -> &mut Self
+	{
+		self.ext = ::std::mem::take(&mut self.ext).push(s); self
+	}
+	/// Chain an extension structure(e.g. `VkPipelineRasterizationConservativeStateCreateInfoEXT`) onto the rasterization state
+	pub fn extend_rasterization<S: ::Extends<VkPipelineRasterizationStateCreateInfo>>(&mut self, s: &'d mut S) -> &mut Self
+	{
+		self.rasterizer_ext = ::std::mem::take(&mut self.rasterizer_ext).push(s); self
+	}
+	/// A debug name given to the pipeline on creation.
+	/// Ignored unless `VK_EXT_debug_marker` is enabled on the device
+	pub fn name(&mut self, name: &'d str) -> &mut Self { self.name = Some(name); self }
 }
 /// Shading State and Input Configuration
 impl<'d> GraphicsPipelineBuilder<'d>
@@ -583,7 +602,8 @@ impl<'d> GraphicsPipelineBuilder<'d>
 	pub fn depth_clamp_enable(&mut self, enable: bool) -> &mut Self { self.rasterizer_state.depthClampEnable = enable as _; self }
 	/// Controls whether primitives are discarded immediately before the rasterization stage
 	pub fn rasterizer_discard_enable(&mut self, enable: bool) -> &mut Self { self.rasterizer_state.rasterizerDiscardEnable = enable as _; self }
-	/// The triangle rendering mode
+	/// The triangle rendering mode. Modes other than `VK_POLYGON_MODE_FILL` require `Feature::FillModeNonSolid`
+	/// to be enabled on the device(see `Device::enabled_features`)
 	pub fn polygon_mode(&mut self, mode: VkPolygonMode) -> &mut Self { self.rasterizer_state.polygonMode = mode; self }
 	/// The triangle facing direction used for primitive culling
 	pub fn cull_mode(&mut self, mode: VkCullModeFlags) -> &mut Self { self.rasterizer_state.cullMode = mode; self }
@@ -622,6 +642,11 @@ impl<'d> GraphicsPipelineBuilder<'d>
 	{
 		self.ms_state = state; self
 	}
+	/// Chain an extension structure(e.g. `VkPipelineSampleLocationsStateCreateInfoEXT`) to the multisample state
+	pub fn extend_multisample<S: ::Extends<VkPipelineMultisampleStateCreateInfo>>(&mut self, s: &'d mut S) -> &mut Self
+	{
+		self.ms_ext = ::std::mem::take(&mut self.ms_ext).push(s); self
+	}
 }
 
 /// Depth/Stencil State
@@ -963,10 +988,17 @@ impl<'d> VertexProcessingStages<'d>
 impl<'d> GraphicsPipelineBuilder<'d>
 {
 	fn rasterized(&self) -> bool { self.rasterizer_state.rasterizerDiscardEnable == false as _ }
-	fn ms_state_ptr(&self, default: &MultisampleState) -> *const VkPipelineMultisampleStateCreateInfo
+	fn rasterizer_state(&self) -> VkPipelineRasterizationStateCreateInfo
+	{
+		VkPipelineRasterizationStateCreateInfo { pNext: self.rasterizer_ext.head(), .. self.rasterizer_state.clone() }
+	}
+	fn ms_state(&self) -> Option<VkPipelineMultisampleStateCreateInfo>
 	{
-		self.ms_state.as_ref().map(|&x| x as *const _)
-			.unwrap_or_else(||if self.rasterized() { default as _ } else { null() }) as _
+		let state = match self.ms_state
+		{
+			Some(s) => s.0.clone(), None if self.rasterized() => MultisampleState::new().0, None => return None
+		};
+		Some(VkPipelineMultisampleStateCreateInfo { pNext: self.ms_ext.head(), .. state })
 	}
 
 	/// Create a graphics pipeline
@@ -978,6 +1010,8 @@ impl<'d> GraphicsPipelineBuilder<'d>
 	#[allow(unused_variables)]
 	pub fn create(&self, device: &::Device, cache: Option<&PipelineCache>) -> ::Result<Pipeline>
 	{
+		debug_assert!(self.rasterizer_state.polygonMode == VK_POLYGON_MODE_FILL || device.enabled_features().contains(::Feature::FillModeNonSolid),
+			"polygon modes other than FILL require Feature::FillModeNonSolid");
 		// VERTEX PROCESSING //
 		let vp = self.vp.as_ref().expect("Required the Vertex Processing Stages for Graphics Pipeline");
 		let (stages, _specinfo) = vp.generate_stages();
@@ -1010,24 +1044,26 @@ impl<'d> GraphicsPipelineBuilder<'d>
 			_ => panic!("Deriving from other info in same creation is invalid for single creation of pipeline")
 		};
 		let flags = self.flags | if base.is_some() { VK_PIPELINE_CREATE_DERIVATIVE_BIT } else { 0 };
-		let ms_empty = MultisampleState::new();
+		let (rs, ms) = (self.rasterizer_state(), self.ms_state());
 		
 		let cinfo = VkGraphicsPipelineCreateInfo
 		{
 			stageCount: stages.len() as _, pStages: stages.as_ptr(), pVertexInputState: &vp.vi, pInputAssemblyState: &vp.ia,
 			pTessellationState: self.tess_state.as_ref().map(|x| &**x as *const _).unwrap_or(::std::ptr::null()),
 			pViewportState: self.viewport_state.as_ref().map(|x| &**x as *const _).unwrap_or(::std::ptr::null()),
-			pRasterizationState: &self.rasterizer_state as *const _, pMultisampleState: self.ms_state_ptr(&ms_empty),
+			pRasterizationState: &rs, pMultisampleState: ms.as_ref().map(|x| x as *const _).unwrap_or(::std::ptr::null()),
 			pDepthStencilState: self.ds_state.as_ref().map(|x| &**x as *const _).unwrap_or(::std::ptr::null()),
 			pColorBlendState: self.color_blending.as_ref().map(|&(ref x, _)| &**x as *const _).unwrap_or(::std::ptr::null()),
 			pDynamicState: ds.as_ref().map(|x| x as *const _).unwrap_or(::std::ptr::null()),
 			layout: self._layout.native_ptr(), renderPass: self.rp.native_ptr(), subpass: self.subpass,
 			basePipelineHandle: if let &BasePipeline::Handle(ref h) = &self._base { h.native_ptr() } else { VK_NULL_HANDLE as _ },
-			basePipelineIndex: -1, flags, .. Default::default()
+			basePipelineIndex: -1, flags, pNext: self.ext.head(), .. Default::default()
 		};
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_graphics_pipelines(device.native_ptr(), cache.map(VkHandle::native_ptr).unwrap_or(VK_NULL_HANDLE as _),
-			1, &cinfo, ::std::ptr::null(), &mut h) }.into_result().map(|_| Pipeline(h, device.clone()))
+		let p = unsafe { device.dispatch().create_graphics_pipelines(device.native_ptr(), cache.map(VkHandle::native_ptr).unwrap_or(VK_NULL_HANDLE as _),
+			1, &cinfo, device.allocation_callbacks(), &mut h) }.into_result_in("vkCreateGraphicsPipelines", "VkDevice").map(|_| Pipeline(h, device.clone()))?;
+		#[cfg(feature = "VK_EXT_debug_marker")] ::debug::name_created(&p, self.name)?;
+		Ok(p)
 	}
 }
 
@@ -1071,10 +1107,9 @@ impl ::Device
 				})
 			}
 			else { None };
-			(vp, stages, ds, _specinfo, dynamic_states)
+			(vp, stages, ds, _specinfo, dynamic_states, x.rasterizer_state(), x.ms_state())
 		}).collect::<Vec<_>>();
-		let ms_empty = MultisampleState::new();
-		let cinfos = builders.iter().zip(aggregates.iter()).map(|(b, &(ref vp, ref stages, ref ds, _, _))|
+		let cinfos = builders.iter().zip(aggregates.iter()).map(|(b, &(ref vp, ref stages, ref ds, _, _, ref rs, ref ms))|
 		{
 			let (base_handle, base_index) = match b._base
 			{
@@ -1088,18 +1123,21 @@ impl ::Device
 				stageCount: stages.len() as _, pStages: stages.as_ptr(), pVertexInputState: &vp.vi, pInputAssemblyState: &vp.ia,
 				pTessellationState: b.tess_state.as_ref().map(|x| &**x as *const _).unwrap_or(::std::ptr::null()),
 				pViewportState: b.viewport_state.as_ref().map(|x| &**x as *const _).unwrap_or(::std::ptr::null()),
-				pRasterizationState: &b.rasterizer_state as *const _, pMultisampleState: b.ms_state_ptr(&ms_empty),
+				pRasterizationState: rs, pMultisampleState: ms.as_ref().map(|x| x as *const _).unwrap_or(::std::ptr::null()),
 				pDepthStencilState: b.ds_state.as_ref().map(|x| &**x as *const _).unwrap_or(::std::ptr::null()),
 				pColorBlendState: b.color_blending.as_ref().map(|&(ref x, _)| &**x as *const _).unwrap_or(::std::ptr::null()),
 				pDynamicState: ds.as_ref().map(|x| x as *const _).unwrap_or(::std::ptr::null()),
 				layout: b._layout.native_ptr(), renderPass: b.rp.native_ptr(), subpass: b.subpass,
-				basePipelineHandle: base_handle, basePipelineIndex: base_index, flags, .. Default::default()
+				basePipelineHandle: base_handle, basePipelineIndex: base_index, flags, pNext: b.ext.head(), .. Default::default()
 			}
 		}).collect::<Vec<_>>();
 		let mut hs = vec![VK_NULL_HANDLE as VkPipeline; builders.len()];
-		unsafe { Resolver::get().create_graphics_pipelines(self.native_ptr(), cache.map(VkHandle::native_ptr).unwrap_or(VK_NULL_HANDLE as _),
-			cinfos.len() as _, cinfos.as_ptr(), ::std::ptr::null(), hs.as_mut_ptr()) }.into_result()
-			.map(|_| hs.into_iter().map(|h| Pipeline(h, self.clone())).collect())
+		let ps = unsafe { self.dispatch().create_graphics_pipelines(self.native_ptr(), cache.map(VkHandle::native_ptr).unwrap_or(VK_NULL_HANDLE as _),
+			cinfos.len() as _, cinfos.as_ptr(), self.allocation_callbacks(), hs.as_mut_ptr()) }.into_result_in("vkCreateGraphicsPipelines", "VkDevice")
+			.map(|_| hs.into_iter().map(|h| Pipeline(h, self.clone())).collect::<Vec<_>>())?;
+		#[cfg(feature = "VK_EXT_debug_marker")]
+		for (p, b) in ps.iter().zip(builders) { ::debug::name_created(p, b.name)?; }
+		Ok(ps)
 	}
 }
 
@@ -1107,7 +1145,9 @@ impl ::Device
 pub struct ComputePipelineBuilder<'d>
 {
 	shader: PipelineShader<'d>,
-	layout: &'d PipelineLayout
+	layout: &'d PipelineLayout,
+	#[cfg_attr(not(all(feature = "Implements", feature = "VK_EXT_debug_marker")), allow(dead_code))]
+	name: Option<&'d str>
 }
 impl<'d> ComputePipelineBuilder<'d>
 {
@@ -1115,9 +1155,12 @@ impl<'d> ComputePipelineBuilder<'d>
 	{
 		ComputePipelineBuilder
 		{
-			shader, layout
+			shader, layout, name: None
 		}
 	}
+	/// A debug name given to the pipeline on creation.
+	/// Ignored unless `VK_EXT_debug_marker` is enabled on the device
+	pub fn name(&mut self, name: &'d str) -> &mut Self { self.name = Some(name); self }
 }
 #[cfg(feature = "Implements")]
 impl<'d> ComputePipelineBuilder<'d>
@@ -1139,13 +1182,15 @@ impl<'d> ComputePipelineBuilder<'d>
 		};
 
 		let mut pipeline = ::std::mem::MaybeUninit::uninit();
-		unsafe
+		let p = unsafe
 		{
-			Resolver::get().create_compute_pipelines(
+			device.dispatch().create_compute_pipelines(
 				device.native_ptr(), cache.map(VkHandle::native_ptr).unwrap_or(VK_NULL_HANDLE as _),
-				1, &cinfo, ::std::ptr::null(), pipeline.as_mut_ptr()
-			).into_result().map(move |_| Pipeline(pipeline.assume_init(), device.clone()))
-		}
+				1, &cinfo, device.allocation_callbacks(), pipeline.as_mut_ptr()
+			).into_result_in("vkCreateComputePipelines", "VkDevice").map(move |_| Pipeline(pipeline.assume_init(), device.clone()))?
+		};
+		#[cfg(feature = "VK_EXT_debug_marker")] ::debug::name_created(&p, self.name)?;
+		Ok(p)
 	}
 }
 /// Following methods are enabled with [feature = "Implements"]
@@ -1161,19 +1206,22 @@ impl ::Device
 	pub fn create_compute_pipelines(&self, builders: &[ComputePipelineBuilder], cache: Option<&PipelineCache>) -> ::Result<Vec<Pipeline>>
 	{
 		let (stages, _specinfos): (Vec<_>, Vec<_>) = builders.iter().map(|b| b.shader.createinfo_native(ShaderStage::COMPUTE)).unzip();
-		let cinfos = builders.iter().zip(stages.into_iter()).map(|(b, stage)| VkComputePipelineCreateInfo
+		let cinfos = builders.iter().zip(stages).map(|(b, stage)| VkComputePipelineCreateInfo
 		{
 			stage, layout: b.layout.native_ptr(), .. Default::default()
 		}).collect::<Vec<_>>();
 
 		let mut pipelines = vec![VK_NULL_HANDLE as _; builders.len()];
-		unsafe
+		let ps = unsafe
 		{
-			Resolver::get().create_compute_pipelines(
+			self.dispatch().create_compute_pipelines(
 				self.native_ptr(), cache.map(VkHandle::native_ptr).unwrap_or(VK_NULL_HANDLE as _),
-				cinfos.len() as _, cinfos.as_ptr(), ::std::ptr::null(), pipelines.as_mut_ptr()
-			).into_result().map(move |_| pipelines.into_iter().map(|h| Pipeline(h, self.clone())).collect())
-		}
+				cinfos.len() as _, cinfos.as_ptr(), self.allocation_callbacks(), pipelines.as_mut_ptr()
+			).into_result_in("vkCreateComputePipelines", "VkDevice").map(move |_| pipelines.into_iter().map(|h| Pipeline(h, self.clone())).collect::<Vec<_>>())?
+		};
+		#[cfg(feature = "VK_EXT_debug_marker")]
+		for (p, b) in ps.iter().zip(builders) { ::debug::name_created(p, b.name)?; }
+		Ok(ps)
 	}
 }
 
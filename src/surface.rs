@@ -1,30 +1,35 @@
 //! Vulkan Surface/Swapchain Extensions
 
 use vk::*;
-use std::rc::Rc as RefCounter;
-use {VkHandle, DeviceChild};
+use {VkHandle, DeviceChild, RefCounter};
+use extsync::ExternalSync;
 #[cfg(feature = "Implements")] use VkResultHandler;
-#[cfg(feature = "Implements")] use std::ptr::null;
-#[cfg(feature = "Implements")] use ::vkresolve::{Resolver, ResolverInterface};
 
 #[cfg(feature = "VK_KHR_android_surface")] use android::ANativeWindow;
 
 struct SurfaceCell(VkSurfaceKHR, ::Instance);
 /// Opaque handle to a surface object
 #[derive(Clone)] pub struct Surface(RefCounter<SurfaceCell>);
-struct SwapchainCell { obj: VkSwapchainKHR, dev: ::Device, #[allow(dead_code)] target: Surface, fmt: VkFormat, size: ::Extent3D }
+struct SwapchainCell
+{
+	obj: VkSwapchainKHR, dev: ::Device, #[allow(dead_code)] target: Surface, fmt: VkFormat, size: ::Extent3D,
+	#[cfg_attr(not(feature = "Implements"), allow(dead_code))] sync: ExternalSync
+}
 /// Opaque handle to a swapchain object
 #[derive(Clone)] pub struct Swapchain(RefCounter<SwapchainCell>);
+ThreadSafe!(Send + Sync: SurfaceCell);
+// acquiring, presenting and setting HDR metadata take `sync`, after the queue lock and in the order of the handles
+ThreadSafe!(Send + Sync: SwapchainCell);
 
 #[cfg(feature = "Implements")]
 impl Drop for SurfaceCell
 {
-	fn drop(&mut self) { unsafe { Resolver::get().destroy_surface_khr(self.1.native_ptr(), self.0, null()); } }
+	fn drop(&mut self) { let _ = unsafe { self.1.dispatch().destroy_surface_khr(self.1.native_ptr(), self.0, self.1.allocation_callbacks()) }; }
 }
 #[cfg(feature = "Implements")]
 impl Drop for SwapchainCell
 {
-	fn drop(&mut self) { unsafe { Resolver::get().destroy_swapchain_khr(self.dev.native_ptr(), self.obj, null()); } }
+	fn drop(&mut self) { let _ = unsafe { self.dev.dispatch().destroy_swapchain_khr(self.dev.native_ptr(), self.obj, self.dev.allocation_callbacks()) }; }
 }
 impl VkHandle for Surface { type Handle = VkSurfaceKHR; fn native_ptr(&self) -> VkSurfaceKHR { self.0 .0 } }
 impl VkHandle for Swapchain { type Handle = VkSwapchainKHR; fn native_ptr(&self) -> VkSwapchainKHR { self.0.obj } }
@@ -56,7 +61,7 @@ impl Surface
 	{
 		let cinfo = VkXlibSurfaceCreateInfoKHR { dpy: display, window, .. Default::default() };
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_xlib_surface_khr(instance.native_ptr(), &cinfo, null(), &mut h) }.into_result()
+		unsafe { instance.dispatch().create_xlib_surface_khr(instance.native_ptr(), &cinfo, instance.allocation_callbacks(), &mut h) }.into_result_in("vkCreateXlibSurfaceKHR", "VkInstance")
 			.map(|_| Surface(RefCounter::new(SurfaceCell(h, instance.clone()))))
 	}
 	/// Create a `Surface` object for a X11 window, using the XCB client-side library
@@ -70,7 +75,7 @@ impl Surface
 	{
 		let cinfo = VkXcbSurfaceCreateInfoKHR { connection, window, .. Default::default() };
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_xcb_surface_khr(instance.native_ptr(), &cinfo, null(), &mut h) }.into_result()
+		unsafe { instance.dispatch().create_xcb_surface_khr(instance.native_ptr(), &cinfo, instance.allocation_callbacks(), &mut h) }.into_result_in("vkCreateXcbSurfaceKHR", "VkInstance")
 			.map(|_| Surface(RefCounter::new(SurfaceCell(h, instance.clone()))))
 	}
 	/// Create a `Surface` object for a Wayland window
@@ -84,7 +89,7 @@ impl Surface
 	{
 		let cinfo = VkWaylandSurfaceCreateInfoKHR { display, surface, .. Default::default() };
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_wayland_surface_khr(instance.native_ptr(), &cinfo, null(), &mut h) }.into_result()
+		unsafe { instance.dispatch().create_wayland_surface_khr(instance.native_ptr(), &cinfo, instance.allocation_callbacks(), &mut h) }.into_result_in("vkCreateWaylandSurfaceKHR", "VkInstance")
 			.map(|_| Surface(RefCounter::new(SurfaceCell(h, instance.clone()))))
 	}
 	/// Create a `Surface` object for an Android native window
@@ -98,7 +103,7 @@ impl Surface
 	{
 		let cinfo = VkAndroidSurfaceCreateInfoKHR { window, .. Default::default() };
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_android_surface_khr(instance.native_ptr(), &cinfo, null(), &mut h) }.into_result()
+		unsafe { instance.dispatch().create_android_surface_khr(instance.native_ptr(), &cinfo, instance.allocation_callbacks(), &mut h) }.into_result_in("vkCreateAndroidSurfaceKHR", "VkInstance")
 			.map(|_| Surface(RefCounter::new(SurfaceCell(h, instance.clone()))))
 	}
 	/// Create a `Surface` object for an Win32 native window
@@ -112,7 +117,7 @@ impl Surface
 	{
 		let cinfo = VkWin32SurfaceCreateInfoKHR { hinstance, hwnd, .. Default::default() };
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_win32_surface_khr(instance.native_ptr(), &cinfo, null(), &mut h) }.into_result()
+		unsafe { instance.dispatch().create_win32_surface_khr(instance.native_ptr(), &cinfo, instance.allocation_callbacks(), &mut h) }.into_result_in("vkCreateWin32SurfaceKHR", "VkInstance")
 			.map(|_| Surface(RefCounter::new(SurfaceCell(h, instance.clone()))))
 	}
 	/// Create a `Surface` object for an macOS native window
@@ -126,7 +131,7 @@ impl Surface
 	{
 		let cinfo = VkMacOSSurfaceCreateInfoMVK { pView: view_ptr, .. Default::default() };
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_macos_surface_mvk(instance.native_ptr(), &cinfo, null(), &mut h) }.into_result()
+		unsafe { instance.dispatch().create_macos_surface_mvk(instance.native_ptr(), &cinfo, instance.allocation_callbacks(), &mut h) }.into_result_in("vkCreateMacOSSurfaceMVK", "VkInstance")
 			.map(|_| Surface(RefCounter::new(SurfaceCell(h, instance.clone()))))
 	}
 	/// Create a `Surface` object representing a display plane and mode
@@ -146,7 +151,7 @@ impl Surface
 			.. Default::default()
 		};
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_display_plane_surface_khr(instance.native_ptr(), &cinfo, null(), &mut h) }.into_result()
+		unsafe { instance.dispatch().create_display_plane_surface_khr(instance.native_ptr(), &cinfo, instance.allocation_callbacks(), &mut h) }.into_result_in("vkCreateDisplayPlaneSurfaceKHR", "VkInstance")
 			.map(|_| Surface(RefCounter::new(SurfaceCell(h, instance.clone()))))
 	}
 }
@@ -168,6 +173,11 @@ impl<'d> SwapchainBuilder<'d>
 		}, surface)
 	}
 	pub fn array_layers(&mut self, layers: u32) -> &mut Self { self.0.imageArrayLayers = layers; self }
+	/// Chain an extension structure(e.g. `VkSwapchainCounterCreateInfoEXT`), borrowed while the builder is used
+	pub fn extend<S: ::Extends<VkSwapchainCreateInfoKHR>>(&mut self, s: &'d mut S) -> &mut Self
+	{
+		self.0.pNext = ::chain::link(self.0.pNext as _, s); self
+	}
 	pub fn share(&mut self, queue_families: &[u32]) -> &mut Self
 	{
 		self.0.imageSharingMode =
@@ -196,11 +206,11 @@ impl<'d> SwapchainBuilder<'d>
 	pub fn create(&self, device: &::Device) -> ::Result<Swapchain>
 	{
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_swapchain_khr(device.native_ptr(), &self.0, null(), &mut h) }.into_result()
+		unsafe { device.dispatch().create_swapchain_khr(device.native_ptr(), &self.0, device.allocation_callbacks(), &mut h) }.into_result_in("vkCreateSwapchainKHR", "VkDevice")
 			.map(|_| Swapchain(RefCounter::new(SwapchainCell
 			{
 				obj: h, dev: device.clone(), target: self.1.clone(), fmt: self.0.imageFormat,
-				size: ::Extent3D(self.0.imageExtent.width, self.0.imageExtent.height, 1)
+				size: ::Extent3D(self.0.imageExtent.width, self.0.imageExtent.height, 1), sync: ExternalSync::default()
 			})))
 	}
 }
@@ -241,7 +251,10 @@ impl Swapchain
 	/// * `VK_ERROR_DEVICE_LOST`
 	/// * `VK_ERROR_OUT_OF_DATE_KHR`
 	/// * `VK_ERROR_SURFACE_LOST_KHR`
-	pub fn acquire_next(&self, timeout: Option<u64>, completion: CompletionHandler) -> ::Result<u32>
+	///
+	/// The index comes with `SuccessCode::Suboptimal` if the swapchain no longer matches the surface exactly.
+	/// `SuccessCode::Timeout` or `SuccessCode::NotReady` is returned(with no valid index) when no image became available in `timeout`
+	pub fn acquire_next(&self, timeout: Option<u64>, completion: CompletionHandler) -> ::Result<(u32, ::SuccessCode)>
 	{
 		let (semaphore, fence) = match completion
 		{
@@ -249,8 +262,9 @@ impl Swapchain
 			CompletionHandler::Queue(s) => (s.native_ptr(), VK_NULL_HANDLE as _)
 		};
 		let mut n = 0;
-		unsafe { Resolver::get().acquire_next_image_khr(self.device().native_ptr(), self.native_ptr(), timeout.unwrap_or(::std::u64::MAX), semaphore, fence, &mut n) }
-			.into_result().map(|_| n)
+		let _g = self.0.sync.lock();
+		unsafe { self.device().dispatch().acquire_next_image_khr(self.device().native_ptr(), self.native_ptr(), timeout.unwrap_or(::std::u64::MAX), semaphore, fence, &mut n) }
+			.into_success_in("vkAcquireNextImageKHR", "VkDevice").map(|c| (n, c))
 	}
 	/// Queue an image for presentation
 	/// # Failures
@@ -261,7 +275,9 @@ impl Swapchain
 	/// * `VK_ERROR_DEVICE_LOST`
 	/// * `VK_ERROR_OUT_OF_DATE_KHR`
 	/// * `VK_ERROR_SURFACE_LOST_KHR`
-	pub fn queue_present(&self, queue: &::Queue, index: u32, wait_semaphores: &[&::Semaphore]) -> ::Result<()>
+	///
+	/// `SuccessCode::Suboptimal` is returned if the swapchain no longer matches the surface exactly
+	pub fn queue_present(&self, queue: &::Queue, index: u32, wait_semaphores: &[&::Semaphore]) -> ::Result<::SuccessCode>
 	{
 		let mut res = 0;
 		let wait_semaphores = wait_semaphores.iter().map(|x| x.native_ptr()).collect::<Vec<_>>();
@@ -271,7 +287,27 @@ impl Swapchain
 			swapchainCount: 1, pSwapchains: &self.native_ptr(), pImageIndices: &index, pResults: &mut res,
 			.. Default::default()
 		};
-		unsafe { Resolver::get().queue_present_khr(queue.native_ptr(), &pinfo) }.into_result().and_then(|_| res.into_result())
+		let (_gq, _gs) = (queue.external_sync().lock(), self.0.sync.lock());
+		unsafe { queue.device().dispatch().queue_present_khr(queue.native_ptr(), &pinfo) }.into_success_in("vkQueuePresentKHR", "VkQueue")?;
+		res.into_success_in("vkQueuePresentKHR", "VkSwapchainKHR")
+	}
+}
+#[cfg(all(feature = "Implements", feature = "VK_EXT_hdr_metadata"))]
+impl ::Device
+{
+	/// Set HDR metadata for each swapchain.
+	/// `VkHdrMetadataEXT` cannot be chained onto `VkSwapchainCreateInfoKHR`, so it is given after the creation
+	/// # Failures
+	/// Returns `Error::MissingEntryPoint` if `vkSetHdrMetadataEXT` is not provided by the device
+	pub fn set_hdr_metadata(&self, targets: &[(&Swapchain, &VkHdrMetadataEXT)]) -> ::Result<()>
+	{
+		let f: PFN_vkSetHdrMetadataEXT = self.extra_procedure("vkSetHdrMetadataEXT").ok_or(::Error::MissingEntryPoint("vkSetHdrMetadataEXT"))?;
+		let (swapchains, metadata): (Vec<_>, Vec<_>) = targets.iter().map(|&(s, m)| (s.native_ptr(), m.clone())).unzip();
+		let mut locked = targets.iter().map(|&(s, _)| &s.0).collect::<Vec<_>>();
+		locked.sort_by_key(|x| x.obj as usize); locked.dedup_by_key(|x| x.obj as usize);
+		let _g = locked.into_iter().map(|x| x.sync.lock()).collect::<Vec<_>>();
+		(f)(self.native_ptr(), swapchains.len() as _, swapchains.as_ptr(), metadata.as_ptr());
+		Ok(())
 	}
 }
 #[cfg(feature = "Implements")]
@@ -286,8 +322,11 @@ impl ::Queue
 	/// * `VK_ERROR_DEVICE_LOST`
 	/// * `VK_ERROR_OUT_OF_DATE_KHR`
 	/// * `VK_ERROR_SURFACE_LOST_KHR`
-	pub fn present(&self, swapchains: &[(&Swapchain, u32)], wait_semaphores: &[&::Semaphore]) -> ::Result<Vec<VkResult>>
+	///
+	/// The results of each swapchains are returned in order on success
+	pub fn present(&self, swapchains: &[(&Swapchain, u32)], wait_semaphores: &[&::Semaphore]) -> ::Result<Vec<::Result<::SuccessCode>>>
 	{
+		let swapchain_objects = swapchains.iter().map(|&(x, _)| x).collect::<Vec<_>>();
 		let mut res = vec![0; swapchains.len()];
 		let wait_semaphores = wait_semaphores.iter().map(|x| x.native_ptr()).collect::<Vec<_>>();
 		let (swapchains, indices): (Vec<_>, Vec<_>) = swapchains.iter().map(|&(ref x, n)| (x.native_ptr(), n)).unzip();
@@ -297,7 +336,13 @@ impl ::Queue
 			swapchainCount: swapchains.len() as _, pSwapchains: swapchains.as_ptr(), pImageIndices: indices.as_ptr(),
 			pResults: res.as_mut_ptr(), .. Default::default()
 		};
-		unsafe { Resolver::get().queue_present_khr(self.native_ptr(), &pinfo) }.into_result().map(|_| res)
+		// locked in the order of the handles so that overlapping presentations never deadlock
+		let mut locked = swapchain_objects.iter().map(|x| &x.0).collect::<Vec<_>>();
+		locked.sort_by_key(|x| x.obj as usize); locked.dedup_by_key(|x| x.obj as usize);
+		let _gq = self.external_sync().lock();
+		let _gs = locked.into_iter().map(|x| x.sync.lock()).collect::<Vec<_>>();
+		unsafe { self.device().dispatch().queue_present_khr(self.native_ptr(), &pinfo) }.into_success_in("vkQueuePresentKHR", "VkQueue")?;
+		Ok(res.into_iter().map(|r| r.into_success_in("vkQueuePresentKHR", "VkSwapchainKHR")).collect())
 	}
 }
 
@@ -388,3 +433,59 @@ impl CompositeAlpha
 	/// Does the value contains this bits
 	pub fn contains(self, value: u32) -> bool { (value | self as u32) != 0 }
 }
+
+#[cfg(all(test, feature = "Implements", feature = "CustomResolver"))]
+mod tests
+{
+	/// Presentations overlapping on a queue and two swapchains in every order finish while the device is waited
+	#[test]
+	#[cfg(feature = "Multithreaded")]
+	fn lock_ordering()
+	{
+		use super::*;
+		use {Fence, Waitable};
+		use std::sync::{mpsc, Arc, Barrier};
+		use std::thread;
+		use std::time::Duration;
+
+		let (driver, instance, device) = ::fakedrv::test_device(|b| { b.add_extension("VK_KHR_surface"); }, |b| { b.add_extension("VK_KHR_swapchain"); });
+		let surface = Surface(RefCounter::new(SurfaceCell(driver.headless_surface(instance.native_ptr()), instance.clone())));
+		let format = VkSurfaceFormatKHR { format: VK_FORMAT_B8G8R8A8_UNORM, colorSpace: VK_COLOR_SPACE_SRGB_NONLINEAR_KHR };
+		let swapchain = || SwapchainBuilder::new(&surface, 2, &format, &::Extent2D(4, 4), ::ImageUsage::COLOR_ATTACHMENT)
+			.create(&device).unwrap();
+		let (sc1, sc2) = (swapchain(), swapchain());
+		let queue = device.queue(0, 0);
+
+		let (tx, rx) = mpsc::channel();
+		let mut workers: Vec<Box<dyn Fn() + Send>> = Vec::new();
+		{ let (q, a, b) = (queue.clone(), sc1.clone(), sc2.clone()); workers.push(Box::new(move || { q.present(&[(&a, 0), (&b, 0)], &[]).unwrap(); })); }
+		{ let (q, a, b) = (queue.clone(), sc1.clone(), sc2.clone()); workers.push(Box::new(move || { q.present(&[(&b, 0), (&a, 0)], &[]).unwrap(); })); }
+		{ let (q, a) = (queue.clone(), sc1.clone()); workers.push(Box::new(move || { a.queue_present(&q, 0, &[]).unwrap(); })); }
+		{ let d = device.clone(); workers.push(Box::new(move || d.wait().unwrap())); }
+		{
+			let (b, fence) = (sc2.clone(), Fence::new(&device, false).unwrap());
+			workers.push(Box::new(move ||
+			{
+				b.acquire_next(None, CompletionHandler::Host(&fence)).unwrap();
+				fence.wait().unwrap(); fence.reset().unwrap();
+			}));
+		}
+		let (count, start) = (workers.len(), Arc::new(Barrier::new(workers.len())));
+		for w in workers
+		{
+			let (tx, start) = (tx.clone(), start.clone());
+			thread::spawn(move || { start.wait(); for _ in 0 .. 5000 { w(); } tx.send(()).unwrap(); });
+		}
+		for _ in 0 .. count { rx.recv_timeout(Duration::from_secs(30)).expect("presentation deadlocked"); }
+		::fakedrv::assert_no_misuse(&driver, &instance);
+	}
+
+	/// HDR metadata fails instead of panicking when the device does not export the command
+	#[test]
+	#[cfg(feature = "VK_EXT_hdr_metadata")]
+	fn hdr_metadata_missing()
+	{
+		let (_driver, _instance, device) = ::fakedrv::default_test_device();
+		assert_eq!(device.set_hdr_metadata(&[]), Err(::Error::MissingEntryPoint("vkSetHdrMetadataEXT")));
+	}
+}
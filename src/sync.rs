@@ -4,8 +4,7 @@
 
 use vk::*;
 #[cfg(feature = "Implements")] use VkHandle;
-#[cfg(feature = "Implements")] use {VkResultHandler, VkResultBox};
-#[cfg(feature = "Implements")] use ::vkresolve::{Resolver, ResolverInterface};
+#[cfg(feature = "Implements")] use {VkResultHandler, SuccessCode};
 
 /// Opaque handle to a fence object
 pub struct Fence(pub VkFence, ::Device);
@@ -38,8 +37,8 @@ impl Fence
 	{
 		let mut h = VK_NULL_HANDLE as _;
 		let flags = if signaled { ::vk::VK_FENCE_CREATE_SIGNALED_BIT } else { 0 };
-		unsafe { Resolver::get().create_fence(device.native_ptr(), &VkFenceCreateInfo { flags, .. Default::default() }, ::std::ptr::null(), &mut h) }
-			.into_result().map(|_| Fence(h, device.clone()))
+		unsafe { device.dispatch().create_fence(device.native_ptr(), &VkFenceCreateInfo { flags, .. Default::default() }, device.allocation_callbacks(), &mut h) }
+			.into_result_in("vkCreateFence", "VkDevice").map(|_| Fence(h, device.clone()))
 	}
 }
 /// Following methods are enabled with [feature = "Implements"]
@@ -55,8 +54,8 @@ impl Semaphore
 	pub fn new(device: &::Device) -> ::Result<Self>
 	{
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_semaphore(device.native_ptr(), &Default::default(), ::std::ptr::null(), &mut h) }
-			.into_result().map(|_| Semaphore(h, device.clone()))
+		unsafe { device.dispatch().create_semaphore(device.native_ptr(), &Default::default(), device.allocation_callbacks(), &mut h) }
+			.into_result_in("vkCreateSemaphore", "VkDevice").map(|_| Semaphore(h, device.clone()))
 	}
 }
 /// Following methods are enabled with [feature = "Implements"]
@@ -72,8 +71,8 @@ impl Event
 	pub fn new(device: &::Device) -> ::Result<Self>
 	{
 		let mut h = VK_NULL_HANDLE as _;
-		unsafe { Resolver::get().create_event(device.native_ptr(), &Default::default(), ::std::ptr::null(), &mut h) }
-			.into_result().map(|_| Event(h, device.clone()))
+		unsafe { device.dispatch().create_event(device.native_ptr(), &Default::default(), device.allocation_callbacks(), &mut h) }
+			.into_result_in("vkCreateEvent", "VkDevice").map(|_| Event(h, device.clone()))
 	}
 }
 
@@ -91,8 +90,12 @@ impl Fence
 	pub fn wait_multiple(objects: &[&Self], wait_all: bool, timeout: Option<u64>) -> ::Result<bool>
 	{
 		let objects_ptr = objects.iter().map(|x| x.0).collect::<Vec<_>>();
-		let vr = unsafe { Resolver::get().wait_for_fences(objects[0].1.native_ptr(), objects_ptr.len() as _, objects_ptr.as_ptr(), wait_all as _, timeout.unwrap_or(::std::u64::MAX)) };
-		match vr { ::vk::VK_SUCCESS => Ok(false), ::vk::VK_TIMEOUT => Ok(true), _ => Err(VkResultBox(vr)) }
+		let vr = unsafe { objects[0].1.dispatch().wait_for_fences(objects[0].1.native_ptr(), objects_ptr.len() as _, objects_ptr.as_ptr(), wait_all as _, timeout.unwrap_or(::std::u64::MAX)) };
+		match vr.into_success_in("vkWaitForFences", "VkDevice")?
+		{
+			SuccessCode::Success => Ok(false), SuccessCode::Timeout => Ok(true),
+			s => Err(::Error::Unexpected(s).in_command("vkWaitForFences", "VkDevice"))
+		}
 	}
 	/// Wait for a fence to become signaled, returns `Ok(true)` if operation is timed out
 	/// # Failures
@@ -103,8 +106,12 @@ impl Fence
 	/// * `VK_ERROR_DEVICE_LOST`
 	pub fn wait_timeout(&self, timeout: u64) -> ::Result<bool>
 	{
-		let vr = unsafe { Resolver::get().wait_for_fences(self.1.native_ptr(), 1, &self.0, false as _, timeout) };
-		match vr { ::vk::VK_SUCCESS => Ok(false), ::vk::VK_TIMEOUT => Ok(true), _ => Err(VkResultBox(vr)) }
+		let vr = unsafe { self.1.dispatch().wait_for_fences(self.1.native_ptr(), 1, &self.0, false as _, timeout) };
+		match vr.into_success_in("vkWaitForFences", "VkDevice")?
+		{
+			SuccessCode::Success => Ok(false), SuccessCode::Timeout => Ok(true),
+			s => Err(::Error::Unexpected(s).in_command("vkWaitForFences", "VkDevice"))
+		}
 	}
 	/// Resets one or more fence objects
 	/// # Failures
@@ -115,7 +122,7 @@ impl Fence
 	pub fn reset_multiple(objects: &[&Self]) -> ::Result<()>
 	{
 		let objects_ptr = objects.iter().map(|x| x.0).collect::<Vec<_>>();
-		unsafe { Resolver::get().reset_fences(objects[0].1.native_ptr(), objects_ptr.len() as _, objects_ptr.as_ptr()) }.into_result()
+		unsafe { objects[0].1.dispatch().reset_fences(objects[0].1.native_ptr(), objects_ptr.len() as _, objects_ptr.as_ptr()) }.into_result_in("vkResetFences", "VkDevice")
 	}
 	/// Resets a fence object
 	/// # Failures
@@ -123,7 +130,7 @@ impl Fence
 	/// 
 	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
 	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
-	pub fn reset(&self) -> ::Result<()> { unsafe { Resolver::get().reset_fences(self.1.native_ptr(), 1, &self.0) }.into_result() }
+	pub fn reset(&self) -> ::Result<()> { unsafe { self.1.dispatch().reset_fences(self.1.native_ptr(), 1, &self.0) }.into_result_in("vkResetFences", "VkDevice") }
 }
 /// Following methods are enabled with [feature = "Implements"]
 #[cfg(feature = "Implements")]
@@ -135,14 +142,14 @@ impl Event
 	/// 
 	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
 	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
-	pub fn set(&self) -> ::Result<()> { unsafe { Resolver::get().set_event(self.1.native_ptr(), self.0) }.into_result() }
+	pub fn set(&self) -> ::Result<()> { unsafe { self.1.dispatch().set_event(self.1.native_ptr(), self.0) }.into_result_in("vkSetEvent", "VkDevice") }
 	/// Reset an event to non-signaled state
 	/// # Failures
 	/// On failure, this command returns
 	/// 
 	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
 	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
-	pub fn reset(&self) -> ::Result<()> { unsafe { Resolver::get().reset_event(self.1.native_ptr(), self.0) }.into_result() }
+	pub fn reset(&self) -> ::Result<()> { unsafe { self.1.dispatch().reset_event(self.1.native_ptr(), self.0) }.into_result_in("vkResetEvent", "VkDevice") }
 }
 
 #[cfg(feature = "Implements")]
@@ -162,8 +169,12 @@ impl Status for Fence
 {
 	fn status(&self) -> ::Result<bool>
 	{
-		let vr = unsafe { Resolver::get().get_fence_status(self.1.native_ptr(), self.0) };
-		match vr { ::vk::VK_SUCCESS => Ok(true), ::vk::VK_NOT_READY => Ok(false), _ => Err(VkResultBox(vr)) }
+		let vr = unsafe { self.1.dispatch().get_fence_status(self.1.native_ptr(), self.0) };
+		match vr.into_success_in("vkGetFenceStatus", "VkDevice")?
+		{
+			SuccessCode::Success => Ok(true), SuccessCode::NotReady => Ok(false),
+			s => Err(::Error::Unexpected(s).in_command("vkGetFenceStatus", "VkDevice"))
+		}
 	}
 }
 #[cfg(feature = "Implements")]
@@ -171,8 +182,12 @@ impl Status for Event
 {
 	fn status(&self) -> ::Result<bool>
 	{
-		let vr = unsafe { Resolver::get().get_event_status(self.1.native_ptr(), self.0) };
-		match vr { ::vk::VK_EVENT_SET => Ok(true), ::vk::VK_EVENT_RESET => Ok(false), _ => Err(VkResultBox(vr)) }
+		let vr = unsafe { self.1.dispatch().get_event_status(self.1.native_ptr(), self.0) };
+		match vr.into_success_in("vkGetEventStatus", "VkDevice")?
+		{
+			SuccessCode::EventSet => Ok(true), SuccessCode::EventReset => Ok(false),
+			s => Err(::Error::Unexpected(s).in_command("vkGetEventStatus", "VkDevice"))
+		}
 	}
 }
 #[cfg(feature = "Implements")]
@@ -188,5 +203,6 @@ impl ::Waitable for Fence
 	fn wait(&self) -> ::Result<()> { self.wait_timeout(::std::u64::MAX).map(|_| ()) }
 }
 
-unsafe impl Send for Fence {}
-unsafe impl Sync for Fence {}
+ThreadSafe!(Send + Sync: Fence, Semaphore);
+// vkSetEvent/vkResetEvent need external synchronization
+ThreadSafe!(Send: Event);
@@ -0,0 +1,490 @@
+//! Staging uploads through a persistent ring buffer
+//!
+//! `Uploader` owns a host-visible `TRANSFER_SRC` buffer used as a ring. Each upload request copies the data into the ring
+//! and records a copy command; `submit` batches the pending copies into one transfer `CommandBuffer` signaling a `Fence`.
+//! Space in the ring(with the command buffer and the fence) is recycled once the fence of the batch becomes signaled.
+//!
+//! ```rust,ignore
+//! let mut uploader = Uploader::new(&device, device.queue(family, 0), family, 4 << 20)?;
+//! uploader.upload_buffer(&vertices, 0, &vertex_data)?;
+//! uploader.upload_image(&texture, &region, &pixels, ImageLayout::ShaderReadOnlyOpt)?;
+//! uploader.submit()?;
+//! uploader.wait()?;
+//! ```
+
+use vk::*;
+use {Device, DeviceMemory, Buffer, BufferDesc, BufferUsage, Image, ImageLayout, MemoryBound, MemoryPropertyFlags, PlainData};
+use {CommandPool, CommandBuffer, Queue, SubmissionBatch, Fence, Status, Waitable, PipelineStageFlags, ImageMemoryBarrier};
+use {VkHandle, DeviceChild, PixelFormat};
+use std::collections::VecDeque;
+use std::mem::{size_of_val, align_of};
+
+/// Alignment of the data copied to an image of `format`: `bufferOffset` must be a multiple of the texel block size and of 4
+fn image_data_alignment(format: VkFormat) -> VkDeviceSize
+{
+	let block = match format
+	{
+		VK_FORMAT_BC1_RGB_UNORM_BLOCK ..= VK_FORMAT_BC1_RGBA_SRGB_BLOCK | VK_FORMAT_BC4_UNORM_BLOCK | VK_FORMAT_BC4_SNORM_BLOCK |
+			VK_FORMAT_ETC2_R8G8B8_UNORM_BLOCK ..= VK_FORMAT_ETC2_R8G8B8A1_SRGB_BLOCK |
+			VK_FORMAT_EAC_R11_UNORM_BLOCK | VK_FORMAT_EAC_R11_SNORM_BLOCK => 8,
+		VK_FORMAT_BC2_UNORM_BLOCK ..= VK_FORMAT_BC3_SRGB_BLOCK | VK_FORMAT_BC5_UNORM_BLOCK ..= VK_FORMAT_BC7_SRGB_BLOCK |
+			VK_FORMAT_ETC2_R8G8B8A8_UNORM_BLOCK | VK_FORMAT_ETC2_R8G8B8A8_SRGB_BLOCK |
+			VK_FORMAT_EAC_R11G11_UNORM_BLOCK ..= VK_FORMAT_ASTC_12x12_SRGB_BLOCK => 16,
+		// formats of unknown size(e.g. multi-planar ones) have no larger blocks
+		f => match f.bit_width() / 8 { 0 => 16, b => b as VkDeviceSize }
+	};
+	// the least common multiple of 4 and the block size
+	match block % 4 { 0 => block, 2 => block * 2, _ => block * 4 }
+}
+
+/// A copy command waiting for `Uploader::submit`. The destination is kept alive until the batch completes
+enum PendingCopy
+{
+	Buffer { dst: Buffer, region: VkBufferCopy },
+	Image { dst: Image, region: VkBufferImageCopy, final_layout: ImageLayout }
+}
+impl PendingCopy
+{
+	/// The image and the subresource range written by this copy
+	fn image_range(&self) -> Option<(&Image, VkImageSubresourceRange, ImageLayout)>
+	{
+		match *self
+		{
+			PendingCopy::Image { ref dst, ref region, final_layout } =>
+			{
+				let s = &region.imageSubresource;
+				Some((dst, VkImageSubresourceRange
+				{
+					aspectMask: s.aspectMask, baseMipLevel: s.mipLevel, levelCount: 1,
+					baseArrayLayer: s.baseArrayLayer, layerCount: s.layerCount
+				}, final_layout))
+			},
+			_ => None
+		}
+	}
+}
+
+/// Submitted batch of copies
+struct UploadBatch
+{
+	fence: Fence, command: CommandBuffer, _copies: Vec<PendingCopy>,
+	/// Position of the ring head just after the last data of this batch
+	end: VkDeviceSize,
+	/// Bytes of the ring occupied by this batch, including the padding and the wasted tail on wrapping
+	bytes: VkDeviceSize
+}
+
+/// Staging upload manager owning a host-visible ring buffer
+pub struct Uploader
+{
+	queue: Queue, pool: CommandPool, ring: Buffer, memory: DeviceMemory, capacity: VkDeviceSize,
+	/// The whole `memory`, mapped while the uploader lives
+	mapped: *mut u8, coherent: bool,
+	/// Next write position
+	head: VkDeviceSize,
+	/// Start of the oldest data still in use
+	tail: VkDeviceSize,
+	/// Bytes of the ring in use(distinguishes the full ring from the empty one when `head == tail`)
+	used: VkDeviceSize,
+	pending: Vec<PendingCopy>, pending_bytes: VkDeviceSize,
+	in_flight: VecDeque<UploadBatch>,
+	free_commands: Vec<CommandBuffer>, free_fences: Vec<Fence>
+}
+impl Uploader
+{
+	/// Create an uploader submitting to `queue` of the `queue_family`, with a ring of `ring_size` bytes.
+	/// The ring is placed in a `HOST_VISIBLE` memory type, `HOST_COHERENT` ones preferred, and kept mapped
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `Error::NoMemoryType` if no host-visible memory type can hold the ring buffer
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_MEMORY_MAP_FAILED`
+	pub fn new(device: &Device, queue: Queue, queue_family: u32, ring_size: VkDeviceSize) -> ::Result<Self>
+	{
+		let ring = BufferDesc::new(ring_size as _, BufferUsage::TRANSFER_SRC).create(device)?;
+		let req = ring.requirements();
+		let type_index = device.host_access().find_type(req.memoryTypeBits,
+				&[VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT | VK_MEMORY_PROPERTY_HOST_COHERENT_BIT, VK_MEMORY_PROPERTY_HOST_VISIBLE_BIT])
+			.ok_or(::Error::NoMemoryType { type_bits: req.memoryTypeBits, flags: MemoryPropertyFlags::HOST_VISIBLE })?;
+		let memory = DeviceMemory::allocate(device, req.size as _, type_index as _)?;
+		ring.bind(&memory, 0)?;
+		let pool = CommandPool::new(device, queue_family, true, true)?;
+		let coherent = (device.host_access().memory_type_flags[type_index as usize] & VK_MEMORY_PROPERTY_HOST_COHERENT_BIT) != 0;
+		let mapped = unsafe { memory.map(0 .. req.size as usize)?.get_mut::<u8>(0) as *mut u8 };
+		Ok(Uploader
+		{
+			queue, pool, ring, memory, capacity: ring_size, mapped, coherent, head: 0, tail: 0, used: 0,
+			pending: Vec::new(), pending_bytes: 0, in_flight: VecDeque::new(),
+			free_commands: Vec::new(), free_fences: Vec::new()
+		})
+	}
+
+	/// Size of the ring buffer in bytes
+	pub fn capacity(&self) -> VkDeviceSize { self.capacity }
+	/// Bytes of the ring occupied by the pending and the in-flight uploads
+	pub fn used_bytes(&self) -> VkDeviceSize { self.used }
+	/// Number of the copies waiting for `submit`
+	pub fn pending_copies(&self) -> usize { self.pending.len() }
+	/// Number of the submitted batches whose fence has not been observed signaled yet
+	pub fn batches_in_flight(&self) -> usize { self.in_flight.len() }
+
+	/// Write `data` to `dst` at `offset` bytes. The copy is executed on the next `submit`.
+	/// If the ring is short of space, the pending copies are submitted and the oldest batches are waited
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_DEVICE_LOST`
+	/// # Panics
+	/// Panics if `data` is larger than the ring
+	pub fn upload_buffer<T: PlainData>(&mut self, dst: &Buffer, offset: VkDeviceSize, data: &[T]) -> ::Result<()>
+	{
+		let bytes = size_of_val(data) as VkDeviceSize;
+		if bytes == 0 { return Ok(()); }
+		let src = self.reserve(bytes, align_of::<T>().max(4) as _)?;
+		self.write(src, data)?;
+		self.pending.push(PendingCopy::Buffer
+		{
+			dst: dst.clone(), region: VkBufferCopy { srcOffset: src, dstOffset: offset, size: bytes }
+		});
+		Ok(())
+	}
+	/// Write `bytes` to the `region` of `dst`, then transition the written subresources to `final_layout`.
+	/// `region.bufferOffset` is ignored; `bufferRowLength` and `bufferImageHeight` describe the layout of `bytes`,
+	/// which is placed in the ring at a multiple of the texel block size of the format of `dst`.
+	/// The previous contents of the written subresources are discarded
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_DEVICE_LOST`
+	/// # Panics
+	/// Panics if `bytes` is larger than the ring
+	pub fn upload_image(&mut self, dst: &Image, region: &VkBufferImageCopy, bytes: &[u8], final_layout: ImageLayout) -> ::Result<()>
+	{
+		if bytes.is_empty() { return Ok(()); }
+		let src = self.reserve(bytes.len() as _, image_data_alignment(dst.format()))?;
+		self.write(src, bytes)?;
+		self.pending.push(PendingCopy::Image
+		{
+			dst: dst.clone(), region: VkBufferImageCopy { bufferOffset: src, .. region.clone() }, final_layout
+		});
+		Ok(())
+	}
+
+	/// Record the pending copies into a transfer command buffer and submit it with a fence.
+	/// Does nothing if there are no pending copies
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_DEVICE_LOST`
+	/// # Panics
+	/// Panics if the same subresource range of an image is requested to be transitioned to different layouts in a batch
+	pub fn submit(&mut self) -> ::Result<()>
+	{
+		if self.pending.is_empty() { return Ok(()); }
+		let command = match self.free_commands.pop() { Some(c) => c, None => self.pool.alloc(1, true)?.remove(0) };
+		let fence = match self.free_fences.pop() { Some(f) => f, None => Fence::new(self.pool.device(), false)? };
+
+		// each subresource range is transitioned once even if several regions of it are written
+		let mut ranges: Vec<(&Image, VkImageSubresourceRange, ImageLayout)> = Vec::new();
+		for (img, range, layout) in self.pending.iter().filter_map(PendingCopy::image_range)
+		{
+			match ranges.iter().find(|&&(i, ref r, _)| i.native_ptr() == img.native_ptr() && *r == range)
+			{
+				Some(&(_, _, l)) => assert!(l == layout, "a subresource range of an image is uploaded with different final layouts in a batch"),
+				None => ranges.push((img, range, layout))
+			}
+		}
+		{
+			let mut rec = match command.begin_once()
+			{
+				Ok(r) => r,
+				Err(e) => { self.free_commands.push(command); self.free_fences.push(fence); return Err(e); }
+			};
+			if !ranges.is_empty()
+			{
+				let barriers: Vec<_> = ranges.iter().map(|&(img, ref r, _)|
+					ImageMemoryBarrier::new_raw(img, r, ImageLayout::Undefined, ImageLayout::TransferDestOpt)).collect();
+				rec.pipeline_barrier(PipelineStageFlags::TOP_OF_PIPE, PipelineStageFlags::TRANSFER, false, &[], &[], &barriers);
+			}
+			for c in &self.pending
+			{
+				match *c
+				{
+					PendingCopy::Buffer { ref dst, ref region } => { rec.copy_buffer(&self.ring, dst, ::std::slice::from_ref(region)); },
+					PendingCopy::Image { ref dst, ref region, .. } =>
+					{
+						rec.copy_buffer_to_image(&self.ring, dst, ImageLayout::TransferDestOpt, ::std::slice::from_ref(region));
+					}
+				}
+			}
+			let barriers: Vec<_> = ranges.iter().map(|&(img, ref r, layout)|
+				ImageMemoryBarrier::new_raw(img, r, ImageLayout::TransferDestOpt, layout)).collect();
+			let written = VkMemoryBarrier
+			{
+				srcAccessMask: VK_ACCESS_TRANSFER_WRITE_BIT, dstAccessMask: VK_ACCESS_MEMORY_READ_BIT | VK_ACCESS_MEMORY_WRITE_BIT,
+				.. Default::default()
+			};
+			rec.pipeline_barrier(PipelineStageFlags::TRANSFER, PipelineStageFlags::ALL_COMMANDS, false, &[written], &[], &barriers);
+		}
+		self.queue.submit(&[SubmissionBatch { command_buffers: (&[command][..]).into(), .. Default::default() }], Some(&fence))?;
+		self.in_flight.push_back(UploadBatch
+		{
+			fence, command, _copies: ::std::mem::take(&mut self.pending),
+			end: self.head, bytes: ::std::mem::replace(&mut self.pending_bytes, 0)
+		});
+		Ok(())
+	}
+	/// Recycle the ring space of the batches whose fence has become signaled, without blocking
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_DEVICE_LOST`
+	pub fn poll(&mut self) -> ::Result<()>
+	{
+		while let Some(true) = self.in_flight.front().map(|b| b.fence.status()).transpose()? { self.retire_oldest()?; }
+		Ok(())
+	}
+	/// Wait for all the submitted batches and recycle their ring space. Pending copies are not submitted
+	/// # Failures
+	/// On failure, this command returns
+	///
+	/// * `VK_ERROR_OUT_OF_HOST_MEMORY`
+	/// * `VK_ERROR_OUT_OF_DEVICE_MEMORY`
+	/// * `VK_ERROR_DEVICE_LOST`
+	pub fn wait(&mut self) -> ::Result<()>
+	{
+		while let Some(b) = self.in_flight.front()
+		{
+			b.fence.wait()?;
+			self.retire_oldest()?;
+		}
+		Ok(())
+	}
+
+	/// Copy `data` to the ring at `offset`, and flush it unless the memory is `HOST_COHERENT`
+	fn write<T: PlainData>(&self, offset: VkDeviceSize, data: &[T]) -> ::Result<()>
+	{
+		let bytes = size_of_val(data) as VkDeviceSize;
+		unsafe { ::std::ptr::copy_nonoverlapping(data.as_ptr() as *const u8, self.mapped.add(offset as usize), bytes as usize) };
+		if self.coherent { return Ok(()); }
+		let atom = self.memory.device().host_access().non_coherent_atom_size;
+		let start = offset / atom * atom;
+		let end = ::std::cmp::min((offset + bytes + atom - 1) / atom * atom, self.memory.size());
+		let range = VkMappedMemoryRange { memory: self.memory.native_ptr(), offset: start, size: end - start, .. Default::default() };
+		unsafe { self.memory.device().flush_mapped_memory_ranges(&[range]) }
+	}
+	/// Pop the oldest batch, which must have been completed, and release its ring space
+	fn retire_oldest(&mut self) -> ::Result<()>
+	{
+		let b = self.in_flight.pop_front().expect("no batch in flight");
+		self.tail = b.end;
+		self.used -= b.bytes;
+		b.fence.reset()?;
+		self.free_fences.push(b.fence);
+		self.free_commands.push(b.command);
+		Ok(())
+	}
+	/// Reserve `size` bytes aligned to `align` in the ring, submitting and waiting the older uploads while there is no room
+	fn reserve(&mut self, size: VkDeviceSize, align: VkDeviceSize) -> ::Result<VkDeviceSize>
+	{
+		assert!(size <= self.capacity, "an upload of {} bytes exceeds the ring buffer({} bytes)", size, self.capacity);
+		self.poll()?;
+		loop
+		{
+			if self.used == 0 { self.head = 0; self.tail = 0; }
+			let start = (self.head + align - 1) / align * align;
+			let found = if self.head > self.tail || self.used == 0
+			{
+				if start + size <= self.capacity { Some(start) } else if size <= self.tail { Some(0) } else { None }
+			}
+			else if start + size <= self.tail { Some(start) } else { None };
+
+			if let Some(start) = found
+			{
+				let consumed = if start >= self.head { start + size - self.head } else { self.capacity - self.head + size };
+				self.head = start + size;
+				self.used += consumed;
+				self.pending_bytes += consumed;
+				return Ok(start);
+			}
+			if let Some(b) = self.in_flight.front() { b.fence.wait()?; self.retire_oldest()?; }
+			else { self.submit()?; }
+		}
+	}
+}
+impl Drop for Uploader
+{
+	/// Waits for the submitted batches so that the ring and the command buffers are not destroyed while in use
+	fn drop(&mut self)
+	{
+		for b in &self.in_flight { let _ = b.fence.wait(); }
+		unsafe { self.memory.unmap() };
+	}
+}
+
+#[cfg(all(test, feature = "Implements", feature = "CustomResolver"))]
+mod tests
+{
+	use super::*;
+	use {ImageDesc, ImageUsage, Extent2D, Readback};
+	use fakedrv::{default_test_device, assert_no_misuse};
+
+	/// Index of the host-visible, host-coherent memory type of the fake driver
+	const HOST_COHERENT_TYPE: u32 = 1;
+
+	/// A transfer destination buffer of `size` bytes in a host-coherent memory
+	fn destination(device: &Device, size: usize) -> (Buffer, DeviceMemory)
+	{
+		let buffer = BufferDesc::new(size, BufferUsage::TRANSFER_DEST).create(device).unwrap();
+		let memory = DeviceMemory::allocate(device, buffer.requirements().size as _, HOST_COHERENT_TYPE).unwrap();
+		buffer.bind(&memory, 0).unwrap();
+		(buffer, memory)
+	}
+	fn bytes(seed: u8, len: usize) -> Vec<u8> { (0 .. len).map(|x| seed.wrapping_add(x as u8)).collect() }
+	/// Offsets in the ring of the pending copies
+	fn source_offsets(uploader: &Uploader) -> Vec<VkDeviceSize>
+	{
+		uploader.pending.iter().map(|c| match *c
+		{
+			PendingCopy::Buffer { ref region, .. } => region.srcOffset,
+			PendingCopy::Image { ref region, .. } => region.bufferOffset
+		}).collect()
+	}
+
+	#[test]
+	fn image_data_alignments()
+	{
+		assert_eq!(image_data_alignment(VK_FORMAT_R8_UNORM), 4);
+		assert_eq!(image_data_alignment(VK_FORMAT_R8G8B8_UNORM), 12);
+		assert_eq!(image_data_alignment(VK_FORMAT_R16G16B16_SFLOAT), 12);
+		assert_eq!(image_data_alignment(VK_FORMAT_R32G32B32_SFLOAT), 12);
+		assert_eq!(image_data_alignment(VK_FORMAT_R64G64B64_SFLOAT), 24);
+		assert_eq!(image_data_alignment(VK_FORMAT_BC1_RGB_UNORM_BLOCK), 8);
+		assert_eq!(image_data_alignment(VK_FORMAT_BC7_SRGB_BLOCK), 16);
+		assert_eq!(image_data_alignment(VK_FORMAT_ASTC_12x12_SRGB_BLOCK), 16);
+	}
+
+	#[test]
+	fn uploads_land()
+	{
+		let (driver, instance, device) = default_test_device();
+		let mut uploader = Uploader::new(&device, device.queue(0, 0), 0, 1024).unwrap();
+		let (buffer, memory) = destination(&device, 64);
+		let image = ImageDesc::new(&Extent2D(3, 2), VK_FORMAT_R8G8B8_UNORM, ImageUsage::TRANSFER_SRC.transfer_dest(), ImageLayout::Undefined)
+			.create(&device).unwrap();
+		let image_memory = DeviceMemory::allocate(&device, image.requirements().size as _, 0).unwrap();
+		image.bind(&image_memory, 0).unwrap();
+		let region = VkBufferImageCopy
+		{
+			bufferOffset: 0, bufferRowLength: 0, bufferImageHeight: 0,
+			imageSubresource: VkImageSubresourceLayers { aspectMask: VK_IMAGE_ASPECT_COLOR_BIT, mipLevel: 0, baseArrayLayer: 0, layerCount: 1 },
+			imageOffset: VkOffset3D { x: 0, y: 0, z: 0 }, imageExtent: VkExtent3D { width: 3, height: 2, depth: 1 }
+		};
+		let texels = bytes(100, 3 * 2 * 3);
+
+		uploader.upload_buffer(&buffer, 8, &[1u32, 2, 3]).unwrap();
+		uploader.upload_image(&image, &region, &texels, ImageLayout::TransferSrcOpt).unwrap();
+		// the texels of 3 bytes are placed at a multiple of 12 bytes
+		assert_eq!(source_offsets(&uploader), vec![0, 12]);
+		uploader.submit().unwrap();
+		uploader.wait().unwrap();
+
+		assert_eq!(memory.map_scoped(8 .. 20).unwrap().slice::<u32>(0, 3), &[1, 2, 3]);
+		let readback = Readback::new(&device, device.queue(0, 0), 0).unwrap();
+		assert_eq!(readback.image(&image, ImageLayout::TransferSrcOpt, &region.imageSubresource).unwrap().data, texels);
+		assert_no_misuse(&driver, &instance);
+	}
+
+	#[test]
+	fn full_ring_submits_and_waits()
+	{
+		let (driver, instance, device) = default_test_device();
+		driver.delay_completion(device.native_ptr(), true);
+		let mut uploader = Uploader::new(&device, device.queue(0, 0), 0, 256).unwrap();
+		let (buffer, memory) = destination(&device, 320);
+		let (first, second) = (bytes(0, 160), bytes(50, 160));
+
+		uploader.upload_buffer(&buffer, 0, &first).unwrap();
+		// no room for another 160 bytes: the first copy is submitted and waited for
+		uploader.upload_buffer(&buffer, 160, &second).unwrap();
+		assert_eq!((uploader.pending_copies(), uploader.batches_in_flight(), uploader.used_bytes()), (1, 0, 160));
+		assert_eq!(source_offsets(&uploader), vec![0]);
+		assert_eq!(memory.map_scoped(0 .. 160).unwrap().slice::<u8>(0, 160), &first[..]);
+		uploader.submit().unwrap();
+		uploader.wait().unwrap();
+		assert_eq!(memory.map_scoped(160 .. 320).unwrap().slice::<u8>(0, 160), &second[..]);
+		driver.delay_completion(device.native_ptr(), false);
+		assert_no_misuse(&driver, &instance);
+	}
+
+	#[test]
+	fn ring_wraps_around()
+	{
+		let (driver, instance, device) = default_test_device();
+		driver.delay_completion(device.native_ptr(), true);
+		let mut uploader = Uploader::new(&device, device.queue(0, 0), 0, 256).unwrap();
+		let (buffer, memory) = destination(&device, 320);
+		let data = [bytes(0, 128), bytes(10, 64), bytes(20, 96)];
+
+		uploader.upload_buffer(&buffer, 0, &data[0]).unwrap();
+		uploader.submit().unwrap();
+		uploader.upload_buffer(&buffer, 128, &data[1]).unwrap();
+		uploader.submit().unwrap();
+		assert_eq!((uploader.batches_in_flight(), uploader.used_bytes()), (2, 192));
+		// 96 bytes fit neither after the second batch nor, until the first batch completes, before it
+		uploader.upload_buffer(&buffer, 224, &data[2]).unwrap();
+		assert_eq!(source_offsets(&uploader), vec![0]);
+		// the 64 bytes skipped at the end stay in use until the wrapped copy completes
+		assert_eq!((uploader.batches_in_flight(), uploader.used_bytes()), (1, 64 + 64 + 96));
+		uploader.submit().unwrap();
+		uploader.wait().unwrap();
+		assert_eq!(uploader.used_bytes(), 0);
+
+		let mapping = memory.map_scoped(0 .. 320).unwrap();
+		assert_eq!(mapping.slice::<u8>(0, 192), &data[.. 2].concat()[..]);
+		assert_eq!(mapping.slice::<u8>(224, 96), &data[2][..]);
+		driver.delay_completion(device.native_ptr(), false);
+		assert_no_misuse(&driver, &instance);
+	}
+
+	#[test]
+	fn poll_and_wait_recycle()
+	{
+		let (driver, instance, device) = default_test_device();
+		driver.delay_completion(device.native_ptr(), true);
+		let mut uploader = Uploader::new(&device, device.queue(0, 0), 0, 256).unwrap();
+		let (buffer, _memory) = destination(&device, 64);
+
+		uploader.upload_buffer(&buffer, 0, &bytes(0, 64)).unwrap();
+		uploader.submit().unwrap();
+		let command = uploader.in_flight[0].command.native_ptr();
+		// the batch is still executing
+		uploader.poll().unwrap();
+		assert_eq!((uploader.batches_in_flight(), uploader.used_bytes()), (1, 64));
+		uploader.wait().unwrap();
+		assert_eq!((uploader.batches_in_flight(), uploader.used_bytes(), uploader.free_commands.len()), (0, 0, 1));
+
+		// the space, the command buffer and the fence are reused by the next batch
+		uploader.upload_buffer(&buffer, 0, &bytes(1, 64)).unwrap();
+		assert_eq!(source_offsets(&uploader), vec![0]);
+		uploader.submit().unwrap();
+		assert_eq!(uploader.in_flight[0].command.native_ptr(), command);
+		assert!(uploader.free_commands.is_empty() && uploader.free_fences.is_empty());
+		uploader.poll().unwrap();
+		assert_eq!(uploader.batches_in_flight(), 1);
+		driver.delay_completion(device.native_ptr(), false);
+		uploader.poll().unwrap();
+		assert_eq!((uploader.batches_in_flight(), uploader.used_bytes(), uploader.free_commands.len()), (0, 0, 1));
+		assert_no_misuse(&driver, &instance);
+	}
+}
@@ -0,0 +1,135 @@
+//! VK_EXT_debug_utils extensions
+
+pub const VK_EXT_DEBUG_UTILS_SPEC_VERSION: usize = 1;
+pub static VK_EXT_DEBUG_UTILS_EXTENSION_NAME: &str = "VK_EXT_debug_utils";
+
+use libc::*;
+use super::*;
+
+mod nd_handle_base_ts { pub enum VkDebugUtilsMessengerEXT {} }
+pub type VkDebugUtilsMessengerEXT = VK_NON_DISPATCHABLE_HANDLE!(VkDebugUtilsMessengerEXT);
+
+pub type VkDebugUtilsMessengerCallbackDataFlagsEXT = VkFlags;
+pub type VkDebugUtilsMessengerCreateFlagsEXT = VkFlags;
+
+pub type VkDebugUtilsMessageSeverityFlagsEXT = VkFlags;
+pub const VK_DEBUG_UTILS_MESSAGE_SEVERITY_VERBOSE_BIT_EXT: VkDebugUtilsMessageSeverityFlagsEXT = 0x0001;
+pub const VK_DEBUG_UTILS_MESSAGE_SEVERITY_INFO_BIT_EXT: VkDebugUtilsMessageSeverityFlagsEXT = 0x0010;
+pub const VK_DEBUG_UTILS_MESSAGE_SEVERITY_WARNING_BIT_EXT: VkDebugUtilsMessageSeverityFlagsEXT = 0x0100;
+pub const VK_DEBUG_UTILS_MESSAGE_SEVERITY_ERROR_BIT_EXT: VkDebugUtilsMessageSeverityFlagsEXT = 0x1000;
+
+pub type VkDebugUtilsMessageTypeFlagsEXT = VkFlags;
+pub const VK_DEBUG_UTILS_MESSAGE_TYPE_GENERAL_BIT_EXT: VkDebugUtilsMessageTypeFlagsEXT = 0x01;
+pub const VK_DEBUG_UTILS_MESSAGE_TYPE_VALIDATION_BIT_EXT: VkDebugUtilsMessageTypeFlagsEXT = 0x02;
+pub const VK_DEBUG_UTILS_MESSAGE_TYPE_PERFORMANCE_BIT_EXT: VkDebugUtilsMessageTypeFlagsEXT = 0x04;
+
+#[repr(C)] #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VkDebugUtilsObjectNameInfoEXT
+{
+	pub sType: VkStructureType, pub pNext: *const c_void,
+	pub objectType: VkObjectType, pub objectHandle: u64, pub pObjectName: *const c_char
+}
+#[repr(C)] #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VkDebugUtilsObjectTagInfoEXT
+{
+	pub sType: VkStructureType, pub pNext: *const c_void,
+	pub objectType: VkObjectType, pub objectHandle: u64, pub tagName: u64,
+	pub tagSize: size_t, pub pTag: *const c_void
+}
+#[repr(C)] #[derive(Debug, Clone, PartialEq)]
+pub struct VkDebugUtilsLabelEXT
+{
+	pub sType: VkStructureType, pub pNext: *const c_void,
+	pub pLabelName: *const c_char, pub color: [c_float; 4]
+}
+#[repr(C)] #[derive(Debug, Clone, PartialEq)]
+pub struct VkDebugUtilsMessengerCallbackDataEXT
+{
+	pub sType: VkStructureType, pub pNext: *const c_void,
+	pub flags: VkDebugUtilsMessengerCallbackDataFlagsEXT,
+	pub pMessageIdName: *const c_char, pub messageIdNumber: i32, pub pMessage: *const c_char,
+	pub queueLabelCount: u32, pub pQueueLabels: *const VkDebugUtilsLabelEXT,
+	pub cmdBufLabelCount: u32, pub pCmdBufLabels: *const VkDebugUtilsLabelEXT,
+	pub objectCount: u32, pub pObjects: *const VkDebugUtilsObjectNameInfoEXT
+}
+#[repr(C)]
+pub struct VkDebugUtilsMessengerCreateInfoEXT
+{
+	pub sType: VkStructureType, pub pNext: *const c_void,
+	pub flags: VkDebugUtilsMessengerCreateFlagsEXT,
+	pub messageSeverity: VkDebugUtilsMessageSeverityFlagsEXT, pub messageType: VkDebugUtilsMessageTypeFlagsEXT,
+	pub pfnUserCallback: PFN_vkDebugUtilsMessengerCallbackEXT, pub pUserData: *mut c_void
+}
+impl Default for VkDebugUtilsObjectNameInfoEXT
+{
+	fn default() -> Self
+	{
+		VkDebugUtilsObjectNameInfoEXT
+		{
+			sType: VK_STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_NAME_INFO_EXT,
+			.. unsafe { std::mem::zeroed() }
+		}
+	}
+}
+impl Default for VkDebugUtilsObjectTagInfoEXT
+{
+	fn default() -> Self
+	{
+		VkDebugUtilsObjectTagInfoEXT
+		{
+			sType: VK_STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_TAG_INFO_EXT,
+			.. unsafe { std::mem::zeroed() }
+		}
+	}
+}
+impl Default for VkDebugUtilsLabelEXT
+{
+	fn default() -> Self
+	{
+		VkDebugUtilsLabelEXT
+		{
+			sType: VK_STRUCTURE_TYPE_DEBUG_UTILS_LABEL_EXT,
+			.. unsafe { std::mem::zeroed() }
+		}
+	}
+}
+impl Default for VkDebugUtilsMessengerCallbackDataEXT
+{
+	fn default() -> Self
+	{
+		VkDebugUtilsMessengerCallbackDataEXT
+		{
+			sType: VK_STRUCTURE_TYPE_DEBUG_UTILS_MESSENGER_CALLBACK_DATA_EXT,
+			.. unsafe { std::mem::zeroed() }
+		}
+	}
+}
+
+pub type PFN_vkDebugUtilsMessengerCallbackEXT = extern "system" fn(messageSeverity: VkDebugUtilsMessageSeverityFlagsEXT, messageTypes: VkDebugUtilsMessageTypeFlagsEXT, pCallbackData: *const VkDebugUtilsMessengerCallbackDataEXT, pUserData: *mut c_void) -> VkBool32;
+pub type PFN_vkCreateDebugUtilsMessengerEXT = extern "system" fn(instance: VkInstance, pCreateInfo: *const VkDebugUtilsMessengerCreateInfoEXT, pAllocator: *const VkAllocationCallbacks, pMessenger: *mut VkDebugUtilsMessengerEXT) -> VkResult;
+pub type PFN_vkDestroyDebugUtilsMessengerEXT = extern "system" fn(instance: VkInstance, messenger: VkDebugUtilsMessengerEXT, pAllocator: *const VkAllocationCallbacks);
+pub type PFN_vkSubmitDebugUtilsMessageEXT = extern "system" fn(instance: VkInstance, messageSeverity: VkDebugUtilsMessageSeverityFlagsEXT, messageTypes: VkDebugUtilsMessageTypeFlagsEXT, pCallbackData: *const VkDebugUtilsMessengerCallbackDataEXT);
+pub type PFN_vkSetDebugUtilsObjectNameEXT = extern "system" fn(device: VkDevice, pNameInfo: *const VkDebugUtilsObjectNameInfoEXT) -> VkResult;
+pub type PFN_vkSetDebugUtilsObjectTagEXT = extern "system" fn(device: VkDevice, pTagInfo: *const VkDebugUtilsObjectTagInfoEXT) -> VkResult;
+pub type PFN_vkQueueBeginDebugUtilsLabelEXT = extern "system" fn(queue: VkQueue, pLabelInfo: *const VkDebugUtilsLabelEXT);
+pub type PFN_vkQueueEndDebugUtilsLabelEXT = extern "system" fn(queue: VkQueue);
+pub type PFN_vkQueueInsertDebugUtilsLabelEXT = extern "system" fn(queue: VkQueue, pLabelInfo: *const VkDebugUtilsLabelEXT);
+pub type PFN_vkCmdBeginDebugUtilsLabelEXT = extern "system" fn(commandBuffer: VkCommandBuffer, pLabelInfo: *const VkDebugUtilsLabelEXT);
+pub type PFN_vkCmdEndDebugUtilsLabelEXT = extern "system" fn(commandBuffer: VkCommandBuffer);
+pub type PFN_vkCmdInsertDebugUtilsLabelEXT = extern "system" fn(commandBuffer: VkCommandBuffer, pLabelInfo: *const VkDebugUtilsLabelEXT);
+
+#[cfg(feature = "Implements")]
+extern "system"
+{
+    pub fn vkCreateDebugUtilsMessengerEXT(instance: VkInstance, pCreateInfo: *const VkDebugUtilsMessengerCreateInfoEXT, pAllocator: *const VkAllocationCallbacks, pMessenger: *mut VkDebugUtilsMessengerEXT) -> VkResult;
+    pub fn vkDestroyDebugUtilsMessengerEXT(instance: VkInstance, messenger: VkDebugUtilsMessengerEXT, pAllocator: *const VkAllocationCallbacks);
+    pub fn vkSubmitDebugUtilsMessageEXT(instance: VkInstance, messageSeverity: VkDebugUtilsMessageSeverityFlagsEXT, messageTypes: VkDebugUtilsMessageTypeFlagsEXT, pCallbackData: *const VkDebugUtilsMessengerCallbackDataEXT);
+    pub fn vkSetDebugUtilsObjectNameEXT(device: VkDevice, pNameInfo: *const VkDebugUtilsObjectNameInfoEXT) -> VkResult;
+    pub fn vkSetDebugUtilsObjectTagEXT(device: VkDevice, pTagInfo: *const VkDebugUtilsObjectTagInfoEXT) -> VkResult;
+    pub fn vkQueueBeginDebugUtilsLabelEXT(queue: VkQueue, pLabelInfo: *const VkDebugUtilsLabelEXT);
+    pub fn vkQueueEndDebugUtilsLabelEXT(queue: VkQueue);
+    pub fn vkQueueInsertDebugUtilsLabelEXT(queue: VkQueue, pLabelInfo: *const VkDebugUtilsLabelEXT);
+    pub fn vkCmdBeginDebugUtilsLabelEXT(commandBuffer: VkCommandBuffer, pLabelInfo: *const VkDebugUtilsLabelEXT);
+    pub fn vkCmdEndDebugUtilsLabelEXT(commandBuffer: VkCommandBuffer);
+    pub fn vkCmdInsertDebugUtilsLabelEXT(commandBuffer: VkCommandBuffer, pLabelInfo: *const VkDebugUtilsLabelEXT);
+}
@@ -0,0 +1,9 @@
+//! VK_KHR_dedicated_allocation
+
+pub const VK_KHR_DEDICATED_ALLOCATION_SPEC_VERSION: usize = 3;
+pub const VK_KHR_DEDICATED_ALLOCATION_EXTENSION_NAME: &str = "VK_KHR_dedicated_allocation";
+
+use super::*;
+
+pub type VkMemoryDedicatedRequirementsKHR = VkMemoryDedicatedRequirements;
+pub type VkMemoryDedicatedAllocateInfoKHR = VkMemoryDedicatedAllocateInfo;
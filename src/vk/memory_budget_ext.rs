@@ -0,0 +1,25 @@
+//! VK_EXT_memory_budget extension
+
+pub const VK_EXT_MEMORY_BUDGET_SPEC_VERSION: usize = 1;
+pub const VK_EXT_MEMORY_BUDGET_EXTENSION_NAME: &str = "VK_EXT_memory_budget";
+
+use super::*;
+use std::mem::zeroed;
+
+#[repr(C)] #[derive(Clone, Debug)]
+pub struct VkPhysicalDeviceMemoryBudgetPropertiesEXT
+{
+    pub sType: VkStructureType, pub pNext: *mut c_void,
+    pub heapBudget: [VkDeviceSize; VK_MAX_MEMORY_HEAPS], pub heapUsage: [VkDeviceSize; VK_MAX_MEMORY_HEAPS]
+}
+impl Default for VkPhysicalDeviceMemoryBudgetPropertiesEXT
+{
+    fn default() -> Self
+    {
+        VkPhysicalDeviceMemoryBudgetPropertiesEXT
+        {
+            sType: VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MEMORY_BUDGET_PROPERTIES_EXT,
+            .. unsafe { zeroed() }
+        }
+    }
+}
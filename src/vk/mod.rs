@@ -354,6 +354,11 @@ pub const VK_STRUCTURE_TYPE_SURFACE_CAPABILITIES_2_KHR: VkStructureType = 100_01
 pub const VK_STRUCTURE_TYPE_SURFACE_FORMAT_2_KHR: VkStructureType = 100_0119_002;
 pub const VK_STRUCTURE_TYPE_IOS_SURFACE_CREATE_INFO_MVK: VkStructureType = 100_0122_000;
 pub const VK_STRUCTURE_TYPE_MACOS_SURFACE_CREATE_INFO_MVK: VkStructureType = 100_0123_000;
+pub const VK_STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_NAME_INFO_EXT: VkStructureType = 100_0128_000;
+pub const VK_STRUCTURE_TYPE_DEBUG_UTILS_OBJECT_TAG_INFO_EXT: VkStructureType = 100_0128_001;
+pub const VK_STRUCTURE_TYPE_DEBUG_UTILS_LABEL_EXT: VkStructureType = 100_0128_002;
+pub const VK_STRUCTURE_TYPE_DEBUG_UTILS_MESSENGER_CALLBACK_DATA_EXT: VkStructureType = 100_0128_003;
+pub const VK_STRUCTURE_TYPE_DEBUG_UTILS_MESSENGER_CREATE_INFO_EXT: VkStructureType = 100_0128_004;
 pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_SAMPLER_FILTER_MINMAX_PROPERTIES_EXT: VkStructureType = 100_0130_000;
 pub const VK_STRUCTURE_TYPE_SAMPLER_REDUCTION_MODE_CREATE_INFO_EXT: VkStructureType = 100_0130_001;
 pub const VK_STRUCTURE_TYPE_SAMPLE_LOCATIONS_INFO_EXT: VkStructureType = 100_0143_000;
@@ -374,6 +379,7 @@ pub const VK_STRUCTURE_TYPE_MEMORY_HOST_POINTER_PROPERTIES_EXT: VkStructureType
 pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTERNAL_MEMORY_HOST_PROEPRTIES_EXT: VkStructureType = 100_0178_002;
 pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_VERTEX_ATTRIBUTE_DIVISOR_PROPERTIES_EXT: VkStructureType = 100_0190_000;
 pub const VK_STRUCTURE_TYPE_PIPELINE_VERTEX_INPUT_DIVISOR_STATE_CREATE_INFO_EXT: VkStructureType = 100_0190_001;
+pub const VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MEMORY_BUDGET_PROPERTIES_EXT: VkStructureType = 100_0237_000;
 pub const VK_STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_INFO_EXT: VkStructureType = 100_0255_000;
 pub const VK_STRUCTURE_TYPE_SURFACE_CAPABILITIES_FULL_SCREEN_EXCLUSIVE_EXT: VkStructureType = 100_0255_002;
 pub const VK_STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_WIN32_INFO_EXT: VkStructureType = 100_0255_001;
@@ -2567,12 +2573,12 @@ impl Default for VkRenderPassBeginInfo
     }
 }
 
-#[repr(C)] #[derive(Debug, Clone, PartialEq, Eq)]
+#[repr(C)] #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct VkDispatchIndirectCommand
 {
     pub x: u32, pub y: u32, pub z: u32
 }
-#[repr(C)] #[derive(Debug, Clone, PartialEq, Eq)]
+#[repr(C)] #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct VkDrawIndexedIndirectCommand
 {
     pub indexCount: u32, pub instanceCount: u32, pub firstIndex: u32,
@@ -2585,7 +2591,7 @@ impl Default for VkDrawIndexedIndirectCommand
         VkDrawIndexedIndirectCommand { instanceCount: 1, .. unsafe { std::mem::zeroed() } }
     }
 }
-#[repr(C)] #[derive(Debug, Clone, PartialEq, Eq)]
+#[repr(C)] #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct VkDrawIndirectCommand
 {
     pub vertexCount: u32, pub instanceCount: u32, pub firstVertex: u32, pub firstInstance: u32
@@ -2736,11 +2742,12 @@ pub type PFN_vkCmdNextSubpass            = extern "system" fn(commandBuffer: VkC
 pub type PFN_vkCmdEndRenderPass          = extern "system" fn(commandBuffer: VkCommandBuffer);
 pub type PFN_vkCmdExecuteCommands        = extern "system" fn(commandBuffer: VkCommandBuffer, commandBufferCount: u32, pCommandBuffers: *const VkCommandBuffer);
 
+// CustomResolver replaces every entry point and DynamicLoaded opens the library at runtime, so no Vulkan library is required at link time
 #[cfg(feature = "Implements")]
-#[cfg_attr(all(not(windows), not(target_os="macos")), link(name = "vulkan"))]
-#[cfg_attr(windows, link(name = "vulkan-1"))]
-#[cfg_attr(target_os="macos", link(name = "MoltenVK", kind = "framework"))]
-#[cfg_attr(target_os="macos", link(name = "Metal", kind = "framework"))]
+#[cfg_attr(all(not(windows), not(target_os="macos"), not(feature = "CustomResolver"), not(feature = "DynamicLoaded")), link(name = "vulkan"))]
+#[cfg_attr(all(windows, not(feature = "CustomResolver"), not(feature = "DynamicLoaded")), link(name = "vulkan-1"))]
+#[cfg_attr(all(target_os="macos", not(feature = "CustomResolver"), not(feature = "DynamicLoaded")), link(name = "MoltenVK", kind = "framework"))]
+#[cfg_attr(all(target_os="macos", not(feature = "CustomResolver"), not(feature = "DynamicLoaded")), link(name = "Metal", kind = "framework"))]
 extern "system"
 {
     pub fn vkCreateInstance(pCreateInfo: *const VkInstanceCreateInfo, pAllocator: *const VkAllocationCallbacks, pInstance: *mut VkInstance) -> VkResult;
@@ -4218,13 +4225,15 @@ pub type PFN_vkGetDescriptorSetLayoutSupport = extern "system" fn(device: VkDevi
     pCreateInfo: *const VkDescriptorSetLayoutCreateInfo, pSupport: *mut VkDescriptorSetLayoutSupport);
 
 #[cfg(feature = "Implements")]
-#[cfg_attr(all(not(windows), not(target_os="macos")), link(name = "vulkan"))]
-#[cfg_attr(windows, link(name = "vulkan-1"))]
-#[cfg_attr(target_os="macos", link(name = "MoltenVK", kind = "framework"))]
-#[cfg_attr(target_os="macos", link(name = "Metal", kind = "framework"))]
+#[cfg_attr(all(not(windows), not(target_os="macos"), not(feature = "CustomResolver"), not(feature = "DynamicLoaded")), link(name = "vulkan"))]
+#[cfg_attr(all(windows, not(feature = "CustomResolver"), not(feature = "DynamicLoaded")), link(name = "vulkan-1"))]
+#[cfg_attr(all(target_os="macos", not(feature = "CustomResolver"), not(feature = "DynamicLoaded")), link(name = "MoltenVK", kind = "framework"))]
+#[cfg_attr(all(target_os="macos", not(feature = "CustomResolver"), not(feature = "DynamicLoaded")), link(name = "Metal", kind = "framework"))]
 extern "system"
 {
     pub fn vkEnumerateInstanceVersion(pApiVersion: *mut u32) -> VkResult;
+    pub fn vkEnumeratePhysicalDeviceGroups(instance: VkInstance, pPhysicalDeviceGroupCount: *mut u32,
+        pPhysicalDeviceGroupProperties: *mut VkPhysicalDeviceGroupProperties) -> VkResult;
     pub fn vkBindBufferMemory2(device: VkDevice, bindInfoCount: u32, pBindInfos: *const VkBindBufferMemoryInfo) -> VkResult;
     pub fn vkBindImageMemory2(device: VkDevice, bindInfoCount: u32, pBindInfos: *const VkBindImageMemoryInfo) -> VkResult;
     pub fn vkGetDeviceGroupPeerMemoryFeatures(device: VkDevice, heapIndex: u32, localDeviceIndex: u32,
@@ -4324,6 +4333,8 @@ ExportExtensions!("VK_KHR_external_fence_fd": external_fence_fd_khr);
 ExportExtensions!("VK_KHR_get_surface_capabilities2": get_surface_capabilities2_khr);
 ExportExtensions!("VK_KHR_storage_buffer_storage_class": storage_buffer_storage_class_khr);
 ExportExtensions!("VK_KHR_relaxed_block_layout": relaxed_block_layout_khr);
+ExportExtensions!("VK_KHR_get_memory_requirements2": get_memory_requirements2_khr);
+ExportExtensions!("VK_KHR_dedicated_allocation": dedicated_allocation_khr);
 ExportExtensions!("VK_EXT_debug_report": debug_report_ext);
 ExportExtensions!("VK_NV_glsl_shader": glsl_shader_nv);
 ExportExtensions!("VK_EXT_depth_range_unrestricted": depth_range_unrestricted_ext);
@@ -4368,6 +4379,7 @@ ExportExtensions!("VK_EXT_swapchain_colorspace": swapchain_colorspace_ext);
 ExportExtensions!("VK_EXT_hdr_metadata": hdr_metadata_ext);
 ExportExtensions!("VK_MVK_ios_surface": ios_surface_mvk);
 ExportExtensions!("VK_MVK_macos_surface": macos_surface_mvk);
+ExportExtensions!("VK_EXT_debug_utils": debug_utils_ext);
 ExportExtensions!("VK_EXT_external_memory_dma_buf": external_memory_dma_buf_ext);
 ExportExtensions!("VK_EXT_queue_family_foreign": queue_family_foreign);
 ExportExtensions!("VK_EXT_sampler_filter_minmax": sampler_filter_minmax_ext);
@@ -4386,5 +4398,6 @@ ExportExtensions!("VK_EXT_global_priority": global_priority_ext);
 ExportExtensions!("VK_EXT_external_memory_host": external_memory_host_ext);
 ExportExtensions!("VK_AMD_buffer_marker": buffer_marker_amd);
 ExportExtensions!("VK_EXT_vertex_attribute_divisor": vertex_attribute_divisor_ext);
+ExportExtensions!("VK_EXT_memory_budget": memory_budget_ext);
 ExportExtensions!("VK_KHR_get_physical_device_properties2": get_physical_device_properties2);
 ExportExtensions!("VK_EXT_full_screen_exclusive": full_screen_exclusive_ext);